@@ -0,0 +1,7 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|query: &str| {
+    let _ = sqlx_sqlite::fuzz::describe(query);
+});
@@ -1,5 +1,4 @@
 use clap::Parser;
-use console::style;
 use sqlx_cli::Opt;
 use std::process;
 
@@ -20,7 +19,7 @@ async fn main() {
     }
 
     if let Err(error) = sqlx_cli::run(opt).await {
-        println!("{} {}", style("error:").bold().red(), error);
-        process::exit(1);
+        sqlx_cli::print_error(&error);
+        process::exit(sqlx_cli::exit_code(&error));
     }
 }
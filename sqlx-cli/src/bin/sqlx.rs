@@ -1,5 +1,4 @@
 use clap::Parser;
-use console::style;
 use sqlx_cli::Opt;
 
 #[tokio::main]
@@ -12,7 +11,7 @@ async fn main() {
 
     // no special handling here
     if let Err(error) = sqlx_cli::run(opt).await {
-        println!("{} {}", style("error:").bold().red(), error);
-        std::process::exit(1);
+        sqlx_cli::print_error(&error);
+        std::process::exit(sqlx_cli::exit_code(&error));
     }
 }
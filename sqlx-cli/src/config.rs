@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::Context;
+use sqlx::config::{Config, OfflineDatabase, DEFAULT_PROFILE};
+
+static CONFIG: OnceLock<ProjectConfig> = OnceLock::new();
+
+/// The `sqlx.toml` found from the current directory, with the selected profile applied.
+#[derive(Debug)]
+pub struct ProjectConfig {
+    pub database_url: Option<String>,
+    pub migrations_source: String,
+    pub migrations_table: String,
+    pub ignore_missing: bool,
+    pub skip_migrations: Vec<i64>,
+    pub offline_dir: Option<PathBuf>,
+    pub offline_per_driver: bool,
+    pub offline_databases: BTreeMap<String, OfflineDatabase>,
+    pub connect_timeout: u64,
+}
+
+impl Default for ProjectConfig {
+    fn default() -> Self {
+        Self {
+            database_url: None,
+            migrations_source: "migrations".into(),
+            migrations_table: "_sqlx_migrations".into(),
+            ignore_missing: false,
+            skip_migrations: Vec::new(),
+            offline_dir: None,
+            offline_per_driver: false,
+            offline_databases: BTreeMap::new(),
+            connect_timeout: 10,
+        }
+    }
+}
+
+/// Load `sqlx.toml` for the rest of the process. Only the first call has any effect.
+///
+/// Selecting a `profile` is an error if there is no `sqlx.toml` or it doesn't define the profile.
+pub fn init(profile: Option<&str>) -> anyhow::Result<()> {
+    let cwd = std::env::current_dir().context("failed to get the current directory")?;
+
+    let config = match Config::find(&cwd)? {
+        Some((_, config)) => resolve(&config, profile.unwrap_or(DEFAULT_PROFILE), &cwd)?,
+        None if profile.is_some() => {
+            anyhow::bail!(
+                "`--profile` was given but there is no {} in this directory or its parents",
+                sqlx::config::FILE_NAME
+            );
+        }
+        None => ProjectConfig::default(),
+    };
+
+    let _ = CONFIG.set(config);
+
+    Ok(())
+}
+
+/// The configuration loaded by [`init()`], or the defaults if it wasn't called.
+pub fn get() -> &'static ProjectConfig {
+    CONFIG.get_or_init(ProjectConfig::default)
+}
+
+fn resolve(config: &Config, profile: &str, cwd: &Path) -> anyhow::Result<ProjectConfig> {
+    let defaults = ProjectConfig::default();
+
+    Ok(ProjectConfig {
+        database_url: config.database_url(profile)?.map(String::from),
+        migrations_source: config
+            .migrate
+            .source
+            .as_deref()
+            // keep paths short for output and generated build scripts where possible
+            .map(|source| {
+                source
+                    .strip_prefix(cwd)
+                    .unwrap_or(source)
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .unwrap_or(defaults.migrations_source),
+        migrations_table: config
+            .migrate
+            .table
+            .clone()
+            .unwrap_or(defaults.migrations_table),
+        ignore_missing: config.migrate.ignore_missing,
+        skip_migrations: config.skip_migrations(profile),
+        offline_dir: config.offline.dir.clone(),
+        offline_per_driver: config.offline.is_per_driver(),
+        offline_databases: config.offline.databases.clone(),
+        connect_timeout: config
+            .cli
+            .connect_timeout
+            .unwrap_or(defaults.connect_timeout),
+    })
+}
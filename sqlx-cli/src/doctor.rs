@@ -0,0 +1,565 @@
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+use anyhow::bail;
+use console::{style, StyledObject};
+use serde_json::json;
+use sqlx::migrate::{Migrate, Migrator, MIGRATIONS_TABLE_VERSION};
+use sqlx::{AnyConnection, Connection, DatabaseKind, DatabaseUrl};
+
+use crate::opt::ConnectOpts;
+use crate::output;
+
+/// Tally of the checks performed by `sqlx doctor`.
+#[derive(Default)]
+struct Report {
+    warnings: usize,
+    errors: usize,
+}
+
+impl Report {
+    fn ok(&mut self, message: impl AsRef<str>) {
+        let message = message.as_ref();
+
+        output::print(
+            "check",
+            json!({ "level": "ok", "message": message }),
+            format_args!("{} {message}", style("ok:").green().bold()),
+        );
+    }
+
+    fn warn(&mut self, message: impl AsRef<str>, hint: impl AsRef<str>) {
+        self.warnings += 1;
+        self.problem(
+            "warning",
+            style("warning:").yellow(),
+            message.as_ref(),
+            hint.as_ref(),
+        );
+    }
+
+    fn error(&mut self, message: impl AsRef<str>, hint: impl AsRef<str>) {
+        self.errors += 1;
+        self.problem(
+            "error",
+            style("error:").red(),
+            message.as_ref(),
+            hint.as_ref(),
+        );
+    }
+
+    fn problem(&self, level: &str, label: StyledObject<&str>, message: &str, hint: &str) {
+        output::print(
+            "check",
+            json!({ "level": level, "message": message, "hint": hint }),
+            format_args!(
+                "{} {message}\n    {} {hint}",
+                label.bold(),
+                style("hint:").dim()
+            ),
+        );
+    }
+}
+
+/// The database drivers a URL may require.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Driver {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl Driver {
+    fn from_url(url: &str) -> Option<Self> {
+        let (scheme, _) = url.split_once(':')?;
+
+        match DatabaseKind::from_scheme(scheme)? {
+            DatabaseKind::Postgres => Some(Self::Postgres),
+            DatabaseKind::MySql => Some(Self::MySql),
+            DatabaseKind::Sqlite => Some(Self::Sqlite),
+            _ => None,
+        }
+    }
+
+    /// The name of the Cargo feature enabling this driver, for both `sqlx` and `sqlx-cli`.
+    fn feature(self) -> &'static str {
+        match self {
+            Self::Postgres => "postgres",
+            Self::MySql => "mysql",
+            Self::Sqlite => "sqlite",
+        }
+    }
+
+    fn compiled_in(self) -> bool {
+        match self {
+            Self::Postgres => cfg!(feature = "postgres"),
+            Self::MySql => cfg!(feature = "mysql"),
+            Self::Sqlite => cfg!(any(feature = "sqlite", feature = "sqlite-unbundled")),
+        }
+    }
+}
+
+pub async fn run(
+    migration_sources: &[String],
+    migrations_table: &str,
+    connect_opts: &ConnectOpts,
+) -> anyhow::Result<()> {
+    let mut report = Report::default();
+
+    if let Some(url) = check_database_url(&mut report, connect_opts) {
+        let driver = check_driver(&mut report, url);
+
+        check_tls(&mut report, url);
+
+        if driver.is_some_and(Driver::compiled_in) {
+            match crate::connect(connect_opts).await {
+                Ok(mut conn) => {
+                    report.ok("the database is reachable");
+
+                    check_server_version(&mut report, &mut conn).await;
+                    check_migrations(&mut report, &mut conn, migration_sources, migrations_table).await;
+
+                    let _ = conn.close().await;
+                }
+                Err(e) => report.error(
+                    format!("failed to connect to the database: {e}"),
+                    "check that the server is running and that the host, port and credentials in DATABASE_URL are correct",
+                ),
+            }
+        }
+
+        if let Some(driver) = driver {
+            check_project_features(&mut report, driver);
+        }
+    }
+
+    check_offline_cache(&mut report, migration_sources);
+
+    if !output::is_json() {
+        println!();
+    }
+
+    if report.errors > 0 {
+        bail!(
+            "{} problem(s) and {} warning(s) found",
+            report.errors,
+            report.warnings
+        );
+    }
+
+    output::print(
+        "doctor_finished",
+        json!({ "warnings": report.warnings, "errors": 0 }),
+        format_args!(
+            "no problems found ({} warning(s))",
+            style(report.warnings).yellow()
+        ),
+    );
+
+    Ok(())
+}
+
+fn check_database_url<'a>(report: &mut Report, connect_opts: &'a ConnectOpts) -> Option<&'a str> {
+    match connect_opts.database_url() {
+        Some(url) => {
+            match DatabaseUrl::parse(url) {
+                Ok(_) => {
+                    report.ok("DATABASE_URL is set");
+                    Some(url)
+                }
+                Err(e) => {
+                    report.error(
+                    "DATABASE_URL is not a valid URL",
+                    format!("{e}; percent-encode any reserved characters in the username or password"),
+                );
+                    None
+                }
+            }
+        }
+        None => {
+            report.error(
+                "DATABASE_URL is not set",
+                "pass `--database-url`, set the `DATABASE_URL` environment variable or add it to a `.env` file",
+            );
+            None
+        }
+    }
+}
+
+fn check_driver(report: &mut Report, url: &str) -> Option<Driver> {
+    let Some(driver) = Driver::from_url(url) else {
+        report.error(
+            "DATABASE_URL does not use a supported scheme",
+            "use a URL starting with `postgres://`, `mysql://`, `mariadb://` or `sqlite:`",
+        );
+        return None;
+    };
+
+    if driver.compiled_in() {
+        report.ok(format!("sqlx-cli supports {}", driver.feature()));
+    } else {
+        report.error(
+            format!(
+                "sqlx-cli was built without support for {}",
+                driver.feature()
+            ),
+            format!(
+                "reinstall with `cargo install sqlx-cli --features {}`",
+                driver.feature()
+            ),
+        );
+    }
+
+    Some(driver)
+}
+
+fn check_tls(report: &mut Report, url: &str) {
+    let query = url.split_once('?').map_or("", |(_, query)| query);
+
+    let requires_tls = query.split('&').any(|param| {
+        let Some((key, value)) = param.split_once('=') else {
+            return false;
+        };
+
+        let value = value.to_ascii_lowercase();
+
+        match key {
+            "sslmode" => matches!(&*value, "require" | "verify-ca" | "verify-full"),
+            "ssl-mode" => matches!(&*value, "required" | "verify_ca" | "verify_identity"),
+            _ => false,
+        }
+    });
+
+    if !requires_tls {
+        return;
+    }
+
+    if cfg!(any(feature = "native-tls", feature = "rustls")) {
+        report.ok("DATABASE_URL requires TLS and sqlx-cli supports it");
+    } else {
+        report.error(
+            "DATABASE_URL requires TLS but sqlx-cli was built without TLS support",
+            "reinstall with `cargo install sqlx-cli --features native-tls` or `--features rustls`",
+        );
+    }
+}
+
+async fn check_server_version(report: &mut Report, conn: &mut AnyConnection) {
+    let (sql, name) = match conn.backend_name() {
+        "PostgreSQL" => ("SHOW server_version", "PostgreSQL"),
+        "MySQL" => ("SELECT VERSION()", "MySQL"),
+        "SQLite" => ("SELECT sqlite_version()", "SQLite"),
+        _ => return,
+    };
+
+    let version: String = match sqlx::query_scalar(sql).fetch_one(&mut *conn).await {
+        Ok(version) => version,
+        Err(e) => {
+            report.warn(
+                format!("failed to query the server version: {e}"),
+                "the remaining checks may not be accurate",
+            );
+            return;
+        }
+    };
+
+    let (name, minimum) = match name {
+        "PostgreSQL" => (name, (13, 0)),
+        "MySQL" if version.contains("MariaDB") => ("MariaDB", (10, 4)),
+        "MySQL" => (name, (5, 7)),
+        _ => (name, (3, 20)),
+    };
+
+    match parse_version(&version) {
+        Some(parsed) if parsed < minimum => report.warn(
+            format!("{name} {version} is older than the oldest version tested with SQLx"),
+            format!("upgrade to {name} {}.{} or newer", minimum.0, minimum.1),
+        ),
+        _ => report.ok(format!("server version is {name} {version}")),
+    }
+}
+
+async fn check_migrations(
+    report: &mut Report,
+    conn: &mut AnyConnection,
+    migration_sources: &[String],
+    migrations_table: &str,
+) {
+    if let Some(missing) = migration_sources
+        .iter()
+        .find(|source| !Path::new(source).is_dir())
+    {
+        report.warn(
+            format!("migrations directory `{missing}` was not found"),
+            "pass `--source` if your migrations are kept elsewhere",
+        );
+        return;
+    }
+
+    let paths: Vec<PathBuf> = migration_sources.iter().map(PathBuf::from).collect();
+
+    let migrator = match Migrator::new(paths).await {
+        Ok(migrator) => migrator,
+        Err(e) => {
+            report.error(
+                format!("failed to resolve migrations: {e}"),
+                "migration files must be named `<VERSION>_<DESCRIPTION>.sql`",
+            );
+            return;
+        }
+    };
+
+    // Don't create or upgrade the migrations table; this command should not modify the database.
+    if let Ok(Some(version)) = conn.migrations_table_version(migrations_table).await {
+        if version < MIGRATIONS_TABLE_VERSION {
+            report.warn(
+                format!(
+                    "`{migrations_table}` was created by an older version of SQLx \
+                     (schema version {version}, current is {MIGRATIONS_TABLE_VERSION})"
+                ),
+                "run `sqlx migrate run` to upgrade it",
+            );
+            return;
+        }
+    }
+
+    match conn.dirty_version(migrations_table).await {
+        Ok(Some(version)) => {
+            report.error(
+                format!("migration {version} was partially applied"),
+                format!("fix the database by hand, then delete the row for this version from `{migrations_table}`"),
+            );
+            return;
+        }
+        Ok(None) => (),
+        Err(_) => {
+            let count = migrator
+                .iter()
+                .filter(|m| !m.migration_type.is_down_migration())
+                .count();
+
+            report.warn(
+                format!("no migrations have been applied; {count} pending"),
+                "run `sqlx migrate run`",
+            );
+            return;
+        }
+    }
+
+    let applied = match conn.list_applied_migrations(migrations_table).await {
+        Ok(applied) => applied,
+        Err(e) => {
+            report.error(
+                format!("failed to list applied migrations: {e}"),
+                format!("check that the database user can read `{migrations_table}`"),
+            );
+            return;
+        }
+    };
+
+    let mut pending = 0;
+    let mut mismatched = Vec::new();
+
+    for migration in migrator
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+    {
+        match applied.iter().find(|a| a.version == migration.version) {
+            Some(a) if a.checksum != migration.checksum => {
+                if migration.migration_type.is_repeatable() {
+                    pending += 1;
+                } else {
+                    mismatched.push(migration.version);
+                }
+            }
+            Some(_) => (),
+            None => pending += 1,
+        }
+    }
+
+    let missing: Vec<_> = applied
+        .iter()
+        .filter(|a| !migrator.version_exists(a.version))
+        .map(|a| a.version)
+        .collect();
+
+    if !mismatched.is_empty() {
+        report.error(
+            format!("applied migrations were modified locally: {mismatched:?}"),
+            "revert the changes to these migration files and add a new migration instead, \
+             or run `sqlx migrate repair` if the changes are already reflected in the database",
+        );
+    }
+
+    if !missing.is_empty() {
+        report.error(
+            format!(
+                "applied migrations are missing from `{}`: {missing:?}",
+                migration_sources.join("`, `")
+            ),
+            "restore the missing files, pass `--ignore-missing` to `sqlx migrate run`, \
+             or run `sqlx migrate repair` to forget them",
+        );
+    }
+
+    if pending > 0 {
+        report.warn(
+            format!("{pending} migration(s) are pending"),
+            "run `sqlx migrate run`",
+        );
+    } else if mismatched.is_empty() && missing.is_empty() {
+        report.ok("migrations are up to date");
+    }
+}
+
+fn check_offline_cache(report: &mut Report, migration_sources: &[String]) {
+    let offline = env::var("SQLX_OFFLINE").is_ok_and(|v| v == "true" || v == "1");
+    let cache_dir: PathBuf =
+        env::var_os("SQLX_OFFLINE_DIR").map_or_else(|| ".sqlx".into(), Into::into);
+    let cache_dir = &*cache_dir;
+
+    let Some(cache_modified) = newest_modified(cache_dir, "query-") else {
+        if offline {
+            report.error(
+                format!(
+                    "SQLX_OFFLINE is set but no query data was found in `{}`",
+                    cache_dir.display()
+                ),
+                "run `cargo sqlx prepare` with DATABASE_URL set",
+            );
+        }
+        return;
+    };
+
+    let migrations_modified = migration_sources
+        .iter()
+        .filter_map(|source| newest_modified(Path::new(source), ""))
+        .max();
+
+    match migrations_modified {
+        Some(migrations_modified) if migrations_modified > cache_modified => report.warn(
+            format!(
+                "query data in `{}` is older than the newest migration",
+                cache_dir.display()
+            ),
+            "run `cargo sqlx prepare` to update it",
+        ),
+        _ => report.ok(format!(
+            "query data in `{}` is present",
+            cache_dir.display()
+        )),
+    }
+}
+
+/// The most recent modification time of the files in `dir` whose names start with `prefix`.
+fn newest_modified(dir: &Path, prefix: &str) -> Option<SystemTime> {
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(prefix))
+        .filter_map(|entry| entry.metadata().ok()?.modified().ok())
+        .max()
+}
+
+fn check_project_features(report: &mut Report, driver: Driver) {
+    if !Path::new("Cargo.toml").exists() {
+        return;
+    }
+
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+
+    let Ok(output) = Command::new(cargo)
+        .args(["metadata", "--format-version=1"])
+        .output()
+    else {
+        return;
+    };
+
+    let Ok(metadata) = serde_json::from_slice::<cargo_metadata::Metadata>(&output.stdout) else {
+        return;
+    };
+
+    let Some(resolve) = &metadata.resolve else {
+        return;
+    };
+
+    let sqlx_features: Vec<&[String]> = resolve
+        .nodes
+        .iter()
+        .filter(|node| {
+            metadata
+                .packages
+                .iter()
+                .any(|p| p.id == node.id && p.name == "sqlx")
+        })
+        .map(|node| &node.features[..])
+        .collect();
+
+    if sqlx_features.is_empty() {
+        return;
+    }
+
+    let feature = driver.feature();
+
+    if sqlx_features.iter().any(|features| {
+        features
+            .iter()
+            .any(|f| f == feature || (feature == "sqlite" && f == "sqlite-unbundled"))
+    }) {
+        report.ok(format!("the `{feature}` feature of `sqlx` is enabled"));
+    } else {
+        report.error(
+            format!("DATABASE_URL is for {feature} but the `{feature}` feature of `sqlx` is not enabled"),
+            format!("add `features = [\"{feature}\"]` to the `sqlx` dependency in Cargo.toml"),
+        );
+    }
+}
+
+/// Parse the major and minor version from a server version string like `15.4 (Debian ...)`
+/// or `10.6.12-MariaDB`.
+fn parse_version(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version
+        .split(|c: char| !c.is_ascii_digit())
+        .map(str::parse::<u32>);
+
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().and_then(Result::ok).unwrap_or(0);
+
+    Some((major, minor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_server_versions() {
+        assert_eq!(parse_version("15.4 (Debian 15.4-1)"), Some((15, 4)));
+        assert_eq!(parse_version("16beta1"), Some((16, 0)));
+        assert_eq!(parse_version("8.0.33"), Some((8, 0)));
+        assert_eq!(
+            parse_version("10.6.12-MariaDB-1:10.6.12+maria"),
+            Some((10, 6))
+        );
+        assert_eq!(parse_version("3.45.0"), Some((3, 45)));
+        assert_eq!(parse_version("unknown"), None);
+    }
+
+    #[test]
+    fn infers_driver_from_url() {
+        assert_eq!(
+            Driver::from_url("postgres://localhost/db"),
+            Some(Driver::Postgres)
+        );
+        assert_eq!(
+            Driver::from_url("mariadb://localhost/db"),
+            Some(Driver::MySql)
+        );
+        assert_eq!(Driver::from_url("sqlite::memory:"), Some(Driver::Sqlite));
+        assert_eq!(Driver::from_url("mssql://localhost/db"), None);
+    }
+}
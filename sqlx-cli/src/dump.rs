@@ -0,0 +1,381 @@
+use std::fs::File;
+use std::path::Path;
+
+use anyhow::{bail, Context};
+use console::style;
+use serde_json::json;
+use sqlx::DatabaseKind;
+
+use crate::database;
+use crate::opt::ConnectOpts;
+use crate::output;
+
+/// Write the schema of the database, or all of it, to stdout as a script which [`load()`] can
+/// restore.
+///
+/// Schema-only dumps still include the rows of the migrations table, so that `migrate run`
+/// after loading them only runs the migrations added since.
+pub async fn dump(
+    connect_opts: &ConnectOpts,
+    migrations_table: &str,
+    schema_only: bool,
+) -> anyhow::Result<()> {
+    let db_url = connect_opts.required_db_url()?;
+
+    match db_url.kind() {
+        #[cfg(feature = "postgres")]
+        Some(DatabaseKind::Postgres) => {
+            postgres::dump(connect_opts, migrations_table, schema_only).await
+        }
+        #[cfg(feature = "mysql")]
+        Some(DatabaseKind::MySql) => mysql::dump(connect_opts, migrations_table, schema_only).await,
+        #[cfg(any(feature = "sqlite", feature = "sqlite-unbundled"))]
+        Some(DatabaseKind::Sqlite) => {
+            sqlite::dump(connect_opts, migrations_table, schema_only).await
+        }
+        _ => unsupported(db_url.scheme()),
+    }
+}
+
+/// Run a script written by [`dump()`] against the database, creating it first if needed.
+pub async fn load(connect_opts: &ConnectOpts, path: &Path) -> anyhow::Result<()> {
+    let db_url = connect_opts.required_db_url()?;
+
+    let script = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+
+    database::create(connect_opts).await?;
+
+    match db_url.kind() {
+        #[cfg(feature = "postgres")]
+        Some(DatabaseKind::Postgres) => postgres::load(&db_url, script)?,
+        #[cfg(feature = "mysql")]
+        Some(DatabaseKind::MySql) => mysql::load(&db_url, script)?,
+        #[cfg(any(feature = "sqlite", feature = "sqlite-unbundled"))]
+        Some(DatabaseKind::Sqlite) => sqlite::load(connect_opts, script).await?,
+        _ => unsupported(db_url.scheme())?,
+    }
+
+    output::print(
+        "database_loaded",
+        json!({ "path": path }),
+        format_args!("Loaded {}", style(path.display()).cyan()),
+    );
+
+    Ok(())
+}
+
+fn unsupported(scheme: &str) -> anyhow::Result<()> {
+    bail!("dumps of `{scheme}` databases are not supported by this build of sqlx-cli")
+}
+
+/// Run a dump or restore tool, failing if it does.
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+fn run(command: &mut std::process::Command) -> anyhow::Result<()> {
+    let program = command.get_program().to_string_lossy().into_owned();
+
+    let status = command
+        .status()
+        .with_context(|| format!("failed to run `{program}`; is it installed and on your PATH?"))?;
+
+    if !status.success() {
+        bail!("`{program}` failed with status: {status}");
+    }
+
+    Ok(())
+}
+
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+async fn table_exists(connect_opts: &ConnectOpts, query: &str) -> anyhow::Result<bool> {
+    use sqlx::Connection;
+
+    let mut conn = crate::connect(connect_opts).await?;
+    let count: i64 = sqlx::query_scalar(query).fetch_one(&mut conn).await?;
+    let _ = conn.close().await;
+
+    Ok(count > 0)
+}
+
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(feature = "postgres")]
+mod postgres {
+    use std::fs::File;
+    use std::process::{Command, Stdio};
+
+    use anyhow::Context;
+    use sqlx::postgres::{PgConnectOptions, PgSslMode};
+    use sqlx::DatabaseUrl;
+
+    use super::{quote_literal, run, table_exists};
+    use crate::opt::ConnectOpts;
+
+    pub async fn dump(
+        connect_opts: &ConnectOpts,
+        migrations_table: &str,
+        schema_only: bool,
+    ) -> anyhow::Result<()> {
+        let client = client(&connect_opts.required_db_url()?)?;
+
+        let mut pg_dump = client("pg_dump");
+        pg_dump.args(["--no-owner", "--no-privileges"]);
+        if schema_only {
+            pg_dump.arg("--schema-only");
+        }
+        run(&mut pg_dump)?;
+
+        let exists = table_exists(
+            connect_opts,
+            &format!(
+                "SELECT COUNT(to_regclass({}))",
+                quote_literal(migrations_table)
+            ),
+        )
+        .await?;
+
+        if schema_only && exists {
+            run(client("pg_dump")
+                .args(["--data-only", "--table"])
+                .arg(migrations_table))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn load(db_url: &DatabaseUrl, script: File) -> anyhow::Result<()> {
+        run(client(db_url)?("psql")
+            .args(["--quiet", "--no-psqlrc", "--single-transaction"])
+            .args(["--set", "ON_ERROR_STOP=1"])
+            .stdin(script)
+            .stdout(Stdio::null()))
+    }
+
+    /// Build commands for the Postgres client tools connecting to the database in `db_url`,
+    /// passing the connection details in the environment to keep the password out of the
+    /// process arguments.
+    fn client(db_url: &DatabaseUrl) -> anyhow::Result<impl Fn(&str) -> Command> {
+        let options: PgConnectOptions = db_url.as_str().parse()?;
+
+        let database = options
+            .get_database()
+            .context("DATABASE_URL must include the name of the database")?
+            .to_owned();
+
+        // `PgConnectOptions` doesn't expose the password.
+        let password = db_url.password().map(String::from);
+
+        let host = match options.get_socket() {
+            Some(socket) => socket.display().to_string(),
+            None => options.get_host().to_owned(),
+        };
+
+        let ssl_mode = match options.get_ssl_mode() {
+            PgSslMode::Disable => "disable",
+            PgSslMode::Allow => "allow",
+            PgSslMode::Prefer => "prefer",
+            PgSslMode::Require => "require",
+            PgSslMode::VerifyCa => "verify-ca",
+            PgSslMode::VerifyFull => "verify-full",
+        };
+
+        Ok(move |program: &str| {
+            let mut command = Command::new(program);
+
+            command
+                .env("PGHOST", &host)
+                .env("PGPORT", options.get_port().to_string())
+                .env("PGUSER", options.get_username())
+                .env("PGDATABASE", &database)
+                .env("PGSSLMODE", ssl_mode)
+                .env_remove("PGPASSWORD");
+
+            if let Some(password) = &password {
+                command.env("PGPASSWORD", password);
+            }
+
+            command
+        })
+    }
+}
+
+#[cfg(feature = "mysql")]
+mod mysql {
+    use std::fs::File;
+
+    use sqlx::DatabaseUrl;
+
+    use super::{quote_literal, run, table_exists};
+    use crate::opt::ConnectOpts;
+    use crate::snapshot::mysql::Client;
+
+    pub async fn dump(
+        connect_opts: &ConnectOpts,
+        migrations_table: &str,
+        schema_only: bool,
+    ) -> anyhow::Result<()> {
+        let client = Client::from_url(&connect_opts.required_db_url()?)?;
+
+        let mut mysqldump = client.command("mysqldump");
+        mysqldump.args(["--single-transaction", "--routines", "--triggers"]);
+        if schema_only {
+            mysqldump.arg("--no-data");
+        }
+        run(mysqldump.arg(&client.database))?;
+
+        let (database, table) = match migrations_table.split_once('.') {
+            Some((database, table)) => (database, table),
+            None => (client.database.as_str(), migrations_table),
+        };
+
+        let exists = table_exists(
+            connect_opts,
+            &format!(
+                "SELECT COUNT(*) FROM information_schema.tables \
+                 WHERE table_schema = {} AND table_name = {}",
+                quote_literal(database),
+                quote_literal(table)
+            ),
+        )
+        .await?;
+
+        if schema_only && exists {
+            run(client
+                .command("mysqldump")
+                .args(["--no-create-info", "--skip-triggers"])
+                .args([database, table]))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn load(db_url: &DatabaseUrl, script: File) -> anyhow::Result<()> {
+        let client = Client::from_url(db_url)?;
+
+        run(client.command("mysql").arg(&client.database).stdin(script))
+    }
+}
+
+#[cfg(any(feature = "sqlite", feature = "sqlite-unbundled"))]
+mod sqlite {
+    use std::fs::File;
+    use std::io::{self, Read, Write};
+
+    use anyhow::Context;
+    use sqlx::{AnyConnection, Connection, Executor};
+
+    use crate::opt::ConnectOpts;
+
+    /// Unlike a file copy, the script can be loaded into another kind of database file, e.g.
+    /// one in WAL mode, and is readable in a diff; the format follows `sqlite3 .dump`.
+    pub async fn dump(
+        connect_opts: &ConnectOpts,
+        migrations_table: &str,
+        schema_only: bool,
+    ) -> anyhow::Result<()> {
+        let mut conn = crate::connect(connect_opts).await?;
+
+        let objects: Vec<(String, String, String)> = sqlx::query_as(
+            "SELECT type, name, sql FROM sqlite_master \
+             WHERE sql IS NOT NULL AND name NOT LIKE 'sqlite_%' \
+             ORDER BY type <> 'table', rowid",
+        )
+        .fetch_all(&mut conn)
+        .await?;
+
+        let mut script = String::from("PRAGMA foreign_keys=OFF;\nBEGIN TRANSACTION;\n");
+
+        for (kind, name, sql) in &objects {
+            script.push_str(sql);
+            script.push_str(";\n");
+
+            if kind == "table" && (!schema_only || name == migrations_table) {
+                dump_rows(&mut conn, name, &mut script).await?;
+            }
+        }
+
+        let autoincrement: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE name = 'sqlite_sequence'")
+                .fetch_one(&mut conn)
+                .await?;
+
+        // the next values of `AUTOINCREMENT` keys
+        if !schema_only && autoincrement > 0 {
+            script.push_str("DELETE FROM sqlite_sequence;\n");
+            dump_rows(&mut conn, "sqlite_sequence", &mut script).await?;
+        }
+
+        script.push_str("COMMIT;\n");
+
+        let _ = conn.close().await;
+
+        io::stdout().lock().write_all(script.as_bytes())?;
+
+        Ok(())
+    }
+
+    pub async fn load(connect_opts: &ConnectOpts, mut script: File) -> anyhow::Result<()> {
+        let mut sql = String::new();
+        script
+            .read_to_string(&mut sql)
+            .context("the script is not valid UTF-8")?;
+
+        let mut conn = crate::connect(connect_opts).await?;
+        conn.execute(&*sql).await?;
+        let _ = conn.close().await;
+
+        Ok(())
+    }
+
+    /// Append an `INSERT` statement for each row of `table` to `script`, with the values
+    /// quoted by SQLite itself.
+    async fn dump_rows(
+        conn: &mut AnyConnection,
+        table: &str,
+        script: &mut String,
+    ) -> anyhow::Result<()> {
+        let columns: Vec<String> = sqlx::query_scalar(&format!(
+            "SELECT name FROM pragma_table_info('{}') ORDER BY cid",
+            table.replace('\'', "''")
+        ))
+        .fetch_all(&mut *conn)
+        .await?;
+
+        if columns.is_empty() {
+            return Ok(());
+        }
+
+        let names = columns
+            .iter()
+            .map(|column| quote_ident(column))
+            .collect::<Vec<_>>()
+            .join(",");
+        let values = columns
+            .iter()
+            .map(|column| format!("quote({})", quote_ident(column)))
+            .collect::<Vec<_>>()
+            .join(" || ',' || ");
+
+        // the statement is built in a string literal, in which quotes are doubled
+        let inserts: Vec<String> = sqlx::query_scalar(&format!(
+            "SELECT 'INSERT INTO {literal}({names}) VALUES(' || {values} || ');' FROM {table}",
+            literal = quote_ident(table).replace('\'', "''"),
+            names = names.replace('\'', "''"),
+            table = quote_ident(table),
+        ))
+        .fetch_all(&mut *conn)
+        .await?;
+
+        for insert in inserts {
+            script.push_str(&insert);
+            script.push('\n');
+        }
+
+        Ok(())
+    }
+
+    fn quote_ident(ident: &str) -> String {
+        format!("\"{}\"", ident.replace('"', "\"\""))
+    }
+}
@@ -0,0 +1,443 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context};
+use futures::TryStreamExt;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use serde_json::json;
+use sha2::Sha256;
+use sqlx::{AnyConnection, Connection, Row};
+
+use crate::opt::ConnectOpts;
+use crate::output;
+
+/// Rules for anonymizing exported data, keyed by table and then by column.
+type Rules = BTreeMap<String, BTreeMap<String, Rule>>;
+
+/// How to replace the values of a column.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+enum Rule {
+    Named(NamedRule),
+    Constant {
+        constant: String,
+    },
+    Mask {
+        #[serde(default = "default_mask")]
+        mask: char,
+        #[serde(default)]
+        keep_last: usize,
+    },
+}
+
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum NamedRule {
+    /// Replace the value with `NULL`.
+    Null,
+    /// Replace the value with a hash of itself.
+    Hash,
+    /// Replace the value with a fake email address.
+    Email,
+    /// Replace the value with a fake full name.
+    Name,
+    /// Replace the value with a fake phone number.
+    Phone,
+}
+
+fn default_mask() -> char {
+    '*'
+}
+
+const FIRST_NAMES: &[&str] = &[
+    "Alex", "Sam", "Jordan", "Taylor", "Morgan", "Casey", "Riley", "Jamie", "Avery", "Quinn",
+];
+
+const LAST_NAMES: &[&str] = &[
+    "Smith", "Jones", "Garcia", "Chen", "Kumar", "Silva", "Novak", "Okafor", "Larsen", "Rossi",
+];
+
+impl Rule {
+    /// Apply the rule to a value. Fake values are derived from a hash of the original keyed with
+    /// `key`, so that equal values stay equal across tables and foreign keys still line up, but
+    /// the originals can't be found by hashing guesses without the key.
+    fn apply(&self, value: Option<String>, key: &[u8]) -> Option<String> {
+        let value = value?;
+
+        let hash = keyed_hash(key, value.as_bytes());
+
+        Some(match self {
+            Rule::Named(NamedRule::Null) => return None,
+            Rule::Named(NamedRule::Hash) => format!("{hash:016x}"),
+            Rule::Named(NamedRule::Email) => format!("user_{hash:016x}@example.com"),
+            Rule::Named(NamedRule::Name) => {
+                format!(
+                    "{} {}",
+                    pick(FIRST_NAMES, hash),
+                    pick(LAST_NAMES, hash >> 32)
+                )
+            }
+            Rule::Named(NamedRule::Phone) => format!("555-{:04}", hash % 10_000),
+            Rule::Constant { constant } => constant.clone(),
+            Rule::Mask { mask, keep_last } => {
+                let len = value.chars().count();
+                let masked = len.saturating_sub(*keep_last);
+
+                value
+                    .chars()
+                    .enumerate()
+                    .map(|(i, c)| if i < masked { *mask } else { c })
+                    .collect()
+            }
+        })
+    }
+}
+
+// The remainder is less than `list.len()`, so the cast can't truncate.
+#[allow(clippy::cast_possible_truncation)]
+fn pick(list: &[&'static str], hash: u64) -> &'static str {
+    list[(hash % list.len() as u64) as usize]
+}
+
+/// The first 64 bits of the HMAC-SHA256 of `bytes`, which is stable between Rust versions, so
+/// exports made at different times with the same key anonymize values the same way.
+fn keyed_hash(key: &[u8], bytes: &[u8]) -> u64 {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(bytes);
+
+    let digest = mac.finalize().into_bytes();
+    u64::from_be_bytes(
+        digest[..8]
+            .try_into()
+            .expect("SHA-256 digests are 32 bytes"),
+    )
+}
+
+/// The SQL dialect differences that matter for the export.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Dialect {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl Dialect {
+    fn of(conn: &AnyConnection) -> anyhow::Result<Self> {
+        Ok(match conn.backend_name() {
+            "PostgreSQL" => Self::Postgres,
+            "MySQL" => Self::MySql,
+            "SQLite" => Self::Sqlite,
+            other => bail!("exporting from {other} is not supported"),
+        })
+    }
+
+    fn quote_ident(self, ident: &str) -> String {
+        match self {
+            Self::MySql => format!("`{}`", ident.replace('`', "``")),
+            _ => format!("\"{}\"", ident.replace('"', "\"\"")),
+        }
+    }
+
+    fn quote_literal(self, value: &str) -> String {
+        let value = value.replace('\'', "''");
+
+        match self {
+            // MySQL treats backslashes as escapes unless `NO_BACKSLASH_ESCAPES` is set.
+            Self::MySql => format!("'{}'", value.replace('\\', "\\\\")),
+            _ => format!("'{value}'"),
+        }
+    }
+
+    /// Select `column` as text, so that values of any type can be read through `AnyConnection`.
+    fn select_as_text(self, column: &str) -> String {
+        let column = self.quote_ident(column);
+
+        match self {
+            Self::Postgres => format!("{column}::text"),
+            Self::MySql => format!("CAST({column} AS CHAR)"),
+            Self::Sqlite => format!("CAST({column} AS TEXT)"),
+        }
+    }
+
+    fn list_tables(self) -> &'static str {
+        match self {
+            Self::Postgres => {
+                "SELECT table_name::text FROM information_schema.tables \
+                 WHERE table_schema = current_schema() AND table_type = 'BASE TABLE' \
+                 ORDER BY table_name"
+            }
+            Self::MySql => {
+                "SELECT CAST(table_name AS CHAR) FROM information_schema.tables \
+                 WHERE table_schema = DATABASE() AND table_type = 'BASE TABLE' \
+                 ORDER BY table_name"
+            }
+            Self::Sqlite => {
+                "SELECT name FROM sqlite_master \
+                 WHERE type = 'table' AND name NOT LIKE 'sqlite_%' \
+                 ORDER BY name"
+            }
+        }
+    }
+
+    fn list_columns(self) -> &'static str {
+        match self {
+            Self::Postgres => {
+                "SELECT column_name::text FROM information_schema.columns \
+                 WHERE table_schema = current_schema() AND table_name = $1 \
+                 ORDER BY ordinal_position"
+            }
+            Self::MySql => {
+                "SELECT CAST(column_name AS CHAR) FROM information_schema.columns \
+                 WHERE table_schema = DATABASE() AND table_name = ? \
+                 ORDER BY ordinal_position"
+            }
+            Self::Sqlite => "SELECT name FROM pragma_table_info(?1) ORDER BY cid",
+        }
+    }
+}
+
+pub async fn run(
+    connect_opts: &ConnectOpts,
+    anonymize: Option<&Path>,
+    anonymize_key: Option<&str>,
+    tables: &[String],
+    output_path: Option<&Path>,
+) -> anyhow::Result<()> {
+    // Without a key, each export uses a random one, so its fake values can't be linked to
+    // those of other exports.
+    let key = match anonymize_key {
+        Some(key) => key.as_bytes().to_vec(),
+        None => rand::random::<[u8; 32]>().to_vec(),
+    };
+
+    let rules: Rules = match anonymize {
+        Some(path) => {
+            let rules = fs::read_to_string(path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            toml::from_str(&rules).with_context(|| {
+                format!("failed to parse anonymization rules in {}", path.display())
+            })?
+        }
+        None => Rules::new(),
+    };
+
+    let mut conn = crate::connect(connect_opts).await?;
+    let dialect = Dialect::of(&conn)?;
+
+    let mut columns = HashMap::new();
+
+    let all_tables: Vec<String> = sqlx::query_scalar(dialect.list_tables())
+        .fetch_all(&mut conn)
+        .await?;
+
+    let tables: Vec<String> = if tables.is_empty() {
+        all_tables
+            .into_iter()
+            .filter(|table| table != "_sqlx_migrations")
+            .collect()
+    } else {
+        if let Some(table) = tables.iter().find(|table| !all_tables.contains(table)) {
+            bail!("table `{table}` does not exist");
+        }
+        tables.to_vec()
+    };
+
+    for table in &tables {
+        let table_columns: Vec<String> = sqlx::query_scalar(dialect.list_columns())
+            .bind(table)
+            .fetch_all(&mut conn)
+            .await?;
+        columns.insert(table.clone(), table_columns);
+    }
+
+    // A typo in the rules would otherwise silently leak the column it was meant to anonymize.
+    for (table, table_rules) in &rules {
+        let Some(table_columns) = columns.get(table) else {
+            bail!("anonymization rules refer to table `{table}`, which is not being exported");
+        };
+
+        if let Some(column) = table_rules.keys().find(|c| !table_columns.contains(c)) {
+            bail!("anonymization rules refer to column `{column}`, which does not exist in table `{table}`");
+        }
+    }
+
+    let mut writer: BufWriter<Box<dyn Write>> = BufWriter::new(match output_path {
+        Some(path) => Box::new(
+            File::create(path).with_context(|| format!("failed to create {}", path.display()))?,
+        ),
+        None => Box::new(io::stdout()),
+    });
+
+    for table in &tables {
+        let table_columns = &columns[table];
+        let table_rules = rules.get(table);
+
+        let rows = export_table(
+            &mut conn,
+            &mut writer,
+            dialect,
+            table,
+            table_columns,
+            table_rules,
+            &key,
+        )
+        .await?;
+
+        // Messages would be mixed into the dump if it's being written to stdout.
+        if output_path.is_some() {
+            output::print(
+                "table_exported",
+                json!({ "table": table, "rows": rows }),
+                format_args!("Exported {rows} rows from {table}"),
+            );
+        }
+    }
+
+    writer.flush()?;
+
+    let _ = conn.close().await;
+
+    Ok(())
+}
+
+async fn export_table(
+    conn: &mut AnyConnection,
+    writer: &mut impl Write,
+    dialect: Dialect,
+    table: &str,
+    columns: &[String],
+    rules: Option<&BTreeMap<String, Rule>>,
+    key: &[u8],
+) -> anyhow::Result<u64> {
+    if columns.is_empty() {
+        return Ok(0);
+    }
+
+    let select = format!(
+        "SELECT {} FROM {}",
+        columns
+            .iter()
+            .map(|column| dialect.select_as_text(column))
+            .collect::<Vec<_>>()
+            .join(", "),
+        dialect.quote_ident(table)
+    );
+
+    let insert = format!(
+        "INSERT INTO {} ({}) VALUES",
+        dialect.quote_ident(table),
+        columns
+            .iter()
+            .map(|column| dialect.quote_ident(column))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    let column_rules: Vec<Option<&Rule>> = columns
+        .iter()
+        .map(|column| rules.and_then(|rules| rules.get(column)))
+        .collect();
+
+    let mut rows = sqlx::query(&select).fetch(&mut *conn);
+    let mut count = 0;
+
+    while let Some(row) = rows.try_next().await? {
+        let mut values = Vec::with_capacity(columns.len());
+
+        for (i, rule) in column_rules.iter().enumerate() {
+            let value: Option<String> = row.try_get(i)?;
+            let value = match rule {
+                Some(rule) => rule.apply(value, key),
+                None => value,
+            };
+
+            values.push(match value {
+                Some(value) => dialect.quote_literal(&value),
+                None => "NULL".to_string(),
+            });
+        }
+
+        writeln!(writer, "{insert} ({});", values.join(", "))?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rules() {
+        let rules: Rules = toml::from_str(
+            r##"
+            [users]
+            email = "email"
+            notes = "null"
+            ssn = { mask = "#", keep_last = 4 }
+            country = { constant = "XX" }
+            "##,
+        )
+        .unwrap();
+
+        let users = &rules["users"];
+        assert_eq!(users["email"], Rule::Named(NamedRule::Email));
+        assert_eq!(users["notes"], Rule::Named(NamedRule::Null));
+        assert_eq!(
+            users["ssn"],
+            Rule::Mask {
+                mask: '#',
+                keep_last: 4
+            }
+        );
+        assert_eq!(
+            users["country"],
+            Rule::Constant {
+                constant: "XX".into()
+            }
+        );
+    }
+
+    #[test]
+    fn applies_rules() {
+        const KEY: &[u8] = b"secret";
+
+        let mask = Rule::Mask {
+            mask: '*',
+            keep_last: 4,
+        };
+        assert_eq!(
+            mask.apply(Some("123-45-6789".into()), KEY).unwrap(),
+            "*******6789"
+        );
+        assert_eq!(mask.apply(None, KEY), None);
+
+        let email = Rule::Named(NamedRule::Email);
+        let a = email.apply(Some("alice@corp.com".into()), KEY).unwrap();
+        assert!(a.ends_with("@example.com"));
+        assert_eq!(email.apply(Some("alice@corp.com".into()), KEY).unwrap(), a);
+        assert_ne!(email.apply(Some("bob@corp.com".into()), KEY).unwrap(), a);
+        assert_ne!(
+            email
+                .apply(Some("alice@corp.com".into()), b"other")
+                .unwrap(),
+            a
+        );
+
+        assert_eq!(
+            Rule::Named(NamedRule::Null).apply(Some("x".into()), KEY),
+            None
+        );
+    }
+
+    #[test]
+    fn quotes_literals() {
+        assert_eq!(Dialect::Postgres.quote_literal(r"it's \n"), r"'it''s \n'");
+        assert_eq!(Dialect::MySql.quote_literal(r"it's \n"), r"'it''s \\n'");
+        assert_eq!(Dialect::MySql.quote_ident("a`b"), "`a``b`");
+    }
+}
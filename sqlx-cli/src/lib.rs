@@ -2,14 +2,22 @@ use std::io;
 use std::time::Duration;
 
 use anyhow::Result;
+use console::style;
 use futures::{Future, TryFutureExt};
 
-use sqlx::{AnyConnection, Connection};
+use sqlx::{AnyConnection, Connection, DatabaseUrl};
 use tokio::{select, signal};
 
-use crate::opt::{Command, ConnectOpts, DatabaseCommand, MigrateCommand};
+use crate::opt::{
+    Command, ConnectOpts, DatabaseCommand, InfoFilter, MigrateCommand, SeedCommand, SnapshotCommand,
+};
 
+mod config;
 mod database;
+mod doctor;
+mod dump;
+mod export;
+mod lint;
 mod metadata;
 // mod migration;
 // mod migrator;
@@ -17,7 +25,11 @@ mod metadata;
 mod completions;
 mod migrate;
 mod opt;
+mod output;
 mod prepare;
+mod replay;
+mod seed;
+mod snapshot;
 
 pub use crate::opt::Opt;
 
@@ -27,6 +39,9 @@ pub async fn run(opt: Opt) -> Result<()> {
     // This is currently necessary for the consumers of the `dialoguer` crate to restore
     // the user's terminal if the process is interrupted while a dialog is being displayed.
 
+    output::set_format(opt.format);
+    config::init(opt.profile.as_deref())?;
+
     let ctrlc_fut = signal::ctrl_c();
     let do_run_fut = do_run(opt);
 
@@ -50,16 +65,72 @@ async fn do_run(opt: Opt) -> Result<()> {
                 reversible,
                 sequential,
                 timestamp,
-            } => migrate::add(&source, &description, reversible, sequential, timestamp).await?,
+                repeatable,
+            } => {
+                migrate::add(
+                    &source,
+                    &description,
+                    reversible,
+                    sequential,
+                    timestamp,
+                    repeatable,
+                )
+                .await?
+            }
             MigrateCommand::Run {
                 source,
+                migrations_table,
+                dry_run,
+                ignore_missing,
+                connect_opts,
+                fan_out,
+                target_version,
+                allow_noop,
+                skip,
+            } => match fan_out.database_urls()? {
+                Some(database_urls) => {
+                    migrate::run_fan_out(
+                        &source,
+                        &migrations_table,
+                        migrations_table.schema_upgrade(),
+                        &connect_opts,
+                        database_urls,
+                        fan_out.jobs.into(),
+                        dry_run,
+                        *ignore_missing,
+                        target_version,
+                        allow_noop,
+                        &skip.versions(),
+                    )
+                    .await?
+                }
+                None => {
+                    migrate::run(
+                        &source,
+                        &migrations_table,
+                        migrations_table.schema_upgrade(),
+                        &connect_opts,
+                        dry_run,
+                        *ignore_missing,
+                        target_version,
+                        allow_noop,
+                        &skip.versions(),
+                    )
+                    .await?
+                }
+            },
+            MigrateCommand::Revert {
+                source,
+                migrations_table,
                 dry_run,
                 ignore_missing,
                 connect_opts,
                 target_version,
             } => {
-                migrate::run(
+                migrate::revert(
                     &source,
+                    &migrations_table,
+                    migrations_table.schema_upgrade(),
                     &connect_opts,
                     dry_run,
                     *ignore_missing,
@@ -67,55 +138,201 @@ async fn do_run(opt: Opt) -> Result<()> {
                 )
                 .await?
             }
-            MigrateCommand::Revert {
+            MigrateCommand::Info {
                 source,
+                migrations_table,
+                connect_opts,
+                exit_code,
+                filter,
+                pending_only,
+                skip,
+            } => {
+                let filter = if pending_only {
+                    vec![InfoFilter::Pending]
+                } else {
+                    filter
+                };
+
+                migrate::info(
+                    &source,
+                    &migrations_table,
+                    migrations_table.schema_upgrade(),
+                    &connect_opts,
+                    exit_code,
+                    &filter,
+                    &skip.versions(),
+                )
+                .await?
+            }
+            MigrateCommand::Baseline {
+                version,
+                source,
+                migrations_table,
                 dry_run,
                 ignore_missing,
                 connect_opts,
-                target_version,
             } => {
-                migrate::revert(
+                migrate::baseline(
                     &source,
+                    &migrations_table,
+                    migrations_table.schema_upgrade(),
                     &connect_opts,
                     dry_run,
                     *ignore_missing,
-                    target_version,
+                    version,
                 )
                 .await?
             }
-            MigrateCommand::Info {
+            MigrateCommand::Repair {
                 source,
+                migrations_table,
+                dry_run,
+                yes,
                 connect_opts,
-            } => migrate::info(&source, &connect_opts).await?,
+            } => {
+                migrate::repair(
+                    &source,
+                    &migrations_table,
+                    migrations_table.schema_upgrade(),
+                    &connect_opts,
+                    dry_run,
+                    yes,
+                )
+                .await?
+            }
             MigrateCommand::BuildScript { source, force } => migrate::build_script(&source, force)?,
         },
 
+        Command::Seed(seed) => match seed.command {
+            SeedCommand::New { description, seeds } => seed::new(&seeds, &description)?,
+            SeedCommand::Run {
+                seeds,
+                dry_run,
+                connect_opts,
+            } => seed::run(&seeds, &connect_opts, dry_run).await?,
+        },
+
         Command::Database(database) => match database.command {
             DatabaseCommand::Create { connect_opts } => database::create(&connect_opts).await?,
             DatabaseCommand::Drop {
                 confirmation,
                 connect_opts,
                 force,
-            } => database::drop(&connect_opts, !confirmation.yes, force).await?,
+            } => database::drop(&connect_opts, &confirmation, force).await?,
             DatabaseCommand::Reset {
                 confirmation,
                 source,
+                migrations_table,
                 connect_opts,
                 force,
-            } => database::reset(&source, &connect_opts, !confirmation.yes, force).await?,
+            } => {
+                database::reset(
+                    &source,
+                    &migrations_table,
+                    migrations_table.schema_upgrade(),
+                    &connect_opts,
+                    &confirmation,
+                    force,
+                )
+                .await?
+            }
             DatabaseCommand::Setup {
                 source,
+                migrations_table,
                 connect_opts,
-            } => database::setup(&source, &connect_opts).await?,
+                seed,
+                seeds,
+            } => {
+                database::setup(
+                    &source,
+                    &migrations_table,
+                    migrations_table.schema_upgrade(),
+                    &connect_opts,
+                )
+                .await?;
+
+                if seed {
+                    seed::run(&seeds, &connect_opts, false).await?;
+                }
+            }
+            DatabaseCommand::Snapshot(snapshot) => match snapshot.command {
+                SnapshotCommand::Save {
+                    name,
+                    snapshot_dir,
+                    connect_opts,
+                } => snapshot::save(&name, &snapshot_dir, &connect_opts).await?,
+                SnapshotCommand::Restore {
+                    name,
+                    confirmation,
+                    snapshot_dir,
+                    connect_opts,
+                    force,
+                } => {
+                    snapshot::restore(&name, &snapshot_dir, &connect_opts, &confirmation, force)
+                        .await?
+                }
+            },
+            DatabaseCommand::Dump {
+                schema_only,
+                migrations_table,
+                connect_opts,
+            } => dump::dump(&connect_opts, &migrations_table, schema_only).await?,
+            DatabaseCommand::Load { file, connect_opts } => {
+                dump::load(&connect_opts, &file).await?
+            }
         },
 
         Command::Prepare {
             check,
             all,
             workspace,
+            jobs,
             connect_opts,
             args,
-        } => prepare::run(check, all, workspace, connect_opts, args).await?,
+        } => prepare::run(check, all, workspace, jobs, connect_opts, args).await?,
+
+        Command::Export {
+            anonymize,
+            anonymize_key,
+            tables,
+            output,
+            connect_opts,
+        } => {
+            export::run(
+                &connect_opts,
+                anonymize.as_deref(),
+                anonymize_key.as_deref(),
+                &tables,
+                output.as_deref(),
+            )
+            .await?
+        }
+
+        Command::Lint {
+            source,
+            allow,
+            warn,
+            deny,
+            database_url,
+        } => lint::run(
+            &source,
+            &lint::Severities { allow, warn, deny },
+            database_url
+                .as_deref()
+                .or(config::get().database_url.as_deref()),
+        )?,
+
+        Command::Replay {
+            file,
+            keep_going,
+            connect_opts,
+        } => replay::run(&file, &connect_opts, keep_going).await?,
+
+        Command::Doctor {
+            source,
+            migrations_table,
+            connect_opts,
+        } => doctor::run(&source, &migrations_table, &connect_opts).await?,
 
         #[cfg(feature = "completions")]
         Command::Completions { shell } => completions::run(shell),
@@ -124,17 +341,40 @@ async fn do_run(opt: Opt) -> Result<()> {
     Ok(())
 }
 
+/// Print an error returned by [`run()`] in the requested output format.
+pub fn print_error(error: &anyhow::Error) {
+    output::print(
+        "error",
+        serde_json::json!({
+            "message": error.to_string(),
+            "causes": error.chain().skip(1).map(ToString::to_string).collect::<Vec<_>>(),
+        }),
+        format_args!("{} {}", style("error:").bold().red(), error),
+    );
+}
+
+/// The exit code of the process after `error`: 2 if `migrate info --exit-code` found applied
+/// migrations which drifted from their files, and 1 otherwise, as for pending migrations.
+pub fn exit_code(error: &anyhow::Error) -> i32 {
+    error
+        .downcast_ref::<migrate::MigrationState>()
+        .map_or(1, migrate::MigrationState::exit_code)
+}
+
 /// Attempt to connect to the database server, retrying up to `ops.connect_timeout`.
 async fn connect(opts: &ConnectOpts) -> anyhow::Result<AnyConnection> {
-    retry_connect_errors(opts, AnyConnection::connect).await
+    let db_url = opts.required_db_url()?;
+
+    retry_connect_errors(opts, &db_url, AnyConnection::connect).await
 }
 
 /// Attempt an operation that may return errors like `ConnectionRefused`,
 /// retrying up until `ops.connect_timeout`.
 ///
-/// The closure is passed `&ops.database_url` for easy composition.
+/// The closure is passed `db_url.as_str()` for easy composition.
 async fn retry_connect_errors<'a, F, Fut, T>(
-    opts: &'a ConnectOpts,
+    opts: &ConnectOpts,
+    db_url: &'a DatabaseUrl,
     mut connect: F,
 ) -> anyhow::Result<T>
 where
@@ -143,14 +383,12 @@ where
 {
     sqlx::any::install_default_drivers();
 
-    let db_url = opts.required_db_url()?;
-
     backoff::future::retry(
         backoff::ExponentialBackoffBuilder::new()
-            .with_max_elapsed_time(Some(Duration::from_secs(opts.connect_timeout)))
+            .with_max_elapsed_time(Some(Duration::from_secs(opts.connect_timeout())))
             .build(),
         || {
-            connect(db_url).map_err(|e| -> backoff::Error<anyhow::Error> {
+            connect(db_url.as_str()).map_err(|e| -> backoff::Error<anyhow::Error> {
                 if let sqlx::Error::Io(ref ioe) = e {
                     match ioe.kind() {
                         io::ErrorKind::ConnectionRefused
@@ -0,0 +1,553 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context};
+use clap::ValueEnum;
+use console::style;
+use serde_json::json;
+
+use crate::output;
+
+/// The checks performed by `sqlx lint`.
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintRule {
+    /// PostgreSQL only: `CREATE INDEX` without `CONCURRENTLY` on a table which already exists,
+    /// which blocks writes to the table while the index is built.
+    NonConcurrentIndex,
+    /// Changing the type of a column, which may rewrite the whole table under a lock.
+    TableRewrite,
+    /// A reversible migration whose `.up.sql` file has no matching `.down.sql` file.
+    MissingDown,
+    /// A migration which both changes the schema and modifies data.
+    MixedDmlDdl,
+}
+
+impl LintRule {
+    fn name(self) -> &'static str {
+        match self {
+            Self::NonConcurrentIndex => "non-concurrent-index",
+            Self::TableRewrite => "table-rewrite",
+            Self::MissingDown => "missing-down",
+            Self::MixedDmlDdl => "mixed-dml-ddl",
+        }
+    }
+
+    fn default_severity(self) -> Severity {
+        match self {
+            Self::MissingDown => Severity::Deny,
+            _ => Severity::Warn,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Severity {
+    Allow,
+    Warn,
+    Deny,
+}
+
+/// The severity each rule is reported with, as set by `--allow`, `--warn` and `--deny`.
+pub struct Severities {
+    pub allow: Vec<LintRule>,
+    pub warn: Vec<LintRule>,
+    pub deny: Vec<LintRule>,
+}
+
+impl Severities {
+    fn of(&self, rule: LintRule) -> Severity {
+        if self.deny.contains(&rule) {
+            Severity::Deny
+        } else if self.warn.contains(&rule) {
+            Severity::Warn
+        } else if self.allow.contains(&rule) {
+            Severity::Allow
+        } else {
+            rule.default_severity()
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Finding {
+    rule: LintRule,
+    file: String,
+    line: usize,
+    message: String,
+}
+
+pub fn run(
+    migration_sources: &[String],
+    severities: &Severities,
+    database_url: Option<&str>,
+) -> anyhow::Result<()> {
+    let mut files: Vec<(String, String)> = Vec::new();
+    // the directory of each file, to report findings with its path
+    let mut sources: HashMap<String, &str> = HashMap::new();
+
+    for migration_source in migration_sources {
+        for entry in fs::read_dir(migration_source)
+            .with_context(|| format!("failed to read migrations directory `{migration_source}`"))?
+        {
+            let entry = entry?;
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+
+            if !file_name.ends_with(".sql") || !entry.file_type()?.is_file() {
+                continue;
+            }
+
+            let sql = fs::read_to_string(entry.path())
+                .with_context(|| format!("failed to read {}", entry.path().display()))?;
+
+            sources.insert(file_name.clone(), migration_source);
+            files.push((file_name, sql));
+        }
+    }
+
+    files.sort();
+
+    // Without a `DATABASE_URL`, assume the rules for PostgreSQL apply.
+    let postgres = database_url.map_or(true, |url| {
+        url.starts_with("postgres:") || url.starts_with("postgresql:")
+    });
+
+    let findings: Vec<Finding> = lint(&files, postgres)
+        .into_iter()
+        .filter(|finding| severities.of(finding.rule) != Severity::Allow)
+        .collect();
+
+    let mut denied = 0;
+
+    for finding in &findings {
+        let severity = severities.of(finding.rule);
+        let path =
+            Path::new(sources.get(&finding.file).copied().unwrap_or_default()).join(&finding.file);
+
+        let label = if severity == Severity::Deny {
+            denied += 1;
+            style(format!("error[{}]:", finding.rule.name()))
+                .red()
+                .bold()
+        } else {
+            style(format!("warning[{}]:", finding.rule.name()))
+                .yellow()
+                .bold()
+        };
+
+        output::print(
+            "lint",
+            json!({
+                "rule": finding.rule.name(),
+                "severity": if severity == Severity::Deny { "deny" } else { "warn" },
+                "file": path,
+                "line": finding.line,
+                "message": finding.message,
+            }),
+            format_args!(
+                "{label} {}\n    {} {}:{}",
+                finding.message,
+                style("-->").dim(),
+                path.display(),
+                finding.line
+            ),
+        );
+    }
+
+    if denied > 0 {
+        bail!(
+            "{denied} of {} problem(s) found in migrations are denied",
+            findings.len()
+        );
+    }
+
+    output::print(
+        "lint_finished",
+        json!({ "warnings": findings.len() }),
+        format_args!(
+            "{} migration file(s) checked, {} warning(s)",
+            files.len(),
+            findings.len()
+        ),
+    );
+
+    Ok(())
+}
+
+fn lint(files: &[(String, String)], postgres: bool) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    let down_versions: BTreeSet<&str> = files
+        .iter()
+        .filter_map(|(name, _)| name.strip_suffix(".down.sql"))
+        .collect();
+
+    for (file, sql) in files {
+        if let Some(stem) = file.strip_suffix(".up.sql") {
+            if !down_versions.contains(stem) {
+                findings.push(Finding {
+                    rule: LintRule::MissingDown,
+                    file: file.clone(),
+                    line: 1,
+                    message: format!("`{stem}.down.sql` is missing"),
+                });
+            }
+        }
+
+        let statements = statements(sql);
+
+        let created_tables: BTreeSet<String> = statements
+            .iter()
+            .filter_map(|(_, words)| created_table(words))
+            .collect();
+
+        let mut first_ddl = None;
+        let mut first_dml = None;
+
+        for (line, words) in &statements {
+            let words: Vec<&str> = words.iter().map(String::as_str).collect();
+
+            match words.first().copied() {
+                Some("CREATE" | "ALTER" | "DROP" | "TRUNCATE" | "RENAME") => {
+                    first_ddl.get_or_insert(*line);
+                }
+                Some("INSERT" | "UPDATE" | "DELETE" | "MERGE" | "COPY") => {
+                    first_dml.get_or_insert(*line);
+                }
+                _ => (),
+            }
+
+            if postgres {
+                if let Some(table) = non_concurrent_index(&words) {
+                    if !created_tables.contains(&table) {
+                        findings.push(Finding {
+                            rule: LintRule::NonConcurrentIndex,
+                            file: file.clone(),
+                            line: *line,
+                            message: format!(
+                                "creating an index on `{table}` without `CONCURRENTLY` blocks \
+                                 writes to it; use `CREATE INDEX CONCURRENTLY` in a migration \
+                                 starting with `-- no-transaction`"
+                            ),
+                        });
+                    }
+                }
+            }
+
+            if let Some(table) = changes_column_type(&words) {
+                findings.push(Finding {
+                    rule: LintRule::TableRewrite,
+                    file: file.clone(),
+                    line: *line,
+                    message: format!(
+                        "changing the type of a column of `{table}` may rewrite the table \
+                         while holding an exclusive lock"
+                    ),
+                });
+            }
+        }
+
+        if let (Some(_), Some(line)) = (first_ddl, first_dml) {
+            findings.push(Finding {
+                rule: LintRule::MixedDmlDdl,
+                file: file.clone(),
+                line,
+                message: "this migration both changes the schema and modifies data; \
+                          consider moving data changes to their own migration"
+                    .into(),
+            });
+        }
+    }
+
+    findings
+}
+
+/// `CREATE TABLE [IF NOT EXISTS] <table>`
+fn created_table(words: &[String]) -> Option<String> {
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+
+    match words.as_slice() {
+        ["CREATE", "TABLE", "IF", "NOT", "EXISTS", table, ..]
+        | ["CREATE", "TABLE", table, ..]
+        | ["CREATE", "UNLOGGED", "TABLE", table, ..] => Some(unqualify(table)),
+        _ => None,
+    }
+}
+
+/// `CREATE [UNIQUE] INDEX ... ON <table>` without `CONCURRENTLY`
+fn non_concurrent_index(words: &[&str]) -> Option<String> {
+    let rest = match words {
+        ["CREATE", "INDEX", rest @ ..] | ["CREATE", "UNIQUE", "INDEX", rest @ ..] => rest,
+        _ => return None,
+    };
+
+    if rest.first() == Some(&"CONCURRENTLY") {
+        return None;
+    }
+
+    let on = rest.iter().position(|word| *word == "ON")?;
+
+    let table = match &rest[on + 1..] {
+        ["ONLY", table, ..] | [table, ..] => table,
+        [] => return None,
+    };
+
+    Some(unqualify(table))
+}
+
+/// `ALTER TABLE <table> ... ALTER [COLUMN] <column> [SET DATA] TYPE` or `MODIFY [COLUMN]`
+fn changes_column_type(words: &[&str]) -> Option<String> {
+    let rest = match words {
+        ["ALTER", "TABLE", "IF", "EXISTS", rest @ ..] | ["ALTER", "TABLE", rest @ ..] => rest,
+        _ => return None,
+    };
+
+    let table = rest.iter().find(|word| **word != "ONLY")?;
+
+    let changes_type = rest
+        .windows(2)
+        .any(|pair| matches!(pair, ["SET", "DATA"] | ["MODIFY", _] | ["CHANGE", _]))
+        || rest
+            .windows(4)
+            .any(|window| matches!(window, ["ALTER", "COLUMN", _, "TYPE"]))
+        || rest
+            .windows(3)
+            .any(|window| matches!(window, ["ALTER", column, "TYPE"] if *column != "COLUMN"));
+
+    changes_type.then(|| unqualify(table))
+}
+
+fn unqualify(name: &str) -> String {
+    let name = name.split('(').next().unwrap_or(name);
+    let name = name.rsplit('.').next().unwrap_or(name);
+    name.trim_matches(|c| c == '"' || c == '`').to_lowercase()
+}
+
+/// Split `sql` into statements, each with the line it starts on and its words in upper case.
+///
+/// Comments and the contents of string literals, quoted identifiers and dollar-quoted strings
+/// are dropped, except that quoted identifiers keep their quotes so they can be matched.
+fn statements(sql: &str) -> Vec<(usize, Vec<String>)> {
+    let mut statements = Vec::new();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut line = 1;
+    let mut start_line = None;
+
+    let mut chars = sql.chars().peekable();
+
+    macro_rules! end_word {
+        () => {
+            if !word.is_empty() {
+                start_line.get_or_insert(line);
+                words.push(std::mem::take(&mut word).to_uppercase());
+            }
+        };
+    }
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => {
+                end_word!();
+                line += 1;
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                end_word!();
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        line += 1;
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                end_word!();
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            '\'' => {
+                end_word!();
+                start_line.get_or_insert(line);
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    // A doubled quote is seen as two adjacent literals, which is equivalent here.
+                    if c == '\'' {
+                        break;
+                    }
+                }
+                words.push("''".into());
+            }
+            '"' | '`' => {
+                word.push(c);
+                for d in chars.by_ref() {
+                    word.push(d);
+                    if d == c {
+                        break;
+                    }
+                }
+            }
+            '$' if word.is_empty() => {
+                // A dollar-quoted string like `$body$ ... $body$`, or a parameter like `$1`.
+                let mut tag = String::from("$");
+                while let Some(&d) = chars.peek() {
+                    if d.is_alphanumeric() || d == '_' {
+                        tag.push(d);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+
+                if chars.peek() != Some(&'$') {
+                    word.push_str(&tag);
+                    continue;
+                }
+
+                chars.next();
+                tag.push('$');
+
+                start_line.get_or_insert(line);
+                let mut body = String::new();
+                for d in chars.by_ref() {
+                    if d == '\n' {
+                        line += 1;
+                    }
+                    body.push(d);
+                    if body.ends_with(&tag) {
+                        break;
+                    }
+                }
+                words.push("$$".into());
+            }
+            ';' => {
+                end_word!();
+                if let Some(start_line) = start_line.take() {
+                    statements.push((start_line, std::mem::take(&mut words)));
+                }
+            }
+            '(' | ')' | ',' => {
+                end_word!();
+                start_line.get_or_insert(line);
+                words.push(c.to_string());
+            }
+            c if c.is_whitespace() => end_word!(),
+            c => word.push(c),
+        }
+    }
+
+    end_word!();
+
+    if let Some(start_line) = start_line {
+        statements.push((start_line, words));
+    }
+
+    statements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint_one(sql: &str) -> Vec<(LintRule, usize)> {
+        lint(&[("1_test.sql".into(), sql.into())], true)
+            .into_iter()
+            .map(|finding| (finding.rule, finding.line))
+            .collect()
+    }
+
+    #[test]
+    fn splits_statements() {
+        let statements = statements(
+            "-- a comment; with a semicolon\n\
+             CREATE TABLE \"my table\" (x TEXT DEFAULT 'a;b');\n\
+             /* block;\ncomment */ CREATE FUNCTION f() RETURNS int AS $$ SELECT 1; $$ LANGUAGE sql;",
+        );
+
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[0].0, 2);
+        assert_eq!(statements[0].1[..3], ["CREATE", "TABLE", "\"MY TABLE\""]);
+        assert_eq!(statements[1].0, 4);
+    }
+
+    #[test]
+    fn flags_non_concurrent_index() {
+        assert_eq!(
+            lint_one("CREATE INDEX users_email ON users (email);"),
+            [(LintRule::NonConcurrentIndex, 1)]
+        );
+        assert_eq!(
+            lint_one("CREATE INDEX CONCURRENTLY users_email ON users (email);"),
+            []
+        );
+        // Indexing a table created in the same migration is fine.
+        assert_eq!(
+            lint_one(
+                "CREATE TABLE users (email TEXT);\nCREATE UNIQUE INDEX ON public.users (email);"
+            ),
+            []
+        );
+        assert!(lint(
+            &[(
+                "1_test.sql".into(),
+                "CREATE INDEX i ON users (email);".into()
+            )],
+            false
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn flags_table_rewrite() {
+        assert_eq!(
+            lint_one("ALTER TABLE users ALTER COLUMN id TYPE BIGINT;"),
+            [(LintRule::TableRewrite, 1)]
+        );
+        assert_eq!(
+            lint_one("ALTER TABLE users ALTER id SET DATA TYPE BIGINT;"),
+            [(LintRule::TableRewrite, 1)]
+        );
+        assert_eq!(
+            lint_one("ALTER TABLE users MODIFY COLUMN id BIGINT;"),
+            [(LintRule::TableRewrite, 1)]
+        );
+        assert_eq!(lint_one("ALTER TABLE users ADD COLUMN type TEXT;"), []);
+        assert_eq!(
+            lint_one("ALTER TABLE users ALTER COLUMN type SET DEFAULT 'a';"),
+            []
+        );
+    }
+
+    #[test]
+    fn flags_mixed_dml_ddl() {
+        assert_eq!(
+            lint_one("CREATE TABLE t (x INT);\n\nINSERT INTO t VALUES (1);"),
+            [(LintRule::MixedDmlDdl, 3)]
+        );
+        assert_eq!(lint_one("INSERT INTO t VALUES ('CREATE TABLE');"), []);
+    }
+
+    #[test]
+    fn flags_missing_down() {
+        let files = [
+            ("1_a.up.sql".to_string(), String::new()),
+            ("1_a.down.sql".to_string(), String::new()),
+            ("2_b.up.sql".to_string(), String::new()),
+        ];
+
+        let findings = lint(&files, true);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule, LintRule::MissingDown);
+        assert_eq!(findings[0].file, "2_b.up.sql");
+    }
+}
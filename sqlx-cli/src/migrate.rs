@@ -1,14 +1,21 @@
-use crate::opt::ConnectOpts;
+use crate::opt::{ConnectOpts, InfoFilter};
+use crate::output;
 use anyhow::{bail, Context};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use console::style;
-use sqlx::migrate::{AppliedMigration, Migrate, MigrateError, MigrationType, Migrator};
-use sqlx::Connection;
+use futures::{stream, StreamExt};
+use serde_json::json;
+use sqlx::migrate::{
+    prepare_migrations_table, AppliedMigration, Migrate, MigrateError, Migration,
+    MigrationMetadata, MigrationType, Migrator, MIGRATIONS_TABLE_VERSION,
+};
+use sqlx::{Connection, DatabaseUrl};
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
 use std::fs::{self, File};
-use std::path::Path;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 fn create_file(
@@ -17,10 +24,11 @@ fn create_file(
     description: &str,
     migration_type: MigrationType,
 ) -> anyhow::Result<()> {
-    use std::path::PathBuf;
-
     let mut file_name = file_prefix.to_string();
-    file_name.push('_');
+    if !migration_type.is_repeatable() {
+        // the prefix of a repeatable migration includes the separator
+        file_name.push('_');
+    }
     file_name.push_str(&description.replace(' ', "_"));
     file_name.push_str(migration_type.suffix());
 
@@ -28,7 +36,11 @@ fn create_file(
     path.push(migration_source);
     path.push(&file_name);
 
-    println!("Creating {}", style(path.display()).cyan());
+    output::print(
+        "migration_created",
+        json!({ "path": path }),
+        format_args!("Creating {}", style(path.display()).cyan()),
+    );
 
     let mut file = File::create(&path).context("Failed to create migration file")?;
 
@@ -65,14 +77,17 @@ impl MigrationOrdering {
             (false, true) => MigrationOrdering::sequential(
                 migrator
                     .iter()
-                    .last()
+                    .rfind(|migration| !migration.migration_type.is_repeatable())
                     .map_or(1, |last_migration| last_migration.version + 1),
             ),
             (false, false) => {
                 // inferring the naming scheme
                 let migrations = migrator
                     .iter()
-                    .filter(|migration| migration.migration_type.is_up_migration())
+                    .filter(|migration| {
+                        migration.migration_type.is_up_migration()
+                            && !migration.migration_type.is_repeatable()
+                    })
                     .rev()
                     .take(2)
                     .collect::<Vec<_>>();
@@ -101,15 +116,21 @@ impl MigrationOrdering {
 }
 
 pub async fn add(
-    migration_source: &str,
+    migration_sources: &[String],
     description: &str,
     reversible: bool,
     sequential: bool,
     timestamp: bool,
+    repeatable: bool,
 ) -> anyhow::Result<()> {
+    // new migrations are numbered after those of every source, but only added to the last one
+    let migration_source = migration_sources
+        .last()
+        .context("no migrations directory given")?;
+
     fs::create_dir_all(migration_source).context("Unable to create migrations directory")?;
 
-    let migrator = Migrator::new(Path::new(migration_source)).await?;
+    let migrator = migrator(migration_sources).await?;
     // Type of newly created migration will be the same as the first one
     // or reversible flag if this is the first migration
     let migration_type = MigrationType::infer(&migrator, reversible);
@@ -117,7 +138,14 @@ pub async fn add(
     let ordering = MigrationOrdering::infer(sequential, timestamp, &migrator);
     let file_prefix = ordering.file_prefix();
 
-    if migration_type.is_reversible() {
+    if repeatable {
+        create_file(
+            migration_source,
+            MigrationType::REPEATABLE_PREFIX,
+            description,
+            MigrationType::Repeatable,
+        )?;
+    } else if migration_type.is_reversible() {
         create_file(
             migration_source,
             file_prefix,
@@ -144,7 +172,7 @@ pub async fn add(
         .map(|mut dir| dir.next().is_some())
         .unwrap_or(false);
 
-    if !has_existing_migrations {
+    if !has_existing_migrations && migration_sources.len() == 1 && !output::is_json() {
         let quoted_source = if migration_source != "migrations" {
             format!("{migration_source:?}")
         } else {
@@ -184,6 +212,13 @@ See: https://docs.rs/sqlx/{version}/sqlx/macro.migrate.html
     Ok(())
 }
 
+/// Resolve the migrations of all of `migration_sources`, merged in order of version.
+async fn migrator(migration_sources: &[String]) -> anyhow::Result<Migrator> {
+    let paths: Vec<PathBuf> = migration_sources.iter().map(PathBuf::from).collect();
+
+    Ok(Migrator::new(paths).await?)
+}
+
 fn short_checksum(checksum: &[u8]) -> String {
     let mut s = String::with_capacity(checksum.len() * 2);
     for b in checksum {
@@ -192,44 +227,129 @@ fn short_checksum(checksum: &[u8]) -> String {
     s
 }
 
-pub async fn info(migration_source: &str, connect_opts: &ConnectOpts) -> anyhow::Result<()> {
-    let migrator = Migrator::new(Path::new(migration_source)).await?;
+pub async fn info(
+    migration_sources: &[String],
+    migrations_table: &str,
+    schema_upgrade: bool,
+    connect_opts: &ConnectOpts,
+    exit_code: bool,
+    filter: &[InfoFilter],
+    skip: &[i64],
+) -> anyhow::Result<()> {
+    let mut migrator = migrator(migration_sources).await?;
+    migrator.set_skip(skip.iter().copied());
     let mut conn = crate::connect(connect_opts).await?;
 
-    conn.ensure_migrations_table().await?;
+    prepare_migrations_table(&mut conn, migrations_table, schema_upgrade).await?;
 
-    let applied_migrations: HashMap<_, _> = conn
-        .list_applied_migrations()
+    let mut applied_migrations: HashMap<_, _> = conn
+        .list_applied_migrations(migrations_table)
         .await?
         .into_iter()
         .map(|m| (m.version, m))
         .collect();
 
+    let _ = conn.close().await;
+
+    let mut pending = 0;
+    let mut drifted = 0;
+
     for migration in migrator.iter() {
         if migration.migration_type.is_down_migration() {
             // Skipping down migrations
             continue;
         }
 
-        let applied = applied_migrations.get(&migration.version);
+        let applied = applied_migrations.remove(&migration.version);
+        let applied = applied.as_ref();
+
+        // a migration recorded as skipped, but no longer in the skip list, is applied by the next
+        // `migrate run`
+        let unskipped =
+            applied.is_some_and(|applied| applied.skipped && !migrator.is_skipped(applied.version));
 
         let (status_msg, mismatched_checksum) = if let Some(applied) = applied {
-            if applied.checksum != migration.checksum {
-                (style("installed (different checksum)").red(), true)
-            } else {
+            if unskipped {
+                (style("pending (skipped before)").yellow(), false)
+            } else if applied.skipped {
+                (style("skipped").dim(), false)
+            } else if applied.checksum == migration.checksum {
                 (style("installed").green(), false)
+            } else if migration.migration_type.is_repeatable() {
+                (style("pending (changed)").yellow(), true)
+            } else {
+                (style("installed (different checksum)").red(), true)
             }
+        } else if migrator.is_skipped(migration.version) {
+            (style("skipped (not recorded)").dim(), false)
         } else {
             (style("pending").yellow(), false)
         };
 
+        // a changed repeatable migration is applied again by the next `migrate run`
+        let is_pending = (applied.is_none() && !migrator.is_skipped(migration.version))
+            || unskipped
+            || (mismatched_checksum && migration.migration_type.is_repeatable());
+        if is_pending {
+            pending += 1;
+        } else if mismatched_checksum {
+            drifted += 1;
+        }
+
+        if !is_listed(filter, is_pending, applied.is_some(), mismatched_checksum) {
+            continue;
+        }
+
+        if output::is_json() {
+            output::event(
+                "migration_status",
+                json!({
+                    "version": migration.version,
+                    "description": migration.description,
+                    "type": migration.migration_type.label(),
+                    "status": match applied {
+                        Some(_) if unskipped => "pending",
+                        Some(applied) if applied.skipped => "skipped",
+                        None if migrator.is_skipped(migration.version) => "skipped",
+                        Some(_) if mismatched_checksum && migration.migration_type.is_repeatable() => {
+                            "changed"
+                        }
+                        Some(_) if mismatched_checksum => "checksum_mismatch",
+                        Some(_) => "installed",
+                        None => "pending",
+                    },
+                    "checksum": match applied {
+                        Some(_) if mismatched_checksum => "mismatch",
+                        Some(_) => "ok",
+                        None => "missing_row",
+                    },
+                    "pending": is_pending,
+                    "skipped": applied.map_or(migrator.is_skipped(migration.version), |a| a.skipped),
+                    "local_checksum": short_checksum(&migration.checksum),
+                    "applied_checksum": applied.map(|a| short_checksum(&a.checksum)),
+                    "installed_on": applied.and_then(installed_on).map(|t| t.to_rfc3339()),
+                    "execution_time_ms": applied
+                        .and_then(|a| a.execution_time)
+                        .map(|t| t.as_secs_f64() * 1000.0),
+                    "applied_by": applied.and_then(|a| a.applied_by.as_deref()),
+                    "hostname": applied.and_then(|a| a.hostname.as_deref()),
+                    "app_version": applied.and_then(|a| a.app_version.as_deref()),
+                }),
+            );
+            continue;
+        }
+
         println!(
             "{}/{} {}",
-            style(migration.version).cyan(),
+            style(display_version(migration)).cyan(),
             status_msg,
             migration.description
         );
 
+        if let Some(details) = applied.and_then(applied_details) {
+            println!("    {}", style(details).dim());
+        }
+
         if mismatched_checksum {
             println!(
                 "applied migration had checksum {}",
@@ -246,9 +366,158 @@ pub async fn info(migration_source: &str, connect_opts: &ConnectOpts) -> anyhow:
         }
     }
 
-    let _ = conn.close().await;
+    // what remains was applied from files which no longer exist
+    let mut missing: Vec<_> = applied_migrations.into_values().collect();
+    missing.sort_by_key(|applied| applied.version);
+    drifted += missing.len();
 
-    Ok(())
+    for applied in missing
+        .iter()
+        .filter(|_| is_listed(filter, false, true, true))
+    {
+        output::print(
+            "migration_status",
+            json!({
+                "version": applied.version,
+                "description": null,
+                "type": null,
+                "status": "missing",
+                "checksum": "missing_file",
+                "pending": false,
+                "local_checksum": null,
+                "applied_checksum": short_checksum(&applied.checksum),
+                "installed_on": installed_on(applied).map(|t| t.to_rfc3339()),
+                "execution_time_ms": applied.execution_time.map(|t| t.as_secs_f64() * 1000.0),
+                "applied_by": applied.applied_by.as_deref(),
+                "hostname": applied.hostname.as_deref(),
+                "app_version": applied.app_version.as_deref(),
+            }),
+            format_args!(
+                "{}/{}",
+                style(applied.version).cyan(),
+                style("installed (missing file)").red()
+            ),
+        );
+
+        if !output::is_json() {
+            if let Some(details) = applied_details(applied) {
+                println!("    {}", style(details).dim());
+            }
+        }
+    }
+
+    let state = if drifted > 0 {
+        Some(MigrationState::Drift(drifted))
+    } else if pending > 0 {
+        Some(MigrationState::Pending(pending))
+    } else {
+        None
+    };
+
+    output::event(
+        "migrate_info_finished",
+        json!({
+            "state": match state {
+                Some(MigrationState::Drift(_)) => "drift",
+                Some(MigrationState::Pending(_)) => "pending",
+                None => "up_to_date",
+            },
+            "pending": pending,
+            "drifted": drifted,
+        }),
+    );
+
+    match state {
+        Some(state) if exit_code => Err(state.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Whether a migration is listed by `migrate info --filter`; all are if no filter is given.
+fn is_listed(filter: &[InfoFilter], pending: bool, applied: bool, modified: bool) -> bool {
+    filter.is_empty()
+        || filter.iter().any(|state| match state {
+            InfoFilter::Pending => pending,
+            InfoFilter::Applied => applied && !pending,
+            InfoFilter::Modified => modified,
+        })
+}
+
+/// The state of the migrations of a database which is not up to date, reported by
+/// `migrate info --exit-code` through the exit code of the process.
+#[derive(Debug)]
+pub enum MigrationState {
+    /// This many migrations are yet to be applied, including changed repeatable migrations.
+    Pending(usize),
+    /// This many applied migrations were changed since, or their files deleted.
+    Drift(usize),
+}
+
+impl MigrationState {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            MigrationState::Pending(_) => 1,
+            MigrationState::Drift(_) => 2,
+        }
+    }
+}
+
+impl std::fmt::Display for MigrationState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationState::Pending(count) => write!(f, "{count} migration(s) pending"),
+            MigrationState::Drift(count) => write!(
+                f,
+                "{count} applied migration(s) differ from their files or have no file"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MigrationState {}
+
+/// When a migration was applied, if it was recorded.
+fn installed_on(applied: &AppliedMigration) -> Option<DateTime<Utc>> {
+    applied.installed_on.map(DateTime::from)
+}
+
+/// The version of a migration as printed; repeatable migrations are only tracked by a version
+/// derived from their description, so they are shown as `R` instead.
+fn display_version(migration: &Migration) -> String {
+    if migration.migration_type.is_repeatable() {
+        "R".to_string()
+    } else {
+        migration.version.to_string()
+    }
+}
+
+/// Describe who applied a migration and how long it took, from whatever was recorded.
+fn applied_details(applied: &AppliedMigration) -> Option<String> {
+    let mut details = Vec::new();
+
+    if let Some(time) = installed_on(applied) {
+        details.push(format!(
+            "installed {}",
+            time.format("%Y-%m-%d %H:%M:%S UTC")
+        ));
+    }
+
+    match (&applied.applied_by, &applied.hostname) {
+        (Some(user), Some(host)) => details.push(format!("applied by {user} on {host}")),
+        (Some(user), None) => details.push(format!("applied by {user}")),
+        (None, Some(host)) => details.push(format!("applied on {host}")),
+        (None, None) => {}
+    }
+
+    if let Some(version) = &applied.app_version {
+        details.push(format!("app version {version}"));
+    }
+
+    if let Some(time) = applied.execution_time {
+        details.push(format!("took {time:.2?}"));
+    }
+
+    (!details.is_empty()).then(|| details.join(", "))
 }
 
 fn validate_applied_migrations(
@@ -263,6 +532,10 @@ fn validate_applied_migrations(
     let migrations: HashSet<_> = migrator.iter().map(|m| m.version).collect();
 
     for applied_migration in applied_migrations {
+        if Migration::is_repeatable_version(applied_migration.version) {
+            continue;
+        }
+
         if !migrations.contains(&applied_migration.version) {
             return Err(MigrateError::VersionMissing(applied_migration.version));
         }
@@ -271,30 +544,140 @@ fn validate_applied_migrations(
     Ok(())
 }
 
+/// Print the outcome of applying or reverting a single migration.
+fn print_migration(
+    event: &str,
+    migration: &Migration,
+    skip: bool,
+    dry_run: bool,
+    elapsed: Duration,
+) {
+    let (text, status) = if skip {
+        ("Skipped", "skipped")
+    } else if dry_run {
+        ("Can apply", "dry_run")
+    } else {
+        ("Applied", "applied")
+    };
+
+    output::print(
+        event,
+        json!({
+            "version": migration.version,
+            "description": migration.description,
+            "type": migration.migration_type.label(),
+            "checksum": short_checksum(&migration.checksum),
+            "status": status,
+            "elapsed_secs": elapsed.as_secs_f64(),
+        }),
+        format_args!(
+            "{} {}/{} {} {}",
+            text,
+            style(display_version(migration)).cyan(),
+            style(migration.migration_type.label()).green(),
+            migration.description,
+            style(format!("({elapsed:?})")).dim()
+        ),
+    );
+}
+
+/// Print a migration recorded as skipped instead of being applied, as it is in the skip list.
+fn print_skipped_migration(migration: &Migration, dry_run: bool) {
+    output::print(
+        "migration_skipped",
+        json!({
+            "version": migration.version,
+            "description": migration.description,
+            "type": migration.migration_type.label(),
+            "checksum": short_checksum(&migration.checksum),
+            "status": if dry_run { "dry_run" } else { "skipped" },
+        }),
+        format_args!(
+            "{} {}/{} {}",
+            if dry_run {
+                "Can record as skipped"
+            } else {
+                "Recorded as skipped"
+            },
+            style(display_version(migration)).cyan(),
+            style(migration.migration_type.label()).green(),
+            migration.description
+        ),
+    );
+}
+
+/// Print SQL which a dry run would execute, preceded by a comment describing it.
+fn print_script(mut details: serde_json::Value, comment: &str, sql: &str) {
+    let sql = sql.trim();
+    details["sql"] = sql.into();
+
+    output::print(
+        "dry_run_script",
+        details,
+        format_args!("{comment}\n{sql}\n"),
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
-    migration_source: &str,
+    migration_sources: &[String],
+    migrations_table: &str,
+    schema_upgrade: bool,
     connect_opts: &ConnectOpts,
     dry_run: bool,
     ignore_missing: bool,
     target_version: Option<i64>,
+    allow_noop: bool,
+    skip: &[i64],
 ) -> anyhow::Result<()> {
-    let migrator = Migrator::new(Path::new(migration_source)).await?;
+    let mut migrator = migrator(migration_sources).await?;
     if let Some(target_version) = target_version {
         if !migrator.version_exists(target_version) {
             bail!(MigrateError::VersionNotPresent(target_version));
         }
     }
 
-    let mut conn = crate::connect(connect_opts).await?;
+    if let Some(&version) = skip.iter().find(|&&v| !migrator.version_exists(v)) {
+        bail!(MigrateError::VersionNotPresent(version));
+    }
+    migrator.set_skip(skip.iter().copied());
 
-    conn.ensure_migrations_table().await?;
+    let mut conn = crate::connect(connect_opts).await?;
 
-    let version = conn.dirty_version().await?;
-    if let Some(version) = version {
-        bail!(MigrateError::Dirty(version));
-    }
+    // A dry run must not create or upgrade the migrations table, so render its creation instead.
+    let table_exists = if dry_run {
+        match conn.migrations_table_version(migrations_table).await? {
+            None => {
+                let sql = conn.ensure_migrations_table_script(migrations_table)?;
+                print_script(
+                    json!({ "table": migrations_table }),
+                    &format!("-- create the migrations table {migrations_table}"),
+                    &sql,
+                );
+                false
+            }
+            Some(version) if version < MIGRATIONS_TABLE_VERSION => bail!(
+                "`{migrations_table}` was created by an older version of SQLx \
+                 (schema version {version}, current is {MIGRATIONS_TABLE_VERSION}); \
+                 run `sqlx migrate info` to upgrade it before a dry run"
+            ),
+            Some(_) => true,
+        }
+    } else {
+        prepare_migrations_table(&mut conn, migrations_table, schema_upgrade).await?;
+        true
+    };
+
+    let applied_migrations = if table_exists {
+        let version = conn.dirty_version(migrations_table).await?;
+        if let Some(version) = version {
+            bail!(MigrateError::Dirty(version));
+        }
 
-    let applied_migrations = conn.list_applied_migrations().await?;
+        conn.list_applied_migrations(migrations_table).await?
+    } else {
+        Vec::new()
+    };
     validate_applied_migrations(&applied_migrations, &migrator, ignore_missing)?;
 
     let latest_version = applied_migrations
@@ -304,7 +687,22 @@ pub async fn run(
         .unwrap_or(0);
     if let Some(target_version) = target_version {
         if target_version < latest_version {
-            bail!(MigrateError::VersionTooOld(target_version, latest_version));
+            if !allow_noop {
+                bail!(MigrateError::VersionTooOld(target_version, latest_version));
+            }
+
+            output::print(
+                "migration_noop",
+                json!({ "target_version": target_version, "latest_version": latest_version }),
+                format_args!(
+                    "Nothing to apply: version {} is already applied, which is newer than {}",
+                    style(latest_version).cyan(),
+                    style(target_version).cyan()
+                ),
+            );
+
+            let _ = conn.close().await;
+            return Ok(());
         }
     }
 
@@ -319,39 +717,71 @@ pub async fn run(
             continue;
         }
 
-        match applied_migrations.get(&migration.version) {
-            Some(applied_migration) => {
-                if migration.checksum != applied_migration.checksum {
-                    bail!(MigrateError::VersionMismatch(migration.version));
+        let applied = applied_migrations.get(&migration.version);
+        let skip = target_version.is_some_and(|target_version| migration.version > target_version);
+
+        if migrator.is_skipped(migration.version) {
+            // intentionally not applied to this database, which is recorded once
+            if applied.is_none() && !skip {
+                if !dry_run {
+                    conn.mark_skipped(migrations_table, migration, &MigrationMetadata::current())
+                        .await?;
                 }
+                print_skipped_migration(migration, dry_run);
             }
-            None => {
-                let skip =
-                    target_version.is_some_and(|target_version| migration.version > target_version);
+            continue;
+        }
 
-                let elapsed = if dry_run || skip {
-                    Duration::new(0, 0)
-                } else {
-                    conn.apply(migration).await?
-                };
-                let text = if skip {
-                    "Skipped"
-                } else if dry_run {
-                    "Can apply"
-                } else {
-                    "Applied"
-                };
-
-                println!(
-                    "{} {}/{} {} {}",
-                    text,
-                    style(migration.version).cyan(),
-                    style(migration.migration_type.label()).green(),
-                    migration.description,
-                    style(format!("({elapsed:?})")).dim()
+        // removed from the skip list since it was recorded as skipped
+        let unskip = applied.is_some_and(|applied| applied.skipped);
+
+        if !unskip && !migration.is_pending(applied)? {
+            continue;
+        }
+
+        if dry_run {
+            if !skip {
+                let mut sql =
+                    conn.apply_script(migrations_table, migration, &MigrationMetadata::current())?;
+                if unskip {
+                    // language=SQL
+                    sql.insert_str(
+                        0,
+                        &format!(
+                            "DELETE FROM {migrations_table} WHERE version = {};\n",
+                            migration.version
+                        ),
+                    );
+                }
+                print_script(
+                    json!({
+                        "version": migration.version,
+                        "description": migration.description,
+                        "type": migration.migration_type.label(),
+                        "checksum": short_checksum(&migration.checksum),
+                    }),
+                    &format!(
+                        "-- {}/{} {}",
+                        display_version(migration),
+                        migration.migration_type.label(),
+                        migration.description
+                    ),
+                    &sql,
                 );
             }
+            continue;
         }
+
+        let elapsed = if skip {
+            Duration::new(0, 0)
+        } else {
+            if unskip {
+                conn.remove_applied_migration(migrations_table, migration.version)
+                    .await?;
+            }
+            conn.apply(migrations_table, migration).await?
+        };
+        print_migration("migration_applied", migration, skip, dry_run, elapsed);
     }
 
     // Close the connection before exiting:
@@ -364,14 +794,103 @@ pub async fn run(
     Ok(())
 }
 
+/// Run pending migrations against each of `database_urls`, up to `jobs` at a time,
+/// and report which databases failed once all of them have been attempted.
+#[allow(clippy::too_many_arguments)]
+pub async fn run_fan_out(
+    migration_sources: &[String],
+    migrations_table: &str,
+    schema_upgrade: bool,
+    connect_opts: &ConnectOpts,
+    database_urls: Vec<DatabaseUrl>,
+    jobs: usize,
+    dry_run: bool,
+    ignore_missing: bool,
+    target_version: Option<i64>,
+    allow_noop: bool,
+    skip: &[i64],
+) -> anyhow::Result<()> {
+    let total = database_urls.len();
+
+    let results: Vec<(String, anyhow::Result<()>)> = stream::iter(database_urls)
+        .map(|database_url| async move {
+            let display_url = database_url.to_string();
+
+            let connect_opts = ConnectOpts {
+                database_url: Some(database_url.as_str().to_owned()),
+                ..connect_opts.clone()
+            };
+
+            output::print(
+                "database_migrating",
+                json!({ "url": display_url }),
+                format_args!("Migrating {}", style(&display_url).bold()),
+            );
+
+            let res = run(
+                migration_sources,
+                migrations_table,
+                schema_upgrade,
+                &connect_opts,
+                dry_run,
+                ignore_missing,
+                target_version,
+                allow_noop,
+                skip,
+            )
+            .await;
+
+            (display_url, res)
+        })
+        .buffered(jobs)
+        .collect()
+        .await;
+
+    let failed: Vec<_> = results
+        .into_iter()
+        .filter_map(|(url, res)| res.err().map(|e| (url, e)))
+        .collect();
+
+    if output::is_json() {
+        output::event(
+            "fan_out_finished",
+            json!({
+                "total": total,
+                "succeeded": total - failed.len(),
+                "failed": failed
+                    .iter()
+                    .map(|(url, e)| json!({ "url": url, "error": format!("{e:#}") }))
+                    .collect::<Vec<_>>(),
+            }),
+        );
+    } else {
+        println!(
+            "{} of {total} databases migrated successfully",
+            style(total - failed.len()).cyan()
+        );
+
+        for (url, e) in &failed {
+            println!("{} {url}: {e:#}", style("Failed").red());
+        }
+    }
+
+    if !failed.is_empty() {
+        bail!("failed to migrate {} of {total} databases", failed.len());
+    }
+
+    Ok(())
+}
+
 pub async fn revert(
-    migration_source: &str,
+    migration_sources: &[String],
+    migrations_table: &str,
+    schema_upgrade: bool,
     connect_opts: &ConnectOpts,
     dry_run: bool,
     ignore_missing: bool,
     target_version: Option<i64>,
 ) -> anyhow::Result<()> {
-    let migrator = Migrator::new(Path::new(migration_source)).await?;
+    let migrator = migrator(migration_sources).await?;
     if let Some(target_version) = target_version {
         if target_version != 0 && !migrator.version_exists(target_version) {
             bail!(MigrateError::VersionNotPresent(target_version));
@@ -380,14 +899,14 @@ pub async fn revert(
 
     let mut conn = crate::connect(connect_opts).await?;
 
-    conn.ensure_migrations_table().await?;
+    prepare_migrations_table(&mut conn, migrations_table, schema_upgrade).await?;
 
-    let version = conn.dirty_version().await?;
+    let version = conn.dirty_version(migrations_table).await?;
     if let Some(version) = version {
         bail!(MigrateError::Dirty(version));
     }
 
-    let applied_migrations = conn.list_applied_migrations().await?;
+    let applied_migrations = conn.list_applied_migrations(migrations_table).await?;
     validate_applied_migrations(&applied_migrations, &migrator, ignore_missing)?;
 
     let latest_version = applied_migrations
@@ -406,6 +925,10 @@ pub async fn revert(
         .map(|m| (m.version, m))
         .collect();
 
+    if let Some(target_version) = target_version {
+        print_revert_plan(&migrator, &applied_migrations, target_version, dry_run)?;
+    }
+
     let mut is_applied = false;
     for migration in migrator.iter().rev() {
         if !migration.migration_type.is_down_migration() {
@@ -414,31 +937,21 @@ pub async fn revert(
             continue;
         }
 
-        if applied_migrations.contains_key(&migration.version) {
+        if let Some(applied) = applied_migrations.get(&migration.version) {
             let skip =
                 target_version.is_some_and(|target_version| migration.version <= target_version);
 
             let elapsed = if dry_run || skip {
                 Duration::new(0, 0)
+            } else if applied.skipped {
+                // it was never run, so only its record is removed
+                conn.remove_applied_migration(migrations_table, migration.version)
+                    .await?;
+                Duration::new(0, 0)
             } else {
-                conn.revert(migration).await?
-            };
-            let text = if skip {
-                "Skipped"
-            } else if dry_run {
-                "Can apply"
-            } else {
-                "Applied"
+                conn.revert(migrations_table, migration).await?
             };
-
-            println!(
-                "{} {}/{} {} {}",
-                text,
-                style(migration.version).cyan(),
-                style(migration.migration_type.label()).green(),
-                migration.description,
-                style(format!("({elapsed:?})")).dim()
-            );
+            print_migration("migration_reverted", migration, skip, dry_run, elapsed);
 
             is_applied = true;
 
@@ -450,7 +963,11 @@ pub async fn revert(
         }
     }
     if !is_applied {
-        println!("No migrations available to revert");
+        output::print(
+            "nothing_to_revert",
+            json!({}),
+            "No migrations available to revert",
+        );
     }
 
     let _ = conn.close().await;
@@ -458,7 +975,298 @@ pub async fn revert(
     Ok(())
 }
 
-pub fn build_script(migration_source: &str, force: bool) -> anyhow::Result<()> {
+/// Print the migrations which will be reverted to reach `target_version`, before touching the
+/// database. Fails if any of them has no down migration, since reverting the others would leave
+/// the database at a version that never existed.
+fn print_revert_plan(
+    migrator: &Migrator,
+    applied_migrations: &HashMap<i64, AppliedMigration>,
+    target_version: i64,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    let down_migrations: HashMap<_, _> = migrator
+        .iter()
+        .filter(|migration| migration.migration_type.is_down_migration())
+        .map(|migration| (migration.version, migration))
+        .collect();
+
+    let mut versions: Vec<i64> = applied_migrations
+        .keys()
+        .copied()
+        .filter(|&version| version > target_version)
+        .collect();
+    versions.sort_unstable_by(|a, b| b.cmp(a));
+
+    if versions.is_empty() {
+        return Ok(());
+    }
+
+    let missing: Vec<i64> = versions
+        .iter()
+        .copied()
+        .filter(|version| !down_migrations.contains_key(version))
+        .collect();
+
+    if !missing.is_empty() {
+        bail!(
+            "cannot revert to version {target_version}: no down migration for {}",
+            missing
+                .iter()
+                .map(i64::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    let mut text = format!(
+        "{} {} migration(s) down to version {}:",
+        if dry_run { "Would revert" } else { "Reverting" },
+        versions.len(),
+        style(target_version).cyan()
+    );
+
+    for version in &versions {
+        let _ = write!(
+            text,
+            "\n  {} {}",
+            style(version).cyan(),
+            down_migrations[version].description
+        );
+    }
+
+    output::print(
+        "revert_plan",
+        json!({
+            "target_version": target_version,
+            "versions": versions,
+            "dry_run": dry_run,
+        }),
+        text,
+    );
+
+    Ok(())
+}
+
+pub async fn baseline(
+    migration_sources: &[String],
+    migrations_table: &str,
+    schema_upgrade: bool,
+    connect_opts: &ConnectOpts,
+    dry_run: bool,
+    ignore_missing: bool,
+    version: i64,
+) -> anyhow::Result<()> {
+    let migrator = migrator(migration_sources).await?;
+    if !migrator
+        .iter()
+        .any(|m| m.version == version && !m.migration_type.is_repeatable())
+    {
+        bail!(MigrateError::VersionNotPresent(version));
+    }
+
+    let mut conn = crate::connect(connect_opts).await?;
+
+    prepare_migrations_table(&mut conn, migrations_table, schema_upgrade).await?;
+
+    let dirty_version = conn.dirty_version(migrations_table).await?;
+    if let Some(dirty_version) = dirty_version {
+        bail!(MigrateError::Dirty(dirty_version));
+    }
+
+    let applied_migrations = conn.list_applied_migrations(migrations_table).await?;
+    validate_applied_migrations(&applied_migrations, &migrator, ignore_missing)?;
+
+    let applied_migrations: HashMap<_, _> = applied_migrations
+        .into_iter()
+        .map(|m| (m.version, m))
+        .collect();
+
+    let metadata = MigrationMetadata::current();
+    let mut is_recorded = false;
+
+    for migration in migrator.iter() {
+        if migration.migration_type.is_down_migration()
+            || migration.migration_type.is_repeatable()
+            || migration.version > version
+        {
+            continue;
+        }
+
+        if !migration.is_pending(applied_migrations.get(&migration.version))? {
+            continue;
+        }
+
+        if !dry_run {
+            conn.mark_applied(migrations_table, migration, &metadata)
+                .await?;
+        }
+
+        output::print(
+            "migration_baselined",
+            json!({
+                "version": migration.version,
+                "description": migration.description,
+                "type": migration.migration_type.label(),
+                "checksum": short_checksum(&migration.checksum),
+                "status": if dry_run { "dry_run" } else { "baselined" },
+            }),
+            format_args!(
+                "{} {}/{} {}",
+                if dry_run { "Can baseline" } else { "Baselined" },
+                style(migration.version).cyan(),
+                style(migration.migration_type.label()).green(),
+                migration.description
+            ),
+        );
+
+        is_recorded = true;
+    }
+
+    if !is_recorded {
+        output::print(
+            "nothing_to_baseline",
+            json!({}),
+            format_args!("All migrations up to {version} are already applied"),
+        );
+    }
+
+    let _ = conn.close().await;
+
+    Ok(())
+}
+
+pub async fn repair(
+    migration_sources: &[String],
+    migrations_table: &str,
+    schema_upgrade: bool,
+    connect_opts: &ConnectOpts,
+    dry_run: bool,
+    yes: bool,
+) -> anyhow::Result<()> {
+    let migrator = migrator(migration_sources).await?;
+    let mut conn = crate::connect(connect_opts).await?;
+
+    prepare_migrations_table(&mut conn, migrations_table, schema_upgrade).await?;
+
+    let version = conn.dirty_version(migrations_table).await?;
+    if let Some(version) = version {
+        bail!(MigrateError::Dirty(version));
+    }
+
+    let migrations: HashMap<_, _> = migrator
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .map(|migration| (migration.version, migration))
+        .collect();
+
+    let mut changed = Vec::new();
+    let mut missing = Vec::new();
+
+    for applied in conn.list_applied_migrations(migrations_table).await? {
+        match migrations.get(&applied.version) {
+            // a changed repeatable migration is run again instead
+            Some(migration) if migration.migration_type.is_repeatable() => {}
+            Some(migration) if migration.checksum != applied.checksum => {
+                changed.push((*migration, applied))
+            }
+            Some(_) => {}
+            None => missing.push(applied),
+        }
+    }
+
+    if changed.is_empty() && missing.is_empty() {
+        output::print(
+            "nothing_to_repair",
+            json!({}),
+            "Nothing to repair; the migrations table matches the migrations folder",
+        );
+        let _ = conn.close().await;
+        return Ok(());
+    }
+
+    if !missing.is_empty() && !dry_run && !yes {
+        let versions = missing
+            .iter()
+            .map(|applied| applied.version.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        if !io::stdin().is_terminal() {
+            bail!(
+                "refusing to remove applied migrations {versions} without confirmation; \
+                 stdin is not a terminal, so pass `-y`"
+            );
+        }
+
+        let prompt = format!(
+            "Remove applied migrations {} from {}? They no longer exist in {}",
+            style(&versions).cyan(),
+            migrations_table,
+            migration_sources.join(", ")
+        );
+
+        if !crate::database::ask_to_continue(prompt).await {
+            output::event("repair_cancelled", json!({ "versions": versions }));
+            let _ = conn.close().await;
+            return Ok(());
+        }
+    }
+
+    let status = if dry_run { "dry_run" } else { "repaired" };
+
+    for (migration, applied) in changed {
+        if !dry_run {
+            conn.update_checksum(migrations_table, migration.version, &migration.checksum)
+                .await?;
+        }
+
+        output::print(
+            "checksum_repaired",
+            json!({
+                "version": migration.version,
+                "description": migration.description,
+                "applied_checksum": short_checksum(&applied.checksum),
+                "local_checksum": short_checksum(&migration.checksum),
+                "status": status,
+            }),
+            format_args!(
+                "{} checksum of {}/{} {}",
+                if dry_run { "Would update" } else { "Updated" },
+                style(migration.version).cyan(),
+                style(migration.migration_type.label()).green(),
+                migration.description
+            ),
+        );
+    }
+
+    for applied in missing {
+        if !dry_run {
+            conn.remove_applied_migration(migrations_table, applied.version)
+                .await?;
+        }
+
+        output::print(
+            "missing_migration_removed",
+            json!({
+                "version": applied.version,
+                "applied_checksum": short_checksum(&applied.checksum),
+                "status": status,
+            }),
+            format_args!(
+                "{} {} from {}, which has no local migration",
+                if dry_run { "Would remove" } else { "Removed" },
+                style(applied.version).cyan(),
+                migrations_table
+            ),
+        );
+    }
+
+    let _ = conn.close().await;
+
+    Ok(())
+}
+
+pub fn build_script(migration_sources: &[String], force: bool) -> anyhow::Result<()> {
     anyhow::ensure!(
         Path::new("Cargo.toml").exists(),
         "must be run in a Cargo project root"
@@ -469,18 +1277,29 @@ pub fn build_script(migration_source: &str, force: bool) -> anyhow::Result<()> {
         "build.rs already exists; use --force to overwrite"
     );
 
+    let mut rerun_if_changed = String::new();
+    for source in migration_sources {
+        writeln!(
+            rerun_if_changed,
+            "    println!(\"cargo:rerun-if-changed={source}\");"
+        )?;
+    }
+
     let contents = format!(
         r#"// generated by `sqlx migrate build-script`
 fn main() {{
     // trigger recompilation when a new migration is added
-    println!("cargo:rerun-if-changed={migration_source}");
-}}
+{rerun_if_changed}}}
 "#,
     );
 
     fs::write("build.rs", contents)?;
 
-    println!("Created `build.rs`; be sure to check it into version control!");
+    output::print(
+        "build_script_created",
+        json!({ "path": "build.rs" }),
+        "Created `build.rs`; be sure to check it into version control!",
+    );
 
     Ok(())
 }
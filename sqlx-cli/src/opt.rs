@@ -1,8 +1,15 @@
+use std::fmt::{self, Debug, Formatter};
 use std::ops::{Deref, Not};
+use std::path::PathBuf;
 
-use clap::{Args, Parser};
+use anyhow::Context;
+use clap::{Args, Parser, ValueEnum};
 #[cfg(feature = "completions")]
 use clap_complete::Shell;
+use sqlx::DatabaseUrl;
+
+use crate::config;
+use crate::lint::LintRule;
 
 #[derive(Parser, Debug)]
 #[clap(version, about, author)]
@@ -11,10 +18,33 @@ pub struct Opt {
     #[clap(long)]
     pub no_dotenv: bool,
 
+    /// The format to print output in.
+    ///
+    /// With `json`, each message is printed to stdout as a JSON object on its own line,
+    /// with its kind given by the `event` key.
+    #[clap(long, value_enum, global = true, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// The profile of `sqlx.toml` to take the database URL from, e.g. `test` or `prod`.
+    ///
+    /// Settings are read from the `sqlx.toml` in the current directory or the nearest of its
+    /// parents, if there is one; the options given on the command line take precedence.
+    /// Defaults to `dev`.
+    #[clap(long, global = true, env = "SQLX_PROFILE")]
+    pub profile: Option<String>,
+
     #[clap(subcommand)]
     pub command: Command,
 }
 
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text.
+    Text,
+    /// Newline-delimited JSON objects.
+    Json,
+}
+
 #[derive(Parser, Debug)]
 pub enum Command {
     #[clap(alias = "db")]
@@ -46,6 +76,13 @@ pub enum Command {
         #[clap(long)]
         workspace: bool,
 
+        /// The number of crates `cargo check` compiles at once. Defaults to the number of CPUs.
+        ///
+        /// With `--workspace`, the workspace members which use SQLx are checked together in one
+        /// build, which expands their queries in parallel and shares the target directory.
+        #[clap(long, short = 'j', value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: Option<u16>,
+
         /// Arguments to be passed to `cargo rustc ...`.
         #[clap(last = true)]
         args: Vec<String>,
@@ -57,9 +94,105 @@ pub enum Command {
     #[clap(alias = "mig")]
     Migrate(MigrateOpt),
 
+    Seed(SeedOpt),
+
+    /// Export the data in the database as SQL `INSERT` statements, anonymizing it on the way.
+    ///
+    /// Values are exported as string literals, which the database converts to the column type
+    /// when the dump is loaded. The `_sqlx_migrations` table is skipped unless named with `--table`,
+    /// so the dump can be loaded into a database which the migrations have been run against.
+    ///
+    /// The rules given to `--anonymize` are a TOML file with a table per database table, mapping
+    /// column names to `"null"`, `"hash"`, `"email"`, `"name"`, `"phone"`,
+    /// `{ constant = "..." }` or `{ mask = "*", keep_last = 4 }`.
+    Export {
+        /// Path to a TOML file of anonymization rules.
+        #[clap(long)]
+        anonymize: Option<PathBuf>,
+
+        /// Secret key for the hashes fake values are derived from. Defaults to a random key, so
+        /// fake values only match within one export; pass the same key to keep them stable
+        /// across exports.
+        #[clap(long, env = "SQLX_ANONYMIZE_KEY", hide_env_values = true)]
+        anonymize_key: Option<String>,
+
+        /// Export only this table. May be given more than once. Defaults to all tables.
+        #[clap(long = "table")]
+        tables: Vec<String>,
+
+        /// Path of the file to write the dump to. Defaults to stdout.
+        #[clap(long, short)]
+        output: Option<PathBuf>,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+    },
+
+    /// Check migration files for patterns which are risky to run against a live database.
+    ///
+    /// Exits with 1 if any problems with a severity of `deny` are found, so it can be run in CI.
+    /// By default, `missing-down` is denied and the other rules warn.
+    Lint {
+        #[clap(flatten)]
+        source: Source,
+
+        /// Don't report problems found by this rule. May be given more than once.
+        #[clap(long, value_enum, value_name = "RULE")]
+        allow: Vec<LintRule>,
+
+        /// Report problems found by this rule as warnings. May be given more than once.
+        #[clap(long, value_enum, value_name = "RULE")]
+        warn: Vec<LintRule>,
+
+        /// Report problems found by this rule as errors. May be given more than once.
+        #[clap(long, value_enum, value_name = "RULE")]
+        deny: Vec<LintRule>,
+
+        /// Used only to skip rules which don't apply to the database; no connection is made.
+        /// Without it, the rules for PostgreSQL are applied.
+        #[clap(long, short = 'D', env)]
+        database_url: Option<String>,
+    },
+
+    /// Re-execute the statements in a recording made with `PgStatementRecorder`.
+    ///
+    /// Statements are executed one at a time, in the order they were recorded, on a single
+    /// connection. Only PostgreSQL is supported. Parameters that were redacted are bound as `NULL`.
+    Replay {
+        /// Path to the recording.
+        file: PathBuf,
+
+        /// Report statements that fail and carry on, instead of stopping at the first one.
+        #[clap(long)]
+        keep_going: bool,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+    },
+
+    /// Diagnose common problems with the environment SQLx runs in.
+    ///
+    /// Checks that `DATABASE_URL` is set and reachable, that the TLS settings and server version
+    /// are supported, that migrations are up to date, that the offline query data in `.sqlx`
+    /// is fresh and that the `sqlx` features enabled in the current crate match the database.
+    ///
+    /// Exits with 1 if any problems are found. Nothing in the database is modified.
+    Doctor {
+        #[clap(flatten)]
+        source: Source,
+
+        #[clap(flatten)]
+        migrations_table: MigrationsTable,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+    },
+
     #[cfg(feature = "completions")]
     /// Generate shell completions for the specified shell
-    Completions { shell: Shell },
+    Completions {
+        shell: Shell,
+    },
 }
 
 /// Group of commands for creating and dropping your database.
@@ -98,6 +231,9 @@ pub enum DatabaseCommand {
         #[clap(flatten)]
         source: Source,
 
+        #[clap(flatten)]
+        migrations_table: MigrationsTable,
+
         #[clap(flatten)]
         connect_opts: ConnectOpts,
 
@@ -111,6 +247,132 @@ pub enum DatabaseCommand {
         #[clap(flatten)]
         source: Source,
 
+        #[clap(flatten)]
+        migrations_table: MigrationsTable,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+
+        /// Also run the seed scripts of `--seed-set` which have not been run yet, after the
+        /// migrations.
+        #[clap(long)]
+        seed: bool,
+
+        #[clap(flatten)]
+        seeds: Seeds,
+    },
+
+    /// Save or restore snapshots of the database specified in your DATABASE_URL.
+    Snapshot(SnapshotOpt),
+
+    /// Write the database specified in your DATABASE_URL to stdout as a SQL script.
+    ///
+    /// PostgreSQL and MySQL databases are dumped with `pg_dump` and `mysqldump`, which must be
+    /// on your PATH; SQLite databases are dumped by sqlx-cli itself.
+    Dump {
+        /// Only dump the schema, along with the rows of the migrations table, so that the
+        /// migrations it was created with are not run again after loading it.
+        #[clap(long)]
+        schema_only: bool,
+
+        #[clap(flatten)]
+        migrations_table: MigrationsTable,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+    },
+
+    /// Run a script written by `database dump` against the database specified in your
+    /// DATABASE_URL, creating the database if it does not exist.
+    ///
+    /// PostgreSQL and MySQL scripts are run with `psql` and `mysql`, which must be on your PATH.
+    Load {
+        /// The script to load.
+        file: PathBuf,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+    },
+}
+
+/// Group of commands for checkpointing a development database.
+///
+/// PostgreSQL snapshots are kept as databases named `<DATABASE>__snapshot_<NAME>`, created with
+/// the database as their template. SQLite snapshots are copies of the database file and MySQL
+/// snapshots are dumps made with `mysqldump`, which are kept in `--snapshot-dir`.
+#[derive(Parser, Debug)]
+pub struct SnapshotOpt {
+    #[clap(subcommand)]
+    pub command: SnapshotCommand,
+}
+
+#[derive(Parser, Debug)]
+pub enum SnapshotCommand {
+    /// Save a snapshot of the database, replacing any existing snapshot with the same name.
+    ///
+    /// PostgreSQL cannot copy a database while other sessions are connected to it.
+    Save {
+        /// Name of the snapshot.
+        name: String,
+
+        #[clap(flatten)]
+        snapshot_dir: SnapshotDir,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+    },
+
+    /// Replace the database with a snapshot saved earlier.
+    Restore {
+        /// Name of the snapshot.
+        name: String,
+
+        #[clap(flatten)]
+        confirmation: Confirmation,
+
+        #[clap(flatten)]
+        snapshot_dir: SnapshotDir,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+
+        /// PostgreSQL only: force drops the database before restoring it.
+        #[clap(long, short, default_value = "false")]
+        force: bool,
+    },
+}
+
+/// Group of commands for creating and running seed scripts.
+///
+/// Seed scripts fill the database with data, e.g. for development or tests. They are SQL files
+/// named like migrations, with a folder for each set of seeds (e.g. `seeds/dev` and
+/// `seeds/test`), and are tracked in their own table, so each is run once, independently of the
+/// migrations.
+#[derive(Parser, Debug)]
+pub struct SeedOpt {
+    #[clap(subcommand)]
+    pub command: SeedCommand,
+}
+
+#[derive(Parser, Debug)]
+pub enum SeedCommand {
+    /// Create a new seed script with the given description.
+    New {
+        description: String,
+
+        #[clap(flatten)]
+        seeds: Seeds,
+    },
+
+    /// Run the seed scripts which have not been run yet.
+    Run {
+        #[clap(flatten)]
+        seeds: Seeds,
+
+        /// Print the SQL of the seed scripts to be run without touching the database.
+        #[clap(long)]
+        dry_run: bool,
+
         #[clap(flatten)]
         connect_opts: ConnectOpts,
     },
@@ -159,6 +421,11 @@ pub enum MigrateCommand {
         /// If set, use sequential versioning for the new migration. Conflicts with `--timestamp`.
         #[clap(short, long, conflicts_with = "timestamp")]
         sequential: bool,
+
+        /// If set, create a repeatable migration, `R__<DESCRIPTION>.sql`, which is applied after
+        /// all other migrations and again whenever it changes.
+        #[clap(long, conflicts_with_all = ["reversible", "timestamp", "sequential"])]
+        repeatable: bool,
     },
 
     /// Run all pending migrations.
@@ -166,7 +433,11 @@ pub enum MigrateCommand {
         #[clap(flatten)]
         source: Source,
 
-        /// List all the migrations to be run without applying
+        #[clap(flatten)]
+        migrations_table: MigrationsTable,
+
+        /// Print the SQL of the migrations to be run, including the statements recording them
+        /// in the migrations table, without touching the database
         #[clap(long)]
         dry_run: bool,
 
@@ -176,10 +447,22 @@ pub enum MigrateCommand {
         #[clap(flatten)]
         connect_opts: ConnectOpts,
 
+        #[clap(flatten)]
+        fan_out: FanOut,
+
+        #[clap(flatten)]
+        skip: SkipMigrations,
+
         /// Apply migrations up to the specified version. If unspecified, apply all
-        /// pending migrations. If already at the target version, then no-op.
+        /// pending migrations. If already at the target version, then no-op. Fails if
+        /// the version does not exist or is older than the latest applied migration.
         #[clap(long)]
         target_version: Option<i64>,
+
+        /// Succeed without applying anything if a migration newer than `--target-version`
+        /// was already applied, instead of failing.
+        #[clap(long, requires = "target_version")]
+        allow_noop: bool,
     },
 
     /// Revert the latest migration with a down file.
@@ -187,6 +470,9 @@ pub enum MigrateCommand {
         #[clap(flatten)]
         source: Source,
 
+        #[clap(flatten)]
+        migrations_table: MigrationsTable,
+
         /// List the migration to be reverted without applying
         #[clap(long)]
         dry_run: bool,
@@ -199,7 +485,8 @@ pub enum MigrateCommand {
 
         /// Revert migrations down to the specified version. If unspecified, revert
         /// only the last migration. Set to 0 to revert all migrations. If already
-        /// at the target version, then no-op.
+        /// at the target version, then no-op. Fails without reverting anything if
+        /// any of the migrations has no down migration.
         #[clap(long)]
         target_version: Option<i64>,
     },
@@ -209,6 +496,78 @@ pub enum MigrateCommand {
         #[clap(flatten)]
         source: Source,
 
+        #[clap(flatten)]
+        migrations_table: MigrationsTable,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+
+        /// Exit with 1 if migrations are pending, or with 2 if applied migrations were
+        /// changed or their files deleted, instead of 0.
+        #[clap(long)]
+        exit_code: bool,
+
+        /// Only list the migrations in these states, separated by commas. The summary and
+        /// `--exit-code` still account for all migrations.
+        #[clap(long, value_enum, value_delimiter = ',')]
+        filter: Vec<InfoFilter>,
+
+        /// Only list the pending migrations; short for `--filter pending`.
+        #[clap(long, conflicts_with = "filter")]
+        pending_only: bool,
+
+        #[clap(flatten)]
+        skip: SkipMigrations,
+    },
+
+    /// Record all migrations up to the given version as applied, without running them.
+    ///
+    /// Use this to adopt migrations on a database which already has the schema they create,
+    /// so that `migrate run` only applies the newer ones. Repeatable migrations are not
+    /// recorded, and are run by the next `migrate run`.
+    Baseline {
+        /// The version of the last migration already reflected in the database.
+        version: i64,
+
+        #[clap(flatten)]
+        source: Source,
+
+        #[clap(flatten)]
+        migrations_table: MigrationsTable,
+
+        /// List the migrations to be recorded without recording them.
+        #[clap(long)]
+        dry_run: bool,
+
+        #[clap(flatten)]
+        ignore_missing: IgnoreMissing,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+    },
+
+    /// Bring the migrations table back in line with the migrations folder.
+    ///
+    /// Applied migrations whose files have been edited since are recorded with the checksum of
+    /// the local file, and applied migrations whose files have been deleted are removed from the
+    /// table, after asking for confirmation. No migrations are run or reverted, so only repair
+    /// the table after making sure the database matches the migrations folder.
+    Repair {
+        #[clap(flatten)]
+        source: Source,
+
+        #[clap(flatten)]
+        migrations_table: MigrationsTable,
+
+        /// List the changes to the migrations table without making them.
+        #[clap(long)]
+        dry_run: bool,
+
+        /// Automatic confirmation. Without this option, you will be prompted before removing
+        /// applied migrations which no longer exist in the migrations folder.
+        #[clap(short)]
+        yes: bool,
+
         #[clap(flatten)]
         connect_opts: ConnectOpts,
     },
@@ -230,29 +589,107 @@ pub enum MigrateCommand {
 #[derive(Args, Debug)]
 pub struct Source {
     /// Path to folder containing migrations.
-    #[clap(long, default_value = "migrations")]
-    source: String,
+    ///
+    /// May be given more than once, e.g. for migrations shared by several applications, to
+    /// apply the migrations of all of the folders in order of version; a version may only be
+    /// used in one of them. New migrations are added to the last one.
+    ///
+    /// [default: `source` in the `[migrate]` table of `sqlx.toml`, or `migrations`]
+    #[clap(long = "source")]
+    sources: Vec<String>,
 }
 
 impl Deref for Source {
+    type Target = [String];
+
+    fn deref(&self) -> &Self::Target {
+        if self.sources.is_empty() {
+            std::slice::from_ref(&config::get().migrations_source)
+        } else {
+            &self.sources
+        }
+    }
+}
+
+/// Arguments for a set of seed scripts.
+#[derive(Args, Debug)]
+pub struct Seeds {
+    /// Path to the folder containing a folder of seed scripts for each set.
+    #[clap(long, default_value = "seeds")]
+    pub seeds_dir: PathBuf,
+
+    /// The set of seed scripts to use, e.g. `dev` or `test`.
+    #[clap(long, default_value = "dev")]
+    pub seed_set: String,
+
+    /// Prefix of the name of the tables tracking the seed scripts which have been run, with one
+    /// table for each set (e.g. `_sqlx_seeds_dev`).
+    #[clap(long, default_value = "_sqlx_seeds")]
+    pub seeds_table: String,
+}
+
+impl Seeds {
+    /// The folder containing the seed scripts of the set.
+    pub fn dir(&self) -> PathBuf {
+        self.seeds_dir.join(&self.seed_set)
+    }
+
+    /// The table tracking the seed scripts of the set which have been run.
+    ///
+    /// Each set has its own table, so that the scripts of one set are not taken for missing
+    /// scripts of another.
+    pub fn table(&self) -> String {
+        format!("{}_{}", self.seeds_table, self.seed_set)
+    }
+}
+
+/// Argument for the table tracking applied migrations.
+#[derive(Args, Debug)]
+pub struct MigrationsTable {
+    /// Name of the table tracking applied migrations, optionally qualified with a schema
+    /// (e.g. `myschema.schema_migrations`). The schema must already exist.
+    ///
+    /// [default: `table` in the `[migrate]` table of `sqlx.toml`, or `_sqlx_migrations`]
+    #[clap(long)]
+    migrations_table: Option<String>,
+
+    /// Fail instead of upgrading the migrations table if it was created by an older version
+    /// of SQLx.
+    #[clap(long)]
+    no_schema_upgrade: bool,
+}
+
+impl MigrationsTable {
+    /// Whether the migrations table may be upgraded to the current schema version.
+    pub fn schema_upgrade(&self) -> bool {
+        !self.no_schema_upgrade
+    }
+}
+
+impl Deref for MigrationsTable {
     type Target = String;
 
     fn deref(&self) -> &Self::Target {
-        &self.source
+        self.migrations_table
+            .as_ref()
+            .unwrap_or(&config::get().migrations_table)
     }
 }
 
 /// Argument for the database URL.
-#[derive(Args, Debug)]
+#[derive(Args, Clone)]
 pub struct ConnectOpts {
-    /// Location of the DB, by default will be read from the DATABASE_URL env var or `.env` files.
+    /// Location of the DB, by default will be read from the DATABASE_URL env var, `.env` files
+    /// or `sqlx.toml`.
     #[clap(long, short = 'D', env)]
     pub database_url: Option<String>,
 
     /// The maximum time, in seconds, to try connecting to the database server before
     /// returning an error.
-    #[clap(long, default_value = "10")]
-    pub connect_timeout: u64,
+    ///
+    /// [default: `connect-timeout` in the `[cli]` table of `sqlx.toml`, or 10]
+    #[clap(long)]
+    pub connect_timeout: Option<u64>,
 
     /// Set whether or not to create SQLite databases in Write-Ahead Log (WAL) mode:
     /// https://www.sqlite.org/wal.html
@@ -267,31 +704,171 @@ pub struct ConnectOpts {
     pub sqlite_create_db_wal: bool,
 }
 
+impl Debug for ConnectOpts {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // the URL may contain a password
+        let database_url =
+            self.database_url
+                .as_deref()
+                .map(|url| match url.parse::<DatabaseUrl>() {
+                    Ok(url) => url.redacted_url().to_string(),
+                    Err(_) => String::from("<invalid URL>"),
+                });
+
+        let mut f = f.debug_struct("ConnectOpts");
+        f.field("database_url", &database_url)
+            .field("connect_timeout", &self.connect_timeout);
+        #[cfg(feature = "_sqlite")]
+        f.field("sqlite_create_db_wal", &self.sqlite_create_db_wal);
+        f.finish()
+    }
+}
+
 impl ConnectOpts {
-    /// Require a database URL to be provided, otherwise
+    /// The database URL given on the command line or in the environment, or else by the
+    /// selected profile of `sqlx.toml`.
+    pub fn database_url(&self) -> Option<&str> {
+        self.database_url
+            .as_deref()
+            .or(config::get().database_url.as_deref())
+    }
+
+    /// Require a valid database URL to be provided, otherwise
     /// return an error.
-    pub fn required_db_url(&self) -> anyhow::Result<&str> {
-        self.database_url.as_deref().ok_or_else(
+    pub fn required_db_url(&self) -> anyhow::Result<DatabaseUrl> {
+        let url = self.database_url().ok_or_else(
             || anyhow::anyhow!(
-                "the `--database-url` option or the `DATABASE_URL` environment variable must be provided"
+                "the `--database-url` option, the `DATABASE_URL` environment variable or `database-url` in `sqlx.toml` must be provided"
             )
-        )
+        )?;
+
+        Ok(url.parse()?)
+    }
+
+    pub fn connect_timeout(&self) -> u64 {
+        self.connect_timeout
+            .unwrap_or(config::get().connect_timeout)
+    }
+}
+
+/// Arguments for running a command against several databases.
+#[derive(Args, Debug)]
+pub struct FanOut {
+    /// Read the URLs of the databases to run against from a file, one per line,
+    /// instead of `--database-url`. Empty lines and lines starting with `#` are ignored.
+    #[clap(long)]
+    pub database_urls_file: Option<PathBuf>,
+
+    /// Run against the databases named by all environment variables matching
+    /// this glob pattern (e.g. `SHARD_*_URL`), in order of the variable names,
+    /// instead of `--database-url`.
+    #[clap(long, value_name = "PATTERN")]
+    pub database_url_vars: Option<String>,
+
+    /// The number of databases to run against concurrently.
+    #[clap(long, default_value = "1", value_parser = clap::value_parser!(u16).range(1..))]
+    pub jobs: u16,
+}
+
+impl FanOut {
+    /// Collect the database URLs to run against, or `None` if neither
+    /// `--database-urls-file` nor `--database-url-vars` were provided.
+    pub fn database_urls(&self) -> anyhow::Result<Option<Vec<DatabaseUrl>>> {
+        if self.database_urls_file.is_none() && self.database_url_vars.is_none() {
+            return Ok(None);
+        }
+
+        let mut urls = Vec::new();
+
+        if let Some(path) = &self.database_urls_file {
+            let contents = std::fs::read_to_string(path)
+                .with_context(|| format!("failed to read database URLs from {}", path.display()))?;
+
+            for (i, line) in contents.lines().enumerate() {
+                let line = line.trim();
+
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+
+                urls.push(
+                    line.parse()
+                        .with_context(|| format!("line {} of {}", i + 1, path.display()))?,
+                );
+            }
+        }
+
+        if let Some(pattern) = &self.database_url_vars {
+            let pattern = glob::Pattern::new(pattern)
+                .with_context(|| format!("invalid pattern for --database-url-vars: {pattern}"))?;
+
+            let mut vars: Vec<(String, String)> = std::env::vars()
+                .filter(|(name, _)| pattern.matches(name))
+                .collect();
+
+            vars.sort();
+
+            for (name, url) in vars {
+                urls.push(url.parse().with_context(|| format!("${name}"))?);
+            }
+        }
+
+        if urls.is_empty() {
+            anyhow::bail!("no database URLs were found to run against");
+        }
+
+        Ok(Some(urls))
+    }
+}
+
+/// Argument for the location of file-based snapshots.
+#[derive(Args, Debug)]
+pub struct SnapshotDir {
+    /// Path to the folder containing SQLite and MySQL snapshots.
+    #[clap(long, default_value = ".sqlx-snapshots")]
+    snapshot_dir: PathBuf,
+}
+
+impl Deref for SnapshotDir {
+    type Target = PathBuf;
+
+    fn deref(&self) -> &Self::Target {
+        &self.snapshot_dir
     }
 }
 
 /// Argument for automatic confirmation.
-#[derive(Args, Copy, Clone, Debug)]
+#[derive(Args, Clone, Debug)]
 pub struct Confirmation {
     /// Automatic confirmation. Without this option, you will be prompted before dropping
     /// your database.
     #[clap(short)]
     pub yes: bool,
+
+    /// Confirm dropping the database without a prompt by passing its name.
+    ///
+    /// When stdin is not a terminal and this option is not set, the name is read from stdin
+    /// instead. Ignored with `-y`.
+    #[clap(long, env = "SQLX_CONFIRM_TOKEN")]
+    pub confirm_token: Option<String>,
+}
+
+/// A state of the migrations listed by `migrate info --filter`.
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InfoFilter {
+    /// Migrations yet to be applied, including changed repeatable migrations.
+    Pending,
+    /// Migrations which were applied and are not pending.
+    Applied,
+    /// Applied migrations which were changed since, or whose files were deleted.
+    Modified,
 }
 
 /// Argument for ignoring applied migrations that were not resolved.
 #[derive(Args, Copy, Clone, Debug)]
 pub struct IgnoreMissing {
-    /// Ignore applied migrations that are missing in the resolved migrations
+    /// Ignore applied migrations that are missing in the resolved migrations. Also enabled by
+    /// `ignore-missing` in the `[migrate]` table of `sqlx.toml`.
     #[clap(long)]
     ignore_missing: bool,
 }
@@ -300,7 +877,32 @@ impl Deref for IgnoreMissing {
     type Target = bool;
 
     fn deref(&self) -> &Self::Target {
-        &self.ignore_missing
+        if self.ignore_missing || config::get().ignore_missing {
+            &true
+        } else {
+            &false
+        }
+    }
+}
+
+/// Argument for migrations which are intentionally not applied.
+#[derive(Args, Clone, Debug)]
+pub struct SkipMigrations {
+    /// The version of a migration which is intentionally not applied to this database, and
+    /// recorded as skipped in the migrations table instead; may be repeated. Added to `skip` in
+    /// the `[migrate]` table of `sqlx.toml` and `skip-migrations` in the selected profile.
+    #[clap(long = "skip", value_name = "VERSION")]
+    skip: Vec<i64>,
+}
+
+impl SkipMigrations {
+    /// The versions given with `--skip` and in `sqlx.toml`, in order.
+    pub fn versions(&self) -> Vec<i64> {
+        let mut versions = self.skip.clone();
+        versions.extend(&config::get().skip_migrations);
+        versions.sort_unstable();
+        versions.dedup();
+        versions
     }
 }
 
@@ -308,6 +910,6 @@ impl Not for IgnoreMissing {
     type Output = bool;
 
     fn not(self) -> Self::Output {
-        !self.ignore_missing
+        !*self
     }
 }
@@ -0,0 +1,43 @@
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+use serde_json::{Map, Value};
+
+use crate::opt::OutputFormat;
+
+static FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+
+/// Set the output format for the rest of the process. Only the first call has any effect.
+pub fn set_format(format: OutputFormat) {
+    let _ = FORMAT.set(format);
+}
+
+pub fn is_json() -> bool {
+    FORMAT.get() == Some(&OutputFormat::Json)
+}
+
+/// Print `text`, or with `--format json`, a JSON object on its own line with `"event": name`
+/// and the entries of `fields`.
+pub fn print(name: &str, fields: Value, text: impl Display) {
+    if is_json() {
+        event(name, fields);
+    } else {
+        println!("{text}");
+    }
+}
+
+/// Print a JSON event with `--format json`; nothing is printed otherwise.
+pub fn event(name: &str, fields: Value) {
+    if !is_json() {
+        return;
+    }
+
+    let mut object = Map::new();
+    object.insert("event".into(), name.into());
+
+    if let Value::Object(fields) = fields {
+        object.extend(fields);
+    }
+
+    println!("{}", Value::Object(object));
+}
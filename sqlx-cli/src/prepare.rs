@@ -6,36 +6,85 @@ use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use anyhow::{bail, Context};
+use cargo_metadata::PackageId as MetadataId;
 use console::style;
-use sqlx::Connection;
+use serde_json::json;
+use sqlx::config::OfflineDatabase;
+use sqlx::{Connection, DatabaseKind, DatabaseUrl};
 
-use crate::metadata::{manifest_dir, Metadata};
+use crate::metadata::{manifest_dir, Metadata, Package};
 use crate::opt::ConnectOpts;
+use crate::output;
 
 pub struct PrepareCtx {
     pub workspace: bool,
     pub all: bool,
+    pub jobs: Option<u16>,
     pub cargo: OsString,
     pub cargo_args: Vec<String>,
     pub metadata: Metadata,
     pub connect_opts: ConnectOpts,
+    /// The database the query data is prepared for, if `offline.per-driver` is set.
+    pub database: Option<DatabaseKind>,
+    /// The Cargo features enabling the queries of the database.
+    pub features: Vec<String>,
 }
 
 impl PrepareCtx {
     /// Path to the directory where cached queries should be placed.
     fn prepare_dir(&self) -> anyhow::Result<PathBuf> {
-        if self.workspace {
+        if let Some(dir) = &crate::config::get().offline_dir {
+            Ok(dir.clone())
+        } else if self.workspace {
             Ok(self.metadata.workspace_root().join(".sqlx"))
         } else {
             Ok(manifest_dir(&self.cargo)?.join(".sqlx"))
         }
     }
+
+    /// The directory of the query data written by this run in `dir`: the subdirectory of the
+    /// database if `offline.per-driver` is set in `sqlx.toml`, so that the data of other
+    /// databases is kept, or else `dir` itself.
+    fn query_dir(&self, dir: &Path) -> PathBuf {
+        match self.database {
+            Some(database) => dir.join(database.as_str()),
+            None => dir.to_path_buf(),
+        }
+    }
+
+    /// Arguments for `cargo check`, before the ones given by the user.
+    ///
+    /// With `--workspace`, the members which depend on `sqlx-macros` are selected with `-p`,
+    /// unless the user selected packages themselves, so they are all checked in one build.
+    fn check_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        if self.workspace && !selects_packages(&self.cargo_args) {
+            for package in workspace_sqlx_macros_dependents(&self.metadata) {
+                args.push("-p".to_owned());
+                args.push(package.name().to_owned());
+            }
+        }
+
+        if let Some(jobs) = self.jobs {
+            args.push("--jobs".to_owned());
+            args.push(jobs.to_string());
+        }
+
+        if !self.features.is_empty() {
+            args.push("--features".to_owned());
+            args.push(self.features.join(","));
+        }
+
+        args
+    }
 }
 
 pub async fn run(
     check: bool,
     all: bool,
     workspace: bool,
+    jobs: Option<u16>,
     connect_opts: ConnectOpts,
     cargo_args: Vec<String>,
 ) -> anyhow::Result<()> {
@@ -49,24 +98,62 @@ hint: This command only works in the manifest directory of a Cargo package or wo
     );
 
     let metadata: Metadata = Metadata::from_current_directory(&cargo)?;
-    let ctx = PrepareCtx {
+    let config = crate::config::get();
+    let default_url = connect_opts.database_url().map(String::from);
+    let mut ctx = PrepareCtx {
         workspace,
         all,
+        jobs,
         cargo,
         cargo_args,
         metadata,
         connect_opts,
+        database: None,
+        features: Vec::new(),
     };
 
+    if config.offline_databases.is_empty() {
+        if config.offline_per_driver {
+            let url = default_url.as_deref().context(
+                "`offline.per-driver` is set in sqlx.toml, so a database URL is required to tell \
+                 which database the query data is for",
+            )?;
+            ctx.database = Some(database_kind(url)?);
+        }
+
+        return prepare_or_check(&ctx, check).await;
+    }
+
+    // Prepare (or check) the queries for each database in `offline.databases` in turn.
+    for (name, database) in &config.offline_databases {
+        let (kind, url) = resolve_database(name, database, default_url.as_deref())?;
+
+        output::print(
+            "prepare_database",
+            json!({ "database": kind.as_str() }),
+            format_args!("preparing queries for {}", kind.as_str()),
+        );
+
+        ctx.database = Some(kind);
+        ctx.features.clone_from(&database.features);
+        ctx.connect_opts.database_url = Some(url);
+
+        prepare_or_check(&ctx, check).await?;
+    }
+
+    Ok(())
+}
+
+async fn prepare_or_check(ctx: &PrepareCtx, check: bool) -> anyhow::Result<()> {
     if check {
-        prepare_check(&ctx).await
+        prepare_check(ctx).await
     } else {
-        prepare(&ctx).await
+        prepare(ctx).await
     }
 }
 
 async fn prepare(ctx: &PrepareCtx) -> anyhow::Result<()> {
-    if ctx.connect_opts.database_url.is_some() {
+    if ctx.connect_opts.database_url().is_some() {
         check_backend(&ctx.connect_opts).await?;
     }
 
@@ -74,27 +161,31 @@ async fn prepare(ctx: &PrepareCtx) -> anyhow::Result<()> {
     run_prepare_step(ctx, &prepare_dir)?;
 
     // Warn if no queries were generated. Glob since the directory may contain unrelated files.
-    if glob_query_files(prepare_dir)?.is_empty() {
-        println!("{} no queries found", style("warning:").yellow());
+    if glob_query_files_of(&prepare_dir, ctx.database)?.is_empty() {
+        warning("no queries found");
         return Ok(());
     }
 
-    if ctx.workspace {
-        println!(
-            "query data written to .sqlx in the workspace root; \
-             please check this into version control"
-        );
+    let query_dir = ctx.query_dir(&prepare_dir);
+    let sqlx_dir = ctx.query_dir(Path::new(".sqlx"));
+    let location = if crate::config::get().offline_dir.is_some() {
+        query_dir.display().to_string()
+    } else if ctx.workspace {
+        format!("{} in the workspace root", sqlx_dir.display())
     } else {
-        println!(
-            "query data written to .sqlx in the current directory; \
-             please check this into version control"
-        );
-    }
+        format!("{} in the current directory", sqlx_dir.display())
+    };
+
+    output::print(
+        "query_data_written",
+        json!({ "path": query_dir }),
+        format_args!("query data written to {location}; please check this into version control"),
+    );
     Ok(())
 }
 
 async fn prepare_check(ctx: &PrepareCtx) -> anyhow::Result<()> {
-    if ctx.connect_opts.database_url.is_some() {
+    if ctx.connect_opts.database_url().is_some() {
         check_backend(&ctx.connect_opts).await?;
     }
 
@@ -105,11 +196,11 @@ async fn prepare_check(ctx: &PrepareCtx) -> anyhow::Result<()> {
     run_prepare_step(ctx, &cache_dir)?;
 
     // Compare .sqlx to cache.
-    let prepare_filenames: HashSet<String> = glob_query_files(&prepare_dir)?
+    let prepare_filenames: HashSet<String> = glob_query_files_of(&prepare_dir, ctx.database)?
         .into_iter()
         .filter_map(|path| path.file_name().map(|f| f.to_string_lossy().into_owned()))
         .collect();
-    let cache_filenames: HashSet<String> = glob_query_files(&cache_dir)?
+    let cache_filenames: HashSet<String> = glob_query_files_of(&cache_dir, ctx.database)?
         .into_iter()
         .filter_map(|path| path.file_name().map(|f| f.to_string_lossy().into_owned()))
         .collect();
@@ -128,14 +219,13 @@ async fn prepare_check(ctx: &PrepareCtx) -> anyhow::Result<()> {
         .next()
         .is_some()
     {
-        println!(
-            "{} potentially unused queries found in .sqlx; you may want to re-run sqlx prepare",
-            style("warning:").yellow()
-        );
+        warning("potentially unused queries found in .sqlx; you may want to re-run sqlx prepare");
     }
 
     // Compare file contents as JSON to ignore superficial differences.
     // Everything in cache checked to be in .sqlx already.
+    let prepare_dir = ctx.query_dir(&prepare_dir);
+    let cache_dir = ctx.query_dir(&cache_dir);
     for filename in cache_filenames {
         let prepare_json = load_json_file(prepare_dir.join(&filename))?;
         let cache_json = load_json_file(cache_dir.join(&filename))?;
@@ -144,9 +234,19 @@ async fn prepare_check(ctx: &PrepareCtx) -> anyhow::Result<()> {
         }
     }
 
+    output::event("prepare_check_passed", json!({ "path": prepare_dir }));
+
     Ok(())
 }
 
+fn warning(message: &str) {
+    output::print(
+        "warning",
+        json!({ "message": message }),
+        format_args!("{} {message}", style("warning:").yellow()),
+    );
+}
+
 fn run_prepare_step(ctx: &PrepareCtx, cache_dir: &Path) -> anyhow::Result<()> {
     // Create and/or clean the directory.
     fs::create_dir_all(cache_dir).context(format!(
@@ -161,8 +261,10 @@ fn run_prepare_step(ctx: &PrepareCtx, cache_dir: &Path) -> anyhow::Result<()> {
         cache_dir
     ))?;
 
-    // Only delete sqlx-*.json files to avoid accidentally deleting any user data.
-    for query_file in glob_query_files(cache_dir).context("Failed to read query cache files")? {
+    // Only delete query-*.json files to avoid accidentally deleting any user data.
+    for query_file in
+        glob_query_files_of(cache_dir, ctx.database).context("Failed to read query cache files")?
+    {
         fs::remove_file(&query_file)
             .with_context(|| format!("Failed to delete query file: {}", query_file.display()))?;
     }
@@ -176,12 +278,13 @@ fn run_prepare_step(ctx: &PrepareCtx, cache_dir: &Path) -> anyhow::Result<()> {
         let mut check_command = Command::new(&ctx.cargo);
         check_command
             .arg("check")
+            .args(ctx.check_args())
             .args(&ctx.cargo_args)
             .env("SQLX_TMP", tmp_dir)
             .env("SQLX_OFFLINE", "false")
             .env("SQLX_OFFLINE_DIR", cache_dir);
 
-        if let Some(database_url) = &ctx.connect_opts.database_url {
+        if let Some(database_url) = ctx.connect_opts.database_url() {
             check_command.env("DATABASE_URL", database_url);
         }
 
@@ -236,9 +339,13 @@ fn setup_minimal_project_recompile(
     };
 
     if let Err(err) = minimal_project_clean(&cargo, recompile_action) {
-        println!(
-            "Failed minimal recompile setup. Cleaning entire project. Err: {}",
-            err
+        output::print(
+            "full_clean",
+            json!({ "reason": err.to_string() }),
+            format_args!(
+                "Failed minimal recompile setup. Cleaning entire project. Err: {}",
+                err
+            ),
         );
         let clean_status = Command::new(&cargo).arg("clean").status()?;
         if !clean_status.success() {
@@ -279,8 +386,8 @@ fn minimal_project_clean(
     Ok(())
 }
 
-fn minimal_project_recompile_action(metadata: &Metadata, all: bool) -> ProjectRecompileAction {
-    // Get all the packages that depend on `sqlx-macros`
+/// Get all the packages that depend on `sqlx-macros`, directly or transitively.
+fn sqlx_macros_dependents(metadata: &Metadata) -> BTreeSet<&MetadataId> {
     let mut sqlx_macros_dependents = BTreeSet::new();
     let sqlx_macros_ids: BTreeSet<_> = metadata
         .entries()
@@ -293,10 +400,33 @@ fn minimal_project_recompile_action(metadata: &Metadata, all: bool) -> ProjectRe
         sqlx_macros_dependents.extend(metadata.all_dependents_of(sqlx_macros_id));
     }
 
+    sqlx_macros_dependents
+}
+
+/// The workspace members which depend on `sqlx-macros`.
+fn workspace_sqlx_macros_dependents(metadata: &Metadata) -> Vec<&Package> {
+    sqlx_macros_dependents(metadata)
+        .into_iter()
+        .filter(|id| metadata.workspace_members().contains(id))
+        .filter_map(|id| metadata.package(id))
+        .collect()
+}
+
+/// Whether `cargo_args` select the packages to check, before any `--` passing arguments on to
+/// `rustc`.
+fn selects_packages(cargo_args: &[String]) -> bool {
+    cargo_args.iter().take_while(|arg| *arg != "--").any(|arg| {
+        matches!(&**arg, "--workspace" | "--all" | "--package")
+            || arg.starts_with("--package=")
+            || arg.starts_with("-p")
+    })
+}
+
+fn minimal_project_recompile_action(metadata: &Metadata, all: bool) -> ProjectRecompileAction {
     // Figure out which `sqlx-macros` dependents are in the workspace vs out
     let mut in_workspace_dependents = Vec::new();
     let mut out_of_workspace_dependents = Vec::new();
-    for dependent in sqlx_macros_dependents {
+    for dependent in sqlx_macros_dependents(metadata) {
         if metadata.workspace_members().contains(dependent) {
             in_workspace_dependents.push(dependent);
         } else {
@@ -337,10 +467,57 @@ fn minimal_project_recompile_action(metadata: &Metadata, all: bool) -> ProjectRe
     }
 }
 
-/// Find all `query-*.json` files in a directory.
-fn glob_query_files(path: impl AsRef<Path>) -> anyhow::Result<Vec<PathBuf>> {
+/// The database of `database` in `offline.databases`, named `name`, and the URL to prepare its
+/// queries against: its own `database-url`, or else `default_url`.
+fn resolve_database(
+    name: &str,
+    database: &OfflineDatabase,
+    default_url: Option<&str>,
+) -> anyhow::Result<(DatabaseKind, String)> {
+    let kind = DatabaseKind::from_scheme(name).with_context(|| {
+        format!("unknown database {name:?} in `offline.databases` of sqlx.toml; expected `postgres`, `mysql` or `sqlite`")
+    })?;
+
+    let url = database.database_url.as_deref().or(default_url).with_context(|| {
+        format!("a database URL is required for {name:?}; set `database-url` in `[offline.databases.{name}]` of sqlx.toml")
+    })?;
+
+    let url_kind = database_kind(url)?;
+    anyhow::ensure!(
+        url_kind == kind,
+        "the database URL for {name:?} in `offline.databases` is for {}; set `database-url` in `[offline.databases.{name}]` of sqlx.toml",
+        url_kind.as_str()
+    );
+
+    Ok((kind, url.to_owned()))
+}
+
+/// The database of a URL.
+fn database_kind(url: &str) -> anyhow::Result<DatabaseKind> {
+    let url = DatabaseUrl::parse(url)?;
+
+    url.kind()
+        .with_context(|| format!("unsupported database URL scheme: {:?}", url.scheme()))
+}
+
+/// The pattern of the names of the query data files saved for `kind` with `offline.per-driver`.
+fn query_files_of(kind: DatabaseKind) -> String {
+    format!("query-*.{}.json", kind.as_str())
+}
+
+/// Find the query data files saved for `database` in its directory in `dir`, or else all those
+/// in `dir`.
+fn glob_query_files_of(dir: &Path, database: Option<DatabaseKind>) -> anyhow::Result<Vec<PathBuf>> {
+    match database {
+        Some(database) => glob_query_files(dir.join(database.as_str()), &query_files_of(database)),
+        None => glob_query_files(dir, "query-*.json"),
+    }
+}
+
+/// Find all files matching a pattern such as `query-*.json` in a directory.
+fn glob_query_files(path: impl AsRef<Path>, query_files: &str) -> anyhow::Result<Vec<PathBuf>> {
     let path = path.as_ref();
-    let pattern = path.join("query-*.json");
+    let pattern = path.join(query_files);
     glob::glob(
         pattern
             .to_str()
@@ -391,4 +568,100 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn workspace_sqlx_macros_dependents_works() -> anyhow::Result<()> {
+        let sample_metadata_path = Path::new("tests")
+            .join("assets")
+            .join("sample_metadata.json");
+        let sample_metadata = std::fs::read_to_string(sample_metadata_path)?;
+        let metadata: Metadata = sample_metadata.parse()?;
+
+        let names: Vec<_> = workspace_sqlx_macros_dependents(&metadata)
+            .into_iter()
+            .map(Package::name)
+            .collect();
+        assert_eq!(names, ["b_in_workspace_lib", "c_in_workspace_bin"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn selects_packages_works() {
+        let args = |args: &[&str]| args.iter().map(|&arg| arg.to_owned()).collect::<Vec<_>>();
+
+        assert!(!selects_packages(&args(&[
+            "--all-targets",
+            "--all-features"
+        ])));
+        assert!(!selects_packages(&args(&["--", "-p", "foo"])));
+        assert!(selects_packages(&args(&["--workspace"])));
+        assert!(selects_packages(&args(&["--all-targets", "-p", "foo"])));
+        assert!(selects_packages(&args(&["-pfoo"])));
+        assert!(selects_packages(&args(&["--package=foo"])));
+    }
+
+    #[test]
+    fn glob_query_files_of_one_database() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        for name in [
+            "query-a.json",
+            "postgres/query-b.postgres.json",
+            "sqlite/query-c.sqlite.json",
+        ] {
+            let path = dir.path().join(name);
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(path, "{}")?;
+        }
+
+        let names = |database: Option<DatabaseKind>| -> anyhow::Result<Vec<String>> {
+            let mut names: Vec<_> = glob_query_files_of(dir.path(), database)?
+                .iter()
+                .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+                .collect();
+            names.sort();
+            Ok(names)
+        };
+
+        assert_eq!(
+            names(Some(DatabaseKind::Postgres))?,
+            ["query-b.postgres.json"]
+        );
+        assert_eq!(names(Some(DatabaseKind::MySql))?, Vec::<String>::new());
+        assert_eq!(names(None)?, ["query-a.json"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_offline_databases() -> anyhow::Result<()> {
+        let database = |url: Option<&str>| OfflineDatabase {
+            database_url: url.map(String::from),
+            features: Vec::new(),
+        };
+
+        assert_eq!(
+            resolve_database(
+                "postgres",
+                &database(Some("postgres://localhost/app")),
+                None
+            )?,
+            (
+                DatabaseKind::Postgres,
+                "postgres://localhost/app".to_owned()
+            )
+        );
+        assert_eq!(
+            resolve_database("mysql", &database(None), Some("mysql://localhost/app"))?,
+            (DatabaseKind::MySql, "mysql://localhost/app".to_owned())
+        );
+
+        assert!(resolve_database("oracle", &database(None), None).is_err());
+        assert!(resolve_database("mysql", &database(None), None).is_err());
+        assert!(
+            resolve_database("mysql", &database(None), Some("postgres://localhost/app")).is_err()
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,94 @@
+use std::path::Path;
+
+use anyhow::bail;
+use sqlx::DatabaseKind;
+
+use crate::opt::ConnectOpts;
+
+pub async fn run(file: &Path, connect_opts: &ConnectOpts, keep_going: bool) -> anyhow::Result<()> {
+    let db_url = connect_opts.required_db_url()?;
+
+    match db_url.kind() {
+        #[cfg(feature = "postgres")]
+        Some(DatabaseKind::Postgres) => {
+            postgres::run(file, &db_url, connect_opts, keep_going).await
+        }
+        _ => {
+            let _ = (file, keep_going);
+            bail!("replaying statements against `{}` databases is not supported; only PostgreSQL recordings can be replayed", db_url.scheme())
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod postgres {
+    use std::path::Path;
+
+    use anyhow::Context;
+    use console::style;
+    use serde_json::json;
+    use sqlx::postgres::{PgConnection, PgReplayReader};
+    use sqlx::{Connection, DatabaseUrl};
+
+    use crate::opt::ConnectOpts;
+    use crate::output;
+
+    pub async fn run(
+        file: &Path,
+        db_url: &DatabaseUrl,
+        connect_opts: &ConnectOpts,
+        keep_going: bool,
+    ) -> anyhow::Result<()> {
+        let reader = PgReplayReader::open(file)
+            .with_context(|| format!("failed to open recording {}", file.display()))?;
+
+        let mut conn =
+            crate::retry_connect_errors(connect_opts, db_url, PgConnection::connect).await?;
+
+        let mut executed = 0_u64;
+        let mut failed = 0_u64;
+        let mut rows_affected = 0_u64;
+
+        for (index, entry) in reader.enumerate() {
+            let entry = entry
+                .with_context(|| format!("failed to read statement {} of recording", index + 1))?;
+
+            match entry.execute(&mut conn).await {
+                Ok(result) => {
+                    executed += 1;
+                    rows_affected += result.rows_affected();
+                }
+                Err(e) if keep_going => {
+                    failed += 1;
+
+                    output::print(
+                        "replay_statement_failed",
+                        json!({ "index": index, "sql": entry.sql, "error": e.to_string() }),
+                        format_args!(
+                            "{} statement {}: {e}\n  {}",
+                            style("failed").red(),
+                            index + 1,
+                            entry.sql
+                        ),
+                    );
+                }
+                Err(e) => {
+                    return Err(e)
+                        .with_context(|| format!("statement {} failed: {}", index + 1, entry.sql));
+                }
+            }
+        }
+
+        let _ = conn.close().await;
+
+        output::print(
+            "replay_finished",
+            json!({ "executed": executed, "failed": failed, "rows_affected": rows_affected }),
+            format_args!(
+                "Replayed {executed} statements ({rows_affected} rows affected), {failed} failed"
+            ),
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use std::fs::{self, File};
+use std::io::Write;
+
+use anyhow::Context;
+use chrono::Utc;
+use console::style;
+use serde_json::json;
+
+use crate::migrate;
+use crate::opt::{ConnectOpts, Seeds};
+use crate::output;
+
+pub fn new(seeds: &Seeds, description: &str) -> anyhow::Result<()> {
+    let dir = seeds.dir();
+    fs::create_dir_all(&dir).context("Unable to create seeds directory")?;
+
+    let path = dir.join(format!(
+        "{}_{}.sql",
+        Utc::now().format("%Y%m%d%H%M%S"),
+        description.replace(' ', "_")
+    ));
+
+    output::print(
+        "seed_created",
+        json!({ "path": path, "seed_set": seeds.seed_set }),
+        format_args!("Creating {}", style(path.display()).cyan()),
+    );
+
+    let mut file = File::create(&path).context("Failed to create seed file")?;
+    file.write_all(b"-- Add seed data here\n")?;
+
+    Ok(())
+}
+
+pub async fn run(seeds: &Seeds, connect_opts: &ConnectOpts, dry_run: bool) -> anyhow::Result<()> {
+    let dir = seeds.dir();
+
+    // seeds are run like migrations, but tracked in a table of their own for each set
+    migrate::run(
+        &[dir.to_string_lossy().into_owned()],
+        &seeds.table(),
+        true,
+        connect_opts,
+        dry_run,
+        false,
+        None,
+        false,
+        &[],
+    )
+    .await
+    .with_context(|| format!("failed to run the `{}` seeds", seeds.seed_set))
+}
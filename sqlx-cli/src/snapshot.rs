@@ -0,0 +1,405 @@
+use std::path::Path;
+
+use anyhow::{bail, ensure};
+use console::style;
+use serde_json::json;
+use sqlx::DatabaseKind;
+
+use crate::database;
+use crate::opt::{Confirmation, ConnectOpts};
+use crate::output;
+
+pub async fn save(
+    name: &str,
+    snapshot_dir: &Path,
+    connect_opts: &ConnectOpts,
+) -> anyhow::Result<()> {
+    validate_name(name)?;
+
+    let db_url = connect_opts.required_db_url()?;
+
+    let location = match db_url.kind() {
+        #[cfg(feature = "postgres")]
+        Some(DatabaseKind::Postgres) => postgres::save(name, db_url.as_str()).await?,
+        #[cfg(feature = "mysql")]
+        Some(DatabaseKind::MySql) => mysql::save(name, snapshot_dir, &db_url)?,
+        #[cfg(any(feature = "sqlite", feature = "sqlite-unbundled"))]
+        Some(DatabaseKind::Sqlite) => sqlite::save(name, snapshot_dir, connect_opts).await?,
+        _ => unsupported(db_url.scheme())?,
+    };
+
+    output::print(
+        "snapshot_saved",
+        json!({ "name": name, "location": location }),
+        format_args!("Saved snapshot {} to {location}", style(name).cyan()),
+    );
+
+    Ok(())
+}
+
+pub async fn restore(
+    name: &str,
+    snapshot_dir: &Path,
+    connect_opts: &ConnectOpts,
+    confirmation: &Confirmation,
+    force: bool,
+) -> anyhow::Result<()> {
+    validate_name(name)?;
+
+    let db_url = connect_opts.required_db_url()?;
+
+    if !confirmation.yes
+        && !database::confirm_drop(&db_url, confirmation.confirm_token.as_deref()).await?
+    {
+        output::event("snapshot_restore_cancelled", json!({ "name": name }));
+        return Ok(());
+    }
+
+    let location = match db_url.kind() {
+        #[cfg(feature = "postgres")]
+        Some(DatabaseKind::Postgres) => postgres::restore(name, db_url.as_str(), force).await?,
+        #[cfg(feature = "mysql")]
+        Some(DatabaseKind::MySql) => mysql::restore(name, snapshot_dir, &db_url).await?,
+        #[cfg(any(feature = "sqlite", feature = "sqlite-unbundled"))]
+        Some(DatabaseKind::Sqlite) => sqlite::restore(name, snapshot_dir, db_url.as_str())?,
+        _ => unsupported(db_url.scheme())?,
+    };
+
+    output::print(
+        "snapshot_restored",
+        json!({ "name": name, "location": location }),
+        format_args!("Restored snapshot {} from {location}", style(name).cyan()),
+    );
+
+    Ok(())
+}
+
+fn unsupported(scheme: &str) -> anyhow::Result<String> {
+    bail!("snapshots of `{scheme}` databases are not supported by this build of sqlx-cli")
+}
+
+/// Snapshot names are used in database and file names, so only a conservative set of
+/// characters is allowed.
+fn validate_name(name: &str) -> anyhow::Result<()> {
+    ensure!(
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-'),
+        "snapshot names may only contain ASCII letters, digits, `_` and `-`; got `{name}`"
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "postgres")]
+mod postgres {
+    use anyhow::{bail, ensure, Context};
+    use sqlx::postgres::{PgConnectOptions, PgConnection};
+    use sqlx::{ConnectOptions, Connection, Executor};
+
+    /// The longest identifier Postgres accepts without truncating it.
+    const MAX_IDENTIFIER_LEN: usize = 63;
+
+    pub async fn save(name: &str, db_url: &str) -> anyhow::Result<String> {
+        let (mut conn, database) = connect_maintenance(db_url).await?;
+        let snapshot = snapshot_database(&database, name)?;
+
+        conn.execute(&*format!("DROP DATABASE IF EXISTS {}", quote(&snapshot)))
+            .await?;
+        conn.execute(&*format!(
+            "CREATE DATABASE {} TEMPLATE {}",
+            quote(&snapshot),
+            quote(&database)
+        ))
+        .await?;
+
+        let _ = conn.close().await;
+
+        Ok(format!("database `{snapshot}`"))
+    }
+
+    pub async fn restore(name: &str, db_url: &str, force: bool) -> anyhow::Result<String> {
+        let (mut conn, database) = connect_maintenance(db_url).await?;
+        let snapshot = snapshot_database(&database, name)?;
+
+        let exists: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM pg_database WHERE datname = $1)")
+                .bind(&snapshot)
+                .fetch_one(&mut conn)
+                .await?;
+
+        if !exists {
+            bail!("no snapshot named `{name}` was found (expected a database named `{snapshot}`)");
+        }
+
+        if force {
+            sqlx::query(
+                "SELECT pg_terminate_backend(pid) FROM pg_stat_activity \
+                 WHERE datname = $1 AND pid <> pg_backend_pid()",
+            )
+            .bind(&database)
+            .execute(&mut conn)
+            .await?;
+        }
+
+        conn.execute(&*format!("DROP DATABASE IF EXISTS {}", quote(&database)))
+            .await?;
+        conn.execute(&*format!(
+            "CREATE DATABASE {} TEMPLATE {}",
+            quote(&database),
+            quote(&snapshot)
+        ))
+        .await?;
+
+        let _ = conn.close().await;
+
+        Ok(format!("database `{snapshot}`"))
+    }
+
+    /// Connect to a database other than the one in `db_url`, which cannot be copied or dropped
+    /// while we're connected to it.
+    async fn connect_maintenance(db_url: &str) -> anyhow::Result<(PgConnection, String)> {
+        let options: PgConnectOptions = db_url.parse()?;
+
+        let database = options
+            .get_database()
+            .context("DATABASE_URL must include the name of the database")?
+            .to_owned();
+
+        let maintenance = if database == "postgres" {
+            "template1"
+        } else {
+            "postgres"
+        };
+
+        let conn = options.database(maintenance).connect().await?;
+
+        Ok((conn, database))
+    }
+
+    fn snapshot_database(database: &str, name: &str) -> anyhow::Result<String> {
+        let snapshot = format!("{database}__snapshot_{name}");
+
+        ensure!(
+            snapshot.len() <= MAX_IDENTIFIER_LEN,
+            "the snapshot database name `{snapshot}` is longer than {MAX_IDENTIFIER_LEN} bytes; \
+             use a shorter snapshot name"
+        );
+
+        Ok(snapshot)
+    }
+
+    fn quote(ident: &str) -> String {
+        format!("\"{}\"", ident.replace('"', "\"\""))
+    }
+}
+
+#[cfg(feature = "mysql")]
+pub(crate) mod mysql {
+    use std::fs::{self, File};
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    use anyhow::{bail, Context};
+    use sqlx::any::Any;
+    use sqlx::migrate::MigrateDatabase;
+    use sqlx::mysql::MySqlConnectOptions;
+    use sqlx::DatabaseUrl;
+
+    pub fn save(name: &str, snapshot_dir: &Path, db_url: &DatabaseUrl) -> anyhow::Result<String> {
+        let client = Client::from_url(db_url)?;
+        let path = client.snapshot_path(snapshot_dir, name);
+
+        fs::create_dir_all(snapshot_dir).context("failed to create the snapshot directory")?;
+
+        let status = client
+            .command("mysqldump")
+            .args(["--single-transaction", "--routines", "--triggers"])
+            .arg("--result-file")
+            .arg(&path)
+            .arg(&client.database)
+            .status()
+            .context("failed to run `mysqldump`; is it installed and on your PATH?")?;
+
+        if !status.success() {
+            bail!("`mysqldump` failed with status: {status}");
+        }
+
+        Ok(path.display().to_string())
+    }
+
+    pub async fn restore(
+        name: &str,
+        snapshot_dir: &Path,
+        db_url: &DatabaseUrl,
+    ) -> anyhow::Result<String> {
+        let client = Client::from_url(db_url)?;
+        let path = client.snapshot_path(snapshot_dir, name);
+
+        let dump = File::open(&path).with_context(|| {
+            format!("no snapshot named `{name}` was found at {}", path.display())
+        })?;
+
+        sqlx::any::install_default_drivers();
+        Any::drop_database(db_url.as_str()).await?;
+        Any::create_database(db_url.as_str()).await?;
+
+        let status = client
+            .command("mysql")
+            .arg(&client.database)
+            .stdin(dump)
+            .status()
+            .context("failed to run `mysql`; is it installed and on your PATH?")?;
+
+        if !status.success() {
+            bail!("`mysql` failed with status: {status}");
+        }
+
+        Ok(path.display().to_string())
+    }
+
+    /// Connection details passed to the `mysql` and `mysqldump` clients.
+    pub(crate) struct Client {
+        options: MySqlConnectOptions,
+        password: Option<String>,
+        pub(crate) database: String,
+    }
+
+    impl Client {
+        pub(crate) fn from_url(db_url: &DatabaseUrl) -> anyhow::Result<Self> {
+            let options: MySqlConnectOptions = db_url.as_str().parse()?;
+
+            let database = options
+                .get_database()
+                .context("DATABASE_URL must include the name of the database")?
+                .to_owned();
+
+            // `MySqlConnectOptions` doesn't expose the password.
+            let password = db_url.password().map(String::from);
+
+            Ok(Self {
+                options,
+                password,
+                database,
+            })
+        }
+
+        fn snapshot_path(&self, snapshot_dir: &Path, name: &str) -> PathBuf {
+            snapshot_dir.join(format!("{}.{name}.sql", self.database))
+        }
+
+        pub(crate) fn command(&self, program: &str) -> Command {
+            let mut command = Command::new(program);
+
+            command
+                .arg("--host")
+                .arg(self.options.get_host())
+                .arg("--port")
+                .arg(self.options.get_port().to_string())
+                .arg("--user")
+                .arg(self.options.get_username());
+
+            if let Some(socket) = self.options.get_socket() {
+                command.arg("--socket").arg(socket);
+            }
+
+            // Keep the password out of the process arguments.
+            if let Some(password) = &self.password {
+                command.env("MYSQL_PWD", password);
+            }
+
+            command
+        }
+    }
+}
+
+#[cfg(any(feature = "sqlite", feature = "sqlite-unbundled"))]
+mod sqlite {
+    use std::fs;
+    use std::io;
+    use std::path::{Path, PathBuf};
+
+    use anyhow::{ensure, Context};
+    use sqlx::sqlite::SqliteConnectOptions;
+    use sqlx::{Connection, Executor};
+
+    use crate::opt::ConnectOpts;
+
+    pub async fn save(
+        name: &str,
+        snapshot_dir: &Path,
+        connect_opts: &ConnectOpts,
+    ) -> anyhow::Result<String> {
+        let (_, path) = paths(connect_opts.required_db_url()?.as_str(), snapshot_dir, name)?;
+
+        fs::create_dir_all(snapshot_dir).context("failed to create the snapshot directory")?;
+
+        // `VACUUM INTO` refuses to overwrite an existing file.
+        remove_if_exists(&path)?;
+
+        let mut conn = crate::connect(connect_opts).await?;
+
+        // Unlike copying the file, this includes any changes still in the WAL.
+        conn.execute(&*format!(
+            "VACUUM INTO '{}'",
+            path.display().to_string().replace('\'', "''")
+        ))
+        .await?;
+
+        let _ = conn.close().await;
+
+        Ok(path.display().to_string())
+    }
+
+    pub fn restore(name: &str, snapshot_dir: &Path, db_url: &str) -> anyhow::Result<String> {
+        let (database, path) = paths(db_url, snapshot_dir, name)?;
+
+        ensure!(
+            path.is_file(),
+            "no snapshot named `{name}` was found at {}",
+            path.display()
+        );
+
+        // Stale WAL and shared memory files would be applied on top of the restored database.
+        for suffix in ["-wal", "-shm"] {
+            let mut sidecar = database.clone().into_os_string();
+            sidecar.push(suffix);
+            remove_if_exists(Path::new(&sidecar))?;
+        }
+
+        fs::copy(&path, &database).with_context(|| {
+            format!(
+                "failed to copy {} to {}",
+                path.display(),
+                database.display()
+            )
+        })?;
+
+        Ok(path.display().to_string())
+    }
+
+    /// The paths of the database file and of its snapshot named `name`.
+    fn paths(db_url: &str, snapshot_dir: &Path, name: &str) -> anyhow::Result<(PathBuf, PathBuf)> {
+        let options: SqliteConnectOptions = db_url.parse()?;
+        let database = options.get_filename().to_owned();
+
+        let file_name = database
+            .file_name()
+            .context("DATABASE_URL must point to a database file")?;
+
+        let mut snapshot_name = file_name.to_owned();
+        snapshot_name.push(format!(".{name}"));
+
+        Ok((database, snapshot_dir.join(snapshot_name)))
+    }
+
+    fn remove_if_exists(path: &Path) -> anyhow::Result<()> {
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("failed to remove {}", path.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
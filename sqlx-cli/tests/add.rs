@@ -169,6 +169,46 @@ fn add_migration_timestamp_reversible() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn add_migration_repeatable() -> anyhow::Result<()> {
+    let dir = TempDir::new()?;
+
+    for args in [
+        ["--sequential", "hello world1"],
+        ["--repeatable", "refresh views"],
+        ["--sequential", "hello world2"],
+    ] {
+        Command::cargo_bin("cargo-sqlx")?
+            .current_dir(&dir)
+            .args(["sqlx", "migrate", "add"])
+            .args(args)
+            .assert()
+            .success();
+    }
+
+    // repeatable migrations don't take up a version
+    let files: Vec<_> = recurse_files(&dir)?
+        .iter()
+        .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(
+        files,
+        [
+            "0001_hello_world1.sql",
+            "0002_hello_world2.sql",
+            "R__refresh_views.sql"
+        ]
+    );
+
+    Command::cargo_bin("cargo-sqlx")?
+        .current_dir(&dir)
+        .args(["sqlx", "migrate", "add", "-r", "--repeatable", "hello"])
+        .assert()
+        .failure();
+
+    Ok(())
+}
+
 struct AddMigrationsResult(Vec<FileName>);
 impl AddMigrationsResult {
     fn len(&self) -> usize {
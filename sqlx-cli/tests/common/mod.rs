@@ -77,7 +77,7 @@ impl TestDatabase {
         let mut conn = SqliteConnection::connect(&self.connection_string())
             .await
             .unwrap();
-        conn.list_applied_migrations()
+        conn.list_applied_migrations("_sqlx_migrations")
             .await
             .unwrap()
             .iter()
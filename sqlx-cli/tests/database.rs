@@ -0,0 +1,227 @@
+use assert_cmd::Command;
+use sqlx::{Connection, Executor, SqliteConnection};
+use std::path::Path;
+
+fn sqlx(args: &[&str]) -> Command {
+    let mut cmd = Command::cargo_bin("cargo-sqlx").unwrap();
+    cmd.arg("sqlx").args(args);
+    cmd
+}
+
+#[test]
+fn drop_database_with_confirmation_token() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("confirm-drop.db");
+    let url = format!("sqlite://{}", path.display());
+
+    let create = || {
+        sqlx(&["database", "create", "--database-url", &url])
+            .assert()
+            .success();
+        assert!(Path::new(&path).exists());
+    };
+
+    let drop = || {
+        let mut cmd = sqlx(&["database", "drop", "--database-url", &url]);
+        cmd.env_remove("SQLX_CONFIRM_TOKEN");
+        cmd
+    };
+
+    create();
+
+    // Without a terminal, a confirmation is required.
+    drop().assert().failure();
+    drop().write_stdin("other.db\n").assert().failure();
+    drop().write_stdin(&b"\xff\xfe\n"[..]).assert().failure();
+    drop()
+        .args(["--confirm-token", "other.db"])
+        .assert()
+        .failure();
+    assert!(path.exists());
+
+    drop().write_stdin("confirm-drop.db\n").assert().success();
+    assert!(!path.exists());
+
+    create();
+
+    drop()
+        .args(["--confirm-token", "confirm-drop.db"])
+        .assert()
+        .success();
+    assert!(!path.exists());
+
+    create();
+
+    drop()
+        .env("SQLX_CONFIRM_TOKEN", "confirm-drop.db")
+        .assert()
+        .success();
+    assert!(!path.exists());
+
+    create();
+
+    // `-y` takes precedence over an exported token.
+    drop()
+        .env("SQLX_CONFIRM_TOKEN", "other.db")
+        .arg("-y")
+        .assert()
+        .success();
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn save_and_restore_sqlite_snapshot() {
+    let dir = tempfile::tempdir().unwrap();
+    let url = format!("sqlite://{}", dir.path().join("snapshot.db").display());
+    let snapshot_dir = dir.path().join("snapshots");
+    let snapshot_dir = snapshot_dir.to_str().unwrap();
+
+    let count = || async {
+        let mut conn = SqliteConnection::connect(&url).await.unwrap();
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM t")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        conn.close().await.unwrap();
+        count
+    };
+
+    sqlx(&["database", "create", "--database-url", &url])
+        .assert()
+        .success();
+
+    let mut conn = SqliteConnection::connect(&url).await.unwrap();
+    conn.execute("CREATE TABLE t (x INTEGER); INSERT INTO t VALUES (1)")
+        .await
+        .unwrap();
+    conn.close().await.unwrap();
+
+    sqlx(&[
+        "database",
+        "snapshot",
+        "save",
+        "before",
+        "--database-url",
+        &url,
+    ])
+    .args(["--snapshot-dir", snapshot_dir])
+    .assert()
+    .success();
+
+    let mut conn = SqliteConnection::connect(&url).await.unwrap();
+    conn.execute("INSERT INTO t VALUES (2)").await.unwrap();
+    conn.close().await.unwrap();
+    assert_eq!(count().await, 2);
+
+    sqlx(&[
+        "database",
+        "snapshot",
+        "restore",
+        "before",
+        "-y",
+        "--database-url",
+        &url,
+    ])
+    .args(["--snapshot-dir", snapshot_dir])
+    .assert()
+    .success();
+    assert_eq!(count().await, 1);
+
+    // Restoring a snapshot which doesn't exist leaves the database untouched.
+    sqlx(&[
+        "database",
+        "snapshot",
+        "restore",
+        "missing",
+        "-y",
+        "--database-url",
+        &url,
+    ])
+    .args(["--snapshot-dir", snapshot_dir])
+    .assert()
+    .failure();
+    assert_eq!(count().await, 1);
+}
+
+#[tokio::test]
+async fn dump_and_load_sqlite_schema() {
+    let dir = tempfile::tempdir().unwrap();
+    let url = format!("sqlite://{}", dir.path().join("dump.db").display());
+    let loaded_url = format!("sqlite://{}", dir.path().join("loaded.db").display());
+
+    sqlx(&["database", "create", "--database-url", &url])
+        .assert()
+        .success();
+    sqlx(&[
+        "migrate",
+        "run",
+        "--database-url",
+        &url,
+        "--source",
+        "tests/migrations_reversible",
+        "--target-version",
+        "20230201000000",
+    ])
+    .assert()
+    .success();
+
+    let mut conn = SqliteConnection::connect(&url).await.unwrap();
+    conn.execute("INSERT INTO test1 VALUES (1), (2)")
+        .await
+        .unwrap();
+    conn.close().await.unwrap();
+
+    let dump = |schema_only: bool| {
+        let mut cmd = sqlx(&["database", "dump", "--database-url", &url]);
+        if schema_only {
+            cmd.arg("--schema-only");
+        }
+        let output = cmd.assert().success().get_output().stdout.clone();
+        String::from_utf8(output).unwrap()
+    };
+
+    let schema = dump(true);
+    assert!(schema.contains("CREATE TABLE test1"), "{schema}");
+    assert!(
+        schema.contains(r#"INSERT INTO "_sqlx_migrations""#),
+        "{schema}"
+    );
+    assert!(!schema.contains(r#"INSERT INTO "test1""#), "{schema}");
+
+    let full = dump(false);
+    assert!(
+        full.contains(r#"INSERT INTO "test1"("x") VALUES(2);"#),
+        "{full}"
+    );
+
+    let script = dir.path().join("schema.sql");
+    std::fs::write(&script, schema).unwrap();
+
+    sqlx(&["database", "load", "--database-url", &loaded_url])
+        .arg(&script)
+        .assert()
+        .success();
+
+    // The migrations recorded in the dump are not run again.
+    let run = sqlx(&[
+        "migrate",
+        "run",
+        "--database-url",
+        &loaded_url,
+        "--source",
+        "tests/migrations_reversible",
+    ])
+    .assert()
+    .success();
+    let stdout = String::from_utf8_lossy(&run.get_output().stdout).into_owned();
+    assert!(!stdout.contains("test1"), "{stdout}");
+    assert!(stdout.contains("test3"), "{stdout}");
+
+    let mut conn = SqliteConnection::connect(&loaded_url).await.unwrap();
+    let rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM test1")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(rows, 0);
+    conn.close().await.unwrap();
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use assert_cmd::Command;
+use common::TestDatabase;
+
+#[tokio::test]
+async fn doctor_reports_migration_state() {
+    let db = TestDatabase::new("doctor_migration_state", "migrations_reversible");
+
+    let doctor = || {
+        Command::cargo_bin("cargo-sqlx")
+            .unwrap()
+            .args([
+                "sqlx",
+                "doctor",
+                "--source",
+                "tests/migrations_reversible",
+                "--database-url",
+                &db.connection_string(),
+            ])
+            .assert()
+            .success()
+    };
+
+    let assert = doctor();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(stdout.contains("the database is reachable"), "{stdout}");
+    assert!(
+        stdout.contains("no migrations have been applied; 5 pending"),
+        "{stdout}"
+    );
+
+    db.run_migration(false, Some(20230301000000), false)
+        .success();
+
+    let assert = doctor();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(stdout.contains("2 migration(s) are pending"), "{stdout}");
+
+    db.run_migration(false, None, false).success();
+    assert_eq!(db.applied_migrations().await.len(), 5);
+
+    let assert = doctor();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(stdout.contains("migrations are up to date"), "{stdout}");
+}
+
+#[test]
+fn doctor_fails_on_unsupported_url() {
+    Command::cargo_bin("cargo-sqlx")
+        .unwrap()
+        .args(["sqlx", "doctor", "--database-url", "mssql://localhost/db"])
+        .assert()
+        .failure();
+}
@@ -0,0 +1,85 @@
+use assert_cmd::Command;
+use sqlx::{Connection, Executor, SqliteConnection};
+
+#[tokio::test]
+async fn export_anonymized_sqlite() {
+    let dir = tempfile::tempdir().unwrap();
+    let url = format!(
+        "sqlite://{}?mode=rwc",
+        dir.path().join("export.db").display()
+    );
+
+    let mut conn = SqliteConnection::connect(&url).await.unwrap();
+    conn.execute(
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT, ssn TEXT, notes TEXT); \
+         INSERT INTO users VALUES (1, 'alice@corp.com', '123-45-6789', 'it''s private'); \
+         INSERT INTO users VALUES (2, 'bob@corp.com', NULL, NULL);",
+    )
+    .await
+    .unwrap();
+    conn.close().await.unwrap();
+
+    let rules = dir.path().join("rules.toml");
+    std::fs::write(
+        &rules,
+        r#"
+        [users]
+        email = "email"
+        ssn = { mask = "*", keep_last = 4 }
+        notes = "null"
+        "#,
+    )
+    .unwrap();
+
+    let assert = Command::cargo_bin("cargo-sqlx")
+        .unwrap()
+        .args(["sqlx", "export", "--database-url", &url, "--anonymize"])
+        .arg(&rules)
+        .assert()
+        .success();
+
+    let dump = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    let lines: Vec<&str> = dump.lines().collect();
+
+    assert_eq!(lines.len(), 2, "{dump}");
+    assert!(!dump.contains("corp.com"), "{dump}");
+    assert!(!dump.contains("private"), "{dump}");
+    assert!(
+        lines[0].starts_with(
+            r#"INSERT INTO "users" ("id", "email", "ssn", "notes") VALUES ('1', 'user_"#
+        ),
+        "{dump}"
+    );
+    assert!(
+        lines[0].ends_with("@example.com', '*******6789', NULL);"),
+        "{dump}"
+    );
+    assert!(lines[1].ends_with("@example.com', NULL, NULL);"), "{dump}");
+
+    // Fake values are only stable between exports given the same key.
+    let export = |key: Option<&str>| {
+        let mut cmd = Command::cargo_bin("cargo-sqlx").unwrap();
+        cmd.args(["sqlx", "export", "--database-url", &url, "--anonymize"])
+            .arg(&rules)
+            .env_remove("SQLX_ANONYMIZE_KEY");
+        if let Some(key) = key {
+            cmd.args(["--anonymize-key", key]);
+        }
+        let assert = cmd.assert().success();
+        String::from_utf8_lossy(&assert.get_output().stdout).into_owned()
+    };
+
+    assert_ne!(export(None), export(None));
+    assert_eq!(export(Some("secret")), export(Some("secret")));
+    assert_ne!(export(Some("secret")), export(Some("other")));
+
+    // Rules naming columns which don't exist are rejected, rather than leaving data unmasked.
+    std::fs::write(&rules, "[users]\nemial = \"email\"\n").unwrap();
+
+    Command::cargo_bin("cargo-sqlx")
+        .unwrap()
+        .args(["sqlx", "export", "--database-url", &url, "--anonymize"])
+        .arg(&rules)
+        .assert()
+        .failure();
+}
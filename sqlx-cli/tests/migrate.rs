@@ -1,6 +1,9 @@
 mod common;
 
+use assert_cmd::Command;
 use common::TestDatabase;
+use sqlx::{Connection, SqliteConnection};
+use std::path::Path;
 
 #[tokio::test]
 async fn run_reversible_migrations() {
@@ -80,6 +83,66 @@ async fn run_reversible_migrations() {
     }
 }
 
+#[tokio::test]
+async fn run_migrations_to_an_applied_target_version() {
+    let db = TestDatabase::new("migrate_run_allow_noop", "migrations_reversible");
+    db.run_migration(false, Some(20230301000000), false)
+        .success();
+
+    let run = |target_version: &str, allow_noop: bool| {
+        let mut cmd = Command::cargo_bin("cargo-sqlx").unwrap();
+        cmd.args([
+            "sqlx",
+            "migrate",
+            "run",
+            "--database-url",
+            &db.connection_string(),
+            "--source",
+            "tests/migrations_reversible",
+            "--target-version",
+            target_version,
+        ]);
+        if allow_noop {
+            cmd.arg("--allow-noop");
+        }
+        cmd.assert()
+    };
+
+    // Behind the applied migrations.
+    run("20230201000000", false).failure();
+    let assert = run("20230201000000", true).success();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(stdout.contains("Nothing to apply"), "{stdout}");
+    assert_eq!(
+        db.applied_migrations().await,
+        [20230101000000, 20230201000000, 20230301000000]
+    );
+
+    // A version which does not exist still fails.
+    run("20230901000000", true).failure();
+
+    // Ahead of the applied migrations, it applies them as without the flag.
+    run("20230401000000", true).success();
+    assert_eq!(db.applied_migrations().await.len(), 4);
+
+    // The flag is only accepted with a target version.
+    Command::cargo_bin("cargo-sqlx")
+        .unwrap()
+        .args([
+            "sqlx",
+            "migrate",
+            "run",
+            "--database-url",
+            &db.connection_string(),
+            "--source",
+            "tests/migrations_reversible",
+            "--allow-noop",
+        ])
+        .assert()
+        .failure();
+    assert_eq!(db.applied_migrations().await.len(), 4);
+}
+
 #[tokio::test]
 async fn revert_migrations() {
     let all_migrations: Vec<i64> = vec![
@@ -145,3 +208,646 @@ async fn revert_migrations() {
         assert_eq!(db.applied_migrations().await, vec![] as Vec<i64>);
     }
 }
+
+#[tokio::test]
+async fn revert_migrations_missing_down() {
+    let all_migrations: Vec<i64> = vec![20230101000000, 20230201000000, 20230301000000];
+
+    let db = TestDatabase::new("migrate_revert_missing_down", "migrations_missing_down");
+    db.run_migration(false, None, false).success();
+
+    // 20230201000000 has no down migration, so nothing is reverted.
+    let assert = db
+        .run_migration(true, Some(20230101000000), false)
+        .failure();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(
+        stdout.contains("no down migration for 20230201000000"),
+        "{stdout}"
+    );
+    assert_eq!(db.applied_migrations().await, all_migrations);
+
+    // The plan is printed before reverting.
+    let assert = db
+        .run_migration(true, Some(20230201000000), false)
+        .success();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(
+        stdout.starts_with("Reverting 1 migration(s) down to version 20230201000000:"),
+        "{stdout}"
+    );
+    assert_eq!(db.applied_migrations().await, all_migrations[..2]);
+}
+
+#[tokio::test]
+async fn run_migrations_dry_run_prints_sql() {
+    let db = TestDatabase::new("migrate_run_dry_run_sql", "migrations_reversible");
+    db.run_migration(false, Some(20230101000000), false)
+        .success();
+
+    let assert = db
+        .run_migration(false, Some(20230201000000), true)
+        .success();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+
+    // The migrations table already exists, so only the pending migration is rendered.
+    assert!(!stdout.contains("CREATE TABLE IF NOT EXISTS"), "{stdout}");
+    assert!(
+        stdout.contains("-- 20230201000000/migrate test2"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("CREATE TABLE test2(x INTEGER PRIMARY KEY);"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("INSERT INTO _sqlx_migrations"), "{stdout}");
+    assert!(
+        stdout.contains("VALUES ( 20230201000000, 'test2'"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("test3"), "{stdout}");
+
+    assert_eq!(db.applied_migrations().await, vec![20230101000000]);
+}
+
+#[tokio::test]
+async fn run_migrations_fan_out() {
+    let all_migrations: Vec<i64> = vec![
+        20230101000000,
+        20230201000000,
+        20230301000000,
+        20230401000000,
+        20230501000000,
+    ];
+
+    let first = TestDatabase::new("migrate_run_fan_out_first", "migrations_reversible");
+    let second = TestDatabase::new("migrate_run_fan_out_second", "migrations_reversible");
+
+    let urls_file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(
+        urls_file.path(),
+        format!(
+            "# shards\n{}\n\n{}\n",
+            first.connection_string(),
+            second.connection_string()
+        ),
+    )
+    .unwrap();
+
+    let assert = Command::cargo_bin("cargo-sqlx")
+        .unwrap()
+        .args([
+            "sqlx",
+            "migrate",
+            "run",
+            "--source",
+            "tests/migrations_reversible",
+            "--jobs",
+            "2",
+            "--database-urls-file",
+        ])
+        .arg(urls_file.path())
+        .assert()
+        .success();
+
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(
+        stdout.contains("2 of 2 databases migrated successfully"),
+        "{stdout}"
+    );
+
+    assert_eq!(first.applied_migrations().await, all_migrations);
+    assert_eq!(second.applied_migrations().await, all_migrations);
+
+    // Databases which fail are reported once all of them have been attempted.
+    let assert = Command::cargo_bin("cargo-sqlx")
+        .unwrap()
+        .args([
+            "sqlx",
+            "migrate",
+            "run",
+            "--source",
+            "tests/migrations_reversible",
+            "--database-url-vars",
+            "SQLX_FAN_OUT_TEST_*",
+        ])
+        .env("SQLX_FAN_OUT_TEST_1", first.connection_string())
+        .env("SQLX_FAN_OUT_TEST_2", "sqlite:///nonexistent/dir/db.sqlite")
+        .assert()
+        .failure();
+
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(
+        stdout.contains("1 of 2 databases migrated successfully"),
+        "{stdout}"
+    );
+}
+
+#[tokio::test]
+async fn run_migrations_json_output() {
+    let db = TestDatabase::new("migrate_run_json_output", "migrations_reversible");
+
+    let assert = Command::cargo_bin("cargo-sqlx")
+        .unwrap()
+        .args([
+            "sqlx",
+            "--format",
+            "json",
+            "migrate",
+            "run",
+            "--target-version",
+            "20230201000000",
+            "--database-url",
+            &db.connection_string(),
+            "--source",
+            "tests/migrations_reversible",
+        ])
+        .assert()
+        .success();
+
+    let events: Vec<serde_json::Value> = assert
+        .get_output()
+        .stdout
+        .split(|b| *b == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_slice(line).unwrap())
+        .collect();
+
+    let statuses: Vec<_> = events
+        .iter()
+        .map(|event| {
+            assert_eq!(event["event"], "migration_applied");
+            assert_eq!(event["checksum"].as_str().unwrap().len(), 96);
+            (
+                event["version"].as_i64().unwrap(),
+                event["status"].as_str().unwrap(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        statuses,
+        [
+            (20230101000000, "applied"),
+            (20230201000000, "applied"),
+            (20230301000000, "skipped"),
+            (20230401000000, "skipped"),
+            (20230501000000, "skipped"),
+        ]
+    );
+
+    // Errors are reported as an event too, along with their causes.
+    let assert = Command::cargo_bin("cargo-sqlx")
+        .unwrap()
+        .args([
+            "sqlx",
+            "--format",
+            "json",
+            "migrate",
+            "run",
+            "--database-url",
+            &db.connection_string(),
+            "--source",
+            "tests/nonexistent",
+        ])
+        .assert()
+        .failure();
+
+    let error: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(error["event"], "error");
+    assert!(
+        error["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("while resolving migrations"),
+        "{error}"
+    );
+    assert!(!error["causes"].as_array().unwrap().is_empty(), "{error}");
+}
+
+#[tokio::test]
+async fn run_migrations_with_config_file() {
+    let db = TestDatabase::new("migrate_run_config_file", "migrations_reversible");
+
+    let project = tempfile::tempdir().unwrap();
+    let source = std::env::current_dir()
+        .unwrap()
+        .join("tests/migrations_reversible");
+    std::fs::write(
+        project.path().join("sqlx.toml"),
+        format!(
+            "database-url = \"sqlite:///nonexistent/dir/db.sqlite\"\n\
+             \n\
+             [profiles.test]\n\
+             database-url = \"{}\"\n\
+             \n\
+             [migrate]\n\
+             source = \"{}\"\n",
+            db.connection_string(),
+            source.display()
+        ),
+    )
+    .unwrap();
+
+    let migrate = |profile: &str| {
+        Command::cargo_bin("cargo-sqlx")
+            .unwrap()
+            .args([
+                "sqlx",
+                "migrate",
+                "run",
+                "--profile",
+                profile,
+                "--target-version",
+                "20230201000000",
+            ])
+            .current_dir(project.path())
+            .env_remove("DATABASE_URL")
+            .assert()
+    };
+
+    // the default profile uses the top-level URL
+    migrate("dev").failure();
+
+    let assert = migrate("prod").failure();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(
+        stdout.contains("profile \"prod\" is not defined"),
+        "{stdout}"
+    );
+
+    migrate("test").success();
+    assert_eq!(
+        db.applied_migrations().await,
+        vec![20230101000000, 20230201000000]
+    );
+}
+
+#[tokio::test]
+async fn repair_migrations() {
+    let all_migrations: Vec<i64> = vec![
+        20230101000000,
+        20230201000000,
+        20230301000000,
+        20230401000000,
+        20230501000000,
+    ];
+
+    let db = TestDatabase::new("migrate_repair", "migrations_reversible");
+    db.run_migration(false, None, false).success();
+
+    // Simulate an edited migration and one whose file was deleted.
+    {
+        let mut conn = SqliteConnection::connect(&db.connection_string())
+            .await
+            .unwrap();
+        sqlx::query("UPDATE _sqlx_migrations SET checksum = x'00' WHERE version = 20230201000000")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time) \
+             VALUES (20230601000000, 'deleted', TRUE, x'00', 0)",
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    }
+    db.run_migration(false, None, false).failure();
+
+    let repair = |args: &[&str]| {
+        Command::cargo_bin("cargo-sqlx")
+            .unwrap()
+            .args([
+                "sqlx",
+                "migrate",
+                "repair",
+                "--database-url",
+                &db.connection_string(),
+                "--source",
+                "tests/migrations_reversible",
+            ])
+            .args(args)
+            .assert()
+    };
+
+    let assert = repair(&["--dry-run"]).success();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(
+        stdout.contains("Would update checksum of 20230201000000/migrate test2"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("Would remove 20230601000000"), "{stdout}");
+
+    // Removing rows requires confirmation, and stdin is not a terminal.
+    let assert = repair(&[]).failure();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(stdout.contains("pass `-y`"), "{stdout}");
+    assert_eq!(
+        db.applied_migrations().await,
+        [&all_migrations[..], &[20230601000000]].concat()
+    );
+
+    repair(&["-y"]).success();
+    assert_eq!(db.applied_migrations().await, all_migrations);
+    db.run_migration(false, None, false).success();
+
+    let assert = repair(&[]).success();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(stdout.starts_with("Nothing to repair"), "{stdout}");
+}
+
+#[tokio::test]
+async fn baseline_migrations() {
+    let db = TestDatabase::new("migrate_baseline", "migrations_reversible");
+
+    let baseline = |args: &[&str]| {
+        Command::cargo_bin("cargo-sqlx")
+            .unwrap()
+            .args([
+                "sqlx",
+                "migrate",
+                "baseline",
+                "--database-url",
+                &db.connection_string(),
+                "--source",
+                "tests/migrations_reversible",
+            ])
+            .args(args)
+            .assert()
+    };
+
+    baseline(&["20230901000000"]).failure();
+
+    let assert = baseline(&["20230201000000", "--dry-run"]).success();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(
+        stdout.contains("Can baseline 20230201000000/migrate test2"),
+        "{stdout}"
+    );
+    assert_eq!(db.applied_migrations().await, vec![] as Vec<i64>);
+
+    let assert = baseline(&["20230201000000"]).success();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(
+        stdout.contains("Baselined 20230101000000/migrate test1"),
+        "{stdout}"
+    );
+    assert_eq!(
+        db.applied_migrations().await,
+        vec![20230101000000, 20230201000000]
+    );
+
+    // The baselined migrations were not run, so the next run only applies the newer ones.
+    db.run_migration(false, Some(20230301000000), false)
+        .success();
+    assert_eq!(
+        db.applied_migrations().await,
+        vec![20230101000000, 20230201000000, 20230301000000]
+    );
+    let mut conn = SqliteConnection::connect(&db.connection_string())
+        .await
+        .unwrap();
+    let tables: Vec<String> =
+        sqlx::query_scalar("SELECT name FROM sqlite_master WHERE name LIKE 'test%' ORDER BY name")
+            .fetch_all(&mut conn)
+            .await
+            .unwrap();
+    assert_eq!(tables, ["test3"]);
+    conn.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn run_migrations_from_several_sources() {
+    let db = TestDatabase::new("migrate_run_several_sources", "migrations_reversible");
+
+    let app = tempfile::tempdir().unwrap();
+    std::fs::write(
+        app.path().join("20230215000000_app.up.sql"),
+        "CREATE TABLE app(x INTEGER PRIMARY KEY);",
+    )
+    .unwrap();
+    std::fs::write(
+        app.path().join("20230215000000_app.down.sql"),
+        "DROP TABLE app;",
+    )
+    .unwrap();
+
+    let migrate = |app: &Path| {
+        Command::cargo_bin("cargo-sqlx")
+            .unwrap()
+            .args([
+                "sqlx",
+                "migrate",
+                "run",
+                "--database-url",
+                &db.connection_string(),
+                "--source",
+                "tests/migrations_reversible",
+                "--source",
+            ])
+            .arg(app)
+            .assert()
+    };
+
+    migrate(app.path()).success();
+    assert_eq!(
+        db.applied_migrations().await,
+        vec![
+            20230101000000,
+            20230201000000,
+            20230215000000,
+            20230301000000,
+            20230401000000,
+            20230501000000,
+        ]
+    );
+
+    // a version may only be defined once
+    std::fs::write(app.path().join("20230101000000_clash.up.sql"), "SELECT 1;").unwrap();
+
+    let assert = migrate(app.path()).failure();
+    let stdout = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    assert!(
+        stdout.contains("migration version 20230101000000 is defined in both"),
+        "{stdout}"
+    );
+}
+
+#[tokio::test]
+async fn info_json_output_and_exit_code() {
+    let db = TestDatabase::new("migrate_info_json_output", "migrations_reversible");
+    db.run_migration(false, Some(20230401000000), false)
+        .success();
+
+    let info = || {
+        let output = Command::cargo_bin("cargo-sqlx")
+            .unwrap()
+            .args([
+                "sqlx",
+                "--format",
+                "json",
+                "migrate",
+                "info",
+                "--exit-code",
+                "--database-url",
+                &db.connection_string(),
+                "--source",
+                "tests/migrations_reversible",
+            ])
+            .output()
+            .unwrap();
+
+        let events: Vec<serde_json::Value> = output
+            .stdout
+            .split(|b| *b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect();
+
+        (output.status.code().unwrap(), events)
+    };
+
+    let (code, events) = info();
+    assert_eq!(code, 1);
+
+    let statuses: Vec<_> = events
+        .iter()
+        .filter(|event| event["event"] == "migration_status")
+        .map(|event| {
+            assert_eq!(
+                event["installed_on"].is_string(),
+                event["checksum"] == "ok",
+                "{event}"
+            );
+            (
+                event["version"].as_i64().unwrap(),
+                event["checksum"].as_str().unwrap(),
+                event["pending"].as_bool().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            (20230101000000, "ok", false),
+            (20230201000000, "ok", false),
+            (20230301000000, "ok", false),
+            (20230401000000, "ok", false),
+            (20230501000000, "missing_row", true),
+        ]
+    );
+
+    let finished = &events[events.len() - 2];
+    assert_eq!(finished["event"], "migrate_info_finished");
+    assert_eq!(finished["state"], "pending");
+    assert_eq!(finished["pending"], 1);
+    assert_eq!(events.last().unwrap()["event"], "error");
+
+    db.run_migration(false, None, false).success();
+
+    let (code, events) = info();
+    assert_eq!(code, 0);
+    assert_eq!(events.last().unwrap()["state"], "up_to_date");
+
+    // Simulate an edited migration and one whose file was deleted.
+    {
+        let mut conn = SqliteConnection::connect(&db.connection_string())
+            .await
+            .unwrap();
+        sqlx::query("UPDATE _sqlx_migrations SET checksum = x'00' WHERE version = 20230201000000")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time) \
+             VALUES (20230601000000, 'deleted', TRUE, x'00', 0)",
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    }
+
+    let (code, events) = info();
+    assert_eq!(code, 2);
+
+    let drifted: Vec<_> = events
+        .iter()
+        .filter(|event| event["event"] == "migration_status" && event["checksum"] != "ok")
+        .map(|event| {
+            (
+                event["version"].as_i64().unwrap(),
+                event["status"].as_str().unwrap(),
+                event["checksum"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        drifted,
+        [
+            (20230201000000, "checksum_mismatch", "mismatch"),
+            (20230601000000, "missing", "missing_file"),
+        ]
+    );
+    assert_eq!(events[events.len() - 2]["state"], "drift");
+    assert_eq!(events[events.len() - 2]["drifted"], 2);
+}
+
+#[tokio::test]
+async fn info_filters_listed_migrations() {
+    let db = TestDatabase::new("migrate_info_filter", "migrations_reversible");
+    db.run_migration(false, Some(20230301000000), false)
+        .success();
+
+    {
+        let mut conn = SqliteConnection::connect(&db.connection_string())
+            .await
+            .unwrap();
+        sqlx::query("UPDATE _sqlx_migrations SET checksum = x'00' WHERE version = 20230201000000")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+    }
+
+    let info = |filter: &[&str]| {
+        let output = Command::cargo_bin("cargo-sqlx")
+            .unwrap()
+            .args([
+                "sqlx",
+                "--format",
+                "json",
+                "migrate",
+                "info",
+                "--exit-code",
+                "--database-url",
+                &db.connection_string(),
+                "--source",
+                "tests/migrations_reversible",
+            ])
+            .args(filter)
+            .output()
+            .unwrap();
+
+        let versions: Vec<i64> = output
+            .stdout
+            .split(|b| *b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice::<serde_json::Value>(line).unwrap())
+            .filter(|event| event["event"] == "migration_status")
+            .map(|event| event["version"].as_i64().unwrap())
+            .collect();
+
+        (output.status.code().unwrap(), versions)
+    };
+
+    // the exit code accounts for all migrations, whichever are listed
+    assert_eq!(
+        info(&["--pending-only"]),
+        (2, vec![20230401000000, 20230501000000])
+    );
+    assert_eq!(info(&["--filter", "modified"]), (2, vec![20230201000000]));
+    assert_eq!(
+        info(&["--filter", "applied,modified"]),
+        (2, vec![20230101000000, 20230201000000, 20230301000000])
+    );
+}
@@ -0,0 +1 @@
+CREATE TABLE test1(x INTEGER PRIMARY KEY);
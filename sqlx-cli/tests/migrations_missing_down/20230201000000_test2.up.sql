@@ -0,0 +1 @@
+CREATE TABLE test2(x INTEGER PRIMARY KEY);
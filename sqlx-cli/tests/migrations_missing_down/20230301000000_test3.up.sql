@@ -0,0 +1 @@
+CREATE TABLE test3(x INTEGER PRIMARY KEY);
@@ -0,0 +1,121 @@
+use assert_cmd::Command;
+use sqlx::{Connection, SqliteConnection};
+
+#[tokio::test]
+async fn setup_and_run_seeds() {
+    let dir = tempfile::tempdir().unwrap();
+    let url = format!("sqlite://{}", dir.path().join("seed.db").display());
+    let seeds_dir = dir.path().join("seeds");
+    let seeds_dir = seeds_dir.to_str().unwrap();
+
+    let sqlx = |args: &[&str]| {
+        Command::cargo_bin("cargo-sqlx")
+            .unwrap()
+            .arg("sqlx")
+            .args(args)
+            .assert()
+    };
+
+    sqlx(&[
+        "seed",
+        "new",
+        "users",
+        "--seeds-dir",
+        seeds_dir,
+        "--seed-set",
+        "test",
+    ])
+    .success();
+
+    let seeds: Vec<_> = std::fs::read_dir(dir.path().join("seeds/test"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(seeds.len(), 1);
+    assert!(seeds[0].to_str().unwrap().ends_with("_users.sql"));
+    std::fs::write(&seeds[0], "INSERT INTO test1 VALUES (42);").unwrap();
+
+    sqlx(&[
+        "database",
+        "setup",
+        "--seed",
+        "--seeds-dir",
+        seeds_dir,
+        "--seed-set",
+        "test",
+        "--source",
+        "tests/migrations_reversible",
+        "--database-url",
+        &url,
+    ])
+    .success();
+
+    // The seeds were run once, and are tracked apart from the migrations.
+    let run = sqlx(&[
+        "seed",
+        "run",
+        "--seeds-dir",
+        seeds_dir,
+        "--seed-set",
+        "test",
+        "--database-url",
+        &url,
+    ])
+    .success();
+    let stdout = String::from_utf8_lossy(&run.get_output().stdout).into_owned();
+    assert!(!stdout.contains("Applied"), "{stdout}");
+
+    let mut conn = SqliteConnection::connect(&url).await.unwrap();
+    let seeded: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_seeds_test")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(seeded, 1);
+    let migrated: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_migrations")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(migrated, 5);
+    let rows: Vec<i64> = sqlx::query_scalar("SELECT x FROM test1")
+        .fetch_all(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(rows, [42]);
+    conn.close().await.unwrap();
+
+    // There is no folder for the default `dev` set.
+    sqlx(&[
+        "seed",
+        "run",
+        "--database-url",
+        &url,
+        "--seeds-dir",
+        seeds_dir,
+    ])
+    .failure();
+
+    // The seeds of another set are tracked apart from those of the `test` set.
+    sqlx(&["seed", "new", "users", "--seeds-dir", seeds_dir]).success();
+
+    sqlx(&[
+        "seed",
+        "run",
+        "--database-url",
+        &url,
+        "--seeds-dir",
+        seeds_dir,
+    ])
+    .success();
+
+    sqlx(&[
+        "seed",
+        "run",
+        "--seeds-dir",
+        seeds_dir,
+        "--seed-set",
+        "test",
+        "--database-url",
+        &url,
+    ])
+    .success();
+}
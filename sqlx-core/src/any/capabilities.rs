@@ -0,0 +1,71 @@
+/// What the database behind an [`AnyConnection`][crate::any::AnyConnection] supports.
+///
+/// Returned by [`AnyConnection::capabilities()`][crate::any::AnyConnection::capabilities], so
+/// that code written against `Any` can adapt its SQL at runtime instead of guessing from the
+/// scheme of the connection URL.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AnyCapabilities {
+    #[doc(hidden)]
+    pub supports_returning: bool,
+    #[doc(hidden)]
+    pub supports_savepoints: bool,
+    #[doc(hidden)]
+    pub placeholder_style: AnyPlaceholderStyle,
+    #[doc(hidden)]
+    pub max_bind_params: usize,
+}
+
+impl AnyCapabilities {
+    /// Whether `INSERT`, `UPDATE` and `DELETE` statements can have a `RETURNING` clause.
+    ///
+    /// This is `false` for MySQL, and for MariaDB before 10.5, which added `INSERT ... RETURNING`.
+    pub fn supports_returning(&self) -> bool {
+        self.supports_returning
+    }
+
+    /// Whether transactions can be nested with `SAVEPOINT`, as done by
+    /// [`Transaction::begin()`][crate::transaction::Transaction::begin] on a transaction.
+    pub fn supports_savepoints(&self) -> bool {
+        self.supports_savepoints
+    }
+
+    /// The syntax for bind parameters in queries.
+    pub fn placeholder_style(&self) -> AnyPlaceholderStyle {
+        self.placeholder_style
+    }
+
+    /// The maximum number of bind parameters in a single query.
+    ///
+    /// For SQLite, this is the default limit of the linked library, which can be lowered at
+    /// compile time or at runtime.
+    pub fn max_bind_params(&self) -> usize {
+        self.max_bind_params
+    }
+}
+
+/// The syntax for bind parameters in a query, as in [`AnyCapabilities::placeholder_style()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AnyPlaceholderStyle {
+    /// `?`, bound in the order they appear in the query (MySQL and SQLite).
+    QuestionMark,
+    /// `$1`, `$2`, etc. (Postgres).
+    Numbered,
+}
+
+impl AnyPlaceholderStyle {
+    /// The placeholder for the bind parameter at `position`, counting from 1.
+    ///
+    /// ```rust
+    /// use sqlx_core::any::AnyPlaceholderStyle;
+    ///
+    /// assert_eq!(AnyPlaceholderStyle::QuestionMark.placeholder(2), "?");
+    /// assert_eq!(AnyPlaceholderStyle::Numbered.placeholder(2), "$2");
+    /// ```
+    pub fn placeholder(self, position: usize) -> String {
+        match self {
+            AnyPlaceholderStyle::QuestionMark => "?".to_owned(),
+            AnyPlaceholderStyle::Numbered => format!("${position}"),
+        }
+    }
+}
@@ -1,5 +1,10 @@
-use crate::any::{Any, AnyArguments, AnyQueryResult, AnyRow, AnyStatement, AnyTypeInfo};
+use crate::any::{
+    Any, AnyArguments, AnyCapabilities, AnyPlaceholderStyle, AnyQueryResult, AnyRow, AnyStatement,
+    AnyTypeInfo,
+};
+use crate::connection::CachedStatements;
 use crate::describe::Describe;
+use crate::transaction::StatementJournal;
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
@@ -10,6 +15,23 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     /// The backend name.
     fn name(&self) -> &str;
 
+    /// What the database supports, for [`AnyConnection::capabilities()`].
+    ///
+    /// The default is conservative: `?` placeholders, no optional features, and the bind
+    /// parameter limit of SQLite before 3.32.0. Backends should override it; it is a provided
+    /// method to avoid a breaking change, but it will become a required method in version 0.9
+    /// and later.
+    ///
+    /// [`AnyConnection::capabilities()`]: crate::any::AnyConnection::capabilities
+    fn capabilities(&self) -> AnyCapabilities {
+        AnyCapabilities {
+            supports_returning: false,
+            supports_savepoints: false,
+            placeholder_style: AnyPlaceholderStyle::QuestionMark,
+            max_bind_params: 999,
+        }
+    }
+
     /// Explicitly close this database connection.
     ///
     /// This method is **not required** for safe and consistent operation. However, it is
@@ -72,6 +94,27 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
         Box::pin(async move { Ok(()) })
     }
 
+    /// Forward to [`Connection::cached_statements()`].
+    ///
+    /// [`Connection::cached_statements()`]: method@crate::connection::Connection::cached_statements
+    fn cached_statements(&self) -> CachedStatements {
+        CachedStatements {
+            size: self.cached_statements_size(),
+            capacity: 0,
+        }
+    }
+
+    /// Forward to [`Connection::set_statement_cache_capacity()`].
+    ///
+    /// [`Connection::set_statement_cache_capacity()`]: method@crate::connection::Connection::set_statement_cache_capacity
+    fn set_statement_cache_capacity(
+        &mut self,
+        capacity: usize,
+    ) -> BoxFuture<'_, crate::Result<()>> {
+        let _ = capacity;
+        Box::pin(async move { Ok(()) })
+    }
+
     /// Forward to [`Connection::shrink_buffers()`].
     ///
     /// [`Connection::shrink_buffers()`]: method@crate::connection::Connection::shrink_buffers
@@ -83,6 +126,20 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     #[doc(hidden)]
     fn should_flush(&self) -> bool;
 
+    /// Forward to [`Connection::set_statement_journal()`].
+    ///
+    /// Backends which don't override this never record any statements.
+    ///
+    /// [`Connection::set_statement_journal()`]: crate::connection::Connection::set_statement_journal
+    #[doc(hidden)]
+    fn set_statement_journal(
+        &mut self,
+        journal: Option<StatementJournal>,
+    ) -> Option<StatementJournal> {
+        drop(journal);
+        None
+    }
+
     #[cfg(feature = "migrate")]
     fn as_migrate(&mut self) -> crate::Result<&mut (dyn crate::migrate::Migrate + Send + 'static)> {
         Err(crate::Error::Configuration(
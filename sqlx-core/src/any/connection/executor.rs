@@ -1,13 +1,43 @@
-use crate::any::{Any, AnyConnection, AnyQueryResult, AnyRow, AnyStatement, AnyTypeInfo};
+use crate::any::{
+    Any, AnyArguments, AnyConnection, AnyConnectionBackend, AnyQueryResult, AnyRow,
+    AnySqlTranslator, AnyStatement, AnyTypeInfo,
+};
 use crate::describe::Describe;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
+use crate::statement::Statement;
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::{stream, FutureExt, StreamExt};
+use std::borrow::Cow;
 use std::future;
 
+// Rewrite `sql` with the translator of the connection, if any. The rewritten query is kept in
+// `translated` so that the backend can borrow it while it runs.
+fn translate<'a>(
+    translator: Option<AnySqlTranslator>,
+    backend: &dyn AnyConnectionBackend,
+    translated: &'a mut String,
+    sql: &'a str,
+    arguments: Option<AnyArguments<'a>>,
+) -> Result<(&'a str, Option<AnyArguments<'a>>), Error> {
+    let Some(translator) = translator else {
+        return Ok((sql, arguments));
+    };
+
+    let rewritten = translator.translate(sql, backend.capabilities().placeholder_style)?;
+    let arguments = rewritten.reorder_arguments(arguments)?;
+
+    match rewritten.into_sql() {
+        Cow::Borrowed(sql) => Ok((sql, arguments)),
+        Cow::Owned(sql) => {
+            *translated = sql;
+            Ok((translated, arguments))
+        }
+    }
+}
+
 impl<'c> Executor<'c> for &'c mut AnyConnection {
     type Database = Any;
 
@@ -23,8 +53,18 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
             Ok(arguments) => arguments,
             Err(error) => return stream::once(future::ready(Err(error))).boxed(),
         };
-        self.backend
-            .fetch_many(query.sql(), query.persistent(), arguments)
+        let persistent = query.persistent();
+        let AnyConnection {
+            backend,
+            translator,
+            translated,
+        } = self;
+        let (sql, arguments) =
+            match translate(*translator, &**backend, translated, query.sql(), arguments) {
+                Ok(translated) => translated,
+                Err(error) => return stream::once(future::ready(Err(error))).boxed(),
+            };
+        backend.fetch_many(sql, persistent, arguments)
     }
 
     fn fetch_optional<'e, 'q: 'e, E>(
@@ -39,8 +79,18 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
             Ok(arguments) => arguments,
             Err(error) => return future::ready(Err(error)).boxed(),
         };
-        self.backend
-            .fetch_optional(query.sql(), query.persistent(), arguments)
+        let persistent = query.persistent();
+        let AnyConnection {
+            backend,
+            translator,
+            translated,
+        } = self;
+        let (sql, arguments) =
+            match translate(*translator, &**backend, translated, query.sql(), arguments) {
+                Ok(translated) => translated,
+                Err(error) => return future::ready(Err(error)).boxed(),
+            };
+        backend.fetch_optional(sql, persistent, arguments)
     }
 
     fn prepare_with<'e, 'q: 'e>(
@@ -51,7 +101,28 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
     where
         'c: 'e,
     {
-        self.backend.prepare_with(sql, parameters)
+        if self.translator.is_none() {
+            return self.backend.prepare_with(sql, parameters);
+        }
+
+        let parameters = parameters.to_vec();
+
+        Box::pin(async move {
+            let AnyConnection {
+                backend,
+                translator,
+                translated,
+            } = self;
+            let (translated, _) = translate(*translator, &**backend, translated, sql, None)?;
+            let statement = backend.prepare_with(translated, &parameters).await?;
+
+            // keep the original query, so that it is translated again when the statement is
+            // executed and the arguments can be reordered
+            Ok(AnyStatement {
+                sql: Cow::Borrowed(sql),
+                ..statement.to_owned()
+            })
+        })
     }
 
     fn describe<'e, 'q: 'e>(
@@ -61,6 +132,18 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
     where
         'c: 'e,
     {
-        self.backend.describe(sql)
+        if self.translator.is_none() {
+            return self.backend.describe(sql);
+        }
+
+        Box::pin(async move {
+            let AnyConnection {
+                backend,
+                translator,
+                translated,
+            } = self;
+            let (translated, _) = translate(*translator, &**backend, translated, sql, None)?;
+            backend.describe(translated).await
+        })
     }
 }
@@ -1,14 +1,14 @@
 use futures_core::future::BoxFuture;
 use std::borrow::Cow;
 
-use crate::any::{Any, AnyConnectOptions};
-use crate::connection::{ConnectOptions, Connection};
+use crate::any::{Any, AnyCapabilities, AnyConnectOptions, AnySqlTranslator};
+use crate::connection::{CachedStatements, ConnectOptions, Connection};
 use crate::error::Error;
 
 use crate::database::Database;
 pub use backend::AnyConnectionBackend;
 
-use crate::transaction::Transaction;
+use crate::transaction::{StatementJournal, Transaction};
 
 mod backend;
 mod executor;
@@ -25,6 +25,9 @@ mod executor;
 #[derive(Debug)]
 pub struct AnyConnection {
     pub(crate) backend: Box<dyn AnyConnectionBackend>,
+    pub(crate) translator: Option<AnySqlTranslator>,
+    // the last query rewritten by `translator`, borrowed by the backend while it runs
+    pub(crate) translated: String,
 }
 
 impl AnyConnection {
@@ -33,6 +36,12 @@ impl AnyConnection {
         self.backend.name()
     }
 
+    /// Returns what the database backend in use supports, such as `RETURNING` clauses and the
+    /// syntax for bind parameters.
+    pub fn capabilities(&self) -> AnyCapabilities {
+        self.backend.capabilities()
+    }
+
     pub(crate) fn connect(options: &AnyConnectOptions) -> BoxFuture<'_, crate::Result<Self>> {
         Box::pin(async {
             let driver = crate::any::driver::from_url(&options.database_url)?;
@@ -49,12 +58,15 @@ impl AnyConnection {
             for<'a> TryFrom<&'a AnyConnectOptions, Error = Error>,
     {
         let res = TryFrom::try_from(options);
+        let translator = options.translator;
 
-        Box::pin(async {
+        Box::pin(async move {
             let options: <DB::Connection as Connection>::Options = res?;
 
             Ok(AnyConnection {
                 backend: Box::new(options.connect().await?),
+                translator,
+                translated: String::new(),
             })
         })
     }
@@ -109,6 +121,17 @@ impl Connection for AnyConnection {
         self.backend.clear_cached_statements()
     }
 
+    fn cached_statements(&self) -> CachedStatements {
+        self.backend.cached_statements()
+    }
+
+    fn set_statement_cache_capacity(
+        &mut self,
+        capacity: usize,
+    ) -> BoxFuture<'_, crate::Result<()>> {
+        self.backend.set_statement_cache_capacity(capacity)
+    }
+
     fn shrink_buffers(&mut self) {
         self.backend.shrink_buffers()
     }
@@ -122,4 +145,12 @@ impl Connection for AnyConnection {
     fn should_flush(&self) -> bool {
         self.backend.should_flush()
     }
+
+    #[doc(hidden)]
+    fn set_statement_journal(
+        &mut self,
+        journal: Option<StatementJournal>,
+    ) -> Option<StatementJournal> {
+        self.backend.set_statement_journal(journal)
+    }
 }
@@ -68,7 +68,11 @@ impl FromStr for AnyKind {
                 Err(Error::Configuration("database URL has the scheme of a MSSQL database but the `mssql` feature is not enabled".into()))
             }
 
-            _ => Err(Error::Configuration(format!("unrecognized database url: {url:?}").into()))
+            // only the scheme, as the rest of the URL may contain a password
+            _ => {
+                let scheme = url.split_once(':').map_or(url, |(scheme, _)| scheme);
+                Err(Error::Configuration(format!("unrecognized database URL scheme: {scheme:?}").into()))
+            }
         }
     }
 }
@@ -1,7 +1,9 @@
 use crate::any::driver;
 use crate::any::{Any, AnyConnection};
 use crate::error::Error;
-use crate::migrate::{AppliedMigration, Migrate, MigrateDatabase, MigrateError, Migration};
+use crate::migrate::{
+    AppliedMigration, Migrate, MigrateDatabase, MigrateError, Migration, MigrationMetadata,
+};
 use futures_core::future::BoxFuture;
 use std::time::Duration;
 
@@ -44,18 +46,61 @@ impl MigrateDatabase for Any {
 }
 
 impl Migrate for AnyConnection {
-    fn ensure_migrations_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
-        Box::pin(async { self.get_migrate()?.ensure_migrations_table().await })
+    fn ensure_migrations_table<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async {
+            self.get_migrate()?
+                .ensure_migrations_table(table_name)
+                .await
+        })
     }
 
-    fn dirty_version(&mut self) -> BoxFuture<'_, Result<Option<i64>, MigrateError>> {
-        Box::pin(async { self.get_migrate()?.dirty_version().await })
+    fn migrations_table_version<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<u32>, MigrateError>> {
+        Box::pin(async {
+            self.get_migrate()?
+                .migrations_table_version(table_name)
+                .await
+        })
     }
 
-    fn list_applied_migrations(
-        &mut self,
-    ) -> BoxFuture<'_, Result<Vec<AppliedMigration>, MigrateError>> {
-        Box::pin(async { self.get_migrate()?.list_applied_migrations().await })
+    fn upgrade_migrations_table<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        from: u32,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            self.get_migrate()?
+                .upgrade_migrations_table(table_name, from)
+                .await
+        })
+    }
+
+    fn dirty_version<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<i64>, MigrateError>> {
+        Box::pin(async { self.get_migrate()?.dirty_version(table_name).await })
+    }
+
+    fn list_applied_migrations<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>> {
+        Box::pin(async {
+            self.get_migrate()?
+                .list_applied_migrations(table_name)
+                .await
+        })
+    }
+
+    fn transactional_ddl(&mut self) -> bool {
+        self.get_migrate()
+            .map_or(true, |conn| conn.transactional_ddl())
     }
 
     fn lock(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
@@ -68,15 +113,96 @@ impl Migrate for AnyConnection {
 
     fn apply<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
-        Box::pin(async { self.get_migrate()?.apply(migration).await })
+        Box::pin(async { self.get_migrate()?.apply(table_name, migration).await })
+    }
+
+    fn apply_with_metadata<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+        metadata: &'m MigrationMetadata,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        Box::pin(async {
+            self.get_migrate()?
+                .apply_with_metadata(table_name, migration, metadata)
+                .await
+        })
+    }
+
+    fn ensure_migrations_table_script(&mut self, table_name: &str) -> Result<String, MigrateError> {
+        self.get_migrate()?
+            .ensure_migrations_table_script(table_name)
+    }
+
+    fn apply_script(
+        &mut self,
+        table_name: &str,
+        migration: &Migration,
+        metadata: &MigrationMetadata,
+    ) -> Result<String, MigrateError> {
+        self.get_migrate()?
+            .apply_script(table_name, migration, metadata)
     }
 
     fn revert<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
-        Box::pin(async { self.get_migrate()?.revert(migration).await })
+        Box::pin(async { self.get_migrate()?.revert(table_name, migration).await })
+    }
+
+    fn mark_applied<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+        metadata: &'m MigrationMetadata,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async {
+            self.get_migrate()?
+                .mark_applied(table_name, migration, metadata)
+                .await
+        })
+    }
+
+    fn mark_skipped<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+        metadata: &'m MigrationMetadata,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async {
+            self.get_migrate()?
+                .mark_skipped(table_name, migration, metadata)
+                .await
+        })
+    }
+
+    fn update_checksum<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        version: i64,
+        checksum: &'e [u8],
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            self.get_migrate()?
+                .update_checksum(table_name, version, checksum)
+                .await
+        })
+    }
+
+    fn remove_applied_migration<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        version: i64,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            self.get_migrate()?
+                .remove_applied_migration(table_name, version)
+                .await
+        })
     }
 }
@@ -6,6 +6,7 @@
 use crate::executor::Executor;
 
 mod arguments;
+mod capabilities;
 pub(crate) mod column;
 mod connection;
 mod database;
@@ -16,6 +17,7 @@ mod query_result;
 pub(crate) mod row;
 mod statement;
 mod transaction;
+mod translate;
 pub(crate) mod type_info;
 pub mod types;
 pub(crate) mod value;
@@ -26,6 +28,7 @@ pub mod driver;
 mod migrate;
 
 pub use arguments::{AnyArgumentBuffer, AnyArguments};
+pub use capabilities::{AnyCapabilities, AnyPlaceholderStyle};
 pub use column::AnyColumn;
 pub use connection::AnyConnection;
 // Used internally in `sqlx-macros`
@@ -40,6 +43,7 @@ pub use query_result::AnyQueryResult;
 pub use row::AnyRow;
 pub use statement::AnyStatement;
 pub use transaction::AnyTransactionManager;
+pub use translate::{AnySqlTranslator, AnyTranslatedSql};
 pub use type_info::{AnyTypeInfo, AnyTypeInfoKind};
 pub use value::{AnyValue, AnyValueRef};
 
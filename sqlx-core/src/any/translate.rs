@@ -0,0 +1,432 @@
+use std::borrow::Cow;
+
+use crate::any::{AnyArgumentBuffer, AnyArguments, AnyPlaceholderStyle};
+use crate::error::Error;
+
+/// Rewrites SQL written for one database so it can be run on another through
+/// [`AnyConnection`][crate::any::AnyConnection].
+///
+/// Enable it for a connection or a pool with
+/// [`AnyConnectOptions::translate_sql()`][crate::any::AnyConnectOptions::translate_sql], and
+/// every query is rewritten for the database in use before it is sent:
+///
+/// * Bind parameters are translated to the [placeholder style][AnyPlaceholderStyle] of the
+///   database: `?` becomes `$1`, `$2`, etc., for Postgres, and `$n` (or the SQLite form `?n`)
+///   becomes `?` for MySQL and SQLite. Numbered placeholders used out of order or more than
+///   once are supported by reordering or repeating the arguments.
+/// * Comments are removed, including nested block comments, which only Postgres supports.
+///   MySQL executable comments and optimizer hints, `/*! ... */` and `/*+ ... */`, are kept.
+///
+/// String literals, quoted identifiers and Postgres dollar-quoted strings are left untouched.
+/// Backslash escapes are only recognized in Postgres `E'...'` strings, so a MySQL string
+/// containing `\'` can confuse the translation. With placeholder translation on, a `?` outside
+/// of a string is always a placeholder, so the `jsonb` operators `?`, `?|` and `?&` must be
+/// written as the functions `jsonb_exists()`, `jsonb_exists_any()` and `jsonb_exists_all()`.
+///
+/// ```rust
+/// use sqlx_core::any::{AnyPlaceholderStyle, AnySqlTranslator};
+///
+/// let translator = AnySqlTranslator::new();
+///
+/// let sql = translator.translate(
+///     "SELECT * FROM users /* active only */ WHERE active AND id = ?",
+///     AnyPlaceholderStyle::Numbered,
+/// )?;
+/// assert_eq!(sql.sql(), "SELECT * FROM users   WHERE active AND id = $1");
+///
+/// let sql = translator.translate(
+///     "SELECT * FROM users WHERE id = $2 AND tenant = $1",
+///     AnyPlaceholderStyle::QuestionMark,
+/// )?;
+/// assert_eq!(sql.sql(), "SELECT * FROM users WHERE id = ? AND tenant = ?");
+/// assert_eq!(sql.argument_order(), Some(&[1, 0][..]));
+/// # Ok::<(), sqlx_core::Error>(())
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AnySqlTranslator {
+    placeholders: bool,
+    strip_comments: bool,
+}
+
+impl AnySqlTranslator {
+    /// Translate placeholders and remove comments.
+    pub fn new() -> Self {
+        Self {
+            placeholders: true,
+            strip_comments: true,
+        }
+    }
+
+    /// Whether to translate placeholders. Defaults to `true`.
+    pub fn placeholders(mut self, translate: bool) -> Self {
+        self.placeholders = translate;
+        self
+    }
+
+    /// Whether to remove comments. Defaults to `true`.
+    pub fn strip_comments(mut self, strip: bool) -> Self {
+        self.strip_comments = strip;
+        self
+    }
+
+    /// Rewrite `sql` with placeholders in `style`.
+    ///
+    /// Returns [`Error::InvalidArgument`] if the query mixes `?` with numbered placeholders,
+    /// or has the placeholder `$0`.
+    pub fn translate<'s>(
+        &self,
+        sql: &'s str,
+        style: AnyPlaceholderStyle,
+    ) -> Result<AnyTranslatedSql<'s>, Error> {
+        let bytes = sql.as_bytes();
+
+        let mut out = String::new();
+        // `sql[copied..i]` is yet to be written to `out`
+        let mut copied = 0;
+
+        let mut replace = |start: usize, end: usize, with: &str| {
+            out.push_str(&sql[copied..start]);
+            out.push_str(with);
+            copied = end;
+        };
+
+        let mut positional = 0;
+        let mut numbered = Vec::new();
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let next = bytes.get(i + 1).copied();
+
+            match bytes[i] {
+                quote @ (b'\'' | b'"' | b'`') => {
+                    let escapes = quote == b'\''
+                        && i > 0
+                        && bytes[i - 1].eq_ignore_ascii_case(&b'e')
+                        && (i < 2 || !is_ident(bytes[i - 2]));
+
+                    i = skip_quoted(bytes, i, escapes);
+                }
+
+                b'-' if next == Some(b'-') => {
+                    let end = sql[i..].find('\n').map_or(sql.len(), |len| i + len);
+
+                    if self.strip_comments {
+                        replace(i, end, "");
+                    }
+
+                    i = end;
+                }
+
+                b'/' if next == Some(b'*') => {
+                    let end = skip_block_comment(bytes, i);
+                    let hint = matches!(bytes.get(i + 2), Some(b'!' | b'+'));
+
+                    if self.strip_comments && !hint {
+                        replace(i, end, " ");
+                    }
+
+                    i = end;
+                }
+
+                b'$' if i == 0 || !is_ident(bytes[i - 1]) => {
+                    if let Some((number, end)) = parse_number(sql, i + 1)? {
+                        numbered.push(number);
+
+                        if self.placeholders && style == AnyPlaceholderStyle::QuestionMark {
+                            replace(i, end, "?");
+                        }
+
+                        i = end;
+                    } else {
+                        i = skip_dollar_quoted(sql, i);
+                    }
+                }
+
+                b'?' => {
+                    if let Some((number, end)) = parse_number(sql, i + 1)? {
+                        numbered.push(number);
+
+                        if self.placeholders {
+                            let with = match style {
+                                AnyPlaceholderStyle::QuestionMark => "?".to_owned(),
+                                AnyPlaceholderStyle::Numbered => format!("${}", number + 1),
+                            };
+
+                            replace(i, end, &with);
+                        }
+
+                        i = end;
+                    } else {
+                        positional += 1;
+
+                        if self.placeholders && style == AnyPlaceholderStyle::Numbered {
+                            replace(i, i + 1, &format!("${positional}"));
+                        }
+
+                        i += 1;
+                    }
+                }
+
+                _ => i += 1,
+            }
+        }
+
+        if self.placeholders && positional > 0 && !numbered.is_empty() {
+            return Err(Error::InvalidArgument(format!(
+                "cannot translate a query which mixes `?` with numbered placeholders: {sql}"
+            )));
+        }
+
+        let argument_order = (self.placeholders
+            && style == AnyPlaceholderStyle::QuestionMark
+            && numbered.iter().enumerate().any(|(i, n)| i != *n))
+        .then_some(numbered);
+
+        let sql = if copied == 0 {
+            Cow::Borrowed(sql)
+        } else {
+            out.push_str(&sql[copied..]);
+            Cow::Owned(out)
+        };
+
+        Ok(AnyTranslatedSql {
+            sql,
+            argument_order,
+        })
+    }
+}
+
+impl Default for AnySqlTranslator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A query rewritten by [`AnySqlTranslator::translate()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnyTranslatedSql<'s> {
+    sql: Cow<'s, str>,
+    argument_order: Option<Vec<usize>>,
+}
+
+impl<'s> AnyTranslatedSql<'s> {
+    /// The rewritten query.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// The rewritten query, borrowed from the original if nothing was changed.
+    pub fn into_sql(self) -> Cow<'s, str> {
+        self.sql
+    }
+
+    /// The index of the argument to bind to each placeholder, counting from 0, if the
+    /// arguments have to be reordered or repeated to match the rewritten query.
+    pub fn argument_order(&self) -> Option<&[usize]> {
+        self.argument_order.as_deref()
+    }
+
+    pub(crate) fn reorder_arguments<'q>(
+        &self,
+        arguments: Option<AnyArguments<'q>>,
+    ) -> Result<Option<AnyArguments<'q>>, Error> {
+        let (Some(order), Some(arguments)) = (&self.argument_order, arguments.as_ref()) else {
+            return Ok(arguments);
+        };
+
+        let values = &arguments.values.0;
+
+        let reordered = order
+            .iter()
+            .map(|&i| {
+                values.get(i).cloned().ok_or_else(|| {
+                    Error::InvalidArgument(format!(
+                        "placeholder ${} has no argument; {} were bound",
+                        i + 1,
+                        values.len()
+                    ))
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Some(AnyArguments {
+            values: AnyArgumentBuffer(reordered),
+        }))
+    }
+}
+
+fn is_ident(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || b >= 0x80
+}
+
+// Returns the index after the closing quote, or the end of `bytes` if there is none.
+fn skip_quoted(bytes: &[u8], start: usize, escapes: bool) -> usize {
+    let quote = bytes[start];
+    let mut i = start + 1;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if escapes => i += 2,
+            b if b == quote && bytes.get(i + 1) == Some(&quote) => i += 2,
+            b if b == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+
+    bytes.len()
+}
+
+fn skip_block_comment(bytes: &[u8], start: usize) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1)) {
+            (b'/', Some(b'*')) => {
+                depth += 1;
+                i += 2;
+            }
+            (b'*', Some(b'/')) => {
+                depth -= 1;
+                i += 2;
+
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+
+    bytes.len()
+}
+
+// `$tag$ ... $tag$`, where the tag may be empty. A `$` which doesn't start a tag is skipped.
+fn skip_dollar_quoted(sql: &str, start: usize) -> usize {
+    let rest = &sql[start + 1..];
+    let tag_len = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(rest.len());
+
+    if !rest[tag_len..].starts_with('$') {
+        return start + 1;
+    }
+
+    let tag = &sql[start..start + tag_len + 2];
+    let body = start + tag.len();
+
+    sql[body..]
+        .find(tag)
+        .map_or(sql.len(), |len| body + len + tag.len())
+}
+
+// The 0-based index of a numbered placeholder starting at `start`, and the index after it.
+fn parse_number(sql: &str, start: usize) -> Result<Option<(usize, usize)>, Error> {
+    let digits = sql[start..]
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(sql.len() - start);
+
+    if digits == 0 {
+        return Ok(None);
+    }
+
+    let end = start + digits;
+
+    match sql[start..end].parse::<usize>() {
+        Ok(number) if number > 0 => Ok(Some((number - 1, end))),
+        _ => Err(Error::InvalidArgument(format!(
+            "invalid placeholder {:?}",
+            &sql[start - 1..end]
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translate(sql: &str, style: AnyPlaceholderStyle) -> (String, Option<Vec<usize>>) {
+        let translated = AnySqlTranslator::new().translate(sql, style).unwrap();
+        let order = translated.argument_order().map(<[usize]>::to_vec);
+
+        (translated.into_sql().into_owned(), order)
+    }
+
+    #[test]
+    fn test_translate_to_numbered() {
+        assert_eq!(
+            translate(
+                "SELECT ?, '?', \"?\", $$ ? $$, $tag$ ' ? $tag$, ?",
+                AnyPlaceholderStyle::Numbered
+            ),
+            (
+                "SELECT $1, '?', \"?\", $$ ? $$, $tag$ ' ? $tag$, $2".to_owned(),
+                None
+            )
+        );
+
+        assert_eq!(
+            translate("SELECT ?2, ?1", AnyPlaceholderStyle::Numbered),
+            ("SELECT $2, $1".to_owned(), None)
+        );
+    }
+
+    #[test]
+    fn test_translate_to_question_mark() {
+        assert_eq!(
+            translate("SELECT $1, $2 FROM a$1", AnyPlaceholderStyle::QuestionMark),
+            ("SELECT ?, ? FROM a$1".to_owned(), None)
+        );
+
+        assert_eq!(
+            translate(
+                "SELECT $2, E'\\'$1', $1, $2",
+                AnyPlaceholderStyle::QuestionMark
+            ),
+            ("SELECT ?, E'\\'$1', ?, ?".to_owned(), Some(vec![1, 0, 1]))
+        );
+    }
+
+    #[test]
+    fn test_strip_comments() {
+        assert_eq!(
+            translate(
+                "SELECT 1 -- one\n, /* two /* nested */ ? */ 2 /*+ hint */ /*! 3 */",
+                AnyPlaceholderStyle::Numbered
+            ),
+            ("SELECT 1 \n,   2 /*+ hint */ /*! 3 */".to_owned(), None)
+        );
+
+        let sql = "SELECT ? /* ? */";
+        let translated = AnySqlTranslator::new()
+            .strip_comments(false)
+            .translate(sql, AnyPlaceholderStyle::Numbered)
+            .unwrap();
+        assert_eq!(translated.sql(), "SELECT $1 /* ? */");
+
+        let translated = AnySqlTranslator::new()
+            .placeholders(false)
+            .translate(sql, AnyPlaceholderStyle::Numbered)
+            .unwrap();
+        assert_eq!(translated.sql(), "SELECT ?  ");
+    }
+
+    #[test]
+    fn test_translate_errors() {
+        let translator = AnySqlTranslator::new();
+
+        assert!(translator
+            .translate("SELECT ?, $1", AnyPlaceholderStyle::Numbered)
+            .is_err());
+        assert!(translator
+            .translate("SELECT $0", AnyPlaceholderStyle::QuestionMark)
+            .is_err());
+        assert!(matches!(
+            translator
+                .translate("SELECT 1", AnyPlaceholderStyle::Numbered)
+                .unwrap()
+                .into_sql(),
+            Cow::Borrowed(_)
+        ));
+    }
+}
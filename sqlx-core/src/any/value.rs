@@ -2,7 +2,7 @@ use std::borrow::Cow;
 
 use crate::any::{Any, AnyTypeInfo, AnyTypeInfoKind};
 use crate::database::Database;
-use crate::error::BoxDynError;
+use crate::error::{BoxDynError, IntegerOutOfRangeError};
 use crate::types::Type;
 use crate::value::{Value, ValueRef};
 
@@ -44,14 +44,11 @@ impl AnyValueKind<'_> {
     pub(in crate::any) fn try_integer<T>(&self) -> Result<T, BoxDynError>
     where
         T: Type<Any> + TryFrom<i16> + TryFrom<i32> + TryFrom<i64>,
-        BoxDynError: From<<T as TryFrom<i16>>::Error>,
-        BoxDynError: From<<T as TryFrom<i32>>::Error>,
-        BoxDynError: From<<T as TryFrom<i64>>::Error>,
     {
         Ok(match self {
-            AnyValueKind::SmallInt(i) => (*i).try_into()?,
-            AnyValueKind::Integer(i) => (*i).try_into()?,
-            AnyValueKind::BigInt(i) => (*i).try_into()?,
+            AnyValueKind::SmallInt(i) => IntegerOutOfRangeError::convert(*i)?,
+            AnyValueKind::Integer(i) => IntegerOutOfRangeError::convert(*i)?,
+            AnyValueKind::BigInt(i) => IntegerOutOfRangeError::convert(*i)?,
             _ => return self.unexpected(),
         })
     }
@@ -123,3 +120,23 @@ impl<'a> ValueRef<'a> for AnyValueRef<'a> {
         matches!(self.kind, AnyValueKind::Null(_))
     }
 }
+
+#[cfg(feature = "json")]
+impl crate::json_writer::DecodeJson for Any {
+    fn decode_json(value: AnyValueRef<'_>) -> Result<crate::types::JsonValue, BoxDynError> {
+        use crate::json_writer::{bytes_to_json, f32_to_json, float_to_json};
+        use crate::types::JsonValue;
+
+        Ok(match value.kind {
+            AnyValueKind::Null(_) => JsonValue::Null,
+            AnyValueKind::Bool(b) => b.into(),
+            AnyValueKind::SmallInt(i) => i.into(),
+            AnyValueKind::Integer(i) => i.into(),
+            AnyValueKind::BigInt(i) => i.into(),
+            AnyValueKind::Real(r) => f32_to_json(r),
+            AnyValueKind::Double(d) => float_to_json(d),
+            AnyValueKind::Text(text) => text.into_owned().into(),
+            AnyValueKind::Blob(blob) => bytes_to_json(&blob),
+        })
+    }
+}
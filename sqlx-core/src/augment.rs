@@ -0,0 +1,511 @@
+//! Rewriting queries to only see some of the rows of selected tables, such as soft-deleted or
+//! historical rows, with [`QueryAugmenter`].
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::script::{find, is_ident_byte};
+
+/// A comment containing this marker anywhere in a query stops [`QueryAugmenter::augment()`]
+/// from changing it.
+pub const UNAUGMENTED_MARKER: &str = "sqlx:unaugmented";
+
+/// Rewrites queries so that configured tables only show the rows matching their filters.
+///
+/// Each reference to a configured table after `FROM` or `JOIN`, or in a comma-separated `FROM`
+/// list, is replaced by a derived table with the filters applied, aliased to the original name:
+///
+/// ```text
+/// SELECT u.name FROM users u JOIN posts ON posts.user_id = u.id
+/// -- becomes
+/// SELECT u.name FROM (SELECT * FROM users WHERE (deleted_at IS NULL)) u JOIN posts ON ...
+/// ```
+///
+/// This leaves the rest of the query, including its bind parameters, untouched, so it works the
+/// same whether the query has a `WHERE` clause or not. The target of `DELETE FROM` is never
+/// rewritten, nor are `INSERT` and `UPDATE` targets, so filtered rows can still be modified.
+///
+/// Table names are matched case-insensitively, including any schema qualifier as written, so a
+/// filter for `users` does not apply to `public.users`. Qualify a name differently from the
+/// configuration to refer to the unfiltered table in a single place, add a comment with
+/// [`UNAUGMENTED_MARKER`] to leave a whole query alone, or use [`without()`][Self::without].
+///
+/// Filters are inserted into the query as-is; they must not contain bind parameters or
+/// untrusted input.
+///
+/// ```rust
+/// use sqlx_core::augment::QueryAugmenter;
+///
+/// let augmenter = QueryAugmenter::new()
+///     .soft_delete("users", "deleted_at")
+///     .as_of("prices", "FOR SYSTEM_TIME AS OF TIMESTAMP '2024-01-01 00:00:00'");
+///
+/// assert_eq!(
+///     augmenter.augment("SELECT * FROM users WHERE id = $1"),
+///     "SELECT * FROM (SELECT * FROM users WHERE (deleted_at IS NULL)) AS users WHERE id = $1"
+/// );
+///
+/// assert_eq!(
+///     augmenter.augment("SELECT p.amount FROM prices p"),
+///     "SELECT p.amount FROM \
+///      (SELECT * FROM prices FOR SYSTEM_TIME AS OF TIMESTAMP '2024-01-01 00:00:00') p"
+/// );
+///
+/// let sql = "SELECT * FROM users /* sqlx:unaugmented */";
+/// assert_eq!(augmenter.augment(sql), sql);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct QueryAugmenter {
+    tables: HashMap<String, TableFilter>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TableFilter {
+    /// Pushed right after the table name, e.g. `FOR SYSTEM_TIME AS OF ...`.
+    clauses: Vec<String>,
+    /// Combined with `AND` into a `WHERE` clause.
+    predicates: Vec<String>,
+}
+
+impl QueryAugmenter {
+    /// An augmenter which does not change any queries.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hide the rows of `table` where `column` is not `NULL`.
+    pub fn soft_delete(self, table: &str, column: &str) -> Self {
+        self.filter(table, format!("{column} IS NULL"))
+    }
+
+    /// Only show the rows of `table` matching `predicate`.
+    ///
+    /// May be called more than once for the same table; all of the predicates must match.
+    pub fn filter(mut self, table: &str, predicate: impl Into<String>) -> Self {
+        self.table(table).predicates.push(predicate.into());
+        self
+    }
+
+    /// Select from `table` with a temporal clause, such as MariaDB's
+    /// `FOR SYSTEM_TIME AS OF TIMESTAMP '...'`, placed right after the table name.
+    pub fn as_of(mut self, table: &str, clause: impl Into<String>) -> Self {
+        self.table(table).clauses.push(clause.into());
+        self
+    }
+
+    /// A copy of this augmenter which leaves `table` unfiltered.
+    pub fn without(&self, table: &str) -> Self {
+        let mut this = self.clone();
+        this.tables.remove(&table.to_ascii_lowercase());
+        this
+    }
+
+    fn table(&mut self, table: &str) -> &mut TableFilter {
+        self.tables.entry(table.to_ascii_lowercase()).or_default()
+    }
+
+    /// Rewrite `sql` so that the configured tables are filtered.
+    ///
+    /// Returns `sql` unchanged if it does not refer to any of them.
+    pub fn augment<'s>(&self, sql: &'s str) -> Cow<'s, str> {
+        if self.tables.is_empty() || sql.contains(UNAUGMENTED_MARKER) {
+            return Cow::Borrowed(sql);
+        }
+
+        let tokens = tokenize(sql);
+        let mut replacements = Vec::new();
+
+        // whether each level of parentheses is in a list of `FROM` items
+        let mut from_lists = vec![false];
+        let mut expect_table = false;
+        let mut prev_word = None;
+        let mut i = 0;
+
+        while i < tokens.len() {
+            let token = &tokens[i];
+            let text = &sql[token.span.clone()];
+
+            if std::mem::take(&mut expect_table) {
+                let is_word =
+                    |word: &str| token.kind == Kind::Word && text.eq_ignore_ascii_case(word);
+
+                // `FROM ONLY table` is kept together inside the derived table
+                let name_start = if is_word("ONLY") { i + 1 } else { i };
+
+                if let Some((end, replacement)) =
+                    self.replace_reference(sql, &tokens, i, name_start)
+                {
+                    replacements.push((token.start()..tokens[end - 1].end(), replacement));
+                    prev_word = None;
+                    i = end;
+                    continue;
+                }
+
+                // `JOIN LATERAL (...)`
+                expect_table = is_word("LATERAL");
+            }
+
+            match token.kind {
+                Kind::Word => match &*text.to_ascii_uppercase() {
+                    "FROM" => {
+                        if !prev_word.is_some_and(|w: &str| w.eq_ignore_ascii_case("DELETE")) {
+                            expect_table = true;
+                            *from_lists.last_mut().expect("BUG: no FROM list state") = true;
+                        }
+                    }
+                    "JOIN" => expect_table = true,
+                    "WHERE" | "GROUP" | "HAVING" | "ORDER" | "LIMIT" | "OFFSET" | "FETCH"
+                    | "FOR" | "UNION" | "EXCEPT" | "INTERSECT" | "WINDOW" | "RETURNING" | "SET"
+                    | "SELECT" | "VALUES" => {
+                        *from_lists.last_mut().expect("BUG: no FROM list state") = false;
+                    }
+                    _ => {}
+                },
+                Kind::Punct(b'(') => from_lists.push(false),
+                Kind::Punct(b')') => {
+                    if from_lists.len() > 1 {
+                        from_lists.pop();
+                    }
+                }
+                Kind::Punct(b',') => {
+                    expect_table = *from_lists.last().expect("BUG: no FROM list state");
+                }
+                _ => {}
+            }
+
+            prev_word = (token.kind == Kind::Word).then_some(text);
+            i += 1;
+        }
+
+        if replacements.is_empty() {
+            return Cow::Borrowed(sql);
+        }
+
+        let mut augmented = String::with_capacity(sql.len() + replacements.len() * 64);
+        let mut copied = 0;
+
+        for (span, replacement) in replacements {
+            augmented.push_str(&sql[copied..span.start]);
+            augmented.push_str(&replacement);
+            copied = span.end;
+        }
+
+        augmented.push_str(&sql[copied..]);
+
+        Cow::Owned(augmented)
+    }
+
+    /// If the table reference starting at `tokens[start]` names a configured table, returns the
+    /// index of the token after the reference and its alias, and the filtered derived table to
+    /// replace them with.
+    ///
+    /// The name of the table starts at `tokens[name_start]`, after any `ONLY`.
+    fn replace_reference(
+        &self,
+        sql: &str,
+        tokens: &[Token],
+        start: usize,
+        name_start: usize,
+    ) -> Option<(usize, String)> {
+        if !tokens.get(name_start).is_some_and(Token::is_name) {
+            return None;
+        }
+
+        // a possibly schema-qualified name
+        let mut end = name_start + 1;
+
+        while tokens.get(end).map(|t| t.kind) == Some(Kind::Punct(b'.'))
+            && tokens.get(end + 1).is_some_and(Token::is_name)
+        {
+            end += 2;
+        }
+
+        // a table-valued function
+        if tokens.get(end).map(|t| t.kind) == Some(Kind::Punct(b'(')) {
+            return None;
+        }
+
+        let name = tokens[name_start..end]
+            .iter()
+            .step_by(2)
+            .map(|t| t.unquoted(sql).to_ascii_lowercase())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let filter = self.tables.get(&name)?;
+
+        let reference = &sql[tokens[start].start()..tokens[end - 1].end()];
+
+        let mut replacement = format!("(SELECT * FROM {reference}");
+
+        for clause in &filter.clauses {
+            replacement.push(' ');
+            replacement.push_str(clause);
+        }
+
+        for (n, predicate) in filter.predicates.iter().enumerate() {
+            replacement.push_str(if n == 0 { " WHERE (" } else { " AND (" });
+            replacement.push_str(predicate);
+            replacement.push(')');
+        }
+
+        replacement.push_str(") ");
+
+        let alias_end = match tokens.get(end) {
+            Some(t) if t.kind == Kind::Word && sql[t.span.clone()].eq_ignore_ascii_case("AS") => {
+                tokens.get(end + 1).filter(|t| t.is_name()).map(|_| end + 2)
+            }
+            Some(t) if t.kind == Kind::Quoted => Some(end + 1),
+            Some(t) if t.kind == Kind::Word && !is_reserved(&sql[t.span.clone()]) => Some(end + 1),
+            _ => None,
+        };
+
+        match alias_end {
+            Some(alias_end) => {
+                replacement.push_str(&sql[tokens[end].start()..tokens[alias_end - 1].end()]);
+                Some((alias_end, replacement))
+            }
+            None => {
+                replacement.push_str("AS ");
+                replacement.push_str(&sql[tokens[end - 1].span.clone()]);
+                Some((end, replacement))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Kind {
+    /// A keyword or unquoted identifier.
+    Word,
+    /// A quoted identifier.
+    Quoted,
+    Punct(u8),
+    /// Anything else, including literals and operators.
+    Other,
+}
+
+#[derive(Debug)]
+pub(crate) struct Token {
+    pub(crate) kind: Kind,
+    pub(crate) span: Range<usize>,
+}
+
+impl Token {
+    fn start(&self) -> usize {
+        self.span.start
+    }
+
+    fn end(&self) -> usize {
+        self.span.end
+    }
+
+    pub(crate) fn is_name(&self) -> bool {
+        matches!(self.kind, Kind::Word | Kind::Quoted)
+    }
+
+    pub(crate) fn unquoted<'s>(&self, sql: &'s str) -> &'s str {
+        let text = &sql[self.span.clone()];
+
+        match self.kind {
+            Kind::Quoted if text.len() >= 2 => &text[1..text.len() - 1],
+            _ => text,
+        }
+    }
+}
+
+/// Words which can follow a table reference but cannot be its alias.
+fn is_reserved(word: &str) -> bool {
+    const RESERVED: &[&str] = &[
+        "CROSS",
+        "EXCEPT",
+        "FETCH",
+        "FOR",
+        "FORCE",
+        "FULL",
+        "GROUP",
+        "HAVING",
+        "IGNORE",
+        "INDEXED",
+        "INNER",
+        "INTERSECT",
+        "JOIN",
+        "LATERAL",
+        "LEFT",
+        "LIMIT",
+        "NATURAL",
+        "NOT",
+        "OFFSET",
+        "ON",
+        "ORDER",
+        "OUTER",
+        "RETURNING",
+        "RIGHT",
+        "SET",
+        "STRAIGHT_JOIN",
+        "TABLESAMPLE",
+        "UNION",
+        "USE",
+        "USING",
+        "WHERE",
+        "WINDOW",
+    ];
+
+    RESERVED.iter().any(|r| word.eq_ignore_ascii_case(r))
+}
+
+pub(crate) fn tokenize(sql: &str) -> Vec<Token> {
+    let bytes = sql.as_bytes();
+
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+
+        let kind = match bytes[i] {
+            b if b.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = find(bytes, i + 2, b"\n").map_or(bytes.len(), |end| end + 1);
+                continue;
+            }
+
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = find(bytes, i + 2, b"*/").map_or(bytes.len(), |end| end + 2);
+                continue;
+            }
+
+            b'\'' => {
+                i = find(bytes, i + 1, b"'").map_or(bytes.len(), |end| end + 1);
+                Kind::Other
+            }
+
+            quote @ (b'"' | b'`') => {
+                i = find(bytes, i + 1, &[quote]).map_or(bytes.len(), |end| end + 1);
+                Kind::Quoted
+            }
+
+            b'$' => {
+                let tag_len = bytes[i + 1..]
+                    .iter()
+                    .take_while(|&&b| b != b'$' && is_ident_byte(b))
+                    .count();
+
+                if bytes.get(i + 1 + tag_len) == Some(&b'$') {
+                    let tag = &bytes[i..i + tag_len + 2];
+                    i = find(bytes, i + tag.len(), tag).map_or(bytes.len(), |end| end + tag.len());
+                } else {
+                    // a `$N` parameter
+                    i += 1 + tag_len;
+                }
+
+                Kind::Other
+            }
+
+            punct @ (b'(' | b')' | b',' | b'.') => {
+                i += 1;
+                Kind::Punct(punct)
+            }
+
+            b if is_ident_byte(b) => {
+                i += bytes[i..].iter().take_while(|&&b| is_ident_byte(b)).count();
+
+                if b.is_ascii_digit() {
+                    Kind::Other
+                } else {
+                    Kind::Word
+                }
+            }
+
+            _ => {
+                i += 1;
+                Kind::Other
+            }
+        };
+
+        tokens.push(Token {
+            kind,
+            span: start..i,
+        });
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryAugmenter;
+
+    fn augmenter() -> QueryAugmenter {
+        QueryAugmenter::new()
+            .soft_delete("users", "deleted_at")
+            .filter("users", "tenant_id = 1")
+            .soft_delete("app.posts", "removed_at")
+    }
+
+    #[test]
+    fn filters_from_and_join() {
+        assert_eq!(
+            augmenter().augment(
+                "SELECT u.id, p.id FROM Users AS u LEFT JOIN app.posts p ON p.user_id = u.id \
+                 WHERE u.id = $1"
+            ),
+            "SELECT u.id, p.id FROM \
+             (SELECT * FROM Users WHERE (deleted_at IS NULL) AND (tenant_id = 1)) AS u \
+             LEFT JOIN (SELECT * FROM app.posts WHERE (removed_at IS NULL)) p \
+             ON p.user_id = u.id WHERE u.id = $1"
+        );
+    }
+
+    #[test]
+    fn filters_from_lists_and_subqueries() {
+        assert_eq!(
+            augmenter().augment(
+                "SELECT count(*) FROM posts, users WHERE EXISTS (SELECT 1 FROM users x, app.posts)"
+            ),
+            "SELECT count(*) FROM posts, \
+             (SELECT * FROM users WHERE (deleted_at IS NULL) AND (tenant_id = 1)) AS users \
+             WHERE EXISTS (SELECT 1 FROM \
+             (SELECT * FROM users WHERE (deleted_at IS NULL) AND (tenant_id = 1)) x, \
+             (SELECT * FROM app.posts WHERE (removed_at IS NULL)) AS posts)"
+        );
+    }
+
+    #[test]
+    fn leaves_writes_and_other_names_alone() {
+        let augmenter = augmenter();
+
+        for sql in [
+            "DELETE FROM users WHERE id = ?",
+            "UPDATE users SET name = 'FROM users' WHERE id = ?",
+            "INSERT INTO users (name) VALUES ('x') RETURNING id",
+            "SELECT * FROM \"public\".users, posts",
+            "SELECT * FROM users_archive JOIN users(1) ON true",
+            "SELECT $$ FROM users $$, col AS users FROM t -- FROM users",
+            "SELECT * FROM users /* sqlx:unaugmented */",
+        ] {
+            assert_eq!(augmenter.augment(sql), sql);
+        }
+
+        assert_eq!(
+            augmenter.without("users").augment("SELECT * FROM users"),
+            "SELECT * FROM users"
+        );
+    }
+
+    #[test]
+    fn places_temporal_clauses_after_the_name() {
+        let augmenter = QueryAugmenter::new().as_of("prices", "FOR SYSTEM_TIME AS OF NOW()");
+
+        assert_eq!(
+            augmenter.augment("SELECT * FROM ONLY prices ORDER BY id"),
+            "SELECT * FROM (SELECT * FROM ONLY prices FOR SYSTEM_TIME AS OF NOW()) AS prices \
+             ORDER BY id"
+        );
+    }
+}
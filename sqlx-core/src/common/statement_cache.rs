@@ -47,6 +47,11 @@ impl<T> StatementCache<T> {
         self.inner.is_empty()
     }
 
+    /// Removes the statement for the given key from the cache, if any.
+    pub fn remove(&mut self, k: &str) -> Option<T> {
+        self.inner.remove(k)
+    }
+
     /// Removes the least recently used item from the cache.
     pub fn remove_lru(&mut self) -> Option<T> {
         self.inner.remove_lru().map(|(_, v)| v)
@@ -67,6 +72,20 @@ impl<T> StatementCache<T> {
         self.inner.capacity()
     }
 
+    /// Changes the maximum number of statements the cache can hold, returning
+    /// the least recently used statements which no longer fit.
+    pub fn set_capacity(&mut self, capacity: usize) -> Vec<T> {
+        let mut evicted = Vec::new();
+
+        while self.len() > capacity {
+            evicted.extend(self.remove_lru());
+        }
+
+        self.inner.set_capacity(capacity);
+
+        evicted
+    }
+
     /// Returns true if the cache capacity is more than 0.
     #[allow(dead_code)] // Only used for some `cfg`s
     pub fn is_enabled(&self) -> bool {
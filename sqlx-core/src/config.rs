@@ -0,0 +1,366 @@
+//! Project configuration read from a `sqlx.toml` file.
+//!
+//! `sqlx-cli` and the query macros look for `sqlx.toml` in the current directory (the crate
+//! directory, for the macros) and then in each of its parents, so one file at the root of a
+//! workspace applies to all of its crates. Every key is optional:
+//!
+//! ```toml
+//! # Used when neither `--database-url` nor `DATABASE_URL` is set.
+//! database-url = "postgres://localhost/app_dev"
+//!
+//! # Selected with `--profile` or `SQLX_PROFILE`; `dev` is used by default and falls back
+//! # to the top-level `database-url` if it isn't defined.
+//! [profiles.test]
+//! database-url = "postgres://localhost/app_test"
+//! # Migrations not applied to the databases of this profile, in addition to `migrate.skip`.
+//! skip-migrations = [20240102000000]
+//!
+//! [migrate]
+//! # Relative to the directory containing `sqlx.toml`.
+//! source = "db/migrations"
+//! table = "_sqlx_migrations"
+//! ignore-missing = false
+//! # Migrations which are recorded as skipped instead of being applied, like `--skip`.
+//! skip = []
+//!
+//! [offline]
+//! # Like `SQLX_OFFLINE=true`.
+//! enabled = false
+//! # Like `SQLX_OFFLINE_DIR`, relative to the directory containing `sqlx.toml`.
+//! dir = ".sqlx"
+//! # Save the query data of each database in its own directory, like `.sqlx/postgres`, to check
+//! # the queries offline against several of them.
+//! per-driver = false
+//!
+//! # The databases `cargo sqlx prepare` saves query data for, implying `per-driver`.
+//! [offline.databases.postgres]
+//! database-url = "postgres://localhost/app_dev"
+//! # Passed to `cargo check` with `--features`.
+//! features = ["postgres"]
+//!
+//! [cli]
+//! connect-timeout = 10
+//! ```
+//!
+//! Command-line flags and environment variables take precedence over the values in the file.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+/// The name of the configuration file.
+pub const FILE_NAME: &str = "sqlx.toml";
+
+/// The profile used when none is selected.
+pub const DEFAULT_PROFILE: &str = "dev";
+
+/// The contents of a `sqlx.toml` file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    /// The database URL used by profiles that don't set their own.
+    pub database_url: Option<String>,
+
+    /// Settings by profile name, e.g. `dev`, `test` or `prod`.
+    pub profiles: BTreeMap<String, Profile>,
+
+    /// Settings for migrations.
+    pub migrate: MigrateConfig,
+
+    /// Settings for offline builds of the query macros.
+    pub offline: OfflineConfig,
+
+    /// Defaults for the flags of `sqlx-cli`.
+    pub cli: CliConfig,
+}
+
+/// The `[profiles.<name>]` tables of `sqlx.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Profile {
+    /// The database URL of the profile.
+    pub database_url: Option<String>,
+
+    /// The versions of migrations which are intentionally not applied to the databases of the
+    /// profile, in addition to [`MigrateConfig::skip`].
+    pub skip_migrations: Vec<i64>,
+}
+
+/// The `[migrate]` table of `sqlx.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct MigrateConfig {
+    /// The migrations directory, instead of `migrations`.
+    pub source: Option<PathBuf>,
+
+    /// The table tracking applied migrations, instead of `_sqlx_migrations`.
+    pub table: Option<String>,
+
+    /// Ignore applied migrations that are missing in the resolved migrations.
+    pub ignore_missing: bool,
+
+    /// The versions of migrations which are intentionally not applied, and recorded as skipped
+    /// in the migrations table instead.
+    pub skip: Vec<i64>,
+}
+
+/// The `[offline]` table of `sqlx.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct OfflineConfig {
+    /// Only use the saved query data, even if a database URL is set.
+    pub enabled: bool,
+
+    /// The directory of the saved query data, instead of `.sqlx`.
+    pub dir: Option<PathBuf>,
+
+    /// Save the data of each query as `query-<hash>.<database>.json` in a directory per
+    /// database, e.g. `.sqlx/postgres/query-<hash>.postgres.json`, rather than as
+    /// `query-<hash>.json` in the offline directory itself, so that the data saved by
+    /// `cargo sqlx prepare` for several databases can be kept side by side.
+    ///
+    /// Offline, the query macros then check each query against the data for the database of
+    /// `DATABASE_URL`, if it is set, or the only database for which there is data among those
+    /// with an enabled driver.
+    pub per_driver: bool,
+
+    /// The databases `cargo sqlx prepare` saves query data for, by name (`postgres`, `mysql` or
+    /// `sqlite`). Setting any implies [`per_driver`][Self::per_driver].
+    pub databases: BTreeMap<String, OfflineDatabase>,
+}
+
+impl OfflineConfig {
+    /// Whether the data of the queries is saved in a directory per database.
+    pub fn is_per_driver(&self) -> bool {
+        self.per_driver || !self.databases.is_empty()
+    }
+}
+
+/// The `[offline.databases.<name>]` tables of `sqlx.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct OfflineDatabase {
+    /// The URL of the database to prepare the queries against, instead of the one given to
+    /// `cargo sqlx prepare`.
+    pub database_url: Option<String>,
+
+    /// The Cargo features enabling the queries for the database, passed to `cargo check` by
+    /// `cargo sqlx prepare`.
+    pub features: Vec<String>,
+}
+
+/// The `[cli]` table of `sqlx.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct CliConfig {
+    /// The default of `--connect-timeout`, in seconds.
+    pub connect_timeout: Option<u64>,
+}
+
+/// An error reading a `sqlx.toml` file.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ConfigError {
+    #[error("error reading {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("error parsing {}: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+
+    #[error("profile {0:?} is not defined in {FILE_NAME}")]
+    UnknownProfile(String),
+}
+
+impl Config {
+    /// Read the `sqlx.toml` in `dir` or the nearest of its parents, returning its path along
+    /// with the configuration, or `None` if there is none.
+    pub fn find(dir: &Path) -> Result<Option<(PathBuf, Config)>, ConfigError> {
+        for dir in dir.ancestors() {
+            let path = dir.join(FILE_NAME);
+
+            if path.is_file() {
+                return Config::from_path(&path).map(|config| Some((path, config)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Read a configuration file, resolving the paths in it relative to its directory.
+    pub fn from_path(path: &Path) -> Result<Config, ConfigError> {
+        let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        let mut config: Config = contents.parse().map_err(|source| ConfigError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        if let Some(dir) = path.parent() {
+            for path in [&mut config.migrate.source, &mut config.offline.dir]
+                .into_iter()
+                .flatten()
+            {
+                *path = dir.join(&*path);
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// The database URL of `profile`.
+    ///
+    /// Returns an error if `profile` is not defined, unless it is [`DEFAULT_PROFILE`], which
+    /// falls back to the top-level `database-url`.
+    pub fn database_url(&self, profile: &str) -> Result<Option<&str>, ConfigError> {
+        match self.profiles.get(profile) {
+            Some(Profile {
+                database_url: Some(url),
+                ..
+            }) => Ok(Some(url)),
+            Some(_) => Ok(self.database_url.as_deref()),
+            None if profile == DEFAULT_PROFILE => Ok(self.database_url.as_deref()),
+            None => Err(ConfigError::UnknownProfile(profile.to_owned())),
+        }
+    }
+
+    /// The versions of migrations skipped for `profile`: those of [`MigrateConfig::skip`] and
+    /// [`Profile::skip_migrations`], in order.
+    pub fn skip_migrations(&self, profile: &str) -> Vec<i64> {
+        let mut versions = self.migrate.skip.clone();
+
+        if let Some(profile) = self.profiles.get(profile) {
+            versions.extend(&profile.skip_migrations);
+        }
+
+        versions.sort_unstable();
+        versions.dedup();
+        versions
+    }
+}
+
+impl FromStr for Config {
+    type Err = toml::de::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_database_url_by_profile() {
+        let config: Config = r#"
+            database-url = "postgres://localhost/dev"
+
+            [profiles.test]
+            database-url = "postgres://localhost/test"
+
+            [profiles.ci]
+        "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            config.database_url("dev").unwrap(),
+            Some("postgres://localhost/dev")
+        );
+        assert_eq!(
+            config.database_url("test").unwrap(),
+            Some("postgres://localhost/test")
+        );
+        assert_eq!(
+            config.database_url("ci").unwrap(),
+            Some("postgres://localhost/dev")
+        );
+        assert!(matches!(
+            config.database_url("prod"),
+            Err(ConfigError::UnknownProfile(_))
+        ));
+    }
+
+    #[test]
+    fn test_skip_migrations_by_profile() {
+        let config: Config = r#"
+            [migrate]
+            skip = [3, 1]
+
+            [profiles.prod]
+            skip-migrations = [2, 3]
+        "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(config.skip_migrations("prod"), [1, 2, 3]);
+        assert_eq!(config.skip_migrations("dev"), [1, 3]);
+        assert!(Config::default().skip_migrations("dev").is_empty());
+    }
+
+    #[test]
+    fn test_unknown_keys() {
+        assert!("[migrate]\nsorce = \"db\"".parse::<Config>().is_err());
+        assert_eq!("".parse::<Config>().unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_paths_relative_to_file() {
+        let dir = std::env::temp_dir().join(format!("sqlx-config-{}", std::process::id()));
+        let nested = dir.join("crates/app");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(
+            dir.join(FILE_NAME),
+            "[migrate]\nsource = \"db/migrations\"\n",
+        )
+        .unwrap();
+
+        let found = Config::find(&nested);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let (path, config) = found.unwrap().unwrap();
+        assert_eq!(path, dir.join(FILE_NAME));
+        assert_eq!(config.migrate.source, Some(dir.join("db/migrations")));
+        assert_eq!(config.offline.dir, None);
+    }
+
+    #[test]
+    fn test_offline_databases() {
+        let config: Config = r#"
+            [offline.databases.postgres]
+            database-url = "postgres://localhost/app"
+            features = ["postgres"]
+
+            [offline.databases.mysql]
+        "#
+        .parse()
+        .unwrap();
+
+        assert!(config.offline.is_per_driver());
+        assert_eq!(
+            config.offline.databases["postgres"],
+            OfflineDatabase {
+                database_url: Some("postgres://localhost/app".into()),
+                features: vec!["postgres".into()],
+            }
+        );
+        assert_eq!(
+            config.offline.databases["mysql"],
+            OfflineDatabase::default()
+        );
+        assert!(!Config::default().offline.is_per_driver());
+    }
+}
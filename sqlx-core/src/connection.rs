@@ -1,7 +1,7 @@
 use crate::database::{Database, HasStatementCache};
 use crate::error::Error;
 
-use crate::transaction::{Transaction, TransactionManager};
+use crate::transaction::{StatementJournal, Transaction, TransactionManager};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
 use std::borrow::Cow;
@@ -140,6 +140,30 @@ pub trait Connection: Send {
         Box::pin(async move { Ok(()) })
     }
 
+    /// The number of statements currently cached in the connection, and how many it can hold.
+    fn cached_statements(&self) -> CachedStatements
+    where
+        Self::Database: HasStatementCache,
+    {
+        CachedStatements {
+            size: self.cached_statements_size(),
+            capacity: 0,
+        }
+    }
+
+    /// Change the number of statements the cache can hold, initially set by the
+    /// `statement_cache_capacity` of the database's `ConnectOptions`.
+    ///
+    /// If the cache holds more statements than that, the least recently used ones are removed,
+    /// closing them on the server if needed. A capacity of `0` disables the cache.
+    fn set_statement_cache_capacity(&mut self, capacity: usize) -> BoxFuture<'_, Result<(), Error>>
+    where
+        Self::Database: HasStatementCache,
+    {
+        let _ = capacity;
+        Box::pin(async move { Ok(()) })
+    }
+
     /// Restore any buffers in the connection to their default capacity, if possible.
     ///
     /// Sending a large query or receiving a resultset with many columns can cause the connection
@@ -160,6 +184,16 @@ pub trait Connection: Send {
     #[doc(hidden)]
     fn should_flush(&self) -> bool;
 
+    /// Record every statement executed on this connection into `journal` from now on, or stop
+    /// recording if `None`. Returns the journal that was in use before.
+    ///
+    /// See [`Transaction::journal()`].
+    #[doc(hidden)]
+    fn set_statement_journal(
+        &mut self,
+        journal: Option<StatementJournal>,
+    ) -> Option<StatementJournal>;
+
     /// Establish a new database connection.
     ///
     /// A value of [`Options`][Self::Options] is parsed from the provided connection string. This parsing
@@ -183,12 +217,26 @@ pub trait Connection: Send {
     }
 }
 
+/// The state of the prepared statement cache of a connection; see
+/// [`Connection::cached_statements()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachedStatements {
+    /// The number of statements currently cached.
+    pub size: usize,
+
+    /// The maximum number of statements the cache holds before evicting the least recently used
+    /// one; `0` if the cache is disabled.
+    pub capacity: usize,
+}
+
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub struct LogSettings {
     pub statements_level: LevelFilter,
     pub slow_statements_level: LevelFilter,
     pub slow_statements_duration: Duration,
+    #[doc(hidden)]
+    pub journal: Option<StatementJournal>,
 }
 
 impl Default for LogSettings {
@@ -197,6 +245,7 @@ impl Default for LogSettings {
             statements_level: LevelFilter::Debug,
             slow_statements_level: LevelFilter::Warn,
             slow_statements_duration: Duration::from_secs(1),
+            journal: None,
         }
     }
 }
@@ -246,6 +295,17 @@ pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug +
         unimplemented!()
     }
 
+    /// Get the URL returned by [`to_url_lossy()`][Self::to_url_lossy] with the password replaced
+    /// by `***`, to put in logs and error messages.
+    ///
+    /// ### Panics
+    /// If the driver does not implement `to_url_lossy()`.
+    fn redacted_url(&self) -> Url {
+        let mut url = self.to_url_lossy();
+        crate::database_url::mask_password(&mut url);
+        url
+    }
+
     /// Establish a new database connection with the options specified by `self`.
     fn connect(&self) -> BoxFuture<'_, Result<Self::Connection, Error>>
     where
@@ -0,0 +1,157 @@
+//! Deadlines propagated from the caller to the database.
+//!
+//! A [`Deadline`] set around a future, e.g. by the middleware of a server which must answer each
+//! request within some time, applies to everything SQLx does while that future is polled:
+//!
+//! * [`Pool::acquire()`][crate::pool::Pool::acquire] gives up at the deadline if it comes before
+//!   the [`acquire_timeout`][crate::pool::PoolOptions::acquire_timeout], with
+//!   [`Error::PoolTimedOut`][crate::error::Error::PoolTimedOut].
+//! * Postgres sets the `statement_timeout` of each query to the time left, so the server stops
+//!   working on it once the caller no longer waits for the result.
+//!
+//! A Postgres query started after the deadline fails with
+//! [`Error::DeadlineExceeded`][crate::error::Error::DeadlineExceeded] without being sent.
+//!
+//! The deadline is only visible while the future is polled, so it is not inherited by tasks the
+//! future spawns; set it again inside them. Deadlines can be nested, with the earliest one
+//! applying.
+//!
+//! ```rust,no_run
+//! # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+//! use std::time::Duration;
+//! use sqlx::deadline::Deadline;
+//!
+//! Deadline::after(Duration::from_secs(2))
+//!     .scope(async {
+//!         sqlx::query("SELECT pg_sleep(1)").execute(&pool).await?;
+//!         sqlx::query("SELECT pg_sleep(1)").execute(&pool).await
+//!     })
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::cell::Cell;
+use std::cmp;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static CURRENT: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// The time by which the caller needs the database to be done.
+///
+/// See [the module documentation](self) for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Deadline(Instant);
+
+impl Deadline {
+    /// A deadline at the given instant.
+    pub fn at(instant: Instant) -> Self {
+        Self(instant)
+    }
+
+    /// A deadline `duration` from now.
+    pub fn after(duration: Duration) -> Self {
+        Self(Instant::now() + duration)
+    }
+
+    /// The instant of the deadline.
+    pub fn instant(&self) -> Instant {
+        self.0
+    }
+
+    /// Apply the deadline to `future`, or the deadline already set if it is earlier.
+    pub fn scope<F: Future>(self, future: F) -> WithDeadline<F> {
+        WithDeadline {
+            deadline: self,
+            future: Box::pin(future),
+        }
+    }
+
+    /// The deadline of the future being polled, if any.
+    pub fn current() -> Option<Self> {
+        CURRENT.with(Cell::get).map(Self)
+    }
+
+    /// The time left until the deadline of the future being polled, if any.
+    ///
+    /// This is `Some(Duration::ZERO)` once the deadline has passed.
+    pub fn remaining() -> Option<Duration> {
+        Self::current().map(|deadline| deadline.0.saturating_duration_since(Instant::now()))
+    }
+}
+
+/// A future with a [`Deadline`]; created by [`Deadline::scope()`].
+#[must_use = "futures do nothing unless polled"]
+pub struct WithDeadline<F> {
+    deadline: Deadline,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for WithDeadline<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let outer = CURRENT.with(Cell::get);
+        let deadline = match outer {
+            Some(outer) => cmp::min(outer, self.deadline.0),
+            None => self.deadline.0,
+        };
+
+        // restore the outer deadline even if the future panics
+        struct Restore(Option<Instant>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.set(self.0));
+            }
+        }
+
+        CURRENT.with(|current| current.set(Some(deadline)));
+        let _restore = Restore(outer);
+
+        self.future.as_mut().poll(cx)
+    }
+}
+
+impl<F> std::fmt::Debug for WithDeadline<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WithDeadline")
+            .field("deadline", &self.deadline)
+            .finish_non_exhaustive()
+    }
+}
+
+#[test]
+fn it_nests_deadlines() {
+    let now = Instant::now();
+    let early = Deadline::at(now + Duration::from_secs(1));
+    let late = Deadline::at(now + Duration::from_secs(10));
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    let deadlines = runtime.block_on(late.scope(async {
+        let outer = Deadline::current();
+        let (inner, innermost) = early
+            .scope(async {
+                let innermost = late.scope(async { Deadline::current() }).await;
+                (Deadline::current(), innermost)
+            })
+            .await;
+
+        (outer, inner, innermost, Deadline::current())
+    }));
+
+    // the earliest deadline applies, until its future completes
+    assert_eq!(
+        deadlines,
+        (Some(late), Some(early), Some(early), Some(late))
+    );
+    assert_eq!(Deadline::current(), None);
+}
@@ -26,6 +26,65 @@ pub type BoxDynError = Box<dyn StdError + 'static + Send + Sync>;
 #[error("unexpected null; try decoding as an `Option`")]
 pub struct UnexpectedNullError;
 
+/// A decoded integer does not fit in the requested Rust type.
+///
+/// Returned when, for example, a `BIGINT` value larger than `i32::MAX` is decoded as `i32`.
+/// To clamp or discard such values instead, decode into
+/// [`Saturating`](crate::types::Saturating) or [`Checked`](crate::types::Checked).
+#[derive(thiserror::Error, Debug)]
+#[error("value {value} is out of range for `{target}`; try decoding as `{suggested}`")]
+pub struct IntegerOutOfRangeError {
+    /// The value that was decoded.
+    pub value: i128,
+    /// The Rust type it was decoded as.
+    pub target: &'static str,
+    /// The smallest Rust integer type that can hold `value`.
+    pub suggested: &'static str,
+}
+
+impl IntegerOutOfRangeError {
+    /// Convert a decoded `value` into `T`, or describe why it does not fit.
+    pub fn convert<T, V>(value: V) -> Result<T, Self>
+    where
+        T: TryFrom<V>,
+        V: Copy + Into<i128>,
+    {
+        T::try_from(value).map_err(|_| {
+            let value = value.into();
+            let target = type_name::<T>();
+
+            // prefer a type of the same signedness
+            let candidates: [(&'static str, i128, i128); 4] =
+                if target.starts_with('u') && value >= 0 {
+                    [
+                        ("u8", 0, u8::MAX.into()),
+                        ("u16", 0, u16::MAX.into()),
+                        ("u32", 0, u32::MAX.into()),
+                        ("u64", 0, u64::MAX.into()),
+                    ]
+                } else {
+                    [
+                        ("i8", i8::MIN.into(), i8::MAX.into()),
+                        ("i16", i16::MIN.into(), i16::MAX.into()),
+                        ("i32", i32::MIN.into(), i32::MAX.into()),
+                        ("i64", i64::MIN.into(), i64::MAX.into()),
+                    ]
+                };
+
+            let suggested = candidates
+                .iter()
+                .find(|(_, min, max)| (*min..=*max).contains(&value))
+                .map_or("i128", |(name, ..)| name);
+
+            IntegerOutOfRangeError {
+                value,
+                target,
+                suggested,
+            }
+        })
+    }
+}
+
 /// Represents all the ways a method can fail within SQLx.
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
@@ -110,6 +169,12 @@ pub enum Error {
     #[error("attempted to acquire a connection on a closed pool")]
     PoolClosed,
 
+    /// The [`Deadline`] of the caller passed before a query was sent.
+    ///
+    /// [`Deadline`]: crate::deadline::Deadline
+    #[error("the deadline passed before the query was sent")]
+    DeadlineExceeded,
+
     /// A background worker has crashed.
     #[error("attempted to communicate with a crashed background worker")]
     WorkerCrashed,
@@ -0,0 +1,110 @@
+//! Cheap, approximate row counts from the statistics of the query planner.
+
+use futures_core::future::BoxFuture;
+
+use crate::acquire::Acquire;
+use crate::database::Database;
+use crate::error::Error;
+
+/// A [`Database`] which can estimate the number of rows in a table or returned by a query
+/// without counting them.
+pub trait EstimateCount: Database {
+    /// Estimate the number of rows in `target`.
+    ///
+    /// See [`estimate_count()`].
+    fn estimate_count<'c>(
+        conn: &'c mut Self::Connection,
+        target: CountTarget<'c>,
+    ) -> BoxFuture<'c, Result<u64, Error>>;
+}
+
+/// What to estimate the number of rows of, for [`estimate_count()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CountTarget<'a> {
+    /// A table or view, optionally qualified with its schema, as in `public.users`.
+    ///
+    /// The name is used as written, so quoted identifiers must include their quotes.
+    Table(&'a str),
+    /// A `SELECT` query, without bind parameters.
+    Query(&'a str),
+}
+
+impl<'a> From<&'a str> for CountTarget<'a> {
+    /// A table if `target` is a name like `users` or `public.users`, and a query otherwise.
+    fn from(target: &'a str) -> Self {
+        let target = target.trim();
+
+        let is_name = target.split('.').all(|part| {
+            part.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        });
+
+        if is_name {
+            CountTarget::Table(target)
+        } else {
+            CountTarget::Query(target)
+        }
+    }
+}
+
+/// Estimate the number of rows in a table, or returned by a query, from the statistics of the
+/// query planner.
+///
+/// This is much cheaper than `SELECT COUNT(*)` on large tables, which has to read every row,
+/// and is meant for the total counts shown in paginated lists, where an approximation will do.
+/// The estimate is only as good as the statistics, which are updated by `ANALYZE`:
+///
+/// * Postgres: tables use `pg_class.reltuples`, and queries and tables which have never been
+///   analyzed use the row estimate of `EXPLAIN`.
+/// * MySQL: tables use `TABLE_ROWS` from `information_schema.TABLES`, which may be off by
+///   40% or more for InnoDB, and queries use the row estimates of `EXPLAIN`.
+/// * SQLite: tables use `sqlite_stat1` once `ANALYZE` has been run. SQLite has no row
+///   estimates for queries, so they and tables without statistics are counted exactly.
+///
+/// `target` is a table name or a query, converted with [`CountTarget::from()`], so a bare or
+/// schema-qualified name is a table and anything else is a query:
+///
+/// ```rust,no_run
+/// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+/// let users = sqlx::estimate_count(&pool, "users").await?;
+/// let active = sqlx::estimate_count(&pool, "SELECT * FROM users WHERE active").await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The name of a table and the text of a query are inserted into the SQL that gets the
+/// estimate, so they must never contain untrusted input.
+pub async fn estimate_count<'a, 't, A>(
+    conn: A,
+    target: impl Into<CountTarget<'t>>,
+) -> Result<u64, Error>
+where
+    A: Acquire<'a>,
+    A::Database: EstimateCount,
+{
+    let mut conn = conn.acquire().await?;
+
+    <A::Database as EstimateCount>::estimate_count(&mut conn, target.into()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_target_from_str() {
+        assert_eq!(CountTarget::from("users"), CountTarget::Table("users"));
+        assert_eq!(
+            CountTarget::from(" public.users "),
+            CountTarget::Table("public.users")
+        );
+        assert_eq!(
+            CountTarget::from("SELECT * FROM users"),
+            CountTarget::Query("SELECT * FROM users")
+        );
+        assert_eq!(
+            CountTarget::from("\"Users\""),
+            CountTarget::Query("\"Users\"")
+        );
+    }
+}
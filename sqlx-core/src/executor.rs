@@ -1,6 +1,7 @@
 use crate::database::Database;
 use crate::describe::Describe;
 use crate::error::{BoxDynError, Error};
+use crate::fetch_guard::check_fetch_all;
 
 use either::Either;
 use futures_core::future::BoxFuture;
@@ -83,6 +84,24 @@ pub trait Executor<'c>: Send + Debug + Sized {
             .boxed()
     }
 
+    /// Execute the query and return the generated rows as a stream, fetching them as set by
+    /// `options`.
+    ///
+    /// Drivers ignore the options they don't support; by default, this is the same as
+    /// [`fetch()`][Self::fetch]. See [`FetchOptions`] for details.
+    fn fetch_with<'e, 'q: 'e, E>(
+        self,
+        query: E,
+        options: FetchOptions,
+    ) -> BoxStream<'e, Result<<Self::Database as Database>::Row, Error>>
+    where
+        'c: 'e,
+        E: 'q + Execute<'q, Self::Database>,
+    {
+        let _ = options;
+        self.fetch(query)
+    }
+
     /// Execute multiple queries and return the generated results as a stream
     /// from each query, in a stream.
     fn fetch_many<'e, 'q: 'e, E>(
@@ -108,6 +127,10 @@ pub trait Executor<'c>: Send + Debug + Sized {
         'c: 'e,
         E: 'q + Execute<'q, Self::Database>,
     {
+        if let Err(error) = check_fetch_all(query.sql()) {
+            return Box::pin(future::ready(Err(error)));
+        }
+
         self.fetch(query).try_collect().boxed()
     }
 
@@ -181,6 +204,75 @@ pub trait Executor<'c>: Send + Debug + Sized {
     ) -> BoxFuture<'e, Result<Describe<Self::Database>, Error>>
     where
         'c: 'e;
+
+    /// Describe the SQL query as with [`describe()`][Self::describe], declaring the types of
+    /// some or all of its parameters up front.
+    ///
+    /// This lets the query macros describe queries in which the database cannot infer the type of
+    /// a parameter on its own. Drivers which cannot make use of declared parameter types ignore
+    /// them.
+    #[doc(hidden)]
+    fn describe_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [<Self::Database as Database>::TypeInfo],
+    ) -> BoxFuture<'e, Result<Describe<Self::Database>, Error>>
+    where
+        'c: 'e,
+    {
+        let _ = parameters;
+        self.describe(sql)
+    }
+
+    /// Execute the query and return the first column of each generated row, in a stream.
+    ///
+    /// This powers [`QueryScalar::fetch_first_column()`]. Drivers
+    /// which can read a single value without materializing the row yield `Right(value)`; the
+    /// default yields every row whole as `Left(row)`.
+    ///
+    /// [`QueryScalar::fetch_first_column()`]: crate::query_scalar::QueryScalar::fetch_first_column
+    #[doc(hidden)]
+    fn fetch_first_column<'e, 'q: 'e, E>(
+        self,
+        query: E,
+    ) -> BoxStream<
+        'e,
+        Result<
+            Either<<Self::Database as Database>::Row, <Self::Database as Database>::Value>,
+            Error,
+        >,
+    >
+    where
+        'c: 'e,
+        E: 'q + Execute<'q, Self::Database>,
+        <Self::Database as Database>::Value: Send,
+    {
+        self.fetch(query).map_ok(Either::Left).boxed()
+    }
+}
+
+/// How [`Executor::fetch_with()`] fetches the rows of a query from the database.
+///
+/// ```rust
+/// # use sqlx_core::executor::FetchOptions;
+/// // fetch the rows 1000 at a time
+/// let options = FetchOptions { fetch_size: 1000 };
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FetchOptions {
+    /// The number of rows to fetch from the server at a time, or `0` (the default) to have the
+    /// server send every row of the result.
+    ///
+    /// With a fetch size, the next rows are only requested once those fetched before have been
+    /// read from the stream, which bounds the memory used by a large result set on both sides
+    /// and makes a slow reader hold back the server.
+    ///
+    /// Only Postgres supports it, by executing the query with the extended query protocol (so
+    /// the query string must be a single statement) and fetching its portal in batches. The
+    /// statement stays open in the meantime, so it should be read to its end, or the stream
+    /// dropped, before long. MySQL and SQLite already read rows from the server as the stream
+    /// is polled.
+    pub fetch_size: u32,
 }
 
 /// A type that may be executed against a database connection.
@@ -0,0 +1,291 @@
+//! Catching `fetch_all()` calls which load every row of a large table, with [`FetchAllGuard`].
+
+use std::collections::HashSet;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::augment::{tokenize, Kind};
+use crate::error::Error;
+
+/// A comment containing this marker anywhere in a query stops [`FetchAllGuard`] from checking
+/// it, for queries which are bounded in another way, such as by a unique key or an aggregate.
+pub const UNBOUNDED_MARKER: &str = "sqlx:unbounded";
+
+static GUARD: RwLock<Option<Arc<FetchAllGuard>>> = RwLock::new(None);
+
+/// Warns, or fails, when `fetch_all()` runs a query without `LIMIT` on one of the configured
+/// tables, to catch accidental full-table loads before they reach production.
+///
+/// Once [installed][Self::install], the guard checks the queries passed to the `fetch_all()`
+/// methods of [`Executor`][crate::executor::Executor], `query()`, `query_as()`,
+/// `query_scalar()` and `raw_sql()`. It is opt-in and only active in builds with debug
+/// assertions; release builds skip the checks entirely.
+///
+/// A query is flagged if it names a configured table after `FROM` or `JOIN` (in a subquery or
+/// CTE as well) and has no `LIMIT` or `FETCH` clause of its own. Table names are matched
+/// case-insensitively, including any schema qualifier as written. Add a comment with
+/// [`UNBOUNDED_MARKER`] to a query which is known to return few rows anyway.
+///
+/// ```rust
+/// use sqlx_core::fetch_guard::FetchAllGuard;
+///
+/// let guard = FetchAllGuard::new().table("events").table("audit.log").deny(true);
+///
+/// assert!(guard.check("SELECT * FROM events WHERE kind = $1").is_err());
+/// assert!(guard.check("SELECT * FROM events ORDER BY id LIMIT 100").is_ok());
+/// assert!(guard.check("SELECT count(*) FROM events /* sqlx:unbounded */").is_ok());
+/// assert!(guard.check("SELECT * FROM users").is_ok());
+///
+/// // check every `fetch_all()` from now on
+/// guard.install();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FetchAllGuard {
+    tables: HashSet<String>,
+    deny: bool,
+}
+
+impl FetchAllGuard {
+    /// A guard which does not check any tables.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check the queries reading `table`.
+    pub fn table(mut self, table: &str) -> Self {
+        self.tables.insert(table.to_ascii_lowercase());
+        self
+    }
+
+    /// Set to `true` to fail the flagged queries with [`Error::InvalidArgument`] instead of
+    /// logging a warning with target `sqlx::fetch_guard`.
+    ///
+    /// Defaults to `false`.
+    pub fn deny(mut self, deny: bool) -> Self {
+        self.deny = deny;
+        self
+    }
+
+    /// Check every `fetch_all()` of this process with this guard, replacing the one installed
+    /// before, if any.
+    pub fn install(self) {
+        *GUARD.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(self));
+    }
+
+    /// Stop checking `fetch_all()` calls.
+    pub fn uninstall() {
+        *GUARD.write().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    /// Check `sql` as if it was passed to `fetch_all()`.
+    ///
+    /// Returns an error if the query is flagged and the guard [denies][Self::deny] it, and logs
+    /// a warning if it only warns.
+    pub fn check(&self, sql: &str) -> Result<(), Error> {
+        let Some(table) = self.unbounded_table(sql) else {
+            return Ok(());
+        };
+
+        if self.deny {
+            return Err(Error::InvalidArgument(format!(
+                "fetch_all() of a query without LIMIT on table {table:?}; \
+                 add a LIMIT or a `{UNBOUNDED_MARKER}` comment: {sql}"
+            )));
+        }
+
+        tracing::warn!(
+            target: "sqlx::fetch_guard",
+            table,
+            sql,
+            "fetch_all() of a query without LIMIT"
+        );
+
+        Ok(())
+    }
+
+    /// The first configured table read by `sql`, unless it has a `LIMIT` or `FETCH` clause.
+    fn unbounded_table(&self, sql: &str) -> Option<String> {
+        if self.tables.is_empty() || sql.contains(UNBOUNDED_MARKER) {
+            return None;
+        }
+
+        let tokens = tokenize(sql);
+
+        let mut table = None;
+        let mut depth = 0_usize;
+        // whether each level of parentheses is in a list of `FROM` items
+        let mut from_lists = vec![false];
+        let mut expect_table = false;
+        let mut prev_word: Option<&str> = None;
+        let mut i = 0;
+
+        while i < tokens.len() {
+            let token = &tokens[i];
+            let text = &sql[token.span.clone()];
+
+            if std::mem::take(&mut expect_table) {
+                let start = if token.kind == Kind::Word && text.eq_ignore_ascii_case("ONLY") {
+                    i + 1
+                } else {
+                    i
+                };
+
+                // a possibly schema-qualified name, unless it is a table-valued function
+                let mut end = start;
+
+                if tokens.get(end).is_some_and(|t| t.is_name()) {
+                    end += 1;
+
+                    while tokens.get(end).map(|t| t.kind) == Some(Kind::Punct(b'.'))
+                        && tokens.get(end + 1).is_some_and(|t| t.is_name())
+                    {
+                        end += 2;
+                    }
+                }
+
+                if end > start && tokens.get(end).map(|t| t.kind) != Some(Kind::Punct(b'(')) {
+                    let name = tokens[start..end]
+                        .iter()
+                        .step_by(2)
+                        .map(|t| t.unquoted(sql).to_ascii_lowercase())
+                        .collect::<Vec<_>>()
+                        .join(".");
+
+                    if table.is_none() && self.tables.contains(&name) {
+                        table = Some(name);
+                    }
+
+                    prev_word = None;
+                    i = end;
+                    continue;
+                }
+            }
+
+            match token.kind {
+                Kind::Word => match &*text.to_ascii_uppercase() {
+                    "FROM" => {
+                        if !prev_word.is_some_and(|w| w.eq_ignore_ascii_case("DELETE")) {
+                            expect_table = true;
+                            *from_lists.last_mut().expect("BUG: no FROM list state") = true;
+                        }
+                    }
+                    "JOIN" => expect_table = true,
+                    // the clauses bounding the rows of the whole query
+                    "LIMIT" | "FETCH" if depth == 0 => return None,
+                    "WHERE" | "GROUP" | "HAVING" | "ORDER" | "LIMIT" | "OFFSET" | "FETCH"
+                    | "FOR" | "UNION" | "EXCEPT" | "INTERSECT" | "WINDOW" | "RETURNING" | "SET"
+                    | "SELECT" | "VALUES" => {
+                        *from_lists.last_mut().expect("BUG: no FROM list state") = false;
+                    }
+                    _ => {}
+                },
+                Kind::Punct(b'(') => {
+                    depth += 1;
+                    from_lists.push(false);
+                }
+                Kind::Punct(b')') => {
+                    depth = depth.saturating_sub(1);
+
+                    if from_lists.len() > 1 {
+                        from_lists.pop();
+                    }
+                }
+                Kind::Punct(b',') => {
+                    expect_table = *from_lists.last().expect("BUG: no FROM list state");
+                }
+                _ => {}
+            }
+
+            prev_word = (token.kind == Kind::Word).then_some(text);
+            i += 1;
+        }
+
+        table
+    }
+}
+
+/// Check a query passed to `fetch_all()` with the installed guard, in builds with debug
+/// assertions.
+pub(crate) fn check_fetch_all(sql: &str) -> Result<(), Error> {
+    if !cfg!(debug_assertions) {
+        return Ok(());
+    }
+
+    let guard = GUARD.read().unwrap_or_else(PoisonError::into_inner).clone();
+
+    match guard {
+        Some(guard) => guard.check(sql),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FetchAllGuard;
+
+    fn guard() -> FetchAllGuard {
+        FetchAllGuard::new()
+            .table("events")
+            .table("audit.log")
+            .deny(true)
+    }
+
+    fn unbounded(sql: &str) -> Option<String> {
+        guard().unbounded_table(sql)
+    }
+
+    #[test]
+    fn flags_configured_tables_without_limit() {
+        assert_eq!(
+            unbounded("SELECT * FROM Events e WHERE e.kind = $1"),
+            Some("events".into())
+        );
+        assert_eq!(
+            unbounded("SELECT * FROM users u JOIN audit.log l ON l.user_id = u.id"),
+            Some("audit.log".into())
+        );
+        assert_eq!(
+            unbounded("SELECT * FROM users, \"events\""),
+            Some("events".into())
+        );
+        assert_eq!(
+            unbounded("WITH e AS (SELECT * FROM ONLY events) SELECT * FROM e"),
+            Some("events".into())
+        );
+        assert_eq!(
+            unbounded("SELECT * FROM users WHERE id IN (SELECT user_id FROM events LIMIT 10)"),
+            Some("events".into())
+        );
+
+        assert!(guard().check("SELECT * FROM events").is_err());
+    }
+
+    #[test]
+    fn ignores_bounded_and_other_queries() {
+        assert_eq!(
+            unbounded("SELECT * FROM events ORDER BY id DESC LIMIT 10"),
+            None
+        );
+        assert_eq!(
+            unbounded("SELECT * FROM events FETCH FIRST 10 ROWS ONLY"),
+            None
+        );
+        assert_eq!(unbounded("SELECT * FROM users"), None);
+        assert_eq!(unbounded("SELECT * FROM log"), None);
+        assert_eq!(
+            unbounded("SELECT * FROM generate_series(1, 10) events"),
+            None
+        );
+        assert_eq!(unbounded("DELETE FROM events RETURNING *"), None);
+        assert_eq!(
+            unbounded("SELECT count(*) FROM events -- sqlx:unbounded"),
+            None
+        );
+        assert_eq!(
+            FetchAllGuard::new().unbounded_table("SELECT * FROM events"),
+            None
+        );
+
+        // only logged
+        assert!(guard().deny(false).check("SELECT * FROM events").is_ok());
+    }
+}
@@ -0,0 +1,529 @@
+//! Loading the SQL fixture files used by `#[sqlx::test]` into any database.
+//!
+//! The same files that seed test databases with `#[sqlx::test(fixtures("users", "orders"))]`
+//! can seed a development or staging database at runtime:
+//!
+//! ```rust,no_run
+//! # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+//! use sqlx::fixtures::Fixtures;
+//!
+//! Fixtures::new("tests/fixtures")
+//!     .apply(&pool, &["users", "orders"])
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Templates
+//! Fixtures may contain placeholders in double braces, which are replaced before the fixture
+//! is executed, both here and in `#[sqlx::test]`:
+//!
+//! * `{{ now }}`: the current time in UTC, as `2024-01-31 12:00:00.000000+00:00`. The time is
+//!   taken once, so it is the same in all fixtures applied together.
+//! * `{{ now - 3 days }}`, `{{ now + 90 minutes }}`: the current time, offset by a number of
+//!   `seconds`, `minutes`, `hours`, `days` or `weeks`.
+//! * `{{ uuid }}`: a new random UUID.
+//! * `{{ uuid alice }}`: a random UUID for the name `alice`, which is the same everywhere the
+//!   name is used in fixtures applied together, so rows can refer to one another.
+//!
+//! Placeholders are replaced with bare text, so they are usually put in a string literal:
+//!
+//! ```sql
+//! INSERT INTO users (id, name, created_at) VALUES ('{{ uuid alice }}', 'alice', '{{ now - 1 day }}');
+//! INSERT INTO orders (user_id, placed_at) VALUES ('{{ uuid alice }}', '{{ now }}');
+//! ```
+//!
+//! Anything else in double braces, such as the Postgres array literal `'{{1,2},{3,4}}'`, is
+//! left as it is.
+//!
+//! # Dependencies
+//! A fixture may start with comments naming the fixtures it depends on, which are then applied
+//! before it, whatever the order in which the fixtures are given:
+//!
+//! ```sql
+//! -- depends: users, tags
+//! INSERT INTO posts (user_id, title) VALUES ('{{ uuid alice }}', 'Hello');
+//! ```
+//!
+//! Dependencies are named like fixtures, without the `.sql` extension. [`Fixtures::apply()`]
+//! also applies the dependencies which are not given, from the directory of the fixture
+//! depending on them; in `#[sqlx::test]`, they must be among the fixtures of the test.
+use std::collections::hash_map::{HashMap, RandomState};
+use std::fmt::Write;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::acquire::Acquire;
+use crate::database::Database;
+use crate::error::Error;
+use crate::executor::Executor;
+
+/// Apply the fixtures with the given names from the `fixtures` directory, relative to the
+/// current directory.
+///
+/// See [`Fixtures::apply()`].
+pub async fn apply<'a, A>(conn: A, names: &[&str]) -> Result<(), Error>
+where
+    A: Acquire<'a>,
+    for<'c> &'c mut <A::Database as Database>::Connection: Executor<'c, Database = A::Database>,
+{
+    Fixtures::default().apply(conn, names).await
+}
+
+/// A directory of SQL fixture files.
+#[derive(Debug, Clone)]
+pub struct Fixtures {
+    dir: PathBuf,
+}
+
+impl Fixtures {
+    /// Look up fixtures in `dir`.
+    ///
+    /// For the fixtures of a `#[sqlx::test]`, this is the `fixtures` directory next to the
+    /// source file of the test.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Apply the fixtures with the given names, in order, in a single transaction.
+    ///
+    /// As with `#[sqlx::test(fixtures(...))]`, a name without an extension refers to the file
+    /// with the name and a `.sql` extension. The [dependencies](self#dependencies) of the
+    /// fixtures are applied before them, and only once. All fixtures are read and their
+    /// [templates](self#templates) are replaced before anything is executed, so a missing file
+    /// or an invalid placeholder leaves the database untouched.
+    pub async fn apply<'a, A>(&self, conn: A, names: &[&str]) -> Result<(), Error>
+    where
+        A: Acquire<'a>,
+        for<'c> &'c mut <A::Database as Database>::Connection: Executor<'c, Database = A::Database>,
+    {
+        // the fixtures given, followed by the dependencies not given, as they are found
+        let mut files: Vec<(PathBuf, String)> = Vec::with_capacity(names.len());
+        let mut pending: Vec<PathBuf> = names.iter().map(|name| self.dir.join(name)).collect();
+        let mut next = 0;
+
+        while next < pending.len() {
+            let mut path = pending[next].clone();
+            next += 1;
+
+            if path.extension().is_none() {
+                path.set_extension("sql");
+            }
+
+            if files.iter().any(|(file, _)| *file == path) {
+                continue;
+            }
+
+            let sql = crate::fs::read_to_string(&path).await.map_err(|e| {
+                io::Error::new(
+                    e.kind(),
+                    format!("failed to read fixture {}: {e}", path.display()),
+                )
+            })?;
+
+            let dir = path.parent().unwrap_or(Path::new(""));
+            pending.extend(dependencies(&sql).map(|dependency| dir.join(dependency)));
+
+            files.push((path, sql));
+        }
+
+        let graph: Vec<(&str, Vec<&str>)> = files
+            .iter()
+            .map(|(path, sql)| {
+                let dependencies = dependencies(sql)
+                    .map(|dependency| fixture_name(Path::new(dependency)))
+                    .collect();
+
+                (fixture_name(path), dependencies)
+            })
+            .collect();
+
+        let mut template = FixtureTemplate::new();
+        let mut scripts = Vec::with_capacity(files.len());
+
+        for index in order_by_dependencies(&graph)? {
+            let (path, sql) = &files[index];
+            scripts.push(template.render(&path.display().to_string(), sql)?);
+        }
+
+        let mut tx = conn.begin().await?;
+
+        for sql in &scripts {
+            tx.execute(&**sql).await?;
+        }
+
+        tx.commit().await
+    }
+}
+
+impl Default for Fixtures {
+    fn default() -> Self {
+        Self::new("fixtures")
+    }
+}
+
+/// The names of the fixtures which the fixture `sql` depends on, from its leading
+/// `-- depends: <name>, ...` comments.
+pub(crate) fn dependencies(sql: &str) -> impl Iterator<Item = &str> {
+    sql.lines()
+        .map(str::trim)
+        .take_while(|line| line.is_empty() || line.starts_with("--"))
+        .filter_map(|line| {
+            let comment = line.trim_start_matches('-').trim_start();
+
+            comment
+                .get(..8)
+                .filter(|keyword| keyword.eq_ignore_ascii_case("depends:"))
+                .map(|_| &comment[8..])
+        })
+        .flat_map(|names| names.split(','))
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+}
+
+/// The name of a fixture, by which other fixtures depend on it: the name of its file without
+/// the extension.
+pub(crate) fn fixture_name(path: &Path) -> &str {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("")
+}
+
+/// Order fixtures, given by name along with the names of their dependencies, so that each
+/// comes after its dependencies and otherwise keeps its place, returning their indices.
+///
+/// Fails if a dependency is not among the fixtures, or if fixtures depend on one another.
+pub(crate) fn order_by_dependencies(fixtures: &[(&str, Vec<&str>)]) -> Result<Vec<usize>, Error> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum State {
+        New,
+        Visiting,
+        Done,
+    }
+
+    fn visit(
+        index: usize,
+        fixtures: &[(&str, Vec<&str>)],
+        states: &mut [State],
+        order: &mut Vec<usize>,
+    ) -> Result<(), Error> {
+        match states[index] {
+            State::Done => return Ok(()),
+            State::Visiting => {
+                return Err(Error::InvalidArgument(format!(
+                    "fixture {:?} depends on itself through its dependencies",
+                    fixtures[index].0
+                )))
+            }
+            State::New => {}
+        }
+
+        states[index] = State::Visiting;
+
+        let (name, dependencies) = &fixtures[index];
+
+        for dependency in dependencies {
+            let Some(dependency) = fixtures.iter().position(|(other, _)| other == dependency)
+            else {
+                return Err(Error::InvalidArgument(format!(
+                    "fixture {name:?} depends on {dependency:?}, which is not applied"
+                )));
+            };
+
+            visit(dependency, fixtures, states, order)?;
+        }
+
+        states[index] = State::Done;
+        order.push(index);
+
+        Ok(())
+    }
+
+    let mut states = vec![State::New; fixtures.len()];
+    let mut order = Vec::with_capacity(fixtures.len());
+
+    for index in 0..fixtures.len() {
+        visit(index, fixtures, &mut states, &mut order)?;
+    }
+
+    Ok(order)
+}
+
+/// Replaces the placeholders in fixtures, keeping the time and named UUIDs the same for all
+/// fixtures rendered with it.
+pub(crate) struct FixtureTemplate {
+    now: SystemTime,
+    uuids: HashMap<String, String>,
+}
+
+impl FixtureTemplate {
+    pub(crate) fn new() -> Self {
+        Self {
+            now: SystemTime::now(),
+            uuids: HashMap::new(),
+        }
+    }
+
+    /// Render the fixture at `path`, which is only used in errors.
+    pub(crate) fn render(&mut self, path: &str, sql: &str) -> Result<String, Error> {
+        let mut out = String::with_capacity(sql.len());
+        let mut rest = sql;
+
+        while let Some(start) = rest.find("{{") {
+            let Some(len) = rest[start + 2..].find("}}") else {
+                break;
+            };
+
+            let inner = &rest[start + 2..start + 2 + len];
+            let mut words = inner.split_whitespace();
+
+            let value = match words.next() {
+                Some("now") => self.now(words),
+                Some("uuid") => self.uuid(words),
+                _ => {
+                    out.push_str(&rest[..start + 2]);
+                    rest = &rest[start + 2..];
+                    continue;
+                }
+            };
+
+            let Some(value) = value else {
+                return Err(Error::InvalidArgument(format!(
+                    "fixture {path}: invalid placeholder {{{{{inner}}}}}"
+                )));
+            };
+
+            out.push_str(&rest[..start]);
+            out.push_str(&value);
+            rest = &rest[start + 2 + len + 2..];
+        }
+
+        out.push_str(rest);
+
+        Ok(out)
+    }
+
+    fn now<'s>(&self, mut words: impl Iterator<Item = &'s str>) -> Option<String> {
+        let Some(sign) = words.next() else {
+            return Some(format_timestamp(self.now));
+        };
+
+        let (Some(amount), Some(unit), None) = (words.next(), words.next(), words.next()) else {
+            return None;
+        };
+
+        let amount: u64 = amount.parse().ok()?;
+
+        let secs = match unit.strip_suffix('s').unwrap_or(unit) {
+            "second" => 1,
+            "minute" => 60,
+            "hour" => 60 * 60,
+            "day" => 24 * 60 * 60,
+            "week" => 7 * 24 * 60 * 60,
+            _ => return None,
+        };
+
+        let offset = amount.checked_mul(secs).map(Duration::from_secs);
+
+        let time = match sign {
+            "+" => offset.and_then(|offset| self.now.checked_add(offset)),
+            "-" => offset.and_then(|offset| self.now.checked_sub(offset)),
+            _ => return None,
+        };
+
+        time.map(format_timestamp)
+    }
+
+    fn uuid<'s>(&mut self, mut words: impl Iterator<Item = &'s str>) -> Option<String> {
+        match (words.next(), words.next()) {
+            (None, _) => Some(random_uuid()),
+            (Some(name), None) => Some(
+                self.uuids
+                    .entry(name.to_owned())
+                    .or_insert_with(random_uuid)
+                    .clone(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+fn format_timestamp(time: SystemTime) -> String {
+    let (secs, micros) = match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => (
+            i64::try_from(since.as_secs()).unwrap_or(i64::MAX),
+            since.subsec_micros(),
+        ),
+        Err(e) => {
+            let before = e.duration();
+            let micros = before.subsec_micros();
+            let secs = -i64::try_from(before.as_secs()).unwrap_or(i64::MAX);
+
+            if micros == 0 {
+                (secs, 0)
+            } else {
+                (secs - 1, 1_000_000 - micros)
+            }
+        }
+    };
+
+    let days = secs.div_euclid(86_400);
+    let secs_of_day = secs.rem_euclid(86_400);
+
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}.{micros:06}+00:00",
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60,
+    )
+}
+
+// Fixtures only need UUIDs to be unique, not unpredictable, so the randomly seeded hasher of
+// `HashMap` will do.
+fn random_uuid() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut bytes = [0u8; 16];
+
+    for half in bytes.chunks_mut(8) {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+        half.copy_from_slice(&hasher.finish().to_le_bytes());
+    }
+
+    // version 4, variant 1
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex = bytes.iter().fold(String::with_capacity(32), |mut hex, b| {
+        let _ = write!(hex, "{b:02x}");
+        hex
+    });
+
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template_at(secs: u64) -> FixtureTemplate {
+        FixtureTemplate {
+            now: UNIX_EPOCH + Duration::from_micros(secs * 1_000_000 + 250),
+            uuids: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_timestamps() {
+        // 2024-02-29 23:59:59 UTC
+        let mut template = template_at(1_709_251_199);
+
+        assert_eq!(
+            template
+                .render(
+                    "t.sql",
+                    "VALUES ('{{ now }}', '{{now + 1 second}}', '{{ now - 2 days }}')"
+                )
+                .unwrap(),
+            "VALUES ('2024-02-29 23:59:59.000250+00:00', '2024-03-01 00:00:00.000250+00:00', \
+             '2024-02-27 23:59:59.000250+00:00')"
+        );
+
+        assert_eq!(
+            template
+                .render("t.sql", "'{{ now - 20000 days }}'")
+                .unwrap(),
+            "'1969-05-28 23:59:59.000250+00:00'"
+        );
+    }
+
+    #[test]
+    fn test_render_uuids() {
+        let mut template = template_at(0);
+        let sql = template
+            .render("t.sql", "{{ uuid a }} {{ uuid }} {{ uuid a }} {{ uuid b }}")
+            .unwrap();
+
+        let uuids: Vec<&str> = sql.split(' ').collect();
+
+        assert_eq!(uuids[0], uuids[2]);
+        assert_ne!(uuids[0], uuids[1]);
+        assert_ne!(uuids[0], uuids[3]);
+
+        for uuid in uuids {
+            assert_eq!(uuid.len(), 36);
+            assert_eq!(&uuid[14..15], "4");
+        }
+    }
+
+    #[test]
+    fn test_render_leaves_other_braces_alone() {
+        let mut template = template_at(0);
+
+        assert_eq!(
+            template
+                .render(
+                    "t.sql",
+                    "SELECT '{{1,2},{3,4}}'::int[][], '{{ unknown }}', '{{'"
+                )
+                .unwrap(),
+            "SELECT '{{1,2},{3,4}}'::int[][], '{{ unknown }}', '{{'"
+        );
+
+        assert!(template
+            .render("t.sql", "{{ now - 3 fortnights }}")
+            .is_err());
+        assert!(template.render("t.sql", "{{ uuid a b }}").is_err());
+    }
+
+    #[test]
+    fn test_dependencies() {
+        let sql = "-- Posts of alice.\n--\n-- depends: users, tags\n--Depends: seed/accounts\n\n\
+                   INSERT INTO posts VALUES (1);\n-- depends: comments\n";
+
+        assert_eq!(
+            dependencies(sql).collect::<Vec<_>>(),
+            ["users", "tags", "seed/accounts"]
+        );
+        assert_eq!(dependencies("INSERT INTO users VALUES (1);").count(), 0);
+    }
+
+    #[test]
+    fn test_order_by_dependencies() {
+        let fixtures = [
+            ("comments", vec!["posts", "users"]),
+            ("posts", vec!["users"]),
+            ("tags", vec![]),
+            ("users", vec![]),
+        ];
+
+        assert_eq!(order_by_dependencies(&fixtures).unwrap(), [3, 1, 0, 2]);
+
+        assert!(order_by_dependencies(&[("posts", vec!["users"])]).is_err());
+        assert!(
+            order_by_dependencies(&[("a", vec!["b"]), ("b", vec!["c"]), ("c", vec!["a"])]).is_err()
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use crate::decode::Decode;
+use crate::error::Error;
+use crate::row::Row;
+use crate::types::Type;
+
+/// A collection of columns that can be built by decoding a result set row by row.
+///
+/// This is implemented for tuples of [`Vec`]s, with one `Vec` per column, for use with
+/// [`Query::fetch_columns()`](crate::query::Query::fetch_columns). Values are decoded
+/// straight into the vectors, so the rows themselves don't need to be kept around as with
+/// [`fetch_all()`](crate::query::Query::fetch_all).
+///
+/// ```rust,ignore
+/// let (ids, names): (Vec<i64>, Vec<String>) = sqlx::query("SELECT id, name FROM users")
+///     .fetch_columns(&pool)
+///     .await?;
+/// ```
+///
+/// If the number of rows is known up front, the vectors can be allocated once with
+/// [`with_capacity()`][Self::with_capacity] and filled from [`fetch()`](crate::query::Query::fetch)
+/// with [`push_row()`][Self::push_row].
+pub trait FromColumns<R: Row>: Sized {
+    /// Create an empty collection with room for `capacity` rows in each column.
+    fn with_capacity(capacity: usize) -> Self;
+
+    /// Decode the values of `row` and append them to their columns.
+    fn push_row(&mut self, row: &R) -> Result<(), Error>;
+}
+
+macro_rules! impl_from_columns_for_tuple {
+    ($( ($idx:tt) -> $T:ident );+;) => {
+        impl<R, $($T,)+> FromColumns<R> for ($(Vec<$T>,)+)
+        where
+            R: Row,
+            usize: crate::column::ColumnIndex<R>,
+            $($T: for<'r> Decode<'r, R::Database> + Type<R::Database>,)+
+        {
+            #[inline]
+            fn with_capacity(capacity: usize) -> Self {
+                ($(Vec::<$T>::with_capacity(capacity),)+)
+            }
+
+            #[inline]
+            fn push_row(&mut self, row: &R) -> Result<(), Error> {
+                // decode the whole row first so the columns stay the same length on errors
+                let values = ($(row.try_get::<$T, _>($idx as usize)?,)+);
+
+                $(self.$idx.push(values.$idx);)+
+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_from_columns_for_tuple!(
+    (0) -> T1;
+);
+
+impl_from_columns_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+);
+
+impl_from_columns_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+);
+
+impl_from_columns_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+);
+
+impl_from_columns_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+);
+
+impl_from_columns_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+);
+
+impl_from_columns_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+);
+
+impl_from_columns_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+);
+
+impl_from_columns_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+);
+
+impl_from_columns_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+);
+
+impl_from_columns_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+);
+
+impl_from_columns_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+);
+
+impl_from_columns_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+);
+
+impl_from_columns_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+    (13) -> T14;
+);
+
+impl_from_columns_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+    (13) -> T14;
+    (14) -> T15;
+);
+
+impl_from_columns_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+    (13) -> T14;
+    (14) -> T15;
+    (15) -> T16;
+);
@@ -1,4 +1,4 @@
-use crate::{error::Error, row::Row};
+use crate::{column::Column, error::Error, row::Row};
 
 /// A record that can be built from a row returned by the database.
 ///
@@ -65,6 +65,40 @@ use crate::{error::Error, row::Row};
 /// reason), `lowercase`, `UPPERCASE`, `camelCase`, `PascalCase`, `SCREAMING_SNAKE_CASE` and `kebab-case`.
 /// The styling of each option is intended to be an example of its behavior.
 ///
+/// #### `alias`
+///
+/// When the same struct is read from queries (or views, or databases) which name a column
+/// differently, you can list additional accepted names with the `alias` attribute.
+/// The attribute may be repeated. The field name (or `rename`, if set) is tried first,
+/// followed by each alias in the order they are listed:
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// struct User {
+///     id: i32,
+///     #[sqlx(alias = "userName", alias = "login")]
+///     user_name: String,
+/// }
+/// ```
+///
+/// #### `case_insensitive`
+///
+/// Placed at the struct level, this attribute makes column lookups fall back to an
+/// ASCII case-insensitive comparison if no column matches a field's name exactly.
+/// This is useful for databases which fold unquoted identifiers to upper case:
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// #[sqlx(case_insensitive)]
+/// struct User {
+///     // matches `ID`, `Id` or `id`
+///     id: i32,
+///     name: String,
+/// }
+/// ```
+///
+/// An exact match always takes precedence, and it can be combined with `rename_all` and `alias`.
+///
 /// #### `default`
 ///
 /// When your struct contains a field that is not present in your query,
@@ -112,6 +146,27 @@ use crate::{error::Error, row::Row};
 ///
 /// This is similar to how `#[serde(default)]` behaves.
 ///
+/// #### `default_on_null`
+///
+/// The `default` attribute only applies when a column is missing from the result.
+/// To also substitute the default value when the column is present but `NULL`,
+/// use `default_on_null` instead. This avoids wrapping fields in `Option` only to
+/// immediately unwrap them again:
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// struct Totals {
+///     customer_id: i32,
+///     // `SUM()` returns `NULL` for empty groups
+///     #[sqlx(default_on_null)]
+///     total_spent: i64,
+/// }
+/// ```
+///
+/// If the struct-level `default` attribute is also present, the value is taken from the
+/// `Default` implementation for the struct. This attribute is compatible with `try_from`,
+/// but cannot be combined with `flatten` or `json`.
+///
 /// #### `flatten`
 ///
 /// If you want to handle a field that implements [`FromRow`],
@@ -302,6 +357,43 @@ pub trait FromRow<'r, R: Row>: Sized {
     fn from_row(row: &'r R) -> Result<Self, Error>;
 }
 
+/// Resolve which column name a derived [`FromRow`] implementation should read for a field.
+///
+/// Returns the first of `names` that exactly matches a column in `row`. If there is none and
+/// `case_insensitive` is set, the name of the first column matching any of `names` ignoring
+/// ASCII case is returned instead. Otherwise, returns the first name so that the lookup fails
+/// with [`Error::ColumnNotFound`] for the primary name of the field.
+#[doc(hidden)]
+pub fn resolve_column_name<'r, R: Row>(
+    row: &'r R,
+    names: &[&'r str],
+    case_insensitive: bool,
+) -> &'r str {
+    let columns = row.columns();
+
+    let exact = names
+        .iter()
+        .find(|name| columns.iter().any(|column| column.name() == **name));
+
+    if let Some(name) = exact {
+        return name;
+    }
+
+    if case_insensitive {
+        let matched = names.iter().find_map(|name| {
+            columns
+                .iter()
+                .find(|column| column.name().eq_ignore_ascii_case(name))
+        });
+
+        if let Some(column) = matched {
+            return column.name();
+        }
+    }
+
+    names[0]
+}
+
 impl<'r, R> FromRow<'r, R> for ()
 where
     R: Row,
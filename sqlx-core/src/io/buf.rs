@@ -10,7 +10,7 @@ pub trait BufExt: Buf {
     fn get_bytes_nul(&mut self) -> Result<Bytes, Error>;
 
     // Read a byte sequence of the exact length
-    fn get_bytes(&mut self, len: usize) -> Bytes;
+    fn get_bytes(&mut self, len: usize) -> Result<Bytes, Error>;
 
     // Read a nul-terminated string
     fn get_str_nul(&mut self) -> Result<String, Error>;
@@ -31,11 +31,12 @@ impl BufExt for Bytes {
         Ok(v)
     }
 
-    fn get_bytes(&mut self, len: usize) -> Bytes {
-        let v = self.slice(..len);
-        self.advance(len);
+    fn get_bytes(&mut self, len: usize) -> Result<Bytes, Error> {
+        if self.len() < len {
+            return Err(err_protocol!("expected {len} bytes, got {}", self.len()));
+        }
 
-        v
+        Ok(self.split_to(len))
     }
 
     fn get_str_nul(&mut self) -> Result<String, Error> {
@@ -47,12 +48,10 @@ impl BufExt for Bytes {
     }
 
     fn get_str(&mut self, len: usize) -> Result<String, Error> {
-        let v = from_utf8(&self[..len])
-            .map_err(|err| err_protocol!("{}", err))
-            .map(ToOwned::to_owned)?;
-
-        self.advance(len);
+        let bytes = self.get_bytes(len)?;
 
-        Ok(v)
+        from_utf8(&bytes)
+            .map(ToOwned::to_owned)
+            .map_err(|err| err_protocol!("{}", err))
     }
 }
@@ -0,0 +1,152 @@
+//! Streaming query results as JSON into an [`AsyncWrite`].
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use futures_core::stream::BoxStream;
+use futures_io::AsyncWrite;
+use futures_util::{AsyncWriteExt, TryStreamExt};
+
+use crate::column::{Column, ColumnIndex};
+use crate::database::Database;
+use crate::error::{BoxDynError, Error};
+use crate::row::Row;
+use crate::types::JsonValue;
+use crate::value::ValueRef;
+
+/// Rows are written once this many bytes have been buffered.
+const BUFFER_SIZE: usize = 8 * 1024;
+
+/// A [`Database`] whose values can be converted to JSON, for
+/// [`Query::fetch_json_array()`][crate::query::Query::fetch_json_array].
+pub trait DecodeJson: Database {
+    /// Convert a value which is not `NULL` to JSON.
+    ///
+    /// Values of types without a natural JSON representation should be returned as strings,
+    /// and types which can't be converted at all should return an error naming the type.
+    fn decode_json(value: Self::ValueRef<'_>) -> Result<JsonValue, BoxDynError>;
+}
+
+/// How [`write_rows()`] separates the rows it writes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum JsonFormat {
+    /// A single JSON array of objects.
+    Array,
+    /// One JSON object per line, also known as NDJSON.
+    Lines,
+}
+
+/// Write each row of `rows` as a JSON object keyed by column name, in column order.
+///
+/// Returns the number of rows written.
+pub(crate) async fn write_rows<DB, W>(
+    mut rows: BoxStream<'_, Result<DB::Row, Error>>,
+    writer: &mut W,
+    format: JsonFormat,
+) -> Result<u64, Error>
+where
+    DB: DecodeJson,
+    usize: ColumnIndex<DB::Row>,
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    let mut buf = Vec::with_capacity(BUFFER_SIZE);
+    let mut count = 0u64;
+
+    if format == JsonFormat::Array {
+        buf.push(b'[');
+    }
+
+    while let Some(row) = rows.try_next().await? {
+        if format == JsonFormat::Array && count > 0 {
+            buf.push(b',');
+        }
+
+        write_row::<DB>(&mut buf, &row)?;
+        count += 1;
+
+        if format == JsonFormat::Lines {
+            buf.push(b'\n');
+        }
+
+        if buf.len() >= BUFFER_SIZE {
+            writer.write_all(&buf).await?;
+            buf.clear();
+        }
+    }
+
+    if format == JsonFormat::Array {
+        buf.push(b']');
+    }
+
+    writer.write_all(&buf).await?;
+    writer.flush().await?;
+
+    Ok(count)
+}
+
+fn write_row<DB>(buf: &mut Vec<u8>, row: &DB::Row) -> Result<(), Error>
+where
+    DB: DecodeJson,
+    usize: ColumnIndex<DB::Row>,
+{
+    buf.push(b'{');
+
+    for (index, column) in row.columns().iter().enumerate() {
+        if index > 0 {
+            buf.push(b',');
+        }
+
+        // writing into a `Vec` only fails if the value can't be serialized, which a string
+        // or a `JsonValue` always can
+        serde_json::to_writer(&mut *buf, column.name()).map_err(Error::protocol)?;
+        buf.push(b':');
+
+        let value = row.try_get_raw(index)?;
+
+        let json = if value.is_null() {
+            JsonValue::Null
+        } else {
+            DB::decode_json(value).map_err(|source| Error::ColumnDecode {
+                index: format!("{:?}", column.name()),
+                source,
+            })?
+        };
+
+        serde_json::to_writer(&mut *buf, &json).map_err(Error::protocol)?;
+    }
+
+    buf.push(b'}');
+
+    Ok(())
+}
+
+/// Convert a float to JSON, which can't represent `NaN` or the infinities, so those become `null`.
+pub fn float_to_json(value: f64) -> JsonValue {
+    serde_json::Number::from_f64(value).map_or(JsonValue::Null, JsonValue::Number)
+}
+
+/// Convert a single-precision float to JSON like [`float_to_json()`].
+///
+/// The value is widened through its shortest decimal representation, so that `0.1f32` is
+/// written as `0.1` and not as `0.10000000149011612`.
+pub fn f32_to_json(value: f32) -> JsonValue {
+    float_to_json(value.to_string().parse().unwrap_or(f64::NAN))
+}
+
+/// Convert bytes to JSON as a string in standard, padded base64.
+pub fn bytes_to_json(value: &[u8]) -> JsonValue {
+    JsonValue::String(STANDARD.encode(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_float_and_bytes_to_json() {
+        assert_eq!(float_to_json(1.5), serde_json::json!(1.5));
+        assert_eq!(float_to_json(f64::NAN), JsonValue::Null);
+        assert_eq!(float_to_json(f64::INFINITY), JsonValue::Null);
+        assert_eq!(f32_to_json(0.1), serde_json::json!(0.1));
+        assert_eq!(f32_to_json(f32::NAN), JsonValue::Null);
+        assert_eq!(bytes_to_json(b"sqlx"), serde_json::json!("c3FseA=="));
+    }
+}
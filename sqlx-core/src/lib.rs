@@ -60,13 +60,24 @@ pub mod column;
 #[macro_use]
 pub mod statement;
 
+pub mod augment;
 pub mod common;
+#[cfg(feature = "sqlx-toml")]
+pub mod config;
 pub mod database;
+pub mod database_url;
+pub mod deadline;
 pub mod describe;
+pub mod estimate;
 pub mod executor;
+pub mod fetch_guard;
+pub mod fixtures;
+pub mod from_columns;
 pub mod from_row;
 pub mod fs;
 pub mod io;
+#[cfg(feature = "json")]
+pub mod json_writer;
 pub mod logger;
 pub mod net;
 pub mod query_as;
@@ -76,9 +87,12 @@ pub mod query_scalar;
 pub mod raw_sql;
 pub mod row;
 pub mod rt;
+pub mod script;
 pub mod sync;
 pub mod type_checking;
 pub mod type_info;
+#[cfg(feature = "sqlx-toml")]
+pub mod type_registry;
 pub mod value;
 
 #[cfg(feature = "migrate")]
@@ -87,6 +101,12 @@ pub mod migrate;
 #[cfg(feature = "any")]
 pub mod any;
 
+#[cfg(all(
+    feature = "tower",
+    any(feature = "_rt-async-std", feature = "_rt-tokio")
+))]
+pub mod tower;
+
 // Implements test support with automatic DB management.
 #[cfg(feature = "migrate")]
 pub mod testing;
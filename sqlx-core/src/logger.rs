@@ -1,4 +1,5 @@
 use crate::connection::LogSettings;
+use crate::transaction::JournalEntry;
 use std::time::Instant;
 
 // Yes these look silly. `tracing` doesn't currently support dynamic levels
@@ -90,6 +91,15 @@ impl<'q> QueryLogger<'q> {
     pub fn finish(&self) {
         let elapsed = self.start.elapsed();
 
+        if let Some(journal) = &self.settings.journal {
+            journal.record(JournalEntry {
+                sql: self.sql.to_owned(),
+                rows_returned: self.rows_returned,
+                rows_affected: self.rows_affected,
+                elapsed,
+            });
+        }
+
         let was_slow = elapsed >= self.settings.slow_statements_duration;
 
         let lvl = if was_slow {
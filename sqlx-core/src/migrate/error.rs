@@ -9,6 +9,13 @@ pub enum MigrateError {
     #[error("while executing migration {1}: {0}")]
     ExecuteMigration(#[source] Error, i64),
 
+    /// One of the [`MigrationHooks`][crate::migrate::MigrationHooks] of migration `{1}` failed.
+    #[error("while running a hook of migration {1}: {0}")]
+    Hook(#[source] Error, i64),
+
+    #[error("while decompressing migration {1}: {0}")]
+    Decompress(#[source] BoxDynError, i64),
+
     #[error("while resolving migrations: {0}")]
     Source(#[source] BoxDynError),
 
@@ -39,4 +46,12 @@ pub enum MigrateError {
         "migration {0} is partially applied; fix and remove row from `_sqlx_migrations` table"
     )]
     Dirty(i64),
+
+    #[error(
+        "migrations table `{0}` must be upgraded from version {1} to {2}, but schema upgrades are disabled"
+    )]
+    SchemaUpgradeRequired(String, u32, u32),
+
+    #[error("database driver does not support {0}")]
+    NotSupported(&'static str),
 }
@@ -0,0 +1,237 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use futures_core::future::BoxFuture;
+
+use crate::acquire::Acquire;
+use crate::connection::Connection;
+use crate::database::Database;
+use crate::error::Error;
+use crate::migrate::{Migrate, MigrateError, Migration, MigrationMetadata, Migrator};
+
+type Hook<DB> = dyn for<'c> Fn(
+        &'c mut <DB as Database>::Connection,
+        &'c Migration,
+    ) -> BoxFuture<'c, Result<(), Error>>
+    + Send
+    + Sync
+    + 'static;
+
+type FailureHook<DB> = dyn for<'c> Fn(
+        &'c mut <DB as Database>::Connection,
+        &'c Migration,
+        &'c MigrateError,
+    ) -> BoxFuture<'c, ()>
+    + Send
+    + Sync
+    + 'static;
+
+/// Callbacks run around each migration applied with [`Migrator::with_hooks()`].
+///
+/// The callbacks are passed the connection the migration is applied on and, unless the
+/// migration opted out of transactions with `-- no-transaction`, are run in the transaction of
+/// the migration: anything they write is committed with it, or rolled back if the migration or
+/// one of the callbacks fails. MySQL commits implicitly before and after most DDL statements, so
+/// the callbacks are run outside of a transaction there.
+///
+/// As with [`PoolOptions::after_connect()`][crate::pool::PoolOptions::after_connect], the
+/// callbacks must return a boxed future:
+///
+/// ```rust,no_run
+/// # async fn example(pool: sqlx::PgPool) -> Result<(), sqlx::migrate::MigrateError> {
+/// use sqlx::migrate::{MigrationHooks, Migrator};
+///
+/// let hooks = MigrationHooks::new().after_migration(|conn, migration| {
+///     Box::pin(async move {
+///         sqlx::query("INSERT INTO audit_log (event, version) VALUES ('migrated', $1)")
+///             .bind(migration.version)
+///             .execute(&mut *conn)
+///             .await?;
+///
+///         Ok(())
+///     })
+/// });
+///
+/// let migrator = Migrator::new(std::path::Path::new("./migrations")).await?;
+/// migrator.with_hooks(hooks).run(&pool).await
+/// # }
+/// ```
+pub struct MigrationHooks<DB: Database> {
+    before: Option<Arc<Hook<DB>>>,
+    after: Option<Arc<Hook<DB>>>,
+    on_failure: Option<Arc<FailureHook<DB>>>,
+}
+
+impl<DB: Database> MigrationHooks<DB> {
+    /// No callbacks, to add them to.
+    pub fn new() -> Self {
+        Self {
+            before: None,
+            after: None,
+            on_failure: None,
+        }
+    }
+
+    /// Run `callback` before each migration is applied.
+    ///
+    /// If it returns an error, the migration is not applied and fails with
+    /// [`MigrateError::Hook`].
+    pub fn before_migration<F>(mut self, callback: F) -> Self
+    where
+        for<'c> F: Fn(&'c mut DB::Connection, &'c Migration) -> BoxFuture<'c, Result<(), Error>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.before = Some(Arc::new(callback));
+        self
+    }
+
+    /// Run `callback` after each migration is applied and recorded in the migrations table,
+    /// before the transaction is committed.
+    ///
+    /// If it returns an error, the migration fails with [`MigrateError::Hook`] and its
+    /// transaction is rolled back.
+    pub fn after_migration<F>(mut self, callback: F) -> Self
+    where
+        for<'c> F: Fn(&'c mut DB::Connection, &'c Migration) -> BoxFuture<'c, Result<(), Error>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.after = Some(Arc::new(callback));
+        self
+    }
+
+    /// Run `callback` when a migration, or one of the other callbacks, fails, with the error
+    /// the migration then fails with.
+    ///
+    /// It is run after the transaction of the migration was rolled back, so that it can still
+    /// use the connection.
+    pub fn on_failure<F>(mut self, callback: F) -> Self
+    where
+        for<'c> F: Fn(&'c mut DB::Connection, &'c Migration, &'c MigrateError) -> BoxFuture<'c, ()>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.on_failure = Some(Arc::new(callback));
+        self
+    }
+}
+
+impl<DB: Database> Default for MigrationHooks<DB> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<DB: Database> Clone for MigrationHooks<DB> {
+    fn clone(&self) -> Self {
+        Self {
+            before: self.before.clone(),
+            after: self.after.clone(),
+            on_failure: self.on_failure.clone(),
+        }
+    }
+}
+
+impl<DB: Database> Debug for MigrationHooks<DB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MigrationHooks")
+            .field("before", &self.before.is_some())
+            .field("after", &self.after.is_some())
+            .field("on_failure", &self.on_failure.is_some())
+            .finish()
+    }
+}
+
+/// A [`Migrator`] running [`MigrationHooks`] around each migration; created by
+/// [`Migrator::with_hooks()`].
+#[derive(Debug)]
+pub struct MigratorWithHooks<'m, DB: Database> {
+    migrator: &'m Migrator,
+    hooks: MigrationHooks<DB>,
+}
+
+impl<'m, DB: Database> MigratorWithHooks<'m, DB>
+where
+    DB::Connection: Migrate,
+{
+    pub(super) fn new(migrator: &'m Migrator, hooks: MigrationHooks<DB>) -> Self {
+        Self { migrator, hooks }
+    }
+
+    /// Run any pending migrations like [`Migrator::run()`], with the hooks.
+    pub async fn run<'a, A>(&self, migrator: A) -> Result<(), MigrateError>
+    where
+        A: Acquire<'a, Database = DB>,
+    {
+        let mut conn = migrator.acquire().await?;
+        self.run_direct(&mut *conn).await
+    }
+
+    // Getting around the annoying "implementation of `Acquire` is not general enough" error
+    #[doc(hidden)]
+    pub async fn run_direct(&self, conn: &mut DB::Connection) -> Result<(), MigrateError> {
+        let pending = self.migrator.start_run(&mut *conn).await?;
+        let metadata = self.migrator.metadata();
+
+        for migration in pending {
+            self.apply(&mut *conn, migration, &metadata).await?;
+        }
+
+        self.migrator.finish_run(conn).await
+    }
+
+    async fn apply(
+        &self,
+        conn: &mut DB::Connection,
+        migration: &Migration,
+        metadata: &MigrationMetadata,
+    ) -> Result<(), MigrateError> {
+        let result = if migration.no_tx || !conn.transactional_ddl() {
+            self.apply_between_hooks(&mut *conn, migration, metadata)
+                .await
+        } else {
+            // the driver runs the migration in a savepoint of this transaction
+            let mut tx = conn.begin().await?;
+
+            match self.apply_between_hooks(&mut tx, migration, metadata).await {
+                Ok(()) => tx.commit().await.map_err(MigrateError::from),
+                Err(error) => {
+                    tx.rollback().await?;
+                    Err(error)
+                }
+            }
+        };
+
+        if let (Err(error), Some(on_failure)) = (&result, &self.hooks.on_failure) {
+            on_failure(conn, migration, error).await;
+        }
+
+        result
+    }
+
+    async fn apply_between_hooks(
+        &self,
+        conn: &mut DB::Connection,
+        migration: &Migration,
+        metadata: &MigrationMetadata,
+    ) -> Result<(), MigrateError> {
+        let hook_error = |e| MigrateError::Hook(e, migration.version);
+
+        if let Some(before) = &self.hooks.before {
+            before(&mut *conn, migration).await.map_err(hook_error)?;
+        }
+
+        conn.apply_with_metadata(&self.migrator.table_name, migration, metadata)
+            .await?;
+
+        if let Some(after) = &self.hooks.after {
+            after(&mut *conn, migration).await.map_err(hook_error)?;
+        }
+
+        Ok(())
+    }
+}
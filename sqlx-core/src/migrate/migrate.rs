@@ -1,5 +1,5 @@
 use crate::error::Error;
-use crate::migrate::{AppliedMigration, MigrateError, Migration};
+use crate::migrate::{AppliedMigration, MigrateError, Migration, MigrationMetadata};
 use futures_core::future::BoxFuture;
 use std::time::Duration;
 
@@ -23,20 +23,90 @@ pub trait MigrateDatabase {
     }
 }
 
+/// The version of the schema of the migrations table created by this version of SQLx.
+///
+/// Older tables are upgraded on the next run, unless disabled with
+/// [`Migrator::set_schema_upgrade()`][crate::migrate::Migrator::set_schema_upgrade]:
+///
+/// * 1: the original schema.
+/// * 2: adds the `applied_by`, `hostname` and `app_version` columns.
+/// * 3: adds the `skipped` column.
+pub const MIGRATIONS_TABLE_VERSION: u32 = 3;
+
+/// Create the migrations table if it doesn't exist, or upgrade it to
+/// [`MIGRATIONS_TABLE_VERSION`] if it was created by an older version of SQLx and `upgrade` is
+/// set, failing with [`MigrateError::SchemaUpgradeRequired`] otherwise.
+pub async fn prepare_migrations_table<C>(
+    conn: &mut C,
+    table_name: &str,
+    upgrade: bool,
+) -> Result<(), MigrateError>
+where
+    C: Migrate + ?Sized,
+{
+    match conn.migrations_table_version(table_name).await? {
+        None => conn.ensure_migrations_table(table_name).await,
+        Some(version) if version >= MIGRATIONS_TABLE_VERSION => Ok(()),
+        Some(version) if upgrade => conn.upgrade_migrations_table(table_name, version).await,
+        Some(version) => Err(MigrateError::SchemaUpgradeRequired(
+            table_name.to_owned(),
+            version,
+            MIGRATIONS_TABLE_VERSION,
+        )),
+    }
+}
+
 // 'e = Executor
+// `table_name` is the table tracking applied migrations, `_sqlx_migrations` by default;
+// it may be qualified with a schema, and is interpolated into SQL as-is
 pub trait Migrate {
     // ensure migrations table exists
-    // will create or migrate it if needed
-    fn ensure_migrations_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>>;
+    // will create it at [MIGRATIONS_TABLE_VERSION] if needed, but never upgrades an existing one
+    fn ensure_migrations_table<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>>;
+
+    // Return the schema version of the migrations table,
+    // detected from its columns, or None if it doesn't exist.
+    // The default always returns None, so the table is only ensured, never upgraded.
+    fn migrations_table_version<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<u32>, MigrateError>> {
+        let _ = table_name;
+        Box::pin(async { Ok(None) })
+    }
+
+    // Upgrade the migrations table from schema version `from` to [MIGRATIONS_TABLE_VERSION],
+    // in a transaction if the database supports transactional DDL.
+    fn upgrade_migrations_table<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        from: u32,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        let _ = (table_name, from);
+        Box::pin(async { Err(MigrateError::NotSupported("upgrading the migrations table")) })
+    }
 
     // Return the version on which the database is dirty or None otherwise.
     // "dirty" means there is a partially applied migration that failed.
-    fn dirty_version(&mut self) -> BoxFuture<'_, Result<Option<i64>, MigrateError>>;
+    fn dirty_version<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<i64>, MigrateError>>;
 
     // Return the ordered list of applied migrations
-    fn list_applied_migrations(
-        &mut self,
-    ) -> BoxFuture<'_, Result<Vec<AppliedMigration>, MigrateError>>;
+    fn list_applied_migrations<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>>;
+
+    // Whether DDL statements are transactional, so that other statements can share the
+    // transaction of a migration; MySQL commits implicitly before and after most of them.
+    fn transactional_ddl(&mut self) -> bool {
+        true
+    }
 
     // Should acquire a database lock so that only one migration process
     // can run at a time. [`Migrate`] will call this function before applying
@@ -52,14 +122,95 @@ pub trait Migrate {
     // returns the time taking to run the migration SQL
     fn apply<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>>;
 
+    // same as `apply()`, additionally recording the given metadata with the migration
+    // drivers that do not record metadata may rely on this default, which ignores it
+    fn apply_with_metadata<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+        metadata: &'m MigrationMetadata,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        let _ = metadata;
+        self.apply(table_name, migration)
+    }
+
+    // Render the statements `ensure_migrations_table()` would execute, without executing them.
+    fn ensure_migrations_table_script(&mut self, table_name: &str) -> Result<String, MigrateError> {
+        let _ = table_name;
+        Err(MigrateError::NotSupported("rendering migration scripts"))
+    }
+
+    // Render the statements `apply_with_metadata()` would execute, including the bookkeeping in
+    // the migrations table, as a script to be reviewed or run by hand; nothing is executed.
+    // Bound values are inlined as literals, and `execution_time` is left at `-1` since it is
+    // only known once the migration has run.
+    fn apply_script(
+        &mut self,
+        table_name: &str,
+        migration: &Migration,
+        metadata: &MigrationMetadata,
+    ) -> Result<String, MigrateError> {
+        let _ = (table_name, migration, metadata);
+        Err(MigrateError::NotSupported("rendering migration scripts"))
+    }
+
     // run a revert SQL from migration in a DDL transaction
     // deletes the row in [_migrations] table with specified migration version on completion (success or failure)
     // returns the time taking to run the migration SQL
     fn revert<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>>;
+
+    // insert a row to [_migrations] table recording the migration as applied, without running it,
+    // e.g. when adopting migrations on a database whose schema already reflects them
+    fn mark_applied<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+        metadata: &'m MigrationMetadata,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        let _ = (table_name, migration, metadata);
+        Box::pin(async { Err(MigrateError::NotSupported("marking migrations as applied")) })
+    }
+
+    // insert a row to [_migrations] table recording the migration as skipped, without running it,
+    // for a migration which is intentionally not applied to this database
+    fn mark_skipped<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+        metadata: &'m MigrationMetadata,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        let _ = (table_name, migration, metadata);
+        Box::pin(async { Err(MigrateError::NotSupported("marking migrations as skipped")) })
+    }
+
+    // replace the checksum recorded for the applied migration with specified version,
+    // e.g. after its file was edited deliberately; the migration itself is not run again
+    fn update_checksum<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        version: i64,
+        checksum: &'e [u8],
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        let _ = (table_name, version, checksum);
+        Box::pin(async { Err(MigrateError::NotSupported("updating migration checksums")) })
+    }
+
+    // delete the row in [_migrations] table with specified migration version,
+    // without running its down migration
+    fn remove_applied_migration<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        version: i64,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        let _ = (table_name, version);
+        Box::pin(async { Err(MigrateError::NotSupported("removing applied migrations")) })
+    }
 }
@@ -1,17 +1,21 @@
 use std::borrow::Cow;
+use std::time::{Duration, SystemTime};
 
 use sha2::{Digest, Sha384};
 
-use super::MigrationType;
+use super::{MigrateError, MigrationType};
 
 #[derive(Debug, Clone)]
 pub struct Migration {
     pub version: i64,
     pub description: Cow<'static, str>,
     pub migration_type: MigrationType,
-    pub sql: Cow<'static, str>,
+    // empty if the migration was embedded compressed, see `sql()`
+    sql: Cow<'static, str>,
     pub checksum: Cow<'static, [u8]>,
     pub no_tx: bool,
+    // the deflate-compressed SQL of a migration embedded with `migrate!(.., compress = true)`
+    compressed_sql: Option<&'static [u8]>,
 }
 
 impl Migration {
@@ -31,12 +35,140 @@ impl Migration {
             sql,
             checksum,
             no_tx,
+            compressed_sql: None,
         }
     }
+
+    /// A migration embedded by `migrate!()`, whose SQL is in `compressed_sql` instead of `sql`
+    /// if it is given.
+    #[doc(hidden)]
+    pub const fn embedded(
+        version: i64,
+        description: Cow<'static, str>,
+        migration_type: MigrationType,
+        sql: Cow<'static, str>,
+        compressed_sql: Option<&'static [u8]>,
+        checksum: Cow<'static, [u8]>,
+        no_tx: bool,
+    ) -> Self {
+        Migration {
+            version,
+            description,
+            migration_type,
+            sql,
+            checksum,
+            no_tx,
+            compressed_sql,
+        }
+    }
+
+    /// Whether the SQL of this migration was embedded compressed.
+    #[doc(hidden)]
+    pub fn is_compressed(&self) -> bool {
+        self.compressed_sql.is_some()
+    }
+
+    /// The version a repeatable migration with `description` is tracked under.
+    ///
+    /// Repeatable migrations have no version of their own, so they are recorded in the
+    /// migrations table under a negative version derived from their description, which cannot
+    /// collide with the (positive) versions of other migrations.
+    pub fn repeatable_version(description: &str) -> i64 {
+        let digest = Sha384::digest(description.as_bytes());
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&digest[..8]);
+
+        i64::from_be_bytes(bytes) | i64::MIN
+    }
+
+    /// Whether `version` is one a repeatable migration is tracked under.
+    ///
+    /// Repeatable migrations are keyed by their description, so renaming one leaves its record
+    /// behind under the old name; such records are not considered missing migrations.
+    pub fn is_repeatable_version(version: i64) -> bool {
+        version < 0
+    }
+
+    /// Whether this migration should be applied, given the record of it having been applied
+    /// already, if any.
+    ///
+    /// Repeatable migrations are applied again whenever their checksum changes; any other applied
+    /// migration which has since been modified is an error.
+    pub fn is_pending(&self, applied: Option<&AppliedMigration>) -> Result<bool, MigrateError> {
+        match applied {
+            None => Ok(true),
+            Some(applied) if applied.checksum == self.checksum => Ok(false),
+            Some(_) if self.migration_type.is_repeatable() => Ok(true),
+            Some(_) => Err(MigrateError::VersionMismatch(self.version)),
+        }
+    }
+
+    /// Get the SQL of this migration, decompressing it first if it was embedded compressed.
+    pub fn sql(&self) -> Result<Cow<'_, str>, MigrateError> {
+        let Some(compressed) = self.compressed_sql else {
+            return Ok(Cow::Borrowed(&self.sql));
+        };
+
+        let bytes = miniz_oxide::inflate::decompress_to_vec(compressed)
+            .map_err(|e| MigrateError::Decompress(format!("{e:?}").into(), self.version))?;
+
+        String::from_utf8(bytes)
+            .map(Cow::Owned)
+            .map_err(|e| MigrateError::Decompress(e.into(), self.version))
+    }
 }
 
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct AppliedMigration {
     pub version: i64,
     pub checksum: Cow<'static, [u8]>,
+    /// How long the migration took to apply, if it was recorded.
+    pub execution_time: Option<Duration>,
+    /// The database user which applied the migration, if recorded.
+    pub applied_by: Option<String>,
+    /// The hostname of the machine which applied the migration, if recorded.
+    pub hostname: Option<String>,
+    /// The application version given to [`Migrator::set_app_version()`][super::Migrator::set_app_version()], if any.
+    pub app_version: Option<String>,
+    /// When the migration was applied, to the second.
+    pub installed_on: Option<SystemTime>,
+    /// Whether the migration was recorded as skipped, without being run, because it was in the
+    /// list given to [`Migrator::set_skip()`][super::Migrator::set_skip()].
+    pub skipped: bool,
+}
+
+impl AppliedMigration {
+    /// A record of the migration `version` applied with `checksum`, with no other details.
+    pub fn new(version: i64, checksum: Cow<'static, [u8]>) -> Self {
+        AppliedMigration {
+            version,
+            checksum,
+            execution_time: None,
+            applied_by: None,
+            hostname: None,
+            app_version: None,
+            installed_on: None,
+            skipped: false,
+        }
+    }
+}
+
+/// Information about the process applying a migration, recorded alongside it.
+#[derive(Debug, Clone, Default)]
+pub struct MigrationMetadata {
+    /// The hostname of the machine applying the migration.
+    pub hostname: Option<String>,
+    /// The version of the application applying the migration.
+    pub app_version: Option<String>,
+}
+
+impl MigrationMetadata {
+    /// Metadata for the current process: the hostname of this machine, without an application version.
+    pub fn current() -> Self {
+        MigrationMetadata {
+            hostname: whoami::fallible::hostname().ok(),
+            app_version: None,
+        }
+    }
 }
@@ -2,6 +2,7 @@ use super::Migrator;
 
 /// Migration Type represents the type of migration
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum MigrationType {
     /// Simple migration are single file migrations with no up / down queries
     Simple,
@@ -13,9 +14,17 @@ pub enum MigrationType {
     /// ReversibleDown migrations represents the  delete or downgrade part of a reversible migrations
     /// It is expected the every migration of this type will have a corresponding up file
     ReversibleDown,
+
+    /// Repeatable migrations have no version, and are applied after all other migrations
+    /// whenever their contents change. They are named `R__<DESCRIPTION>.sql`, and are typically
+    /// used to redefine views, functions and triggers, e.g. with `CREATE OR REPLACE`.
+    Repeatable,
 }
 
 impl MigrationType {
+    /// The prefix of the file name of a repeatable migration.
+    pub const REPEATABLE_PREFIX: &'static str = "R__";
+
     pub fn from_filename(filename: &str) -> Self {
         if filename.ends_with(MigrationType::ReversibleUp.suffix()) {
             MigrationType::ReversibleUp
@@ -31,6 +40,7 @@ impl MigrationType {
             MigrationType::Simple => false,
             MigrationType::ReversibleUp => true,
             MigrationType::ReversibleDown => true,
+            MigrationType::Repeatable => false,
         }
     }
 
@@ -39,6 +49,7 @@ impl MigrationType {
             MigrationType::Simple => true,
             MigrationType::ReversibleUp => true,
             MigrationType::ReversibleDown => false,
+            MigrationType::Repeatable => true,
         }
     }
 
@@ -47,6 +58,7 @@ impl MigrationType {
             MigrationType::Simple => false,
             MigrationType::ReversibleUp => false,
             MigrationType::ReversibleDown => true,
+            MigrationType::Repeatable => false,
         }
     }
 
@@ -55,6 +67,7 @@ impl MigrationType {
             MigrationType::Simple => "migrate",
             MigrationType::ReversibleUp => "migrate",
             MigrationType::ReversibleDown => "revert",
+            MigrationType::Repeatable => "repeatable",
         }
     }
 
@@ -63,6 +76,7 @@ impl MigrationType {
             MigrationType::Simple => ".sql",
             MigrationType::ReversibleUp => ".up.sql",
             MigrationType::ReversibleDown => ".down.sql",
+            MigrationType::Repeatable => ".sql",
         }
     }
 
@@ -71,11 +85,21 @@ impl MigrationType {
             MigrationType::Simple => "-- Add migration script here\n",
             MigrationType::ReversibleUp => "-- Add up migration script here\n",
             MigrationType::ReversibleDown => "-- Add down migration script here\n",
+            MigrationType::Repeatable => {
+                "-- Add repeatable migration script here; it is re-applied whenever it changes\n"
+            }
         }
     }
 
+    pub fn is_repeatable(&self) -> bool {
+        matches!(self, MigrationType::Repeatable)
+    }
+
     pub fn infer(migrator: &Migrator, reversible: bool) -> MigrationType {
-        match migrator.iter().next() {
+        match migrator
+            .iter()
+            .find(|migration| !migration.migration_type.is_repeatable())
+        {
             Some(first_migration) => first_migration.migration_type,
             None => {
                 if reversible {
@@ -1,5 +1,9 @@
 use crate::acquire::Acquire;
-use crate::migrate::{AppliedMigration, Migrate, MigrateError, Migration, MigrationSource};
+use crate::database::Database;
+use crate::migrate::{
+    prepare_migrations_table, AppliedMigration, Migrate, MigrateError, Migration, MigrationHooks,
+    MigrationMetadata, MigrationSource, MigratorWithHooks, MIGRATIONS_TABLE_VERSION,
+};
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
@@ -23,6 +27,22 @@ pub struct Migrator {
     pub locking: bool,
     #[doc(hidden)]
     pub no_tx: bool,
+    #[doc(hidden)]
+    pub app_version: Option<Cow<'static, str>>,
+    #[doc(hidden)]
+    pub table_name: Cow<'static, str>,
+    #[doc(hidden)]
+    pub schema_upgrade: bool,
+    #[doc(hidden)]
+    pub skip: Cow<'static, [i64]>,
+}
+
+/// What a run does with each migration, from [`Migrator::plan()`].
+#[derive(Default)]
+struct Plan<'m> {
+    pending: Vec<&'m Migration>,
+    skip: Vec<&'m Migration>,
+    unskip: Vec<&'m Migration>,
 }
 
 fn validate_applied_migrations(
@@ -36,6 +56,10 @@ fn validate_applied_migrations(
     let migrations: HashSet<_> = migrator.iter().map(|m| m.version).collect();
 
     for applied_migration in applied_migrations {
+        if Migration::is_repeatable_version(applied_migration.version) {
+            continue;
+        }
+
         if !migrations.contains(&applied_migration.version) {
             return Err(MigrateError::VersionMissing(applied_migration.version));
         }
@@ -51,6 +75,10 @@ impl Migrator {
         ignore_missing: false,
         no_tx: false,
         locking: true,
+        app_version: None,
+        table_name: Cow::Borrowed("_sqlx_migrations"),
+        schema_upgrade: true,
+        skip: Cow::Borrowed(&[]),
     };
 
     /// Creates a new instance with the given source.
@@ -71,6 +99,9 @@ impl Migrator {
     /// # }
     /// ```
     /// See [MigrationSource] for details on structure of the `./migrations` directory.
+    ///
+    /// The migrations of several directories are merged by passing them as a `Vec<PathBuf>` or
+    /// `&[PathBuf]`; the resolution fails if a version is defined in more than one of them.
     pub async fn new<'s, S>(source: S) -> Result<Self, MigrateError>
     where
         S: MigrationSource<'s>,
@@ -100,6 +131,65 @@ impl Migrator {
         self
     }
 
+    /// Specify the table used to track applied migrations. Defaults to `_sqlx_migrations`.
+    ///
+    /// The name may be qualified with a schema, e.g. `myschema.schema_migrations`, which lets
+    /// independent applications sharing a database each keep their own migration history.
+    /// The schema must already exist.
+    ///
+    /// The name is interpolated into SQL as-is, so it must not come from untrusted input.
+    pub fn set_table_name(&mut self, table_name: impl Into<Cow<'static, str>>) -> &Self {
+        self.table_name = table_name.into();
+        self
+    }
+
+    /// Get the name of the table used to track applied migrations.
+    pub fn table_name(&self) -> &str {
+        &self.table_name
+    }
+
+    /// Specify whether to upgrade the migrations table if it was created by an older version of
+    /// SQLx. Defaults to `true`.
+    ///
+    /// When disabled, [`run()`][Self::run] and [`undo()`][Self::undo] fail with
+    /// [`MigrateError::SchemaUpgradeRequired`] instead, so that upgrading SQLx never alters the
+    /// table in the middle of a deploy. The upgrade can then be run at a convenient time,
+    /// e.g. with `sqlx migrate run`. See [`MIGRATIONS_TABLE_VERSION`] for the versions.
+    ///
+    /// [`MIGRATIONS_TABLE_VERSION`]: crate::migrate::MIGRATIONS_TABLE_VERSION
+    pub fn set_schema_upgrade(&mut self, schema_upgrade: bool) -> &Self {
+        self.schema_upgrade = schema_upgrade;
+        self
+    }
+
+    /// Specify an application version to record with each migration applied by this migrator.
+    ///
+    /// It is listed by `sqlx migrate info`, alongside the database user, hostname and execution
+    /// time which are always recorded.
+    pub fn set_app_version(&mut self, app_version: impl Into<Cow<'static, str>>) -> &Self {
+        self.app_version = Some(app_version.into());
+        self
+    }
+
+    /// Specify the versions of migrations which are intentionally not applied to this database,
+    /// e.g. a data fix which is only needed in one environment.
+    ///
+    /// [`run()`][Self::run] records each of them as skipped in the migrations table, without
+    /// running it, unless it was applied already, and applies the other migrations as usual.
+    /// A migration which was recorded as skipped, but is no longer in the list, is applied by
+    /// the next run. Listing a version without a migration is an error
+    /// ([`MigrateError::VersionNotPresent`]).
+    pub fn set_skip(&mut self, versions: impl IntoIterator<Item = i64>) -> &Self {
+        self.skip = Cow::Owned(versions.into_iter().collect());
+        self
+    }
+
+    /// Check if the migration with `version` is in the list given to
+    /// [`set_skip()`][Self::set_skip].
+    pub fn is_skipped(&self, version: i64) -> bool {
+        self.skip.contains(&version)
+    }
+
     /// Get an iterator over all known migrations.
     pub fn iter(&self) -> slice::Iter<'_, Migration> {
         self.migrations.iter()
@@ -142,22 +232,50 @@ impl Migrator {
     pub async fn run_direct<C>(&self, conn: &mut C) -> Result<(), MigrateError>
     where
         C: Migrate,
+    {
+        let pending = self.start_run(&mut *conn).await?;
+        let metadata = self.metadata();
+
+        for migration in pending {
+            conn.apply_with_metadata(&self.table_name, migration, &metadata)
+                .await?;
+        }
+
+        self.finish_run(conn).await
+    }
+
+    /// Run any pending migrations like [`run()`][Self::run], calling `hooks` around each of
+    /// them, e.g. to invalidate caches or record an audit event in the same transaction as the
+    /// migration.
+    ///
+    /// See [`MigrationHooks`] for details.
+    pub fn with_hooks<DB: Database>(&self, hooks: MigrationHooks<DB>) -> MigratorWithHooks<'_, DB>
+    where
+        DB::Connection: Migrate,
+    {
+        MigratorWithHooks::new(self, hooks)
+    }
+
+    /// Lock the database, prepare the migrations table and validate the applied migrations,
+    /// returning those to apply, in order.
+    pub(super) async fn start_run<C>(&self, conn: &mut C) -> Result<Vec<&Migration>, MigrateError>
+    where
+        C: Migrate + ?Sized,
     {
         // lock the database for exclusive access by the migrator
         if self.locking {
             conn.lock().await?;
         }
 
-        // creates [_migrations] table only if needed
-        // eventually this will likely migrate previous versions of the table
-        conn.ensure_migrations_table().await?;
+        // creates [_migrations] table only if needed, and upgrades previous versions of it
+        prepare_migrations_table(&mut *conn, &self.table_name, self.schema_upgrade).await?;
 
-        let version = conn.dirty_version().await?;
+        let version = conn.dirty_version(&self.table_name).await?;
         if let Some(version) = version {
             return Err(MigrateError::Dirty(version));
         }
 
-        let applied_migrations = conn.list_applied_migrations().await?;
+        let applied_migrations = conn.list_applied_migrations(&self.table_name).await?;
         validate_applied_migrations(&applied_migrations, self)?;
 
         let applied_migrations: HashMap<_, _> = applied_migrations
@@ -165,23 +283,61 @@ impl Migrator {
             .map(|m| (m.version, m))
             .collect();
 
+        let plan = self.plan(&applied_migrations)?;
+        let metadata = self.metadata();
+
+        for migration in plan.skip {
+            conn.mark_skipped(&self.table_name, migration, &metadata)
+                .await?;
+        }
+
+        // the migrations no longer skipped are applied in place of their records
+        for migration in plan.unskip {
+            conn.remove_applied_migration(&self.table_name, migration.version)
+                .await?;
+        }
+
+        Ok(plan.pending)
+    }
+
+    /// Sort the migrations into those to apply, in order, those to record as skipped, and
+    /// those recorded as skipped which are to be applied now.
+    fn plan(
+        &self,
+        applied_migrations: &HashMap<i64, AppliedMigration>,
+    ) -> Result<Plan<'_>, MigrateError> {
+        if let Some(version) = self.skip.iter().find(|v| !self.version_exists(**v)) {
+            return Err(MigrateError::VersionNotPresent(*version));
+        }
+
+        let mut plan = Plan::default();
+
         for migration in self.iter() {
             if migration.migration_type.is_down_migration() {
                 continue;
             }
 
-            match applied_migrations.get(&migration.version) {
-                Some(applied_migration) => {
-                    if migration.checksum != applied_migration.checksum {
-                        return Err(MigrateError::VersionMismatch(migration.version));
-                    }
-                }
-                None => {
-                    conn.apply(migration).await?;
+            let applied = applied_migrations.get(&migration.version);
+
+            if self.is_skipped(migration.version) {
+                if applied.is_none() {
+                    plan.skip.push(migration);
                 }
+            } else if applied.is_some_and(|applied| applied.skipped) {
+                plan.unskip.push(migration);
+                plan.pending.push(migration);
+            } else if migration.is_pending(applied)? {
+                plan.pending.push(migration);
             }
         }
 
+        Ok(plan)
+    }
+
+    pub(super) async fn finish_run<C>(&self, conn: &mut C) -> Result<(), MigrateError>
+    where
+        C: Migrate + ?Sized,
+    {
         // unlock the migrator to allow other migrators to run
         // but do nothing as we already migrated
         if self.locking {
@@ -191,6 +347,209 @@ impl Migrator {
         Ok(())
     }
 
+    /// The metadata recorded with each migration applied by this migrator.
+    pub(super) fn metadata(&self) -> MigrationMetadata {
+        MigrationMetadata {
+            app_version: self.app_version.as_deref().map(str::to_owned),
+            ..MigrationMetadata::current()
+        }
+    }
+
+    /// Render the SQL that [`run()`][Self::run] would execute to apply pending migrations,
+    /// including creating the migrations table and recording each migration in it, without
+    /// executing it or otherwise modifying the database.
+    ///
+    /// This lets the script be reviewed, or run by hand, before it is applied to production.
+    /// Fails with [`MigrateError::SchemaUpgradeRequired`] if the migrations table was created by
+    /// an older version of SQLx, as the upgrade would have to be applied first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use sqlx::migrate::MigrateError;
+    /// # fn main() -> Result<(), MigrateError> {
+    /// #     sqlx::__rt::test_block_on(async move {
+    /// use sqlx::migrate::Migrator;
+    /// use sqlx::sqlite::SqlitePoolOptions;
+    ///
+    /// let m = Migrator::new(std::path::Path::new("./migrations")).await?;
+    /// let pool = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    /// println!("{}", m.script(&pool).await?);
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    pub async fn script<'a, A>(&self, migrator: A) -> Result<String, MigrateError>
+    where
+        A: Acquire<'a>,
+        <A::Connection as Deref>::Target: Migrate,
+    {
+        let mut conn = migrator.acquire().await?;
+        self.script_direct(&mut *conn).await
+    }
+
+    #[doc(hidden)]
+    pub async fn script_direct<C>(&self, conn: &mut C) -> Result<String, MigrateError>
+    where
+        C: Migrate,
+    {
+        let mut script = String::new();
+
+        let applied_migrations = match conn.migrations_table_version(&self.table_name).await? {
+            None => {
+                script.push_str(
+                    conn.ensure_migrations_table_script(&self.table_name)?
+                        .trim(),
+                );
+                Vec::new()
+            }
+            Some(version) if version < MIGRATIONS_TABLE_VERSION => {
+                return Err(MigrateError::SchemaUpgradeRequired(
+                    self.table_name.to_string(),
+                    version,
+                    MIGRATIONS_TABLE_VERSION,
+                ));
+            }
+            Some(_) => {
+                if let Some(version) = conn.dirty_version(&self.table_name).await? {
+                    return Err(MigrateError::Dirty(version));
+                }
+
+                conn.list_applied_migrations(&self.table_name).await?
+            }
+        };
+
+        validate_applied_migrations(&applied_migrations, self)?;
+
+        let applied_migrations: HashMap<_, _> = applied_migrations
+            .into_iter()
+            .map(|m| (m.version, m))
+            .collect();
+
+        let metadata = self.metadata();
+
+        let plan = self.plan(&applied_migrations)?;
+
+        for migration in plan.pending {
+            if !script.is_empty() {
+                script.push_str("\n\n");
+            }
+
+            if plan.unskip.iter().any(|m| m.version == migration.version) {
+                // language=SQL
+                script.push_str(&format!(
+                    "DELETE FROM {} WHERE version = {};\n\n",
+                    self.table_name, migration.version
+                ));
+            }
+
+            script.push_str(
+                conn.apply_script(&self.table_name, migration, &metadata)?
+                    .trim(),
+            );
+        }
+
+        Ok(script)
+    }
+
+    /// Record all migrations up to and including `version` as applied, without running them.
+    ///
+    /// This is for adopting migrations on an existing database whose schema already reflects
+    /// them, so that [`run()`][Self::run] only applies the newer ones. Migrations which have
+    /// already been applied are left as they are, and repeatable migrations are not recorded,
+    /// so they are run by the next call to [`run()`][Self::run].
+    ///
+    /// Fails with [`MigrateError::VersionNotPresent`] if there is no migration with `version`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use sqlx::migrate::MigrateError;
+    /// # fn main() -> Result<(), MigrateError> {
+    /// #     sqlx::__rt::test_block_on(async move {
+    /// use sqlx::migrate::Migrator;
+    /// use sqlx::sqlite::SqlitePoolOptions;
+    ///
+    /// let m = Migrator::new(std::path::Path::new("./migrations")).await?;
+    /// let pool = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    /// m.baseline(&pool, 20240101000000).await?;
+    /// m.run(&pool).await
+    /// #     })
+    /// # }
+    /// ```
+    pub async fn baseline<'a, A>(&self, migrator: A, version: i64) -> Result<(), MigrateError>
+    where
+        A: Acquire<'a>,
+        <A::Connection as Deref>::Target: Migrate,
+    {
+        let mut conn = migrator.acquire().await?;
+        self.baseline_direct(&mut *conn, version).await
+    }
+
+    #[doc(hidden)]
+    pub async fn baseline_direct<C>(&self, conn: &mut C, version: i64) -> Result<(), MigrateError>
+    where
+        C: Migrate,
+    {
+        if !self
+            .iter()
+            .any(|m| m.version == version && !m.migration_type.is_repeatable())
+        {
+            return Err(MigrateError::VersionNotPresent(version));
+        }
+
+        // lock the database for exclusive access by the migrator
+        if self.locking {
+            conn.lock().await?;
+        }
+
+        // creates [_migrations] table only if needed, and upgrades previous versions of it
+        prepare_migrations_table(&mut *conn, &self.table_name, self.schema_upgrade).await?;
+
+        let dirty_version = conn.dirty_version(&self.table_name).await?;
+        if let Some(dirty_version) = dirty_version {
+            return Err(MigrateError::Dirty(dirty_version));
+        }
+
+        let applied_migrations = conn.list_applied_migrations(&self.table_name).await?;
+        validate_applied_migrations(&applied_migrations, self)?;
+
+        let applied_migrations: HashMap<_, _> = applied_migrations
+            .into_iter()
+            .map(|m| (m.version, m))
+            .collect();
+
+        let metadata = self.metadata();
+
+        for migration in self.iter() {
+            if migration.migration_type.is_down_migration()
+                || migration.migration_type.is_repeatable()
+                || migration.version > version
+            {
+                continue;
+            }
+
+            let applied = applied_migrations.get(&migration.version);
+
+            if self.is_skipped(migration.version) {
+                if applied.is_none() {
+                    conn.mark_skipped(&self.table_name, migration, &metadata)
+                        .await?;
+                }
+            } else if migration.is_pending(applied)? {
+                conn.mark_applied(&self.table_name, migration, &metadata)
+                    .await?;
+            }
+        }
+
+        // unlock the migrator to allow other migrators to run
+        if self.locking {
+            conn.unlock().await?;
+        }
+
+        Ok(())
+    }
+
     /// Run down migrations against the database until a specific version.
     ///
     /// # Examples
@@ -220,16 +579,15 @@ impl Migrator {
             conn.lock().await?;
         }
 
-        // creates [_migrations] table only if needed
-        // eventually this will likely migrate previous versions of the table
-        conn.ensure_migrations_table().await?;
+        // creates [_migrations] table only if needed, and upgrades previous versions of it
+        prepare_migrations_table(&mut *conn, &self.table_name, self.schema_upgrade).await?;
 
-        let version = conn.dirty_version().await?;
+        let version = conn.dirty_version(&self.table_name).await?;
         if let Some(version) = version {
             return Err(MigrateError::Dirty(version));
         }
 
-        let applied_migrations = conn.list_applied_migrations().await?;
+        let applied_migrations = conn.list_applied_migrations(&self.table_name).await?;
         validate_applied_migrations(&applied_migrations, self)?;
 
         let applied_migrations: HashMap<_, _> = applied_migrations
@@ -241,10 +599,19 @@ impl Migrator {
             .iter()
             .rev()
             .filter(|m| m.migration_type.is_down_migration())
-            .filter(|m| applied_migrations.contains_key(&m.version))
             .filter(|m| m.version > target)
         {
-            conn.revert(migration).await?;
+            match applied_migrations.get(&migration.version) {
+                None => {}
+                // it was never run, so only its record is removed
+                Some(applied) if applied.skipped => {
+                    conn.remove_applied_migration(&self.table_name, migration.version)
+                        .await?;
+                }
+                Some(_) => {
+                    conn.revert(&self.table_name, migration).await?;
+                }
+            }
         }
 
         // unlock the migrator to allow other migrators to run
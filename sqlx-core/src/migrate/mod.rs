@@ -1,4 +1,5 @@
 mod error;
+mod hooks;
 #[allow(clippy::module_inception)]
 mod migrate;
 mod migration;
@@ -7,11 +8,12 @@ mod migrator;
 mod source;
 
 pub use error::MigrateError;
-pub use migrate::{Migrate, MigrateDatabase};
-pub use migration::{AppliedMigration, Migration};
+pub use hooks::{MigrationHooks, MigratorWithHooks};
+pub use migrate::{prepare_migrations_table, Migrate, MigrateDatabase, MIGRATIONS_TABLE_VERSION};
+pub use migration::{AppliedMigration, Migration, MigrationMetadata};
 pub use migration_type::MigrationType;
 pub use migrator::Migrator;
 pub use source::MigrationSource;
 
 #[doc(hidden)]
-pub use source::resolve_blocking;
+pub use source::{resolve_blocking, resolve_blocking_merged};
@@ -3,6 +3,7 @@ use crate::migrate::{Migration, MigrationType};
 use futures_core::future::BoxFuture;
 
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fs;
 use std::io;
@@ -14,6 +15,9 @@ use std::path::{Path, PathBuf};
 /// `<VERSION>` is a string that can be parsed into `i64` and its value is
 /// greater than zero, and `<DESCRIPTION>` is a string.
 ///
+/// Files named `R__<DESCRIPTION>.sql` are repeatable migrations, which are applied after all
+/// versioned migrations, in order of description, and re-applied whenever their contents change.
+///
 /// Files that don't match this format are silently ignored.
 ///
 /// You can create a new empty migration script using sqlx-cli:
@@ -21,7 +25,7 @@ use std::path::{Path, PathBuf};
 ///
 /// Note that migrations for each database are tracked using the
 /// `_sqlx_migrations` table (stored in the database). If a migration's hash
-/// changes and it has already been run, this will cause an error.
+/// changes and it has already been run, this will cause an error, unless it is repeatable.
 pub trait MigrationSource<'s>: Debug {
     fn resolve(self) -> BoxFuture<'s, Result<Vec<Migration>, BoxDynError>>;
 }
@@ -44,6 +48,29 @@ impl MigrationSource<'static> for PathBuf {
     }
 }
 
+/// Several directories, e.g. the migrations shared by a library crate and those of the
+/// application using it, merged with [`resolve_blocking_merged()`].
+impl<'s> MigrationSource<'s> for &'s [PathBuf] {
+    fn resolve(self) -> BoxFuture<'s, Result<Vec<Migration>, BoxDynError>> {
+        Box::pin(async move {
+            let canonical = self
+                .iter()
+                .map(|path| path.canonicalize())
+                .collect::<io::Result<Vec<_>>>()?;
+            let migrations_with_paths =
+                crate::rt::spawn_blocking(move || resolve_blocking_merged(&canonical)).await?;
+
+            Ok(migrations_with_paths.into_iter().map(|(m, _p)| m).collect())
+        })
+    }
+}
+
+impl MigrationSource<'static> for Vec<PathBuf> {
+    fn resolve(self) -> BoxFuture<'static, Result<Vec<Migration>, BoxDynError>> {
+        Box::pin(async move { self.as_slice().resolve().await })
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 #[error("{message}")]
 pub struct ResolveError {
@@ -94,26 +121,41 @@ pub fn resolve_blocking(path: &Path) -> Result<Vec<(Migration, PathBuf)>, Resolv
         // would be a breaking change.
         let file_name = file_name.to_string_lossy();
 
-        let parts = file_name.splitn(2, '_').collect::<Vec<_>>();
-
-        if parts.len() != 2 || !parts[1].ends_with(".sql") {
-            // not of the format: <VERSION>_<DESCRIPTION>.<REVERSIBLE_DIRECTION>.sql; ignore
-            continue;
-        }
-
-        let version: i64 = parts[0].parse()
-            .map_err(|_e| ResolveError {
-                message: format!("error parsing migration filename {file_name:?}; expected integer version prefix (e.g. `01_foo.sql`)"),
-                source: None,
-            })?;
-
-        let migration_type = MigrationType::from_filename(parts[1]);
-
-        // remove the `.sql` and replace `_` with ` `
-        let description = parts[1]
-            .trim_end_matches(migration_type.suffix())
-            .replace('_', " ")
-            .to_owned();
+        let (version, migration_type, description) = if let Some(name) = file_name
+            .strip_prefix(MigrationType::REPEATABLE_PREFIX)
+            .and_then(|name| name.strip_suffix(MigrationType::Repeatable.suffix()))
+        {
+            let description = name.replace('_', " ");
+
+            (
+                Migration::repeatable_version(&description),
+                MigrationType::Repeatable,
+                description,
+            )
+        } else {
+            let parts = file_name.splitn(2, '_').collect::<Vec<_>>();
+
+            if parts.len() != 2 || !parts[1].ends_with(".sql") {
+                // not of the format: <VERSION>_<DESCRIPTION>.<REVERSIBLE_DIRECTION>.sql; ignore
+                continue;
+            }
+
+            let version: i64 = parts[0].parse()
+                .map_err(|_e| ResolveError {
+                    message: format!("error parsing migration filename {file_name:?}; expected integer version prefix (e.g. `01_foo.sql`)"),
+                    source: None,
+                })?;
+
+            let migration_type = MigrationType::from_filename(parts[1]);
+
+            // remove the `.sql` and replace `_` with ` `
+            let description = parts[1]
+                .trim_end_matches(migration_type.suffix())
+                .replace('_', " ")
+                .to_owned();
+
+            (version, migration_type, description)
+        };
 
         let sql = fs::read_to_string(&entry_path).map_err(|e| ResolveError {
             message: format!(
@@ -138,8 +180,65 @@ pub fn resolve_blocking(path: &Path) -> Result<Vec<(Migration, PathBuf)>, Resolv
         ));
     }
 
-    // Ensure that we are sorted by version in ascending order.
-    migrations.sort_by_key(|(m, _)| m.version);
+    sort_migrations(&mut migrations);
 
     Ok(migrations)
 }
+
+/// Resolve and merge the migrations from several directories, sorted by version.
+///
+/// Each version may only be defined in one of the directories;
+/// a version appearing in more than one is reported as an error.
+pub fn resolve_blocking_merged(
+    paths: &[PathBuf],
+) -> Result<Vec<(Migration, PathBuf)>, ResolveError> {
+    let mut migrations = Vec::new();
+    let mut sources: HashMap<i64, &Path> = HashMap::new();
+
+    for path in paths {
+        let resolved = resolve_blocking(path)?;
+
+        for (migration, _) in &resolved {
+            if let Some(other) = sources.get(&migration.version) {
+                let migration = if migration.migration_type.is_repeatable() {
+                    format!("repeatable migration {:?}", migration.description)
+                } else {
+                    format!("migration version {}", migration.version)
+                };
+
+                return Err(ResolveError {
+                    message: format!(
+                        "{migration} is defined in both {} and {}",
+                        other.display(),
+                        path.display(),
+                    ),
+                    source: None,
+                });
+            }
+        }
+
+        sources.extend(resolved.iter().map(|(m, _)| (m.version, path.as_path())));
+        migrations.extend(resolved);
+    }
+
+    sort_migrations(&mut migrations);
+
+    Ok(migrations)
+}
+
+/// Sort versioned migrations by version in ascending order, followed by the repeatable migrations
+/// ordered by description.
+///
+/// A stable sort keeps the up/down halves of a reversible migration in their original order.
+fn sort_migrations(migrations: &mut [(Migration, PathBuf)]) {
+    migrations.sort_by(|(a, _), (b, _)| {
+        match (
+            a.migration_type.is_repeatable(),
+            b.migration_type.is_repeatable(),
+        ) {
+            (false, false) => a.version.cmp(&b.version),
+            (true, true) => a.description.cmp(&b.description),
+            (a_repeatable, b_repeatable) => a_repeatable.cmp(&b_repeatable),
+        }
+    });
+}
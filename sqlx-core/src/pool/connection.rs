@@ -9,6 +9,7 @@ use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
 
+use super::events::{CloseReason, PoolEvent};
 use super::inner::{is_beyond_max_lifetime, DecrementSizeGuard, PoolInner};
 use crate::pool::options::PoolConnectionMetadata;
 use std::future::Future;
@@ -85,7 +86,18 @@ impl<DB: Database> PoolConnection<DB> {
     /// [`.close()`]: Connection::close
     pub async fn close(mut self) -> Result<(), Error> {
         let floating = self.take_live().float(self.pool.clone());
-        floating.inner.raw.close().await
+
+        let res = if self.pool.is_closing_hard() {
+            floating.inner.raw.close_hard().await
+        } else {
+            floating.inner.raw.close().await
+        };
+
+        self.pool.events.send(PoolEvent::ConnectionClosed {
+            reason: CloseReason::Requested,
+        });
+
+        res
     }
 
     /// Close this connection on-drop, instead of returning it to the pool.
@@ -166,9 +178,12 @@ impl<DB: Database> PoolConnection<DB> {
         async move {
             if let Some(floating) = floating {
                 // Don't hold the connection forever if it hangs while trying to close
-                crate::rt::timeout(CLOSE_ON_DROP_TIMEOUT, floating.close())
-                    .await
-                    .ok();
+                crate::rt::timeout(
+                    CLOSE_ON_DROP_TIMEOUT,
+                    floating.close(CloseReason::Requested),
+                )
+                .await
+                .ok();
             }
 
             pool.min_connections_maintenance(None).await;
@@ -275,14 +290,14 @@ impl<DB: Database> Floating<DB, Live<DB>> {
     async fn return_to_pool(mut self) -> bool {
         // Immediately close the connection.
         if self.guard.pool.is_closed() {
-            self.close().await;
+            self.close(CloseReason::PoolClosed).await;
             return false;
         }
 
         // If the connection is beyond max lifetime, close the connection and
         // immediately create a new connection
         if is_beyond_max_lifetime(&self.inner, &self.guard.pool.options) {
-            self.close().await;
+            self.close(CloseReason::MaxLifetime).await;
             return false;
         }
 
@@ -291,14 +306,14 @@ impl<DB: Database> Floating<DB, Live<DB>> {
             match (test)(&mut self.inner.raw, meta).await {
                 Ok(true) => (),
                 Ok(false) => {
-                    self.close().await;
+                    self.close(CloseReason::Rejected).await;
                     return false;
                 }
                 Err(error) => {
                     tracing::warn!(%error, "error from `after_release`");
                     // Connection is broken, don't try to gracefully close as
                     // something weird might happen.
-                    self.close_hard().await;
+                    self.close_hard(CloseReason::Rejected).await;
                     return false;
                 }
             }
@@ -317,8 +332,12 @@ impl<DB: Database> Floating<DB, Live<DB>> {
                 "error occurred while testing the connection on-release",
             );
 
+            self.guard.pool.events.send(PoolEvent::HealthCheckFailed {
+                error: error.to_string(),
+            });
+
             // Connection is broken, don't try to gracefully close.
-            self.close_hard().await;
+            self.close_hard(CloseReason::HealthCheckFailed).await;
             false
         } else {
             // if the connection is still viable, release it to the pool
@@ -327,15 +346,40 @@ impl<DB: Database> Floating<DB, Live<DB>> {
         }
     }
 
-    pub async fn close(self) {
+    pub async fn close(mut self, reason: CloseReason) {
+        if self.guard.pool.is_closing_hard() {
+            return self.close_hard(reason).await;
+        }
+
+        let meta = self.metadata();
+
+        if !self
+            .guard
+            .pool
+            .before_close(&mut self.inner.raw, meta)
+            .await
+        {
+            return self.close_hard(reason).await;
+        }
+
         // This isn't used anywhere that we care about the return value
         let _ = self.inner.raw.close().await;
 
+        self.guard
+            .pool
+            .events
+            .send(PoolEvent::ConnectionClosed { reason });
+
         // `guard` is dropped as intended
     }
 
-    pub async fn close_hard(self) {
+    pub async fn close_hard(self, reason: CloseReason) {
         let _ = self.inner.raw.close_hard().await;
+
+        self.guard
+            .pool
+            .events
+            .send(PoolEvent::ConnectionClosed { reason });
     }
 
     pub fn detach(self) -> DB::Connection {
@@ -380,16 +424,42 @@ impl<DB: Database> Floating<DB, Idle<DB>> {
         }
     }
 
-    pub async fn close(self) -> DecrementSizeGuard<DB> {
+    pub async fn close(mut self, reason: CloseReason) -> DecrementSizeGuard<DB> {
+        if self.guard.pool.is_closing_hard() {
+            return self.close_hard(reason).await;
+        }
+
+        let meta = self.metadata();
+
+        if !self
+            .guard
+            .pool
+            .before_close(&mut self.inner.live.raw, meta)
+            .await
+        {
+            return self.close_hard(reason).await;
+        }
+
         if let Err(error) = self.inner.live.raw.close().await {
             tracing::debug!(%error, "error occurred while closing the pool connection");
         }
+
+        self.guard
+            .pool
+            .events
+            .send(PoolEvent::ConnectionClosed { reason });
+
         self.guard
     }
 
-    pub async fn close_hard(self) -> DecrementSizeGuard<DB> {
+    pub async fn close_hard(self, reason: CloseReason) -> DecrementSizeGuard<DB> {
         let _ = self.inner.live.raw.close_hard().await;
 
+        self.guard
+            .pool
+            .events
+            .send(PoolEvent::ConnectionClosed { reason });
+
         self.guard
     }
 
@@ -0,0 +1,204 @@
+use std::collections::VecDeque;
+use std::fmt::{self, Debug, Formatter};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use futures_core::Stream;
+
+/// The number of events buffered for each [`PoolEvents`] stream before the oldest are dropped.
+pub const POOL_EVENTS_CAPACITY: usize = 1024;
+
+/// Something that happened to a [`Pool`][crate::pool::Pool] or one of its connections.
+///
+/// Received from [`Pool::events()`][crate::pool::Pool::events].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PoolEvent {
+    /// A new connection was opened, and [`after_connect`] and [`prepare_on_connect`] succeeded.
+    ///
+    /// [`after_connect`]: crate::pool::PoolOptions::after_connect
+    /// [`prepare_on_connect`]: crate::pool::PoolOptions::prepare_on_connect
+    ConnectionCreated,
+
+    /// A call to [`Pool::acquire()`][crate::pool::Pool::acquire] returned
+    /// [`Error::PoolTimedOut`][crate::error::Error::PoolTimedOut].
+    AcquireTimeout {
+        /// How long the caller waited before giving up.
+        wait: Duration,
+    },
+
+    /// A connection was closed by the pool.
+    ///
+    /// Connections given up with [`PoolConnection::detach()`] or
+    /// [`PoolConnection::leak()`] are not reported.
+    ///
+    /// [`PoolConnection::detach()`]: crate::pool::PoolConnection::detach
+    /// [`PoolConnection::leak()`]: crate::pool::PoolConnection::leak
+    ConnectionClosed {
+        /// Why the connection was closed.
+        reason: CloseReason,
+    },
+
+    /// Pinging a connection failed, either on acquire when
+    /// [`test_before_acquire`][crate::pool::PoolOptions::test_before_acquire] is set,
+    /// or when the connection was returned to the pool.
+    ///
+    /// It is followed by a [`ConnectionClosed`][Self::ConnectionClosed] event for the connection.
+    HealthCheckFailed {
+        /// The error returned by the ping.
+        error: String,
+    },
+
+    /// This stream fell behind by more than [`POOL_EVENTS_CAPACITY`] events, and the oldest of
+    /// them were dropped.
+    Lagged {
+        /// The number of events that were dropped.
+        missed: u64,
+    },
+}
+
+/// Why a [`Pool`][crate::pool::Pool] closed a connection.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CloseReason {
+    /// The pool was closed.
+    PoolClosed,
+    /// The connection was older than [`max_lifetime`][crate::pool::PoolOptions::max_lifetime].
+    MaxLifetime,
+    /// The connection was idle for longer than
+    /// [`idle_timeout`][crate::pool::PoolOptions::idle_timeout].
+    IdleTimeout,
+    /// Pinging the connection failed.
+    HealthCheckFailed,
+    /// [`before_acquire`] or [`after_release`] returned `false` or an error.
+    ///
+    /// [`before_acquire`]: crate::pool::PoolOptions::before_acquire
+    /// [`after_release`]: crate::pool::PoolOptions::after_release
+    Rejected,
+    /// [`PoolConnection::close()`] or [`PoolConnection::close_on_drop()`] was called.
+    ///
+    /// [`PoolConnection::close()`]: crate::pool::PoolConnection::close
+    /// [`PoolConnection::close_on_drop()`]: crate::pool::PoolConnection::close_on_drop
+    Requested,
+}
+
+/// A stream of the [`PoolEvent`]s of a pool, returned by
+/// [`Pool::events()`][crate::pool::Pool::events].
+///
+/// Only events that happen after the stream was created are received. The stream does not keep
+/// the pool open, and ends once the pool and all of its connections have been dropped.
+pub struct PoolEvents {
+    subscriber: Arc<Subscriber>,
+}
+
+#[derive(Default)]
+pub(super) struct EventSender {
+    subscribers: Mutex<Vec<Weak<Subscriber>>>,
+}
+
+#[derive(Default)]
+struct Subscriber {
+    state: Mutex<SubscriberState>,
+}
+
+#[derive(Default)]
+struct SubscriberState {
+    queue: VecDeque<PoolEvent>,
+    missed: u64,
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+impl EventSender {
+    pub(super) fn subscribe(&self) -> PoolEvents {
+        let subscriber = Arc::new(Subscriber::default());
+
+        lock(&self.subscribers).push(Arc::downgrade(&subscriber));
+
+        PoolEvents { subscriber }
+    }
+
+    pub(super) fn send(&self, event: PoolEvent) {
+        let mut subscribers = lock(&self.subscribers);
+
+        subscribers.retain(|subscriber| {
+            let Some(subscriber) = subscriber.upgrade() else {
+                return false;
+            };
+
+            let mut state = lock(&subscriber.state);
+
+            if state.queue.len() == POOL_EVENTS_CAPACITY {
+                state.queue.pop_front();
+                state.missed += 1;
+            }
+
+            state.queue.push_back(event.clone());
+
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+
+            true
+        });
+    }
+
+    /// End all streams once they have received the events sent so far.
+    pub(super) fn close(&self) {
+        for subscriber in lock(&self.subscribers).drain(..) {
+            if let Some(subscriber) = subscriber.upgrade() {
+                let mut state = lock(&subscriber.state);
+
+                state.closed = true;
+
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            }
+        }
+    }
+}
+
+impl Stream for PoolEvents {
+    type Item = PoolEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = lock(&self.subscriber.state);
+
+        // report dropped events before the event that displaced the last of them
+        if state.missed > 0 {
+            let missed = std::mem::take(&mut state.missed);
+            return Poll::Ready(Some(PoolEvent::Lagged { missed }));
+        }
+
+        if let Some(event) = state.queue.pop_front() {
+            return Poll::Ready(Some(event));
+        }
+
+        if state.closed {
+            return Poll::Ready(None);
+        }
+
+        state.waker = Some(cx.waker().clone());
+
+        Poll::Pending
+    }
+}
+
+impl Debug for PoolEvents {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let state = lock(&self.subscriber.state);
+
+        f.debug_struct("PoolEvents")
+            .field("pending", &state.queue.len())
+            .field("closed", &state.closed)
+            .finish()
+    }
+}
+
+// A panic while holding one of these locks cannot leave the state inconsistent.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
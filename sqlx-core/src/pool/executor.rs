@@ -6,7 +6,7 @@ use futures_util::TryStreamExt;
 use crate::database::Database;
 use crate::describe::Describe;
 use crate::error::Error;
-use crate::executor::{Execute, Executor};
+use crate::executor::{Execute, Executor, FetchOptions};
 use crate::pool::Pool;
 
 impl<'p, DB: Database> Executor<'p> for &'_ Pool<DB>
@@ -36,6 +36,28 @@ where
         })
     }
 
+    fn fetch_with<'e, 'q: 'e, E>(
+        self,
+        query: E,
+        options: FetchOptions,
+    ) -> BoxStream<'e, Result<DB::Row, Error>>
+    where
+        E: 'q + Execute<'q, Self::Database>,
+    {
+        let pool = self.clone();
+
+        Box::pin(try_stream! {
+            let mut conn = pool.acquire().await?;
+            let mut s = conn.fetch_with(query, options);
+
+            while let Some(v) = s.try_next().await? {
+                r#yield!(v);
+            }
+
+            Ok(())
+        })
+    }
+
     fn fetch_optional<'e, 'q: 'e, E>(
         self,
         query: E,
@@ -48,6 +70,29 @@ where
         Box::pin(async move { pool.acquire().await?.fetch_optional(query).await })
     }
 
+    #[doc(hidden)]
+    fn fetch_first_column<'e, 'q: 'e, E>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<DB::Row, DB::Value>, Error>>
+    where
+        E: 'q + Execute<'q, Self::Database>,
+        DB::Value: Send,
+    {
+        let pool = self.clone();
+
+        Box::pin(try_stream! {
+            let mut conn = pool.acquire().await?;
+            let mut s = conn.fetch_first_column(query);
+
+            while let Some(v) = s.try_next().await? {
+                r#yield!(v);
+            }
+
+            Ok(())
+        })
+    }
+
     fn prepare_with<'e, 'q: 'e>(
         self,
         sql: &'q str,
@@ -1,7 +1,10 @@
 use super::connection::{Floating, Idle, Live};
+use super::events::{CloseReason, EventSender, PoolEvent};
+use super::metrics::{MetricsRecorder, PoolMetric};
 use crate::connection::ConnectOptions;
 use crate::connection::Connection;
 use crate::database::Database;
+use crate::deadline::Deadline;
 use crate::error::Error;
 use crate::pool::{deadline_as_timeout, CloseEvent, Pool, PoolOptions};
 use crossbeam_queue::ArrayQueue;
@@ -30,7 +33,10 @@ pub(crate) struct PoolInner<DB: Database> {
     pub(super) size: AtomicU32,
     pub(super) num_idle: AtomicUsize,
     is_closed: AtomicBool,
+    closing_hard: AtomicBool,
     pub(super) on_closed: event_listener::Event,
+    pub(super) events: EventSender,
+    pub(super) metrics: MetricsRecorder,
     pub(super) options: PoolOptions<DB>,
     pub(crate) acquire_time_level: Option<Level>,
     pub(crate) acquire_slow_level: Option<Level>,
@@ -59,7 +65,10 @@ impl<DB: Database> PoolInner<DB> {
             size: AtomicU32::new(0),
             num_idle: AtomicUsize::new(0),
             is_closed: AtomicBool::new(false),
+            closing_hard: AtomicBool::new(false),
             on_closed: event_listener::Event::new(),
+            events: EventSender::default(),
+            metrics: MetricsRecorder::default(),
             acquire_time_level: private_level_filter_to_trace_level(options.acquire_time_level),
             acquire_slow_level: private_level_filter_to_trace_level(options.acquire_slow_level),
             options,
@@ -85,10 +94,45 @@ impl<DB: Database> PoolInner<DB> {
         self.num_idle.load(Ordering::Acquire)
     }
 
+    /// Count `metric`, and pass it to `options.on_metric` if set.
+    fn record(&self, metric: PoolMetric) {
+        self.metrics.record(metric);
+
+        if let Some(on_metric) = &self.options.on_metric {
+            on_metric(metric);
+        }
+    }
+
+    /// Call `options.before_close` if set, returning `false` if it failed.
+    pub(super) async fn before_close(
+        &self,
+        conn: &mut DB::Connection,
+        meta: PoolConnectionMetadata,
+    ) -> bool {
+        let Some(callback) = &self.options.before_close else {
+            return true;
+        };
+
+        match callback(conn, meta).await {
+            Ok(()) => true,
+            Err(error) => {
+                tracing::warn!(%error, "error returned from before_close");
+                false
+            }
+        }
+    }
+
     pub(super) fn is_closed(&self) -> bool {
         self.is_closed.load(Ordering::Acquire)
     }
 
+    /// Returns `true` once [`close_with_timeout()`][Self::close_with_timeout] gave up on closing
+    /// connections gracefully; the connections still checked out are then closed without
+    /// `options.before_close` or waiting on the server when they are returned.
+    pub(super) fn is_closing_hard(&self) -> bool {
+        self.closing_hard.load(Ordering::Acquire)
+    }
+
     fn mark_closed(&self) {
         self.is_closed.store(true, Ordering::Release);
         self.on_closed.notify(usize::MAX);
@@ -101,7 +145,10 @@ impl<DB: Database> PoolInner<DB> {
             for permits in 1..=self.options.max_connections {
                 // Close any currently idle connections in the pool.
                 while let Some(idle) = self.idle_conns.pop() {
-                    let _ = idle.live.float((*self).clone()).close().await;
+                    idle.live
+                        .float((*self).clone())
+                        .close(CloseReason::PoolClosed)
+                        .await;
                 }
 
                 if self.size() == 0 {
@@ -114,6 +161,25 @@ impl<DB: Database> PoolInner<DB> {
         }
     }
 
+    pub(super) async fn close_with_timeout(self: &Arc<Self>, timeout: Duration) -> u32 {
+        if crate::rt::timeout(timeout, self.close()).await.is_ok() {
+            return 0;
+        }
+
+        self.closing_hard.store(true, Ordering::Release);
+
+        // close what is left in the idle queue without waiting on the server
+        while let Some(idle) = self.idle_conns.pop() {
+            self.num_idle.fetch_sub(1, Ordering::AcqRel);
+            idle.live
+                .float((*self).clone())
+                .close_hard(CloseReason::PoolClosed)
+                .await;
+        }
+
+        self.size()
+    }
+
     pub(crate) fn close_event(&self) -> CloseEvent {
         CloseEvent {
             listener: (!self.is_closed()).then(|| self.on_closed.listen()),
@@ -245,10 +311,16 @@ impl<DB: Database> PoolInner<DB> {
         }
 
         let acquire_started_at = Instant::now();
-        let deadline = acquire_started_at + self.options.acquire_timeout;
+
+        // give up at the caller's deadline if it comes first
+        let acquire_timeout = match Deadline::remaining() {
+            Some(remaining) => cmp::min(remaining, self.options.acquire_timeout),
+            None => self.options.acquire_timeout,
+        };
+        let deadline = acquire_started_at + acquire_timeout;
 
         let acquired = crate::rt::timeout(
-            self.options.acquire_timeout,
+            acquire_timeout,
             async {
                 loop {
                     // Handles the close-event internally
@@ -285,15 +357,29 @@ impl<DB: Database> PoolInner<DB> {
                     };
 
                     // Attempt to connect...
-                    return self.connect(deadline, guard).await;
+                    return self.connect(deadline, guard, true).await;
                 }
             }
         )
             .await
-            .map_err(|_| Error::PoolTimedOut)??;
+            .map_err(|_| Error::PoolTimedOut)
+            .and_then(|res| res)
+            .map_err(|e| {
+                if matches!(e, Error::PoolTimedOut) {
+                    let wait = acquire_started_at.elapsed();
+                    self.record(PoolMetric::AcquireTimeout { wait });
+                    self.events.send(PoolEvent::AcquireTimeout { wait });
+                }
+
+                e
+            })?;
 
         let acquired_after = acquire_started_at.elapsed();
 
+        self.record(PoolMetric::Acquired {
+            wait: acquired_after,
+        });
+
         let acquire_slow_level = self
             .acquire_slow_level
             .filter(|_| acquired_after > self.options.acquire_slow_threshold);
@@ -318,20 +404,25 @@ impl<DB: Database> PoolInner<DB> {
         Ok(acquired)
     }
 
+    /// Open a new connection, retrying as set by the [`RetryPolicy`][super::RetryPolicy];
+    /// `acquiring` is whether a caller of `acquire()` is waiting for it.
     pub(super) async fn connect(
         self: &Arc<Self>,
         deadline: Instant,
         guard: DecrementSizeGuard<DB>,
+        acquiring: bool,
     ) -> Result<Floating<DB, Live<DB>>, Error> {
         if self.is_closed() {
             return Err(Error::PoolClosed);
         }
 
-        let mut backoff = Duration::from_millis(10);
+        let policy = &self.options.retry_policy;
         let max_backoff = deadline_as_timeout(deadline)? / 5;
+        let mut attempts = 0;
 
         loop {
             let timeout = deadline_as_timeout(deadline)?;
+            attempts += 1;
 
             // clone the connect options arc so it can be used without holding the RwLockReadGuard
             // across an async await point
@@ -343,7 +434,7 @@ impl<DB: Database> PoolInner<DB> {
 
             // result here is `Result<Result<C, Error>, TimeoutError>`
             // if this block does not return, sleep for the backoff timeout and try again
-            match crate::rt::timeout(timeout, connect_options.connect()).await {
+            let error = match crate::rt::timeout(timeout, connect_options.connect()).await {
                 // successfully established connection
                 Ok(Ok(mut raw)) => {
                     // See comment on `PoolOptions::after_connect`
@@ -352,43 +443,74 @@ impl<DB: Database> PoolInner<DB> {
                         idle_for: Duration::ZERO,
                     };
 
-                    let res = if let Some(callback) = &self.options.after_connect {
-                        callback(&mut raw, meta).await
-                    } else {
-                        Ok(())
+                    let res = match self.options.statement_cache_capacity {
+                        Some((capacity, set_capacity)) => set_capacity(&mut raw, capacity)
+                            .await
+                            .map_err(|error| (error, "statement_cache_capacity")),
+                        None => Ok(()),
+                    };
+
+                    let res = match (res, &self.options.after_connect) {
+                        (Ok(()), Some(callback)) => callback(&mut raw, meta)
+                            .await
+                            .map_err(|error| (error, "after_connect")),
+                        (res, _) => res,
+                    };
+
+                    // Prepared last, as `after_connect` may change how names are resolved.
+                    let res = match (res, &self.options.prepare_on_connect) {
+                        (Ok(()), Some(prepare)) => prepare(&mut raw)
+                            .await
+                            .map_err(|error| (error, "prepare_on_connect")),
+                        (res, _) => res,
                     };
 
                     match res {
-                        Ok(()) => return Ok(Floating::new_live(raw, guard)),
-                        Err(error) => {
-                            tracing::error!(%error, "error returned from after_connect");
+                        Ok(()) => {
+                            self.events.send(PoolEvent::ConnectionCreated);
+                            return Ok(Floating::new_live(raw, guard));
+                        }
+                        Err((error, callback)) => {
+                            tracing::error!(%error, "error returned from {callback}");
+                            self.record(PoolMetric::ConnectionError);
                             // The connection is broken, don't try to close nicely.
                             let _ = raw.close_hard().await;
 
                             // Fall through to the backoff.
+                            error
                         }
                     }
                 }
 
-                // an IO error while connecting is assumed to be the system starting up
-                Ok(Err(Error::Io(e))) if e.kind() == std::io::ErrorKind::ConnectionRefused => (),
-
-                // We got a transient database error, retry.
-                Ok(Err(Error::Database(error))) if error.is_transient_in_connect_phase() => (),
+                // by default, a refused connection is assumed to be the system starting up
+                // and transient database errors are retried
+                Ok(Err(e)) if policy.should_retry(&e) => {
+                    self.record(PoolMetric::ConnectionError);
+                    e
+                }
 
                 // Any other error while connection should immediately
                 // terminate and bubble the error up
-                Ok(Err(e)) => return Err(e),
+                Ok(Err(e)) => {
+                    self.record(PoolMetric::ConnectionError);
+                    return Err(e);
+                }
 
                 // timed out
                 Err(_) => return Err(Error::PoolTimedOut),
+            };
+
+            if !policy.should_attempt(attempts, acquiring) {
+                return Err(error);
             }
 
-            // If the connection is refused, wait in exponentially
-            // increasing steps for the server to come up,
-            // capped by a factor of the remaining time until the deadline
-            crate::rt::sleep(backoff).await;
-            backoff = cmp::min(backoff * 2, max_backoff);
+            tracing::debug!(%error, attempts, "failed to open a connection; retrying");
+
+            // By default, wait in exponentially increasing steps for the server to come up,
+            // capped by a factor of the remaining time until the deadline,
+            // and never sleeping past the deadline itself
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            crate::rt::sleep(cmp::min(policy.delay(attempts, max_backoff), remaining)).await;
         }
     }
 
@@ -410,7 +532,7 @@ impl<DB: Database> PoolInner<DB> {
 
             // We skip `after_release` since the connection was never provided to user code
             // besides `after_connect`, if they set it.
-            self.release(self.connect(deadline, guard).await?);
+            self.release(self.connect(deadline, guard, false).await?);
         }
 
         Ok(())
@@ -437,6 +559,7 @@ impl<DB: Database> PoolInner<DB> {
 impl<DB: Database> Drop for PoolInner<DB> {
     fn drop(&mut self) {
         self.mark_closed();
+        self.events.close();
 
         if let Some(parent) = &self.options.parent_pool {
             // Release the stolen permits.
@@ -473,8 +596,11 @@ async fn check_idle_conn<DB: Database>(
             // either way we're fine to just discard the connection
             // the error itself here isn't necessarily unexpected so WARN is too strong
             tracing::info!(%error, "ping on idle connection returned error");
+            conn.guard.pool.events.send(PoolEvent::HealthCheckFailed {
+                error: error.to_string(),
+            });
             // connection is broken so don't try to close nicely
-            return Err(conn.close_hard().await);
+            return Err(conn.close_hard(CloseReason::HealthCheckFailed).await);
         }
     }
 
@@ -483,13 +609,13 @@ async fn check_idle_conn<DB: Database>(
         match test(&mut conn.live.raw, meta).await {
             Ok(false) => {
                 // connection was rejected by user-defined hook, close nicely
-                return Err(conn.close().await);
+                return Err(conn.close(CloseReason::Rejected).await);
             }
 
             Err(error) => {
                 tracing::warn!(%error, "error from `before_acquire`");
                 // connection is broken so don't try to close nicely
-                return Err(conn.close_hard().await);
+                return Err(conn.close_hard(CloseReason::Rejected).await);
             }
 
             Ok(true) => {}
@@ -543,10 +669,16 @@ fn spawn_maintenance_tasks<DB: Database>(pool: &Arc<PoolInner<DB>>) {
                     // the queue in the meantime - that's fine, there is no harm in checking more
                     for _ in 0..pool.num_idle() {
                         if let Some(conn) = pool.try_acquire() {
-                            if is_beyond_idle_timeout(&conn, &pool.options)
-                                || is_beyond_max_lifetime(&conn, &pool.options)
-                            {
-                                let _ = conn.close().await;
+                            let reason = if is_beyond_idle_timeout(&conn, &pool.options) {
+                                Some(CloseReason::IdleTimeout)
+                            } else if is_beyond_max_lifetime(&conn, &pool.options) {
+                                Some(CloseReason::MaxLifetime)
+                            } else {
+                                None
+                            };
+
+                            if let Some(reason) = reason {
+                                let _ = conn.close(reason).await;
                                 pool.min_connections_maintenance(Some(next_run)).await;
                             } else {
                                 pool.release(conn.into_live());
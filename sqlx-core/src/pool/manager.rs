@@ -0,0 +1,131 @@
+//! Building blocks for managing SQLx connections with a third-party pool.
+//!
+//! See [`ConnectionManager`] for details.
+
+use std::fmt::{self, Debug, Formatter};
+
+use crate::connection::{ConnectOptions, Connection};
+use crate::database::Database;
+use crate::error::Error;
+
+/// Opens and recycles connections on behalf of a connection pool other than [`Pool`][super::Pool].
+///
+/// Teams that have standardized on a generic pool such as `deadpool` or `bb8` still need
+/// something to create connections and check them before reuse. `ConnectionManager` performs
+/// those steps the same way [`Pool`][super::Pool] does, so the glue for any such pool is reduced
+/// to forwarding a few calls:
+///
+/// * [`connect()`][Self::connect] opens a new connection with the configured options.
+/// * [`recycle()`][Self::recycle] pings a connection being returned to (or taken from) the pool,
+///   which also flushes anything left pending on it, like the rollback of a dropped
+///   [`Transaction`][crate::transaction::Transaction].
+///
+/// A connection for which `recycle()` returned an error should be discarded.
+///
+/// Connections opened this way work with the query macros and every other API accepting a
+/// `&mut DB::Connection`.
+///
+/// With the `deadpool` or `bb8` features enabled, `ConnectionManager` implements the manager
+/// trait of the respective crate, so it can be used to build such a pool directly:
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "deadpool")]
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use sqlx::pool::ConnectionManager;
+/// use sqlx::postgres::Postgres;
+///
+/// type PgPool = deadpool::managed::Pool<ConnectionManager<Postgres>>;
+///
+/// let manager = ConnectionManager::from_url("postgres://localhost/app")?;
+/// let pool = PgPool::builder(manager).max_size(8).build()?;
+///
+/// let mut conn = pool.get().await?;
+/// let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut *conn).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ConnectionManager<DB: Database> {
+    options: <DB::Connection as Connection>::Options,
+}
+
+impl<DB: Database> ConnectionManager<DB> {
+    /// Create a manager which opens connections with the given options.
+    pub fn new(options: <DB::Connection as Connection>::Options) -> Self {
+        Self { options }
+    }
+
+    /// Create a manager which opens connections to the given URL.
+    pub fn from_url(url: &str) -> Result<Self, Error> {
+        Ok(Self::new(url.parse()?))
+    }
+
+    /// Get the options used to open new connections.
+    pub fn connect_options(&self) -> &<DB::Connection as Connection>::Options {
+        &self.options
+    }
+
+    /// Open a new connection.
+    pub async fn connect(&self) -> Result<DB::Connection, Error> {
+        self.options.connect().await
+    }
+
+    /// Check that a connection is still usable, flushing any pending work on it.
+    ///
+    /// An error means the connection is broken and should be discarded.
+    pub async fn recycle(&self, conn: &mut DB::Connection) -> Result<(), Error> {
+        conn.ping().await
+    }
+}
+
+impl<DB: Database> Clone for ConnectionManager<DB> {
+    fn clone(&self) -> Self {
+        Self {
+            options: self.options.clone(),
+        }
+    }
+}
+
+impl<DB: Database> Debug for ConnectionManager<DB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectionManager")
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+#[cfg(feature = "deadpool")]
+impl<DB: Database> deadpool::managed::Manager for ConnectionManager<DB> {
+    type Type = DB::Connection;
+    type Error = Error;
+
+    async fn create(&self) -> Result<DB::Connection, Error> {
+        self.connect().await
+    }
+
+    async fn recycle(
+        &self,
+        conn: &mut DB::Connection,
+        _metrics: &deadpool::managed::Metrics,
+    ) -> deadpool::managed::RecycleResult<Error> {
+        Ok(ConnectionManager::recycle(self, conn).await?)
+    }
+}
+
+#[cfg(feature = "bb8")]
+impl<DB: Database> bb8::ManageConnection for ConnectionManager<DB> {
+    type Connection = DB::Connection;
+    type Error = Error;
+
+    async fn connect(&self) -> Result<DB::Connection, Error> {
+        ConnectionManager::connect(self).await
+    }
+
+    async fn is_valid(&self, conn: &mut DB::Connection) -> Result<(), Error> {
+        self.recycle(conn).await
+    }
+
+    fn has_broken(&self, _conn: &mut DB::Connection) -> bool {
+        // Whether the connection is still usable can only be known by pinging it.
+        false
+    }
+}
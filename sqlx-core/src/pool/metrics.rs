@@ -0,0 +1,202 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// The upper bounds of the buckets of [`AcquireWaitHistogram`], inclusive.
+pub const ACQUIRE_WAIT_BUCKETS: [Duration; 12] = [
+    Duration::from_millis(1),
+    Duration::from_millis(5),
+    Duration::from_millis(10),
+    Duration::from_millis(25),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_millis(2500),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+];
+
+const NUM_BUCKETS: usize = ACQUIRE_WAIT_BUCKETS.len() + 1;
+
+/// A snapshot of the metrics of a [`Pool`][crate::pool::Pool], returned by
+/// [`Pool::metrics()`][crate::pool::Pool::metrics].
+///
+/// The counters start at zero when the pool is created and only go up. Acquires are counted for
+/// [`Pool::acquire()`][crate::pool::Pool::acquire] and everything that goes through it, such as
+/// executing a query on `&Pool` or beginning a transaction, but not for
+/// [`Pool::try_acquire()`][crate::pool::Pool::try_acquire].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PoolMetrics {
+    /// The number of open connections, idle or in use.
+    pub size: u32,
+    /// The number of connections in the idle queue.
+    pub idle: usize,
+    /// The number of connections in use, checked out of the pool.
+    pub active: u32,
+    /// The number of connections returned by an acquire.
+    pub acquires: u64,
+    /// The number of acquires which returned [`Error::PoolTimedOut`][crate::error::Error::PoolTimedOut].
+    pub acquire_timeouts: u64,
+    /// The number of failed attempts to open a connection, including those that were retried and
+    /// those where [`after_connect`] or [`prepare_on_connect`] returned an error.
+    ///
+    /// [`after_connect`]: crate::pool::PoolOptions::after_connect
+    /// [`prepare_on_connect`]: crate::pool::PoolOptions::prepare_on_connect
+    pub connection_errors: u64,
+    /// How long the acquires counted in [`acquires`][Self::acquires] waited for a connection.
+    pub acquire_wait: AcquireWaitHistogram,
+}
+
+/// A histogram of the time acquires waited for a connection, in the buckets of
+/// [`ACQUIRE_WAIT_BUCKETS`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcquireWaitHistogram {
+    counts: [u64; NUM_BUCKETS],
+    sum: Duration,
+}
+
+impl AcquireWaitHistogram {
+    /// The upper bound of each bucket and the number of acquires that fell into it.
+    ///
+    /// The counts are not cumulative. The last bucket, with no upper bound, counts the acquires
+    /// that waited longer than the last of [`ACQUIRE_WAIT_BUCKETS`].
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<Duration>, u64)> + '_ {
+        ACQUIRE_WAIT_BUCKETS
+            .iter()
+            .copied()
+            .map(Some)
+            .chain([None])
+            .zip(self.counts.iter().copied())
+    }
+
+    /// The number of acquires in all buckets.
+    pub fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// The total time waited by the acquires in all buckets.
+    pub fn sum(&self) -> Duration {
+        self.sum
+    }
+}
+
+/// A measurement passed to [`PoolOptions::on_metric()`][crate::pool::PoolOptions::on_metric]
+/// as it is taken, to export it as it happens.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PoolMetric {
+    /// An acquire returned a connection.
+    Acquired {
+        /// How long the caller waited for the connection.
+        wait: Duration,
+    },
+
+    /// An acquire returned [`Error::PoolTimedOut`][crate::error::Error::PoolTimedOut].
+    AcquireTimeout {
+        /// How long the caller waited before giving up.
+        wait: Duration,
+    },
+
+    /// An attempt to open a connection failed.
+    ConnectionError,
+}
+
+#[derive(Default)]
+pub(super) struct MetricsRecorder {
+    acquires: AtomicU64,
+    acquire_timeouts: AtomicU64,
+    connection_errors: AtomicU64,
+    acquire_wait_counts: [AtomicU64; NUM_BUCKETS],
+    acquire_wait_sum_nanos: AtomicU64,
+}
+
+impl MetricsRecorder {
+    pub(super) fn record(&self, metric: PoolMetric) {
+        // the counters are independent of each other and of any other state
+        match metric {
+            PoolMetric::Acquired { wait } => {
+                let bucket = ACQUIRE_WAIT_BUCKETS.partition_point(|bound| *bound < wait);
+                let nanos = u64::try_from(wait.as_nanos()).unwrap_or(u64::MAX);
+
+                self.acquire_wait_counts[bucket].fetch_add(1, Ordering::Relaxed);
+                self.acquire_wait_sum_nanos
+                    .fetch_add(nanos, Ordering::Relaxed);
+                self.acquires.fetch_add(1, Ordering::Relaxed);
+            }
+            PoolMetric::AcquireTimeout { .. } => {
+                self.acquire_timeouts.fetch_add(1, Ordering::Relaxed);
+            }
+            PoolMetric::ConnectionError => {
+                self.connection_errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub(super) fn snapshot(&self, size: u32, idle: usize) -> PoolMetrics {
+        PoolMetrics {
+            size,
+            idle,
+            active: size.saturating_sub(u32::try_from(idle).unwrap_or(u32::MAX)),
+            acquires: self.acquires.load(Ordering::Relaxed),
+            acquire_timeouts: self.acquire_timeouts.load(Ordering::Relaxed),
+            connection_errors: self.connection_errors.load(Ordering::Relaxed),
+            acquire_wait: AcquireWaitHistogram {
+                counts: std::array::from_fn(|i| {
+                    self.acquire_wait_counts[i].load(Ordering::Relaxed)
+                }),
+                sum: Duration::from_nanos(self.acquire_wait_sum_nanos.load(Ordering::Relaxed)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_records_acquire_waits_in_buckets() {
+        let recorder = MetricsRecorder::default();
+
+        for wait in [
+            Duration::ZERO,
+            Duration::from_millis(1),
+            Duration::from_micros(1001),
+            Duration::from_secs(60),
+        ] {
+            recorder.record(PoolMetric::Acquired { wait });
+        }
+        recorder.record(PoolMetric::AcquireTimeout {
+            wait: Duration::from_secs(30),
+        });
+        recorder.record(PoolMetric::ConnectionError);
+
+        let metrics = recorder.snapshot(3, 1);
+
+        assert_eq!(metrics.active, 2);
+        assert_eq!(metrics.acquires, 4);
+        assert_eq!(metrics.acquire_timeouts, 1);
+        assert_eq!(metrics.connection_errors, 1);
+
+        let buckets: Vec<_> = metrics
+            .acquire_wait
+            .buckets()
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        assert_eq!(
+            buckets,
+            [
+                (Some(Duration::from_millis(1)), 2),
+                (Some(Duration::from_millis(5)), 1),
+                (None, 1),
+            ]
+        );
+        assert_eq!(metrics.acquire_wait.count(), 4);
+        assert_eq!(
+            metrics.acquire_wait.sum(),
+            Duration::from_micros(60_002_001)
+        );
+    }
+}
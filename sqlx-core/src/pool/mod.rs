@@ -53,6 +53,11 @@
 //! A connection or transaction may also be manually acquired with
 //! [`Pool::acquire`] or
 //! [`Pool::begin`].
+//!
+//! # Using another connection pool
+//!
+//! Applications already built around a generic pool can use SQLx connections with it through
+//! [`ConnectionManager`].
 
 use std::borrow::Cow;
 use std::fmt;
@@ -72,10 +77,14 @@ use crate::error::Error;
 use crate::transaction::Transaction;
 
 pub use self::connection::PoolConnection;
+pub use self::events::{CloseReason, PoolEvent, PoolEvents, POOL_EVENTS_CAPACITY};
 use self::inner::PoolInner;
+pub use self::manager::ConnectionManager;
 #[doc(hidden)]
 pub use self::maybe::MaybePoolConnection;
+pub use self::metrics::{AcquireWaitHistogram, PoolMetric, PoolMetrics, ACQUIRE_WAIT_BUCKETS};
 pub use self::options::{PoolConnectionMetadata, PoolOptions};
+pub use self::retry::{Backoff, RetryPolicy};
 
 #[macro_use]
 mod executor;
@@ -84,8 +93,12 @@ mod executor;
 pub mod maybe;
 
 mod connection;
+mod events;
 mod inner;
+mod manager;
+mod metrics;
 mod options;
+mod retry;
 
 /// An asynchronous pool of SQLx database connections.
 ///
@@ -442,6 +455,34 @@ impl<DB: Database> Pool<DB> {
         self.0.close()
     }
 
+    /// Shut down the connection pool as [`.close()`][Pool::close] does, giving up on closing
+    /// connections gracefully after `timeout`.
+    ///
+    /// Idle connections are closed first, then checked-out connections as they are returned;
+    /// [`before_close`][PoolOptions::before_close] is called for each. Once `timeout` has
+    /// elapsed, this returns without waiting any longer: the remaining idle connections are
+    /// closed without waiting on the server, and the connections still checked out are marked
+    /// to be closed the same way, skipping `before_close`, as soon as they are returned,
+    /// dropped or [closed][PoolConnection::close].
+    ///
+    /// Returns the number of connections still checked out at that point, or `0` if all
+    /// connections were closed in time.
+    ///
+    /// ```no_run
+    /// # async fn f(pool: sqlx::PgPool) {
+    /// use std::time::Duration;
+    ///
+    /// let remaining = pool.close_with_timeout(Duration::from_secs(10)).await;
+    ///
+    /// if remaining > 0 {
+    ///     eprintln!("{remaining} connections were still in use at shutdown");
+    /// }
+    /// # }
+    /// ```
+    pub fn close_with_timeout(&self, timeout: Duration) -> impl Future<Output = u32> + '_ {
+        self.0.close_with_timeout(timeout)
+    }
+
     /// Returns `true` if [`.close()`][Pool::close] has been called on the pool, `false` otherwise.
     pub fn is_closed(&self) -> bool {
         self.0.is_closed()
@@ -531,6 +572,56 @@ impl<DB: Database> Pool<DB> {
         self.0.close_event()
     }
 
+    /// Get a stream of the events that happen to this pool and its connections from now on.
+    ///
+    /// Each call returns an independent stream which receives every event. Events are buffered
+    /// until the stream is polled; a stream that falls too far behind receives
+    /// [`PoolEvent::Lagged`] in place of the events it missed.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: sqlx::PgPool) {
+    /// use futures_util::StreamExt;
+    /// use sqlx::pool::PoolEvent;
+    ///
+    /// let mut events = pool.events();
+    ///
+    /// tokio::spawn(async move {
+    ///     while let Some(event) = events.next().await {
+    ///         if let PoolEvent::AcquireTimeout { wait } = event {
+    ///             eprintln!("timed out acquiring a connection after {wait:?}");
+    ///         }
+    ///     }
+    /// });
+    /// # }
+    /// ```
+    pub fn events(&self) -> PoolEvents {
+        self.0.events.subscribe()
+    }
+
+    /// Get a snapshot of the metrics of this pool: its connections, and the acquires, acquire
+    /// timeouts and connection errors counted since it was created.
+    ///
+    /// To export each measurement as it is taken instead, see
+    /// [`PoolOptions::on_metric()`].
+    ///
+    /// ```rust,no_run
+    /// # fn example(pool: sqlx::PgPool) {
+    /// let metrics = pool.metrics();
+    ///
+    /// println!(
+    ///     "{} of {} connections in use, {} acquires timed out",
+    ///     metrics.active, metrics.size, metrics.acquire_timeouts
+    /// );
+    ///
+    /// for (bound, count) in metrics.acquire_wait.buckets() {
+    ///     println!("waited up to {bound:?}: {count}");
+    /// }
+    /// # }
+    /// ```
+    pub fn metrics(&self) -> PoolMetrics {
+        self.0.metrics.snapshot(self.0.size(), self.0.num_idle())
+    }
+
     /// Returns the number of connections currently active. This includes idle connections.
     pub fn size(&self) -> u32 {
         self.0.size()
@@ -1,7 +1,10 @@
 use crate::connection::Connection;
-use crate::database::Database;
+use crate::database::{Database, HasStatementCache};
 use crate::error::Error;
+use crate::executor::Executor;
 use crate::pool::inner::PoolInner;
+use crate::pool::metrics::PoolMetric;
+use crate::pool::retry::RetryPolicy;
 use crate::pool::Pool;
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
@@ -52,6 +55,12 @@ pub struct PoolOptions<DB: Database> {
                 + Sync,
         >,
     >,
+    pub(crate) statement_cache_capacity: Option<(usize, SetStatementCacheCapacity<DB>)>,
+    pub(crate) prepare_on_connect: Option<
+        Arc<
+            dyn Fn(&mut DB::Connection) -> BoxFuture<'_, Result<(), Error>> + 'static + Send + Sync,
+        >,
+    >,
     pub(crate) before_acquire: Option<
         Arc<
             dyn Fn(
@@ -74,11 +83,21 @@ pub struct PoolOptions<DB: Database> {
                 + Sync,
         >,
     >,
+    pub(crate) before_close: Option<
+        Arc<
+            dyn Fn(&mut DB::Connection, PoolConnectionMetadata) -> BoxFuture<'_, Result<(), Error>>
+                + 'static
+                + Send
+                + Sync,
+        >,
+    >,
+    pub(crate) on_metric: Option<Arc<dyn Fn(PoolMetric) + 'static + Send + Sync>>,
     pub(crate) max_connections: u32,
     pub(crate) acquire_time_level: LevelFilter,
     pub(crate) acquire_slow_level: LevelFilter,
     pub(crate) acquire_slow_threshold: Duration,
     pub(crate) acquire_timeout: Duration,
+    pub(crate) retry_policy: RetryPolicy,
     pub(crate) min_connections: u32,
     pub(crate) max_lifetime: Option<Duration>,
     pub(crate) idle_timeout: Option<Duration>,
@@ -95,13 +114,18 @@ impl<DB: Database> Clone for PoolOptions<DB> {
         PoolOptions {
             test_before_acquire: self.test_before_acquire,
             after_connect: self.after_connect.clone(),
+            statement_cache_capacity: self.statement_cache_capacity,
+            prepare_on_connect: self.prepare_on_connect.clone(),
             before_acquire: self.before_acquire.clone(),
             after_release: self.after_release.clone(),
+            before_close: self.before_close.clone(),
+            on_metric: self.on_metric.clone(),
             max_connections: self.max_connections,
             acquire_time_level: self.acquire_time_level,
             acquire_slow_threshold: self.acquire_slow_threshold,
             acquire_slow_level: self.acquire_slow_level,
             acquire_timeout: self.acquire_timeout,
+            retry_policy: self.retry_policy.clone(),
             min_connections: self.min_connections,
             max_lifetime: self.max_lifetime,
             idle_timeout: self.idle_timeout,
@@ -111,6 +135,9 @@ impl<DB: Database> Clone for PoolOptions<DB> {
     }
 }
 
+type SetStatementCacheCapacity<DB> =
+    fn(&mut <DB as Database>::Connection, usize) -> BoxFuture<'_, Result<(), Error>>;
+
 /// Metadata for the connection being processed by a [`PoolOptions`] callback.
 #[derive(Debug)] // Don't want to commit to any other trait impls yet.
 #[non_exhaustive] // So we can safely add fields in the future.
@@ -144,8 +171,12 @@ impl<DB: Database> PoolOptions<DB> {
         Self {
             // User-specifiable routines
             after_connect: None,
+            statement_cache_capacity: None,
+            prepare_on_connect: None,
             before_acquire: None,
             after_release: None,
+            before_close: None,
+            on_metric: None,
             test_before_acquire: true,
             // A production application will want to set a higher limit than this.
             max_connections: 10,
@@ -158,6 +189,7 @@ impl<DB: Database> PoolOptions<DB> {
             // to not flag typical time to add a new connection to a pool.
             acquire_slow_threshold: Duration::from_secs(2),
             acquire_timeout: Duration::from_secs(30),
+            retry_policy: RetryPolicy::new(),
             idle_timeout: Some(Duration::from_secs(10 * 60)),
             max_lifetime: Some(Duration::from_secs(30 * 60)),
             fair: true,
@@ -268,6 +300,21 @@ impl<DB: Database> PoolOptions<DB> {
         self.acquire_timeout
     }
 
+    /// Set how failed attempts to open a connection are retried.
+    ///
+    /// By default, refused connections and transient database errors are retried with
+    /// exponential backoff until the [acquire timeout][Self::acquire_timeout] runs out.
+    /// See [`RetryPolicy`] for the options.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Get how failed attempts to open a connection are retried.
+    pub fn get_retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
     /// Set the maximum lifetime of individual connections.
     ///
     /// Any connection with a lifetime greater than this will be closed.
@@ -390,6 +437,77 @@ impl<DB: Database> PoolOptions<DB> {
         self
     }
 
+    /// Set the capacity of the prepared statement cache of each new connection, overriding the
+    /// `statement_cache_capacity` of the database's `ConnectOptions` for this pool only.
+    ///
+    /// This is applied right after connecting, before [`after_connect`][Self::after_connect];
+    /// see [`Connection::set_statement_cache_capacity()`]. A capacity of `0` disables the cache.
+    pub fn statement_cache_capacity(mut self, capacity: usize) -> Self
+    where
+        DB: HasStatementCache,
+    {
+        self.statement_cache_capacity = Some((capacity, |conn, capacity| {
+            conn.set_statement_cache_capacity(capacity)
+        }));
+        self
+    }
+
+    /// Get the capacity of the statement cache of new connections, if set.
+    pub fn get_statement_cache_capacity(&self) -> Option<usize> {
+        self.statement_cache_capacity.map(|(capacity, _)| capacity)
+    }
+
+    /// Prepare the given statements on each new connection, after
+    /// [`after_connect`][Self::after_connect] if set.
+    ///
+    /// The statements are added to the connection's statement cache, so the first query using
+    /// one of them on a fresh connection doesn't need to prepare it; this avoids latency spikes
+    /// for the hottest queries when the pool is (re)filled, e.g. after a deploy. Queries built
+    /// with `query!()` or `query()` are only served from the cache if they use the exact same
+    /// SQL string.
+    ///
+    /// If more statements are given than fit in the connection's statement cache (see
+    /// `statement_cache_capacity` of the database's `ConnectOptions`), the first ones are
+    /// evicted again.
+    ///
+    /// If preparing a statement fails, the error is logged and the connection is closed, as with
+    /// an error from `after_connect`.
+    ///
+    /// ```no_run
+    /// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+    /// use sqlx::postgres::PgPoolOptions;
+    ///
+    /// let pool = PgPoolOptions::new()
+    ///     .prepare_on_connect([
+    ///         "SELECT id, name FROM users WHERE id = $1",
+    ///         "UPDATE users SET last_seen = now() WHERE id = $1",
+    ///     ])
+    ///     .connect("postgres:// …").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn prepare_on_connect<I>(mut self, statements: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        let statements: Arc<[String]> = statements.into_iter().map(Into::into).collect();
+
+        self.prepare_on_connect = Some(Arc::new(move |conn| {
+            let statements = Arc::clone(&statements);
+
+            Box::pin(async move {
+                for sql in statements.iter() {
+                    conn.prepare(sql).await?;
+                }
+
+                Ok(())
+            })
+        }));
+        self
+    }
+
     /// Perform an asynchronous action on a previously idle connection before giving it out.
     ///
     /// Alongside the connection, the closure gets [`PoolConnectionMetadata`] which contains
@@ -500,6 +618,84 @@ impl<DB: Database> PoolOptions<DB> {
         self
     }
 
+    /// Perform an asynchronous action on a connection before the pool closes it gracefully, for
+    /// any [`CloseReason`][super::CloseReason], such as the pool being closed or the connection
+    /// reaching its [`max_lifetime`][Self::max_lifetime].
+    ///
+    /// This can be used to clean up state on the server which outlives the connection, such as
+    /// statements prepared through a connection multiplexer, or to log the connection.
+    ///
+    /// If the callback returns an error, it is logged and the connection is closed without
+    /// further communication with the server. It is not called for connections closed because
+    /// they are broken, such as when a [health check][Self::test_before_acquire] fails.
+    ///
+    /// ```no_run
+    /// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+    /// use sqlx::Executor;
+    /// use sqlx::postgres::PgPoolOptions;
+    ///
+    /// let pool = PgPoolOptions::new()
+    ///     .before_close(|conn, _meta| Box::pin(async move {
+    ///         conn.execute("DEALLOCATE ALL").await?;
+    ///         Ok(())
+    ///     }))
+    ///     .connect("postgres:// …").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// For a discussion on why `Box::pin()` is required, see [the type-level docs][Self].
+    pub fn before_close<F>(mut self, callback: F) -> Self
+    where
+        for<'c> F: Fn(&'c mut DB::Connection, PoolConnectionMetadata) -> BoxFuture<'c, Result<(), Error>>
+            + 'static
+            + Send
+            + Sync,
+    {
+        self.before_close = Some(Arc::new(callback));
+        self
+    }
+
+    /// Call `callback` with each [`PoolMetric`] as it is measured, such as the time an acquire
+    /// waited for a connection, to export it without wrapping every call into the pool.
+    ///
+    /// The callback is called synchronously by the task doing the measured operation, so it
+    /// should return quickly, e.g. by updating a counter or a histogram. The number of idle and
+    /// active connections are better read from [`Pool::metrics()`] when the metrics are
+    /// collected.
+    ///
+    /// ```no_run
+    /// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::sync::atomic::{AtomicU64, Ordering};
+    /// use std::sync::Arc;
+    /// use sqlx::pool::PoolMetric;
+    /// use sqlx::postgres::PgPoolOptions;
+    ///
+    /// let slow_acquires = Arc::new(AtomicU64::new(0));
+    ///
+    /// let pool = PgPoolOptions::new()
+    ///     .on_metric({
+    ///         let slow_acquires = slow_acquires.clone();
+    ///         move |metric| {
+    ///             if let PoolMetric::Acquired { wait } = metric {
+    ///                 if wait.as_millis() > 100 {
+    ///                     slow_acquires.fetch_add(1, Ordering::Relaxed);
+    ///                 }
+    ///             }
+    ///         }
+    ///     })
+    ///     .connect("postgres:// …").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_metric<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(PoolMetric) + 'static + Send + Sync,
+    {
+        self.on_metric = Some(Arc::new(callback));
+        self
+    }
+
     /// Set the parent `Pool` from which the new pool will inherit its semaphore.
     ///
     /// This is currently an internal-only API.
@@ -591,6 +787,11 @@ impl<DB: Database> Debug for PoolOptions<DB> {
             .field("max_lifetime", &self.max_lifetime)
             .field("idle_timeout", &self.idle_timeout)
             .field("test_before_acquire", &self.test_before_acquire)
+            .field(
+                "statement_cache_capacity",
+                &self.get_statement_cache_capacity(),
+            )
+            .field("retry_policy", &self.retry_policy)
             .finish()
     }
 }
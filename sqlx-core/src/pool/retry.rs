@@ -0,0 +1,278 @@
+use std::cmp;
+use std::collections::hash_map::RandomState;
+use std::fmt::{self, Debug, Formatter};
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::Error;
+
+/// How a [`Pool`][super::Pool] retries failed attempts to open a connection, set with
+/// [`PoolOptions::retry_policy()`][super::PoolOptions::retry_policy].
+///
+/// The default policy retries until the acquire timeout runs out, waiting 10 milliseconds after
+/// the first failure and twice as long after each one after that, up to a fifth of the acquire
+/// timeout. It retries the errors which [`is_transient()`][Self::is_transient] returns `true` for,
+/// and errors returned by [`after_connect`] or [`prepare_on_connect`].
+///
+/// Attempts are always bounded by the acquire timeout as well, after which
+/// [`Error::PoolTimedOut`] is returned.
+///
+/// ```rust,no_run
+/// # use std::time::Duration;
+/// # use sqlx_core::pool::{Backoff, PoolOptions, RetryPolicy};
+/// # use sqlx_core::error::Error;
+/// # fn f<DB: sqlx_core::database::Database>() -> PoolOptions<DB> {
+/// PoolOptions::new().retry_policy(
+///     RetryPolicy::new()
+///         .max_attempts(5)
+///         .backoff(Backoff::Exponential {
+///             initial: Duration::from_millis(50),
+///             factor: 2,
+///             max: Some(Duration::from_secs(1)),
+///         })
+///         .jitter(0.5)
+///         .retry_if(|error| {
+///             RetryPolicy::is_transient(error)
+///                 || matches!(error, Error::Io(e) if e.kind() == std::io::ErrorKind::ConnectionReset)
+///         }),
+/// )
+/// # }
+/// ```
+///
+/// [`after_connect`]: super::PoolOptions::after_connect
+/// [`prepare_on_connect`]: super::PoolOptions::prepare_on_connect
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: Option<u32>,
+    backoff: Backoff,
+    jitter: f64,
+    retry_if: Option<Arc<dyn Fn(&Error) -> bool + 'static + Send + Sync>>,
+    retry_on_acquire: bool,
+}
+
+/// How long a [`RetryPolicy`] waits after a failed attempt before the next one.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Backoff {
+    /// Wait the same time after every failure.
+    Constant(Duration),
+
+    /// Wait `initial` after the first failure, then `factor` times as long as the previous wait.
+    Exponential {
+        /// The wait after the first failure.
+        initial: Duration,
+        /// How much longer each wait is than the previous one.
+        factor: u32,
+        /// The longest wait, or a fifth of the acquire timeout if `None`.
+        max: Option<Duration>,
+    },
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RetryPolicy {
+    /// The default policy; see the [type-level docs][Self].
+    pub fn new() -> Self {
+        Self {
+            max_attempts: None,
+            backoff: Backoff::Exponential {
+                initial: Duration::from_millis(10),
+                factor: 2,
+                max: None,
+            },
+            jitter: 0.0,
+            retry_if: None,
+            retry_on_acquire: true,
+        }
+    }
+
+    /// A policy which never retries: the first error opening a connection is returned.
+    pub fn never() -> Self {
+        Self::new().max_attempts(1)
+    }
+
+    /// Set the number of attempts to open a connection, including the first one, or `None` to
+    /// retry until the acquire timeout runs out.
+    ///
+    /// At least one attempt is always made.
+    pub fn max_attempts(mut self, max: impl Into<Option<u32>>) -> Self {
+        self.max_attempts = max.into().map(|max| cmp::max(max, 1));
+        self
+    }
+
+    /// Set how long to wait between attempts.
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Shorten each wait by a random fraction of it, up to `jitter`, so the connections of many
+    /// pools which failed at the same time are not retried at the same time.
+    ///
+    /// Clamped to `0.0..=1.0`; `0.0`, the default, disables jitter.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = if jitter.is_nan() {
+            0.0
+        } else {
+            jitter.clamp(0.0, 1.0)
+        };
+        self
+    }
+
+    /// Set which errors opening a connection are retried, instead of
+    /// [`is_transient()`][Self::is_transient].
+    ///
+    /// Errors returned by [`after_connect`] or [`prepare_on_connect`] are always retried.
+    ///
+    /// [`after_connect`]: super::PoolOptions::after_connect
+    /// [`prepare_on_connect`]: super::PoolOptions::prepare_on_connect
+    pub fn retry_if<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&Error) -> bool + 'static + Send + Sync,
+    {
+        self.retry_if = Some(Arc::new(predicate));
+        self
+    }
+
+    /// If `false`, a connection opened for a caller of [`Pool::acquire()`][super::Pool::acquire]
+    /// is not retried, and the first error is returned to the caller; only the connections
+    /// opened in the background to maintain [`min_connections`] are.
+    ///
+    /// Defaults to `true`.
+    ///
+    /// [`min_connections`]: super::PoolOptions::min_connections
+    pub fn retry_on_acquire(mut self, retry: bool) -> Self {
+        self.retry_on_acquire = retry;
+        self
+    }
+
+    /// Returns `true` for the errors the default policy retries: the connection being refused,
+    /// as when the server is starting up, and database errors which the driver deems transient
+    /// while connecting.
+    pub fn is_transient(error: &Error) -> bool {
+        match error {
+            Error::Io(e) => e.kind() == std::io::ErrorKind::ConnectionRefused,
+            Error::Database(e) => e.is_transient_in_connect_phase(),
+            _ => false,
+        }
+    }
+
+    pub(super) fn should_retry(&self, error: &Error) -> bool {
+        match &self.retry_if {
+            Some(predicate) => predicate(error),
+            None => Self::is_transient(error),
+        }
+    }
+
+    /// Whether to make another attempt after `attempts` failed, when opening a connection for a
+    /// caller of `acquire()` or not.
+    pub(super) fn should_attempt(&self, attempts: u32, acquiring: bool) -> bool {
+        (self.retry_on_acquire || !acquiring)
+            && self.max_attempts.map_or(true, |max| attempts < max)
+    }
+
+    /// The wait after `attempts` failed attempts, capped by `default_max` unless the backoff sets
+    /// its own maximum.
+    pub(super) fn delay(&self, attempts: u32, default_max: Duration) -> Duration {
+        let delay = match self.backoff {
+            Backoff::Constant(delay) => delay,
+            Backoff::Exponential {
+                initial,
+                factor,
+                max,
+            } => {
+                let multiplier = factor.saturating_pow(attempts.saturating_sub(1));
+
+                cmp::min(
+                    initial.checked_mul(multiplier).unwrap_or(Duration::MAX),
+                    max.unwrap_or(default_max),
+                )
+            }
+        };
+
+        if self.jitter > 0.0 {
+            delay.mul_f64(1.0 - self.jitter * random_fraction())
+        } else {
+            delay
+        }
+    }
+}
+
+impl Debug for RetryPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("backoff", &self.backoff)
+            .field("jitter", &self.jitter)
+            .field("retry_if", &self.retry_if.as_ref().map(|_| ".."))
+            .field("retry_on_acquire", &self.retry_on_acquire)
+            .finish()
+    }
+}
+
+/// A number in `0.0..1.0`, random enough to spread out retries.
+fn random_fraction() -> f64 {
+    // every `RandomState` is keyed differently, which saves depending on `rand`
+    let bits = RandomState::new().build_hasher().finish();
+
+    (bits >> 11) as f64 / (1_u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_backs_off_exponentially_up_to_the_max() {
+        let policy = RetryPolicy::new();
+        let default_max = Duration::from_millis(100);
+
+        let delays: Vec<_> = (1..=5)
+            .map(|attempts| policy.delay(attempts, default_max).as_millis())
+            .collect();
+        assert_eq!(delays, [10, 20, 40, 80, 100]);
+
+        let policy = policy.backoff(Backoff::Exponential {
+            initial: Duration::from_secs(1),
+            factor: 3,
+            max: Some(Duration::from_secs(5)),
+        });
+        assert_eq!(policy.delay(2, default_max), Duration::from_secs(3));
+        assert_eq!(policy.delay(3, default_max), Duration::from_secs(5));
+        assert_eq!(policy.delay(u32::MAX, default_max), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn it_jitters_within_bounds() {
+        let policy = RetryPolicy::new()
+            .backoff(Backoff::Constant(Duration::from_secs(1)))
+            .jitter(0.25);
+
+        for _ in 0..100 {
+            let delay = policy.delay(1, Duration::MAX);
+            assert!(
+                delay > Duration::from_millis(750) && delay <= Duration::from_secs(1),
+                "{delay:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn it_counts_attempts() {
+        let policy = RetryPolicy::new().max_attempts(3);
+        assert!(policy.should_attempt(2, true));
+        assert!(!policy.should_attempt(3, true));
+
+        let policy = policy.retry_on_acquire(false);
+        assert!(!policy.should_attempt(1, true));
+        assert!(policy.should_attempt(1, false));
+
+        assert!(!RetryPolicy::never().should_attempt(1, false));
+        assert!(RetryPolicy::new().should_attempt(u32::MAX, true));
+    }
+}
@@ -5,10 +5,16 @@ use futures_core::stream::BoxStream;
 use futures_util::{future, StreamExt, TryFutureExt, TryStreamExt};
 
 use crate::arguments::{Arguments, IntoArguments};
+#[cfg(feature = "json")]
+use crate::column::ColumnIndex;
 use crate::database::{Database, HasStatementCache};
 use crate::encode::Encode;
 use crate::error::{BoxDynError, Error};
-use crate::executor::{Execute, Executor};
+use crate::executor::{Execute, Executor, FetchOptions};
+use crate::fetch_guard::check_fetch_all;
+use crate::from_columns::FromColumns;
+#[cfg(feature = "json")]
+use crate::json_writer::{self, DecodeJson, JsonFormat};
 use crate::statement::Statement;
 use crate::types::Type;
 
@@ -216,6 +222,24 @@ where
         executor.fetch(self)
     }
 
+    /// Execute the query and return the generated results as a stream, fetched as set by
+    /// `options`, e.g. in batches of [`fetch_size`][FetchOptions::fetch_size] rows.
+    ///
+    /// See [`FetchOptions`] for details.
+    #[inline]
+    pub fn fetch_with<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+        options: FetchOptions,
+    ) -> BoxStream<'e, Result<DB::Row, Error>>
+    where
+        'q: 'e,
+        A: 'e,
+        E: Executor<'c, Database = DB>,
+    {
+        executor.fetch_with(self, options)
+    }
+
     /// Execute multiple queries and return the generated results as a stream.
     ///
     /// For each query in the stream, any generated rows are returned first,
@@ -252,6 +276,87 @@ where
         executor.fetch_all(self).await
     }
 
+    /// Execute the query and decode the resulting rows column by column, e.g. into one [`Vec`]
+    /// per column with `C = (Vec<i64>, Vec<String>)`.
+    ///
+    /// Unlike [`fetch_all()`][Self::fetch_all], the rows are decoded as they are received and
+    /// not kept in memory. See [`FromColumns`] for details.
+    ///
+    /// ### Note: beware result set size.
+    /// This will attempt to collect the full result set of the query into memory.
+    ///
+    /// To avoid exhausting available memory, ensure the result set has a known upper bound,
+    /// e.g. using `LIMIT`.
+    pub async fn fetch_columns<'e, 'c: 'e, E, C>(self, executor: E) -> Result<C, Error>
+    where
+        'q: 'e,
+        A: 'e,
+        E: Executor<'c, Database = DB>,
+        C: FromColumns<DB::Row>,
+    {
+        let mut rows = executor.fetch(self);
+        let mut columns = C::with_capacity(rows.size_hint().0);
+
+        while let Some(row) = rows.try_next().await? {
+            columns.push_row(&row)?;
+        }
+
+        Ok(columns)
+    }
+
+    /// Execute the query and write the resulting rows into `writer` as a JSON array of objects,
+    /// keyed by column name in the order of the columns.
+    ///
+    /// Rows are converted as they are received and written in small batches, so memory use
+    /// stays constant however large the result set is, e.g. when proxying a query to an HTTP
+    /// response body. `writer` is flushed at the end. Returns the number of rows written.
+    ///
+    /// `NULL` is written as `null`, and the conversion of other values is up to the driver, see
+    /// [`DecodeJson`]. Values which can't be converted fail with [`Error::ColumnDecode`], after
+    /// the preceding rows may have been written.
+    ///
+    /// `writer` is a `futures` [`AsyncWrite`]; with Tokio, wrap it in
+    /// `tokio_util::compat::TokioAsyncWriteCompatExt::compat_write()`.
+    ///
+    /// [`AsyncWrite`]: futures_io::AsyncWrite
+    #[cfg(feature = "json")]
+    pub async fn fetch_json_array<'e, 'c: 'e, E, W>(
+        self,
+        executor: E,
+        writer: &mut W,
+    ) -> Result<u64, Error>
+    where
+        'q: 'e,
+        A: 'e,
+        E: Executor<'c, Database = DB>,
+        DB: DecodeJson,
+        usize: ColumnIndex<DB::Row>,
+        W: futures_io::AsyncWrite + Unpin + ?Sized,
+    {
+        json_writer::write_rows::<DB, W>(executor.fetch(self), writer, JsonFormat::Array).await
+    }
+
+    /// Execute the query and write each resulting row into `writer` as a JSON object on its own
+    /// line, also known as NDJSON or JSON Lines.
+    ///
+    /// See [`fetch_json_array()`][Self::fetch_json_array] for details.
+    #[cfg(feature = "json")]
+    pub async fn fetch_json_lines<'e, 'c: 'e, E, W>(
+        self,
+        executor: E,
+        writer: &mut W,
+    ) -> Result<u64, Error>
+    where
+        'q: 'e,
+        A: 'e,
+        E: Executor<'c, Database = DB>,
+        DB: DecodeJson,
+        usize: ColumnIndex<DB::Row>,
+        W: futures_io::AsyncWrite + Unpin + ?Sized,
+    {
+        json_writer::write_rows::<DB, W>(executor.fetch(self), writer, JsonFormat::Lines).await
+    }
+
     /// Execute the query, returning the first row or [`Error::RowNotFound`] otherwise.
     ///
     /// ### Note: for best performance, ensure the query returns at most one row.
@@ -390,6 +495,33 @@ where
             .boxed()
     }
 
+    /// Execute the query and return the generated results as a stream, fetched as set by
+    /// `options`.
+    ///
+    /// See [`Query::fetch_with()`].
+    pub fn fetch_with<'e, 'c: 'e, E>(
+        mut self,
+        executor: E,
+        options: FetchOptions,
+    ) -> BoxStream<'e, Result<O, Error>>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        F: 'e,
+        O: 'e,
+    {
+        Box::pin(try_stream! {
+            let mut s = executor.fetch_with(self.inner, options);
+
+            while let Some(row) = s.try_next().await? {
+                r#yield!((self.mapper)(row)?);
+            }
+
+            Ok(())
+        })
+    }
+
     /// Execute multiple queries and return the generated results as a stream
     /// from each query, in a stream.
     #[deprecated = "Only the SQLite driver supports multiple statements in one prepared statement and that behavior is deprecated. Use `sqlx::raw_sql()` instead."]
@@ -435,6 +567,8 @@ where
         F: 'e,
         O: 'e,
     {
+        check_fetch_all(self.sql())?;
+
         self.fetch(executor).try_collect().await
     }
 
@@ -8,7 +8,8 @@ use crate::arguments::IntoArguments;
 use crate::database::{Database, HasStatementCache};
 use crate::encode::Encode;
 use crate::error::{BoxDynError, Error};
-use crate::executor::{Execute, Executor};
+use crate::executor::{Execute, Executor, FetchOptions};
+use crate::fetch_guard::check_fetch_all;
 use crate::from_row::FromRow;
 use crate::query::{query, query_statement, query_statement_with, query_with_result, Query};
 use crate::types::Type;
@@ -102,6 +103,28 @@ where
             .boxed()
     }
 
+    /// Execute the query and return the generated results as a stream, fetched as set by
+    /// `options`.
+    ///
+    /// See [`Query::fetch_with()`].
+    pub fn fetch_with<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+        options: FetchOptions,
+    ) -> BoxStream<'e, Result<O, Error>>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        O: 'e,
+        A: 'e,
+    {
+        executor
+            .fetch_with(self.inner, options)
+            .map(|row| O::from_row(&row?))
+            .boxed()
+    }
+
     /// Execute multiple queries and return the generated results as a stream
     /// from each query, in a stream.
     #[deprecated = "Only the SQLite driver supports multiple statements in one prepared statement and that behavior is deprecated. Use `sqlx::raw_sql()` instead. See https://github.com/launchbadge/sqlx/issues/3108 for discussion."]
@@ -142,6 +165,8 @@ where
         O: 'e,
         A: 'e,
     {
+        check_fetch_all(self.sql())?;
+
         self.fetch(executor).try_collect().await
     }
 
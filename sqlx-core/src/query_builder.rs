@@ -7,11 +7,12 @@ use std::marker::PhantomData;
 use crate::arguments::{Arguments, IntoArguments};
 use crate::database::Database;
 use crate::encode::Encode;
+use crate::error::Error;
 use crate::from_row::FromRow;
 use crate::query::Query;
 use crate::query_as::QueryAs;
 use crate::query_scalar::QueryScalar;
-use crate::types::Type;
+use crate::types::{HasVectorDistance, Type, Vector, VectorDistance};
 use crate::Either;
 
 /// A builder type for constructing queries at runtime.
@@ -528,6 +529,58 @@ where
     }
 }
 
+impl<'args, DB> QueryBuilder<'args, DB>
+where
+    DB: HasVectorDistance,
+    Vector: Encode<'args, DB> + Type<DB>,
+{
+    /// Push an expression computing `distance` between the vectors in `column` and `vector`,
+    /// which is bound as a parameter.
+    ///
+    /// With Postgres this is one of the [`pgvector`] operators, which can use an index when
+    /// the query is ordered by it. With SQLite it is one of the `vec_distance_*()` functions
+    /// of [`sqlite-vec`], which does not support [`VectorDistance::InnerProduct`].
+    ///
+    /// Returns an error, and pushes nothing, if the database does not support `distance`.
+    ///
+    /// ```rust
+    /// use sqlx::postgres::Postgres;
+    /// use sqlx::types::{Vector, VectorDistance};
+    /// use sqlx::QueryBuilder;
+    ///
+    /// let mut query: QueryBuilder<Postgres> = QueryBuilder::new("SELECT id FROM item ORDER BY ");
+    /// query
+    ///     .push_vector_distance("embedding", VectorDistance::Cosine, Vector(vec![0.1, 0.2, 0.3]))?
+    ///     .push(" LIMIT 5");
+    ///
+    /// assert_eq!(query.sql(), "SELECT id FROM item ORDER BY (embedding <=> $1) LIMIT 5");
+    /// # Ok::<(), sqlx::Error>(())
+    /// ```
+    ///
+    /// [`pgvector`]: https://github.com/pgvector/pgvector#querying
+    /// [`sqlite-vec`]: https://alexgarcia.xyz/sqlite-vec/api-reference.html
+    pub fn push_vector_distance(
+        &mut self,
+        column: impl Display,
+        distance: VectorDistance,
+        vector: impl Into<Vector>,
+    ) -> Result<&mut Self, Error> {
+        let (open, separator, close) = DB::vector_distance_sql(distance).ok_or_else(|| {
+            Error::InvalidArgument(format!(
+                "{distance:?} vector distance is not supported by {}",
+                DB::NAME
+            ))
+        })?;
+
+        Ok(self
+            .push(open)
+            .push(column)
+            .push(separator)
+            .push_bind(vector.into())
+            .push(close))
+    }
+}
+
 /// A wrapper around `QueryBuilder` for creating comma(or other token)-separated lists.
 ///
 /// See [`QueryBuilder::separated()`] for details.
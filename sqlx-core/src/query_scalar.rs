@@ -1,17 +1,26 @@
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
 use either::Either;
-use futures_core::stream::BoxStream;
+use futures_core::stream::{BoxStream, Stream};
 use futures_util::{StreamExt, TryFutureExt, TryStreamExt};
 
 use crate::arguments::IntoArguments;
 use crate::database::{Database, HasStatementCache};
+use crate::decode::Decode;
 use crate::encode::Encode;
-use crate::error::{BoxDynError, Error};
-use crate::executor::{Execute, Executor};
+use crate::error::{mismatched_types, BoxDynError, Error};
+use crate::executor::{Execute, Executor, FetchOptions};
+use crate::fetch_guard::check_fetch_all;
 use crate::from_row::FromRow;
 use crate::query_as::{
     query_as, query_as_with_result, query_statement_as, query_statement_as_with, QueryAs,
 };
+use crate::type_info::TypeInfo;
 use crate::types::Type;
+use crate::value::{Value, ValueRef};
 
 /// A single SQL query as a prepared statement which extracts only the first column of each row.
 /// Returned by [`query_scalar()`].
@@ -44,6 +53,70 @@ where
     }
 }
 
+/// A stream of the first column of each row returned by a query, decoded as `O`.
+/// Returned by [`QueryScalar::fetch_first_column()`].
+///
+/// Where the driver supports it, only the first column is read from each row as it is received,
+/// and the rest of the row is never materialized.
+#[must_use = "streams do nothing unless polled"]
+pub struct QueryScalarStream<'e, DB: Database, O> {
+    inner: BoxStream<'e, Result<Either<DB::Row, DB::Value>, Error>>,
+    output: PhantomData<fn() -> O>,
+}
+
+impl<DB, O> Stream for QueryScalarStream<'_, DB, O>
+where
+    DB: Database,
+    O: for<'r> Decode<'r, DB> + Type<DB>,
+    (O,): for<'r> FromRow<'r, DB::Row>,
+{
+    type Item = Result<O, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx).map(|next| {
+            next.map(|res| match res? {
+                Either::Left(row) => <(O,)>::from_row(&row).map(|(it,)| it),
+                Either::Right(value) => decode_first_column::<DB, O>(&value),
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<DB: Database, O> Debug for QueryScalarStream<'_, DB, O> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryScalarStream").finish_non_exhaustive()
+    }
+}
+
+/// Decode the first column of a row, as [`Row::try_get()`][crate::row::Row::try_get] would.
+fn decode_first_column<DB, O>(value: &DB::Value) -> Result<O, Error>
+where
+    DB: Database,
+    O: for<'r> Decode<'r, DB> + Type<DB>,
+{
+    let value = value.as_ref();
+
+    if !value.is_null() {
+        let ty = value.type_info();
+
+        if !ty.is_null() && !O::compatible(&ty) {
+            return Err(Error::ColumnDecode {
+                index: "0".into(),
+                source: mismatched_types::<DB, O>(&ty),
+            });
+        }
+    }
+
+    O::decode(value).map_err(|source| Error::ColumnDecode {
+        index: "0".into(),
+        source,
+    })
+}
+
 impl<'q, DB: Database, O> QueryScalar<'q, DB, O, <DB as Database>::Arguments<'q>> {
     /// Bind a value for use with this SQL query.
     ///
@@ -96,6 +169,47 @@ where
         self.inner.fetch(executor).map_ok(|it| it.0).boxed()
     }
 
+    /// Execute the query and return the generated results as a stream, like
+    /// [`fetch()`][Self::fetch], but reading only the first column of each row where the driver
+    /// supports it, so the rest of the row is never materialized.
+    #[inline]
+    pub fn fetch_first_column<'e, 'c: 'e, E>(self, executor: E) -> QueryScalarStream<'e, DB, O>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        DB::Value: Send,
+        A: 'e,
+        O: 'e + for<'r> Decode<'r, DB> + Type<DB>,
+    {
+        QueryScalarStream {
+            inner: executor.fetch_first_column(self.inner.inner),
+            output: PhantomData,
+        }
+    }
+
+    /// Execute the query and return the generated results as a stream, fetched as set by
+    /// `options`.
+    ///
+    /// See [`Query::fetch_with()`][crate::query::Query::fetch_with].
+    pub fn fetch_with<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+        options: FetchOptions,
+    ) -> BoxStream<'e, Result<O, Error>>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        A: 'e,
+        O: 'e,
+    {
+        self.inner
+            .fetch_with(executor, options)
+            .map_ok(|it| it.0)
+            .boxed()
+    }
+
     /// Execute multiple queries and return the generated results as a stream
     /// from each query, in a stream.
     #[inline]
@@ -134,6 +248,8 @@ where
         (O,): 'e,
         A: 'e,
     {
+        check_fetch_all(self.sql())?;
+
         self.inner
             .fetch(executor)
             .map_ok(|it| it.0)
@@ -190,6 +306,43 @@ where
     }
 }
 
+impl<'q, DB, O, A> QueryScalar<'q, DB, Option<O>, A>
+where
+    DB: Database,
+    O: Send + Unpin,
+    A: 'q + IntoArguments<'q, DB>,
+    (Option<O>,): Send + Unpin + for<'r> FromRow<'r, DB::Row>,
+{
+    /// Execute the query, returning the value of the first row, or `None` if there are no rows
+    /// or the value is `NULL`.
+    ///
+    /// This is [`fetch_optional()`][Self::fetch_optional] for a nullable column, without the
+    /// `Option<Option<O>>`, for when there is no difference between a missing row and a missing
+    /// value:
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+    /// let nickname: Option<String> =
+    ///     sqlx::query_scalar::<_, Option<String>>("SELECT nickname FROM users WHERE id = $1")
+    ///         .bind(1_i64)
+    ///         .fetch_optional_scalar(&pool)
+    ///         .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn fetch_optional_scalar<'e, 'c: 'e, E>(self, executor: E) -> Result<Option<O>, Error>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        O: 'e,
+        A: 'e,
+    {
+        Ok(self.fetch_optional(executor).await?.flatten())
+    }
+}
+
 /// Execute a single SQL query as a prepared statement (transparently cached) and extract the first
 /// column of each row.
 ///
@@ -1,4 +1,4 @@
-use crate::column::ColumnIndex;
+use crate::column::{Column, ColumnIndex};
 use crate::database::Database;
 use crate::decode::Decode;
 use crate::error::{mismatched_types, Error};
@@ -120,18 +120,103 @@ pub trait Row: Unpin + Send + Sync + 'static {
 
             if !ty.is_null() && !T::compatible(&ty) {
                 return Err(Error::ColumnDecode {
-                    index: format!("{index:?}"),
+                    index: describe_column(self, &index),
                     source: mismatched_types::<Self::Database, T>(&ty),
                 });
             }
         }
 
         T::decode(value).map_err(|source| Error::ColumnDecode {
-            index: format!("{index:?}"),
+            index: describe_column(self, &index),
             source,
         })
     }
 
+    /// Index into the database row and decode a single value, returning `default` if the
+    /// value is `NULL`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column does not exist or its value cannot be decoded into the requested type.
+    /// See [`try_get_or`](Self::try_get_or) for a non-panicking version.
+    ///
+    #[inline]
+    #[track_caller]
+    fn get_or<'r, T, I>(&'r self, index: I, default: T) -> T
+    where
+        I: ColumnIndex<Self>,
+        T: Decode<'r, Self::Database> + Type<Self::Database>,
+    {
+        self.try_get_or::<T, I>(index, default).unwrap()
+    }
+
+    /// Index into the database row and decode a single value, returning `default` if the
+    /// value is `NULL`.
+    ///
+    /// # Errors
+    ///
+    ///  * [`ColumnNotFound`] if the column by the given name was not found.
+    ///  * [`ColumnIndexOutOfBounds`] if the `usize` index was greater than the number of columns in the row.
+    ///  * [`ColumnDecode`] if the value could not be decoded into the requested type.
+    ///
+    /// [`ColumnDecode`]: Error::ColumnDecode
+    /// [`ColumnNotFound`]: Error::ColumnNotFound
+    /// [`ColumnIndexOutOfBounds`]: Error::ColumnIndexOutOfBounds
+    ///
+    #[inline]
+    fn try_get_or<'r, T, I>(&'r self, index: I, default: T) -> Result<T, Error>
+    where
+        I: ColumnIndex<Self>,
+        T: Decode<'r, Self::Database> + Type<Self::Database>,
+    {
+        Ok(self.try_get::<Option<T>, I>(index)?.unwrap_or(default))
+    }
+
+    /// Index into the database row and decode a single value, returning [`Default::default()`]
+    /// if the value is `NULL`.
+    ///
+    /// # Errors
+    ///
+    ///  * [`ColumnNotFound`] if the column by the given name was not found.
+    ///  * [`ColumnIndexOutOfBounds`] if the `usize` index was greater than the number of columns in the row.
+    ///  * [`ColumnDecode`] if the value could not be decoded into the requested type.
+    ///
+    /// [`ColumnDecode`]: Error::ColumnDecode
+    /// [`ColumnNotFound`]: Error::ColumnNotFound
+    /// [`ColumnIndexOutOfBounds`]: Error::ColumnIndexOutOfBounds
+    ///
+    #[inline]
+    fn try_get_or_default<'r, T, I>(&'r self, index: I) -> Result<T, Error>
+    where
+        I: ColumnIndex<Self>,
+        T: Decode<'r, Self::Database> + Type<Self::Database> + Default,
+    {
+        Ok(self.try_get::<Option<T>, I>(index)?.unwrap_or_default())
+    }
+
+    /// Index into the database row and decode a single value, returning `None` if the
+    /// column does not exist _or_ its value is `NULL`.
+    ///
+    /// This is useful when the same code reads rows from queries which may or may not
+    /// select a given column, as it flattens both cases into a single `Option`.
+    ///
+    /// # Errors
+    ///
+    ///  * [`ColumnDecode`] if the value could not be decoded into the requested type.
+    ///
+    /// [`ColumnDecode`]: Error::ColumnDecode
+    ///
+    fn try_get_optional<'r, T, I>(&'r self, index: I) -> Result<Option<T>, Error>
+    where
+        I: ColumnIndex<Self>,
+        T: Decode<'r, Self::Database> + Type<Self::Database>,
+    {
+        match self.try_get::<Option<T>, I>(index) {
+            Err(Error::ColumnNotFound(_) | Error::ColumnIndexOutOfBounds { .. }) => Ok(None),
+            res => res,
+        }
+    }
+
     /// Index into the database row and decode a single value.
     ///
     /// Unlike [`try_get`](Self::try_get), this method does not check that the type
@@ -157,7 +242,7 @@ pub trait Row: Unpin + Send + Sync + 'static {
         let value = self.try_get_raw(&index)?;
 
         T::decode(value).map_err(|source| Error::ColumnDecode {
-            index: format!("{index:?}"),
+            index: describe_column(self, &index),
             source,
         })
     }
@@ -176,3 +261,21 @@ pub trait Row: Unpin + Send + Sync + 'static {
     where
         I: ColumnIndex<Self>;
 }
+
+// Describe a column for `Error::ColumnDecode`, naming it if it was indexed by position.
+fn describe_column<R: Row + ?Sized, I: ColumnIndex<R>>(row: &R, index: &I) -> String {
+    let label = format!("{index:?}");
+
+    let name = index
+        .index(row)
+        .ok()
+        .and_then(|i| row.columns().get(i))
+        .map(Column::name);
+
+    match name {
+        Some(name) if !name.is_empty() && label != format!("{name:?}") => {
+            format!("{label} ({name:?})")
+        }
+        _ => label,
+    }
+}
@@ -0,0 +1,207 @@
+//! Splitting SQL scripts into statements for [`Transaction::execute_script()`].
+//!
+//! [`Transaction::execute_script()`]: crate::transaction::Transaction::execute_script
+
+use crate::arguments::Arguments;
+use crate::database::Database;
+use crate::encode::Encode;
+use crate::error::BoxDynError;
+use crate::types::Type;
+
+/// A value that can be bound to the parameters of a script run with
+/// [`Transaction::execute_script()`].
+///
+/// Implemented for every type that can be bound to a query without borrowing from it. SQLite
+/// borrows `&str` and `&[u8]` arguments, so bind a `String` or `Vec<u8>` there instead.
+///
+/// [`Transaction::execute_script()`]: crate::transaction::Transaction::execute_script
+pub trait ScriptBind<DB: Database>: Send + Sync {
+    /// Add this value to the arguments of a statement.
+    fn add_to<'a>(&'a self, arguments: &mut DB::Arguments<'a>) -> Result<(), BoxDynError>;
+}
+
+impl<DB, T> ScriptBind<DB> for T
+where
+    DB: Database,
+    T: for<'a> Encode<'a, DB> + Type<DB> + Send + Sync,
+{
+    fn add_to<'a>(&'a self, arguments: &mut DB::Arguments<'a>) -> Result<(), BoxDynError> {
+        arguments.add(self)
+    }
+}
+
+/// A piece of a statement in a script.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Fragment<'s> {
+    /// SQL to be sent as-is.
+    Sql(&'s str),
+    /// A `$N` parameter, numbered from 1.
+    Param(usize),
+}
+
+/// Split `sql` on semicolons into statements, finding the `$N` parameters in each.
+///
+/// Semicolons and `$N` inside string literals, quoted identifiers, dollar-quoted strings and
+/// comments are ignored. Statements containing nothing but whitespace and comments are skipped.
+pub(crate) fn split(sql: &str) -> Vec<Vec<Fragment<'_>>> {
+    let bytes = sql.as_bytes();
+
+    let mut statements = Vec::new();
+    let mut fragments = Vec::new();
+    let mut has_content = false;
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = find(bytes, i + 2, b"\n").map_or(bytes.len(), |end| end + 1);
+                continue;
+            }
+
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = find(bytes, i + 2, b"*/").map_or(bytes.len(), |end| end + 2);
+                continue;
+            }
+
+            // a doubled quote inside the literal is seen as two adjacent literals, which is
+            // equivalent here
+            quote @ (b'\'' | b'"' | b'`') => {
+                i = find(bytes, i + 1, &[quote]).map_or(bytes.len(), |end| end + 1);
+                has_content = true;
+                continue;
+            }
+
+            b'$' if !(i > 0 && is_ident_byte(bytes[i - 1])) => {
+                let digits = bytes[i + 1..]
+                    .iter()
+                    .take_while(|b| b.is_ascii_digit())
+                    .count();
+
+                if digits > 0 {
+                    let end = i + 1 + digits;
+
+                    fragments.push(Fragment::Sql(&sql[start..i]));
+                    // `usize::MAX` is never a valid index, so it is reported as out of range
+                    fragments.push(Fragment::Param(
+                        sql[i + 1..end].parse().unwrap_or(usize::MAX),
+                    ));
+
+                    has_content = true;
+                    start = end;
+                    i = end;
+                    continue;
+                }
+
+                let tag_len = bytes[i + 1..]
+                    .iter()
+                    .take_while(|&&b| b != b'$' && is_ident_byte(b))
+                    .count();
+
+                if bytes.get(i + 1 + tag_len) == Some(&b'$') {
+                    let tag = &bytes[i..i + tag_len + 2];
+
+                    i = find(bytes, i + tag.len(), tag).map_or(bytes.len(), |end| end + tag.len());
+                    has_content = true;
+                    continue;
+                }
+            }
+
+            b';' => {
+                fragments.push(Fragment::Sql(&sql[start..i]));
+
+                if std::mem::take(&mut has_content) {
+                    statements.push(std::mem::take(&mut fragments));
+                } else {
+                    fragments.clear();
+                }
+
+                start = i + 1;
+            }
+
+            b if !b.is_ascii_whitespace() => has_content = true,
+
+            _ => {}
+        }
+
+        i += 1;
+    }
+
+    if has_content {
+        fragments.push(Fragment::Sql(&sql[start..]));
+        statements.push(fragments);
+    }
+
+    statements
+}
+
+pub(crate) fn find(haystack: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|pos| from + pos)
+}
+
+pub(crate) fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || !b.is_ascii()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split, Fragment::*};
+
+    #[test]
+    fn splits_statements() {
+        assert_eq!(
+            split("CREATE TABLE t (x INT);\n\n-- a comment; with a semicolon\nINSERT INTO t VALUES (1)"),
+            vec![
+                vec![Sql("CREATE TABLE t (x INT)")],
+                vec![Sql("\n\n-- a comment; with a semicolon\nINSERT INTO t VALUES (1)")],
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_empty_statements() {
+        assert_eq!(
+            split(" ; /* nothing */ ;SELECT 1;;\n"),
+            vec![vec![Sql("SELECT 1")]]
+        );
+    }
+
+    #[test]
+    fn finds_params() {
+        assert_eq!(
+            split("UPDATE t SET x = $1 WHERE y = $2::int; DELETE FROM t WHERE x = $1"),
+            vec![
+                vec![
+                    Sql("UPDATE t SET x = "),
+                    Param(1),
+                    Sql(" WHERE y = "),
+                    Param(2),
+                    Sql("::int"),
+                ],
+                vec![Sql(" DELETE FROM t WHERE x = "), Param(1), Sql("")],
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_quoted_text() {
+        assert_eq!(
+            split(
+                "SELECT '$1;', \"a;$2\", `b;`, 'it''s; $3';\n\
+                 CREATE FUNCTION f() RETURNS int AS $body$ SELECT $1; $body$ LANGUAGE sql;\n\
+                 SELECT $$;$$, foo$1"
+            ),
+            vec![
+                vec![Sql("SELECT '$1;', \"a;$2\", `b;`, 'it''s; $3'")],
+                vec![Sql(
+                    "\nCREATE FUNCTION f() RETURNS int AS $body$ SELECT $1; $body$ LANGUAGE sql"
+                )],
+                vec![Sql("\nSELECT $$;$$, foo$1")],
+            ]
+        );
+    }
+}
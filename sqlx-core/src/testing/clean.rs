@@ -0,0 +1,40 @@
+use crate::acquire::Acquire;
+use crate::error::Error;
+use crate::testing::TestSupport;
+
+/// Delete every row from `tables` and reset their sequences or autoincrement counters.
+///
+/// Foreign keys between the listed tables are dealt with by the driver, so they may be given
+/// in any order:
+///
+/// * Postgres truncates them together with `TRUNCATE ... RESTART IDENTITY`.
+/// * MySQL truncates them one by one with `FOREIGN_KEY_CHECKS` disabled.
+/// * SQLite deletes from them in one transaction with foreign key checks deferred to the
+///   commit, then clears their entries in `sqlite_sequence`.
+///
+/// Tables which are not listed but reference one that is are not emptied; with Postgres and
+/// SQLite this is an error if they contain any rows. With MySQL the references are left dangling.
+///
+/// Table names are interpolated into the statements as-is, so they may be qualified with a
+/// schema but must be quoted by the caller if necessary.
+///
+/// ```rust,ignore
+/// #[sqlx::test]
+/// async fn starts_empty(pool: PgPool) -> sqlx::Result<()> {
+///     sqlx::testing::clean_tables(&pool, &["comment", "post", "users"]).await?;
+///     // ...
+/// }
+/// ```
+pub async fn clean_tables<'a, A>(conn: A, tables: &[&str]) -> Result<(), Error>
+where
+    A: Acquire<'a>,
+    A::Database: TestSupport,
+{
+    if tables.is_empty() {
+        return Ok(());
+    }
+
+    let mut conn = conn.acquire().await?;
+
+    <A::Database as TestSupport>::clean_tables(&mut conn, tables).await
+}
@@ -1,20 +1,33 @@
+use std::any::Any;
 use std::future::Future;
+use std::path::Path;
 use std::time::Duration;
 
 use futures_core::future::BoxFuture;
 
 use base64::{engine::general_purpose::URL_SAFE, Engine as _};
+pub use clean::clean_tables;
 pub use fixtures::FixtureSnapshot;
+pub use plan::{explain, QueryPlan};
+#[cfg(feature = "proptest")]
+pub use roundtrip::{proptest, roundtrip, roundtrip_with, Roundtrip};
 use sha2::{Digest, Sha512};
 
 use crate::connection::{ConnectOptions, Connection};
 use crate::database::Database;
-use crate::error::Error;
+use crate::error::{BoxDynError, Error};
 use crate::executor::Executor;
+use crate::fixtures::{dependencies, fixture_name, order_by_dependencies, FixtureTemplate};
 use crate::migrate::{Migrate, Migrator};
 use crate::pool::{Pool, PoolConnection, PoolOptions};
 
+mod clean;
 mod fixtures;
+mod plan;
+#[cfg(feature = "proptest")]
+mod roundtrip;
+#[cfg(feature = "proptest")]
+pub mod strategy;
 
 pub trait TestSupport: Database {
     /// Get parameters to construct a `Pool` suitable for testing.
@@ -44,6 +57,36 @@ pub trait TestSupport: Database {
     fn snapshot(conn: &mut Self::Connection)
         -> BoxFuture<'_, Result<FixtureSnapshot<Self>, Error>>;
 
+    /// Run `EXPLAIN` on `query` and return the plan the database chose for it.
+    ///
+    /// See [`assert_index_used!()`][crate::assert_index_used]. The default implementation
+    /// returns an error.
+    fn explain<'c>(
+        _conn: &'c mut Self::Connection,
+        _query: &'c str,
+    ) -> BoxFuture<'c, Result<QueryPlan, Error>> {
+        Box::pin(async {
+            Err(Error::Configuration(
+                format!("EXPLAIN is not supported for {}", Self::NAME).into(),
+            ))
+        })
+    }
+
+    /// Delete every row from `tables` and reset their sequences, whatever the foreign keys
+    /// between them.
+    ///
+    /// See [`clean_tables()`]. The default implementation returns an error.
+    fn clean_tables<'c>(
+        _conn: &'c mut Self::Connection,
+        _tables: &'c [&'c str],
+    ) -> BoxFuture<'c, Result<(), Error>> {
+        Box::pin(async {
+            Err(Error::Configuration(
+                format!("cleaning tables is not supported for {}", Self::NAME).into(),
+            ))
+        })
+    }
+
     /// Generate a unique database name for the given test path.
     fn db_name(args: &TestArgs) -> String {
         let mut hasher = Sha512::new();
@@ -56,15 +99,75 @@ pub trait TestSupport: Database {
     }
 }
 
-pub struct TestFixture {
-    pub path: &'static str,
-    pub contents: &'static str,
+pub enum TestFixture {
+    /// A SQL script, which may name its dependencies in `-- depends:` comments.
+    Script {
+        path: &'static str,
+        contents: &'static str,
+    },
+    /// An async function receiving the test pool, to insert data SQL can't express.
+    Function {
+        name: &'static str,
+        depends: &'static [&'static str],
+        run: FixtureFn,
+    },
+}
+
+/// A fixture function taking a `&Pool<DB>` as `&dyn Any`, as fixtures aren't generic over the
+/// database.
+pub type FixtureFn =
+    Box<dyn Fn(&dyn Any) -> BoxFuture<'static, Result<(), BoxDynError>> + Send + Sync>;
+
+impl TestFixture {
+    /// A fixture running `f` with a pool connected to the test database.
+    pub fn function<DB, F, Fut, E>(
+        name: &'static str,
+        depends: &'static [&'static str],
+        f: F,
+    ) -> Self
+    where
+        DB: Database,
+        F: Fn(Pool<DB>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: Into<BoxDynError>,
+    {
+        TestFixture::Function {
+            name,
+            depends,
+            run: Box::new(move |pool| {
+                let pool = pool.downcast_ref::<Pool<DB>>().unwrap_or_else(|| {
+                    panic!("test fixture {name:?} takes a pool of another database than the test")
+                });
+
+                let fut = f(pool.clone());
+
+                Box::pin(async move { fut.await.map_err(Into::into) })
+            }),
+        }
+    }
+
+    /// The name other fixtures depend on it by.
+    fn name(&self) -> &'static str {
+        match self {
+            TestFixture::Script { path, .. } => fixture_name(Path::new(*path)),
+            TestFixture::Function { name, .. } => name,
+        }
+    }
+
+    fn dependencies(&self) -> Vec<&'static str> {
+        match self {
+            TestFixture::Script { contents, .. } => dependencies(contents)
+                .map(|dependency| fixture_name(Path::new(dependency)))
+                .collect(),
+            TestFixture::Function { depends, .. } => depends.to_vec(),
+        }
+    }
 }
 
 pub struct TestArgs {
     pub test_path: &'static str,
     pub migrator: Option<&'static Migrator>,
-    pub fixtures: &'static [TestFixture],
+    pub fixtures: Vec<TestFixture>,
 }
 
 pub trait TestFn {
@@ -156,7 +259,7 @@ impl TestArgs {
         TestArgs {
             test_path,
             migrator: None,
-            fixtures: &[],
+            fixtures: Vec::new(),
         }
     }
 
@@ -164,7 +267,7 @@ impl TestArgs {
         self.migrator = Some(migrator);
     }
 
-    pub fn fixtures(&mut self, fixtures: &'static [TestFixture]) {
+    pub fn fixtures(&mut self, fixtures: Vec<TestFixture>) {
         self.fixtures = fixtures;
     }
 }
@@ -261,11 +364,47 @@ async fn setup_test_db<DB: Database>(
             .expect("failed to apply migrations");
     }
 
-    for fixture in args.fixtures {
-        (&mut conn)
-            .execute(fixture.contents)
-            .await
-            .unwrap_or_else(|e| panic!("failed to apply test fixture {:?}: {:?}", fixture.path, e));
+    let graph: Vec<_> = args
+        .fixtures
+        .iter()
+        .map(|fixture| (fixture.name(), fixture.dependencies()))
+        .collect();
+
+    let order = order_by_dependencies(&graph)
+        .unwrap_or_else(|e| panic!("failed to order test fixtures: {e}"));
+
+    let mut template = FixtureTemplate::new();
+    // only connected for fixture functions
+    let mut pool = None;
+
+    for fixture in order.into_iter().map(|index| &args.fixtures[index]) {
+        match fixture {
+            TestFixture::Script { path, contents } => {
+                let sql = template
+                    .render(path, contents)
+                    .unwrap_or_else(|e| panic!("failed to render test fixture {path:?}: {e}"));
+
+                (&mut conn)
+                    .execute(&*sql)
+                    .await
+                    .unwrap_or_else(|e| panic!("failed to apply test fixture {path:?}: {e:?}"));
+            }
+            TestFixture::Function { name, run, .. } => {
+                let pool = pool.get_or_insert_with(|| {
+                    PoolOptions::<DB>::new()
+                        .max_connections(1)
+                        .connect_lazy_with(copts.clone())
+                });
+
+                run(pool as &dyn Any)
+                    .await
+                    .unwrap_or_else(|e| panic!("failed to apply test fixture {name:?}: {e}"));
+            }
+        }
+    }
+
+    if let Some(pool) = pool {
+        pool.close().await;
     }
 
     conn.close()
@@ -0,0 +1,79 @@
+use std::fmt::{self, Display};
+
+use crate::acquire::Acquire;
+use crate::error::Error;
+use crate::testing::TestSupport;
+
+/// The plan chosen by the database for a query, as reported by `EXPLAIN`.
+#[derive(Debug, Clone, Default)]
+pub struct QueryPlan {
+    /// The plan as printed by the database, one node per line.
+    pub lines: Vec<String>,
+    /// The names of the indexes the plan reads from.
+    pub indexes: Vec<String>,
+}
+
+impl QueryPlan {
+    /// Returns `true` if the plan reads from the index named `index`.
+    pub fn uses_index(&self, index: &str) -> bool {
+        self.indexes.iter().any(|used| used == index)
+    }
+}
+
+impl Display for QueryPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{line}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Run `EXPLAIN` on `query` and return the plan the database chose for it.
+///
+/// `query` must not contain bind parameters. Session settings that influence the planner
+/// only apply if `conn` is the connection they were set on.
+pub async fn explain<'a, A>(conn: A, query: &str) -> Result<QueryPlan, Error>
+where
+    A: Acquire<'a>,
+    A::Database: TestSupport,
+{
+    let mut conn = conn.acquire().await?;
+
+    <A::Database as TestSupport>::explain(&mut conn, query).await
+}
+
+/// Assert that the database uses an index for a query, as reported by `EXPLAIN`.
+///
+/// The first argument is anything a connection can be [acquired][crate::acquire::Acquire]
+/// from, such as `&pool` or `&mut *conn`. The query must not contain bind parameters.
+///
+/// Panics with the full plan if the index is not used.
+///
+/// ```rust,ignore
+/// #[sqlx::test]
+/// async fn lookup_uses_index(pool: PgPool) {
+///     sqlx::assert_index_used!(&pool, "SELECT * FROM users WHERE email = 'a@b.c'", "users_email_idx");
+/// }
+/// ```
+///
+/// Query planners prefer scanning small tables over using an index, so the test database may
+/// need more rows, or planner settings such as Postgres' `enable_seqscan = off` set on the
+/// connection that is passed in.
+#[macro_export]
+macro_rules! assert_index_used {
+    ($conn:expr, $query:expr, $index:expr $(,)?) => {{
+        let index: &str = $index;
+        let plan = $crate::testing::explain($conn, $query)
+            .await
+            .expect("failed to EXPLAIN query");
+
+        assert!(
+            plan.uses_index(index),
+            "expected query to use index `{}`, but its plan was:\n{}",
+            index,
+            plan
+        );
+    }};
+}
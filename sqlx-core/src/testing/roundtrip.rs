@@ -0,0 +1,170 @@
+use std::fmt::Debug;
+
+pub use proptest;
+use proptest::strategy::{BoxedStrategy, Strategy, ValueTree};
+use proptest::test_runner::{Config, TestRunner};
+
+use crate::acquire::Acquire;
+use crate::arguments::{Arguments, IntoArguments};
+use crate::column::ColumnIndex;
+use crate::database::{Database, HasStatementCache};
+use crate::decode::Decode;
+use crate::encode::Encode;
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::query::query_with;
+use crate::row::Row;
+use crate::types::Type;
+
+/// A type with a strategy to generate the values of it that `DB` stores without loss, to check
+/// its [`Encode`] and [`Decode`] implementations with [`roundtrip()`].
+///
+/// The drivers implement it for the types they support, with the ranges and precision of their
+/// column types; implement it for a custom type to check it the same way.
+///
+/// ```rust,ignore
+/// use sqlx::testing::proptest::prelude::*;
+/// use sqlx::testing::Roundtrip;
+///
+/// impl Roundtrip<Postgres> for Meters {
+///     fn strategy() -> BoxedStrategy<Self> {
+///         (0..=i32::MAX).prop_map(Meters).boxed()
+///     }
+/// }
+/// ```
+pub trait Roundtrip<DB: Database>:
+    for<'q> Encode<'q, DB>
+    + for<'r> Decode<'r, DB>
+    + Type<DB>
+    + PartialEq
+    + Debug
+    + Clone
+    + Send
+    + 'static
+{
+    /// Generate values of `Self` which are expected to be decoded as they were encoded.
+    fn strategy() -> BoxedStrategy<Self>;
+}
+
+/// Bind values of `T` generated by [`Roundtrip::strategy()`], select them back and check they
+/// decode to the values that were bound.
+///
+/// As many values are checked as [`proptest`] is configured to run cases, 256 unless
+/// `PROPTEST_CASES` is set. If a value fails, it is shrunk to the simplest value that still fails
+/// and an error is returned with it; this includes errors from the database.
+///
+/// ```rust,ignore
+/// #[sqlx::test]
+/// async fn meters_roundtrip(pool: PgPool) -> sqlx::Result<()> {
+///     sqlx::testing::roundtrip::<Meters, _>(&pool).await
+/// }
+/// ```
+pub async fn roundtrip<'a, T, A>(conn: A) -> Result<(), Error>
+where
+    A: Acquire<'a>,
+    A::Database: HasStatementCache,
+    T: Roundtrip<A::Database>,
+    for<'c> &'c mut <A::Database as Database>::Connection: Executor<'c, Database = A::Database>,
+    for<'q> <A::Database as Database>::Arguments<'q>: IntoArguments<'q, A::Database>,
+    usize: ColumnIndex<<A::Database as Database>::Row>,
+{
+    roundtrip_with(conn, T::strategy()).await
+}
+
+/// Like [`roundtrip()`], with the values generated by `strategy` instead of
+/// [`Roundtrip::strategy()`].
+pub async fn roundtrip_with<'a, A, S>(conn: A, strategy: S) -> Result<(), Error>
+where
+    A: Acquire<'a>,
+    A::Database: HasStatementCache,
+    S: Strategy,
+    S::Value: for<'q> Encode<'q, A::Database>
+        + for<'r> Decode<'r, A::Database>
+        + Type<A::Database>
+        + PartialEq
+        + Debug
+        + Clone
+        + Send
+        + 'static,
+    for<'c> &'c mut <A::Database as Database>::Connection: Executor<'c, Database = A::Database>,
+    for<'q> <A::Database as Database>::Arguments<'q>: IntoArguments<'q, A::Database>,
+    usize: ColumnIndex<<A::Database as Database>::Row>,
+{
+    let mut conn = conn.acquire().await?;
+    let mut runner = TestRunner::new(Config::default());
+
+    for _ in 0..runner.config().cases {
+        let mut tree = strategy
+            .new_tree(&mut runner)
+            .map_err(|reason| Error::Configuration(reason.to_string().into()))?;
+
+        let Err(mut failure) = check(&mut conn, tree.current()).await else {
+            continue;
+        };
+        let mut failing = tree.current();
+
+        // as `proptest` does: simplify while the value fails, complicate again once it passes
+        if tree.simplify() {
+            loop {
+                match check(&mut conn, tree.current()).await {
+                    Ok(()) if !tree.complicate() => break,
+                    Ok(()) => {}
+                    Err(error) => {
+                        failure = error;
+                        failing = tree.current();
+
+                        if !tree.simplify() {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        return Err(Error::Decode(
+            format!("{failing:?} did not survive a roundtrip: {failure}").into(),
+        ));
+    }
+
+    Ok(())
+}
+
+async fn check<DB, T>(conn: &mut DB::Connection, value: T) -> Result<(), Error>
+where
+    DB: Database + HasStatementCache,
+    T: for<'q> Encode<'q, DB>
+        + for<'r> Decode<'r, DB>
+        + Type<DB>
+        + PartialEq
+        + Debug
+        + Clone
+        + Send
+        + 'static,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    for<'q> DB::Arguments<'q>: IntoArguments<'q, DB>,
+    usize: ColumnIndex<DB::Row>,
+{
+    // declared first, as `arguments` takes the lifetime of the query borrowing it
+    let mut sql = String::from("SELECT ");
+
+    let mut arguments = DB::Arguments::default();
+    arguments.add(value.clone()).map_err(Error::Encode)?;
+
+    // Postgres numbers its placeholders after the arguments added so far
+    arguments
+        .format_placeholder(&mut sql)
+        .map_err(|e| Error::Encode(e.into()))?;
+
+    // not cached, as the statement is the same for every type but its parameter type is not
+    let row = query_with(&sql, arguments)
+        .persistent(false)
+        .fetch_one(&mut *conn)
+        .await?;
+    let decoded: T = row.try_get(0)?;
+
+    if decoded != value {
+        return Err(Error::Decode(format!("decoded as {decoded:?}").into()));
+    }
+
+    Ok(())
+}
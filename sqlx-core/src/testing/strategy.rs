@@ -0,0 +1,54 @@
+//! [`proptest`] strategies for the date and time types, within the bounds of a column type,
+//! for implementations of [`Roundtrip`][super::Roundtrip].
+
+#[cfg(feature = "chrono")]
+pub mod chrono {
+    use std::ops::RangeInclusive;
+
+    use chrono::{Datelike, NaiveDate, NaiveTime};
+    use proptest::strategy::Strategy;
+
+    /// Dates from January 1st of the first of `years` to December 31st of the last.
+    pub fn date(years: RangeInclusive<i32>) -> impl Strategy<Value = NaiveDate> {
+        let first = NaiveDate::from_ymd_opt(*years.start(), 1, 1).expect("year out of range");
+        let last = NaiveDate::from_ymd_opt(*years.end(), 12, 31).expect("year out of range");
+
+        (first.num_days_from_ce()..=last.num_days_from_ce()).prop_map(|days| {
+            NaiveDate::from_num_days_from_ce_opt(days).expect("BUG: day out of range")
+        })
+    }
+
+    /// Times of day in whole microseconds.
+    pub fn time_micros() -> impl Strategy<Value = NaiveTime> {
+        (0..86_400_u32, 0..1_000_000_u32).prop_map(|(secs, micros)| {
+            NaiveTime::from_num_seconds_from_midnight_opt(secs, micros * 1000)
+                .expect("BUG: time out of range")
+        })
+    }
+}
+
+#[cfg(feature = "time")]
+pub mod time {
+    use std::ops::RangeInclusive;
+
+    use proptest::strategy::Strategy;
+    use time::{Date, Month, Time};
+
+    /// Dates from January 1st of the first of `years` to December 31st of the last.
+    pub fn date(years: RangeInclusive<i32>) -> impl Strategy<Value = Date> {
+        let first =
+            Date::from_calendar_date(*years.start(), Month::January, 1).expect("year out of range");
+        let last =
+            Date::from_calendar_date(*years.end(), Month::December, 31).expect("year out of range");
+
+        (first.to_julian_day()..=last.to_julian_day())
+            .prop_map(|day| Date::from_julian_day(day).expect("BUG: day out of range"))
+    }
+
+    /// Times of day in whole microseconds.
+    pub fn time_micros() -> impl Strategy<Value = Time> {
+        (0..24_u8, 0..60_u8, 0..60_u8, 0..1_000_000_u32).prop_map(|(h, m, s, micros)| {
+            Time::from_hms_micro(h, m, s, micros).expect("BUG: time out of range")
+        })
+    }
+}
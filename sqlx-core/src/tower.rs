@@ -0,0 +1,246 @@
+//! [Tower] middleware running each request in its own transaction.
+//!
+//! [`TransactionLayer`] begins a transaction from a [`Pool`] before passing the request on and
+//! inserts a [`RequestTransaction`] handle into the request extensions. Once the inner service
+//! has produced a response, the transaction is committed if the response status is a success
+//! (`2xx`) or a redirection (`3xx`), and rolled back otherwise or if the service failed.
+//!
+//! If the transaction cannot be started or committed, the error is logged and an empty
+//! `500 Internal Server Error` response is returned instead.
+//!
+//! ### Example: Axum
+//! ```rust,no_run
+//! use axum::{routing::post, Extension, Router};
+//! use sqlx::postgres::{PgPool, Postgres};
+//! use sqlx_core::tower::{RequestTransaction, TransactionLayer};
+//!
+//! async fn create_user(Extension(tx): Extension<RequestTransaction<Postgres>>) -> String {
+//!     let mut tx = tx.lock().await.expect("request is still in flight");
+//!
+//!     sqlx::query("INSERT INTO users DEFAULT VALUES")
+//!         .execute(&mut **tx)
+//!         .await
+//!         .unwrap();
+//!
+//!     "created".into()
+//! }
+//!
+//! fn app(pool: PgPool) -> Router {
+//!     Router::new()
+//!         .route("/users", post(create_user))
+//!         .layer(TransactionLayer::new(pool))
+//! }
+//! ```
+//!
+//! [Tower]: https://docs.rs/tower
+
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_core::future::BoxFuture;
+use http::{Request, Response, StatusCode};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::database::Database;
+use crate::pool::Pool;
+use crate::sync::{AsyncMutex, AsyncMutexGuard};
+use crate::transaction::Transaction;
+
+/// A [`Layer`] wrapping services in a [`TransactionService`].
+///
+/// See [the module documentation](self) for details.
+pub struct TransactionLayer<DB: Database> {
+    pool: Pool<DB>,
+}
+
+impl<DB: Database> TransactionLayer<DB> {
+    /// Create a layer which begins each request's transaction from the given pool.
+    pub fn new(pool: Pool<DB>) -> Self {
+        Self { pool }
+    }
+}
+
+impl<DB: Database> Clone for TransactionLayer<DB> {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+impl<DB: Database> Debug for TransactionLayer<DB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransactionLayer")
+            .field("pool", &self.pool)
+            .finish()
+    }
+}
+
+impl<S, DB: Database> Layer<S> for TransactionLayer<DB> {
+    type Service = TransactionService<S, DB>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TransactionService {
+            inner,
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+/// A [`Service`] running each request in a transaction; created by [`TransactionLayer`].
+pub struct TransactionService<S, DB: Database> {
+    inner: S,
+    pool: Pool<DB>,
+}
+
+impl<S: Clone, DB: Database> Clone for TransactionService<S, DB> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+impl<S: Debug, DB: Database> Debug for TransactionService<S, DB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransactionService")
+            .field("inner", &self.inner)
+            .field("pool", &self.pool)
+            .finish()
+    }
+}
+
+impl<S, DB, ReqBody, ResBody> Service<Request<ReqBody>> for TransactionService<S, DB>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    DB: Database,
+    ReqBody: Send + 'static,
+    ResBody: Default + Send,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        // The service that was driven to readiness is the one which must handle this request.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let pool = self.pool.clone();
+
+        Box::pin(async move {
+            let tx = match pool.begin().await {
+                Ok(tx) => RequestTransaction::new(tx),
+                Err(error) => {
+                    tracing::error!(%error, "failed to begin request transaction");
+                    return Ok(internal_server_error());
+                }
+            };
+
+            req.extensions_mut().insert(tx.clone());
+
+            // Dropping the transaction on error rolls it back.
+            let response = inner.call(req).await?;
+
+            // A handler may still hold the lock if it spawned a task using the transaction.
+            let taken = tx.slot.lock().await.take();
+
+            let Some(tx) = taken else {
+                return Ok(response);
+            };
+
+            let status = response.status();
+
+            let result = if status.is_success() || status.is_redirection() {
+                tx.commit().await
+            } else {
+                tx.rollback().await
+            };
+
+            match result {
+                Ok(()) => Ok(response),
+                Err(error) => {
+                    tracing::error!(%error, %status, "failed to finish request transaction");
+                    Ok(internal_server_error())
+                }
+            }
+        })
+    }
+}
+
+fn internal_server_error<B: Default>() -> Response<B> {
+    let mut response = Response::new(B::default());
+    *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+    response
+}
+
+/// Handle to the transaction of the current request, found in its extensions.
+///
+/// Cloning the handle is cheap; all clones refer to the same transaction.
+pub struct RequestTransaction<DB: Database> {
+    slot: Arc<AsyncMutex<Option<Transaction<'static, DB>>>>,
+}
+
+impl<DB: Database> RequestTransaction<DB> {
+    fn new(tx: Transaction<'static, DB>) -> Self {
+        Self {
+            slot: Arc::new(AsyncMutex::new(Some(tx))),
+        }
+    }
+
+    /// Lock the transaction for use, waiting for any other user of it to finish.
+    ///
+    /// Returns `None` once the request has completed and the transaction has been finished.
+    pub async fn lock(&self) -> Option<TransactionGuard<'_, DB>> {
+        let guard = self.slot.lock().await;
+
+        guard.is_some().then_some(TransactionGuard { guard })
+    }
+}
+
+impl<DB: Database> Clone for RequestTransaction<DB> {
+    fn clone(&self) -> Self {
+        Self {
+            slot: self.slot.clone(),
+        }
+    }
+}
+
+impl<DB: Database> Debug for RequestTransaction<DB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestTransaction").finish_non_exhaustive()
+    }
+}
+
+/// Exclusive access to the transaction of the current request.
+///
+/// Returned by [`RequestTransaction::lock()`].
+pub struct TransactionGuard<'a, DB: Database> {
+    guard: AsyncMutexGuard<'a, Option<Transaction<'static, DB>>>,
+}
+
+impl<DB: Database> Deref for TransactionGuard<'_, DB> {
+    type Target = Transaction<'static, DB>;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard
+            .as_ref()
+            .expect("BUG: guard created for finished transaction")
+    }
+}
+
+impl<DB: Database> DerefMut for TransactionGuard<'_, DB> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.guard
+            .as_mut()
+            .expect("BUG: guard created for finished transaction")
+    }
+}
@@ -1,12 +1,20 @@
 use std::borrow::Cow;
 use std::fmt::{self, Debug, Formatter};
 use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use futures_core::future::BoxFuture;
 
+use crate::arguments::{Arguments, ImmutableArguments};
+use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
+use crate::executor::Executor;
 use crate::pool::MaybePoolConnection;
+use crate::query::query_with;
+use crate::raw_sql::raw_sql;
+use crate::script::{self, Fragment, ScriptBind};
 
 /// Generic management of database transactions.
 ///
@@ -89,6 +97,9 @@ where
 {
     connection: MaybePoolConnection<'c, DB>,
     open: bool,
+    journal: Option<StatementJournal>,
+    // the journal of the connection before `journal` was attached, while it's attached
+    outer_journal: Option<Option<StatementJournal>>,
 }
 
 impl<'c, DB> Transaction<'c, DB>
@@ -103,30 +114,191 @@ where
         let mut conn = conn.into();
 
         Box::pin(async move {
-            DB::TransactionManager::begin(&mut conn, statement).await?;
+            // the `SAVEPOINT` of a nested transaction is not part of the enclosing journal
+            let journal = conn.set_statement_journal(None);
+            let res = DB::TransactionManager::begin(&mut conn, statement).await;
+            conn.set_statement_journal(journal);
+            res?;
 
             Ok(Self {
                 connection: conn,
                 open: true,
+                journal: None,
+                outer_journal: None,
             })
         })
     }
 
     /// Commits this transaction or savepoint.
     pub async fn commit(mut self) -> Result<(), Error> {
-        DB::TransactionManager::commit(&mut self.connection).await?;
+        self.detach_journal();
+        without_journal::<DB>(&mut self.connection, DB::TransactionManager::commit).await?;
         self.open = false;
 
+        if let Some(journal) = &self.journal {
+            journal.finish(false);
+        }
+
         Ok(())
     }
 
     /// Aborts this transaction or savepoint.
     pub async fn rollback(mut self) -> Result<(), Error> {
-        DB::TransactionManager::rollback(&mut self.connection).await?;
+        self.detach_journal();
+        without_journal::<DB>(&mut self.connection, DB::TransactionManager::rollback).await?;
         self.open = false;
 
+        if let Some(journal) = &self.journal {
+            journal.finish(true);
+        }
+
         Ok(())
     }
+
+    /// Start recording every statement executed in this transaction or savepoint, returning a
+    /// handle to the recorded statements.
+    ///
+    /// The handle stays usable after the transaction is gone, so when a transaction is rolled
+    /// back, either explicitly or by being dropped without a call to [`commit`][Self::commit],
+    /// [`StatementJournal::entries()`] gives the exact statements that were run before it.
+    /// Calling this again returns the same journal.
+    ///
+    /// Statements are recorded as they are sent, before any placeholders are substituted,
+    /// whether they succeed or not. The statements for beginning, committing and rolling back
+    /// transactions and savepoints are not recorded. While a savepoint opened in this transaction
+    /// has a journal of its own, the statements in it are only recorded there.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+    /// let mut tx = pool.begin().await?;
+    /// let journal = tx.journal();
+    ///
+    /// if let Err(e) = transfer(&mut tx).await {
+    ///     drop(tx);
+    ///
+    ///     for entry in journal.entries() {
+    ///         eprintln!("{} ({} rows)", entry.sql(), entry.rows_affected());
+    ///     }
+    ///
+    ///     return Err(e);
+    /// }
+    ///
+    /// tx.commit().await
+    /// # }
+    /// # async fn transfer(_: &mut sqlx::PgTransaction<'_>) -> sqlx::Result<()> { Ok(()) }
+    /// ```
+    pub fn journal(&mut self) -> StatementJournal {
+        if let Some(journal) = &self.journal {
+            return journal.clone();
+        }
+
+        let journal = StatementJournal::default();
+        let outer = self.connection.set_statement_journal(Some(journal.clone()));
+
+        self.outer_journal = Some(outer);
+        self.journal = Some(journal.clone());
+
+        journal
+    }
+
+    fn detach_journal(&mut self) {
+        if let Some(outer) = self.outer_journal.take() {
+            self.connection.set_statement_journal(outer);
+        }
+    }
+
+    /// Runs a script of several statements separated by semicolons in this transaction,
+    /// returning the combined result of all of them.
+    ///
+    /// The script may refer to `binds` as `$1`, `$2` and so on in any of its statements. Each
+    /// statement is rewritten to the placeholder syntax of the database and given only the
+    /// values it refers to, so this works for databases which don't support `$N` parameters
+    /// or don't allow several statements in a prepared statement. Statements without
+    /// parameters are executed as-is, without being prepared.
+    ///
+    /// Semicolons and `$N` in string literals, quoted identifiers, dollar-quoted strings and
+    /// comments are left alone. The statements are executed in order, stopping at the first
+    /// error, which leaves the transaction to be rolled back.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(mut tx: sqlx::Transaction<'_, sqlx::Postgres>) -> sqlx::Result<()> {
+    /// let cutoff = 90_i32;
+    /// let archived_by = "maintenance";
+    ///
+    /// tx.execute_script(
+    ///     "INSERT INTO archived_orders SELECT *, $2 FROM orders WHERE age_days > $1;
+    ///      DELETE FROM orders WHERE age_days > $1;",
+    ///     &[&cutoff, &archived_by],
+    /// )
+    /// .await?;
+    ///
+    /// tx.commit().await
+    /// # }
+    /// ```
+    pub async fn execute_script(
+        &mut self,
+        sql: &str,
+        binds: &[&dyn ScriptBind<DB>],
+    ) -> Result<DB::QueryResult, Error>
+    where
+        for<'e> &'e mut DB::Connection: Executor<'e, Database = DB>,
+    {
+        let mut result = DB::QueryResult::default();
+
+        for (index, statement) in script::split(sql).into_iter().enumerate() {
+            if let [Fragment::Sql(sql)] = statement[..] {
+                result.extend([raw_sql(sql).execute(&mut **self).await?]);
+                continue;
+            }
+
+            let mut query = String::new();
+            let mut arguments = DB::Arguments::default();
+
+            for fragment in &statement {
+                match *fragment {
+                    Fragment::Sql(sql) => query.push_str(sql),
+                    Fragment::Param(param) => {
+                        let bind =
+                            param
+                                .checked_sub(1)
+                                .and_then(|i| binds.get(i))
+                                .ok_or_else(|| {
+                                    Error::InvalidArgument(format!(
+                                        "statement {} of the script refers to ${param}, \
+                                     but {} binds were given",
+                                        index + 1,
+                                        binds.len()
+                                    ))
+                                })?;
+
+                        bind.add_to(&mut arguments).map_err(Error::Encode)?;
+                        arguments
+                            .format_placeholder(&mut query)
+                            .map_err(|e| Error::Encode(Box::new(e)))?;
+                    }
+                }
+            }
+
+            let done = query_with(&query, ImmutableArguments(arguments))
+                .execute(&mut **self)
+                .await?;
+
+            result.extend([done]);
+        }
+
+        Ok(result)
+    }
+}
+
+async fn without_journal<DB: Database>(
+    conn: &mut DB::Connection,
+    op: fn(&mut DB::Connection) -> BoxFuture<'_, Result<(), Error>>,
+) -> Result<(), Error> {
+    let journal = conn.set_statement_journal(None);
+    let res = op(conn).await;
+    conn.set_statement_journal(journal);
+
+    res
 }
 
 // NOTE: fails to compile due to lack of lazy normalization
@@ -263,6 +435,12 @@ where
 {
     fn drop(&mut self) {
         if self.open {
+            self.detach_journal();
+
+            if let Some(journal) = &self.journal {
+                journal.finish(true);
+            }
+
             // starts a rollback operation
 
             // what this does depends on the database but generally this means we queue a rollback
@@ -274,6 +452,80 @@ where
     }
 }
 
+/// The statements executed in a transaction, as recorded after a call to
+/// [`Transaction::journal()`].
+///
+/// This is a cheap handle to a shared list; clones refer to the same journal.
+#[derive(Debug, Clone, Default)]
+pub struct StatementJournal {
+    inner: Arc<Mutex<JournalState>>,
+}
+
+#[derive(Debug, Default)]
+struct JournalState {
+    entries: Vec<JournalEntry>,
+    rolled_back: bool,
+}
+
+impl StatementJournal {
+    /// The statements recorded so far, in the order they were executed.
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.lock().entries.clone()
+    }
+
+    /// Whether the transaction was rolled back, either explicitly or by being dropped without
+    /// being committed.
+    ///
+    /// This is `false` while the transaction is still in progress.
+    pub fn is_rolled_back(&self) -> bool {
+        self.lock().rolled_back
+    }
+
+    pub(crate) fn record(&self, entry: JournalEntry) {
+        self.lock().entries.push(entry);
+    }
+
+    fn finish(&self, rolled_back: bool) {
+        self.lock().rolled_back = rolled_back;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, JournalState> {
+        // a panic while the lock is held can't leave the journal half-updated
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A statement recorded in a [`StatementJournal`].
+#[derive(Debug, Clone)]
+pub struct JournalEntry {
+    pub(crate) sql: String,
+    pub(crate) rows_returned: u64,
+    pub(crate) rows_affected: u64,
+    pub(crate) elapsed: Duration,
+}
+
+impl JournalEntry {
+    /// The SQL of the statement, as it was sent to the database.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// The number of rows the statement returned.
+    pub fn rows_returned(&self) -> u64 {
+        self.rows_returned
+    }
+
+    /// The number of rows the statement inserted, updated or deleted.
+    pub fn rows_affected(&self) -> u64 {
+        self.rows_affected
+    }
+
+    /// How long the statement took, until its last row was received or it failed.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
 pub fn begin_ansi_transaction_sql(depth: usize) -> Cow<'static, str> {
     if depth == 0 {
         Cow::Borrowed("BEGIN")
@@ -37,6 +37,12 @@ pub trait TypeChecking: Database {
     /// if applicable.
     fn get_feature_gate(info: &Self::TypeInfo) -> Option<&'static str>;
 
+    /// Get the `TypeInfo` for a Rust type written as a query parameter override (`expr as T`),
+    /// if it is one of the types known to the macros.
+    ///
+    /// `rust_type` is matched as described by [`rust_type_matches()`].
+    fn type_info_for_rust_type(rust_type: &str) -> Option<Self::TypeInfo>;
+
     /// If `value` is a well-known type, decode and format it using `Debug`.
     ///
     /// If `value` is not a well-known type or could not be decoded, the reason is printed instead.
@@ -121,6 +127,22 @@ where
     }
 }
 
+/// Returns `true` if `written`, a Rust type from user code, names the type `known`
+/// from the list of types supported by the macros.
+///
+/// Whitespace is ignored, and `written` may leave out a leading part of the path of `known`,
+/// so `Uuid` and `types::Uuid` both match `sqlx::types::Uuid`.
+#[doc(hidden)]
+pub fn rust_type_matches(known: &str, written: &str) -> bool {
+    let known: String = known.chars().filter(|c| !c.is_whitespace()).collect();
+    let written: String = written.chars().filter(|c| !c.is_whitespace()).collect();
+
+    known == written
+        || known
+            .strip_suffix(&*written)
+            .is_some_and(|prefix| prefix.ends_with("::"))
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! select_input_type {
@@ -176,6 +198,22 @@ macro_rules! impl_type_checking {
                 $get_gate
             }
 
+            fn type_info_for_rust_type(rust_type: &str) -> Option<Self::TypeInfo> {
+                use $crate::type_checking::rust_type_matches;
+
+                match () {
+                    $(
+                        $(#[$meta])?
+                        _ if rust_type_matches(stringify!($ty), rust_type)
+                            $(|| rust_type_matches(stringify!($input), rust_type))? =>
+                        {
+                            Some(<$ty as sqlx_core::types::Type<$database>>::type_info())
+                        }
+                    )*
+                    _ => None
+                }
+            }
+
             fn fmt_value_debug(value: &Self::Value) -> $crate::type_checking::FmtValue<Self> {
                 use $crate::value::Value;
 
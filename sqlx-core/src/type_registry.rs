@@ -0,0 +1,178 @@
+//! Rust types for SQL types, registered by build scripts for the query macros.
+//!
+//! The query macros only know the Rust types of the SQL types supported by SQLx (see
+//! [`TypeChecking`][crate::type_checking::TypeChecking]). Other types, like domains, enums,
+//! or the types of extensions, need a type override such as `as "x: _"` in each query.
+//!
+//! Instead, the build script of a crate can write a [`TypeRegistry`] to `OUT_DIR`, and the
+//! macros expanded in that crate then use the registered Rust types for those SQL types, both
+//! for the output columns and for type-checking bind parameters. A registered type replaces
+//! the built-in mapping of a SQL type, if there is one.
+//!
+//! ```rust,no_run
+//! // build.rs
+//! use sqlx_core::database_url::DatabaseKind;
+//! use sqlx_core::type_registry::TypeRegistry;
+//!
+//! fn main() -> std::io::Result<()> {
+//!     TypeRegistry::new()
+//!         .add(DatabaseKind::Postgres, "citext", "crate::types::CiText")
+//!         .add(DatabaseKind::Postgres, "email", "crate::types::Email")
+//!         .write()
+//! }
+//! ```
+//!
+//! Crates providing such types can export a function adding their mappings, to be chained
+//! in the build scripts of the crates using them:
+//!
+//! ```rust
+//! use sqlx_core::database_url::DatabaseKind;
+//! use sqlx_core::type_registry::TypeRegistry;
+//!
+//! pub fn register_types(registry: TypeRegistry) -> TypeRegistry {
+//!     registry.add(DatabaseKind::Postgres, "ltree", "::my_types::LTree")
+//! }
+//! ```
+//!
+//! The Rust types are written as they would be in the crate expanding the macros, so they
+//! should be absolute paths.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ConfigError;
+use crate::database_url::DatabaseKind;
+
+/// The name of the registry file in `OUT_DIR`.
+pub const FILE_NAME: &str = "sqlx-types.toml";
+
+/// Rust types for SQL types, by database.
+///
+/// SQL types are matched case-insensitively against the name of the type described by the
+/// database.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TypeRegistry {
+    postgres: BTreeMap<String, String>,
+    mysql: BTreeMap<String, String>,
+    sqlite: BTreeMap<String, String>,
+}
+
+impl TypeRegistry {
+    /// An empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `rust_type` for the SQL type `sql_type` of `database`.
+    pub fn add(mut self, database: DatabaseKind, sql_type: &str, rust_type: &str) -> Self {
+        self.types_mut(database)
+            .insert(sql_type.to_ascii_lowercase(), rust_type.to_owned());
+        self
+    }
+
+    /// The Rust type registered for the SQL type `sql_type` of `database`, if any.
+    pub fn rust_type(&self, database: DatabaseKind, sql_type: &str) -> Option<&str> {
+        self.types(database)
+            .get(&sql_type.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    /// Write the registry to [`FILE_NAME`] in `OUT_DIR`, for the macros expanded in the crate
+    /// of the calling build script.
+    pub fn write(&self) -> std::io::Result<()> {
+        let out_dir = std::env::var_os("OUT_DIR").ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "OUT_DIR is not set; TypeRegistry::write() must be called from a build script",
+            )
+        })?;
+
+        self.write_to(&Path::new(&out_dir).join(FILE_NAME))
+    }
+
+    /// Write the registry to `path`.
+    pub fn write_to(&self, path: &Path) -> std::io::Result<()> {
+        let contents = toml::to_string(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        std::fs::write(path, contents)
+    }
+
+    /// Read the registry written to `out_dir` by [`write()`][Self::write], or `None` if there
+    /// is none.
+    pub fn find(out_dir: &Path) -> Result<Option<TypeRegistry>, ConfigError> {
+        let path = out_dir.join(FILE_NAME);
+
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        TypeRegistry::from_path(&path).map(Some)
+    }
+
+    /// Read a registry file.
+    pub fn from_path(path: &Path) -> Result<TypeRegistry, ConfigError> {
+        let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        toml::from_str(&contents).map_err(|source| ConfigError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    fn types(&self, database: DatabaseKind) -> &BTreeMap<String, String> {
+        match database {
+            DatabaseKind::Postgres => &self.postgres,
+            DatabaseKind::MySql => &self.mysql,
+            DatabaseKind::Sqlite => &self.sqlite,
+        }
+    }
+
+    fn types_mut(&mut self, database: DatabaseKind) -> &mut BTreeMap<String, String> {
+        match database {
+            DatabaseKind::Postgres => &mut self.postgres,
+            DatabaseKind::MySql => &mut self.mysql,
+            DatabaseKind::Sqlite => &mut self.sqlite,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TypeRegistry;
+    use crate::database_url::DatabaseKind;
+
+    #[test]
+    fn registry_roundtrip() {
+        let registry = TypeRegistry::new()
+            .add(DatabaseKind::Postgres, "CITEXT", "crate::types::CiText")
+            .add(DatabaseKind::Sqlite, "money", "crate::types::Money");
+
+        assert_eq!(
+            registry.rust_type(DatabaseKind::Postgres, "citext"),
+            Some("crate::types::CiText")
+        );
+        assert_eq!(registry.rust_type(DatabaseKind::MySql, "citext"), None);
+        assert_eq!(
+            registry.rust_type(DatabaseKind::Sqlite, "MONEY"),
+            Some("crate::types::Money")
+        );
+
+        let dir = std::env::temp_dir().join(format!("sqlx-type-registry-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        assert_eq!(TypeRegistry::find(&dir).unwrap(), None);
+
+        registry.write_to(&dir.join(super::FILE_NAME)).unwrap();
+
+        assert_eq!(TypeRegistry::find(&dir).unwrap(), Some(registry));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
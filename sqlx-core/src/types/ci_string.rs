@@ -0,0 +1,177 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// A string which compares, hashes and orders case-insensitively.
+///
+/// Use it for values like email addresses or usernames which the database already treats as
+/// case-insensitive, so that a `HashSet<CiString>` or a `BTreeMap<CiString, _>` agrees with a
+/// `UNIQUE` constraint about which values are duplicates:
+///
+/// * Postgres: the `citext` type from the [`citext`] extension, which must be enabled with
+///   `CREATE EXTENSION citext`.
+/// * MySQL: any text type with a case-insensitive collation (the ones ending in `_ci`,
+///   which includes the default collations).
+/// * SQLite: `TEXT` columns declared with `COLLATE NOCASE`.
+///
+/// The value is passed to and from the database unchanged, preserving its case.
+///
+/// ```rust
+/// use sqlx::types::CiString;
+///
+/// assert_eq!(CiString::from("Alice@Example.com"), "alice@example.com");
+/// assert!(CiString::from("bob") > CiString::from("ALICE"));
+/// ```
+///
+/// ### Note: Case Folding
+/// Strings are compared by the lowercase mapping of their characters as defined by Unicode,
+/// without regard to locale. This matches `citext` under most locales, but not every collation
+/// of every database: SQLite's `NOCASE` only folds ASCII letters, and MySQL's accent-insensitive
+/// collations (`_ai_ci`) also consider `é` and `e` equal.
+///
+/// [`citext`]: https://www.postgresql.org/docs/current/citext.html
+#[derive(Clone, Default)]
+pub struct CiString(pub String);
+
+impl CiString {
+    /// Extract the inner value.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+
+    fn folded(&self) -> impl Iterator<Item = char> + '_ {
+        fold(&self.0)
+    }
+}
+
+fn fold(s: &str) -> impl Iterator<Item = char> + '_ {
+    s.chars().flat_map(char::to_lowercase)
+}
+
+impl From<String> for CiString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for CiString {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
+impl From<CiString> for String {
+    fn from(value: CiString) -> Self {
+        value.0
+    }
+}
+
+impl FromStr for CiString {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.to_owned()))
+    }
+}
+
+impl Deref for CiString {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<str> for CiString {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Debug for CiString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl Display for CiString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PartialEq for CiString {
+    fn eq(&self, other: &Self) -> bool {
+        self.folded().eq(other.folded())
+    }
+}
+
+impl Eq for CiString {}
+
+impl PartialEq<str> for CiString {
+    fn eq(&self, other: &str) -> bool {
+        self.folded().eq(fold(other))
+    }
+}
+
+impl PartialEq<&str> for CiString {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl PartialEq<String> for CiString {
+    fn eq(&self, other: &String) -> bool {
+        *self == **other
+    }
+}
+
+impl PartialOrd for CiString {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CiString {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.folded().cmp(other.folded())
+    }
+}
+
+impl Hash for CiString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for c in self.folded() {
+            c.hash(state);
+        }
+
+        // like `str`, so that hashes of consecutive values don't run together
+        state.write_u8(0xff);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_ci_string() {
+        assert_eq!(CiString::from("ΑΒΓ"), "αβγ");
+        assert_eq!(CiString::from("ÀB"), CiString::from("àb"));
+        assert_ne!(CiString::from("a"), CiString::from("ab"));
+        assert_eq!(
+            CiString::from("a").cmp(&CiString::from("B")),
+            Ordering::Less
+        );
+
+        let set: HashSet<_> = ["Alice", "ALICE", "alice", "Bob"]
+            .into_iter()
+            .map(CiString::from)
+            .collect();
+        assert_eq!(set.len(), 2);
+
+        assert_eq!(format!("{:?}", CiString::from("Alice")), "\"Alice\"");
+    }
+}
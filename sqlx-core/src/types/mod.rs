@@ -21,6 +21,7 @@ use crate::database::Database;
 use crate::type_info::TypeInfo;
 
 mod non_zero;
+mod overflow;
 
 #[cfg(feature = "bstr")]
 #[cfg_attr(docsrs, doc(cfg(feature = "bstr")))]
@@ -30,13 +31,19 @@ pub mod bstr;
 #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
 mod json;
 
+mod ci_string;
 mod text;
+mod vector;
 
 #[cfg(feature = "uuid")]
 #[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
 #[doc(no_inline)]
 pub use uuid::{self, Uuid};
 
+#[cfg(feature = "uuid-v7")]
+#[cfg_attr(docsrs, doc(cfg(feature = "uuid-v7")))]
+pub mod uuid_v7;
+
 #[cfg(feature = "chrono")]
 #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
 pub mod chrono {
@@ -81,9 +88,12 @@ pub mod mac_address {
     pub use mac_address::MacAddress;
 }
 
+pub use ci_string::CiString;
 #[cfg(feature = "json")]
 pub use json::{Json, JsonRawValue, JsonValue};
+pub use overflow::{Checked, Saturating};
 pub use text::Text;
+pub use vector::{HasVectorDistance, Vector, VectorDistance};
 
 #[cfg(feature = "bstr")]
 pub use bstr::{BStr, BString};
@@ -0,0 +1,160 @@
+//! Adapters for decoding integers that may not fit in the requested Rust type.
+
+use std::ops::{Deref, DerefMut};
+
+use crate::database::Database;
+use crate::decode::Decode;
+use crate::error::BoxDynError;
+use crate::types::Type;
+
+/// Decode an integer column into `T`, clamping values outside its range to `T::MIN` or `T::MAX`.
+///
+/// Decoding a value that does not fit into a plain integer type returns
+/// [`IntegerOutOfRangeError`](crate::error::IntegerOutOfRangeError). This adapter is meant
+/// for ETL and reporting code, where an approximate value is preferable to a failed row.
+///
+/// Any signed integer column can be decoded, regardless of its width.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::types::Saturating;
+///
+/// let Saturating(count): Saturating<i16> = sqlx::query_scalar("SELECT 100000::BIGINT")
+///     .fetch_one(conn)
+///     .await?;
+///
+/// assert_eq!(count, i16::MAX);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Saturating<T>(pub T);
+
+/// Decode an integer column into `T`, or `None` if the value is outside its range.
+///
+/// Unlike decoding into `T` directly, an out-of-range value is not an error. `NULL` is still
+/// an error, unless this is wrapped in an `Option`.
+///
+/// Any signed integer column can be decoded, regardless of its width.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::types::Checked;
+///
+/// let Checked(count): Checked<i16> = sqlx::query_scalar("SELECT 100000::BIGINT")
+///     .fetch_one(conn)
+///     .await?;
+///
+/// assert_eq!(count, None);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checked<T>(pub Option<T>);
+
+impl<T> Saturating<T> {
+    /// Extract the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Checked<T> {
+    /// Extract the inner value.
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for Saturating<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Saturating<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+// Compatible with any of the signed integer types, which are all decoded through `i64`.
+fn int_compatible<DB>(ty: &DB::TypeInfo) -> bool
+where
+    DB: Database,
+    i16: Type<DB>,
+    i32: Type<DB>,
+    i64: Type<DB>,
+{
+    <i16 as Type<DB>>::compatible(ty)
+        || <i32 as Type<DB>>::compatible(ty)
+        || <i64 as Type<DB>>::compatible(ty)
+}
+
+macro_rules! impl_overflow {
+    ($($int:ty),* $(,)?) => {
+        $(impl<DB> Type<DB> for Saturating<$int>
+        where
+            DB: Database,
+            i16: Type<DB>,
+            i32: Type<DB>,
+            i64: Type<DB>,
+        {
+            fn type_info() -> <DB as Database>::TypeInfo {
+                <i64 as Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &<DB as Database>::TypeInfo) -> bool {
+                int_compatible::<DB>(ty)
+            }
+        }
+
+        impl<'r, DB> Decode<'r, DB> for Saturating<$int>
+        where
+            DB: Database,
+            i64: Decode<'r, DB>,
+        {
+            fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+                let int = <i64 as Decode<'r, DB>>::decode(value)?;
+
+                Ok(Saturating(<$int>::try_from(int).unwrap_or(if int < 0 {
+                    <$int>::MIN
+                } else {
+                    <$int>::MAX
+                })))
+            }
+        }
+
+        impl<DB> Type<DB> for Checked<$int>
+        where
+            DB: Database,
+            i16: Type<DB>,
+            i32: Type<DB>,
+            i64: Type<DB>,
+        {
+            fn type_info() -> <DB as Database>::TypeInfo {
+                <i64 as Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &<DB as Database>::TypeInfo) -> bool {
+                int_compatible::<DB>(ty)
+            }
+        }
+
+        impl<'r, DB> Decode<'r, DB> for Checked<$int>
+        where
+            DB: Database,
+            i64: Decode<'r, DB>,
+        {
+            fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+                let int = <i64 as Decode<'r, DB>>::decode(value)?;
+
+                Ok(Checked(<$int>::try_from(int).ok()))
+            }
+        })*
+    };
+}
+
+impl_overflow!(i8, i16, i32, i64, u8, u16, u32, u64);
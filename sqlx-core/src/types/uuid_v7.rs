@@ -0,0 +1,163 @@
+//! Generation of time-ordered UUIDs ([version 7]).
+//!
+//! A UUIDv7 starts with the number of milliseconds since the Unix epoch, so new values are
+//! always inserted at the end of a B-tree index instead of at random positions like UUIDv4,
+//! which keeps primary keys compact and inserts fast.
+//!
+//! ```rust
+//! use sqlx::types::uuid_v7;
+//!
+//! let first = uuid_v7::new();
+//! let second = uuid_v7::new();
+//!
+//! assert!(first < second);
+//! assert_eq!(first.get_version_num(), 7);
+//! ```
+//!
+//! They are stored like any other [`Uuid`]: as `UUID` in PostgreSQL, `BINARY(16)` in MySQL and
+//! `BLOB` in SQLite, which all compare in the same order as the bytes of the UUID.
+//!
+//! To give a key to records which may not have one yet, bind them with [`OrNew`], which
+//! generates a UUIDv7 when the value is bound for insertion:
+//!
+//! ```rust,ignore
+//! let mut builder = QueryBuilder::new("INSERT INTO orders (id, customer) ");
+//!
+//! builder.push_values(&orders, |mut b, order| {
+//!     b.push_bind(uuid_v7::OrNew(order.id)).push_bind(&order.customer);
+//! });
+//! ```
+//!
+//! The query macros use `Uuid` for `UUID` columns in PostgreSQL and SQLite; in MySQL, a
+//! `BINARY(16)` column is typed as `Vec<u8>` unless overridden, e.g. `SELECT id AS "id: Uuid"`.
+//!
+//! [version 7]: https://www.rfc-editor.org/rfc/rfc9562#section-5.7
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use uuid::{NoContext, Timestamp, Uuid};
+
+use crate::database::Database;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+
+/// Generate a UUIDv7 for the current time.
+///
+/// UUIDs generated by the same process are ordered by their creation, even within the same
+/// millisecond.
+pub fn new() -> Uuid {
+    Uuid::now_v7()
+}
+
+/// Generate a UUIDv7 for the given time, e.g. to backfill the keys of existing rows from their
+/// creation date.
+///
+/// Times before the Unix epoch are clamped to it.
+pub fn at(time: SystemTime) -> Uuid {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+
+    Uuid::new_v7(Timestamp::from_unix(
+        NoContext,
+        since_epoch.as_secs(),
+        since_epoch.subsec_nanos(),
+    ))
+}
+
+/// The smallest UUIDv7 for the given time, with every random bit set to zero.
+///
+/// All UUIDv7s generated at or after `time` compare greater than or equal to this, so it can be
+/// bound as the lower bound of a range query on a key column:
+///
+/// ```rust,ignore
+/// let since = SystemTime::now() - Duration::from_secs(3600);
+///
+/// let recent = sqlx::query_as::<_, Event>("SELECT * FROM events WHERE id >= $1")
+///     .bind(uuid_v7::lower_bound(since))
+///     .fetch_all(&pool)
+///     .await?;
+/// ```
+pub fn lower_bound(time: SystemTime) -> Uuid {
+    let millis = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+
+    uuid::Builder::from_unix_timestamp_millis(u64::try_from(millis).unwrap_or(u64::MAX), &[0; 10])
+        .into_uuid()
+}
+
+/// The time embedded in a UUIDv7, with millisecond precision.
+///
+/// Returns `None` if `uuid` is not a UUIDv7.
+pub fn timestamp(uuid: &Uuid) -> Option<SystemTime> {
+    if uuid.get_version_num() != 7 {
+        return None;
+    }
+
+    let (secs, nanos) = uuid.get_timestamp()?.to_unix();
+
+    Some(UNIX_EPOCH + Duration::new(secs, nanos))
+}
+
+/// Binds the UUID it holds, or a new UUIDv7 generated as it is bound if it holds `None`.
+///
+/// This gives a key to records that don't have one yet, such as those read from a staging
+/// table or an import, as they are inserted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct OrNew(pub Option<Uuid>);
+
+impl<DB: Database> Type<DB> for OrNew
+where
+    Uuid: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <Uuid as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <Uuid as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for OrNew
+where
+    Uuid: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        self.0.unwrap_or_else(Uuid::now_v7).encode_by_ref(buf)
+    }
+
+    fn produces(&self) -> Option<DB::TypeInfo> {
+        self.0.unwrap_or_else(Uuid::nil).produces()
+    }
+
+    fn size_hint(&self) -> usize {
+        self.0.unwrap_or_else(Uuid::nil).size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uuid_v7() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+
+        let uuid = at(time);
+        assert_eq!(uuid.get_version_num(), 7);
+        assert_eq!(timestamp(&uuid), Some(time));
+
+        let bound = lower_bound(time);
+        assert_eq!(timestamp(&bound), Some(time));
+        assert!(bound <= uuid);
+        assert!(lower_bound(time + Duration::from_millis(1)) > uuid);
+
+        assert!(new() < new());
+        assert_eq!(timestamp(&Uuid::nil()), None);
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+use crate::database::Database;
+use crate::error::BoxDynError;
+
+/// A vector of single-precision floats, e.g. an embedding for similarity search.
+///
+/// Supported by Postgres with the [`pgvector`] extension, as `VECTOR(n)`, and by SQLite
+/// with [`sqlite-vec`], as a `BLOB` of little-endian floats or text like `[1,2,3]`.
+///
+/// The text format, used by [`Display`] and [`FromStr`], is the one both extensions accept:
+/// a comma-separated list in square brackets.
+///
+/// To order rows by their distance to a vector, see [`QueryBuilder::push_vector_distance()`].
+///
+/// [`pgvector`]: https://github.com/pgvector/pgvector
+/// [`sqlite-vec`]: https://github.com/asg017/sqlite-vec
+/// [`QueryBuilder::push_vector_distance()`]: crate::query_builder::QueryBuilder::push_vector_distance
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Vector(pub Vec<f32>);
+
+impl Vector {
+    /// Extract the inner value.
+    pub fn into_inner(self) -> Vec<f32> {
+        self.0
+    }
+}
+
+impl From<Vec<f32>> for Vector {
+    fn from(value: Vec<f32>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&[f32]> for Vector {
+    fn from(value: &[f32]) -> Self {
+        Self(value.to_vec())
+    }
+}
+
+impl From<Vector> for Vec<f32> {
+    fn from(value: Vector) -> Self {
+        value.0
+    }
+}
+
+impl Deref for Vector {
+    type Target = Vec<f32>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Vector {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Display for Vector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+
+        for (i, value) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+
+            write!(f, "{value}")?;
+        }
+
+        f.write_str("]")
+    }
+}
+
+impl FromStr for Vector {
+    type Err = BoxDynError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = s
+            .trim()
+            .strip_prefix('[')
+            .and_then(|s| s.strip_suffix(']'))
+            .ok_or_else(|| format!("expected a vector in square brackets, got {s:?}"))?;
+
+        if inner.trim().is_empty() {
+            return Ok(Self::default());
+        }
+
+        inner
+            .split(',')
+            .map(|value| {
+                value
+                    .trim()
+                    .parse::<f32>()
+                    .map_err(|e| format!("invalid vector element {value:?}: {e}").into())
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// A measure of the distance between two [`Vector`]s.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum VectorDistance {
+    /// Euclidean distance.
+    L2,
+    /// Cosine distance: one minus the cosine similarity.
+    Cosine,
+    /// The negated inner product, so that more similar vectors are closer, as with the other
+    /// distances.
+    InnerProduct,
+    /// Manhattan or taxicab distance.
+    L1,
+}
+
+/// A database which can compute the distance between [`Vector`]s, for
+/// [`QueryBuilder::push_vector_distance()`][crate::query_builder::QueryBuilder::push_vector_distance].
+pub trait HasVectorDistance: Database {
+    /// The SQL before, between and after the two vectors to compute `distance`,
+    /// or `None` if it is not supported.
+    fn vector_distance_sql(
+        distance: VectorDistance,
+    ) -> Option<(&'static str, &'static str, &'static str)>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_text() {
+        let vector: Vector = "[1, 2.5,-3e-2]".parse().unwrap();
+        assert_eq!(vector, Vector(vec![1.0, 2.5, -0.03]));
+        assert_eq!(vector.to_string(), "[1,2.5,-0.03]");
+
+        assert_eq!("[]".parse::<Vector>().unwrap(), Vector::default());
+        assert!("1,2".parse::<Vector>().is_err());
+        assert!("[1,x]".parse::<Vector>().is_err());
+    }
+}
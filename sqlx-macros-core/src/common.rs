@@ -35,3 +35,58 @@ pub(crate) fn resolve_path(path: impl AsRef<Path>, err_span: Span) -> syn::Resul
 
     Ok(base_dir_path.join(path))
 }
+
+/// The `sqlx.toml` nearest to `CARGO_MANIFEST_DIR`, if there is one, along with its path.
+#[cfg(any(feature = "macros", feature = "migrate"))]
+pub(crate) fn config() -> Result<Option<&'static (PathBuf, sqlx_core::config::Config)>, String> {
+    use once_cell::sync::Lazy;
+    use sqlx_core::config::Config;
+
+    static CONFIG: Lazy<Result<Option<(PathBuf, Config)>, String>> = Lazy::new(|| {
+        let manifest_dir = env::var("CARGO_MANIFEST_DIR")
+            .map_err(|_| "CARGO_MANIFEST_DIR is not set; please use Cargo to build".to_string())?;
+
+        Config::find(Path::new(&manifest_dir)).map_err(|e| e.to_string())
+    });
+
+    CONFIG.as_ref().map(Option::as_ref).map_err(Clone::clone)
+}
+
+/// The types registered by the build script of the crate in `OUT_DIR`, if it wrote any.
+#[cfg(feature = "macros")]
+pub(crate) fn type_registry(
+) -> Result<Option<&'static sqlx_core::type_registry::TypeRegistry>, String> {
+    use once_cell::sync::Lazy;
+    use sqlx_core::type_registry::TypeRegistry;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    type Cached = Result<Option<&'static TypeRegistry>, String>;
+
+    // keyed by `OUT_DIR`, as the same process may expand the macros of several crates
+    static REGISTRIES: Lazy<Mutex<HashMap<PathBuf, Cached>>> = Lazy::new(Default::default);
+
+    // only set for crates with a build script
+    let Some(out_dir) = env::var_os("OUT_DIR") else {
+        return Ok(None);
+    };
+
+    let mut registries = REGISTRIES
+        .lock()
+        // Just reset the cache on error
+        .unwrap_or_else(|poison_err| {
+            let mut guard = poison_err.into_inner();
+            *guard = Default::default();
+            guard
+        });
+
+    registries
+        .entry(PathBuf::from(out_dir))
+        .or_insert_with_key(|out_dir| {
+            // leaked once per crate, to be borrowed by the expansion of every macro in it
+            TypeRegistry::find(out_dir)
+                .map(|registry| registry.map(|registry| &*Box::leak(Box::new(registry))))
+                .map_err(|e| e.to_string())
+        })
+        .clone()
+}
@@ -2,11 +2,19 @@ macro_rules! impl_database_ext {
     (
         $database:path,
         row: $row:path,
+        $(unspecified-param-type: $unspecified:expr,)?
         $(describe-blocking: $describe:path,)?
     ) => {
         impl $crate::database::DatabaseExt for $database {
             const DATABASE_PATH: &'static str = stringify!($database);
             const ROW_PATH: &'static str = stringify!($row);
+
+            $(
+                fn unspecified_param_type() -> Option<Self::TypeInfo> {
+                    Some($unspecified)
+                }
+            )?
+
             impl_describe_blocking!($database, $($describe)?);
         }
     }
@@ -16,6 +24,7 @@ macro_rules! impl_describe_blocking {
     ($database:path $(,)?) => {
         fn describe_blocking(
             query: &str,
+            parameters: &[<Self as sqlx_core::database::Database>::TypeInfo],
             database_url: &str,
         ) -> sqlx_core::Result<sqlx_core::describe::Describe<Self>> {
             use $crate::database::CachingDescribeBlocking;
@@ -23,12 +32,14 @@ macro_rules! impl_describe_blocking {
             // This can't be a provided method because the `static` can't reference `Self`.
             static CACHE: CachingDescribeBlocking<$database> = CachingDescribeBlocking::new();
 
-            CACHE.describe(query, database_url)
+            CACHE.describe(query, parameters, database_url)
         }
     };
     ($database:path, $describe:path) => {
         fn describe_blocking(
             query: &str,
+            // Declaring parameter types is only useful for PostgreSQL.
+            _parameters: &[<Self as sqlx_core::database::Database>::TypeInfo],
             database_url: &str,
         ) -> sqlx_core::Result<sqlx_core::describe::Describe<Self>> {
             $describe(query, database_url)
@@ -59,6 +70,8 @@ impl_database_ext! {
 impl_database_ext! {
     sqlx::postgres::Postgres,
     row: sqlx::postgres::PgRow,
+    // An OID of 0 leaves the type of the parameter for Postgres to infer.
+    unspecified-param-type: sqlx::postgres::PgTypeInfo::with_oid(sqlx::postgres::types::Oid(0)),
 }
 
 #[cfg(feature = "_sqlite")]
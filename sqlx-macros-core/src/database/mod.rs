@@ -25,7 +25,18 @@ pub trait DatabaseExt: Database + TypeChecking {
         syn::parse_str(Self::ROW_PATH).unwrap()
     }
 
-    fn describe_blocking(query: &str, database_url: &str) -> sqlx_core::Result<Describe<Self>>;
+    /// A type to declare for parameters whose type should be inferred by the database,
+    /// if it supports declaring the types of only some parameters.
+    fn unspecified_param_type() -> Option<Self::TypeInfo> {
+        None
+    }
+
+    /// Describe `query`, declaring the types of its leading parameters as `parameters`.
+    fn describe_blocking(
+        query: &str,
+        parameters: &[Self::TypeInfo],
+        database_url: &str,
+    ) -> sqlx_core::Result<Describe<Self>>;
 }
 
 #[allow(dead_code)]
@@ -41,7 +52,12 @@ impl<DB: DatabaseExt> CachingDescribeBlocking<DB> {
         }
     }
 
-    pub fn describe(&self, query: &str, database_url: &str) -> sqlx_core::Result<Describe<DB>>
+    pub fn describe(
+        &self,
+        query: &str,
+        parameters: &[DB::TypeInfo],
+        database_url: &str,
+    ) -> sqlx_core::Result<Describe<DB>>
     where
         for<'a> &'a mut DB::Connection: Executor<'a, Database = DB>,
     {
@@ -58,7 +74,7 @@ impl<DB: DatabaseExt> CachingDescribeBlocking<DB> {
                 }
             };
 
-            conn.describe(query).await
+            conn.describe_with(query, parameters).await
         })
     }
 }
@@ -59,6 +59,7 @@ pub struct SqlxContainerAttributes {
     pub repr: Option<Ident>,
     pub no_pg_array: bool,
     pub default: bool,
+    pub case_insensitive: bool,
 }
 
 pub enum JsonAttribute {
@@ -68,7 +69,9 @@ pub enum JsonAttribute {
 
 pub struct SqlxChildAttributes {
     pub rename: Option<String>,
+    pub aliases: Vec<String>,
     pub default: bool,
+    pub default_on_null: bool,
     pub flatten: bool,
     pub try_from: Option<Type>,
     pub skip: bool,
@@ -82,6 +85,7 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
     let mut rename_all = None;
     let mut no_pg_array = None;
     let mut default = None;
+    let mut case_insensitive = None;
 
     for attr in input {
         if attr.path().is_ident("sqlx") {
@@ -92,6 +96,8 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
                     try_set!(no_pg_array, true, attr);
                 } else if meta.path.is_ident("default") {
                     try_set!(default, true, attr);
+                } else if meta.path.is_ident("case_insensitive") {
+                    try_set!(case_insensitive, true, attr);
                 } else if meta.path.is_ident("rename_all") {
                     meta.input.parse::<Token![=]>()?;
                     let lit: LitStr = meta.input.parse()?;
@@ -140,12 +146,15 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
         rename_all,
         no_pg_array: no_pg_array.unwrap_or(false),
         default: default.unwrap_or(false),
+        case_insensitive: case_insensitive.unwrap_or(false),
     })
 }
 
 pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttributes> {
     let mut rename = None;
+    let mut aliases = Vec::new();
     let mut default = false;
+    let mut default_on_null = false;
     let mut try_from = None;
     let mut flatten = false;
     let mut skip: bool = false;
@@ -157,12 +166,18 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
                 meta.input.parse::<Token![=]>()?;
                 let val: LitStr = meta.input.parse()?;
                 try_set!(rename, val.value(), val);
+            } else if meta.path.is_ident("alias") {
+                meta.input.parse::<Token![=]>()?;
+                let val: LitStr = meta.input.parse()?;
+                aliases.push(val.value());
             } else if meta.path.is_ident("try_from") {
                 meta.input.parse::<Token![=]>()?;
                 let val: LitStr = meta.input.parse()?;
                 try_set!(try_from, val.parse()?, val);
             } else if meta.path.is_ident("default") {
                 default = true;
+            } else if meta.path.is_ident("default_on_null") {
+                default_on_null = true;
             } else if meta.path.is_ident("flatten") {
                 flatten = true;
             } else if meta.path.is_ident("skip") {
@@ -188,11 +203,20 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
                 "Cannot use `json` and `flatten` together on the same field"
             );
         }
+
+        if default_on_null && (flatten || json.is_some()) {
+            fail!(
+                attr,
+                "Cannot use `default_on_null` together with `flatten` or `json` on the same field"
+            );
+        }
     }
 
     Ok(SqlxChildAttributes {
         rename,
+        aliases,
         default,
+        default_on_null,
         flatten,
         try_from,
         skip,
@@ -97,6 +97,55 @@ fn expand_derive_from_row_struct(
                 }
             };
 
+            let col: Expr = if attributes.aliases.is_empty() && !container_attributes.case_insensitive {
+                parse_quote!(#id_s)
+            } else {
+                let aliases = &attributes.aliases;
+                let case_insensitive = container_attributes.case_insensitive;
+
+                parse_quote!(
+                    ::sqlx::__resolve_column_name(__row, &[#id_s, #(#aliases),*], #case_insensitive)
+                )
+            };
+
+            if attributes.default_on_null {
+                let fallback: Expr = if attributes.default || !container_attributes.default {
+                    parse_quote!(::std::default::Default::default())
+                } else {
+                    parse_quote!(__default.#id)
+                };
+
+                let decoded = attributes.try_from.as_ref().unwrap_or(ty);
+
+                predicates
+                    .push(parse_quote!(#decoded: ::sqlx::decode::Decode<#lifetime, R::Database>));
+                predicates.push(parse_quote!(#decoded: ::sqlx::types::Type<R::Database>));
+
+                let value: Expr = match &attributes.try_from {
+                    Some(try_from) => parse_quote!(
+                        <#ty as ::std::convert::TryFrom::<#try_from>>::try_from(v)
+                            .map_err(|e| {
+                                // Triggers a lint warning if `TryFrom::Err = Infallible`
+                                #[allow(unreachable_code)]
+                                ::sqlx::Error::ColumnDecode {
+                                    index: #id_s.to_string(),
+                                    source: sqlx::__spec_error!(e),
+                                }
+                            })?
+                    ),
+                    None => parse_quote!(v),
+                };
+
+                return Some(parse_quote!(
+                    let #id: #ty = match __row.try_get::<::std::option::Option<#decoded>, _>(#col) {
+                        ::std::result::Result::Ok(::std::option::Option::Some(v)) => #value,
+                        ::std::result::Result::Ok(::std::option::Option::None)
+                        | ::std::result::Result::Err(::sqlx::Error::ColumnNotFound(_)) => #fallback,
+                        ::std::result::Result::Err(e) => return ::std::result::Result::Err(e),
+                    };
+                ));
+            }
+
             let expr: Expr = match (attributes.flatten, attributes.try_from, attributes.json) {
                 // <No attributes>
                 (false, None, None) => {
@@ -104,7 +153,7 @@ fn expand_derive_from_row_struct(
                         .push(parse_quote!(#ty: ::sqlx::decode::Decode<#lifetime, R::Database>));
                     predicates.push(parse_quote!(#ty: ::sqlx::types::Type<R::Database>));
 
-                    parse_quote!(__row.try_get(#id_s))
+                    parse_quote!(__row.try_get(#col))
                 }
                 // Flatten
                 (true, None, None) => {
@@ -140,7 +189,7 @@ fn expand_derive_from_row_struct(
                     predicates.push(parse_quote!(#try_from: ::sqlx::types::Type<R::Database>)); 
 
                     parse_quote!(
-                        __row.try_get(#id_s)
+                        __row.try_get(#col)
                             .and_then(|v| {
                                 <#ty as ::std::convert::TryFrom::<#try_from>>::try_from(v)
                                     .map_err(|e| {
@@ -161,7 +210,7 @@ fn expand_derive_from_row_struct(
                     predicates.push(parse_quote!(::sqlx::types::Json<#try_from>: ::sqlx::types::Type<R::Database>));
 
                     parse_quote!(
-                        __row.try_get::<::sqlx::types::Json<_>, _>(#id_s)
+                        __row.try_get::<::sqlx::types::Json<_>, _>(#col)
                             .and_then(|v| {
                                 <#ty as ::std::convert::TryFrom::<#try_from>>::try_from(v.0)
                                     .map_err(|e| {
@@ -185,14 +234,14 @@ fn expand_derive_from_row_struct(
                         .push(parse_quote!(::sqlx::types::Json<#ty>: ::sqlx::decode::Decode<#lifetime, R::Database>));
                     predicates.push(parse_quote!(::sqlx::types::Json<#ty>: ::sqlx::types::Type<R::Database>));
 
-                    parse_quote!(__row.try_get::<::sqlx::types::Json<_>, _>(#id_s).map(|x| x.0))
+                    parse_quote!(__row.try_get::<::sqlx::types::Json<_>, _>(#col).map(|x| x.0))
                 },
                 (false, None, Some(JsonAttribute::Nullable)) => {
                     predicates
                         .push(parse_quote!(::core::option::Option<::sqlx::types::Json<#ty>>: ::sqlx::decode::Decode<#lifetime, R::Database>));
                     predicates.push(parse_quote!(::core::option::Option<::sqlx::types::Json<#ty>>: ::sqlx::types::Type<R::Database>));
 
-                    parse_quote!(__row.try_get::<::core::option::Option<::sqlx::types::Json<_>>, _>(#id_s).map(|x| x.and_then(|y| y.0)))
+                    parse_quote!(__row.try_get::<::core::option::Option<::sqlx::types::Json<_>>, _>(#col).map(|x| x.and_then(|y| y.0)))
                 },
             };
 
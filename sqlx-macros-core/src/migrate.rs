@@ -3,9 +3,10 @@ extern crate proc_macro;
 
 use std::path::{Path, PathBuf};
 
-use proc_macro2::TokenStream;
+use proc_macro2::{Literal, TokenStream};
 use quote::{quote, ToTokens, TokenStreamExt};
-use syn::LitStr;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitBool, LitStr, Token};
 
 use sqlx_core::migrate::{Migration, MigrationType};
 
@@ -19,14 +20,81 @@ impl ToTokens for QuoteMigrationType {
             MigrationType::ReversibleDown => {
                 quote! { ::sqlx::migrate::MigrationType::ReversibleDown }
             }
+            MigrationType::Repeatable => quote! { ::sqlx::migrate::MigrationType::Repeatable },
+            other => unreachable!("unsupported migration type {other:?}"),
         };
         tokens.append_all(ts);
     }
 }
 
+/// Input to `migrate!()`: the migrations directory, optionally followed by `key = value` options.
+///
+/// Without a directory, the one set in `sqlx.toml` is used, or else `./migrations`.
+pub struct MigrateInput {
+    dir: Option<LitStr>,
+    options: MigrateOptions,
+}
+
+#[derive(Default)]
+struct MigrateOptions {
+    /// `compress = true`: embed migrations deflate-compressed, to be decompressed when applied.
+    compress: bool,
+    /// `exclude = ["..."]`: skip migrations whose path relative to their directory matches.
+    exclude: Vec<glob::Pattern>,
+    /// `table_name = "..."`: track applied migrations in this table instead of `_sqlx_migrations`.
+    table_name: Option<LitStr>,
+}
+
+impl Parse for MigrateInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let dir = if input.is_empty() {
+            None
+        } else {
+            Some(input.parse()?)
+        };
+        let mut options = MigrateOptions::default();
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+
+            if input.is_empty() {
+                break;
+            }
+
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+
+            if key == "compress" {
+                options.compress = input.parse::<LitBool>()?.value;
+            } else if key == "exclude" {
+                let patterns;
+                syn::bracketed!(patterns in input);
+
+                for pattern in patterns.parse_terminated(<LitStr as Parse>::parse, Token![,])? {
+                    let parsed = glob::Pattern::new(&pattern.value()).map_err(|e| {
+                        syn::Error::new_spanned(&pattern, format!("invalid glob pattern: {e}"))
+                    })?;
+
+                    options.exclude.push(parsed);
+                }
+            } else if key == "table_name" {
+                options.table_name = Some(input.parse()?);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    key,
+                    "expected one of `compress`, `exclude` or `table_name`",
+                ));
+            }
+        }
+
+        Ok(MigrateInput { dir, options })
+    }
+}
+
 struct QuoteMigration {
     migration: Migration,
     path: PathBuf,
+    compress: bool,
 }
 
 impl ToTokens for QuoteMigration {
@@ -42,7 +110,7 @@ impl ToTokens for QuoteMigration {
 
         let migration_type = QuoteMigrationType(*migration_type);
 
-        let sql = self
+        let include = self
             .path
             .canonicalize()
             .map_err(|e| {
@@ -64,23 +132,65 @@ impl ToTokens for QuoteMigration {
             })
             .unwrap_or_else(|e| quote! { compile_error!(#e) });
 
+        let (sql, compressed_sql) = if self.compress {
+            let compressed = Literal::byte_string(&miniz_oxide::deflate::compress_to_vec(
+                self.migration
+                    .sql()
+                    .expect("BUG: migrations read from files are not compressed")
+                    .as_bytes(),
+                6,
+            ));
+
+            // The file is still included so it is watched, but never referenced so it is not
+            // embedded uncompressed as well.
+            (
+                quote! { { const _: &str = #include; "" } },
+                quote! { ::std::option::Option::Some(#compressed as &[u8]) },
+            )
+        } else {
+            (include, quote! { ::std::option::Option::None })
+        };
+
         let ts = quote! {
-            ::sqlx::migrate::Migration {
-                version: #version,
-                description: ::std::borrow::Cow::Borrowed(#description),
-                migration_type:  #migration_type,
-                sql: ::std::borrow::Cow::Borrowed(#sql),
-                no_tx: #no_tx,
-                checksum: ::std::borrow::Cow::Borrowed(&[
+            ::sqlx::migrate::Migration::embedded(
+                #version,
+                ::std::borrow::Cow::Borrowed(#description),
+                #migration_type,
+                ::std::borrow::Cow::Borrowed(#sql),
+                #compressed_sql,
+                ::std::borrow::Cow::Borrowed(&[
                     #(#checksum),*
                 ]),
-            }
+                #no_tx,
+            )
         };
 
         tokens.append_all(ts);
     }
 }
 
+pub fn expand_migrator_from_input(input: MigrateInput) -> crate::Result<TokenStream> {
+    let path = match &input.dir {
+        Some(dir) => crate::common::resolve_path(dir.value(), dir.span())?,
+        None => default_migrations_path()?,
+    };
+
+    expand_merged_migrator(&[path], &input.options)
+}
+
+/// The migrations directory set in `sqlx.toml`, or else `./migrations`.
+pub(crate) fn default_migrations_path() -> crate::Result<PathBuf> {
+    let config = crate::common::config()?;
+
+    match config.and_then(|(_, config)| config.migrate.source.clone()) {
+        Some(source) => Ok(source),
+        None => Ok(crate::common::resolve_path(
+            "./migrations",
+            proc_macro2::Span::call_site(),
+        )?),
+    }
+}
+
 pub fn expand_migrator_from_lit_dir(dir: LitStr) -> crate::Result<TokenStream> {
     expand_migrator_from_dir(&dir.value(), dir.span())
 }
@@ -94,21 +204,47 @@ pub(crate) fn expand_migrator_from_dir(
     expand_migrator(&path)
 }
 
+pub(crate) fn expand_migrator_from_lit_dirs(dirs: &[LitStr]) -> crate::Result<TokenStream> {
+    let paths = dirs
+        .iter()
+        .map(|dir| crate::common::resolve_path(dir.value(), dir.span()))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    expand_merged_migrator(&paths, &MigrateOptions::default())
+}
+
 pub(crate) fn expand_migrator(path: &Path) -> crate::Result<TokenStream> {
-    let path = path.canonicalize().map_err(|e| {
-        format!(
-            "error canonicalizing migration directory {}: {e}",
-            path.display()
-        )
-    })?;
+    expand_merged_migrator(&[path.to_path_buf()], &MigrateOptions::default())
+}
+
+fn expand_merged_migrator(
+    paths: &[PathBuf],
+    options: &MigrateOptions,
+) -> crate::Result<TokenStream> {
+    let paths = paths
+        .iter()
+        .map(|path| {
+            path.canonicalize().map_err(|e| {
+                format!(
+                    "error canonicalizing migration directory {}: {e}",
+                    path.display()
+                )
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
 
     // Use the same code path to resolve migrations at compile time and runtime.
-    let migrations = sqlx_core::migrate::resolve_blocking(&path)?
+    let migrations = sqlx_core::migrate::resolve_blocking_merged(&paths)?
         .into_iter()
-        .map(|(migration, path)| QuoteMigration { migration, path });
+        .filter(|(_, path)| !is_excluded(path, &paths, &options.exclude))
+        .map(|(migration, path)| QuoteMigration {
+            migration,
+            path,
+            compress: options.compress,
+        });
 
     #[cfg(any(sqlx_macros_unstable, procmacro2_semver_exempt))]
-    {
+    for path in &paths {
         let path = path.to_str().ok_or_else(|| {
             format!(
                 "migration directory path cannot be represented as a string: {:?}",
@@ -119,12 +255,55 @@ pub(crate) fn expand_migrator(path: &Path) -> crate::Result<TokenStream> {
         proc_macro::tracked_path::path(path);
     }
 
+    let config_table_name =
+        crate::common::config()?.and_then(|(_, config)| config.migrate.table.clone());
+
+    let table_name = options
+        .table_name
+        .as_ref()
+        .map(LitStr::value)
+        .or(config_table_name)
+        .map(|table_name| {
+            quote! { table_name: ::std::borrow::Cow::Borrowed(#table_name), }
+        });
+
+    // skipped for the profile selected at compile time, like the database of the query macros
+    let skip = crate::common::config()?
+        .map(|(_, config)| {
+            let profile = std::env::var("SQLX_PROFILE");
+            config.skip_migrations(
+                profile
+                    .as_deref()
+                    .unwrap_or(sqlx_core::config::DEFAULT_PROFILE),
+            )
+        })
+        .filter(|versions| !versions.is_empty())
+        .map(|versions| {
+            quote! { skip: ::std::borrow::Cow::Borrowed(&[#(#versions),*]), }
+        });
+
     Ok(quote! {
         ::sqlx::migrate::Migrator {
-            migrations: ::std::borrow::Cow::Borrowed(&[
+            migrations: ::std::borrow::Cow::Borrowed({
+                // a constant, as the migrations are built by a `const fn`, which is not
+                // promoted to a `'static` outside of constant contexts like `#[sqlx::test]`
+                const MIGRATIONS: &[::sqlx::migrate::Migration] = &[
                     #(#migrations),*
-            ]),
+                ];
+                MIGRATIONS
+            }),
+            #table_name
+            #skip
             ..::sqlx::migrate::Migrator::DEFAULT
         }
     })
 }
+
+fn is_excluded(path: &Path, dirs: &[PathBuf], exclude: &[glob::Pattern]) -> bool {
+    let relative = dirs
+        .iter()
+        .find_map(|dir| path.strip_prefix(dir).ok())
+        .unwrap_or(path);
+
+    exclude.iter().any(|pattern| pattern.matches_path(relative))
+}
@@ -2,7 +2,7 @@ use crate::database::DatabaseExt;
 use crate::query::QueryMacroInput;
 use either::Either;
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote, quote_spanned};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use sqlx_core::describe::Describe;
 use syn::spanned::Spanned;
 use syn::{Expr, ExprCast, ExprGroup, Type};
@@ -25,12 +25,23 @@ pub fn quote_args<DB: DatabaseExt>(
         .map(|i| format_ident!("arg{}", i))
         .collect::<Vec<_>>();
 
-    let arg_name = &arg_names;
-    let arg_expr = input.arg_exprs.iter().cloned().map(strip_wildcard);
-
-    let arg_bindings = quote! {
-        #(let #arg_name = &(#arg_expr);)*
-    };
+    let arg_bindings = arg_names
+        .iter()
+        .zip(input.arg_exprs.iter().cloned().map(strip_wildcard))
+        .enumerate()
+        .map(|(i, (name, expr))| {
+            if input.is_list_arg(i) {
+                quote! {
+                    let #name = &(#expr);
+                    let #name = ::sqlx::list_params::as_slice(#name);
+                }
+            } else {
+                quote! {
+                    let #name = &(#expr);
+                }
+            }
+        })
+        .collect::<TokenStream>();
 
     let args_check = match info.parameters() {
         None | Some(Either::Right(_)) => {
@@ -56,7 +67,8 @@ pub fn quote_args<DB: DatabaseExt>(
                     }
 
                     let param_ty =
-                        DB::param_type_for_id(param_ty)
+                        super::registered_type::<DB>(param_ty)?
+                            .or_else(|| DB::param_type_for_id(param_ty))
                             .ok_or_else(|| {
                                 if let Some(feature_gate) = DB::get_feature_gate(param_ty) {
                                     format!(
@@ -72,6 +84,13 @@ pub fn quote_args<DB: DatabaseExt>(
                             .parse::<TokenStream>()
                             .map_err(|_| format!("Rust type mapping for {param_ty} not parsable"))?;
 
+                    // the values of a list parameter are checked against its type
+                    let dupe_value = if input.is_list_arg(i) {
+                        quote!(::sqlx::list_params::dupe_item)
+                    } else {
+                        quote!(::sqlx::ty_match::dupe_value)
+                    };
+
                     Ok(quote_spanned!(expr.span() =>
                         // this shouldn't actually run
                         #[allow(clippy::missing_panics_doc, clippy::unreachable)]
@@ -79,7 +98,7 @@ pub fn quote_args<DB: DatabaseExt>(
                             use ::sqlx::ty_match::{WrapSameExt as _, MatchBorrowExt as _};
 
                             // evaluate the expression only once in case it contains moves
-                            let expr = ::sqlx::ty_match::dupe_value(#name);
+                            let expr = #dupe_value(#name);
 
                             // if `expr` is `Option<T>`, get `Option<$ty>`, otherwise `$ty`
                             let ty_check = ::sqlx::ty_match::WrapSame::<#param_ty, _>::new(&expr).wrap_same();
@@ -100,6 +119,24 @@ pub fn quote_args<DB: DatabaseExt>(
 
     let args_count = input.arg_exprs.len();
 
+    let (size_hints, adds): (Vec<_>, Vec<_>) = arg_names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            if input.is_list_arg(i) {
+                (
+                    quote!(0),
+                    quote!(::sqlx::list_params::add_list::<#db_path, _>(&mut query_args, #name)),
+                )
+            } else {
+                (
+                    quote!(::sqlx::encode::Encode::<#db_path>::size_hint(#name)),
+                    quote!(query_args.add(#name)),
+                )
+            }
+        })
+        .unzip();
+
     Ok(quote! {
         #arg_bindings
 
@@ -108,13 +145,76 @@ pub fn quote_args<DB: DatabaseExt>(
         let mut query_args = <#db_path as ::sqlx::database::Database>::Arguments::<'_>::default();
         query_args.reserve(
             #args_count,
-            0 #(+ ::sqlx::encode::Encode::<#db_path>::size_hint(#arg_name))*
+            0 #(+ #size_hints)*
         );
         let query_args = ::core::result::Result::<_, ::sqlx::error::BoxDynError>::Ok(query_args)
-        #(.and_then(move |mut query_args| query_args.add(#arg_name).map(move |()| query_args) ))*;
+        #(.and_then(move |mut query_args| #adds.map(move |()| query_args) ))*;
     })
 }
 
+/// The parameter types to declare when describing the query, taken from the type overrides
+/// (`expr as T`) of its arguments.
+///
+/// This lets the database describe queries in which it cannot infer the type of a parameter
+/// by itself, like `$1 IS NULL` in Postgres.
+pub fn declared_param_types<DB: DatabaseExt>(input: &QueryMacroInput) -> Vec<DB::TypeInfo> {
+    let mut declared = Vec::new();
+    let mut len = 0;
+
+    for expr in &input.arg_exprs {
+        let type_info = get_type_override(expr)
+            .and_then(override_type_name)
+            .and_then(|name| DB::type_info_for_rust_type(&name));
+
+        match type_info {
+            Some(type_info) => {
+                declared.push(type_info);
+                // Only declare parameters up to the last one with a recognized override.
+                len = declared.len();
+            }
+            None => match DB::unspecified_param_type() {
+                Some(type_info) => declared.push(type_info),
+                None => break,
+            },
+        }
+    }
+
+    declared.truncate(len);
+    declared
+}
+
+/// The name of the Rust type of an override, with `Option` and lifetimes stripped.
+fn override_type_name(ty: &Type) -> Option<String> {
+    let ty = match ty {
+        Type::Infer(_) => return None,
+        Type::Path(path) => option_inner(path).unwrap_or(ty),
+        _ => ty,
+    };
+
+    let ty = match ty {
+        Type::Reference(reference) => &*reference.elem,
+        _ => ty,
+    };
+
+    Some(ty.to_token_stream().to_string())
+}
+
+fn option_inner(path: &syn::TypePath) -> Option<&Type> {
+    let segment = path.path.segments.last()?;
+
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            syn::GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 fn get_type_override(expr: &Expr) -> Option<&Type> {
     match expr {
         Expr::Group(group) => get_type_override(&group.expr),
@@ -10,6 +10,7 @@ use once_cell::sync::Lazy;
 use serde::{Serialize, Serializer};
 
 use sqlx_core::database::Database;
+use sqlx_core::database_url::DatabaseKind;
 use sqlx_core::describe::Describe;
 
 use crate::database::DatabaseExt;
@@ -78,7 +79,8 @@ pub struct DynQueryData {
 }
 
 impl DynQueryData {
-    /// Loads a query given the path to its "query-<hash>.json" file. Subsequent calls for the same
+    /// Loads a query given the path to its "query-<hash>.json" file, or one of its
+    /// "query-<hash>.<database>.json" files. Subsequent calls for the same
     /// path are retrieved from an in-memory cache.
     pub fn from_data_file(path: impl AsRef<Path>, query: &str) -> crate::Result<Self> {
         let path = path.as_ref();
@@ -150,10 +152,18 @@ where
         }
     }
 
-    pub(super) fn save_in(&self, dir: impl AsRef<Path>) -> crate::Result<()> {
+    /// Save the data in `dir`, or in its subdirectory for the database if `per_driver` is set.
+    pub(super) fn save_in(&self, dir: impl AsRef<Path>, per_driver: bool) -> crate::Result<()> {
         use std::io::ErrorKind;
 
-        let path = dir.as_ref().join(format!("query-{}.json", self.hash));
+        let database = per_driver.then(|| database_kind(DB::URL_SCHEMES)).flatten();
+        let path = data_file_path(dir.as_ref(), &self.hash, database);
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|err| format!("failed to create {dir:?}: {err:?}"))?;
+        }
+
         match std::fs::remove_file(&path) {
             Ok(()) => {}
             Err(err)
@@ -190,6 +200,33 @@ where
     }
 }
 
+/// The name of the file holding the data of a query: `query-<hash>.json`, or
+/// `query-<hash>.<database>.json` for the data of one of several databases.
+pub(super) fn data_file_name(hash: &str, database: Option<DatabaseKind>) -> String {
+    match database {
+        Some(database) => format!("query-{hash}.{}.json", database.as_str()),
+        None => format!("query-{hash}.json"),
+    }
+}
+
+/// The path of the file holding the data of a query in `dir`, in the subdirectory of the
+/// database for the data of one of several databases.
+pub(super) fn data_file_path(dir: &Path, hash: &str, database: Option<DatabaseKind>) -> PathBuf {
+    let file_name = data_file_name(hash, database);
+
+    match database {
+        Some(database) => dir.join(database.as_str()).join(file_name),
+        None => dir.join(file_name),
+    }
+}
+
+/// The database of a driver, from the schemes of its URLs.
+pub(super) fn database_kind(url_schemes: &[&str]) -> Option<DatabaseKind> {
+    url_schemes
+        .iter()
+        .find_map(|scheme| DatabaseKind::from_scheme(scheme))
+}
+
 pub(super) fn hash_string(query: &str) -> String {
     // picked `sha2` because it's already in the dependency tree for both MySQL and Postgres
     use sha2::{Digest, Sha256};
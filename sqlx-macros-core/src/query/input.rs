@@ -6,6 +6,8 @@ use syn::punctuated::Punctuated;
 use syn::{Expr, LitBool, LitStr, Token};
 use syn::{ExprArray, Type};
 
+use super::list_params::{self, ListParam};
+
 /// Macro input shared by `query!()` and `query_file!()`
 pub struct QueryMacroInput {
     pub(super) sql: String,
@@ -19,6 +21,11 @@ pub struct QueryMacroInput {
     pub(super) checked: bool,
 
     pub(super) file_path: Option<String>,
+
+    /// Whether `sql` was changed from the source by rewriting list parameters.
+    pub(super) sql_rewritten: bool,
+
+    pub(super) list_params: Vec<ListParam>,
 }
 
 enum QuerySrc {
@@ -29,6 +36,8 @@ enum QuerySrc {
 pub enum RecordType {
     Given(Type),
     Scalar,
+    // the scalar is always `Option<_>`, as with `query_scalar_optional!()`
+    OptionalScalar,
     Generated,
 }
 
@@ -79,6 +88,13 @@ impl Parse for QueryMacroInput {
                 // of the column in SQL
                 input.parse::<syn::Token![_]>()?;
                 record_type = RecordType::Scalar;
+            } else if key == "optional_scalar" {
+                if !matches!(record_type, RecordType::Generated) {
+                    return Err(input.error("colliding `scalar` or `record` key"));
+                }
+
+                input.parse::<syn::Token![_]>()?;
+                record_type = RecordType::OptionalScalar;
             } else if key == "checked" {
                 let lit_bool = input.parse::<LitBool>()?;
                 checked = lit_bool.value;
@@ -96,18 +112,35 @@ impl Parse for QueryMacroInput {
         let arg_exprs = args.unwrap_or_default();
 
         let file_path = src.file_path(src_span)?;
+        let sql = src.resolve(src_span)?;
+
+        let (sql, sql_rewritten, list_params) = match list_params::rewrite(&sql)
+            .map_err(|message| syn::Error::new(src_span, message))?
+        {
+            Some((rewritten, list_params)) => (rewritten, true, list_params),
+            None => (sql, false, Vec::new()),
+        };
 
         Ok(QueryMacroInput {
-            sql: src.resolve(src_span)?,
+            sql,
             src_span,
             record_type,
             arg_exprs,
             checked,
             file_path,
+            sql_rewritten,
+            list_params,
         })
     }
 }
 
+impl QueryMacroInput {
+    /// Whether the argument at `index` is bound to a `?...` list parameter.
+    pub(super) fn is_list_arg(&self, index: usize) -> bool {
+        self.list_params.iter().any(|list| list.arg == index)
+    }
+}
+
 impl QuerySrc {
     /// If the query source is a file, read it to a string. Otherwise return the query string.
     fn resolve(self, source_span: Span) -> syn::Result<String> {
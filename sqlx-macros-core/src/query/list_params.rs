@@ -0,0 +1,201 @@
+//! List parameters, written `IN ($1...)` for Postgres and `IN (?...)` for MySQL and SQLite.
+//!
+//! `[NOT] IN ($N...)` is rewritten to `= ANY($N)` (`<> ALL($N)`), so the argument is checked and
+//! bound as an array. `IN (?...)` is checked as `IN (?)` against the type of one value, and
+//! expanded at runtime to one placeholder per value by `::sqlx::list_params::expand_sql()`.
+
+/// A `?...` list parameter.
+pub struct ListParam {
+    /// The index of the argument bound to it.
+    pub arg: usize,
+    /// The offset of its `?` in the rewritten query.
+    pub offset: usize,
+}
+
+/// Rewrite the list parameters of `sql`, returning `None` if it has none.
+pub fn rewrite(sql: &str) -> Result<Option<(String, Vec<ListParam>)>, String> {
+    let bytes = sql.as_bytes();
+
+    let mut rewritten = String::with_capacity(sql.len());
+    let mut lists = Vec::new();
+    // the end of the part of `sql` already copied to `rewritten`
+    let mut copied = 0;
+    // the number of positional `?` parameters before `i`
+    let mut positional = 0;
+    let mut numbered = false;
+    let mut found = false;
+
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => i = skip_quoted(bytes, i, quote),
+
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = sql[i..].find('\n').map_or(bytes.len(), |end| i + end);
+            }
+
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = sql[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| i + 2 + end + 2);
+            }
+
+            b'$' if bytes.get(i + 1).is_some_and(u8::is_ascii_digit) => {
+                let end = digits_end(bytes, i + 1);
+
+                if sql[end..].starts_with("...") {
+                    let (start, stop, negated) = in_list_bounds(sql, i, end + 3)?;
+
+                    rewritten.push_str(&sql[copied..start]);
+                    rewritten.push_str(if negated { "<> ALL(" } else { "= ANY(" });
+                    rewritten.push_str(&sql[i..end]);
+                    rewritten.push(')');
+
+                    copied = stop;
+                    found = true;
+                    i = stop;
+                } else {
+                    i = end;
+                }
+            }
+
+            b'$' => i = skip_dollar_quoted(sql, i),
+
+            b'?' if bytes.get(i + 1).is_some_and(u8::is_ascii_digit) => {
+                numbered = true;
+                i = digits_end(bytes, i + 1);
+            }
+
+            b'?' => {
+                if sql[i + 1..].starts_with("...") {
+                    in_list_bounds(sql, i, i + 4)?;
+
+                    rewritten.push_str(&sql[copied..=i]);
+
+                    lists.push(ListParam {
+                        arg: positional,
+                        offset: rewritten.len() - 1,
+                    });
+
+                    copied = i + 4;
+                    found = true;
+                    i += 4;
+                } else {
+                    i += 1;
+                }
+
+                positional += 1;
+            }
+
+            _ => i += 1,
+        }
+    }
+
+    if !found {
+        return Ok(None);
+    }
+
+    if numbered && !lists.is_empty() {
+        return Err(
+            "`?...` list parameters cannot be combined with numbered `?NNN` parameters".into(),
+        );
+    }
+
+    rewritten.push_str(&sql[copied..]);
+
+    Ok(Some((rewritten, lists)))
+}
+
+/// The start of the `[NOT] IN (` before the list parameter at `param..end`, the end of the `)`
+/// after it, and whether it is negated.
+fn in_list_bounds(sql: &str, param: usize, end: usize) -> Result<(usize, usize, bool), String> {
+    let error = || {
+        format!(
+            "list parameter `{}` must be the only item of an `IN (...)` list",
+            &sql[param..end]
+        )
+    };
+
+    let before = sql[..param].trim_end();
+    let before = before.strip_suffix('(').ok_or_else(error)?.trim_end();
+    let before = strip_keyword(before, "IN").ok_or_else(error)?;
+
+    let (start, negated) = match strip_keyword(before.trim_end(), "NOT") {
+        Some(rest) => (rest.len(), true),
+        None => (before.len(), false),
+    };
+
+    let after = &sql[end..];
+    let close = after.len() - after.trim_start().len();
+
+    if !after[close..].starts_with(')') {
+        return Err(error());
+    }
+
+    Ok((start, end + close + 1, negated))
+}
+
+/// `sql` without the keyword at its end, if it ends with the keyword.
+fn strip_keyword<'a>(sql: &'a str, keyword: &str) -> Option<&'a str> {
+    let start = sql.len().checked_sub(keyword.len())?;
+
+    if !sql.is_char_boundary(start) || !sql[start..].eq_ignore_ascii_case(keyword) {
+        return None;
+    }
+
+    let rest = &sql[..start];
+
+    match rest.bytes().last() {
+        Some(c) if c.is_ascii_alphanumeric() || c == b'_' => None,
+        _ => Some(rest),
+    }
+}
+
+fn digits_end(bytes: &[u8], start: usize) -> usize {
+    start
+        + bytes[start..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count()
+}
+
+fn skip_quoted(bytes: &[u8], start: usize, quote: u8) -> usize {
+    let mut i = start + 1;
+
+    while i < bytes.len() {
+        if bytes[i] == quote {
+            // a doubled quote is an escaped one
+            if bytes.get(i + 1) == Some(&quote) {
+                i += 2;
+                continue;
+            }
+
+            return i + 1;
+        }
+
+        i += 1;
+    }
+
+    bytes.len()
+}
+
+/// Skip a dollar-quoted string of Postgres at `start`, like `$$text$$` or `$tag$text$tag$`.
+fn skip_dollar_quoted(sql: &str, start: usize) -> usize {
+    let tag_len = sql[start + 1..]
+        .bytes()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == b'_')
+        .count();
+
+    let tag_end = start + 1 + tag_len;
+
+    if sql.as_bytes().get(tag_end) != Some(&b'$') {
+        return start + 1;
+    }
+
+    let tag = &sql[start..=tag_end];
+
+    sql[tag_end + 1..]
+        .find(tag)
+        .map_or(sql.len(), |end| tag_end + 1 + end + tag.len())
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::{fs, io};
 
@@ -12,14 +12,16 @@ use sqlx_core::database::Database;
 use sqlx_core::{column::Column, describe::Describe, type_info::TypeInfo};
 
 use crate::database::DatabaseExt;
-use crate::query::data::{hash_string, DynQueryData, QueryData};
+use crate::query::data::{data_file_path, database_kind, hash_string, DynQueryData, QueryData};
 use crate::query::input::RecordType;
 use either::Either;
+use sqlx_core::database_url::DatabaseKind;
 use url::Url;
 
 mod args;
 mod data;
 mod input;
+mod list_params;
 mod output;
 
 #[derive(Copy, Clone)]
@@ -73,6 +75,8 @@ struct Metadata {
     manifest_dir: PathBuf,
     offline: bool,
     database_url: Option<String>,
+    offline_dir: Option<PathBuf>,
+    offline_per_driver: bool,
     workspace_root: Arc<Mutex<Option<PathBuf>>>,
 }
 
@@ -108,7 +112,9 @@ impl Metadata {
 
 // If we are in a workspace, lookup `workspace_root` since `CARGO_MANIFEST_DIR` won't
 // reflect the workspace dir: https://github.com/rust-lang/cargo/issues/3946
-static METADATA: Lazy<Metadata> = Lazy::new(|| {
+//
+// An invalid `sqlx.toml` is reported by every macro invocation as a compile error.
+static METADATA: Lazy<Result<Metadata, String>> = Lazy::new(|| {
     let manifest_dir: PathBuf = env("CARGO_MANIFEST_DIR")
         .expect("`CARGO_MANIFEST_DIR` must be set")
         .into();
@@ -135,47 +141,105 @@ static METADATA: Lazy<Metadata> = Lazy::new(|| {
         proc_macro::tracked_path::path(env_path);
     }
 
+    // Values from `sqlx.toml` are only used if the environment doesn't set them.
+    let config = crate::common::config()?;
+
+    #[cfg(procmacro2_semver_exempt)]
+    if let Some(config_path) = config.and_then(|(path, _)| path.to_str()) {
+        proc_macro::tracked_path::path(config_path);
+    }
+
+    let config = config.map(|(_, config)| config);
+
     let offline = env("SQLX_OFFLINE")
         .map(|s| s.eq_ignore_ascii_case("true") || s == "1")
-        .unwrap_or(false);
+        .unwrap_or_else(|_| config.map_or(false, |config| config.offline.enabled));
+
+    let database_url = match (env("DATABASE_URL"), config) {
+        (Ok(database_url), _) => Some(database_url),
+        (Err(_), Some(config)) => {
+            let profile = env("SQLX_PROFILE");
+            let profile = profile
+                .as_deref()
+                .unwrap_or(sqlx_core::config::DEFAULT_PROFILE);
+
+            config
+                .database_url(profile)
+                .map_err(|e| e.to_string())?
+                .map(String::from)
+        }
+        (Err(_), None) => None,
+    };
 
-    let database_url = env("DATABASE_URL").ok();
+    let offline_dir = config.and_then(|config| config.offline.dir.clone());
+    let offline_per_driver = config.map_or(false, |config| config.offline.is_per_driver());
 
-    Metadata {
+    Ok(Metadata {
         manifest_dir,
         offline,
         database_url,
+        offline_dir,
+        offline_per_driver,
         workspace_root: Arc::new(Mutex::new(None)),
-    }
+    })
 });
 
+fn metadata() -> crate::Result<&'static Metadata> {
+    METADATA
+        .as_ref()
+        .map_err(|e| syn::Error::new(proc_macro2::Span::call_site(), e).into())
+}
+
 pub fn expand_input<'a>(
     input: QueryMacroInput,
     drivers: impl IntoIterator<Item = &'a QueryDriver>,
 ) -> crate::Result<TokenStream> {
-    let data_source = match &*METADATA {
+    let drivers: Vec<&QueryDriver> = drivers.into_iter().collect();
+    let metadata = metadata()?;
+
+    let data_source = match metadata {
         Metadata {
             offline: false,
             database_url: Some(db_url),
             ..
         } => QueryDataSource::live(db_url)?,
 
-        Metadata { offline, .. } => {
+        Metadata {
+            offline,
+            database_url,
+            ..
+        } => {
             // Try load the cached query metadata file.
-            let filename = format!("query-{}.json", hash_string(&input.sql));
-
-            // Check SQLX_OFFLINE_DIR, then local .sqlx, then workspace .sqlx.
-            let dirs = [
-                || env("SQLX_OFFLINE_DIR").ok().map(PathBuf::from),
-                || Some(METADATA.manifest_dir.join(".sqlx")),
-                || Some(METADATA.workspace_root().join(".sqlx")),
-            ];
-            let Some(data_file_path) = dirs
+            let hash = hash_string(&input.sql);
+
+            // The data may be saved for each of several databases; prefer the one of
+            // `DATABASE_URL` if it is set, even though it is not connected to.
+            let url_database = database_url
+                .as_deref()
+                .and_then(|url| Url::parse(url).ok())
+                .and_then(|url| DatabaseKind::from_scheme(url.scheme()));
+            let databases: Vec<DatabaseKind> = drivers
                 .iter()
-                .filter_map(|path| path())
-                .map(|path| path.join(&filename))
-                .find(|path| path.exists())
-            else {
+                .filter_map(|driver| database_kind(driver.url_schemes))
+                .collect();
+
+            // Check SQLX_OFFLINE_DIR, then the directory set in sqlx.toml, then local .sqlx,
+            // then workspace .sqlx.
+            let dirs: [&dyn Fn() -> Option<PathBuf>; 4] = [
+                &|| env("SQLX_OFFLINE_DIR").ok().map(PathBuf::from),
+                &|| metadata.offline_dir.clone(),
+                &|| Some(metadata.manifest_dir.join(".sqlx")),
+                &|| Some(metadata.workspace_root().join(".sqlx")),
+            ];
+            let mut data_file_path = None;
+            for dir in dirs.iter().filter_map(|path| path()) {
+                data_file_path = find_data_file(&dir, &hash, url_database, &databases)?;
+
+                if data_file_path.is_some() {
+                    break;
+                }
+            }
+            let Some(data_file_path) = data_file_path else {
                 return Err(
                     if *offline {
                         "`SQLX_OFFLINE=true` but there is no cached data for this query, run `cargo sqlx prepare` to update the query cache or unset `SQLX_OFFLINE`"
@@ -212,6 +276,49 @@ pub fn expand_input<'a>(
     }
 }
 
+/// Find the data of the query with `hash` in `dir`: the data for `url_database` if there is, or
+/// else for the only one of `databases` for which there is, or else the data saved for a single
+/// database.
+fn find_data_file(
+    dir: &Path,
+    hash: &str,
+    url_database: Option<DatabaseKind>,
+    databases: &[DatabaseKind],
+) -> crate::Result<Option<PathBuf>> {
+    let path_for = |database| data_file_path(dir, hash, database);
+
+    if let Some(path) = url_database
+        .map(|database| path_for(Some(database)))
+        .filter(|path| path.exists())
+    {
+        return Ok(Some(path));
+    }
+
+    if url_database.is_none() {
+        let found: Vec<DatabaseKind> = databases
+            .iter()
+            .copied()
+            .filter(|database| path_for(Some(*database)).exists())
+            .collect();
+
+        match *found {
+            [] => {}
+            [database] => return Ok(Some(path_for(Some(database)))),
+            _ => {
+                let names: Vec<&str> = found.iter().map(DatabaseKind::as_str).collect();
+
+                return Err(format!(
+                    "there is cached data for this query for several databases ({}); set `DATABASE_URL` to choose which one to check it against",
+                    names.join(", ")
+                )
+                .into());
+            }
+        }
+    }
+
+    Ok(Some(path_for(None)).filter(|path| path.exists()))
+}
+
 fn expand_with<DB: DatabaseExt>(
     input: QueryMacroInput,
     data_source: QueryDataSource,
@@ -222,7 +329,8 @@ where
     let (query_data, offline): (QueryData<DB>, bool) = match data_source {
         QueryDataSource::Cached(dyn_data) => (QueryData::from_dyn_data(dyn_data)?, true),
         QueryDataSource::Live { database_url, .. } => {
-            let describe = DB::describe_blocking(&input.sql, database_url)?;
+            let parameters = args::declared_param_types::<DB>(&input);
+            let describe = DB::describe_blocking(&input.sql, &parameters, database_url)?;
             (QueryData::from_describe(&input.sql, describe), false)
         }
     };
@@ -273,7 +381,7 @@ where
         .all(|it| it.type_info().is_void())
     {
         let db_path = DB::db_path();
-        let sql = &input.sql;
+        let sql = output::quote_sql(&input);
 
         quote! {
             ::sqlx::__query_with_result::<#db_path, _>(#sql, #query_args)
@@ -316,13 +424,19 @@ where
 
                 record_tokens
             }
+            RecordType::Given(Type::Tuple(ref out_ty)) => {
+                output::quote_query_as_tuple::<DB>(&input, out_ty, &query_args, &data.describe)?
+            }
             RecordType::Given(ref out_ty) => {
                 let columns = output::columns_to_rust::<DB>(&data.describe)?;
 
                 output::quote_query_as::<DB>(&input, out_ty, &query_args, &columns)
             }
             RecordType::Scalar => {
-                output::quote_query_scalar::<DB>(&input, &query_args, &data.describe)?
+                output::quote_query_scalar::<DB>(&input, &query_args, &data.describe, false)?
+            }
+            RecordType::OptionalScalar => {
+                output::quote_query_scalar::<DB>(&input, &query_args, &data.describe, true)?
             }
         }
     };
@@ -366,7 +480,7 @@ where
                     }
 
                     // .sqlx exists and is a directory, store data.
-                    data.save_in(path)?;
+                    data.save_in(path, metadata()?.offline_per_driver)?;
                 }
             }
         }
@@ -387,3 +501,17 @@ fn env(name: &str) -> Result<String, std::env::VarError> {
         std::env::var(name)
     }
 }
+
+/// The Rust type registered for `type_info` by the build script of the crate, if any.
+fn registered_type<DB: DatabaseExt>(
+    type_info: &DB::TypeInfo,
+) -> crate::Result<Option<&'static str>> {
+    let (Some(registry), Some(database)) = (
+        crate::common::type_registry()?,
+        database_kind(DB::URL_SCHEMES),
+    ) else {
+        return Ok(None);
+    };
+
+    Ok(registry.rust_type(database, type_info.name()))
+}
@@ -1,6 +1,6 @@
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::{quote, ToTokens, TokenStreamExt};
-use syn::Type;
+use quote::{format_ident, quote, ToTokens, TokenStreamExt};
+use syn::{Type, TypeTuple};
 
 use sqlx_core::column::Column;
 use sqlx_core::describe::Describe;
@@ -78,11 +78,15 @@ impl Display for DisplayColumn<'_> {
 
 pub fn columns_to_rust<DB: DatabaseExt>(describe: &Describe<DB>) -> crate::Result<Vec<RustColumn>> {
     (0..describe.columns().len())
-        .map(|i| column_to_rust(describe, i))
+        .map(|i| column_to_rust(describe, i, false))
         .collect::<crate::Result<Vec<_>>>()
 }
 
-fn column_to_rust<DB: DatabaseExt>(describe: &Describe<DB>, i: usize) -> crate::Result<RustColumn> {
+fn column_to_rust<DB: DatabaseExt>(
+    describe: &Describe<DB>,
+    i: usize,
+    force_nullable: bool,
+) -> crate::Result<RustColumn> {
     let column = &describe.columns()[i];
 
     // add raw prefix to all identifiers
@@ -91,11 +95,12 @@ fn column_to_rust<DB: DatabaseExt>(describe: &Describe<DB>, i: usize) -> crate::
 
     let ColumnOverride { nullability, type_ } = decl.r#override;
 
-    let nullable = match nullability {
-        ColumnNullabilityOverride::NonNull => false,
-        ColumnNullabilityOverride::Nullable => true,
-        ColumnNullabilityOverride::None => describe.nullable(i).unwrap_or(true),
-    };
+    let nullable = force_nullable
+        || match nullability {
+            ColumnNullabilityOverride::NonNull => false,
+            ColumnNullabilityOverride::Nullable => true,
+            ColumnNullabilityOverride::None => describe.nullable(i).unwrap_or(true),
+        };
     let type_ = match (type_, nullable) {
         (ColumnTypeOverride::Exact(type_), false) => ColumnType::Exact(type_.to_token_stream()),
         (ColumnTypeOverride::Exact(type_), true) => {
@@ -130,55 +135,14 @@ pub fn quote_query_as<DB: DatabaseExt>(
     bind_args: &Ident,
     columns: &[RustColumn],
 ) -> TokenStream {
-    let instantiations = columns.iter().enumerate().map(
-        |(
-            i,
-            RustColumn {
-                var_name, type_, ..
-            },
-        )| {
-            match (input.checked, type_) {
-                // we guarantee the type is valid so we can skip the runtime check
-                (true, ColumnType::Exact(type_)) => quote! {
-                    // binding to a `let` avoids confusing errors about
-                    // "try expression alternatives have incompatible types"
-                    // it doesn't seem to hurt inference in the other branches
-                    #[allow(non_snake_case)]
-                    let #var_name = row.try_get_unchecked::<#type_, _>(#i)?.into();
-                },
-                // type was overridden to be a wildcard so we fallback to the runtime check
-                (true, ColumnType::Wildcard) => quote! (
-                #[allow(non_snake_case)]
-                let #var_name = row.try_get(#i)?;
-                ),
-                (true, ColumnType::OptWildcard) => {
-                    quote! (
-                    #[allow(non_snake_case)]
-                    let #var_name = row.try_get::<::std::option::Option<_>, _>(#i)?;
-                    )
-                }
-                // macro is the `_unchecked!()` variant so this will die in decoding if it's wrong
-                (false, _) => quote!(
-                #[allow(non_snake_case)]
-                let #var_name = row.try_get_unchecked(#i)?;
-                ),
-            }
-        },
-    );
+    let instantiations = quote_instantiations(input, columns);
 
     let ident = columns.iter().map(|col| &col.ident);
     let var_name = columns.iter().map(|col| &col.var_name);
 
     let db_path = DB::db_path();
     let row_path = DB::row_path();
-
-    // if this query came from a file, use `include_str!()` to tell the compiler where it came from
-    let sql = if let Some(ref path) = &input.file_path {
-        quote::quote_spanned! { input.src_span => include_str!(#path) }
-    } else {
-        let sql = &input.sql;
-        quote! { #sql }
-    };
+    let sql = quote_sql(input);
 
     quote! {
         ::sqlx::__query_with_result::<#db_path, _>(#sql, #bind_args).try_map(|row: #row_path| {
@@ -191,10 +155,146 @@ pub fn quote_query_as<DB: DatabaseExt>(
     }
 }
 
+/// `query_as!()` with a tuple as the output type, which takes the columns in order, whatever
+/// their names.
+pub fn quote_query_as_tuple<DB: DatabaseExt>(
+    input: &QueryMacroInput,
+    out_ty: &TypeTuple,
+    bind_args: &Ident,
+    describe: &Describe<DB>,
+) -> crate::Result<TokenStream> {
+    let len = describe.columns().len();
+
+    if out_ty.elems.len() != len {
+        return Err(syn::Error::new_spanned(
+            out_ty,
+            format!(
+                "expected a tuple of {len} elements to match the columns of the query, got {}",
+                out_ty.elems.len()
+            ),
+        )
+        .into());
+    }
+
+    let columns = (0..len)
+        .map(|i| {
+            // the names of the columns don't have to be valid identifiers, but if they are,
+            // overrides such as `"id!"` or `"id: MyId"` apply as usual
+            let type_ = match column_to_rust(describe, i, false) {
+                Ok(column) => column.type_,
+                Err(_) => ColumnType::Exact(inferred_type(describe, i, false)),
+            };
+
+            RustColumn {
+                ident: quote::format_ident!("_{}", i),
+                var_name: quote::format_ident!("sqlx_query_as_{}", i),
+                type_,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let instantiations = quote_instantiations(input, &columns);
+    let var_name = columns.iter().map(|col| &col.var_name);
+
+    let db_path = DB::db_path();
+    let row_path = DB::row_path();
+    let sql = quote_sql(input);
+
+    Ok(quote! {
+        ::sqlx::__query_with_result::<#db_path, _>(#sql, #bind_args).try_map(|row: #row_path| {
+            use ::sqlx::Row as _;
+
+            #(#instantiations)*
+
+            let record: #out_ty = (#(#var_name,)*);
+
+            ::std::result::Result::Ok(record)
+        })
+    })
+}
+
+fn quote_instantiations(input: &QueryMacroInput, columns: &[RustColumn]) -> Vec<TokenStream> {
+    columns
+        .iter()
+        .enumerate()
+        .map(
+            |(
+                i,
+                RustColumn {
+                    var_name, type_, ..
+                },
+            )| {
+                match (input.checked, type_) {
+                    // we guarantee the type is valid so we can skip the runtime check
+                    (true, ColumnType::Exact(type_)) => quote! {
+                        // binding to a `let` avoids confusing errors about
+                        // "try expression alternatives have incompatible types"
+                        // it doesn't seem to hurt inference in the other branches
+                        #[allow(non_snake_case)]
+                        let #var_name = row.try_get_unchecked::<#type_, _>(#i)?.into();
+                    },
+                    // type was overridden to be a wildcard so we fallback to the runtime check
+                    (true, ColumnType::Wildcard) => quote! (
+                    #[allow(non_snake_case)]
+                    let #var_name = row.try_get(#i)?;
+                    ),
+                    (true, ColumnType::OptWildcard) => {
+                        quote! (
+                        #[allow(non_snake_case)]
+                        let #var_name = row.try_get::<::std::option::Option<_>, _>(#i)?;
+                        )
+                    }
+                    // macro is the `_unchecked!()` variant so this will die in decoding if it's wrong
+                    (false, _) => quote!(
+                    #[allow(non_snake_case)]
+                    let #var_name = row.try_get_unchecked(#i)?;
+                    ),
+                }
+            },
+        )
+        .collect()
+}
+
+pub(super) fn quote_sql(input: &QueryMacroInput) -> TokenStream {
+    let sql = match &input.file_path {
+        // if this query came from a file, use `include_str!()` to tell the compiler where it came
+        // from; the rewritten query must be used as-is, but still depends on the file
+        Some(path) if input.sql_rewritten => {
+            let sql = &input.sql;
+            quote::quote_spanned! { input.src_span => {
+                const _: &str = include_str!(#path);
+                #sql
+            }}
+        }
+        Some(path) => quote::quote_spanned! { input.src_span => include_str!(#path) },
+        None => {
+            let sql = &input.sql;
+            quote! { #sql }
+        }
+    };
+
+    if input.list_params.is_empty() {
+        return sql;
+    }
+
+    // one `?` per value of each `?...` list parameter
+    let lists = input.list_params.iter().map(|list| {
+        let offset = list.offset;
+        let arg_name = format_ident!("arg{}", list.arg);
+
+        quote! { (#offset, ::sqlx::list_params::bound_len(#arg_name)) }
+    });
+
+    quote! {
+        ::sqlx::list_params::expand_sql(#sql, &[#(#lists),*])
+    }
+}
+
 pub fn quote_query_scalar<DB: DatabaseExt>(
     input: &QueryMacroInput,
     bind_args: &Ident,
     describe: &Describe<DB>,
+    force_nullable: bool,
 ) -> crate::Result<TokenStream> {
     let columns = describe.columns();
 
@@ -207,30 +307,53 @@ pub fn quote_query_scalar<DB: DatabaseExt>(
     }
 
     // attempt to parse a column override, otherwise fall back to the inferred type of the column
-    let ty = if let Ok(rust_col) = column_to_rust(describe, 0) {
+    let ty = if let Ok(rust_col) = column_to_rust(describe, 0, force_nullable) {
         rust_col.type_.to_token_stream()
     } else if input.checked {
-        let ty = get_column_type::<DB>(0, &columns[0]);
-        if describe.nullable(0).unwrap_or(true) {
-            quote! { ::std::option::Option<#ty> }
-        } else {
-            ty
-        }
+        inferred_type(describe, 0, force_nullable)
+    } else if force_nullable {
+        quote! { ::std::option::Option<_> }
     } else {
         quote! { _ }
     };
 
     let db = DB::db_path();
-    let query = &input.sql;
+    let query = quote_sql(input);
 
     Ok(quote! {
         ::sqlx::__query_scalar_with_result::<#db, #ty, _>(#query, #bind_args)
     })
 }
 
+// the type of a column whose name can't carry overrides
+fn inferred_type<DB: DatabaseExt>(
+    describe: &Describe<DB>,
+    i: usize,
+    force_nullable: bool,
+) -> TokenStream {
+    let ty = get_column_type::<DB>(i, &describe.columns()[i]);
+
+    if force_nullable || describe.nullable(i).unwrap_or(true) {
+        quote! { ::std::option::Option<#ty> }
+    } else {
+        ty
+    }
+}
+
 fn get_column_type<DB: DatabaseExt>(i: usize, column: &DB::Column) -> TokenStream {
     let type_info = column.type_info();
 
+    match super::registered_type::<DB>(type_info) {
+        Ok(Some(ty)) => {
+            return ty.parse().unwrap_or_else(|_| {
+                let message = format!("Rust type mapping for {type_info} not parsable");
+                syn::Error::new(Span::call_site(), message).to_compile_error()
+            })
+        }
+        Ok(None) => (),
+        Err(e) => return syn::Error::new(Span::call_site(), e).to_compile_error(),
+    }
+
     <DB as TypeChecking>::return_type_for_id(type_info).map_or_else(
         || {
             let message =
@@ -4,10 +4,18 @@ use syn::parse::Parser;
 
 #[cfg(feature = "migrate")]
 struct Args {
-    fixtures: Vec<(FixturesType, Vec<syn::LitStr>)>,
+    fixtures: Vec<(FixturesType, Vec<Fixture>)>,
     migrations: MigrationsOpt,
 }
 
+#[cfg(feature = "migrate")]
+enum Fixture {
+    /// A SQL script, resolved according to the [`FixturesType`] of its `fixtures` attribute.
+    Script(syn::LitStr),
+    /// An async function receiving the test pool, with the fixtures it depends on.
+    Function(syn::Path, Vec<syn::LitStr>),
+}
+
 #[cfg(feature = "migrate")]
 enum FixturesType {
     None,
@@ -20,6 +28,7 @@ enum FixturesType {
 enum MigrationsOpt {
     InferredPath,
     ExplicitPath(syn::LitStr),
+    ExplicitPaths(Vec<syn::LitStr>),
     ExplicitMigrator(syn::Path),
     Disabled,
 }
@@ -90,55 +99,47 @@ fn expand_advanced(args: AttributeArgs, input: syn::ItemFn) -> crate::Result<Tok
     let mut fixtures = Vec::new();
 
     for (fixture_type, fixtures_local) in args.fixtures {
-        let mut res = match fixture_type {
-            FixturesType::None => vec![],
-            FixturesType::RelativePath => fixtures_local
-                .into_iter()
-                .map(|fixture| {
-                    let mut fixture_str = fixture.value();
-                    add_sql_extension_if_missing(&mut fixture_str);
-
-                    let path = format!("fixtures/{}", fixture_str);
-
-                    quote! {
-                        ::sqlx::testing::TestFixture {
-                            path: #path,
-                            contents: include_str!(#path),
+        for fixture in fixtures_local {
+            let fixture = match fixture {
+                Fixture::Script(fixture) => {
+                    let path = match &fixture_type {
+                        FixturesType::None | FixturesType::RelativePath => {
+                            let mut fixture_str = fixture.value();
+                            add_sql_extension_if_missing(&mut fixture_str);
+
+                            format!("fixtures/{}", fixture_str)
                         }
-                    }
-                })
-                .collect(),
-            FixturesType::CustomRelativePath(path) => fixtures_local
-                .into_iter()
-                .map(|fixture| {
-                    let mut fixture_str = fixture.value();
-                    add_sql_extension_if_missing(&mut fixture_str);
+                        FixturesType::CustomRelativePath(path) => {
+                            let mut fixture_str = fixture.value();
+                            add_sql_extension_if_missing(&mut fixture_str);
 
-                    let path = format!("{}/{}", path.value(), fixture_str);
+                            format!("{}/{}", path.value(), fixture_str)
+                        }
+                        FixturesType::ExplicitPath => fixture.value(),
+                    };
 
                     quote! {
-                        ::sqlx::testing::TestFixture {
+                        ::sqlx::testing::TestFixture::Script {
                             path: #path,
                             contents: include_str!(#path),
                         }
                     }
-                })
-                .collect(),
-            FixturesType::ExplicitPath => fixtures_local
-                .into_iter()
-                .map(|fixture| {
-                    let path = fixture.value();
+                }
+                Fixture::Function(path, depends) => {
+                    let name = path
+                        .segments
+                        .last()
+                        .map(|segment| segment.ident.to_string())
+                        .unwrap_or_default();
 
                     quote! {
-                        ::sqlx::testing::TestFixture {
-                            path: #path,
-                            contents: include_str!(#path),
-                        }
+                        ::sqlx::testing::TestFixture::function(#name, &[#(#depends),*], #path)
                     }
-                })
-                .collect(),
-        };
-        fixtures.append(&mut res)
+                }
+            };
+
+            fixtures.push(fixture);
+        }
     }
 
     let migrations = match args.migrations {
@@ -146,9 +147,12 @@ fn expand_advanced(args: AttributeArgs, input: syn::ItemFn) -> crate::Result<Tok
             let migrator = crate::migrate::expand_migrator_from_lit_dir(path)?;
             quote! { args.migrator(&#migrator); }
         }
+        MigrationsOpt::ExplicitPaths(paths) => {
+            let migrator = crate::migrate::expand_migrator_from_lit_dirs(&paths)?;
+            quote! { args.migrator(&#migrator); }
+        }
         MigrationsOpt::InferredPath if !inputs.is_empty() => {
-            let migrations_path =
-                crate::common::resolve_path("./migrations", proc_macro2::Span::call_site())?;
+            let migrations_path = crate::migrate::default_migrations_path()?;
 
             if migrations_path.is_dir() {
                 let migrator = crate::migrate::expand_migrator(&migrations_path)?;
@@ -175,7 +179,7 @@ fn expand_advanced(args: AttributeArgs, input: syn::ItemFn) -> crate::Result<Tok
 
             #migrations
 
-            args.fixtures(&[#(#fixtures),*]);
+            args.fixtures(vec![#(#fixtures),*]);
 
             // We need to give a coercion site or else we get "unimplemented trait" errors.
             let f: fn(#(#fn_arg_types),*) -> _ = #name;
@@ -215,6 +219,9 @@ fn parse_args(attr_args: AttributeArgs) -> syn::Result<Args> {
                         parenthesized!(content in meta.input);
                         let list = content.parse_terminated(<LitStr as Parse>::parse, Comma)?;
                         parse_fixtures_scripts_args(&mut fixtures_type, list, &mut fixtures_local)?;
+                    } else if matches!(fixtures_type, FixturesType::None) {
+                        // a fixture function, parsed along with the other fixtures below
+                        return Err(meta.error("not `path` or `scripts`"));
                     } else {
                         return Err(syn::Error::new_spanned(
                             meta.path,
@@ -226,11 +233,21 @@ fn parse_args(attr_args: AttributeArgs) -> syn::Result<Args> {
                 });
 
                 if parse_nested.is_err() {
-                    // fixtures("<file_1>","<file_2>") or fixtures("<path/file_1.sql>","<path/file_2.sql>")
-                    let args =
-                        list.parse_args_with(<Punctuated<LitStr, Token![,]>>::parse_terminated)?;
+                    // fixtures("<file_1>","<file_2>") or fixtures("<path/file_1.sql>","<path/file_2.sql>"),
+                    // along with fixture functions: fixtures("<file_1>", <fn>, <fn>(depends("<name>")))
+                    fixtures_local.clear();
+
+                    let args = list
+                        .parse_args_with(<Punctuated<FixtureArg, Token![,]>>::parse_terminated)?;
                     for arg in args {
-                        parse_fixtures_args(&mut fixtures_type, arg, &mut fixtures_local)?;
+                        match arg {
+                            FixtureArg::Script(arg) => {
+                                parse_fixtures_args(&mut fixtures_type, arg, &mut fixtures_local)?
+                            }
+                            FixtureArg::Function(path, depends) => {
+                                fixtures_local.push(Fixture::Function(path, depends))
+                            }
+                        }
                     }
                 }
 
@@ -244,6 +261,30 @@ fn parse_args(attr_args: AttributeArgs) -> syn::Result<Args> {
                     ));
                 }
 
+                // migrations = ["<path>", "<path>"]
+                if let Expr::Array(array) = &value.value {
+                    let paths = array
+                        .elems
+                        .iter()
+                        .map(|elem| match elem {
+                            Expr::Lit(syn::ExprLit {
+                                lit: Lit::Str(s), ..
+                            }) => Ok(s.clone()),
+                            expr => Err(syn::Error::new_spanned(expr, "expected string")),
+                        })
+                        .collect::<syn::Result<Vec<_>>>()?;
+
+                    if paths.is_empty() {
+                        return Err(syn::Error::new_spanned(
+                            array,
+                            "expected at least one migrations path",
+                        ));
+                    }
+
+                    migrations = MigrationsOpt::ExplicitPaths(paths);
+                    continue;
+                }
+
                 fn recurse_lit_lookup(expr: Expr) -> Option<Lit> {
                     match expr {
                         Expr::Lit(syn::ExprLit { lit, .. }) => {
@@ -296,7 +337,7 @@ fn parse_args(attr_args: AttributeArgs) -> syn::Result<Args> {
             arg => {
                 return Err(syn::Error::new_spanned(
                     arg,
-                    r#"expected `fixtures("<filename>", ...)` or `migrations = "<path>" | false` or `migrator = "<rust path>"`"#,
+                    r#"expected `fixtures("<filename>", ...)` or `migrations = "<path>" | ["<path>", ...] | false` or `migrator = "<rust path>"`"#,
                 ))
             }
         }
@@ -312,7 +353,7 @@ fn parse_args(attr_args: AttributeArgs) -> syn::Result<Args> {
 fn parse_fixtures_args(
     fixtures_type: &mut FixturesType,
     litstr: syn::LitStr,
-    fixtures_local: &mut Vec<syn::LitStr>,
+    fixtures_local: &mut Vec<Fixture>,
 ) -> syn::Result<()> {
     //  fixtures(path = "<path>", scripts("<file_1>","<file_2>")) checking `path` argument
     let path_str = litstr.value();
@@ -357,7 +398,7 @@ fn parse_fixtures_args(
             "expected explicit path fixtures to have `.sql` extension",
         ));
     }
-    fixtures_local.push(litstr);
+    fixtures_local.push(Fixture::Script(litstr));
     Ok(())
 }
 
@@ -380,7 +421,7 @@ fn parse_fixtures_path_args(
 fn parse_fixtures_scripts_args(
     fixtures_type: &mut FixturesType,
     list: syn::punctuated::Punctuated<syn::LitStr, syn::Token![,]>,
-    fixtures_local: &mut Vec<syn::LitStr>,
+    fixtures_local: &mut Vec<Fixture>,
 ) -> syn::Result<()> {
     //  fixtures(path = "<path>", scripts("<file_1>","<file_2>")) checking `scripts` argument
 
@@ -391,10 +432,52 @@ fn parse_fixtures_scripts_args(
         ));
     }
 
-    fixtures_local.extend(list);
+    fixtures_local.extend(list.into_iter().map(Fixture::Script));
     Ok(())
 }
 
+/// An argument of `fixtures(...)` other than `path` and `scripts`.
+#[cfg(feature = "migrate")]
+enum FixtureArg {
+    Script(syn::LitStr),
+    Function(syn::Path, Vec<syn::LitStr>),
+}
+
+#[cfg(feature = "migrate")]
+impl syn::parse::Parse for FixtureArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::LitStr) {
+            return input.parse().map(FixtureArg::Script);
+        }
+
+        // <fn> or <fn>(depends("<name>", ...))
+        let path: syn::Path = input.parse()?;
+        let mut depends = Vec::new();
+
+        if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+
+            let keyword: syn::Ident = content.parse()?;
+            if keyword != "depends" {
+                return Err(syn::Error::new_spanned(
+                    keyword,
+                    r#"expected `depends("<fixture>", ...)`"#,
+                ));
+            }
+
+            let names;
+            syn::parenthesized!(names in content);
+            depends.extend(
+                names
+                    .parse_terminated(<syn::LitStr as syn::parse::Parse>::parse, syn::Token![,])?,
+            );
+        }
+
+        Ok(FixtureArg::Function(path, depends))
+    }
+}
+
 #[cfg(feature = "migrate")]
 fn add_sql_extension_if_missing(fixture: &mut String) {
     let has_extension = std::path::Path::new(&fixture).extension().is_some();
@@ -8,14 +8,14 @@ use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::{stream, StreamExt, TryFutureExt, TryStreamExt};
 use sqlx_core::any::{
-    Any, AnyArguments, AnyColumn, AnyConnectOptions, AnyConnectionBackend, AnyQueryResult, AnyRow,
-    AnyStatement, AnyTypeInfo, AnyTypeInfoKind,
+    Any, AnyArguments, AnyCapabilities, AnyColumn, AnyConnectOptions, AnyConnectionBackend,
+    AnyPlaceholderStyle, AnyQueryResult, AnyRow, AnyStatement, AnyTypeInfo, AnyTypeInfoKind,
 };
-use sqlx_core::connection::Connection;
+use sqlx_core::connection::{CachedStatements, Connection};
 use sqlx_core::database::Database;
 use sqlx_core::describe::Describe;
 use sqlx_core::executor::Executor;
-use sqlx_core::transaction::TransactionManager;
+use sqlx_core::transaction::{StatementJournal, TransactionManager};
 use std::borrow::Cow;
 use std::{future, pin::pin};
 
@@ -26,6 +26,18 @@ impl AnyConnectionBackend for MySqlConnection {
         <MySql as Database>::NAME
     }
 
+    fn capabilities(&self) -> AnyCapabilities {
+        let stream = &self.inner.stream;
+
+        AnyCapabilities {
+            // MariaDB 10.5 added `INSERT ... RETURNING`; MySQL has no `RETURNING` at all
+            supports_returning: stream.is_mariadb && stream.server_version >= (10, 5, 0),
+            supports_savepoints: true,
+            placeholder_style: AnyPlaceholderStyle::QuestionMark,
+            max_bind_params: u16::MAX.into(),
+        }
+    }
+
     fn close(self: Box<Self>) -> BoxFuture<'static, sqlx_core::Result<()>> {
         Connection::close(*self)
     }
@@ -61,6 +73,25 @@ impl AnyConnectionBackend for MySqlConnection {
         MySqlTransactionManager::get_transaction_depth(self)
     }
 
+    fn cached_statements_size(&self) -> usize {
+        Connection::cached_statements_size(self)
+    }
+
+    fn clear_cached_statements(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::clear_cached_statements(self)
+    }
+
+    fn cached_statements(&self) -> CachedStatements {
+        Connection::cached_statements(self)
+    }
+
+    fn set_statement_cache_capacity(
+        &mut self,
+        capacity: usize,
+    ) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::set_statement_cache_capacity(self, capacity)
+    }
+
     fn shrink_buffers(&mut self) {
         Connection::shrink_buffers(self);
     }
@@ -73,6 +104,13 @@ impl AnyConnectionBackend for MySqlConnection {
         Connection::should_flush(self)
     }
 
+    fn set_statement_journal(
+        &mut self,
+        journal: Option<StatementJournal>,
+    ) -> Option<StatementJournal> {
+        Connection::set_statement_journal(self, journal)
+    }
+
     #[cfg(feature = "migrate")]
     fn as_migrate(
         &mut self,
@@ -1,4 +1,5 @@
 use crate::encode::{Encode, IsNull};
+use crate::protocol::text::ColumnType;
 use crate::types::Type;
 use crate::{MySql, MySqlTypeInfo};
 pub(crate) use sqlx_core::arguments::*;
@@ -11,6 +12,8 @@ pub struct MySqlArguments {
     pub(crate) values: Vec<u8>,
     pub(crate) types: Vec<MySqlTypeInfo>,
     pub(crate) null_bitmap: NullBitMap,
+    // whether a `NaN` or infinite `FLOAT` or `DOUBLE` was added
+    pub(crate) non_finite: bool,
 }
 
 impl MySqlArguments {
@@ -30,6 +33,16 @@ impl MySqlArguments {
             }
         };
 
+        if !is_null.is_null() {
+            let value = &self.values[value_length_before_encoding..];
+
+            self.non_finite |= match (ty.r#type, value.len()) {
+                (ColumnType::Float, 4) => !f32::from_le_bytes(value.try_into()?).is_finite(),
+                (ColumnType::Double, 8) => !f64::from_le_bytes(value.try_into()?).is_finite(),
+                _ => false,
+            };
+        }
+
         self.types.push(ty);
         self.null_bitmap.push(is_null);
 
@@ -105,4 +118,21 @@ mod test {
 
         assert_eq!([0b01010101, 0b1].as_slice(), bit_map.deref());
     }
+
+    #[test]
+    fn it_tracks_non_finite_floats() {
+        let mut args = MySqlArguments::default();
+
+        args.add(1.5_f64).unwrap();
+        args.add(None::<f32>).unwrap();
+        args.add(i64::MAX).unwrap();
+        assert!(!args.non_finite);
+
+        args.add(f32::NAN).unwrap();
+        assert!(args.non_finite);
+
+        let mut args = MySqlArguments::default();
+        args.add(f64::NEG_INFINITY).unwrap();
+        assert!(args.non_finite);
+    }
 }
@@ -12,6 +12,52 @@ pub struct MySqlColumn {
 
     #[cfg_attr(feature = "offline", serde(skip))]
     pub(crate) flags: Option<ColumnFlags>,
+
+    #[cfg_attr(feature = "offline", serde(skip))]
+    pub(crate) origin: Option<MySqlColumnOrigin>,
+}
+
+/// Where a column of a result comes from, as sent by the server.
+#[derive(Debug, Clone)]
+pub(crate) struct MySqlColumnOrigin {
+    pub(crate) schema: UStr,
+    pub(crate) table: UStr,
+    pub(crate) table_alias: UStr,
+    pub(crate) name: UStr,
+}
+
+impl MySqlColumn {
+    /// Returns the name of the database of the table this column is from, if applicable.
+    ///
+    /// This will be `None` if the column is the result of an expression.
+    pub fn schema(&self) -> Option<&str> {
+        self.origin.as_ref().map(|origin| &*origin.schema)
+    }
+
+    /// Returns the name of the table this column is from, if applicable.
+    ///
+    /// This is the name of the table itself, even if the query gave it an alias (or, for a
+    /// view, the name of the view). This will be `None` if the column is the result of an
+    /// expression.
+    pub fn table(&self) -> Option<&str> {
+        self.origin.as_ref().map(|origin| &*origin.table)
+    }
+
+    /// Returns the name or alias by which the query refers to the table this column is from,
+    /// if applicable.
+    ///
+    /// This will be `None` if the column is the result of an expression.
+    pub fn table_alias(&self) -> Option<&str> {
+        self.origin.as_ref().map(|origin| &*origin.table_alias)
+    }
+
+    /// Returns the name of this column in its table, even if the query gave it an alias, if
+    /// applicable.
+    ///
+    /// This will be `None` if the column is the result of an expression.
+    pub fn original_name(&self) -> Option<&str> {
+        self.origin.as_ref().map(|origin| &*origin.name)
+    }
 }
 
 impl Column for MySqlColumn {
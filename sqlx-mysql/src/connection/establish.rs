@@ -30,6 +30,8 @@ impl MySqlConnection {
                 status_flags: Default::default(),
                 cache_statement: StatementCache::new(options.statement_cache_capacity),
                 log_settings: options.log_settings.clone(),
+                local_infile: options.local_infile.clone(),
+                reject_non_finite_floats: options.reject_non_finite_floats,
             }),
         })
     }
@@ -87,7 +89,15 @@ impl<'a> DoHandshake<'a> {
         // FIXME: server version parse is a bit ugly
         // expecting MAJOR.MINOR.PATCH
 
-        let mut server_version = handshake.server_version.split('.');
+        stream.is_mariadb = handshake.server_version.contains("MariaDB");
+
+        // MariaDB before 11.0 prefixes its version for compatibility with old MySQL clients
+        let server_version = match handshake.server_version.strip_prefix("5.5.5-") {
+            Some(version) if stream.is_mariadb => version,
+            _ => &handshake.server_version,
+        };
+
+        let mut server_version = server_version.split('.');
 
         let server_version_major: u16 = server_version
             .next()
@@ -119,7 +129,11 @@ impl<'a> DoHandshake<'a> {
         let mut stream = tls::maybe_upgrade(stream, self.options).await?;
 
         let auth_response = if let (Some(plugin), Some(password)) = (plugin, &options.password) {
-            Some(plugin.scramble(&mut stream, password, &nonce).await?)
+            Some(
+                plugin
+                    .scramble(&mut stream, password.as_str(), &nonce)
+                    .await?,
+            )
         } else {
             None
         };
@@ -155,7 +169,10 @@ impl<'a> DoHandshake<'a> {
                         .plugin
                         .scramble(
                             &mut stream,
-                            options.password.as_deref().unwrap_or_default(),
+                            options
+                                .password
+                                .as_ref()
+                                .map_or("", |password| password.as_str()),
                             &nonce,
                         )
                         .await?;
@@ -166,7 +183,10 @@ impl<'a> DoHandshake<'a> {
 
                 id => {
                     if let (Some(plugin), Some(password)) = (plugin, &options.password) {
-                        if plugin.handle(&mut stream, packet, password, &nonce).await? {
+                        if plugin
+                            .handle(&mut stream, packet, password.as_str(), &nonce)
+                            .await?
+                        {
                             // plugin signaled authentication is ok
                             break;
                         }
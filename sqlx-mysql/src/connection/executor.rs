@@ -1,16 +1,16 @@
 use super::MySqlStream;
+use crate::column::MySqlColumnOrigin;
 use crate::connection::stream::Waiting;
 use crate::describe::Describe;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
 use crate::ext::ustr::UStr;
-use crate::io::MySqlBufExt;
 use crate::logger::QueryLogger;
 use crate::protocol::response::Status;
 use crate::protocol::statement::{
     BinaryRow, Execute as StatementExecute, Prepare, PrepareOk, StmtClose,
 };
-use crate::protocol::text::{ColumnDefinition, ColumnFlags, Query, TextRow};
+use crate::protocol::text::{ColumnCount, ColumnDefinition, ColumnFlags, Query, TextRow};
 use crate::statement::{MySqlStatement, MySqlStatementMetadata};
 use crate::HashMap;
 use crate::{
@@ -108,6 +108,14 @@ impl MySqlConnection {
     {
         let mut logger = QueryLogger::new(sql, self.inner.log_settings.clone());
 
+        if self.inner.reject_non_finite_floats
+            && arguments.as_ref().is_some_and(|args| args.non_finite)
+        {
+            return Err(Error::Encode(
+                "MySQL does not support NaN or infinite floating-point values".into(),
+            ));
+        }
+
         self.inner.stream.wait_until_ready().await?;
         self.inner.stream.waiting.push_back(Waiting::Result);
 
@@ -158,8 +166,15 @@ impl MySqlConnection {
 
             loop {
                 // query response is a meta-packet which may be one of:
-                //  Ok, Err, ResultSet, or (unhandled) LocalInfileRequest
-                let mut packet = self.inner.stream.recv_packet().await?;
+                //  Ok, Err, ResultSet, or LocalInfileRequest
+                let packet = self.inner.stream.recv_packet().await?;
+
+                if packet[0] == 0xfb {
+                    // `LOAD DATA LOCAL INFILE` requests the contents of a file, after which
+                    // the server sends the response to the statement
+                    self.send_local_infile(&packet[1..]).await?;
+                    continue;
+                }
 
                 if packet[0] == 0x00 || packet[0] == 0xff {
                     // first packet in a query response is OK or ERR
@@ -168,12 +183,8 @@ impl MySqlConnection {
 
                     self.inner.status_flags = ok.status;
 
-                    let rows_affected = ok.affected_rows;
-                    logger.increase_rows_affected(rows_affected);
-                    let done = MySqlQueryResult {
-                        rows_affected,
-                        last_insert_id: ok.last_insert_id,
-                    };
+                    logger.increase_rows_affected(ok.affected_rows);
+                    let done = MySqlQueryResult::from_ok(&ok);
 
                     r#yield!(Either::Left(done));
 
@@ -189,9 +200,7 @@ impl MySqlConnection {
                 // otherwise, this first packet is the start of the result-set metadata,
                 *self.inner.stream.waiting.front_mut().unwrap() = Waiting::Row;
 
-                let num_columns = packet.get_uint_lenenc(); // column count
-                let num_columns = usize::try_from(num_columns)
-                    .map_err(|_| err_protocol!("column count overflows usize: {num_columns}"))?;
+                let ColumnCount(num_columns) = packet.decode()?;
 
                 if needs_metadata {
                     column_names = Arc::new(recv_result_metadata(&mut self.inner.stream, num_columns, Arc::make_mut(&mut columns)).await?);
@@ -212,10 +221,7 @@ impl MySqlConnection {
 
                         self.inner.status_flags = eof.status;
 
-                        r#yield!(Either::Left(MySqlQueryResult {
-                            rows_affected: 0,
-                            last_insert_id: 0,
-                        }));
+                        r#yield!(Either::Left(MySqlQueryResult::default()));
 
                         if eof.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                             // more result sets exist, continue to the next one
@@ -382,7 +388,10 @@ async fn recv_result_columns(
     Ok(())
 }
 
-fn recv_next_result_column(def: &ColumnDefinition, ordinal: usize) -> Result<MySqlColumn, Error> {
+pub(crate) fn recv_next_result_column(
+    def: &ColumnDefinition,
+    ordinal: usize,
+) -> Result<MySqlColumn, Error> {
     // if the alias is empty, use the alias
     // only then use the name
     let name = match (def.name()?, def.alias()?) {
@@ -392,11 +401,23 @@ fn recv_next_result_column(def: &ColumnDefinition, ordinal: usize) -> Result<MyS
 
     let type_info = MySqlTypeInfo::from_column(def);
 
+    // expressions have no table
+    let origin = match def.table()? {
+        "" => None,
+        table => Some(MySqlColumnOrigin {
+            schema: UStr::new(def.schema()?),
+            table: UStr::new(table),
+            table_alias: UStr::new(def.table_alias()?),
+            name: UStr::new(def.name()?),
+        }),
+    };
+
     Ok(MySqlColumn {
         name,
         type_info,
         ordinal,
         flags: Some(def.flags),
+        origin,
     })
 }
 
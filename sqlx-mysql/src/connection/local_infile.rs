@@ -0,0 +1,104 @@
+use std::fmt::{self, Debug, Formatter};
+use std::io;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures_core::stream::{BoxStream, Stream};
+use futures_util::{StreamExt, TryStreamExt};
+
+use crate::error::{BoxDynError, Error};
+use crate::protocol::response::Status;
+use crate::MySqlConnection;
+
+// the largest packet of file data sent; a packet of `0xFF_FF_FF` bytes or more would be split,
+// and could end with an empty packet, which also marks the end of the file
+const MAX_PACKET_LEN: usize = 0xFF_FF_FE;
+
+/// Supplies the contents of the files named by `LOAD DATA LOCAL INFILE` statements; see
+/// [`MySqlConnectOptions::local_infile_handler()`][crate::MySqlConnectOptions::local_infile_handler].
+#[derive(Clone)]
+pub(crate) struct LocalInfileHandler {
+    #[allow(clippy::type_complexity)]
+    open: Arc<dyn Fn(&str) -> BoxStream<'static, Result<Bytes, BoxDynError>> + Send + Sync>,
+}
+
+impl LocalInfileHandler {
+    pub(crate) fn new<F, S>(open: F) -> Self
+    where
+        F: Fn(&str) -> S + Send + Sync + 'static,
+        S: Stream<Item = Result<Bytes, BoxDynError>> + Send + 'static,
+    {
+        LocalInfileHandler {
+            open: Arc::new(move |file_name| open(file_name).boxed()),
+        }
+    }
+}
+
+impl Debug for LocalInfileHandler {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalInfileHandler").finish_non_exhaustive()
+    }
+}
+
+impl MySqlConnection {
+    /// Answer the request of the server for the contents of `file_name`, sent in place of the
+    /// result of a `LOAD DATA LOCAL INFILE` statement.
+    ///
+    /// https://dev.mysql.com/doc/dev/mysql-server/latest/page_protocol_com_query_response_local_infile_request.html
+    pub(crate) async fn send_local_infile(&mut self, file_name: &[u8]) -> Result<(), Error> {
+        let file_name = String::from_utf8_lossy(file_name);
+
+        let result = match &self.inner.local_infile {
+            Some(handler) => {
+                let mut data = (handler.open)(&file_name);
+                self.send_local_infile_data(&mut data).await?
+            }
+
+            // the server only sends the request if the client allows it, unless it misbehaves
+            None => Err(Error::Protocol(format!(
+                "server requested local file {file_name:?}, but no local infile handler is set"
+            ))),
+        };
+
+        // an empty packet marks the end of the file
+        self.inner.stream.write_packet(&[][..])?;
+        self.inner.stream.flush().await?;
+
+        let Err(error) = result else {
+            // the response to the statement follows
+            return Ok(());
+        };
+
+        // read the response here, so that the connection is ready for the next statement
+        // once the error is returned
+        let ok = self.inner.stream.recv_ok().await?;
+
+        if !ok.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
+            self.inner.stream.waiting.pop_front();
+        }
+
+        Err(error)
+    }
+
+    /// Send the chunks of `data` until its end, returning an error of the handler in `Ok` so that
+    /// the caller can still end the file; errors of the connection are returned in `Err`.
+    async fn send_local_infile_data(
+        &mut self,
+        data: &mut BoxStream<'static, Result<Bytes, BoxDynError>>,
+    ) -> Result<Result<(), Error>, Error> {
+        loop {
+            let chunk = match data.try_next().await {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => return Ok(Ok(())),
+                Err(error) => return Ok(Err(Error::Io(io::Error::other(error)))),
+            };
+
+            // an empty chunk would end the file early
+            for packet in chunk.chunks(MAX_PACKET_LEN) {
+                self.inner.stream.write_packet(packet)?;
+            }
+
+            self.inner.stream.flush().await?;
+        }
+    }
+}
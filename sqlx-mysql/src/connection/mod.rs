@@ -1,8 +1,11 @@
 use std::borrow::Cow;
 use std::fmt::{self, Debug, Formatter};
 
+#[cfg(any(test, feature = "fuzz"))]
+pub(crate) use executor::recv_next_result_column;
 use futures_core::future::BoxFuture;
 use futures_util::FutureExt;
+pub(crate) use local_infile::LocalInfileHandler;
 pub(crate) use sqlx_core::connection::*;
 pub(crate) use stream::{MySqlStream, Waiting};
 
@@ -12,12 +15,13 @@ use crate::protocol::response::Status;
 use crate::protocol::statement::StmtClose;
 use crate::protocol::text::{Ping, Quit};
 use crate::statement::MySqlStatementMetadata;
-use crate::transaction::Transaction;
+use crate::transaction::{StatementJournal, Transaction};
 use crate::{MySql, MySqlConnectOptions};
 
 mod auth;
 mod establish;
 mod executor;
+mod local_infile;
 mod stream;
 mod tls;
 
@@ -42,6 +46,12 @@ pub(crate) struct MySqlConnectionInner {
     cache_statement: StatementCache<(u32, MySqlStatementMetadata)>,
 
     log_settings: LogSettings,
+
+    // supplies the files requested by `LOAD DATA LOCAL INFILE`, if they are allowed
+    local_infile: Option<LocalInfileHandler>,
+
+    // fail queries binding `NaN` or infinite floats instead of sending them
+    reject_non_finite_floats: bool,
 }
 
 impl MySqlConnection {
@@ -113,11 +123,44 @@ impl Connection for MySqlConnection {
         })
     }
 
+    fn cached_statements(&self) -> CachedStatements {
+        CachedStatements {
+            size: self.inner.cache_statement.len(),
+            capacity: self.inner.cache_statement.capacity(),
+        }
+    }
+
+    fn set_statement_cache_capacity(
+        &mut self,
+        capacity: usize,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            for (statement_id, _) in self.inner.cache_statement.set_capacity(capacity) {
+                self.inner
+                    .stream
+                    .send_packet(StmtClose {
+                        statement: statement_id,
+                    })
+                    .await?;
+            }
+
+            Ok(())
+        })
+    }
+
     #[doc(hidden)]
     fn should_flush(&self) -> bool {
         !self.inner.stream.write_buffer().is_empty()
     }
 
+    #[doc(hidden)]
+    fn set_statement_journal(
+        &mut self,
+        journal: Option<StatementJournal>,
+    ) -> Option<StatementJournal> {
+        std::mem::replace(&mut self.inner.log_settings.journal, journal)
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
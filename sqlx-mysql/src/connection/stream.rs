@@ -5,10 +5,10 @@ use bytes::{Buf, Bytes, BytesMut};
 
 use crate::collation::{CharSet, Collation};
 use crate::error::Error;
-use crate::io::MySqlBufExt;
 use crate::io::{ProtocolDecode, ProtocolEncode};
 use crate::net::{BufferedSocket, Socket};
-use crate::protocol::response::{EofPacket, ErrPacket, OkPacket, Status};
+use crate::protocol::response::{EofPacket, OkPacket, Status};
+use crate::protocol::text::ColumnCount;
 use crate::protocol::{Capabilities, Packet};
 use crate::{MySqlConnectOptions, MySqlDatabaseError};
 
@@ -16,6 +16,7 @@ pub struct MySqlStream<S = Box<dyn Socket>> {
     // Wrapping the socket in `Box` allows us to unsize in-place.
     pub(crate) socket: BufferedSocket<S>,
     pub(crate) server_version: (u16, u16, u16),
+    pub(crate) is_mariadb: bool,
     pub(super) capabilities: Capabilities,
     pub(crate) sequence_id: u8,
     pub(crate) waiting: VecDeque<Waiting>,
@@ -43,7 +44,6 @@ impl<S: Socket> MySqlStream<S> {
         let mut capabilities = Capabilities::PROTOCOL_41
             | Capabilities::IGNORE_SPACE
             | Capabilities::DEPRECATE_EOF
-            | Capabilities::FOUND_ROWS
             | Capabilities::TRANSACTIONS
             | Capabilities::SECURE_CONNECTION
             | Capabilities::PLUGIN_AUTH_LENENC_DATA
@@ -57,10 +57,19 @@ impl<S: Socket> MySqlStream<S> {
             capabilities |= Capabilities::CONNECT_WITH_DB;
         }
 
+        if options.found_rows {
+            capabilities |= Capabilities::FOUND_ROWS;
+        }
+
+        if options.local_infile.is_some() {
+            capabilities |= Capabilities::LOCAL_FILES;
+        }
+
         Self {
             waiting: VecDeque::new(),
             capabilities,
             server_version: (0, 0, 0),
+            is_mariadb: false,
             sequence_id: 0,
             collation,
             charset,
@@ -98,6 +107,11 @@ impl<S: Socket> MySqlStream<S> {
                     if !ok.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                         self.waiting.pop_front();
                     }
+                } else if !packet.is_empty() && packet[0] == 0xfb {
+                    // the statement was dropped before the server requested a local file,
+                    // so send it an empty one; the response to the statement follows
+                    self.write_packet(&[][..])?;
+                    self.flush().await?;
                 } else {
                     *self.waiting.front_mut().unwrap() = Waiting::Row;
                     self.skip_result_metadata(packet).await?;
@@ -176,9 +190,7 @@ impl<S: Socket> MySqlStream<S> {
 
             // instead of letting this packet be looked at everywhere, we check here
             // and emit a proper Error
-            return Err(
-                MySqlDatabaseError(ErrPacket::decode_with(payload, self.capabilities)?).into(),
-            );
+            return Err(MySqlDatabaseError(Packet(payload).decode_with(self.capabilities)?).into());
         }
 
         Ok(Packet(payload))
@@ -203,8 +215,8 @@ impl<S: Socket> MySqlStream<S> {
         }
     }
 
-    async fn skip_result_metadata(&mut self, mut packet: Packet<Bytes>) -> Result<(), Error> {
-        let num_columns: u64 = packet.get_uint_lenenc(); // column count
+    async fn skip_result_metadata(&mut self, packet: Packet<Bytes>) -> Result<(), Error> {
+        let ColumnCount(num_columns) = packet.decode()?;
 
         for _ in 0..num_columns {
             let _ = self.recv_packet().await?;
@@ -219,6 +231,7 @@ impl<S: Socket> MySqlStream<S> {
         MySqlStream {
             socket: self.socket.boxed(),
             server_version: self.server_version,
+            is_mariadb: self.is_mariadb,
             capabilities: self.capabilities,
             sequence_id: self.sequence_id,
             waiting: self.waiting,
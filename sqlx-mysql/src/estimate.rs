@@ -0,0 +1,72 @@
+use futures_core::future::BoxFuture;
+use sqlx_core::estimate::{CountTarget, EstimateCount};
+use sqlx_core::row::Row;
+
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::query_scalar::query_scalar;
+use crate::{MySql, MySqlConnection};
+
+impl EstimateCount for MySql {
+    fn estimate_count<'c>(
+        conn: &'c mut MySqlConnection,
+        target: CountTarget<'c>,
+    ) -> BoxFuture<'c, Result<u64, Error>> {
+        Box::pin(async move {
+            let query = match target {
+                CountTarget::Table(table) => {
+                    let (schema, name) = match table.split_once('.') {
+                        Some((schema, name)) => (Some(unquote(schema)), unquote(name)),
+                        None => (None, unquote(table)),
+                    };
+
+                    // language=MySQL
+                    let rows: Option<u64> = query_scalar(
+                        "SELECT TABLE_ROWS FROM information_schema.TABLES \
+                         WHERE TABLE_SCHEMA = COALESCE(?, DATABASE()) AND TABLE_NAME = ?",
+                    )
+                    .bind(schema)
+                    .bind(name)
+                    .fetch_optional(&mut *conn)
+                    .await?
+                    .ok_or_else(|| {
+                        Error::InvalidArgument(format!("table {table:?} does not exist"))
+                    })?;
+
+                    // `TABLE_ROWS` is `NULL` for views
+                    if let Some(rows) = rows {
+                        return Ok(rows);
+                    }
+
+                    format!("SELECT * FROM {table}")
+                }
+                CountTarget::Query(query) => query.to_owned(),
+            };
+
+            let plan = conn.fetch_all(&*format!("EXPLAIN {query}")).await?;
+
+            // One row is returned for each table read, which are joined in nested loops, so
+            // the estimate for the query is the product of the rows each of them returns.
+            let mut estimate = 1.0;
+
+            for row in plan {
+                let rows: Option<u64> = row.try_get_unchecked("rows")?;
+                let filtered: Option<f64> = row.try_get_unchecked("filtered")?;
+
+                #[allow(clippy::cast_precision_loss)]
+                let rows = rows.unwrap_or(1) as f64;
+
+                estimate *= rows * filtered.unwrap_or(100.0) / 100.0;
+            }
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Ok(estimate.round() as u64)
+        })
+    }
+}
+
+fn unquote(name: &str) -> &str {
+    name.strip_prefix('`')
+        .and_then(|name| name.strip_suffix('`'))
+        .unwrap_or(name)
+}
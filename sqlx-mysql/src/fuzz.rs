@@ -0,0 +1,205 @@
+//! Entry points for the fuzz targets in `fuzz/`; not part of the public API.
+
+use bytes::{Buf, Bytes};
+
+use crate::connection::recv_next_result_column;
+use crate::error::Error;
+use crate::protocol::connect::{AuthSwitchRequest, Handshake};
+use crate::protocol::response::{EofPacket, ErrPacket};
+use crate::protocol::statement::{BinaryRow, PrepareOk};
+use crate::protocol::text::{ColumnCount, ColumnDefinition, TextRow};
+use crate::protocol::{Capabilities, Packet};
+
+/// Read `data` as the packets sent by a server to a connection: a handshake, then the
+/// responses to queries, decoding each packet as the connection would.
+///
+/// Stops at the first packet which is incomplete or fails to decode, and returns its error.
+pub fn server_packets(data: &[u8]) -> Result<(), Error> {
+    let mut packets = Packets(Bytes::copy_from_slice(data));
+
+    let Some(handshake) = packets.next()? else {
+        return Ok(());
+    };
+    let handshake: Handshake = handshake.decode()?;
+    let capabilities = handshake.server_capabilities;
+
+    while let Some(packet) = packets.next()? {
+        match packet[0] {
+            // the response to `COM_STMT_PREPARE` starts with 0x00 as well, the connection
+            // knows which one to expect
+            0x00 if packet.len() == 12 => {
+                packet.decode_with::<PrepareOk, _>(capabilities)?;
+            }
+            0x00 => {
+                packet.ok()?;
+            }
+            0xff => {
+                packet.decode_with::<ErrPacket, _>(capabilities)?;
+            }
+            0xfe => {
+                packet.decode_with::<AuthSwitchRequest, _>(true)?;
+            }
+            _ => result_set(&mut packets, packet, capabilities)?,
+        }
+    }
+
+    Ok(())
+}
+
+fn result_set(
+    packets: &mut Packets,
+    packet: Packet<Bytes>,
+    capabilities: Capabilities,
+) -> Result<(), Error> {
+    let ColumnCount(num_columns) = packet.decode()?;
+
+    let mut columns = Vec::new();
+
+    for ordinal in 0..num_columns {
+        let Some(packet) = packets.next()? else {
+            return Ok(());
+        };
+        let def: ColumnDefinition = packet.decode_with(capabilities)?;
+
+        columns.push(recv_next_result_column(&def, ordinal)?);
+    }
+
+    if !capabilities.contains(Capabilities::DEPRECATE_EOF) {
+        let Some(packet) = packets.next()? else {
+            return Ok(());
+        };
+        packet.decode_with::<EofPacket, _>(capabilities)?;
+    }
+
+    while let Some(packet) = packets.next()? {
+        if packet[0] == 0xfe && packet.len() < 9 {
+            packet.eof(capabilities)?;
+            break;
+        }
+
+        // rows of prepared statements start with 0x00, which text rows may also start with
+        let row = if packet[0] == 0x00 {
+            packet.decode_with::<BinaryRow, _>(&columns)?.0
+        } else {
+            packet.decode_with::<TextRow, _>(&columns)?.0
+        };
+
+        for index in 0..columns.len() {
+            row.get(index);
+        }
+    }
+
+    Ok(())
+}
+
+/// Splits packets off the bytes sent by a server, like the stream of a connection.
+struct Packets(Bytes);
+
+impl Packets {
+    fn next(&mut self) -> Result<Option<Packet<Bytes>>, Error> {
+        if self.0.len() < 4 {
+            return Ok(None);
+        }
+
+        // cannot overflow
+        #[allow(clippy::cast_possible_truncation)]
+        let packet_size = self.0.get_uint_le(3) as usize;
+        let _sequence_id = self.0.get_u8();
+
+        if self.0.len() < packet_size {
+            return Ok(None);
+        }
+
+        let payload = self.0.split_to(packet_size);
+
+        if payload.is_empty() {
+            return Err(err_protocol!("Packet empty"));
+        }
+
+        Ok(Some(Packet(payload)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A handshake, and the responses to a query and to the preparation and execution of a
+    /// statement, like the seed of the fuzz target.
+    const PACKETS: &[&[u8]] = &[
+        b"\x0a8.0.18\0\x19\0\0\0\x114aB0c\x06g\0\xff\xff\xff\x02\0\xff\xc7\x15\0\0\0\0\0\0\0\0\0\0\
+          tL\x03s\x0f[4\x0dl4. \0caching_sha2_password\0",
+        b"\xfemysql_native_password\x000123456789abcdefghij\0",
+        b"\0\0\0\x02\0\0\0",
+        // SELECT id, name FROM tweet
+        b"\x02",
+        b"\x03def\x04sqlx\x05tweet\x05tweet\x02id\x02id\x0c\xff\0\x0b\0\0\0\x03\x03\x10\0\0\0",
+        b"\x03def\x04sqlx\x05tweet\x05tweet\x04name\x04name\x0c\xff\0\xfc\x03\0\0\xfd\0\0\0\0\0",
+        b"\x0242\x05hello",
+        b"\x0243\xfb",
+        b"\xfe\0\0\x02\0\0\0",
+        // the same, prepared and executed
+        b"\0\x01\0\0\0\x02\0\x01\0\0\0\0",
+        b"\x02",
+        b"\x03def\x04sqlx\x05tweet\x05tweet\x02id\x02id\x0c\xff\0\x0b\0\0\0\x03\x03\x10\0\0\0",
+        b"\x03def\x04sqlx\x05tweet\x05tweet\x04name\x04name\x0c\xff\0\xfc\x03\0\0\xfd\0\0\0\0\0",
+        b"\0\0*\0\0\0\x05hello",
+        b"\0\x08+\0\0\0",
+        b"\xfe\0\0\x02\0\0\0",
+        b"\xff\x19\x04#42000Unknown database 'unknown'",
+    ];
+
+    fn with_packet(preceding: &[&[u8]], payload: &[u8]) -> Vec<u8> {
+        let mut data = Vec::new();
+
+        for packet in preceding.iter().chain([&payload]) {
+            data.extend(&packet.len().to_le_bytes()[..3]);
+            data.push(0);
+            data.extend(*packet);
+        }
+
+        data
+    }
+
+    #[track_caller]
+    fn decode(data: &[u8]) -> Result<(), Error> {
+        let result = server_packets(data);
+
+        if let Err(error) = &result {
+            assert!(
+                matches!(error, Error::Protocol(_)),
+                "expected a protocol error, got {error:?} in {data:02x?}"
+            );
+        }
+
+        result
+    }
+
+    // `fuzz/` finds more, but is not run with the tests
+    #[test]
+    fn it_fails_on_truncated_and_corrupted_packets_without_panicking() {
+        let (last, preceding) = PACKETS.split_last().unwrap();
+        decode(&with_packet(preceding, last)).unwrap();
+
+        let handshake = PACKETS[0];
+
+        // the handshake ends with the NUL-terminated name of the auth plugin
+        for len in 0..handshake.len() {
+            decode(&with_packet(&[], &handshake[..len])).unwrap_err();
+        }
+
+        for (i, packet) in PACKETS.iter().enumerate() {
+            for len in 0..packet.len() {
+                let _ = decode(&with_packet(&PACKETS[..i], &packet[..len]));
+            }
+
+            for index in 0..packet.len() {
+                for byte in [0x00, 0xfb, 0xfe, 0xff] {
+                    let mut corrupted = packet.to_vec();
+                    corrupted[index] = byte;
+                    let _ = decode(&with_packet(&PACKETS[..i], &corrupted));
+                }
+            }
+        }
+    }
+}
@@ -8,7 +8,7 @@ pub trait MySqlBufExt: Buf {
     // NOTE: 0xfb or NULL is only returned for binary value encoding to indicate NULL.
     // NOTE: 0xff is only returned during a result set to indicate ERR.
     // <https://dev.mysql.com/doc/internals/en/integer.html#packet-Protocol::LengthEncodedInteger>
-    fn get_uint_lenenc(&mut self) -> u64;
+    fn get_uint_lenenc(&mut self) -> Result<u64, Error>;
 
     // Read a length-encoded string.
     #[allow(dead_code)]
@@ -19,18 +19,38 @@ pub trait MySqlBufExt: Buf {
 }
 
 impl MySqlBufExt for Bytes {
-    fn get_uint_lenenc(&mut self) -> u64 {
-        match self.get_u8() {
-            0xfc => u64::from(self.get_u16_le()),
-            0xfd => self.get_uint_le(3),
-            0xfe => self.get_u64_le(),
+    fn get_uint_lenenc(&mut self) -> Result<u64, Error> {
+        let Some(&first) = self.first() else {
+            return Err(err_protocol!(
+                "expected a length-encoded integer, got 0 bytes"
+            ));
+        };
 
-            v => u64::from(v),
+        let size = match first {
+            0xfc => 2,
+            0xfd => 3,
+            0xfe => 8,
+            _ => 0,
+        };
+
+        if self.len() < 1 + size {
+            return Err(err_protocol!(
+                "expected a length-encoded integer of {} bytes, got {}",
+                1 + size,
+                self.len()
+            ));
         }
+
+        self.advance(1);
+
+        Ok(match size {
+            0 => u64::from(first),
+            size => self.get_uint_le(size),
+        })
     }
 
     fn get_str_lenenc(&mut self) -> Result<String, Error> {
-        let size = self.get_uint_lenenc();
+        let size = self.get_uint_lenenc()?;
         let size = usize::try_from(size)
             .map_err(|_| err_protocol!("string length overflows usize: {size}"))?;
 
@@ -38,10 +58,10 @@ impl MySqlBufExt for Bytes {
     }
 
     fn get_bytes_lenenc(&mut self) -> Result<Bytes, Error> {
-        let size = self.get_uint_lenenc();
+        let size = self.get_uint_lenenc()?;
         let size = usize::try_from(size)
             .map_err(|_| err_protocol!("string length overflows usize: {size}"))?;
 
-        Ok(self.split_to(size))
+        self.get_bytes(size)
     }
 }
@@ -0,0 +1,164 @@
+use bytes::Buf;
+use sqlx_core::json_writer::{bytes_to_json, f32_to_json, float_to_json, DecodeJson};
+use sqlx_core::types::JsonValue;
+
+use crate::decode::Decode;
+use crate::error::BoxDynError;
+use crate::protocol::text::{ColumnFlags, ColumnType};
+use crate::types::MySqlTime;
+use crate::{MySql, MySqlValueFormat, MySqlValueRef};
+
+impl DecodeJson for MySql {
+    fn decode_json(value: MySqlValueRef<'_>) -> Result<JsonValue, BoxDynError> {
+        let ty = &value.type_info;
+        let is_binary = ty.flags.contains(ColumnFlags::BINARY);
+
+        Ok(match ty.r#type {
+            ColumnType::Null => JsonValue::Null,
+
+            ColumnType::Tiny if ty.max_size == Some(1) => {
+                <bool as Decode<MySql>>::decode(value)?.into()
+            }
+
+            ColumnType::Tiny
+            | ColumnType::Short
+            | ColumnType::Long
+            | ColumnType::Int24
+            | ColumnType::LongLong
+            | ColumnType::Year
+                if ty.flags.contains(ColumnFlags::UNSIGNED) =>
+            {
+                <u64 as Decode<MySql>>::decode(value)?.into()
+            }
+
+            ColumnType::Tiny
+            | ColumnType::Short
+            | ColumnType::Long
+            | ColumnType::Int24
+            | ColumnType::LongLong
+            | ColumnType::Year => <i64 as Decode<MySql>>::decode(value)?.into(),
+
+            ColumnType::Bit => <u64 as Decode<MySql>>::decode(value)?.into(),
+
+            ColumnType::Float => f32_to_json(<f32 as Decode<MySql>>::decode(value)?),
+            ColumnType::Double => float_to_json(<f64 as Decode<MySql>>::decode(value)?),
+
+            // sent as text in both protocols; a string keeps the precision
+            ColumnType::Decimal | ColumnType::NewDecimal => value.as_str()?.into(),
+
+            ColumnType::Json => <JsonValue as Decode<MySql>>::decode(value)?,
+
+            ColumnType::Time => match value.format() {
+                MySqlValueFormat::Text => value.as_str()?.into(),
+                MySqlValueFormat::Binary => <MySqlTime as Decode<MySql>>::decode(value)?
+                    .to_string()
+                    .into(),
+            },
+
+            ColumnType::Date | ColumnType::Datetime | ColumnType::Timestamp => {
+                match value.format() {
+                    MySqlValueFormat::Text => value.as_str()?.into(),
+                    MySqlValueFormat::Binary => {
+                        format_datetime(ty.r#type, value.as_bytes()?)?.into()
+                    }
+                }
+            }
+
+            ColumnType::VarChar
+            | ColumnType::VarString
+            | ColumnType::String
+            | ColumnType::TinyBlob
+            | ColumnType::Blob
+            | ColumnType::MediumBlob
+            | ColumnType::LongBlob
+                if is_binary =>
+            {
+                bytes_to_json(value.as_bytes()?)
+            }
+
+            ColumnType::VarChar
+            | ColumnType::VarString
+            | ColumnType::String
+            | ColumnType::TinyBlob
+            | ColumnType::Blob
+            | ColumnType::MediumBlob
+            | ColumnType::LongBlob
+            | ColumnType::Enum
+            | ColumnType::Set => value.as_str()?.into(),
+
+            ColumnType::Geometry => {
+                return Err(format!("cannot convert a value of type {ty} to JSON").into())
+            }
+        })
+    }
+}
+
+/// Format a `DATE`, `DATETIME` or `TIMESTAMP` in the binary protocol the way MySQL does.
+fn format_datetime(ty: ColumnType, mut buf: &[u8]) -> Result<String, BoxDynError> {
+    if buf.is_empty() {
+        return Err("empty buffer".into());
+    }
+
+    // the length is 0, 4, 7 or 11, and the fields left out are all zero
+    let len = buf.get_u8();
+
+    if buf.len() < usize::from(len) || !matches!(len, 0 | 4 | 7 | 11) {
+        return Err(format!("invalid length {len} for a date or time value").into());
+    }
+
+    let (year, month, day) = match len {
+        0 => (0, 0, 0),
+        _ => (buf.get_u16_le(), buf.get_u8(), buf.get_u8()),
+    };
+
+    let mut formatted = format!("{year:04}-{month:02}-{day:02}");
+
+    if ty != ColumnType::Date {
+        let (hour, minute, second) = match len {
+            0 | 4 => (0, 0, 0),
+            _ => (buf.get_u8(), buf.get_u8(), buf.get_u8()),
+        };
+
+        formatted.push_str(&format!(" {hour:02}:{minute:02}:{second:02}"));
+
+        if len == 11 {
+            let micros = buf.get_u32_le();
+
+            if micros != 0 {
+                formatted.push_str(&format!(".{micros:06}"));
+            }
+        }
+    }
+
+    Ok(formatted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_datetime() {
+        assert_eq!(
+            format_datetime(ColumnType::Date, &[4, 0xE8, 0x07, 10, 14]).unwrap(),
+            "2024-10-14"
+        );
+        assert_eq!(
+            format_datetime(ColumnType::Datetime, &[7, 0xE8, 0x07, 10, 14, 9, 5, 0]).unwrap(),
+            "2024-10-14 09:05:00"
+        );
+        assert_eq!(
+            format_datetime(
+                ColumnType::Timestamp,
+                &[11, 0xE8, 0x07, 10, 14, 9, 5, 0, 0x40, 0xE2, 0x01, 0x00]
+            )
+            .unwrap(),
+            "2024-10-14 09:05:00.123456"
+        );
+        assert_eq!(
+            format_datetime(ColumnType::Datetime, &[0]).unwrap(),
+            "0000-00-00 00:00:00"
+        );
+        assert!(format_datetime(ColumnType::Date, &[4, 0xE8]).is_err());
+    }
+}
@@ -19,7 +19,13 @@ mod column;
 mod connection;
 mod database;
 mod error;
+mod estimate;
+#[cfg(any(test, feature = "fuzz"))]
+#[doc(hidden)]
+pub mod fuzz;
 mod io;
+#[cfg(feature = "json")]
+mod json_writer;
 mod options;
 mod protocol;
 mod query_result;
@@ -34,6 +40,9 @@ mod value;
 #[cfg(feature = "migrate")]
 mod migrate;
 
+#[cfg(feature = "monitoring")]
+pub mod monitoring;
+
 #[cfg(feature = "migrate")]
 mod testing;
 
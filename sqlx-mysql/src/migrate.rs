@@ -1,6 +1,6 @@
 use std::str::FromStr;
-use std::time::Duration;
 use std::time::Instant;
+use std::time::{Duration, UNIX_EPOCH};
 
 use futures_core::future::BoxFuture;
 pub(crate) use sqlx_core::migrate::*;
@@ -75,33 +75,94 @@ impl MigrateDatabase for MySql {
 }
 
 impl Migrate for MySqlConnection {
-    fn ensure_migrations_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+    fn ensure_migrations_table<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            self.execute(&*create_migrations_table_sql(table_name))
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn migrations_table_version<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<u32>, MigrateError>> {
         Box::pin(async move {
+            let (schema, table) = split_table_name(table_name);
+
+            // Each version is recognized by a column it added.
             // language=MySQL
-            self.execute(
+            let columns: Vec<String> = query_scalar(
                 r#"
-CREATE TABLE IF NOT EXISTS _sqlx_migrations (
-    version BIGINT PRIMARY KEY,
-    description TEXT NOT NULL,
-    installed_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
-    success BOOLEAN NOT NULL,
-    checksum BLOB NOT NULL,
-    execution_time BIGINT NOT NULL
-);
+SELECT CAST(column_name AS CHAR) FROM information_schema.columns
+WHERE table_schema = COALESCE(?, DATABASE()) AND table_name = ?
                 "#,
             )
+            .bind(schema)
+            .bind(table)
+            .fetch_all(&mut *self)
             .await?;
 
+            Ok(match () {
+                _ if columns.is_empty() => None,
+                _ if columns.iter().any(|c| c.eq_ignore_ascii_case("skipped")) => Some(3),
+                _ if columns
+                    .iter()
+                    .any(|c| c.eq_ignore_ascii_case("app_version")) =>
+                {
+                    Some(2)
+                }
+                _ => Some(1),
+            })
+        })
+    }
+
+    fn upgrade_migrations_table<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        from: u32,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // MySQL commits DDL implicitly, so this can't be done in a transaction;
+            // each version is upgraded with a single, atomic `ALTER TABLE` instead.
+            if from < 2 {
+                // language=MySQL
+                self.execute(&*format!(
+                    r#"
+ALTER TABLE {table_name}
+    ADD COLUMN applied_by TEXT,
+    ADD COLUMN hostname TEXT,
+    ADD COLUMN app_version TEXT
+                    "#
+                ))
+                .await?;
+            }
+
+            if from < 3 {
+                // language=MySQL
+                self.execute(&*format!(
+                    "ALTER TABLE {table_name} ADD COLUMN skipped BOOLEAN NOT NULL DEFAULT FALSE"
+                ))
+                .await?;
+            }
+
             Ok(())
         })
     }
 
-    fn dirty_version(&mut self) -> BoxFuture<'_, Result<Option<i64>, MigrateError>> {
+    fn dirty_version<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<i64>, MigrateError>> {
         Box::pin(async move {
             // language=SQL
-            let row: Option<(i64,)> = query_as(
-                "SELECT version FROM _sqlx_migrations WHERE success = false ORDER BY version LIMIT 1",
-            )
+            let row: Option<(i64,)> = query_as(&format!(
+                "SELECT version FROM {table_name} WHERE success = false ORDER BY version LIMIT 1"
+            ))
             .fetch_optional(self)
             .await?;
 
@@ -109,28 +170,60 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
         })
     }
 
-    fn list_applied_migrations(
-        &mut self,
-    ) -> BoxFuture<'_, Result<Vec<AppliedMigration>, MigrateError>> {
+    fn list_applied_migrations<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>> {
         Box::pin(async move {
             // language=SQL
-            let rows: Vec<(i64, Vec<u8>)> =
-                query_as("SELECT version, checksum FROM _sqlx_migrations ORDER BY version")
-                    .fetch_all(self)
-                    .await?;
+            let rows: Vec<AppliedMigrationRow> = query_as(&format!(
+                r#"
+SELECT version, checksum, execution_time, applied_by, hostname, app_version,
+    CAST(UNIX_TIMESTAMP(installed_on) AS SIGNED), skipped
+FROM {table_name}
+ORDER BY version
+                "#
+            ))
+            .fetch_all(self)
+            .await?;
 
             let migrations = rows
                 .into_iter()
-                .map(|(version, checksum)| AppliedMigration {
-                    version,
-                    checksum: checksum.into(),
-                })
+                .map(
+                    |(
+                        version,
+                        checksum,
+                        execution_time,
+                        applied_by,
+                        hostname,
+                        app_version,
+                        installed_on,
+                        skipped,
+                    )| {
+                        let mut migration = AppliedMigration::new(version, checksum.into());
+                        // `-1` until the time of a completed migration has been recorded
+                        migration.execution_time =
+                            u64::try_from(execution_time).ok().map(Duration::from_nanos);
+                        migration.applied_by = applied_by;
+                        migration.hostname = hostname;
+                        migration.app_version = app_version;
+                        migration.installed_on = installed_on
+                            .and_then(|secs| u64::try_from(secs).ok())
+                            .map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+                        migration.skipped = skipped;
+                        migration
+                    },
+                )
                 .collect();
 
             Ok(migrations)
         })
     }
 
+    fn transactional_ddl(&mut self) -> bool {
+        false
+    }
+
     fn lock(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
         Box::pin(async move {
             let database_name = current_database(self).await?;
@@ -169,80 +262,89 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
 
     fn apply<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
         Box::pin(async move {
-            // Use a single transaction for the actual migration script and the essential bookeeping so we never
-            // execute migrations twice. See https://github.com/launchbadge/sqlx/issues/1966.
-            // The `execution_time` however can only be measured for the whole transaction. This value _only_ exists for
-            // data lineage and debugging reasons, so it is not super important if it is lost. So we initialize it to -1
-            // and update it once the actual transaction completed.
-            let mut tx = self.begin().await?;
-            let start = Instant::now();
+            let metadata = MigrationMetadata::current();
+            apply_migration(self, table_name, migration, &metadata).await
+        })
+    }
 
-            // For MySQL we cannot really isolate migrations due to implicit commits caused by table modification, see
-            // https://dev.mysql.com/doc/refman/8.0/en/implicit-commit.html
-            //
-            // To somewhat try to detect this, we first insert the migration into the migration table with
-            // `success=FALSE` and later modify the flag.
-            //
-            // language=MySQL
-            let _ = query(
-                r#"
-    INSERT INTO _sqlx_migrations ( version, description, success, checksum, execution_time )
-    VALUES ( ?, ?, FALSE, ?, -1 )
-                "#,
-            )
-            .bind(migration.version)
-            .bind(&*migration.description)
-            .bind(&*migration.checksum)
-            .execute(&mut *tx)
-            .await?;
+    fn apply_with_metadata<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+        metadata: &'m MigrationMetadata,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        Box::pin(apply_migration(self, table_name, migration, metadata))
+    }
+
+    fn ensure_migrations_table_script(&mut self, table_name: &str) -> Result<String, MigrateError> {
+        Ok(create_migrations_table_sql(table_name))
+    }
 
-            let _ = tx
-                .execute(&*migration.sql)
-                .await
-                .map_err(|e| MigrateError::ExecuteMigration(e, migration.version))?;
+    fn apply_script(
+        &mut self,
+        table_name: &str,
+        migration: &Migration,
+        metadata: &MigrationMetadata,
+    ) -> Result<String, MigrateError> {
+        let mut script = String::from("START TRANSACTION;\n\n");
 
+        if migration.migration_type.is_repeatable() {
             // language=MySQL
-            let _ = query(
-                r#"
-    UPDATE _sqlx_migrations
-    SET success = TRUE
-    WHERE version = ?
-                "#,
-            )
-            .bind(migration.version)
-            .execute(&mut *tx)
-            .await?;
+            script.push_str(&format!(
+                "DELETE FROM {table_name} WHERE version = {};\n\n",
+                migration.version
+            ));
+        }
+
+        // language=MySQL
+        script.push_str(&format!(
+            r#"INSERT INTO {table_name} (
+    version, description, success, checksum, execution_time, applied_by, hostname, app_version
+)
+VALUES ( {}, {}, FALSE, X'{}', -1, CURRENT_USER(), {}, {} );
+
+"#,
+            migration.version,
+            quote_literal(&migration.description),
+            hex::encode(&migration.checksum),
+            metadata
+                .hostname
+                .as_deref()
+                .map_or("NULL".into(), quote_literal),
+            metadata
+                .app_version
+                .as_deref()
+                .map_or("NULL".into(), quote_literal),
+        ));
 
-            tx.commit().await?;
+        script.push_str(migration.sql()?.trim());
+        if !script.ends_with(';') {
+            script.push(';');
+        }
 
-            // Update `elapsed_time`.
-            // NOTE: The process may disconnect/die at this point, so the elapsed time value might be lost. We accept
-            //       this small risk since this value is not super important.
+        // language=MySQL
+        script.push_str(&format!(
+            r#"
 
-            let elapsed = start.elapsed();
+UPDATE {table_name}
+SET success = TRUE
+WHERE version = {};
 
-            #[allow(clippy::cast_possible_truncation)]
-            let _ = query(
-                r#"
-    UPDATE _sqlx_migrations
-    SET execution_time = ?
-    WHERE version = ?
-                "#,
-            )
-            .bind(elapsed.as_nanos() as i64)
-            .bind(migration.version)
-            .execute(self)
-            .await?;
+COMMIT;
+"#,
+            migration.version
+        ));
 
-            Ok(elapsed)
-        })
+        Ok(script)
     }
 
     fn revert<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
         Box::pin(async move {
@@ -258,21 +360,21 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
             // `success=FALSE` and later remove the migration altogether.
             //
             // language=MySQL
-            let _ = query(
+            let _ = query(&format!(
                 r#"
-    UPDATE _sqlx_migrations
+    UPDATE {table_name}
     SET success = FALSE
     WHERE version = ?
-                "#,
-            )
+                "#
+            ))
             .bind(migration.version)
             .execute(&mut *tx)
             .await?;
 
-            tx.execute(&*migration.sql).await?;
+            tx.execute(&*migration.sql()?).await?;
 
             // language=SQL
-            let _ = query(r#"DELETE FROM _sqlx_migrations WHERE version = ?"#)
+            let _ = query(&format!(r#"DELETE FROM {table_name} WHERE version = ?"#))
                 .bind(migration.version)
                 .execute(&mut *tx)
                 .await?;
@@ -284,6 +386,218 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
             Ok(elapsed)
         })
     }
+
+    fn mark_applied<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+        metadata: &'m MigrationMetadata,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(record_unrun_migration(
+            self, table_name, migration, metadata, false,
+        ))
+    }
+
+    fn mark_skipped<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+        metadata: &'m MigrationMetadata,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(record_unrun_migration(
+            self, table_name, migration, metadata, true,
+        ))
+    }
+
+    fn update_checksum<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        version: i64,
+        checksum: &'e [u8],
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=MySQL
+            let _ = query(&format!(
+                r#"UPDATE {table_name} SET checksum = ? WHERE version = ?"#
+            ))
+            .bind(checksum)
+            .bind(version)
+            .execute(self)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn remove_applied_migration<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        version: i64,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=MySQL
+            let _ = query(&format!(r#"DELETE FROM {table_name} WHERE version = ?"#))
+                .bind(version)
+                .execute(self)
+                .await?;
+
+            Ok(())
+        })
+    }
+}
+
+fn create_migrations_table_sql(table_name: &str) -> String {
+    // language=MySQL
+    format!(
+        r#"
+CREATE TABLE IF NOT EXISTS {table_name} (
+    version BIGINT PRIMARY KEY,
+    description TEXT NOT NULL,
+    installed_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    success BOOLEAN NOT NULL,
+    checksum BLOB NOT NULL,
+    execution_time BIGINT NOT NULL,
+    applied_by TEXT,
+    hostname TEXT,
+    app_version TEXT,
+    skipped BOOLEAN NOT NULL DEFAULT FALSE
+);
+"#
+    )
+}
+
+// backslashes start an escape sequence unless `NO_BACKSLASH_ESCAPES` is set, so escape them too
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''"))
+}
+
+type AppliedMigrationRow = (
+    i64,
+    Vec<u8>,
+    i64,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<i64>,
+    bool,
+);
+
+/// Record `migration` as applied without running it, either because it was applied by other
+/// means or because it is `skipped`.
+async fn record_unrun_migration(
+    conn: &mut MySqlConnection,
+    table_name: &str,
+    migration: &Migration,
+    metadata: &MigrationMetadata,
+    skipped: bool,
+) -> Result<(), MigrateError> {
+    // language=MySQL
+    let _ = query(&format!(
+        r#"
+    INSERT INTO {table_name} (
+        version, description, success, checksum, execution_time, applied_by, hostname,
+        app_version, skipped
+    )
+    VALUES ( ?, ?, TRUE, ?, -1, CURRENT_USER(), ?, ?, ? )
+        "#
+    ))
+    .bind(migration.version)
+    .bind(&*migration.description)
+    .bind(&*migration.checksum)
+    .bind(&metadata.hostname)
+    .bind(&metadata.app_version)
+    .bind(skipped)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn apply_migration(
+    conn: &mut MySqlConnection,
+    table_name: &str,
+    migration: &Migration,
+    metadata: &MigrationMetadata,
+) -> Result<Duration, MigrateError> {
+    // Use a single transaction for the actual migration script and the essential bookeeping so we never
+    // execute migrations twice. See https://github.com/launchbadge/sqlx/issues/1966.
+    // The `execution_time` however can only be measured for the whole transaction. This value _only_ exists for
+    // data lineage and debugging reasons, so it is not super important if it is lost. So we initialize it to -1
+    // and update it once the actual transaction completed.
+    let mut tx = conn.begin().await?;
+    let start = Instant::now();
+
+    // For MySQL we cannot really isolate migrations due to implicit commits caused by table modification, see
+    // https://dev.mysql.com/doc/refman/8.0/en/implicit-commit.html
+    //
+    // To somewhat try to detect this, we first insert the migration into the migration table with
+    // `success=FALSE` and later modify the flag.
+    //
+    // A repeatable migration replaces the record of its previous run.
+    if migration.migration_type.is_repeatable() {
+        // language=MySQL
+        let _ = query(&format!(r#"DELETE FROM {table_name} WHERE version = ?"#))
+            .bind(migration.version)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    // language=MySQL
+    let _ = query(&format!(
+        r#"
+    INSERT INTO {table_name} (
+        version, description, success, checksum, execution_time, applied_by, hostname, app_version
+    )
+    VALUES ( ?, ?, FALSE, ?, -1, CURRENT_USER(), ?, ? )
+        "#
+    ))
+    .bind(migration.version)
+    .bind(&*migration.description)
+    .bind(&*migration.checksum)
+    .bind(&metadata.hostname)
+    .bind(&metadata.app_version)
+    .execute(&mut *tx)
+    .await?;
+
+    let _ = tx
+        .execute(&*migration.sql()?)
+        .await
+        .map_err(|e| MigrateError::ExecuteMigration(e, migration.version))?;
+
+    // language=MySQL
+    let _ = query(&format!(
+        r#"
+    UPDATE {table_name}
+    SET success = TRUE
+    WHERE version = ?
+        "#
+    ))
+    .bind(migration.version)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    // Update `elapsed_time`.
+    // NOTE: The process may disconnect/die at this point, so the elapsed time value might be lost. We accept
+    //       this small risk since this value is not super important.
+
+    let elapsed = start.elapsed();
+
+    #[allow(clippy::cast_possible_truncation)]
+    let _ = query(&format!(
+        r#"
+    UPDATE {table_name}
+    SET execution_time = ?
+    WHERE version = ?
+        "#
+    ))
+    .bind(elapsed.as_nanos() as i64)
+    .bind(migration.version)
+    .execute(conn)
+    .await?;
+
+    Ok(elapsed)
 }
 
 async fn current_database(conn: &mut MySqlConnection) -> Result<String, MigrateError> {
@@ -300,3 +614,11 @@ fn generate_lock_id(database_name: &str) -> String {
         0x3d32ad9e * (CRC_IEEE.checksum(database_name.as_bytes()) as i64)
     )
 }
+
+// splits a possibly schema-qualified table name into its schema and table
+fn split_table_name(table_name: &str) -> (Option<&str>, &str) {
+    match table_name.rsplit_once('.') {
+        Some((schema, table)) => (Some(schema), table),
+        None => (None, table_name),
+    }
+}
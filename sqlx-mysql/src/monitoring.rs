@@ -0,0 +1,412 @@
+//! Typed access to the statement digests in [`performance_schema`] and to the status of
+//! replication.
+//!
+//! Like the tables themselves, the columns read here differ between MySQL and MariaDB and
+//! across their versions. The functions here read each field from whichever name it has on the
+//! server they query, and leave fields the server doesn't have as `None`.
+//!
+//! ```rust,no_run
+//! # async fn example(pool: sqlx::MySqlPool) -> sqlx::Result<()> {
+//! use std::time::Duration;
+//! use sqlx::mysql::monitoring;
+//!
+//! for digest in monitoring::statement_digests(&pool).await?.iter().take(10) {
+//!     println!("{:?} {:?}", digest.sum_timer_wait, digest.digest_text);
+//! }
+//!
+//! for replica in monitoring::replica_status(&pool).await? {
+//!     if !replica.sql_running || replica.seconds_behind_source > Some(Duration::from_secs(30)) {
+//!         println!("replication from {:?} is behind", replica.source_host);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`performance_schema`]: https://dev.mysql.com/doc/refman/8.0/en/performance-schema-statement-summary-tables.html
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::acquire::Acquire;
+use crate::column::Column;
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::query::query;
+use crate::query_scalar::query_scalar;
+use crate::row::Row;
+use crate::{MySql, MySqlConnection, MySqlRow};
+
+/// The statistics of a normalized statement, as a row of
+/// `performance_schema.events_statements_summary_by_digest`.
+///
+/// Times are measured by the timers of the Performance Schema in picoseconds, and are zero
+/// for statements which are not instrumented.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MySqlStatementDigest {
+    /// `SCHEMA_NAME`: the default database of the statements, if any.
+    pub schema_name: Option<String>,
+    /// `DIGEST`: the hash identifying the normalized statement, `None` for the row counting
+    /// the statements which did not fit in the table.
+    pub digest: Option<String>,
+    /// `DIGEST_TEXT`: the normalized statement, with constants replaced by `?`.
+    pub digest_text: Option<String>,
+    /// `QUERY_SAMPLE_TEXT`: the text of one of the statements (MySQL 8.0 and later).
+    pub query_sample_text: Option<String>,
+    /// `COUNT_STAR`: the number of times the statement was run.
+    pub count_star: u64,
+    /// `SUM_TIMER_WAIT`: the total time spent running the statement.
+    pub sum_timer_wait: Duration,
+    /// `MIN_TIMER_WAIT`: the shortest time the statement took.
+    pub min_timer_wait: Duration,
+    /// `AVG_TIMER_WAIT`: the average time the statement took.
+    pub avg_timer_wait: Duration,
+    /// `MAX_TIMER_WAIT`: the longest time the statement took.
+    pub max_timer_wait: Duration,
+    /// `QUANTILE_95`: the 95th percentile of the time the statement took (MySQL 8.0 and
+    /// later).
+    pub quantile_95: Option<Duration>,
+    /// `SUM_LOCK_TIME`: the total time spent waiting for table locks.
+    pub sum_lock_time: Duration,
+    /// `SUM_ERRORS`: the number of runs which returned an error.
+    pub sum_errors: u64,
+    /// `SUM_WARNINGS`: the number of warnings returned.
+    pub sum_warnings: u64,
+    /// `SUM_ROWS_AFFECTED`: the total number of rows changed.
+    pub sum_rows_affected: u64,
+    /// `SUM_ROWS_SENT`: the total number of rows returned.
+    pub sum_rows_sent: u64,
+    /// `SUM_ROWS_EXAMINED`: the total number of rows read by the storage engines.
+    pub sum_rows_examined: u64,
+    /// `SUM_CREATED_TMP_TABLES`: the number of internal temporary tables created.
+    pub sum_created_tmp_tables: u64,
+    /// `SUM_CREATED_TMP_DISK_TABLES`: the number of internal temporary tables created on disk.
+    pub sum_created_tmp_disk_tables: u64,
+    /// `SUM_SELECT_FULL_JOIN`: the number of joins which scanned a whole table.
+    pub sum_select_full_join: u64,
+    /// `SUM_SELECT_SCAN`: the number of full scans of the first table of a join.
+    pub sum_select_scan: u64,
+    /// `SUM_NO_INDEX_USED`: the number of runs which scanned a table without an index.
+    pub sum_no_index_used: u64,
+    /// The time since `FIRST_SEEN`, when the statement was first run.
+    pub since_first_seen: Option<Duration>,
+    /// The time since `LAST_SEEN`, when the statement was last run.
+    pub since_last_seen: Option<Duration>,
+}
+
+/// The status of replication from a source, as a row of `SHOW REPLICA STATUS`.
+///
+/// Before MySQL 8.0.22 and with MariaDB, the columns are named after the master and the
+/// slave rather than the source and the replica; the fields are read from either.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MySqlReplicaStatus {
+    /// `Channel_Name`: the replication channel, with multi-source replication in MySQL.
+    pub channel_name: Option<String>,
+    /// `Source_Host`: the host of the source.
+    pub source_host: Option<String>,
+    /// `Source_Port`: the port of the source.
+    pub source_port: Option<u16>,
+    /// `Source_User`: the user the replica connects to the source as.
+    pub source_user: Option<String>,
+    /// `Replica_IO_State`: what the I/O thread is doing, as shown in the process list.
+    pub io_state: Option<String>,
+    /// Whether `Replica_IO_Running` is `Yes`, meaning the I/O thread is connected to the
+    /// source.
+    pub io_running: bool,
+    /// Whether `Replica_SQL_Running` is `Yes`, meaning the SQL thread is applying events.
+    pub sql_running: bool,
+    /// `Seconds_Behind_Source`: how far the SQL thread is behind the source, `None` if it
+    /// is not running.
+    pub seconds_behind_source: Option<Duration>,
+    /// `Source_Log_File`: the binary log of the source the I/O thread is reading.
+    pub source_log_file: Option<String>,
+    /// `Read_Source_Log_Pos`: the position in [`source_log_file`][Self::source_log_file]
+    /// the I/O thread has read up to.
+    pub read_source_log_pos: Option<u64>,
+    /// `Relay_Source_Log_File`: the binary log of the source containing the last event
+    /// applied by the SQL thread.
+    pub relay_source_log_file: Option<String>,
+    /// `Exec_Source_Log_Pos`: the position in
+    /// [`relay_source_log_file`][Self::relay_source_log_file] the SQL thread has applied up to.
+    pub exec_source_log_pos: Option<u64>,
+    /// `Last_IO_Errno`: the last error of the I/O thread, `0` if none.
+    pub last_io_errno: u32,
+    /// `Last_IO_Error`: the message of the last error of the I/O thread.
+    pub last_io_error: Option<String>,
+    /// `Last_SQL_Errno`: the last error of the SQL thread, `0` if none.
+    pub last_sql_errno: u32,
+    /// `Last_SQL_Error`: the message of the last error of the SQL thread.
+    pub last_sql_error: Option<String>,
+    /// `Retrieved_Gtid_Set`: the GTIDs received from the source (MySQL).
+    pub retrieved_gtid_set: Option<String>,
+    /// `Executed_Gtid_Set`: the GTIDs applied (MySQL).
+    pub executed_gtid_set: Option<String>,
+}
+
+/// Get the statistics of every statement digest from
+/// `performance_schema.events_statements_summary_by_digest`, the digests with the highest
+/// total time first.
+///
+/// The table is empty if the Performance Schema is disabled, as it is by default in MariaDB,
+/// or the `statements_digest` consumer is off.
+pub async fn statement_digests<'a, A>(conn: A) -> Result<Vec<MySqlStatementDigest>, Error>
+where
+    A: Acquire<'a, Database = MySql>,
+{
+    let mut conn = conn.acquire().await?;
+
+    // language=MySQL
+    let columns: Vec<String> = query_scalar(
+        "SELECT CAST(COLUMN_NAME AS CHAR) FROM information_schema.COLUMNS \
+         WHERE TABLE_SCHEMA = 'performance_schema' \
+         AND TABLE_NAME = 'events_statements_summary_by_digest'",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let columns: HashSet<String> = columns
+        .into_iter()
+        .map(|column| column.to_ascii_uppercase())
+        .collect();
+
+    let select = Select::new(&columns)
+        .column("schema_name", "SCHEMA_NAME")
+        .column("digest", "DIGEST")
+        .column("digest_text", "DIGEST_TEXT")
+        .column("query_sample_text", "QUERY_SAMPLE_TEXT")
+        .column("count_star", "COUNT_STAR")
+        .column("sum_timer_wait", "SUM_TIMER_WAIT")
+        .column("min_timer_wait", "MIN_TIMER_WAIT")
+        .column("avg_timer_wait", "AVG_TIMER_WAIT")
+        .column("max_timer_wait", "MAX_TIMER_WAIT")
+        .column("quantile_95", "QUANTILE_95")
+        .column("sum_lock_time", "SUM_LOCK_TIME")
+        .column("sum_errors", "SUM_ERRORS")
+        .column("sum_warnings", "SUM_WARNINGS")
+        .column("sum_rows_affected", "SUM_ROWS_AFFECTED")
+        .column("sum_rows_sent", "SUM_ROWS_SENT")
+        .column("sum_rows_examined", "SUM_ROWS_EXAMINED")
+        .column("sum_created_tmp_tables", "SUM_CREATED_TMP_TABLES")
+        .column("sum_created_tmp_disk_tables", "SUM_CREATED_TMP_DISK_TABLES")
+        .column("sum_select_full_join", "SUM_SELECT_FULL_JOIN")
+        .column("sum_select_scan", "SUM_SELECT_SCAN")
+        .column("sum_no_index_used", "SUM_NO_INDEX_USED")
+        .expr("since_first_seen", "FIRST_SEEN", since)
+        .expr("since_last_seen", "LAST_SEEN", since)
+        .from(
+            "performance_schema.events_statements_summary_by_digest ORDER BY SUM_TIMER_WAIT DESC",
+        );
+
+    let rows = query(&select).fetch_all(&mut *conn).await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(MySqlStatementDigest {
+                schema_name: row.try_get("schema_name")?,
+                digest: row.try_get("digest")?,
+                digest_text: row.try_get("digest_text")?,
+                query_sample_text: row.try_get("query_sample_text")?,
+                count_star: counter(row, "count_star")?,
+                sum_timer_wait: timer(row, "sum_timer_wait")?.unwrap_or_default(),
+                min_timer_wait: timer(row, "min_timer_wait")?.unwrap_or_default(),
+                avg_timer_wait: timer(row, "avg_timer_wait")?.unwrap_or_default(),
+                max_timer_wait: timer(row, "max_timer_wait")?.unwrap_or_default(),
+                quantile_95: timer(row, "quantile_95")?,
+                sum_lock_time: timer(row, "sum_lock_time")?.unwrap_or_default(),
+                sum_errors: counter(row, "sum_errors")?,
+                sum_warnings: counter(row, "sum_warnings")?,
+                sum_rows_affected: counter(row, "sum_rows_affected")?,
+                sum_rows_sent: counter(row, "sum_rows_sent")?,
+                sum_rows_examined: counter(row, "sum_rows_examined")?,
+                sum_created_tmp_tables: counter(row, "sum_created_tmp_tables")?,
+                sum_created_tmp_disk_tables: counter(row, "sum_created_tmp_disk_tables")?,
+                sum_select_full_join: counter(row, "sum_select_full_join")?,
+                sum_select_scan: counter(row, "sum_select_scan")?,
+                sum_no_index_used: counter(row, "sum_no_index_used")?,
+                since_first_seen: micros(row, "since_first_seen")?,
+                since_last_seen: micros(row, "since_last_seen")?,
+            })
+        })
+        .collect()
+}
+
+/// Get the status of replication into this server, with `SHOW REPLICA STATUS` or
+/// `SHOW SLAVE STATUS` on servers which don't support it.
+///
+/// There is one row for each replication channel, and none if the server is not a replica.
+/// This needs the `REPLICATION CLIENT` privilege.
+pub async fn replica_status<'a, A>(conn: A) -> Result<Vec<MySqlReplicaStatus>, Error>
+where
+    A: Acquire<'a, Database = MySql>,
+{
+    let mut conn = conn.acquire().await?;
+
+    let sql = if supports_show_replica(&conn) {
+        "SHOW REPLICA STATUS"
+    } else {
+        "SHOW SLAVE STATUS"
+    };
+
+    // `SHOW` statements are sent without arguments, so every value comes back as text
+    let rows = conn.fetch_all(sql).await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(MySqlReplicaStatus {
+                channel_name: text(row, &["Channel_Name", "Channel_name"])?,
+                source_host: text(row, &["Source_Host", "Master_Host"])?,
+                source_port: parse(row, &["Source_Port", "Master_Port"])?,
+                source_user: text(row, &["Source_User", "Master_User"])?,
+                io_state: text(row, &["Replica_IO_State", "Slave_IO_State"])?,
+                io_running: yes(row, &["Replica_IO_Running", "Slave_IO_Running"])?,
+                sql_running: yes(row, &["Replica_SQL_Running", "Slave_SQL_Running"])?,
+                seconds_behind_source: parse(
+                    row,
+                    &["Seconds_Behind_Source", "Seconds_Behind_Master"],
+                )?
+                .map(Duration::from_secs),
+                source_log_file: text(row, &["Source_Log_File", "Master_Log_File"])?,
+                read_source_log_pos: parse(row, &["Read_Source_Log_Pos", "Read_Master_Log_Pos"])?,
+                relay_source_log_file: text(
+                    row,
+                    &["Relay_Source_Log_File", "Relay_Master_Log_File"],
+                )?,
+                exec_source_log_pos: parse(row, &["Exec_Source_Log_Pos", "Exec_Master_Log_Pos"])?,
+                last_io_errno: parse(row, &["Last_IO_Errno"])?.unwrap_or_default(),
+                last_io_error: text(row, &["Last_IO_Error"])?,
+                last_sql_errno: parse(row, &["Last_SQL_Errno"])?.unwrap_or_default(),
+                last_sql_error: text(row, &["Last_SQL_Error"])?,
+                retrieved_gtid_set: text(row, &["Retrieved_Gtid_Set"])?,
+                executed_gtid_set: text(row, &["Executed_Gtid_Set"])?,
+            })
+        })
+        .collect()
+}
+
+// `SHOW REPLICA STATUS` was added in MySQL 8.0.22 and MariaDB 10.5.1
+fn supports_show_replica(conn: &MySqlConnection) -> bool {
+    let stream = &conn.inner.stream;
+
+    if stream.is_mariadb {
+        stream.server_version >= (10, 5, 1)
+    } else {
+        stream.server_version >= (8, 0, 22)
+    }
+}
+
+/// Builds a `SELECT` from a table, reading `NULL` for the columns which don't exist in the
+/// table on this server.
+struct Select<'c> {
+    columns: &'c HashSet<String>,
+    sql: String,
+}
+
+impl<'c> Select<'c> {
+    fn new(columns: &'c HashSet<String>) -> Self {
+        Self {
+            columns,
+            sql: String::from("SELECT "),
+        }
+    }
+
+    fn column(self, alias: &str, name: &str) -> Self {
+        self.expr(alias, name, str::to_owned)
+    }
+
+    fn expr(mut self, alias: &str, name: &str, expr: impl FnOnce(&str) -> String) -> Self {
+        let expr = if self.columns.contains(name) {
+            expr(name)
+        } else {
+            "NULL".to_owned()
+        };
+
+        if !self.sql.ends_with(' ') {
+            self.sql.push_str(", ");
+        }
+
+        let _ = write!(self.sql, "{expr} AS {alias}");
+        self
+    }
+
+    fn from(mut self, from: &str) -> String {
+        let _ = write!(self.sql, " FROM {from}");
+        self.sql
+    }
+}
+
+fn since(column: &str) -> String {
+    format!("TIMESTAMPDIFF(MICROSECOND, {column}, NOW(6))")
+}
+
+fn counter(row: &MySqlRow, column: &str) -> Result<u64, Error> {
+    Ok(row.try_get::<Option<u64>, _>(column)?.unwrap_or_default())
+}
+
+fn timer(row: &MySqlRow, column: &str) -> Result<Option<Duration>, Error> {
+    let picos: Option<u64> = row.try_get(column)?;
+
+    Ok(picos.map(|picos| Duration::from_nanos(picos / 1000)))
+}
+
+fn micros(row: &MySqlRow, column: &str) -> Result<Option<Duration>, Error> {
+    let micros: Option<i64> = row.try_get(column)?;
+
+    // a statement may have been seen after `NOW(6)` was taken for the query
+    Ok(micros.map(|micros| Duration::from_micros(micros.try_into().unwrap_or_default())))
+}
+
+// Empty values in `SHOW` output, such as `Last_IO_Error` without an error, are `None`.
+fn text(row: &MySqlRow, names: &[&str]) -> Result<Option<String>, Error> {
+    let Some(column) = row
+        .columns()
+        .iter()
+        .find(|column| names.contains(&column.name()))
+    else {
+        return Ok(None);
+    };
+
+    let value: Option<String> = row.try_get_unchecked(column.ordinal())?;
+
+    Ok(value.filter(|value| !value.is_empty()))
+}
+
+fn parse<T: std::str::FromStr>(row: &MySqlRow, names: &[&str]) -> Result<Option<T>, Error> {
+    text(row, names)?
+        .map(|value| {
+            value.parse().map_err(|_| {
+                Error::Decode(format!("unexpected value {value:?} for {}", names[0]).into())
+            })
+        })
+        .transpose()
+}
+
+fn yes(row: &MySqlRow, names: &[&str]) -> Result<bool, Error> {
+    Ok(text(row, names)?.is_some_and(|value| value.eq_ignore_ascii_case("yes")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_falls_back_to_null() {
+        let columns: HashSet<String> = ["DIGEST", "FIRST_SEEN"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let sql = Select::new(&columns)
+            .column("digest", "DIGEST")
+            .column("quantile_95", "QUANTILE_95")
+            .expr("since_first_seen", "FIRST_SEEN", since)
+            .from("t");
+
+        assert_eq!(
+            sql,
+            "SELECT DIGEST AS digest, NULL AS quantile_95, \
+             TIMESTAMPDIFF(MICROSECOND, FIRST_SEEN, NOW(6)) AS since_first_seen FROM t"
+        );
+    }
+}
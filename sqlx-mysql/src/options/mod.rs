@@ -4,7 +4,12 @@ mod connect;
 mod parse;
 mod ssl_mode;
 
-use crate::{connection::LogSettings, net::tls::CertificateInput};
+use crate::connection::{LocalInfileHandler, LogSettings};
+use crate::error::BoxDynError;
+use crate::net::tls::CertificateInput;
+use bytes::Bytes;
+use futures_core::Stream;
+use sqlx_core::database_url::Password;
 pub use ssl_mode::MySqlSslMode;
 
 /// Options and flags which can be used to configure a MySQL connection.
@@ -65,7 +70,7 @@ pub struct MySqlConnectOptions {
     pub(crate) port: u16,
     pub(crate) socket: Option<PathBuf>,
     pub(crate) username: String,
-    pub(crate) password: Option<String>,
+    pub(crate) password: Option<Password>,
     pub(crate) database: Option<String>,
     pub(crate) ssl_mode: MySqlSslMode,
     pub(crate) ssl_ca: Option<CertificateInput>,
@@ -80,6 +85,9 @@ pub struct MySqlConnectOptions {
     pub(crate) no_engine_substitution: bool,
     pub(crate) timezone: Option<String>,
     pub(crate) set_names: bool,
+    pub(crate) found_rows: bool,
+    pub(crate) local_infile: Option<LocalInfileHandler>,
+    pub(crate) reject_non_finite_floats: bool,
 }
 
 impl Default for MySqlConnectOptions {
@@ -111,6 +119,9 @@ impl MySqlConnectOptions {
             no_engine_substitution: true,
             timezone: Some(String::from("+00:00")),
             set_names: true,
+            found_rows: true,
+            local_infile: None,
+            reject_non_finite_floats: false,
         }
     }
 
@@ -148,7 +159,7 @@ impl MySqlConnectOptions {
 
     /// Sets the password to connect with.
     pub fn password(mut self, password: &str) -> Self {
-        self.password = Some(password.to_owned());
+        self.password = Some(password.into());
         self
     }
 
@@ -403,6 +414,86 @@ impl MySqlConnectOptions {
         self.set_names = flag_val;
         self
     }
+
+    /// Sets the `CLIENT_FOUND_ROWS` flag, which makes the server report the number of rows
+    /// matched by an `UPDATE` as the rows affected, rather than the number of rows changed.
+    ///
+    /// This also affects upserts: an `INSERT ... ON DUPLICATE KEY UPDATE` which leaves an
+    /// existing row unchanged counts 1 row affected with the flag set, and 0 without it.
+    ///
+    /// Enabled by default.
+    ///
+    /// See also [`MySqlQueryResult::rows_matched()`][crate::MySqlQueryResult::rows_matched] and
+    /// [`MySqlQueryResult::rows_changed()`][crate::MySqlQueryResult::rows_changed].
+    pub fn found_rows(mut self, flag_val: bool) -> Self {
+        self.found_rows = flag_val;
+        self
+    }
+
+    /// Sets whether queries binding a `NaN` or infinite `f32` or `f64` fail with
+    /// [`Error::Encode`] before they are sent to the server.
+    ///
+    /// MySQL's `FLOAT` and `DOUBLE` types cannot store these values, and how the server handles
+    /// them varies by version and SQL mode. The default is `false`, leaving them to the server.
+    ///
+    /// [`Error::Encode`]: crate::Error::Encode
+    pub fn reject_non_finite_floats(mut self, reject: bool) -> Self {
+        self.reject_non_finite_floats = reject;
+        self
+    }
+
+    /// Allows `LOAD DATA LOCAL INFILE` statements, which load a file sent by the client into a
+    /// table, and sets the handler which supplies the contents of the files they name.
+    ///
+    /// The handler is called with the name of the file, as written in the statement, and
+    /// returns its contents as a stream of chunks. If the stream returns an error, the file is
+    /// ended early and the statement returns the error; the rows already sent are loaded unless
+    /// the statement runs in a transaction which is then rolled back.
+    ///
+    /// The server decides which file to request, so the handler should only supply the files
+    /// which the application means to load. The server must also allow it, with the
+    /// `local_infile` system variable, which is disabled by default as of MySQL 8.0.
+    ///
+    /// The statement must be executed as a plain string, such as with
+    /// [`Executor::execute()`][crate::executor::Executor::execute] or [`raw_sql()`][sqlx_core::raw_sql::raw_sql],
+    /// as MySQL cannot prepare it. Its future should not be cancelled while the file is sent, as
+    /// the server would still expect the rest of it.
+    ///
+    /// Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> sqlx::Result<()> {
+    /// use futures_util::stream;
+    /// use sqlx::mysql::{MySqlConnectOptions, MySqlConnection};
+    /// use sqlx::{ConnectOptions, Executor};
+    ///
+    /// let mut conn = MySqlConnectOptions::new()
+    ///     .local_infile_handler(|file_name| {
+    ///         let data = match file_name {
+    ///             "users.csv" => Ok("1,alice\n2,bob\n".into()),
+    ///             _ => Err(format!("not loading {file_name:?}").into()),
+    ///         };
+    ///
+    ///         stream::once(async { data })
+    ///     })
+    ///     .connect()
+    ///     .await?;
+    ///
+    /// conn.execute("LOAD DATA LOCAL INFILE 'users.csv' INTO TABLE users FIELDS TERMINATED BY ','")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn local_infile_handler<F, S>(mut self, handler: F) -> Self
+    where
+        F: Fn(&str) -> S + Send + Sync + 'static,
+        S: Stream<Item = Result<Bytes, BoxDynError>> + Send + 'static,
+    {
+        self.local_infile = Some(LocalInfileHandler::new(handler));
+        self
+    }
 }
 
 impl MySqlConnectOptions {
@@ -16,6 +16,10 @@ pub struct AuthSwitchRequest {
 
 impl ProtocolDecode<'_, bool> for AuthSwitchRequest {
     fn decode_with(mut buf: Bytes, enable_cleartext_plugin: bool) -> Result<Self, Error> {
+        if buf.is_empty() {
+            return Err(err_protocol!("expected at least 1 byte, got 0"));
+        }
+
         let header = buf.get_u8();
         if header != 0xfe {
             return Err(err_protocol!(
@@ -48,7 +52,7 @@ impl ProtocolDecode<'_, bool> for AuthSwitchRequest {
                 buf.len()
             ));
         }
-        let data = buf.get_bytes(20);
+        let data = buf.get_bytes(20)?;
         buf.advance(1); // NUL-terminator
 
         Ok(Self { plugin, data })
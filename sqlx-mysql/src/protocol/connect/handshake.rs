@@ -29,10 +29,23 @@ pub(crate) struct Handshake {
 
 impl ProtocolDecode<'_> for Handshake {
     fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
+        if buf.is_empty() {
+            return Err(err_protocol!("expected at least 1 byte, got 0"));
+        }
+
         let protocol_version = buf.get_u8(); // int<1>
         let server_version = buf.get_str_nul()?; // string<NUL>
+
+        // the fixed-length fields up to the second part of the auth plugin data
+        if buf.len() < 31 {
+            return Err(err_protocol!(
+                "expected at least 31 bytes after the server version, got {}",
+                buf.len()
+            ));
+        }
+
         let connection_id = buf.get_u32_le(); // int<4>
-        let auth_plugin_data_1 = buf.get_bytes(8); // string<8>
+        let auth_plugin_data_1 = buf.get_bytes(8)?; // string<8>
 
         buf.advance(1); // reserved: string<1>
 
@@ -63,8 +76,8 @@ impl ProtocolDecode<'_> for Handshake {
 
         let auth_plugin_data_2 = if capabilities.contains(Capabilities::SECURE_CONNECTION) {
             let len = cmp::max(auth_plugin_data_len.saturating_sub(9), 12);
-            let v = buf.get_bytes(len as usize);
-            buf.advance(1); // NUL-terminator
+            let v = buf.get_bytes(len as usize)?;
+            buf.get_bytes(1)?; // NUL-terminator
 
             v
         } else {
@@ -20,6 +20,13 @@ pub struct EofPacket {
 
 impl ProtocolDecode<'_, Capabilities> for EofPacket {
     fn decode_with(mut buf: Bytes, _: Capabilities) -> Result<Self, Error> {
+        if buf.len() < 5 {
+            return Err(err_protocol!(
+                "expected at least 5 bytes, got {}",
+                buf.len()
+            ));
+        }
+
         let header = buf.get_u8();
         if header != 0xfe {
             return Err(err_protocol!(
@@ -17,6 +17,13 @@ pub struct ErrPacket {
 
 impl ProtocolDecode<'_, Capabilities> for ErrPacket {
     fn decode_with(mut buf: Bytes, capabilities: Capabilities) -> Result<Self, Error> {
+        if buf.len() < 3 {
+            return Err(err_protocol!(
+                "expected at least 3 bytes, got {}",
+                buf.len()
+            ));
+        }
+
         let header = buf.get_u8();
         if header != 0xff {
             return Err(err_protocol!(
@@ -12,28 +12,67 @@ pub struct OkPacket {
     pub last_insert_id: u64,
     pub status: Status,
     pub warnings: u16,
+    /// Human-readable information about the statement, e.g. `Rows matched: 1  Changed: 0  Warnings: 0`.
+    pub info: Bytes,
+}
+
+impl OkPacket {
+    /// The number of rows matched and changed by an `UPDATE` statement, parsed from [`Self::info`].
+    ///
+    /// Returns `None` for other statements, or if the server sends localized messages.
+    pub fn rows_matched_changed(&self) -> Option<(u64, u64)> {
+        let info = std::str::from_utf8(&self.info).ok()?;
+
+        let (matched, rest) = info.strip_prefix("Rows matched: ")?.split_once(' ')?;
+        let changed = rest
+            .trim_start()
+            .strip_prefix("Changed: ")?
+            .split(' ')
+            .next()?;
+
+        Some((matched.parse().ok()?, changed.parse().ok()?))
+    }
 }
 
 impl ProtocolDecode<'_> for OkPacket {
     fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
-        let header = buf.get_u8();
+        let Some(&header) = buf.first() else {
+            return Err(err_protocol!("expected at least 1 byte, got 0"));
+        };
         if header != 0 && header != 0xfe {
             return Err(err_protocol!(
                 "expected 0x00 or 0xfe (OK_Packet) but found 0x{:02x}",
                 header
             ));
         }
+        buf.advance(1);
+
+        let affected_rows = buf.get_uint_lenenc()?;
+        let last_insert_id = buf.get_uint_lenenc()?;
+
+        if buf.len() < 4 {
+            return Err(err_protocol!(
+                "expected at least 4 bytes after the last insert id, got {}",
+                buf.len()
+            ));
+        }
 
-        let affected_rows = buf.get_uint_lenenc();
-        let last_insert_id = buf.get_uint_lenenc();
         let status = Status::from_bits_truncate(buf.get_u16_le());
         let warnings = buf.get_u16_le();
 
+        // the info string is documented as `string<EOF>`, but servers send it length-encoded
+        let mut lenenc = buf.clone();
+        let info = match lenenc.get_uint_lenenc().map(usize::try_from) {
+            Ok(Ok(len)) if len <= lenenc.len() => lenenc.split_to(len),
+            _ => buf,
+        };
+
         Ok(Self {
             affected_rows,
             last_insert_id,
             status,
             warnings,
+            info,
         })
     }
 }
@@ -49,4 +88,16 @@ fn test_decode_ok_packet() {
     assert_eq!(p.warnings, 0);
     assert!(p.status.contains(Status::SERVER_STATUS_AUTOCOMMIT));
     assert!(p.status.contains(Status::SERVER_SESSION_STATE_CHANGED));
+    assert_eq!(p.rows_matched_changed(), None);
+}
+
+#[test]
+fn test_decode_ok_packet_with_info() {
+    const DATA: &[u8] = b"\x00\x03\x00\x22\x00\x00\x00(Rows matched: 3  Changed: 1  Warnings: 0";
+
+    let p = OkPacket::decode(DATA.into()).unwrap();
+
+    assert_eq!(p.affected_rows, 3);
+    assert_eq!(&*p.info, b"Rows matched: 3  Changed: 1  Warnings: 0");
+    assert_eq!(p.rows_matched_changed(), Some((3, 1)));
 }
@@ -15,6 +15,10 @@ pub(crate) struct BinaryRow(pub(crate) Row);
 
 impl<'de> ProtocolDecode<'de, &'de [MySqlColumn]> for BinaryRow {
     fn decode_with(mut buf: Bytes, columns: &'de [MySqlColumn]) -> Result<Self, Error> {
+        if buf.is_empty() {
+            return Err(err_protocol!("expected at least 1 byte, got 0"));
+        }
+
         let header = buf.get_u8();
         if header != 0 {
             return Err(err_protocol!(
@@ -27,7 +31,7 @@ impl<'de> ProtocolDecode<'de, &'de [MySqlColumn]> for BinaryRow {
         let offset = buf.len();
 
         let null_bitmap_len = (columns.len() + 9) / 8;
-        let null_bitmap = buf.get_bytes(null_bitmap_len);
+        let null_bitmap = buf.get_bytes(null_bitmap_len)?;
 
         let mut values = Vec::with_capacity(columns.len());
 
@@ -76,7 +80,7 @@ impl<'de> ProtocolDecode<'de, &'de [MySqlColumn]> for BinaryRow {
                 | ColumnType::Decimal
                 | ColumnType::Json
                 | ColumnType::NewDecimal => {
-                    let size = buf.get_uint_lenenc();
+                    let size = buf.get_uint_lenenc()?;
                     usize::try_from(size)
                         .map_err(|_| err_protocol!("BLOB length out of range: {size}"))?
                 }
@@ -88,13 +92,26 @@ impl<'de> ProtocolDecode<'de, &'de [MySqlColumn]> for BinaryRow {
                 | ColumnType::Date
                 | ColumnType::Datetime => {
                     // Leave the length byte on the front of the value because decoding uses it.
-                    buf[0] as usize + 1
+                    let Some(&len) = buf.first() else {
+                        return Err(err_protocol!("expected the length of a temporal value"));
+                    };
+
+                    usize::from(len) + 1
                 }
 
                 // NOTE: MySQL will never generate NULL types for non-NULL values
-                ColumnType::Null => unreachable!(),
+                ColumnType::Null => {
+                    return Err(err_protocol!("expected NULL for a column of type NULL"));
+                }
             };
 
+            if buf.len() < size {
+                return Err(err_protocol!(
+                    "expected a value of {size} bytes, got {}",
+                    buf.len()
+                ));
+            }
+
             let offset = offset - buf.len();
 
             values.push(Some(offset..(offset + size)));
@@ -97,6 +97,22 @@ pub enum ColumnType {
 }
 
 // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_com_query_response_text_resultset_column_definition.html
+// https://dev.mysql.com/doc/dev/mysql-server/latest/page_protocol_com_query_response_text_resultset.html
+
+/// The first packet of a result set, with the number of [`ColumnDefinition`]s which follow.
+#[derive(Debug)]
+pub(crate) struct ColumnCount(pub(crate) usize);
+
+impl ProtocolDecode<'_> for ColumnCount {
+    fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
+        let num_columns = buf.get_uint_lenenc()?;
+
+        usize::try_from(num_columns)
+            .map(Self)
+            .map_err(|_| err_protocol!("column count overflows usize: {num_columns}"))
+    }
+}
+
 // https://mariadb.com/kb/en/resultset/#column-definition-packet
 // https://dev.mysql.com/doc/internals/en/com-query-response.html#packet-Protocol::ColumnDefinition41
 
@@ -104,11 +120,8 @@ pub enum ColumnType {
 pub(crate) struct ColumnDefinition {
     #[allow(unused)]
     catalog: Bytes,
-    #[allow(unused)]
     schema: Bytes,
-    #[allow(unused)]
     table_alias: Bytes,
-    #[allow(unused)]
     table: Bytes,
     alias: Bytes,
     name: Bytes,
@@ -132,6 +145,18 @@ impl ColumnDefinition {
     pub(crate) fn alias(&self) -> Result<&str, Error> {
         from_utf8(&self.alias).map_err(Error::protocol)
     }
+
+    pub(crate) fn schema(&self) -> Result<&str, Error> {
+        from_utf8(&self.schema).map_err(Error::protocol)
+    }
+
+    pub(crate) fn table_alias(&self) -> Result<&str, Error> {
+        from_utf8(&self.table_alias).map_err(Error::protocol)
+    }
+
+    pub(crate) fn table(&self) -> Result<&str, Error> {
+        from_utf8(&self.table).map_err(Error::protocol)
+    }
 }
 
 impl ProtocolDecode<'_, Capabilities> for ColumnDefinition {
@@ -142,7 +167,15 @@ impl ProtocolDecode<'_, Capabilities> for ColumnDefinition {
         let table = buf.get_bytes_lenenc()?;
         let alias = buf.get_bytes_lenenc()?;
         let name = buf.get_bytes_lenenc()?;
-        let _next_len = buf.get_uint_lenenc(); // always 0x0c
+        let _next_len = buf.get_uint_lenenc()?; // always 0x0c
+
+        if buf.len() < 10 {
+            return Err(err_protocol!(
+                "expected at least 10 bytes after the column name, got {}",
+                buf.len()
+            ));
+        }
+
         let collation = buf.get_u16_le();
         let max_size = buf.get_u32_le();
         let type_id = buf.get_u8();
@@ -17,15 +17,22 @@ impl<'de> ProtocolDecode<'de, &'de [MySqlColumn]> for TextRow {
         let mut values = Vec::with_capacity(columns.len());
 
         for _ in columns {
-            if buf[0] == 0xfb {
+            if buf.first() == Some(&0xfb) {
                 // NULL is sent as 0xfb
                 values.push(None);
                 buf.advance(1);
             } else {
-                let size = buf.get_uint_lenenc();
+                let size = buf.get_uint_lenenc()?;
                 let size = usize::try_from(size)
                     .map_err(|_| err_protocol!("TextRow length out of range: {size}"))?;
 
+                if buf.len() < size {
+                    return Err(err_protocol!(
+                        "expected a value of {size} bytes, got {}",
+                        buf.len()
+                    ));
+                }
+
                 let offset = offset - buf.len();
 
                 values.push(Some(offset..(offset + size)));
@@ -1,25 +1,69 @@
 use std::iter::{Extend, IntoIterator};
 
+use crate::protocol::response::OkPacket;
+
 #[derive(Debug, Default)]
 pub struct MySqlQueryResult {
     pub(super) rows_affected: u64,
+    pub(super) rows_matched: u64,
+    pub(super) rows_changed: u64,
     pub(super) last_insert_id: u64,
 }
 
 impl MySqlQueryResult {
+    pub(super) fn from_ok(ok: &OkPacket) -> Self {
+        let (rows_matched, rows_changed) = ok
+            .rows_matched_changed()
+            .unwrap_or((ok.affected_rows, ok.affected_rows));
+
+        Self {
+            rows_affected: ok.affected_rows,
+            rows_matched,
+            rows_changed,
+            last_insert_id: ok.last_insert_id,
+        }
+    }
+
     pub fn last_insert_id(&self) -> u64 {
         self.last_insert_id
     }
 
+    /// The number of rows affected by the statement, as reported by the server.
+    ///
+    /// For an `UPDATE`, this is the number of rows matched by its `WHERE` clause, even if they
+    /// were already set to the new values, unless [`found_rows`] was disabled on connecting;
+    /// see [`rows_matched()`][Self::rows_matched] and [`rows_changed()`][Self::rows_changed]
+    /// to get either regardless.
+    ///
+    /// [`found_rows`]: crate::MySqlConnectOptions::found_rows
     pub fn rows_affected(&self) -> u64 {
         self.rows_affected
     }
+
+    /// The number of rows matched by an `UPDATE` statement, including rows which were already
+    /// set to the new values.
+    ///
+    /// For other statements, or if the server sends localized messages (`lc_messages` other
+    /// than English), this is the same as [`rows_affected()`][Self::rows_affected].
+    pub fn rows_matched(&self) -> u64 {
+        self.rows_matched
+    }
+
+    /// The number of rows actually changed by an `UPDATE` statement.
+    ///
+    /// For other statements, or if the server sends localized messages (`lc_messages` other
+    /// than English), this is the same as [`rows_affected()`][Self::rows_affected].
+    pub fn rows_changed(&self) -> u64 {
+        self.rows_changed
+    }
 }
 
 impl Extend<MySqlQueryResult> for MySqlQueryResult {
     fn extend<T: IntoIterator<Item = MySqlQueryResult>>(&mut self, iter: T) {
         for elem in iter {
             self.rows_affected += elem.rows_affected;
+            self.rows_matched += elem.rows_matched;
+            self.rows_changed += elem.rows_changed;
             self.last_insert_id = elem.last_insert_id;
         }
     }
@@ -41,6 +41,29 @@ impl Row for MySqlRow {
     }
 }
 
+impl MySqlRow {
+    /// The value of a column exactly as the server sent it, or `None` if it is `NULL`.
+    ///
+    /// The bytes are in the [format][Self::value_format] of the row: the text protocol for
+    /// plain queries and the binary protocol for prepared statements. This allows decoding types
+    /// without a [`Decode`] impl yourself, or passing values on without decoding them at all.
+    ///
+    /// [`Decode`]: sqlx_core::decode::Decode
+    pub fn raw_value<I>(&self, index: I) -> Result<Option<&[u8]>, Error>
+    where
+        I: ColumnIndex<Self>,
+    {
+        let index = index.index(self)?;
+
+        Ok(self.row.get(index))
+    }
+
+    /// The format of the values of this row, as returned by [`raw_value()`][Self::raw_value].
+    pub fn value_format(&self) -> MySqlValueFormat {
+        self.format
+    }
+}
+
 impl ColumnIndex<MySqlRow> for &'_ str {
     fn index(&self, row: &MySqlRow) -> Result<usize, Error> {
         row.column_names
@@ -8,6 +8,7 @@ use once_cell::sync::OnceCell;
 use sqlx_core::connection::Connection;
 use sqlx_core::query_builder::QueryBuilder;
 use sqlx_core::query_scalar::query_scalar;
+use sqlx_core::row::Row;
 use std::fmt::Write;
 
 use crate::error::Error;
@@ -18,6 +19,9 @@ use crate::{MySql, MySqlConnectOptions, MySqlConnection};
 
 pub(crate) use sqlx_core::testing::*;
 
+#[cfg(feature = "proptest")]
+mod roundtrip;
+
 // Using a blocking `OnceCell` here because the critical sections are short.
 static MASTER_POOL: OnceCell<Pool<MySql>> = OnceCell::new();
 
@@ -103,6 +107,66 @@ impl TestSupport for MySql {
         // but I'm keeping the code around for now because I plan to come back to it.
         todo!()
     }
+
+    fn explain<'c>(
+        conn: &'c mut MySqlConnection,
+        query: &'c str,
+    ) -> BoxFuture<'c, Result<QueryPlan, Error>> {
+        Box::pin(async move {
+            let rows = conn.fetch_all(&*format!("EXPLAIN {query}")).await?;
+
+            let mut plan = QueryPlan::default();
+
+            // One row is returned for each table read, with the index it uses in `key`.
+            for row in rows {
+                let table: Option<String> = row.try_get("table")?;
+                let access: Option<String> = row.try_get("type")?;
+                let key: Option<String> = row.try_get("key")?;
+                let extra: Option<String> = row.try_get("Extra")?;
+
+                plan.lines.push(format!(
+                    "table: {}, type: {}, key: {}, extra: {}",
+                    table.as_deref().unwrap_or("NULL"),
+                    access.as_deref().unwrap_or("NULL"),
+                    key.as_deref().unwrap_or("NULL"),
+                    extra.as_deref().unwrap_or("NULL"),
+                ));
+
+                plan.indexes.extend(key);
+            }
+
+            Ok(plan)
+        })
+    }
+
+    fn clean_tables<'c>(
+        conn: &'c mut MySqlConnection,
+        tables: &'c [&'c str],
+    ) -> BoxFuture<'c, Result<(), Error>> {
+        Box::pin(async move {
+            // `TRUNCATE` refuses tables referenced by a foreign key, even from an empty table.
+            let checks: i64 = query_scalar("SELECT @@SESSION.foreign_key_checks")
+                .fetch_one(&mut *conn)
+                .await?;
+
+            conn.execute("SET SESSION foreign_key_checks = 0").await?;
+
+            let mut res = Ok(());
+
+            for table in tables {
+                // `TRUNCATE` also resets `AUTO_INCREMENT`.
+                if let Err(e) = conn.execute(&*format!("TRUNCATE TABLE {table}")).await {
+                    res = Err(e);
+                    break;
+                }
+            }
+
+            conn.execute(&*format!("SET SESSION foreign_key_checks = {checks}"))
+                .await?;
+
+            res
+        })
+    }
 }
 
 async fn test_context(args: &TestArgs) -> Result<TestContext<MySql>, Error> {
@@ -0,0 +1,73 @@
+use sqlx_core::testing::proptest::prelude::*;
+use sqlx_core::testing::Roundtrip;
+
+use crate::MySql;
+
+macro_rules! impl_roundtrip {
+    ($($ty:ty => $strategy:expr),* $(,)?) => {
+        $(
+            impl Roundtrip<MySql> for $ty {
+                fn strategy() -> BoxedStrategy<Self> {
+                    $strategy.boxed()
+                }
+            }
+        )*
+    };
+}
+
+impl_roundtrip!(
+    bool => any::<bool>(),
+    i8 => any::<i8>(),
+    i16 => any::<i16>(),
+    i32 => any::<i32>(),
+    i64 => any::<i64>(),
+    u8 => any::<u8>(),
+    u16 => any::<u16>(),
+    u32 => any::<u32>(),
+    u64 => any::<u64>(),
+    // `NaN` and the infinities are refused by the encoder
+    f32 => any::<f32>().prop_filter("not finite", |v| v.is_finite()),
+    f64 => any::<f64>().prop_filter("not finite", |v| v.is_finite()),
+    String => any::<String>(),
+    Vec<u8> => any::<Vec<u8>>(),
+);
+
+#[cfg(feature = "chrono")]
+mod chrono {
+    use ::chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+    use sqlx_core::testing::strategy::chrono::{date, time_micros};
+
+    use super::*;
+
+    // the supported range of `DATE` and `DATETIME`
+    impl_roundtrip!(
+        NaiveDate => date(1000..=9999),
+        NaiveTime => time_micros(),
+        NaiveDateTime => (date(1000..=9999), time_micros()).prop_map(|(d, t)| d.and_time(t)),
+    );
+}
+
+#[cfg(feature = "time")]
+mod time {
+    use ::time::{Date, PrimitiveDateTime, Time};
+    use sqlx_core::testing::strategy::time::{date, time_micros};
+
+    use super::*;
+
+    // the supported range of `DATE` and `DATETIME`
+    impl_roundtrip!(
+        Date => date(1000..=9999),
+        Time => time_micros(),
+        PrimitiveDateTime =>
+            (date(1000..=9999), time_micros()).prop_map(|(d, t)| PrimitiveDateTime::new(d, t)),
+    );
+}
+
+#[cfg(feature = "uuid")]
+impl_roundtrip!(uuid::Uuid => any::<u128>().prop_map(uuid::Uuid::from_u128));
+
+#[cfg(feature = "rust_decimal")]
+impl_roundtrip!(
+    rust_decimal::Decimal => (any::<i64>(), 0..=10_u32)
+        .prop_map(|(num, scale)| rust_decimal::Decimal::new(num, scale)),
+);
@@ -0,0 +1,229 @@
+//! Round trips of values through the binary protocol, and the bytes of the fixed-size encodings.
+//!
+//! The binary protocol of MySQL is little-endian whatever the platform of the client or the
+//! server; as in the Postgres driver, the expected bytes are spelled out so that these tests fail
+//! on a big-endian or 32-bit target if a value is encoded or decoded in the byte order or word
+//! size of the host.
+//!
+//! The round trips are properties over the values generated by `Roundtrip::strategy()` for each
+//! type, so they only run with the `proptest` feature.
+
+use std::fmt::Debug;
+
+use sqlx_core::decode::Decode;
+use sqlx_core::encode::{Encode, IsNull};
+use sqlx_core::types::Type;
+
+use crate::value::MySqlValueFormat;
+use crate::{MySql, MySqlValueRef};
+
+fn encode<T>(value: &T) -> Vec<u8>
+where
+    T: for<'q> Encode<'q, MySql>,
+{
+    let mut buf = Vec::new();
+    assert!(matches!(value.encode_by_ref(&mut buf), Ok(IsNull::No)));
+    buf
+}
+
+fn decode<T>(bytes: &[u8]) -> T
+where
+    T: for<'r> Decode<'r, MySql> + Type<MySql>,
+{
+    T::decode(MySqlValueRef {
+        value: Some(bytes),
+        row: None,
+        type_info: T::type_info(),
+        format: MySqlValueFormat::Binary,
+    })
+    .unwrap()
+}
+
+#[track_caller]
+fn assert_bytes<T>(value: T, expected: &[u8])
+where
+    T: for<'q> Encode<'q, MySql> + for<'r> Decode<'r, MySql> + Type<MySql>,
+    T: PartialEq + Debug,
+{
+    assert_eq!(encode(&value), expected, "encoded from {value:?}");
+    assert_eq!(decode::<T>(expected), value);
+}
+
+#[test]
+fn it_encodes_integers_in_little_endian() {
+    assert_bytes(true, &[1]);
+    assert_bytes(-2_i8, &[0xfe]);
+    assert_bytes(0x0102_i16, &[0x02, 0x01]);
+    assert_bytes(-2_i16, &[0xfe, 0xff]);
+    assert_bytes(0x0102_0304_i32, &[0x04, 0x03, 0x02, 0x01]);
+    assert_bytes(
+        0x0102_0304_0506_0708_i64,
+        &[0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01],
+    );
+    assert_bytes(0x0102_u16, &[0x02, 0x01]);
+    assert_bytes(0x0102_0304_u32, &[0x04, 0x03, 0x02, 0x01]);
+    assert_bytes(
+        u64::MAX - 1,
+        &[0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+    );
+}
+
+#[test]
+fn it_encodes_floats_in_little_endian() {
+    assert_bytes(1.5_f32, &[0x00, 0x00, 0xc0, 0x3f]);
+    assert_bytes(1.5_f64, &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf8, 0x3f]);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn it_encodes_chrono_values_in_little_endian() {
+    use chrono::NaiveDate;
+
+    assert_bytes(
+        NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+        &[4, 0xe8, 0x07, 2, 29],
+    );
+    assert_bytes(
+        NaiveDate::from_ymd_opt(2024, 2, 29)
+            .unwrap()
+            .and_hms_micro_opt(1, 2, 3, 0x0001_0203)
+            .unwrap(),
+        &[11, 0xe8, 0x07, 2, 29, 1, 2, 3, 0x03, 0x02, 0x01, 0x00],
+    );
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn it_encodes_time_values_in_little_endian() {
+    use time::macros::{date, datetime};
+
+    assert_bytes(date!(2024 - 02 - 29), &[4, 0xe8, 0x07, 2, 29]);
+    assert_bytes(
+        datetime!(2024-02-29 1:02:03.066_051),
+        &[11, 0xe8, 0x07, 2, 29, 1, 2, 3, 0x03, 0x02, 0x01, 0x00],
+    );
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn it_encodes_uuids_in_their_byte_order() {
+    let uuid = uuid::Uuid::from_u128(0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10);
+
+    let bytes = [
+        0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+        0x10,
+    ];
+
+    // sent with the length prefix of `BINARY(16)`, which is stripped from the values of a row
+    assert_eq!(encode(&uuid), [&[16], &bytes[..]].concat());
+    assert_eq!(decode::<uuid::Uuid>(&bytes), uuid);
+}
+
+#[cfg(feature = "proptest")]
+mod properties {
+    use sqlx_core::testing::proptest::prelude::*;
+    use sqlx_core::testing::proptest::test_runner::TestRunner;
+    use sqlx_core::testing::Roundtrip;
+
+    use super::*;
+    use crate::ext::ustr::UStr;
+    use crate::io::ProtocolDecode;
+    use crate::protocol::statement::BinaryRow;
+    use crate::MySqlColumn;
+
+    /// Decode `encoded` as the only value of a binary row, which strips the length prefix of
+    /// strings and blobs the way a connection does.
+    fn decode_from_row<T>(encoded: &[u8]) -> T
+    where
+        T: for<'r> Decode<'r, MySql> + Type<MySql>,
+    {
+        let columns = [MySqlColumn {
+            ordinal: 0,
+            name: UStr::Static(""),
+            type_info: T::type_info(),
+            flags: None,
+            origin: None,
+        }];
+
+        // the header of a row, then the bitmap of its NULL values
+        let packet = [&[0x00, 0x00][..], encoded].concat();
+        let BinaryRow(row) = BinaryRow::decode_with(packet.into(), &columns).unwrap();
+
+        decode(row.get(0).unwrap())
+    }
+
+    /// Encode the values of `T::strategy()` and check they decode to themselves; a value that
+    /// fails is shrunk before it is reported.
+    #[track_caller]
+    fn assert_round_trips<T: Roundtrip<MySql>>() {
+        TestRunner::default()
+            .run(&T::strategy(), |value| {
+                let bytes = encode(&value);
+                prop_assert_eq!(
+                    decode_from_row::<T>(&bytes),
+                    value,
+                    "decoded from {:02x?}",
+                    bytes
+                );
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn integers_round_trip() {
+        assert_round_trips::<bool>();
+        assert_round_trips::<i8>();
+        assert_round_trips::<i16>();
+        assert_round_trips::<i32>();
+        assert_round_trips::<i64>();
+        assert_round_trips::<u8>();
+        assert_round_trips::<u16>();
+        assert_round_trips::<u32>();
+        assert_round_trips::<u64>();
+    }
+
+    #[test]
+    fn floats_round_trip() {
+        assert_round_trips::<f32>();
+        assert_round_trips::<f64>();
+    }
+
+    #[test]
+    fn strings_and_bytes_round_trip() {
+        assert_round_trips::<String>();
+        assert_round_trips::<Vec<u8>>();
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_values_round_trip() {
+        use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+        assert_round_trips::<NaiveDate>();
+        assert_round_trips::<NaiveTime>();
+        assert_round_trips::<NaiveDateTime>();
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_values_round_trip() {
+        use time::{Date, PrimitiveDateTime, Time};
+
+        assert_round_trips::<Date>();
+        assert_round_trips::<Time>();
+        assert_round_trips::<PrimitiveDateTime>();
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn uuids_round_trip() {
+        assert_round_trips::<uuid::Uuid>();
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn decimals_round_trip() {
+        assert_round_trips::<rust_decimal::Decimal>();
+    }
+}
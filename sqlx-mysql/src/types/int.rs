@@ -2,7 +2,7 @@ use byteorder::{ByteOrder, LittleEndian};
 
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
-use crate::error::BoxDynError;
+use crate::error::{BoxDynError, IntegerOutOfRangeError};
 use crate::protocol::text::{ColumnFlags, ColumnType};
 use crate::types::Type;
 use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
@@ -116,19 +116,19 @@ fn int_decode(value: MySqlValueRef<'_>) -> Result<i64, BoxDynError> {
 
 impl Decode<'_, MySql> for i8 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)?.try_into().map_err(Into::into)
+        Ok(IntegerOutOfRangeError::convert(int_decode(value)?)?)
     }
 }
 
 impl Decode<'_, MySql> for i16 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)?.try_into().map_err(Into::into)
+        Ok(IntegerOutOfRangeError::convert(int_decode(value)?)?)
     }
 }
 
 impl Decode<'_, MySql> for i32 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)?.try_into().map_err(Into::into)
+        Ok(IntegerOutOfRangeError::convert(int_decode(value)?)?)
     }
 }
 
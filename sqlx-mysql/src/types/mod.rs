@@ -13,15 +13,16 @@
 //! | `u16`                                 | SMALLINT UNSIGNED                                    |
 //! | `u32`                                 | INT UNSIGNED                                         |
 //! | `u64`                                 | BIGINT UNSIGNED                                      |
-//! | `f32`                                 | FLOAT                                                |
-//! | `f64`                                 | DOUBLE                                               |
+//! | `f32`                                 | FLOAT (see note)                                     |
+//! | `f64`                                 | DOUBLE (see note)                                    |
 //! | `&str`, [`String`]                    | VARCHAR, CHAR, TEXT                                  |
 //! | `&[u8]`, `Vec<u8>`                    | VARBINARY, BINARY, BLOB                              |
+//! | `sqlx::types::CiString`               | VARCHAR, CHAR, TEXT with a `_ci` collation           |
 //! | `IpAddr`                              | VARCHAR, TEXT                                        |
 //! | `Ipv4Addr`                            | INET4 (MariaDB-only), VARCHAR, TEXT                  |
 //! | `Ipv6Addr`                            | INET6 (MariaDB-only), VARCHAR, TEXT                  |
 //! | [`MySqlTime`]                         | TIME (encode and decode full range)                  |
-//! | [`Duration`][std::time::Duration]     | TIME (for decoding positive values only)             |
+//! | [`Duration`][std::time::Duration]     | TIME (positive values only; see note)                |
 //!
 //! ##### Note: `BOOLEAN`/`BOOL` Type
 //! MySQL and MariaDB treat `BOOLEAN` as an alias of the `TINYINT` type:
@@ -40,12 +41,23 @@
 //! Thus, you must use the type override syntax in the query to tell the macros you are expecting
 //! a `bool` column. See the docs for `query!()` and `query_as!()` for details on this syntax.
 //!
+//! ### NOTE: `NaN` and infinity
+//! MySQL's `FLOAT` and `DOUBLE` types cannot store `NaN` or infinite values, and how the server
+//! handles one varies by version and SQL mode. To fail queries binding one before they reach the
+//! server, set
+//! [`MySqlConnectOptions::reject_non_finite_floats()`][crate::MySqlConnectOptions::reject_non_finite_floats].
+//!
 //! ### NOTE: MySQL's `TIME` type is signed
 //! MySQL's `TIME` type can be used as either a time-of-day value, or a signed interval.
 //! Thus, it may take on negative values.
 //!
 //! Decoding a [`std::time::Duration`] returns an error if the `TIME` value is negative.
 //!
+//! Encoding a [`std::time::Duration`] truncates it to the microsecond precision of `TIME`,
+//! and returns an error if it is longer than `838:59:59.999999`, the maximum `TIME` value.
+//! To bind a longer duration, such as a TTL, as a number of seconds, use
+//! [`Duration::as_secs()`][std::time::Duration::as_secs] instead.
+//!
 //! ### [`chrono`](https://crates.io/crates/chrono)
 //!
 //! Requires the `chrono` Cargo feature flag.
@@ -137,6 +149,9 @@
 //! If you want to use the text format compatible with the `UUID()` function,
 //! use [`uuid::fmt::Hyphenated`][::uuid::fmt::Hyphenated] in the place of `Uuid`.
 //!
+//! The macros use `Vec<u8>` for `BINARY(16)` columns like any other binary column, since MySQL
+//! has no way to tell a UUID apart; opt in with a type override, e.g. `SELECT id AS "id: Uuid"`.
+//!
 //! The MySQL official blog has an article showing how to support both binary and text format UUIDs
 //! by storing the binary and adding a generated column for the text format, though this is rather
 //! verbose and fiddly: <https://dev.mysql.com/blog-archive/storing-uuid-values-in-mysql-tables/>
@@ -162,6 +177,8 @@ pub(crate) use sqlx_core::types::*;
 
 pub use mysql_time::{MySqlTime, MySqlTimeError, MySqlTimeSign};
 
+#[cfg(test)]
+mod binary_layout;
 mod bool;
 mod bytes;
 mod float;
@@ -550,6 +550,21 @@ impl<'r> Decode<'r, MySql> for Duration {
     }
 }
 
+/// Encoded as a positive `TIME` value, truncated to microseconds like the conversion to
+/// [`MySqlTime`].
+impl<'q> Encode<'q, MySql> for Duration {
+    fn encode_by_ref(
+        &self,
+        buf: &mut <MySql as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        MySqlTime::try_from(*self)?.encode_by_ref(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        MySqlTime::try_from(*self).map_or(1, |time| time.size_hint())
+    }
+}
+
 // Not exposing this as a `FromStr` impl currently because `MySqlTime` is not designed to be
 // a general interchange type.
 fn parse(text: &str) -> Result<MySqlTime, BoxDynError> {
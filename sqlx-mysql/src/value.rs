@@ -8,7 +8,7 @@ use crate::error::{BoxDynError, UnexpectedNullError};
 use crate::protocol::text::ColumnType;
 use crate::{MySql, MySqlTypeInfo};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 #[repr(u8)]
 pub enum MySqlValueFormat {
     Text,
@@ -9,17 +9,17 @@ use std::borrow::Cow;
 use std::{future, pin::pin};
 
 use sqlx_core::any::{
-    Any, AnyArguments, AnyColumn, AnyConnectOptions, AnyConnectionBackend, AnyQueryResult, AnyRow,
-    AnyStatement, AnyTypeInfo, AnyTypeInfoKind,
+    Any, AnyArguments, AnyCapabilities, AnyColumn, AnyConnectOptions, AnyConnectionBackend,
+    AnyPlaceholderStyle, AnyQueryResult, AnyRow, AnyStatement, AnyTypeInfo, AnyTypeInfoKind,
 };
 
 use crate::type_info::PgType;
-use sqlx_core::connection::Connection;
+use sqlx_core::connection::{CachedStatements, Connection};
 use sqlx_core::database::Database;
 use sqlx_core::describe::Describe;
 use sqlx_core::executor::Executor;
 use sqlx_core::ext::ustr::UStr;
-use sqlx_core::transaction::TransactionManager;
+use sqlx_core::transaction::{StatementJournal, TransactionManager};
 
 sqlx_core::declare_driver_with_optional_migrate!(DRIVER = Postgres);
 
@@ -28,6 +28,15 @@ impl AnyConnectionBackend for PgConnection {
         <Postgres as Database>::NAME
     }
 
+    fn capabilities(&self) -> AnyCapabilities {
+        AnyCapabilities {
+            supports_returning: true,
+            supports_savepoints: true,
+            placeholder_style: AnyPlaceholderStyle::Numbered,
+            max_bind_params: u16::MAX.into(),
+        }
+    }
+
     fn close(self: Box<Self>) -> BoxFuture<'static, sqlx_core::Result<()>> {
         Connection::close(*self)
     }
@@ -63,6 +72,25 @@ impl AnyConnectionBackend for PgConnection {
         PgTransactionManager::get_transaction_depth(self)
     }
 
+    fn cached_statements_size(&self) -> usize {
+        Connection::cached_statements_size(self)
+    }
+
+    fn clear_cached_statements(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::clear_cached_statements(self)
+    }
+
+    fn cached_statements(&self) -> CachedStatements {
+        Connection::cached_statements(self)
+    }
+
+    fn set_statement_cache_capacity(
+        &mut self,
+        capacity: usize,
+    ) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::set_statement_cache_capacity(self, capacity)
+    }
+
     fn shrink_buffers(&mut self) {
         Connection::shrink_buffers(self);
     }
@@ -75,6 +103,13 @@ impl AnyConnectionBackend for PgConnection {
         Connection::should_flush(self)
     }
 
+    fn set_statement_journal(
+        &mut self,
+        journal: Option<StatementJournal>,
+    ) -> Option<StatementJournal> {
+        Connection::set_statement_journal(self, journal)
+    }
+
     #[cfg(feature = "migrate")]
     fn as_migrate(
         &mut self,
@@ -136,6 +136,47 @@ impl PgArguments {
 
         Ok(())
     }
+
+    /// Like [`apply_patches()`][Self::apply_patches], for when `conn` cannot run queries, such as
+    /// during a `COPY`: the OIDs of the types named by the arguments must already be cached.
+    pub(crate) fn apply_cached_patches(&mut self, conn: &PgConnection) -> Result<(), Error> {
+        let PgArgumentBuffer {
+            ref patches,
+            ref type_holes,
+            ref mut buffer,
+            ..
+        } = self.buffer;
+
+        for patch in patches {
+            let buf = &mut buffer[patch.buf_offset..];
+            let ty = &self.types[patch.arg_index];
+
+            (patch.callback)(buf, ty);
+        }
+
+        for (offset, kind) in type_holes {
+            let oid = match kind {
+                HoleKind::Type { name } => conn.inner.cache_type_oid.get(name).copied(),
+                HoleKind::Array(array) => conn
+                    .inner
+                    .cache_type_oid
+                    .get(&array.elem_name)
+                    .and_then(|elem_oid| conn.inner.cache_elem_type_to_array.get(elem_oid))
+                    .copied(),
+            };
+
+            let oid = oid.ok_or_else(|| Error::TypeNotFound {
+                type_name: match kind {
+                    HoleKind::Type { name } => name.to_string(),
+                    HoleKind::Array(array) => array.name.to_string(),
+                },
+            })?;
+
+            buffer[*offset..(*offset + 4)].copy_from_slice(&oid.0.to_be_bytes());
+        }
+
+        Ok(())
+    }
 }
 
 impl<'q> Arguments<'q> for PgArguments {
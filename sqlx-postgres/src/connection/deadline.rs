@@ -0,0 +1,79 @@
+use std::cmp;
+use std::time::Duration;
+
+use sqlx_core::deadline::Deadline;
+
+use crate::error::Error;
+use crate::message::{BackendMessageFormat, TransactionStatus};
+use crate::PgConnection;
+
+impl PgConnection {
+    /// Queue setting the `statement_timeout` of the next statement to the time left until the
+    /// [`Deadline`] of the caller, if any, or resetting it to the session default after a
+    /// statement with a deadline.
+    ///
+    /// The timeout is never raised above the `statement_timeout` the session is configured
+    /// with, and is sent in the same round-trip as the statement; returns whether it was queued,
+    /// in which case [`recv_deadline()`][Self::recv_deadline] must be called once it is flushed.
+    /// A `statement_timeout` set with `SET` by the application is replaced.
+    pub(super) fn queue_deadline(&mut self) -> Result<bool, Error> {
+        let remaining = Deadline::remaining();
+
+        if remaining.is_none() && !self.inner.deadline_timeout {
+            return Ok(false);
+        }
+
+        if remaining == Some(Duration::ZERO) {
+            return Err(Error::DeadlineExceeded);
+        }
+
+        // the statement fails anyway, and could only be `ROLLBACK`
+        if let TransactionStatus::Error = self.inner.transaction_status {
+            return Ok(false);
+        }
+
+        let sql = match remaining {
+            Some(remaining) => format!(
+                "SELECT set_config('statement_timeout', \
+                 CASE WHEN reset_val::bigint = 0 THEN '{millis}' \
+                 ELSE LEAST(reset_val::bigint, {millis})::text END, false) \
+                 FROM pg_settings WHERE name = 'statement_timeout'",
+                millis = timeout_millis(remaining)
+            ),
+            None => "SET statement_timeout TO DEFAULT".to_owned(),
+        };
+
+        self.queue_simple_query(&sql)?;
+
+        self.inner.deadline_timeout = remaining.is_some();
+
+        Ok(true)
+    }
+
+    /// Receive the response to the `statement_timeout` queued by
+    /// [`queue_deadline()`][Self::queue_deadline], ahead of that of the statement.
+    pub(super) async fn recv_deadline(&mut self) -> Result<(), Error> {
+        loop {
+            let message = self.inner.stream.recv().await?;
+
+            if let BackendMessageFormat::ReadyForQuery = message.format {
+                return self.handle_ready_for_query(message);
+            }
+        }
+    }
+}
+
+// rounded up, as zero disables the timeout
+fn timeout_millis(remaining: Duration) -> u128 {
+    let millis = remaining.as_millis() + u128::from(remaining.subsec_nanos() % 1_000_000 != 0);
+
+    cmp::min(millis, i32::MAX as u128)
+}
+
+#[test]
+fn it_rounds_timeouts_up() {
+    assert_eq!(timeout_millis(Duration::from_nanos(1)), 1);
+    assert_eq!(timeout_millis(Duration::from_millis(1500)), 1500);
+    assert_eq!(timeout_millis(Duration::from_micros(1500)), 2);
+    assert_eq!(timeout_millis(Duration::MAX), i32::MAX as u128);
+}
@@ -512,8 +512,9 @@ WHERE rngtypid = $1
 
     /// Infer nullability for columns of this statement using EXPLAIN VERBOSE.
     ///
-    /// This currently only marks columns that are on the inner half of an outer join
-    /// and returns `None` for all others.
+    /// This marks columns that are on the inner half of an outer join as nullable and, for
+    /// statements without parameters, columns computed by expressions which are never `NULL`
+    /// (see [`is_non_null_expr()`]) as non-null. It returns `None` for all others.
     async fn nullables_from_explain(
         &mut self,
         stmt_id: StatementId,
@@ -555,7 +556,19 @@ WHERE rngtypid = $1
                 },
         }) = explains.first()
         {
-            nullables.resize(outputs.len(), None);
+            // The plan is generated with every parameter set to `NULL`, which Postgres may
+            // constant-fold into the outputs; e.g. `CASE WHEN $1 IS NULL THEN 1 END` becomes `1`.
+            // The outputs are only a faithful picture of the query when it has no parameters.
+            if params_len == 0 {
+                nullables.extend(
+                    outputs
+                        .iter()
+                        .map(|output| is_non_null_expr(output).then_some(false)),
+                );
+            } else {
+                nullables.resize(outputs.len(), None);
+            }
+
             visit_plan(plan, outputs, &mut nullables);
         }
 
@@ -564,28 +577,180 @@ WHERE rngtypid = $1
 }
 
 fn visit_plan(plan: &Plan, outputs: &[String], nullables: &mut Vec<Option<bool>>) {
-    if let Some(plan_outputs) = &plan.output {
-        // all outputs of a Full Join must be marked nullable
-        // otherwise, all outputs of the inner half of an outer join must be marked nullable
-        if plan.join_type.as_deref() == Some("Full")
-            || plan.parent_relation.as_deref() == Some("Inner")
-        {
-            for output in plan_outputs {
-                if let Some(i) = outputs.iter().position(|o| o == output) {
-                    // N.B. this may produce false positives but those don't cause runtime errors
-                    nullables[i] = Some(true);
-                }
-            }
+    // all outputs of a Full Join must be marked nullable
+    if plan.join_type.as_deref() == Some("Full") {
+        mark_nullable(plan, outputs, nullables);
+    }
+
+    // otherwise, all outputs of the nullable half of an outer join must be marked nullable;
+    // the planner may turn `a LEFT JOIN b` into a Right Join with `b` as the outer half
+    // (e.g. when `b` is a CTE), so which half that is depends on the join type
+    let nullable_half = match plan.join_type.as_deref() {
+        Some("Left") => "Inner",
+        Some("Right") => "Outer",
+        _ => return,
+    };
+
+    for plan in plan.plans.iter().flatten() {
+        if plan.parent_relation.as_deref() == Some(nullable_half) {
+            mark_nullable(plan, outputs, nullables);
         }
+
+        visit_plan(plan, outputs, nullables);
+    }
+}
+
+fn mark_nullable(plan: &Plan, outputs: &[String], nullables: &mut [Option<bool>]) {
+    for output in plan.output.iter().flatten() {
+        if let Some(i) = outputs.iter().position(|o| o == output) {
+            // N.B. this may produce false positives but those don't cause runtime errors
+            nullables[i] = Some(true);
+        }
+    }
+}
+
+/// Returns `true` if `expr`, an output expression as printed by EXPLAIN VERBOSE, never evaluates
+/// to `NULL`.
+///
+/// This recognizes literals other than `NULL`, `count(..)`, casts of those, and `COALESCE(..)`
+/// with at least one such argument. Expressions wrapped in parentheses are not recognized as
+/// Postgres uses them for values pulled up from subqueries, which may be on the nullable side
+/// of an outer join.
+fn is_non_null_expr(expr: &str) -> bool {
+    let expr = expr.trim();
+
+    if let Some((value, ty)) = split_cast(expr) {
+        // `(count(*))::integer`
+        let value = value
+            .strip_prefix('(')
+            .and_then(|value| value.strip_suffix(')'))
+            .filter(|inner| closing_paren(value) == Some(inner.len() + 1))
+            .unwrap_or(value);
+
+        return is_type_name(ty) && is_non_null_expr(value);
+    }
+
+    if let Some(args) = call_args(expr, "count") {
+        return !args.is_empty();
+    }
+
+    if let Some(args) = call_args(expr, "COALESCE") {
+        return split_args(args).into_iter().any(is_non_null_expr);
     }
 
-    if let Some(plans) = &plan.plans {
-        if let Some("Left") | Some("Right") = plan.join_type.as_deref() {
-            for plan in plans {
-                visit_plan(plan, outputs, nullables);
+    is_literal(expr)
+}
+
+fn is_literal(expr: &str) -> bool {
+    if expr == "true" || expr == "false" {
+        return true;
+    }
+
+    if let Some(quoted) = expr.strip_prefix('\'') {
+        // a string is a single literal if its only unescaped quote is the last character
+        return quoted
+            .strip_suffix('\'')
+            .is_some_and(|s| !s.replace("''", "").contains('\''));
+    }
+
+    let digits = expr.strip_prefix('-').unwrap_or(expr);
+
+    digits.starts_with(|c: char| c.is_ascii_digit())
+        && digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'))
+}
+
+/// Split `value::type` at its last `::` outside of quotes or parentheses.
+fn split_cast(expr: &str) -> Option<(&str, &str)> {
+    top_level_indices(expr)
+        .filter(|&i| expr[i..].starts_with("::"))
+        .last()
+        .map(|i| (&expr[..i], &expr[i + 2..]))
+}
+
+/// Postgres prints type names in lowercase, which tells them apart from keywords like `IS NULL`.
+fn is_type_name(ty: &str) -> bool {
+    let ty = ty.trim_end_matches("[]");
+    let ty = match ty.find('(') {
+        Some(i) if ty.ends_with(')') => &ty[..i],
+        _ => ty,
+    };
+
+    !ty.is_empty()
+        && (ty.starts_with('"')
+            || ty
+                .chars()
+                .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_' | '.' | ' ')))
+}
+
+/// The arguments of `expr` if it is a single call to the function `name`.
+fn call_args<'a>(expr: &'a str, name: &str) -> Option<&'a str> {
+    let rest = expr.strip_prefix(name)?;
+
+    if closing_paren(rest)? != rest.len() - 1 {
+        return None;
+    }
+
+    Some(&rest[1..rest.len() - 1])
+}
+
+/// The index of the parenthesis closing the one `expr` starts with.
+fn closing_paren(expr: &str) -> Option<usize> {
+    if !expr.starts_with('(') {
+        return None;
+    }
+
+    let mut depth = 0usize;
+    let mut quoted = false;
+
+    for (i, c) in expr.char_indices() {
+        match c {
+            '\'' | '"' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => {
+                depth -= 1;
+
+                if depth == 0 {
+                    return Some(i);
+                }
             }
+            _ => (),
         }
     }
+
+    None
+}
+
+fn split_args(args: &str) -> Vec<&str> {
+    let mut split = Vec::new();
+    let mut start = 0;
+
+    for i in top_level_indices(args).filter(|&i| args[i..].starts_with(',')) {
+        split.push(&args[start..i]);
+        start = i + 1;
+    }
+
+    split.push(&args[start..]);
+    split
+}
+
+/// The byte indices of `expr` which are outside of quotes or parentheses.
+fn top_level_indices(expr: &str) -> impl Iterator<Item = usize> + '_ {
+    let mut depth = 0usize;
+    let mut quoted = false;
+
+    expr.char_indices().filter_map(move |(i, c)| {
+        match c {
+            '\'' | '"' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth = depth.saturating_sub(1),
+            _ if !quoted && depth == 0 => return Some(i),
+            _ => (),
+        }
+
+        None
+    })
 }
 
 #[derive(serde::Deserialize, Debug)]
@@ -679,3 +844,39 @@ fn explain_parsing() {
         "unexpected parse from {utility_statement:?}: {utility_statement_parsed:?}"
     )
 }
+
+#[test]
+fn non_null_expressions() {
+    for expr in [
+        "1",
+        "1.5",
+        "'-1'::integer",
+        "'it''s'::text",
+        "true",
+        "count(*)",
+        "count(DISTINCT t.id)",
+        "(count(*))::integer",
+        "COALESCE(max(t.id), 0)",
+        "COALESCE(t.a, t.b, 'x'::character varying(10))",
+        "'{}'::integer[]",
+    ] {
+        assert!(is_non_null_expr(expr), "{expr:?} should be non-null");
+    }
+
+    for expr in [
+        "NULL::integer",
+        "t.id",
+        "(1)",
+        "(COALESCE(t.id, 1))",
+        "(b.x)::bigint",
+        "max(t.id)",
+        "COALESCE(t.a, t.b)",
+        "COALESCE(t.a, NULL::integer)",
+        "count(*) OVER (?)",
+        "('a'::text || (t.name)::text)",
+        "'a'::text IS NULL",
+        "'a' || 'b'",
+    ] {
+        assert!(!is_non_null_expr(expr), "{expr:?} should be nullable");
+    }
+}
@@ -3,7 +3,8 @@ use crate::HashMap;
 use crate::common::StatementCache;
 use crate::connection::{sasl, stream::PgStream};
 use crate::error::Error;
-use crate::io::StatementId;
+use crate::executor::Executor;
+use crate::io::{StatementId, StatementNames};
 use crate::message::{
     Authentication, BackendKeyData, BackendMessageFormat, Password, ReadyForQuery, Startup,
 };
@@ -16,6 +17,14 @@ use super::PgConnectionInner;
 
 impl PgConnection {
     pub(crate) async fn establish(options: &PgConnectOptions) -> Result<Self, Error> {
+        let statement_names = StatementNames::new(
+            options
+                .statement_name_prefix
+                .as_deref()
+                .unwrap_or(StatementNames::DEFAULT.prefix()),
+            options.unique_statement_names,
+        )?;
+
         // Upgrade to TLS if we were asked to and the server supports it
         let mut stream = PgStream::connect(options).await?;
 
@@ -45,6 +54,19 @@ impl PgConnection {
             params.push(("options", options));
         }
 
+        let statement_timeout = options
+            .statement_timeout
+            .map(|timeout| timeout.as_millis().to_string());
+
+        if let Some(ref statement_timeout) = statement_timeout {
+            params.push(("statement_timeout", statement_timeout));
+        }
+
+        if options.replication {
+            // a logical replication connection, which also accepts SQL
+            params.push(("replication", "database"));
+        }
+
         stream.write(Startup {
             username: Some(&options.username),
             database: options.database.as_deref(),
@@ -77,7 +99,10 @@ impl PgConnection {
 
                         stream
                             .send(Password::Cleartext(
-                                options.password.as_deref().unwrap_or_default(),
+                                options
+                                    .password
+                                    .as_ref()
+                                    .map_or("", |password| password.as_str()),
                             ))
                             .await?;
                     }
@@ -91,7 +116,10 @@ impl PgConnection {
                         stream
                             .send(Password::Md5 {
                                 username: &options.username,
-                                password: options.password.as_deref().unwrap_or_default(),
+                                password: options
+                                    .password
+                                    .as_ref()
+                                    .map_or("", |password| password.as_str()),
                                 salt: body.salt,
                             })
                             .await?;
@@ -135,7 +163,9 @@ impl PgConnection {
             }
         }
 
-        Ok(PgConnection {
+        let cache_search_path = stream.parameter_statuses.get("search_path").cloned();
+
+        let mut conn = PgConnection {
             inner: Box::new(PgConnectionInner {
                 stream,
                 process_id,
@@ -143,13 +173,35 @@ impl PgConnection {
                 transaction_status,
                 transaction_depth: 0,
                 pending_ready_for_query_count: 0,
-                next_statement_id: StatementId::NAMED_START,
+                fetching_portal: false,
+                next_statement_id: StatementId::named_start(statement_names),
                 cache_statement: StatementCache::new(options.statement_cache_capacity),
+                cache_search_path,
+                search_path_changed: false,
+                search_path_revertible: false,
+                stale_statements: options.stale_statements.clone(),
+                stale_statements_seen: options.stale_statements.position(),
+                deadline_timeout: false,
                 cache_type_oid: HashMap::new(),
                 cache_type_info: HashMap::new(),
                 cache_elem_type_to_array: HashMap::new(),
+                on_error_rollback: options.on_error_rollback,
+                statement_savepoint: false,
+                ignored_errors: 0,
                 log_settings: options.log_settings.clone(),
+                statement_recorder: options.statement_recorder.clone(),
+                time_zone_policy: options.time_zone_policy,
+                result_formats: options.result_formats.clone(),
             }),
-        })
+        };
+
+        // Unlike a `role` startup parameter, this keeps the login user as the session default,
+        // which `RESET ROLE` returns to.
+        if let Some(ref role) = options.role {
+            conn.execute(&*format!("SET ROLE \"{}\"", role.replace('"', "\"\"")))
+                .await?;
+        }
+
+        Ok(conn)
     }
 }
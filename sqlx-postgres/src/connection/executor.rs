@@ -1,22 +1,25 @@
+use super::stale_statements::is_stale_statement;
 use crate::describe::Describe;
-use crate::error::Error;
-use crate::executor::{Execute, Executor};
+use crate::error::{error_codes::DUPLICATE_PREPARED_STATEMENT, Error};
+use crate::executor::{Execute, Executor, FetchOptions};
 use crate::io::{PortalId, StatementId};
 use crate::logger::QueryLogger;
 use crate::message::{
-    self, BackendMessageFormat, Bind, Close, CommandComplete, DataRow, ParameterDescription, Parse,
-    ParseComplete, Query, RowDescription,
+    self, BackendMessage, BackendMessageFormat, Bind, Close, CommandComplete, DataRow,
+    ParameterDescription, Parse, ParseComplete, Query, RowDescription, TransactionStatus,
 };
 use crate::statement::PgStatementMetadata;
+use crate::value::PgResultFormats;
 use crate::{
-    statement::PgStatement, PgArguments, PgConnection, PgQueryResult, PgRow, PgTypeInfo,
+    statement::PgStatement, PgArguments, PgConnection, PgQueryResult, PgRow, PgTypeInfo, PgValue,
     PgValueFormat, Postgres,
 };
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_core::Stream;
-use futures_util::TryStreamExt;
+use futures_util::{StreamExt, TryStreamExt};
 use sqlx_core::arguments::Arguments;
+use sqlx_core::bytes::Bytes;
 use sqlx_core::Either;
 use std::{borrow::Cow, pin::pin, sync::Arc};
 
@@ -26,7 +29,7 @@ async fn prepare(
     parameters: &[PgTypeInfo],
     metadata: Option<Arc<PgStatementMetadata>>,
 ) -> Result<(StatementId, Arc<PgStatementMetadata>), Error> {
-    let id = conn.inner.next_statement_id;
+    let mut id = conn.inner.next_statement_id;
     conn.inner.next_statement_id = id.next();
 
     // build a list of type OIDs to send to the database in the PARSE command
@@ -39,29 +42,61 @@ async fn prepare(
         param_types.push(conn.resolve_type_id(&ty.0).await?);
     }
 
-    // flush and wait until we are re-ready
-    conn.wait_until_ready().await?;
+    conn.check_time_zones(sql, &param_types).await?;
 
-    // next we send the PARSE command to the server
-    conn.inner.stream.write_msg(Parse {
-        param_types: &param_types,
-        query: sql,
-        statement: id,
-    })?;
+    let mut renamed = false;
 
-    if metadata.is_none() {
-        // get the statement columns and parameters
-        conn.inner
-            .stream
-            .write_msg(message::Describe::Statement(id))?;
-    }
+    loop {
+        // flush and wait until we are re-ready
+        conn.wait_until_ready().await?;
+
+        // next we send the PARSE command to the server
+        conn.inner.stream.write_msg(Parse {
+            param_types: &param_types,
+            query: sql,
+            statement: id,
+        })?;
+
+        if metadata.is_none() {
+            // get the statement columns and parameters
+            conn.inner
+                .stream
+                .write_msg(message::Describe::Statement(id))?;
+        }
+
+        // we ask for the server to immediately send us the result of the PARSE command
+        conn.write_sync();
+        conn.inner.stream.flush().await?;
+
+        // indicates that the SQL query string is now successfully parsed and has semantic validity
+        match conn.inner.stream.recv_expect::<ParseComplete>().await {
+            Ok(_) => break,
+
+            // another client sharing the server session, as through a multiplexer, took the name
+            Err(Error::Database(error))
+                if !renamed && error.code().as_deref() == Some(DUPLICATE_PREPARED_STATEMENT) =>
+            {
+                let names = id.names().with_random_tag();
+                id = StatementId::named_start(names);
+                conn.inner.next_statement_id = id.next();
+                renamed = true;
+
+                conn.wait_until_ready().await?;
+
+                // the failure aborted the transaction, which the caller has to roll back
+                if let TransactionStatus::Error = conn.inner.transaction_status {
+                    return Err(Error::Database(error));
+                }
 
-    // we ask for the server to immediately send us the result of the PARSE command
-    conn.write_sync();
-    conn.inner.stream.flush().await?;
+                tracing::warn!(
+                    %error,
+                    "prepared statement name taken; renaming the statements of this connection"
+                );
+            }
 
-    // indicates that the SQL query string is now successfully parsed and has semantic validity
-    conn.inner.stream.recv_expect::<ParseComplete>().await?;
+            Err(error) => return Err(error),
+        }
+    }
 
     let metadata = if let Some(metadata) = metadata {
         // each SYNC produces one READY FOR QUERY
@@ -159,6 +194,35 @@ impl PgConnection {
         self.inner.pending_ready_for_query_count += 1;
     }
 
+    /// Close the unnamed portal and [Sync] if it is being fetched in batches, which is done
+    /// once all its rows are fetched, or before the next query if the fetch was abandoned.
+    pub(crate) fn end_portal_fetch(&mut self) -> Result<(), Error> {
+        if self.inner.fetching_portal {
+            self.inner
+                .stream
+                .write_msg(Close::Portal(PortalId::UNNAMED))?;
+            self.write_sync();
+
+            self.inner.fetching_portal = false;
+        }
+
+        Ok(())
+    }
+
+    /// Ask for the next `fetch_size` rows of the unnamed portal, which was suspended.
+    fn write_portal_fetch(&mut self, fetch_size: u32) -> Result<(), Error> {
+        self.inner.stream.write_msg(message::Execute {
+            portal: PortalId::UNNAMED,
+            limit: fetch_size,
+        })?;
+
+        // unlike [Sync], [Flush] has the server send what it has without ending the portal
+        self.inner.stream.write_msg(message::Flush)?;
+        self.inner.fetching_portal = true;
+
+        Ok(())
+    }
+
     async fn get_or_prepare<'a>(
         &mut self,
         sql: &str,
@@ -169,6 +233,9 @@ impl PgConnection {
         // a statement object
         metadata: Option<Arc<PgStatementMetadata>>,
     ) -> Result<(StatementId, Arc<PgStatementMetadata>), Error> {
+        self.check_search_path().await?;
+        self.evict_stale_statements().await?;
+
         if let Some(statement) = self.inner.cache_statement.get_mut(sql) {
             return Ok((*statement).clone());
         }
@@ -190,6 +257,80 @@ impl PgConnection {
         Ok(statement)
     }
 
+    /// Get or prepare the statement for `sql`, and fill in the types of `arguments` which
+    /// could only be known once it is prepared.
+    async fn prepare_arguments(
+        &mut self,
+        sql: &str,
+        arguments: &mut PgArguments,
+        persistent: bool,
+        metadata: Option<Arc<PgStatementMetadata>>,
+    ) -> Result<(StatementId, Arc<PgStatementMetadata>), Error> {
+        let (statement, metadata) = self
+            .get_or_prepare(sql, &arguments.types, persistent, metadata)
+            .await?;
+
+        // patch holes created during encoding
+        arguments.apply_patches(self, &metadata.parameters).await?;
+
+        Ok((statement, metadata))
+    }
+
+    /// Queue the messages to execute `statement` with `arguments`, returning up to `limit` rows
+    /// in `result_formats`, or the rows in batches of `fetch_size` if it is not zero.
+    fn write_execute(
+        &mut self,
+        statement: StatementId,
+        arguments: &PgArguments,
+        num_params: u16,
+        limit: u8,
+        fetch_size: u32,
+        result_formats: &PgResultFormats,
+    ) -> Result<(), Error> {
+        // bind to attach the arguments to the statement and create a portal
+        self.inner.stream.write_msg(Bind {
+            portal: PortalId::UNNAMED,
+            statement,
+            formats: &[PgValueFormat::Binary],
+            num_params,
+            params: &arguments.buffer,
+            result_formats: result_formats.as_slice(),
+        })?;
+
+        if fetch_size > 0 {
+            // the portal is ended once all of its rows are fetched
+            return self.write_portal_fetch(fetch_size);
+        }
+
+        // executes the portal up to the passed limit
+        // the protocol-level limit acts nearly identically to the `LIMIT` in SQL
+        self.inner.stream.write_msg(message::Execute {
+            portal: PortalId::UNNAMED,
+            limit: limit.into(),
+        })?;
+        // From https://www.postgresql.org/docs/current/protocol-flow.html:
+        //
+        // "An unnamed portal is destroyed at the end of the transaction, or as
+        // soon as the next Bind statement specifying the unnamed portal as
+        // destination is issued. (Note that a simple Query message also
+        // destroys the unnamed portal."
+
+        // we ask the database server to close the unnamed portal and free the associated resources
+        // earlier - after the execution of the current query.
+        self.inner
+            .stream
+            .write_msg(Close::Portal(PortalId::UNNAMED))?;
+
+        // finally, [Sync] asks postgres to process the messages that we sent and respond with
+        // a [ReadyForQuery] message when it's completely done. Theoretically, we could send
+        // dozens of queries before a [Sync] and postgres can handle that. Execution on the server
+        // is still serial but it would reduce round-trips. Some kind of builder pattern that is
+        // termed batching might suit this.
+        self.write_sync();
+
+        Ok(())
+    }
+
     pub(crate) async fn run<'e, 'c: 'e, 'q: 'e>(
         &'c mut self,
         query: &'q str,
@@ -198,14 +339,37 @@ impl PgConnection {
         persistent: bool,
         metadata_opt: Option<Arc<PgStatementMetadata>>,
     ) -> Result<impl Stream<Item = Result<Either<PgQueryResult, PgRow>, Error>> + 'e, Error> {
+        self.run_as(query, arguments, limit, 0, persistent, metadata_opt)
+            .await
+    }
+
+    /// Like [`run()`][Self::run], producing an `R` from each row instead of a [`PgRow`], and
+    /// fetching the rows in batches of `fetch_size` if it is not zero.
+    async fn run_as<'e, 'c: 'e, 'q: 'e, R: FromDataRow + 'e>(
+        &'c mut self,
+        query: &'q str,
+        arguments: Option<PgArguments>,
+        limit: u8,
+        fetch_size: u32,
+        persistent: bool,
+        metadata_opt: Option<Arc<PgStatementMetadata>>,
+    ) -> Result<impl Stream<Item = Result<Either<PgQueryResult, R>, Error>> + 'e, Error> {
         let mut logger = QueryLogger::new(query, self.inner.log_settings.clone());
 
         // before we continue, wait until we are "ready" to accept more queries
         self.wait_until_ready().await?;
 
         let mut metadata: Arc<PgStatementMetadata>;
+        let mut retry = None;
+        let deadline;
+
+        // only a prepared statement has a portal to fetch in batches
+        let arguments = match arguments {
+            None if fetch_size > 0 => Some(PgArguments::default()),
+            arguments => arguments,
+        };
 
-        let format = if let Some(mut arguments) = arguments {
+        let mut formats = if let Some(mut arguments) = arguments {
             // Check this before we write anything to the stream.
             //
             // Note: Postgres actually interprets this value as unsigned,
@@ -221,57 +385,40 @@ impl PgConnection {
 
             // prepare the statement if this our first time executing it
             // always return the statement ID here
-            let (statement, metadata_) = self
-                .get_or_prepare(query, &arguments.types, persistent, metadata_opt)
+            let statement;
+            (statement, metadata) = self
+                .prepare_arguments(query, &mut arguments, persistent, metadata_opt)
                 .await?;
 
-            metadata = metadata_;
+            if let Some(recorder) = &self.inner.statement_recorder {
+                recorder.record_prepared(query, &metadata.parameters, &arguments.buffer);
+            }
 
-            // patch holes created during encoding
-            arguments.apply_patches(self, &metadata.parameters).await?;
+            let formats = self.result_formats(&metadata);
 
             // consume messages till `ReadyForQuery` before bind and execute
             self.wait_until_ready().await?;
 
-            // bind to attach the arguments to the statement and create a portal
-            self.inner.stream.write_msg(Bind {
-                portal: PortalId::UNNAMED,
-                statement,
-                formats: &[PgValueFormat::Binary],
-                num_params,
-                params: &arguments.buffer,
-                result_formats: &[PgValueFormat::Binary],
-            })?;
-
-            // executes the portal up to the passed limit
-            // the protocol-level limit acts nearly identically to the `LIMIT` in SQL
-            self.inner.stream.write_msg(message::Execute {
-                portal: PortalId::UNNAMED,
-                limit: limit.into(),
-            })?;
-            // From https://www.postgresql.org/docs/current/protocol-flow.html:
-            //
-            // "An unnamed portal is destroyed at the end of the transaction, or as
-            // soon as the next Bind statement specifying the unnamed portal as
-            // destination is issued. (Note that a simple Query message also
-            // destroys the unnamed portal."
+            deadline = self.queue_deadline()?;
 
-            // we ask the database server to close the unnamed portal and free the associated resources
-            // earlier - after the execution of the current query.
-            self.inner
-                .stream
-                .write_msg(Close::Portal(PortalId::UNNAMED))?;
+            self.write_execute(
+                statement, &arguments, num_params, limit, fetch_size, &formats,
+            )?;
 
-            // finally, [Sync] asks postgres to process the messages that we sent and respond with
-            // a [ReadyForQuery] message when it's completely done. Theoretically, we could send
-            // dozens of queries before a [Sync] and postgres can handle that. Execution on the server
-            // is still serial but it would reduce round-trips. Some kind of builder pattern that is
-            // termed batching might suit this.
-            self.write_sync();
+            // a statement which fails outside of a transaction because it is stale can be
+            // prepared and executed again
+            if let TransactionStatus::Idle = self.inner.transaction_status {
+                retry = Some((arguments, num_params));
+            }
 
-            // prepared statements are binary
-            PgValueFormat::Binary
+            formats
         } else {
+            if let Some(recorder) = &self.inner.statement_recorder {
+                recorder.record_simple(query);
+            }
+
+            deadline = self.queue_deadline()?;
+
             // Query will trigger a ReadyForQuery
             self.inner.stream.write_msg(Query(query))?;
             self.inner.pending_ready_for_query_count += 1;
@@ -280,14 +427,45 @@ impl PgConnection {
             metadata = Arc::new(PgStatementMetadata::default());
 
             // and unprepared statements are text
-            PgValueFormat::Text
+            PgResultFormats::All(PgValueFormat::Text)
         };
 
+        self.track_search_path(query);
+
         self.inner.stream.flush().await?;
 
+        if deadline {
+            self.recv_deadline().await?;
+        }
+
         Ok(try_stream! {
             loop {
-                let message = self.inner.stream.recv().await?;
+                let message = match self.inner.stream.recv().await {
+                    Ok(message) => message,
+
+                    // nothing has been returned yet, as the error is raised on `Bind`
+                    Err(error) if retry.is_some() && is_stale_statement(&error) => {
+                        let (mut arguments, num_params) = retry.take().unwrap();
+
+                        self.wait_until_ready().await?;
+                        self.report_stale_statement(query).await?;
+
+                        let statement;
+                        (statement, metadata) = self
+                            .prepare_arguments(query, &mut arguments, persistent, None)
+                            .await?;
+
+                        formats = self.result_formats(&metadata);
+
+                        self.wait_until_ready().await?;
+                        self.write_execute(statement, &arguments, num_params, limit, fetch_size, &formats)?;
+                        self.inner.stream.flush().await?;
+
+                        continue;
+                    }
+
+                    Err(error) => Err(error)?,
+                };
 
                 match message.format {
                     BackendMessageFormat::BindComplete
@@ -307,20 +485,37 @@ impl PgConnection {
                     BackendMessageFormat::CommandComplete => {
                         // a SQL command completed normally
                         let cc: CommandComplete = message.decode()?;
+                        retry = None;
+
+                        if fetch_size > 0 {
+                            self.end_portal_fetch()?;
+                            self.inner.stream.flush().await?;
+                        }
 
                         let rows_affected = cc.rows_affected();
                         logger.increase_rows_affected(rows_affected);
                         r#yield!(Either::Left(PgQueryResult {
                             rows_affected,
+                            command_tag: Some(cc.into_tag()),
                         }));
                     }
 
                     BackendMessageFormat::EmptyQueryResponse => {
                         // empty query string passed to an unprepared execute
+                        if fetch_size > 0 {
+                            self.end_portal_fetch()?;
+                            self.inner.stream.flush().await?;
+                        }
                     }
 
                     // Message::ErrorResponse is handled in self.stream.recv()
 
+                    // the batch of rows has been read, so ask for the next one
+                    BackendMessageFormat::PortalSuspended if fetch_size > 0 => {
+                        self.write_portal_fetch(fetch_size)?;
+                        self.inner.stream.flush().await?;
+                    }
+
                     // incomplete query execution has finished
                     BackendMessageFormat::PortalSuspended => {}
 
@@ -339,14 +534,10 @@ impl PgConnection {
 
                     BackendMessageFormat::DataRow => {
                         logger.increment_rows_returned();
+                        retry = None;
 
                         // one of the set of rows returned by a SELECT, FETCH, etc query
-                        let data: DataRow = message.decode()?;
-                        let row = PgRow {
-                            data,
-                            format,
-                            metadata: Arc::clone(&metadata),
-                        };
+                        let row = R::from_data_row(message.contents, &formats, &metadata)?;
 
                         r#yield!(Either::Right(row));
                     }
@@ -371,18 +562,180 @@ impl PgConnection {
     }
 }
 
-impl<'c> Executor<'c> for &'c mut PgConnection {
-    type Database = Postgres;
+/// What [`PgConnection::run_as()`] produces from each `DataRow` message.
+trait FromDataRow: Sized + Send {
+    fn from_data_row(
+        contents: Bytes,
+        formats: &PgResultFormats,
+        metadata: &Arc<PgStatementMetadata>,
+    ) -> Result<Self, Error>;
+}
 
-    fn fetch_many<'e, 'q, E>(
-        self,
+impl FromDataRow for PgRow {
+    fn from_data_row(
+        contents: Bytes,
+        formats: &PgResultFormats,
+        metadata: &Arc<PgStatementMetadata>,
+    ) -> Result<Self, Error> {
+        Ok(PgRow {
+            data: DataRow::decode_body(contents)?,
+            formats: formats.clone(),
+            metadata: Arc::clone(metadata),
+        })
+    }
+}
+
+/// Only the first column of the row, for [`Executor::fetch_first_column()`].
+impl FromDataRow for PgValue {
+    fn from_data_row(
+        contents: Bytes,
+        formats: &PgResultFormats,
+        metadata: &Arc<PgStatementMetadata>,
+    ) -> Result<Self, Error> {
+        let column = metadata
+            .columns
+            .first()
+            .ok_or(Error::ColumnIndexOutOfBounds { index: 0, len: 0 })?;
+
+        Ok(PgValue {
+            value: DataRow::decode_first_value(contents)?,
+            type_info: column.type_info.clone(),
+            format: formats.get(0),
+        })
+    }
+}
+
+/// The savepoint set before each statement in a transaction with `on_error_rollback` enabled.
+const STATEMENT_SAVEPOINT: &str = "_sqlx_statement";
+
+/// Returns `true` if `result` completed a statement which may have committed, rolled back or
+/// released the statement savepoint, or set a savepoint of its own that releasing would remove.
+fn removes_savepoint<R>(result: &Either<PgQueryResult, R>) -> bool {
+    matches!(
+        result,
+        Either::Left(done) if matches!(
+            done.command(),
+            Some("COMMIT" | "ROLLBACK" | "SAVEPOINT" | "RELEASE" | "PREPARE TRANSACTION")
+        )
+    )
+}
+
+/// Returns `true` if `sql` starts with a transaction control statement, which is not wrapped
+/// with the statement savepoint, as it would remove the savepoint or be nested in it.
+fn controls_transaction(sql: &str) -> bool {
+    let keyword = sql
+        .trim_start()
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .unwrap_or_default();
+
+    [
+        "BEGIN",
+        "START",
+        "COMMIT",
+        "END",
+        "ROLLBACK",
+        "ABORT",
+        "SAVEPOINT",
+        "RELEASE",
+        "PREPARE",
+    ]
+    .iter()
+    .any(|control| keyword.eq_ignore_ascii_case(control))
+}
+
+impl PgConnection {
+    /// Set the statement savepoint for `sql` if `on_error_rollback` is enabled and a
+    /// transaction is open.
+    ///
+    /// Returns `true` if the savepoint was set.
+    async fn set_statement_savepoint(&mut self, sql: &str) -> Result<bool, Error> {
+        if !self.inner.on_error_rollback {
+            return Ok(false);
+        }
+
+        self.wait_until_ready().await?;
+
+        if !self.in_transaction() || controls_transaction(sql) {
+            return Ok(false);
+        }
+
+        self.queue_simple_query(&format!("SAVEPOINT {STATEMENT_SAVEPOINT}"))?;
+        self.inner.statement_savepoint = true;
+
+        Ok(true)
+    }
+
+    /// Roll back to the statement savepoint if the statement failed the transaction, or
+    /// release it otherwise.
+    async fn finish_statement_savepoint(&mut self, savepoint_gone: bool) -> Result<(), Error> {
+        self.inner.statement_savepoint = false;
+
+        // the transaction status is only known once the statement has finished
+        self.wait_until_ready().await?;
+
+        match self.inner.transaction_status {
+            TransactionStatus::Error => {
+                self.queue_simple_query(&format!(
+                    "ROLLBACK TO SAVEPOINT {STATEMENT_SAVEPOINT}; \
+                     RELEASE SAVEPOINT {STATEMENT_SAVEPOINT}"
+                ))?;
+                self.wait_until_ready().await?;
+            }
+            TransactionStatus::Transaction if !savepoint_gone => {
+                // this is sent along with the next query
+                self.queue_simple_query(&format!("RELEASE SAVEPOINT {STATEMENT_SAVEPOINT}"))?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Queue rolling back to the statement savepoint of a statement whose stream or future was
+    /// dropped before its end, which is undone like a failed statement.
+    ///
+    /// The errors of the statement are ignored when they are received with the next query.
+    fn abandon_statement_savepoint(&mut self) {
+        self.inner.statement_savepoint = false;
+
+        self.end_portal_fetch()
+            .expect("BUG: Close message somehow too large for protocol");
+        self.inner.ignored_errors = self.inner.pending_ready_for_query_count;
+
+        self.queue_simple_query(&format!(
+            "ROLLBACK TO SAVEPOINT {STATEMENT_SAVEPOINT}; \
+             RELEASE SAVEPOINT {STATEMENT_SAVEPOINT}"
+        ))
+        .expect("BUG: Rollback query somehow too large for protocol");
+    }
+}
+
+/// Abandons the statement savepoint if it is dropped before the statement has finished, like
+/// `Rollback` does for transactions.
+struct StatementSavepointGuard<'c> {
+    conn: &'c mut PgConnection,
+}
+
+impl Drop for StatementSavepointGuard<'_> {
+    fn drop(&mut self) {
+        if self.conn.inner.statement_savepoint {
+            self.conn.abandon_statement_savepoint();
+        }
+    }
+}
+
+impl PgConnection {
+    /// The body of [`Executor::fetch_many()`], producing an `R` from each row, fetched in
+    /// batches of `fetch_size` if it is not zero.
+    fn fetch_many_as<'e, 'c: 'e, 'q: 'e, R, E>(
+        &'c mut self,
         mut query: E,
-    ) -> BoxStream<'e, Result<Either<PgQueryResult, PgRow>, Error>>
+        fetch_size: u32,
+    ) -> BoxStream<'e, Result<Either<PgQueryResult, R>, Error>>
     where
-        'c: 'e,
-        E: Execute<'q, Self::Database>,
-        'q: 'e,
-        E: 'q,
+        R: FromDataRow + 'e,
+        E: Execute<'q, Postgres> + 'q,
     {
         let sql = query.sql();
         // False positive: https://github.com/rust-lang/rust-clippy/issues/12560
@@ -393,15 +746,70 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
 
         Box::pin(try_stream! {
             let arguments = arguments?;
-            let mut s = pin!(self.run(sql, arguments, 0, persistent, metadata).await?);
+            let savepoint = self.set_statement_savepoint(sql).await?;
+            let mut savepoint_gone = false;
+
+            let guard = StatementSavepointGuard { conn: self };
+            let conn = &mut *guard.conn;
+
+            let res = match conn.run_as(sql, arguments, 0, fetch_size, persistent, metadata).await {
+                Ok(s) => {
+                    let mut s = pin!(s);
+
+                    loop {
+                        match s.try_next().await {
+                            Ok(Some(v)) => {
+                                savepoint_gone |= removes_savepoint(&v);
+                                r#yield!(v);
+                            }
+                            Ok(None) => break Ok(()),
+                            Err(e) => break Err(e),
+                        }
+                    }
+                }
+                Err(e) => Err(e),
+            };
 
-            while let Some(v) = s.try_next().await? {
-                r#yield!(v);
+            if savepoint {
+                conn.finish_statement_savepoint(savepoint_gone).await?;
             }
 
-            Ok(())
+            res
         })
     }
+}
+
+impl<'c> Executor<'c> for &'c mut PgConnection {
+    type Database = Postgres;
+
+    fn fetch_many<'e, 'q, E>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<PgQueryResult, PgRow>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        self.fetch_many_as(query, 0)
+    }
+
+    fn fetch_with<'e, 'q, E>(
+        self,
+        query: E,
+        options: FetchOptions,
+    ) -> BoxStream<'e, Result<PgRow, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        self.fetch_many_as(query, options.fetch_size)
+            .try_filter_map(|step| async move { Ok(step.right()) })
+            .boxed()
+    }
 
     fn fetch_optional<'e, 'q, E>(self, mut query: E) -> BoxFuture<'e, Result<Option<PgRow>, Error>>
     where
@@ -419,23 +827,61 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
 
         Box::pin(async move {
             let arguments = arguments?;
-            let mut s = pin!(self.run(sql, arguments, 1, persistent, metadata).await?);
-
-            // With deferred constraints we need to check all responses as we
-            // could get a OK response (with uncommitted data), only to get an
-            // error response after (when the deferred constraint is actually
-            // checked).
-            let mut ret = None;
-            while let Some(result) = s.try_next().await? {
-                match result {
-                    Either::Right(r) if ret.is_none() => ret = Some(r),
-                    _ => {}
+            let savepoint = self.set_statement_savepoint(sql).await?;
+            let mut savepoint_gone = false;
+
+            let guard = StatementSavepointGuard { conn: self };
+            let conn = &mut *guard.conn;
+
+            let res = async {
+                let mut s = pin!(conn.run(sql, arguments, 1, persistent, metadata).await?);
+
+                // With deferred constraints we need to check all responses as we
+                // could get a OK response (with uncommitted data), only to get an
+                // error response after (when the deferred constraint is actually
+                // checked).
+                let mut ret = None;
+                while let Some(result) = s.try_next().await? {
+                    savepoint_gone |= removes_savepoint(&result);
+
+                    match result {
+                        Either::Right(r) if ret.is_none() => ret = Some(r),
+                        _ => {}
+                    }
                 }
+                Ok(ret)
+            }
+            .await;
+
+            if savepoint {
+                conn.finish_statement_savepoint(savepoint_gone).await?;
             }
-            Ok(ret)
+
+            res
         })
     }
 
+    #[doc(hidden)]
+    fn fetch_first_column<'e, 'q, E>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<PgRow, PgValue>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        self.fetch_many_as(query, 0)
+            .try_filter_map(|step| async move {
+                Ok(match step {
+                    Either::Left(_) => None,
+                    Either::Right(value) => Some(Either::Right(value)),
+                })
+            })
+            .boxed()
+    }
+
     fn prepare_with<'e, 'q: 'e>(
         self,
         sql: &'q str,
@@ -477,4 +923,39 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
             })
         })
     }
+
+    fn describe_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [PgTypeInfo],
+    ) -> BoxFuture<'e, Result<Describe<Self::Database>, Error>>
+    where
+        'c: 'e,
+    {
+        if parameters.is_empty() {
+            return self.describe(sql);
+        }
+
+        Box::pin(async move {
+            self.wait_until_ready().await?;
+
+            // Bypass the statement cache, which is keyed only by the SQL
+            // and may hold a statement prepared with different parameter types.
+            let (stmt_id, metadata) = prepare(self, sql, parameters, None).await?;
+
+            let nullable = self.get_nullable_for_columns(stmt_id, &metadata).await;
+
+            self.inner.stream.write_msg(Close::Statement(stmt_id))?;
+            self.write_sync();
+            self.inner.stream.flush().await?;
+            self.wait_for_close_complete(1).await?;
+            self.recv_ready_for_query().await?;
+
+            Ok(Describe {
+                columns: metadata.columns.clone(),
+                nullable: nullable?,
+                parameters: Some(Either::Left(metadata.parameters.clone())),
+            })
+        })
+    }
 }
@@ -15,19 +15,27 @@ use crate::message::{
     TransactionStatus,
 };
 use crate::statement::PgStatementMetadata;
-use crate::transaction::Transaction;
+use crate::transaction::{StatementJournal, Transaction};
 use crate::types::Oid;
-use crate::{PgConnectOptions, PgTypeInfo, Postgres};
+use crate::{
+    PgConnectOptions, PgStatementRecorder, PgTimeZonePolicy, PgTypeInfo, PgValueFormat, Postgres,
+};
 
 pub(crate) use sqlx_core::connection::*;
 
+pub(crate) use self::stale_statements::StaleStatements;
 pub use self::stream::PgStream;
 
+mod deadline;
 pub(crate) mod describe;
 mod establish;
 mod executor;
+mod result_format;
 mod sasl;
+mod search_path;
+mod stale_statements;
 mod stream;
+mod time_zone;
 mod tls;
 
 /// A connection to a PostgreSQL database.
@@ -58,19 +66,58 @@ pub struct PgConnectionInner {
     // cache statement by query string to the id and columns
     cache_statement: StatementCache<(StatementId, Arc<PgStatementMetadata>)>,
 
+    // the `search_path` reported by the server when the cached statements were prepared,
+    // if it reports it at all
+    cache_search_path: Option<String>,
+
+    // for servers which don't report the `search_path`: whether SQL which may have changed it
+    // was sent since the cached statements were cleared, and since the connection was last
+    // seen outside of a transaction, whose end may revert the change
+    search_path_changed: bool,
+    search_path_revertible: bool,
+
+    // statements found to be stale by this or other connections made with the same options,
+    // and the position in it up to which they have been evicted from this cache
+    stale_statements: StaleStatements,
+    stale_statements_seen: u64,
+
+    // whether the `statement_timeout` was last set for the deadline of a statement
+    deadline_timeout: bool,
+
     // cache user-defined types by id <-> info
     cache_type_info: HashMap<Oid, PgTypeInfo>,
-    cache_type_oid: HashMap<UStr, Oid>,
-    cache_elem_type_to_array: HashMap<Oid, Oid>,
+    pub(crate) cache_type_oid: HashMap<UStr, Oid>,
+    pub(crate) cache_elem_type_to_array: HashMap<Oid, Oid>,
 
     // number of ReadyForQuery messages that we are currently expecting
     pub(crate) pending_ready_for_query_count: usize,
 
+    // whether the unnamed portal is being fetched in batches, without a Sync to end it yet
+    pub(crate) fetching_portal: bool,
+
     // current transaction status
     transaction_status: TransactionStatus,
     pub(crate) transaction_depth: usize,
 
+    // whether to wrap statements in transactions with a savepoint
+    on_error_rollback: bool,
+
+    // whether the savepoint of a statement is set and the statement has not finished yet
+    statement_savepoint: bool,
+
+    // the number of ReadyForQuery messages up to which database errors are ignored, as they
+    // belong to a statement whose stream was dropped and which was rolled back
+    ignored_errors: usize,
+
     log_settings: LogSettings,
+
+    pub(crate) statement_recorder: Option<PgStatementRecorder>,
+
+    // whether to check for timestamps converted with the session time zone
+    time_zone_policy: PgTimeZonePolicy,
+
+    // the types whose values are requested in text instead of binary
+    result_formats: HashMap<Oid, PgValueFormat>,
 }
 
 impl PgConnection {
@@ -81,12 +128,18 @@ impl PgConnection {
 
     // will return when the connection is ready for another query
     pub(crate) async fn wait_until_ready(&mut self) -> Result<(), Error> {
+        // a batched fetch was dropped before its end
+        self.end_portal_fetch()?;
+
         if !self.inner.stream.write_buffer_mut().is_empty() {
             self.inner.stream.flush().await?;
         }
 
         while self.inner.pending_ready_for_query_count > 0 {
-            let message = self.inner.stream.recv().await?;
+            let message = match self.inner.stream.recv().await {
+                Err(Error::Database(_)) if self.inner.ignored_errors > 0 => continue,
+                message => message?,
+            };
 
             if let BackendMessageFormat::ReadyForQuery = message.format {
                 self.handle_ready_for_query(message)?;
@@ -100,6 +153,7 @@ impl PgConnection {
         let r: ReadyForQuery = self.inner.stream.recv_expect().await?;
 
         self.inner.pending_ready_for_query_count -= 1;
+        self.inner.ignored_errors = self.inner.ignored_errors.saturating_sub(1);
         self.inner.transaction_status = r.transaction_status;
 
         Ok(())
@@ -112,6 +166,7 @@ impl PgConnection {
             .pending_ready_for_query_count
             .checked_sub(1)
             .ok_or_else(|| err_protocol!("received more ReadyForQuery messages than expected"))?;
+        self.inner.ignored_errors = self.inner.ignored_errors.saturating_sub(1);
 
         self.inner.transaction_status = message.decode::<ReadyForQuery>()?.transaction_status;
 
@@ -123,9 +178,14 @@ impl PgConnection {
     /// Used for rolling back transactions and releasing advisory locks.
     #[inline(always)]
     pub(crate) fn queue_simple_query(&mut self, query: &str) -> Result<(), Error> {
+        // the portal of a batched fetch must be ended by `Sync` before another query
+        self.end_portal_fetch()?;
+
         self.inner.stream.write_msg(Query(query))?;
         self.inner.pending_ready_for_query_count += 1;
 
+        self.track_search_path(query);
+
         Ok(())
     }
 
@@ -229,6 +289,42 @@ impl Connection for PgConnection {
         })
     }
 
+    fn cached_statements(&self) -> CachedStatements {
+        CachedStatements {
+            size: self.inner.cache_statement.len(),
+            capacity: self.inner.cache_statement.capacity(),
+        }
+    }
+
+    fn set_statement_cache_capacity(
+        &mut self,
+        capacity: usize,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.wait_until_ready().await?;
+
+            let evicted = self.inner.cache_statement.set_capacity(capacity);
+
+            if evicted.is_empty() {
+                return Ok(());
+            }
+
+            let closed = evicted.len();
+
+            for (id, _) in evicted {
+                self.inner.stream.write_msg(Close::Statement(id))?;
+            }
+
+            self.write_sync();
+            self.inner.stream.flush().await?;
+
+            self.wait_for_close_complete(closed).await?;
+            self.recv_ready_for_query().await?;
+
+            Ok(())
+        })
+    }
+
     fn shrink_buffers(&mut self) {
         self.inner.stream.shrink_buffers();
     }
@@ -242,6 +338,14 @@ impl Connection for PgConnection {
     fn should_flush(&self) -> bool {
         !self.inner.stream.write_buffer().is_empty()
     }
+
+    #[doc(hidden)]
+    fn set_statement_journal(
+        &mut self,
+        journal: Option<StatementJournal>,
+    ) -> Option<StatementJournal> {
+        std::mem::replace(&mut self.inner.log_settings.journal, journal)
+    }
 }
 
 // Implement `AsMut<Self>` so that `PgConnection` can be wrapped in
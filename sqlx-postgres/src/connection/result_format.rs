@@ -0,0 +1,45 @@
+use crate::statement::PgStatementMetadata;
+use crate::types::Oid;
+use crate::value::{PgResultFormats, PgValueFormat};
+use crate::PgConnection;
+
+impl PgConnection {
+    /// Sets the format in which the server sends values of the type with `oid` in the results
+    /// of prepared statements, for the rest of the session.
+    ///
+    /// See [`PgConnectOptions::result_format()`][crate::PgConnectOptions::result_format].
+    pub fn set_result_format(&mut self, oid: Oid, format: PgValueFormat) {
+        match format {
+            PgValueFormat::Binary => self.inner.result_formats.remove(&oid),
+            PgValueFormat::Text => self.inner.result_formats.insert(oid, format),
+        };
+    }
+
+    /// The formats to request for the columns of a prepared statement.
+    pub(super) fn result_formats(&self, metadata: &PgStatementMetadata) -> PgResultFormats {
+        let binary = PgResultFormats::All(PgValueFormat::Binary);
+
+        if self.inner.result_formats.is_empty() {
+            return binary;
+        }
+
+        let formats: Vec<PgValueFormat> = metadata
+            .columns
+            .iter()
+            .map(|column| {
+                column
+                    .type_info
+                    .0
+                    .try_oid()
+                    .and_then(|oid| self.inner.result_formats.get(&oid).copied())
+                    .unwrap_or(PgValueFormat::Binary)
+            })
+            .collect();
+
+        if formats.contains(&PgValueFormat::Text) {
+            PgResultFormats::PerColumn(formats.into())
+        } else {
+            binary
+        }
+    }
+}
@@ -87,7 +87,10 @@ pub(crate) async fn authenticate(
 
     // SaltedPassword := Hi(Normalize(password), salt, i)
     let salted_password = hi(
-        options.password.as_deref().unwrap_or_default(),
+        options
+            .password
+            .as_ref()
+            .map_or("", |password| password.as_str()),
         &cont.salt,
         cont.iterations,
     )?;
@@ -0,0 +1,177 @@
+use sqlx_core::connection::Connection;
+use sqlx_core::executor::Executor;
+
+use crate::error::Error;
+use crate::message::TransactionStatus;
+use crate::PgConnection;
+
+impl PgConnection {
+    /// Set the `search_path` of the session to `schemas`, in order, and clear the statement
+    /// cache.
+    ///
+    /// Each schema is quoted as an identifier, so names from untrusted input (e.g. the schema
+    /// of a tenant) can't inject SQL, and are matched exactly rather than folded to lower
+    /// case. An empty list leaves only the system schemas in the path.
+    ///
+    /// Prepared statements keep referring to the tables they were prepared against, and their
+    /// result columns are cached, so a statement cached under the previous `search_path` could
+    /// read the tables of another schema or fail with `cached plan must not change result
+    /// type`. The cache is also cleared when the `search_path` is changed any other way:
+    ///
+    /// * As of PostgreSQL 18, the server reports changes to the `search_path`, including those
+    ///   undone at the end of a transaction.
+    /// * With older servers, before the next statement is prepared after running SQL which
+    ///   mentions `search_path` (as in `SET search_path` or `set_config('search_path', ...)`)
+    ///   or could reset it (`RESET ALL`, `DISCARD ALL`). As the end of a transaction, or the
+    ///   rollback of a savepoint, can undo such a change, the cache is cleared again then.
+    ///
+    /// Changing the `search_path` with SQL which doesn't name it, e.g. by binding
+    /// `'search_path'` as the name of a setting in a query of your own, is only detected as of
+    /// PostgreSQL 18. [`PgTransactionExt::set_local()`][crate::PgTransactionExt::set_local]
+    /// is tracked with older servers too.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// conn.set_search_path(&["tenant_42", "public"]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_search_path(&mut self, schemas: &[&str]) -> Result<(), Error> {
+        let sql = search_path_sql(schemas);
+
+        self.execute(&*sql).await?;
+        self.clear_cached_statements().await?;
+        self.inner.search_path_changed = false;
+
+        self.inner.cache_search_path = self
+            .inner
+            .stream
+            .parameter_statuses
+            .get("search_path")
+            .cloned();
+
+        Ok(())
+    }
+
+    /// Clear the statement cache if the `search_path` the cached statements were prepared with
+    /// may have changed.
+    pub(super) async fn check_search_path(&mut self) -> Result<(), Error> {
+        if let Some(reported) = self.inner.stream.parameter_statuses.get("search_path") {
+            if Some(reported) != self.inner.cache_search_path.as_ref() {
+                let reported = reported.clone();
+
+                self.clear_cached_statements().await?;
+                self.inner.cache_search_path = Some(reported);
+            }
+
+            return Ok(());
+        }
+
+        let idle = match self.inner.transaction_status {
+            TransactionStatus::Idle => true,
+            TransactionStatus::Transaction => false,
+            // nothing can be prepared until the transaction is rolled back
+            TransactionStatus::Error => return Ok(()),
+        };
+
+        if self.inner.search_path_changed || (idle && self.inner.search_path_revertible) {
+            self.clear_cached_statements().await?;
+            self.inner.search_path_changed = false;
+        }
+
+        if idle {
+            self.inner.search_path_revertible = false;
+        }
+
+        Ok(())
+    }
+
+    /// Note that `sql`, which is about to be executed, may change the `search_path`, for
+    /// servers which don't report it.
+    pub(crate) fn track_search_path(&mut self, sql: &str) {
+        if self
+            .inner
+            .stream
+            .parameter_statuses
+            .contains_key("search_path")
+        {
+            return;
+        }
+
+        if may_change_search_path(sql) {
+            self.search_path_changed();
+        } else if self.inner.search_path_revertible && contains_keyword(sql, "rollback") {
+            // `ROLLBACK TO SAVEPOINT` undoes changes made after the savepoint
+            self.inner.search_path_changed = true;
+        }
+    }
+
+    /// Note that the `search_path` may have been changed, for servers which don't report it.
+    pub(crate) fn search_path_changed(&mut self) {
+        self.inner.search_path_changed = true;
+        self.inner.search_path_revertible = true;
+    }
+}
+
+// Conservative, so that the statement cache is cleared rather than used with another
+// `search_path`: `RESET` and `DISCARD` may reset it without naming it.
+fn may_change_search_path(sql: &str) -> bool {
+    ["search_path", "reset", "discard"]
+        .iter()
+        .any(|keyword| contains_keyword(sql, keyword))
+}
+
+// Whether `keyword`, which must be lower case, appears in `sql` as a word of its own (or in
+// quotes), rather than as part of an identifier like `password_reset_tokens`.
+fn contains_keyword(sql: &str, keyword: &str) -> bool {
+    let is_word = |byte: Option<&u8>| byte.is_some_and(|b| b.is_ascii_alphanumeric() || *b == b'_');
+    let sql = sql.as_bytes();
+
+    sql.windows(keyword.len())
+        .enumerate()
+        .any(|(start, window)| {
+            window.eq_ignore_ascii_case(keyword.as_bytes())
+                && !is_word(start.checked_sub(1).and_then(|i| sql.get(i)))
+                && !is_word(sql.get(start + keyword.len()))
+        })
+}
+
+fn search_path_sql(schemas: &[&str]) -> String {
+    if schemas.is_empty() {
+        return "SET search_path TO ''".to_owned();
+    }
+
+    let schemas: Vec<String> = schemas
+        .iter()
+        .map(|schema| format!("\"{}\"", schema.replace('"', "\"\"")))
+        .collect();
+
+    format!("SET search_path TO {}", schemas.join(", "))
+}
+
+#[test]
+fn it_quotes_search_path() {
+    assert_eq!(
+        search_path_sql(&["tenant_42", "public"]),
+        r#"SET search_path TO "tenant_42", "public""#
+    );
+    assert_eq!(
+        search_path_sql(&[r#"a"; DROP TABLE users; --"#]),
+        r#"SET search_path TO "a""; DROP TABLE users; --""#
+    );
+    assert_eq!(search_path_sql(&[]), "SET search_path TO ''");
+}
+
+#[test]
+fn it_detects_search_path_changes() {
+    assert!(may_change_search_path("SET search_path TO tenant_b"));
+    assert!(may_change_search_path("set local SEARCH_PATH = 'a'"));
+    assert!(may_change_search_path(
+        "SELECT set_config('search_path', $1, false)"
+    ));
+    assert!(may_change_search_path("RESET ALL"));
+    assert!(may_change_search_path("DISCARD ALL"));
+    assert!(!may_change_search_path("SELECT * FROM tenant_data"));
+    assert!(!may_change_search_path("DELETE FROM password_reset_tokens"));
+    assert!(!may_change_search_path("SET statement_timeout TO 0"));
+}
@@ -0,0 +1,175 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use sqlx_core::connection::Connection;
+
+use crate::error::Error;
+use crate::message::Close;
+use crate::PgConnection;
+
+/// The number of stale statements remembered for connections which haven't caught up yet;
+/// a connection further behind clears its whole statement cache instead.
+const LOG_CAPACITY: usize = 64;
+
+/// The statements found to be stale by the connections made with the same options, e.g. all
+/// the connections of a pool, so that each of them re-prepares those statements before using
+/// them again.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct StaleStatements(Arc<StaleStatementsInner>);
+
+#[derive(Debug, Default)]
+struct StaleStatementsInner {
+    // the position of the next statement to be logged
+    next: AtomicU64,
+    // the SQL of the last statements logged, by position
+    log: Mutex<VecDeque<(u64, String)>>,
+}
+
+impl StaleStatements {
+    /// The position of the next statement to be logged.
+    pub(crate) fn position(&self) -> u64 {
+        self.0.next.load(Ordering::Acquire)
+    }
+
+    /// Log `sql` as stale, returning the position a connection which had seen the log up to
+    /// `seen` is at afterwards.
+    fn push(&self, sql: &str, seen: u64) -> u64 {
+        let mut log = self.0.log.lock().unwrap_or_else(|e| e.into_inner());
+
+        let position = self.0.next.load(Ordering::Acquire);
+
+        if log.len() == LOG_CAPACITY {
+            log.pop_front();
+        }
+        log.push_back((position, sql.to_owned()));
+
+        self.0.next.store(position + 1, Ordering::Release);
+
+        // skip the statement this connection just logged, unless others are still unseen
+        if seen == position {
+            position + 1
+        } else {
+            seen
+        }
+    }
+
+    /// The statements logged since `seen`, and the position after them, or `None` if some of
+    /// them are no longer in the log.
+    fn since(&self, seen: u64) -> (u64, Option<Vec<String>>) {
+        let log = self.0.log.lock().unwrap_or_else(|e| e.into_inner());
+
+        let position = self.0.next.load(Ordering::Acquire);
+
+        if seen == position {
+            return (position, Some(Vec::new()));
+        }
+
+        match log.front() {
+            Some(&(first, _)) if first <= seen => {
+                let statements = log
+                    .iter()
+                    .filter(|(logged, _)| *logged >= seen)
+                    .map(|(_, sql)| sql.clone())
+                    .collect();
+
+                (position, Some(statements))
+            }
+            _ => (position, None),
+        }
+    }
+}
+
+impl PgConnection {
+    /// Evict the statements other connections made with the same options found to be stale
+    /// since this connection last checked.
+    pub(super) async fn evict_stale_statements(&mut self) -> Result<(), Error> {
+        let seen = self.inner.stale_statements_seen;
+
+        if self.inner.stale_statements.position() == seen {
+            return Ok(());
+        }
+
+        let (position, statements) = self.inner.stale_statements.since(seen);
+        self.inner.stale_statements_seen = position;
+
+        match statements {
+            Some(statements) => {
+                for sql in statements {
+                    self.evict_statement(&sql).await?;
+                }
+            }
+            None => self.clear_cached_statements().await?,
+        }
+
+        Ok(())
+    }
+
+    /// Evict the statement for `sql` from the cache, as it was found to be stale, and have the
+    /// other connections made with the same options evict it too.
+    pub(super) async fn report_stale_statement(&mut self, sql: &str) -> Result<(), Error> {
+        self.evict_statement(sql).await?;
+
+        self.inner.stale_statements_seen = self
+            .inner
+            .stale_statements
+            .push(sql, self.inner.stale_statements_seen);
+
+        Ok(())
+    }
+
+    async fn evict_statement(&mut self, sql: &str) -> Result<(), Error> {
+        let Some((id, _)) = self.inner.cache_statement.remove(sql) else {
+            return Ok(());
+        };
+
+        self.wait_until_ready().await?;
+
+        self.inner.stream.write_msg(Close::Statement(id))?;
+        self.write_sync();
+        self.inner.stream.flush().await?;
+
+        self.wait_for_close_complete(1).await?;
+        self.recv_ready_for_query().await?;
+
+        Ok(())
+    }
+}
+
+/// Whether `error` means that a prepared statement no longer matches the schema, or no longer
+/// exists on the server, so it can be prepared again and the query retried.
+pub(super) fn is_stale_statement(error: &Error) -> bool {
+    let Some(error) = error.as_database_error() else {
+        return false;
+    };
+
+    match error.code().as_deref() {
+        // feature_not_supported, which is also used for other errors
+        Some("0A000") => error.message() == "cached plan must not change result type",
+        // invalid_sql_statement_name, e.g. after `DEALLOCATE ALL`
+        Some("26000") => true,
+        _ => false,
+    }
+}
+
+#[test]
+fn it_logs_stale_statements() {
+    let stale = StaleStatements::default();
+    assert_eq!(stale.since(0), (0, Some(vec![])));
+
+    // the connection which logged the first statement has seen it already
+    assert_eq!(stale.push("SELECT 1", 0), 1);
+    assert_eq!(stale.push("SELECT 2", 0), 0);
+    assert_eq!(
+        stale.since(0),
+        (2, Some(vec!["SELECT 1".to_owned(), "SELECT 2".to_owned()]))
+    );
+    assert_eq!(stale.since(1), (2, Some(vec!["SELECT 2".to_owned()])));
+
+    for i in 0..LOG_CAPACITY {
+        stale.push(&format!("SELECT {i}"), 0);
+    }
+
+    assert_eq!(stale.since(0), (66, None));
+    assert_eq!(stale.since(65).1.map(|s| s.len()), Some(1));
+}
@@ -0,0 +1,164 @@
+use crate::error::Error;
+use crate::io::StatementId;
+use crate::message::{self, ParameterDescription, Parse, ParseComplete, TransactionStatus};
+use crate::type_info::PgType;
+use crate::types::Oid;
+use crate::{PgConnection, PgTimeZonePolicy};
+
+/// The savepoint set around the check when it runs in a transaction, as a statement the server
+/// can't infer the parameters of would otherwise abort it.
+const CHECK_SAVEPOINT: &str = "_sqlx_time_zone_check";
+
+impl PgConnection {
+    /// Check the `TIMESTAMP` and `TIMESTAMPTZ` parameters of `sql` against the types the server
+    /// infers for them, as configured with [`PgTimeZonePolicy`].
+    ///
+    /// `param_types` are the types the parameters will be bound as.
+    pub(super) async fn check_time_zones(
+        &mut self,
+        sql: &str,
+        param_types: &[Oid],
+    ) -> Result<(), Error> {
+        let policy = self.inner.time_zone_policy;
+
+        if policy == PgTimeZonePolicy::Allow
+            || !param_types.iter().any(|&oid| timestamp_kind(oid).is_some())
+        {
+            return Ok(());
+        }
+
+        // leave the types of the timestamps unspecified, for the server to infer them
+        let probe_types: Vec<Oid> = param_types
+            .iter()
+            .map(|&oid| match timestamp_kind(oid) {
+                Some(_) => Oid(0),
+                None => oid,
+            })
+            .collect();
+
+        self.wait_until_ready().await?;
+
+        let in_transaction = self.in_transaction();
+        if in_transaction {
+            self.queue_simple_query(&format!("SAVEPOINT {CHECK_SAVEPOINT}"))?;
+            self.wait_until_ready().await?;
+        }
+
+        let inferred = self.describe_unnamed(sql, &probe_types).await;
+
+        if in_transaction {
+            if let TransactionStatus::Error = self.inner.transaction_status {
+                self.queue_simple_query(&format!(
+                    "ROLLBACK TO SAVEPOINT {CHECK_SAVEPOINT}; RELEASE SAVEPOINT {CHECK_SAVEPOINT}"
+                ))?;
+            } else {
+                self.queue_simple_query(&format!("RELEASE SAVEPOINT {CHECK_SAVEPOINT}"))?;
+            }
+            self.wait_until_ready().await?;
+        }
+
+        let inferred = match inferred {
+            Ok(inferred) => inferred,
+            // the statement is invalid, or the server can't infer the parameters without their
+            // types; any error is returned when it is prepared for real
+            Err(Error::Database(_)) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        for (i, (&bound, &inferred)) in param_types.iter().zip(&inferred.types).enumerate() {
+            let (Some(bound_kind), Some(inferred_kind)) =
+                (timestamp_kind(bound), timestamp_kind(inferred))
+            else {
+                continue;
+            };
+
+            if bound_kind.array != inferred_kind.array
+                || bound_kind.time_zone == inferred_kind.time_zone
+            {
+                continue;
+            }
+
+            let message = format!(
+                "parameter ${} is bound as {} where {} is expected, \
+                 so it is converted using the session time zone; \
+                 bind it as {} or cast it with `${}::{}` if this is intended",
+                i + 1,
+                bound_kind.name(),
+                inferred_kind.name(),
+                inferred_kind.name(),
+                i + 1,
+                bound_kind.name().to_ascii_lowercase(),
+            );
+
+            match policy {
+                PgTimeZonePolicy::Allow => {}
+                PgTimeZonePolicy::Warn => tracing::warn!(sql, "{message}"),
+                PgTimeZonePolicy::Deny => return Err(Error::Encode(message.into())),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parse `sql` into the unnamed statement and return the types of its parameters.
+    async fn describe_unnamed(
+        &mut self,
+        sql: &str,
+        param_types: &[Oid],
+    ) -> Result<ParameterDescription, Error> {
+        self.inner.stream.write_msg(Parse {
+            param_types,
+            query: sql,
+            statement: StatementId::UNNAMED,
+        })?;
+        self.inner
+            .stream
+            .write_msg(message::Describe::Statement(StatementId::UNNAMED))?;
+        self.write_sync();
+        self.inner.stream.flush().await?;
+
+        let res = async {
+            self.inner.stream.recv_expect::<ParseComplete>().await?;
+            self.inner
+                .stream
+                .recv_expect::<ParameterDescription>()
+                .await
+        }
+        .await;
+
+        // skip the row description, and the rest of the response on error
+        self.wait_until_ready().await?;
+
+        res
+    }
+}
+
+/// A `TIMESTAMP` or `TIMESTAMPTZ`, or an array of either.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TimestampKind {
+    time_zone: bool,
+    array: bool,
+}
+
+impl TimestampKind {
+    fn name(self) -> &'static str {
+        match (self.time_zone, self.array) {
+            (false, false) => "TIMESTAMP",
+            (true, false) => "TIMESTAMPTZ",
+            (false, true) => "TIMESTAMP[]",
+            (true, true) => "TIMESTAMPTZ[]",
+        }
+    }
+}
+
+fn timestamp_kind(oid: Oid) -> Option<TimestampKind> {
+    let (time_zone, array) = match PgType::try_from_oid(oid)? {
+        PgType::Timestamp => (false, false),
+        PgType::Timestamptz => (true, false),
+        PgType::TimestampArray => (false, true),
+        PgType::TimestamptzArray => (true, true),
+        _ => return None,
+    };
+
+    Some(TimestampKind { time_zone, array })
+}
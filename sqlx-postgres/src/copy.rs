@@ -1,12 +1,15 @@
 use std::borrow::Cow;
 use std::ops::{Deref, DerefMut};
+use std::pin::pin;
 
 use futures_core::future::BoxFuture;
-use futures_core::stream::BoxStream;
+use futures_core::stream::{BoxStream, Stream};
+use futures_util::{StreamExt, TryStreamExt};
 
 use sqlx_core::bytes::{BufMut, Bytes};
 
 use crate::connection::PgConnection;
+use crate::copy_binary::{self, PgCopyBinaryReader, PgCopyDecode, PgCopyEncode};
 use crate::error::{Error, Result};
 use crate::ext::async_stream::TryAsyncStream;
 use crate::io::AsyncRead;
@@ -15,7 +18,7 @@ use crate::message::{
     CopyOutResponse, CopyResponseData, Query, ReadyForQuery,
 };
 use crate::pool::{Pool, PoolConnection};
-use crate::Postgres;
+use crate::{PgArguments, Postgres};
 
 impl PgConnection {
     /// Issue a `COPY FROM STDIN` statement and transition the connection to streaming data
@@ -61,6 +64,66 @@ impl PgConnection {
     ) -> Result<BoxStream<'c, Result<Bytes>>> {
         pg_begin_copy_out(self, statement).await
     }
+
+    /// Issue a `COPY ... FROM STDIN (FORMAT BINARY)` statement and send `rows` in the binary
+    /// format, which is the fastest way to load many rows into Postgres.
+    ///
+    /// Each row is a tuple of values, one per column of the statement and in the same order,
+    /// whose types must be the types of the columns; see [`PgCopyEncode`]. The number of rows
+    /// copied is returned. If a row cannot be encoded, the `COPY` is aborted and the error is
+    /// returned.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// use futures_util::stream;
+    ///
+    /// let users = stream::iter((1..=1_000_000).map(|id: i32| (id, format!("user {id}"))));
+    ///
+    /// let copied = conn
+    ///     .copy_in_binary("COPY users (id, name) FROM STDIN (FORMAT BINARY)", users)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_in_binary<R: PgCopyEncode>(
+        &mut self,
+        statement: &str,
+        rows: impl Stream<Item = R>,
+    ) -> Result<u64> {
+        pg_copy_in_binary(self, statement, rows).await
+    }
+
+    /// Issue a `COPY ... TO STDOUT (FORMAT BINARY)` statement and stream the rows it returns,
+    /// decoded as `R`.
+    ///
+    /// Each row is decoded as a tuple of values, one per column of the statement and in the same
+    /// order, whose types must be the types of the columns; see [`PgCopyDecode`].
+    ///
+    /// The note about cancelling a stream of [`copy_out_raw()`][Self::copy_out_raw] applies.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// use futures_util::TryStreamExt;
+    ///
+    /// let mut users = conn
+    ///     .copy_out_binary::<(i32, Option<String>)>("COPY users (id, name) TO STDOUT (FORMAT BINARY)")
+    ///     .await?;
+    ///
+    /// while let Some((id, name)) = users.try_next().await? {
+    ///     println!("{id}: {name:?}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_out_binary<'c, R>(
+        &'c mut self,
+        statement: &str,
+    ) -> Result<BoxStream<'c, Result<R>>>
+    where
+        R: PgCopyDecode + Send + 'c,
+    {
+        pg_begin_copy_out_binary(self, statement).await
+    }
 }
 
 /// Implements methods for directly executing `COPY FROM/TO STDOUT` on a [`PgPool`][crate::PgPool].
@@ -111,6 +174,34 @@ pub trait PgPoolCopyExt {
         &'a self,
         statement: &'a str,
     ) -> BoxFuture<'a, Result<BoxStream<'static, Result<Bytes>>>>;
+
+    /// Issue a `COPY ... FROM STDIN (FORMAT BINARY)` statement and send `rows` in the binary
+    /// format.
+    ///
+    /// A single connection will be checked out for the duration.
+    ///
+    /// See [`PgConnection::copy_in_binary()`] for details.
+    fn copy_in_binary<'a, R, S>(
+        &'a self,
+        statement: &'a str,
+        rows: S,
+    ) -> BoxFuture<'a, Result<u64>>
+    where
+        R: PgCopyEncode + Send + 'a,
+        S: Stream<Item = R> + Send + 'a;
+
+    /// Issue a `COPY ... TO STDOUT (FORMAT BINARY)` statement and stream the rows it returns,
+    /// decoded as `R`.
+    ///
+    /// A single connection will be checked out for the duration.
+    ///
+    /// See [`PgConnection::copy_out_binary()`] for details.
+    fn copy_out_binary<'a, R>(
+        &'a self,
+        statement: &'a str,
+    ) -> BoxFuture<'a, Result<BoxStream<'static, Result<R>>>>
+    where
+        R: PgCopyDecode + Send + 'static;
 }
 
 impl PgPoolCopyExt for Pool<Postgres> {
@@ -127,11 +218,32 @@ impl PgPoolCopyExt for Pool<Postgres> {
     ) -> BoxFuture<'a, Result<BoxStream<'static, Result<Bytes>>>> {
         Box::pin(async { pg_begin_copy_out(self.acquire().await?, statement).await })
     }
+
+    fn copy_in_binary<'a, R, S>(&'a self, statement: &'a str, rows: S) -> BoxFuture<'a, Result<u64>>
+    where
+        R: PgCopyEncode + Send + 'a,
+        S: Stream<Item = R> + Send + 'a,
+    {
+        Box::pin(async { pg_copy_in_binary(self.acquire().await?, statement, rows).await })
+    }
+
+    fn copy_out_binary<'a, R>(
+        &'a self,
+        statement: &'a str,
+    ) -> BoxFuture<'a, Result<BoxStream<'static, Result<R>>>>
+    where
+        R: PgCopyDecode + Send + 'static,
+    {
+        Box::pin(async { pg_begin_copy_out_binary(self.acquire().await?, statement).await })
+    }
 }
 
 // (1 GiB - 1) - 1 - length prefix (4 bytes)
 pub const PG_COPY_MAX_DATA_LEN: usize = 0x3fffffff - 1 - 4;
 
+// how much of the rows sent by `PgCopyIn::send_row()` is buffered before it is flushed
+const COPY_ROWS_FLUSH_LEN: usize = 64 * 1024;
+
 /// A connection in streaming `COPY FROM STDIN` mode.
 ///
 /// Created by [PgConnection::copy_in_raw] or [Pool::copy_out_raw].
@@ -143,6 +255,8 @@ pub const PG_COPY_MAX_DATA_LEN: usize = 0x3fffffff - 1 - 4;
 pub struct PgCopyIn<C: DerefMut<Target = PgConnection>> {
     conn: Option<C>,
     response: CopyResponseData,
+    // whether the header of the binary format was sent by `send_row()`
+    binary_header_sent: bool,
 }
 
 impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
@@ -161,6 +275,7 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
         Ok(PgCopyIn {
             conn: Some(conn),
             response,
+            binary_header_sent: false,
         })
     }
 
@@ -207,6 +322,80 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
         Ok(self)
     }
 
+    /// Send a row in the binary format, preceded by the header of the format if it is the first.
+    /// [`finish()`][Self::finish] then sends the trailer of the format.
+    ///
+    /// The statement must be a `COPY ... FROM STDIN (FORMAT BINARY)`, and the row must have a value
+    /// of the type of each of its columns; see [`PgCopyEncode`]. Rows are buffered, and sent once
+    /// enough of them were, or when the `COPY` is finished.
+    ///
+    /// As the connection cannot run queries during a `COPY`, the OIDs of the types which the
+    /// values name rather than give, such as arrays of custom types, must have been looked up by
+    /// the connection already, as [`PgConnection::copy_in_binary()`] does for the first row.
+    /// Otherwise, [`Error::TypeNotFound`] is returned.
+    pub async fn send_row(&mut self, row: impl PgCopyEncode) -> Result<&mut Self> {
+        let mut args = encode_copy_row(row)?;
+
+        args.apply_cached_patches(self.conn.as_deref().expect("send_row: conn taken"))?;
+        self.send_args(args).await?;
+
+        Ok(self)
+    }
+
+    async fn send_args(&mut self, args: PgArguments) -> Result<()> {
+        if args.types.len() != self.response.format_codes.len() {
+            return Err(Error::InvalidArgument(format!(
+                "a row of {} values was sent to a COPY of {} columns",
+                args.types.len(),
+                self.response.format_codes.len()
+            )));
+        }
+
+        let mut buf = Vec::new();
+        self.send_binary_header()?;
+        copy_binary::encode_row(&mut buf, &args)?;
+
+        let stream = &mut self
+            .conn
+            .as_deref_mut()
+            .expect("send_row: conn taken")
+            .inner
+            .stream;
+
+        for chunk in buf.chunks(PG_COPY_MAX_DATA_LEN) {
+            stream.write_msg(CopyData(chunk))?;
+        }
+
+        if stream.write_buffer().get().len() >= COPY_ROWS_FLUSH_LEN {
+            stream.flush().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the header of the binary format, unless it was already.
+    fn send_binary_header(&mut self) -> Result<()> {
+        if self.binary_header_sent {
+            return Ok(());
+        }
+
+        if self.is_textual() {
+            return Err(Error::InvalidArgument(
+                "rows can only be sent to a `COPY ... FROM STDIN (FORMAT BINARY)`".into(),
+            ));
+        }
+
+        self.conn
+            .as_deref_mut()
+            .expect("send_row: conn taken")
+            .inner
+            .stream
+            .write_msg(CopyData(copy_binary::HEADER))?;
+        self.binary_header_sent = true;
+
+        Ok(())
+    }
+
     /// Copy data directly from `source` to the database without requiring an intermediate buffer.
     ///
     /// `source` will be read to the end.
@@ -290,6 +479,12 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
             .take()
             .expect("CopyWriter::finish: conn taken illegally");
 
+        if self.binary_header_sent {
+            conn.inner
+                .stream
+                .write_msg(CopyData(copy_binary::TRAILER))?;
+        }
+
         conn.inner.stream.send(CopyDone).await?;
         let cc: CommandComplete = match conn.inner.stream.recv_expect().await {
             Ok(cc) => cc,
@@ -350,3 +545,81 @@ async fn pg_begin_copy_out<'c, C: DerefMut<Target = PgConnection> + Send + 'c>(
 
     Ok(Box::pin(stream))
 }
+
+async fn pg_copy_in_binary<C: DerefMut<Target = PgConnection>, R: PgCopyEncode>(
+    mut conn: C,
+    statement: &str,
+    rows: impl Stream<Item = R>,
+) -> Result<u64> {
+    let mut rows = pin!(rows);
+
+    // Encode the first row before the `COPY` begins, while the connection can still look up
+    // the OIDs of the types it names; the following rows likely name the same ones.
+    let first = match rows.next().await {
+        Some(row) => {
+            let mut args = encode_copy_row(row)?;
+            let types = args.types.clone();
+            args.apply_patches(&mut conn, &types).await?;
+            Some(args)
+        }
+        None => None,
+    };
+
+    let mut copy = PgCopyIn::begin(conn, statement).await?;
+
+    let sent = async {
+        // an empty `COPY` still needs the header and trailer
+        copy.send_binary_header()?;
+
+        if let Some(args) = first {
+            copy.send_args(args).await?;
+        }
+
+        while let Some(row) = rows.next().await {
+            copy.send_row(row).await?;
+        }
+
+        Ok::<_, Error>(())
+    }
+    .await;
+
+    match sent {
+        Ok(()) => copy.finish().await,
+        Err(error) => {
+            copy.abort(error.to_string()).await?;
+            Err(error)
+        }
+    }
+}
+
+async fn pg_begin_copy_out_binary<'c, C, R>(
+    conn: C,
+    statement: &str,
+) -> Result<BoxStream<'c, Result<R>>>
+where
+    C: DerefMut<Target = PgConnection> + Send + 'c,
+    R: PgCopyDecode + Send + 'c,
+{
+    let mut data = pg_begin_copy_out(conn, statement).await?;
+    let mut reader = PgCopyBinaryReader::default();
+
+    let stream: TryAsyncStream<'c, R> = try_stream! {
+        while let Some(chunk) = data.try_next().await? {
+            reader.push(&chunk);
+
+            while let Some(row) = reader.next_row()? {
+                r#yield!(R::decode_row(&row)?);
+            }
+        }
+
+        reader.finish()
+    };
+
+    Ok(Box::pin(stream))
+}
+
+fn encode_copy_row(row: impl PgCopyEncode) -> Result<PgArguments> {
+    let mut args = PgArguments::default();
+    row.encode_row(&mut args).map_err(Error::Encode)?;
+    Ok(args)
+}
@@ -0,0 +1,510 @@
+//! The binary format of `COPY`, for sending and receiving typed rows with
+//! [`PgConnection::copy_in_binary()`][crate::PgConnection::copy_in_binary] and
+//! [`PgConnection::copy_out_binary()`][crate::PgConnection::copy_out_binary].
+//!
+//! <https://www.postgresql.org/docs/current/sql-copy.html#id-1.9.3.55.9.4>
+
+use std::cmp;
+
+use sqlx_core::bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::decode::Decode;
+use crate::encode::Encode;
+use crate::error::{BoxDynError, Error};
+use crate::types::Type;
+use crate::value::{PgValueFormat, PgValueRef};
+use crate::{PgArguments, Postgres};
+
+/// The signature, flags and header extension length which start the binary format.
+pub(crate) const HEADER: &[u8] = b"PGCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\0";
+
+/// The field count of `-1` which ends the binary format.
+pub(crate) const TRAILER: &[u8] = &[0xff, 0xff];
+
+// the flag set when each row starts with its OID, `WITH OIDS` before Postgres 12
+const FLAG_OIDS: u32 = 1 << 16;
+
+/// A row which can be sent by [`PgCopyIn::send_row()`][crate::PgCopyIn::send_row].
+///
+/// Implemented for tuples of up to 16 values which implement [`Encode`]. The values are sent
+/// in the binary format of their types, which must be the types of the columns of the `COPY`:
+/// unlike a query parameter, a value is not converted to the type of its column.
+pub trait PgCopyEncode {
+    /// Add the values of this row to `args`, one per column of the `COPY` and in their order.
+    fn encode_row(self, args: &mut PgArguments) -> Result<(), BoxDynError>;
+}
+
+/// A row which can be received from
+/// [`PgConnection::copy_out_binary()`][crate::PgConnection::copy_out_binary].
+///
+/// Implemented for tuples of up to 16 values which implement [`Decode`]. As the binary format
+/// does not include the types of the columns, each value is decoded as the type of its Rust
+/// type, which must be the type of its column.
+pub trait PgCopyDecode: Sized {
+    fn decode_row(row: &PgCopyRow) -> Result<Self, Error>;
+}
+
+/// A row received in the binary format of `COPY`.
+#[derive(Debug, Clone)]
+pub struct PgCopyRow {
+    fields: Vec<Option<Bytes>>,
+}
+
+impl PgCopyRow {
+    /// The number of fields of the row.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// The field at `index` in the binary format of its type, or `None` if it is `NULL`.
+    pub fn try_get_raw(&self, index: usize) -> Result<Option<&[u8]>, Error> {
+        self.fields
+            .get(index)
+            .map(Option::as_deref)
+            .ok_or(Error::ColumnIndexOutOfBounds {
+                index,
+                len: self.fields.len(),
+            })
+    }
+
+    /// Decode the field at `index` as a `T`.
+    pub fn try_decode<'r, T>(&'r self, index: usize) -> Result<T, Error>
+    where
+        T: Decode<'r, Postgres> + Type<Postgres>,
+    {
+        let value = PgValueRef {
+            value: self.try_get_raw(index)?,
+            row: None,
+            type_info: T::type_info(),
+            format: PgValueFormat::Binary,
+        };
+
+        T::decode(value).map_err(|source| Error::ColumnDecode {
+            index: format!("{index:?}"),
+            source,
+        })
+    }
+}
+
+/// Append a row with the values of `args` to `buf`.
+pub(crate) fn encode_row(buf: &mut Vec<u8>, args: &PgArguments) -> Result<(), Error> {
+    let count = i16::try_from(args.types.len()).map_err(|_| {
+        Error::InvalidArgument(format!(
+            "too many values in a row to COPY: {}",
+            args.types.len()
+        ))
+    })?;
+
+    buf.put_i16(count);
+    // each value is already prefixed with its length, or `-1` if `NULL`
+    buf.extend_from_slice(&args.buffer);
+
+    Ok(())
+}
+
+/// Splits the data of a `COPY TO STDOUT` in the binary format into rows, however it is divided
+/// into messages.
+#[derive(Default)]
+pub(crate) struct PgCopyBinaryReader {
+    buf: BytesMut,
+    header_read: bool,
+    finished: bool,
+}
+
+impl PgCopyBinaryReader {
+    pub(crate) fn push(&mut self, data: &[u8]) {
+        self.buf.extend_from_slice(data);
+    }
+
+    /// The next row received in full, if any.
+    pub(crate) fn next_row(&mut self) -> Result<Option<PgCopyRow>, Error> {
+        if !self.header_read && !self.read_header()? {
+            return Ok(None);
+        }
+
+        if self.finished {
+            return if self.buf.is_empty() {
+                Ok(None)
+            } else {
+                Err(err_protocol!(
+                    "binary COPY data continues after its trailer"
+                ))
+            };
+        }
+
+        if self.buf.len() < 2 {
+            return Ok(None);
+        }
+
+        let count = (&self.buf[..]).get_i16();
+
+        if count == -1 {
+            self.buf.advance(2);
+            self.finished = true;
+            return self.next_row();
+        }
+
+        let count = usize::try_from(count)
+            .map_err(|_| err_protocol!("invalid field count in binary COPY data: {count}"))?;
+
+        let mut fields = Vec::with_capacity(count);
+        let mut offset = 2;
+
+        for _ in 0..count {
+            let Some(mut len) = self.buf.get(offset..offset + 4) else {
+                return Ok(None);
+            };
+            let len = len.get_i32();
+            offset += 4;
+
+            if len == -1 {
+                fields.push(None);
+                continue;
+            }
+
+            let len = usize::try_from(len)
+                .map_err(|_| err_protocol!("invalid field length in binary COPY data: {len}"))?;
+
+            if self.buf.len() < offset + len {
+                return Ok(None);
+            }
+
+            fields.push(Some(offset..offset + len));
+            offset += len;
+        }
+
+        let row = self.buf.split_to(offset).freeze();
+
+        Ok(Some(PgCopyRow {
+            fields: fields
+                .into_iter()
+                .map(|range| range.map(|range| row.slice(range)))
+                .collect(),
+        }))
+    }
+
+    /// Check that the data ended with the trailer.
+    pub(crate) fn finish(&self) -> Result<(), Error> {
+        if self.finished {
+            Ok(())
+        } else {
+            Err(err_protocol!("binary COPY data ended without its trailer"))
+        }
+    }
+
+    fn read_header(&mut self) -> Result<bool, Error> {
+        let signature = &HEADER[..11];
+
+        if !signature.starts_with(&self.buf[..cmp::min(self.buf.len(), 11)]) {
+            return Err(err_protocol!(
+                "expected COPY data in the binary format; use `COPY ... TO STDOUT (FORMAT BINARY)`"
+            ));
+        }
+
+        let Some(mut rest) = self.buf.get(11..19) else {
+            return Ok(false);
+        };
+
+        let flags = rest.get_u32();
+        let extension_len = rest.get_u32() as usize;
+
+        if flags & FLAG_OIDS != 0 {
+            return Err(err_protocol!("binary COPY data with OIDs is not supported"));
+        }
+
+        if self.buf.len() < 19 + extension_len {
+            return Ok(false);
+        }
+
+        self.buf.advance(19 + extension_len);
+        self.header_read = true;
+
+        Ok(true)
+    }
+}
+
+macro_rules! impl_copy_row_for_tuple {
+    ($( ($idx:tt) -> $T:ident );+;) => {
+        impl<$($T,)+> PgCopyEncode for ($($T,)+)
+        where
+            $($T: for<'q> Encode<'q, Postgres> + Type<Postgres>,)+
+        {
+            #[inline]
+            fn encode_row(self, args: &mut PgArguments) -> Result<(), BoxDynError> {
+                $(args.add(self.$idx)?;)+
+                Ok(())
+            }
+        }
+
+        impl<$($T,)+> PgCopyDecode for ($($T,)+)
+        where
+            $($T: for<'r> Decode<'r, Postgres> + Type<Postgres>,)+
+        {
+            #[inline]
+            fn decode_row(row: &PgCopyRow) -> Result<Self, Error> {
+                Ok(($(row.try_decode($idx)?,)+))
+            }
+        }
+    };
+}
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+    (13) -> T14;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+    (13) -> T14;
+    (14) -> T15;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+    (13) -> T14;
+    (14) -> T15;
+    (15) -> T16;
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode<R: PgCopyEncode>(rows: impl IntoIterator<Item = R>) -> Vec<u8> {
+        let mut buf = HEADER.to_vec();
+
+        for row in rows {
+            let mut args = PgArguments::default();
+            row.encode_row(&mut args).unwrap();
+            encode_row(&mut buf, &args).unwrap();
+        }
+
+        buf.extend_from_slice(TRAILER);
+        buf
+    }
+
+    #[test]
+    fn it_reads_rows_however_they_are_split() {
+        let data = encode([(1_i32, Some("one")), (2, None)]);
+
+        for chunk_len in 1..=data.len() {
+            let mut reader = PgCopyBinaryReader::default();
+            let mut rows = Vec::new();
+
+            for chunk in data.chunks(chunk_len) {
+                reader.push(chunk);
+
+                while let Some(row) = reader.next_row().unwrap() {
+                    rows.push(<(i32, Option<String>)>::decode_row(&row).unwrap());
+                }
+            }
+
+            reader.finish().unwrap();
+            assert_eq!(rows, [(1, Some("one".to_owned())), (2, None)]);
+        }
+    }
+
+    #[test]
+    fn it_rejects_text_and_truncated_data() {
+        let mut reader = PgCopyBinaryReader::default();
+        reader.push(b"1\tone\n");
+        assert!(reader.next_row().is_err());
+
+        let data = encode([(1_i64,)]);
+        let mut reader = PgCopyBinaryReader::default();
+        reader.push(&data[..data.len() - 2]);
+        assert!(reader.next_row().unwrap().is_some());
+        assert!(reader.next_row().unwrap().is_none());
+        assert!(reader.finish().is_err());
+    }
+
+    #[test]
+    fn it_reports_the_field_which_failed_to_decode() {
+        let data = encode([(1_i32, None::<i16>)]);
+        let mut reader = PgCopyBinaryReader::default();
+        reader.push(&data);
+        let row = reader.next_row().unwrap().unwrap();
+
+        assert_eq!(row.len(), 2);
+        assert_eq!(row.try_get_raw(0).unwrap(), Some(&[0, 0, 0, 1][..]));
+        assert_eq!(row.try_get_raw(1).unwrap(), None);
+        assert!(matches!(
+            row.try_decode::<i16>(1),
+            Err(Error::ColumnDecode { index, .. }) if index == "1"
+        ));
+        assert!(matches!(
+            <(i32, Option<i16>, i32)>::decode_row(&row),
+            Err(Error::ColumnIndexOutOfBounds { index: 2, len: 2 })
+        ));
+    }
+}
@@ -239,4 +239,6 @@ pub(crate) mod error_codes {
     pub const NOT_NULL_VIOLATION: &str = "23502";
     /// Caused when a check constraint is violated.
     pub const CHECK_VIOLATION: &str = "23514";
+    /// Caused when a prepared statement is given a name which is already taken.
+    pub const DUPLICATE_PREPARED_STATEMENT: &str = "42P05";
 }
@@ -0,0 +1,74 @@
+use futures_core::future::BoxFuture;
+use sqlx_core::estimate::{CountTarget, EstimateCount};
+
+use crate::error::Error;
+use crate::query_as::query_as;
+use crate::query_scalar::query_scalar;
+use crate::{PgConnection, Postgres};
+
+impl EstimateCount for Postgres {
+    fn estimate_count<'c>(
+        conn: &'c mut PgConnection,
+        target: CountTarget<'c>,
+    ) -> BoxFuture<'c, Result<u64, Error>> {
+        Box::pin(async move {
+            let query = match target {
+                CountTarget::Table(table) => {
+                    // language=PostgreSQL
+                    let (reltuples, name): (f32, String) = query_as(
+                        "SELECT reltuples, oid::regclass::text FROM pg_class \
+                         WHERE oid = to_regclass($1)",
+                    )
+                    .bind(table)
+                    .fetch_optional(&mut *conn)
+                    .await?
+                    .ok_or_else(|| {
+                        Error::InvalidArgument(format!("relation {table:?} does not exist"))
+                    })?;
+
+                    // `reltuples` is -1 for tables which have never been vacuumed or analyzed
+                    // (0 before Postgres 14), and always 0 for views
+                    if reltuples > 0.0 {
+                        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                        return Ok(reltuples.round() as u64);
+                    }
+
+                    format!("SELECT * FROM {name}")
+                }
+                CountTarget::Query(query) => query.to_owned(),
+            };
+
+            let plan: String = query_scalar(&format!("EXPLAIN {query}"))
+                .persistent(false)
+                .fetch_one(&mut *conn)
+                .await?;
+
+            // the first line of the plan is the top node, with the estimate for the whole query:
+            // `Seq Scan on users  (cost=0.00..35.50 rows=2550 width=4)`
+            parse_explain_rows(&plan)
+                .ok_or_else(|| Error::Protocol(format!("no row estimate in query plan {plan:?}")))
+        })
+    }
+}
+
+fn parse_explain_rows(line: &str) -> Option<u64> {
+    let (_, rest) = line.split_once(" rows=")?;
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+
+    rest[..end].parse().ok()
+}
+
+#[test]
+fn test_parse_explain_rows() {
+    assert_eq!(
+        parse_explain_rows("Seq Scan on users  (cost=0.00..35.50 rows=2550 width=4)"),
+        Some(2550)
+    );
+    assert_eq!(
+        parse_explain_rows("Result  (cost=0.00..0.01 rows=1 width=4)"),
+        Some(1)
+    );
+    assert_eq!(parse_explain_rows("  Filter: active"), None);
+}
@@ -0,0 +1,189 @@
+//! Entry points for the fuzz targets in `fuzz/`; not part of the public API.
+
+use std::ops::ControlFlow;
+
+use sqlx_core::bytes::BytesMut;
+
+use crate::error::Error;
+use crate::message::{
+    Authentication, BackendKeyData, BackendMessageFormat, CommandComplete, CopyBothResponse,
+    CopyData, CopyInResponse, CopyOutResponse, DataRow, Notice, Notification, ParameterDescription,
+    ParameterStatus, ReadyForQuery, ReceivedMessage, RowDescription,
+};
+use crate::PgDatabaseError;
+
+/// Read `data` as the bytes sent by a server, decoding each message as a connection would,
+/// including the parts of the messages which are only decoded on use.
+///
+/// Stops at the first message which is incomplete or fails to decode, and returns its error.
+pub fn backend_messages(data: &[u8]) -> Result<(), Error> {
+    let mut buf = BytesMut::from(data);
+
+    loop {
+        match ReceivedMessage::split_from(&mut buf)? {
+            ControlFlow::Break(message) => decode(message)?,
+            ControlFlow::Continue(_) => return Ok(()),
+        }
+    }
+}
+
+fn decode(message: ReceivedMessage) -> Result<(), Error> {
+    match message.format {
+        BackendMessageFormat::Authentication => {
+            if let Authentication::Sasl(sasl) = message.decode()? {
+                sasl.mechanisms().for_each(drop);
+            }
+        }
+        BackendMessageFormat::BackendKeyData => {
+            message.decode::<BackendKeyData>()?;
+        }
+        BackendMessageFormat::CommandComplete => {
+            message.decode::<CommandComplete>()?.rows_affected();
+        }
+        BackendMessageFormat::CopyBothResponse => {
+            message.decode::<CopyBothResponse>()?;
+        }
+        BackendMessageFormat::CopyData => {
+            message.decode::<CopyData<_>>()?;
+        }
+        BackendMessageFormat::CopyInResponse => {
+            message.decode::<CopyInResponse>()?;
+        }
+        BackendMessageFormat::CopyOutResponse => {
+            message.decode::<CopyOutResponse>()?;
+        }
+        BackendMessageFormat::DataRow => {
+            let row: DataRow = message.decode()?;
+            for index in 0..row.values.len() {
+                row.get(index);
+            }
+        }
+        BackendMessageFormat::ErrorResponse => {
+            let error: PgDatabaseError = message.decode()?;
+            error.position();
+            error.line();
+        }
+        BackendMessageFormat::NoticeResponse => {
+            message.decode::<Notice>()?.severity();
+        }
+        BackendMessageFormat::NotificationResponse => {
+            message.decode::<Notification>()?;
+        }
+        BackendMessageFormat::ParameterDescription => {
+            message.decode::<ParameterDescription>()?;
+        }
+        BackendMessageFormat::ParameterStatus => {
+            message.decode::<ParameterStatus>()?;
+        }
+        BackendMessageFormat::ReadyForQuery => {
+            message.decode::<ReadyForQuery>()?;
+        }
+        BackendMessageFormat::RowDescription => {
+            message.decode::<RowDescription>()?;
+        }
+        // no body
+        BackendMessageFormat::BindComplete
+        | BackendMessageFormat::CloseComplete
+        | BackendMessageFormat::CopyDone
+        | BackendMessageFormat::EmptyQueryResponse
+        | BackendMessageFormat::NoData
+        | BackendMessageFormat::ParseComplete
+        | BackendMessageFormat::PortalSuspended => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The messages of a connection and of a query, like the seed of the fuzz target.
+    const MESSAGES: &[(u8, &[u8])] = &[
+        (b'R', b"\0\0\0\x0aSCRAM-SHA-256\0\0"),
+        (
+            b'R',
+            b"\0\0\0\x0br=fyko+d2lbbFgONRv9qkxdawL3rfcNHYJY1ZVvWVs7j,s=QSXCR+Q6sek8bf92,i=4096",
+        ),
+        (
+            b'R',
+            b"\0\0\0\x0cv=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=",
+        ),
+        (b'R', b"\0\0\0\x05\x01\x02\x03\x04"),
+        (b'R', b"\0\0\0\0"),
+        (b'S', b"server_version\x0015.4\0"),
+        (b'K', b"\0\0\x04\xd2\0\0\x16."),
+        (b'Z', b"I"),
+        (b'1', b""),
+        (b't', b"\0\x02\0\0\0\x17\0\0\0\x19"),
+        (
+            b'T',
+            b"\0\x02id\0\0\0@\0\0\x01\0\0\0\x17\0\x04\xff\xff\xff\xff\0\x01\
+              name\0\0\0@\0\0\x02\0\0\0\x19\xff\xff\xff\xff\xff\xff\0\x01",
+        ),
+        (b'2', b""),
+        (b'D', b"\0\x02\0\0\0\x04\0\0\0*\xff\xff\xff\xff"),
+        (b'C', b"SELECT 1\0"),
+        (
+            b'N',
+            b"SNOTICE\0VNOTICE\0C42710\0Mextension \"uuid-ossp\" already exists, skipping\0\
+              Fextension.c\0L1656\0RCreateExtension\0\0",
+        ),
+        (
+            b'E',
+            b"SERROR\0VERROR\0C42601\0Msyntax error at or near \"SELEC\"\0P1\0\
+              Fscan.l\0L1176\0Rscanner_yyerror\0\0",
+        ),
+        (b'A', b"\0\0\x10\xe1channel\0payload\0"),
+        (b'G', b"\0\0\x02\0\0\0\0"),
+        (b'H', b"\x01\0\x01\0\x01"),
+        (b'd', b"1\tone\n"),
+        (b'c', b""),
+        (b'C', b"COPY 1\0"),
+        (b'n', b""),
+        (b'I', b""),
+        (b's', b""),
+        (b'3', b""),
+        (b'Z', b"T"),
+    ];
+
+    fn message(format: u8, body: &[u8]) -> Vec<u8> {
+        let mut data = vec![format];
+        data.extend(u32::try_from(body.len() + 4).unwrap().to_be_bytes());
+        data.extend(body);
+        data
+    }
+
+    #[track_caller]
+    fn assert_protocol_error_or_ok(data: &[u8]) {
+        match backend_messages(data) {
+            Ok(()) | Err(Error::Protocol(_)) => {}
+            Err(error) => panic!("expected a protocol error, got {error:?} in {data:02x?}"),
+        }
+    }
+
+    // `fuzz/` finds more, but is not run with the tests
+    #[test]
+    fn it_fails_on_truncated_and_corrupted_messages_without_panicking() {
+        let messages: Vec<u8> = MESSAGES
+            .iter()
+            .flat_map(|&(format, body)| message(format, body))
+            .collect();
+
+        backend_messages(&messages).unwrap();
+
+        for &(format, body) in MESSAGES {
+            for len in 0..body.len() {
+                assert_protocol_error_or_ok(&message(format, &body[..len]));
+            }
+
+            for index in 0..body.len() {
+                for byte in [0x00, b',', 0x7f, 0xff] {
+                    let mut corrupted = body.to_vec();
+                    corrupted[index] = byte;
+                    assert_protocol_error_or_ok(&message(format, &corrupted));
+                }
+            }
+        }
+    }
+}
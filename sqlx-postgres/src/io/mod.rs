@@ -1,14 +1,29 @@
 mod buf_mut;
 
 pub use buf_mut::PgBufMutExt;
+use std::collections::BTreeSet;
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::num::{NonZeroU32, Saturating};
+use std::sync::{Mutex, PoisonError};
+
+use crate::error::Error;
 
 pub(crate) use sqlx_core::io::*;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub(crate) struct StatementId(IdInner);
+pub(crate) struct StatementId {
+    id: IdInner,
+    names: StatementNames,
+}
+
+/// How the statements of a connection are named: a prefix, and the random tag of the connection
+/// if names are to be unique, followed by a number.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct StatementNames {
+    prefix: &'static str,
+    tag: Option<u64>,
+}
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub(crate) struct PortalId(IdInner);
@@ -18,26 +33,44 @@ struct IdInner(Option<NonZeroU32>);
 
 pub(crate) struct DisplayId {
     prefix: &'static str,
+    tag: Option<u64>,
     id: NonZeroU32,
 }
 
 impl StatementId {
     #[allow(dead_code)]
-    pub const UNNAMED: Self = Self(IdInner::UNNAMED);
-
-    pub const NAMED_START: Self = Self(IdInner::NAMED_START);
+    pub const UNNAMED: Self = Self {
+        id: IdInner::UNNAMED,
+        names: StatementNames::DEFAULT,
+    };
 
     #[cfg(test)]
-    pub const TEST_VAL: Self = Self(IdInner::TEST_VAL);
-
-    const NAME_PREFIX: &'static str = "sqlx_s_";
+    pub const TEST_VAL: Self = Self {
+        id: IdInner::TEST_VAL,
+        names: StatementNames::DEFAULT,
+    };
+
+    /// The first named statement of a connection naming them with `names`.
+    pub fn named_start(names: StatementNames) -> Self {
+        Self {
+            id: IdInner::NAMED_START,
+            names,
+        }
+    }
 
     pub fn next(&self) -> Self {
-        Self(self.0.next())
+        Self {
+            id: self.id.next(),
+            names: self.names,
+        }
+    }
+
+    pub fn names(&self) -> StatementNames {
+        self.names
     }
 
     pub fn name_len(&self) -> Saturating<usize> {
-        self.0.name_len(Self::NAME_PREFIX)
+        self.id.name_len(self.names.prefix, self.names.tag)
     }
 
     /// Get a type to format this statement ID with [`Display`].
@@ -45,17 +78,92 @@ impl StatementId {
     /// Returns `None` if this is the unnamed statement.
     #[inline(always)]
     pub fn display(&self) -> Option<DisplayId> {
-        self.0.display(Self::NAME_PREFIX)
+        self.id.display(self.names.prefix, self.names.tag)
     }
 
     pub fn put_name_with_nul(&self, buf: &mut Vec<u8>) {
-        self.0.put_name_with_nul(Self::NAME_PREFIX, buf)
+        self.id
+            .put_name_with_nul(self.names.prefix, self.names.tag, buf)
     }
 }
 
+impl StatementNames {
+    pub const DEFAULT: Self = Self {
+        prefix: "sqlx_s_",
+        tag: None,
+    };
+
+    /// Postgres truncates names to 63 bytes, which would make them collide; this leaves room for
+    /// the tag, an underscore and the largest number.
+    pub const MAX_PREFIX_LEN: usize = 63 - 16 - 1 - 10;
+
+    pub fn new(prefix: &str, unique: bool) -> Result<Self, Error> {
+        if prefix.len() > Self::MAX_PREFIX_LEN {
+            return Err(Error::Configuration(
+                format!(
+                    "statement name prefix {prefix:?} is longer than {} bytes",
+                    Self::MAX_PREFIX_LEN
+                )
+                .into(),
+            ));
+        }
+
+        if prefix.contains('\0') {
+            return Err(Error::Configuration(
+                "statement name prefix contains NUL".into(),
+            ));
+        }
+
+        let names = Self {
+            prefix: intern_prefix(prefix),
+            tag: None,
+        };
+
+        Ok(if unique {
+            names.with_random_tag()
+        } else {
+            names
+        })
+    }
+
+    pub fn prefix(&self) -> &'static str {
+        self.prefix
+    }
+
+    /// The same prefix with a new random tag.
+    pub fn with_random_tag(self) -> Self {
+        Self {
+            prefix: self.prefix,
+            tag: Some(rand::random()),
+        }
+    }
+}
+
+/// Leak each distinct prefix once, so `StatementId` stays `Copy`; applications use one or few.
+fn intern_prefix(prefix: &str) -> &'static str {
+    static PREFIXES: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+    if prefix == StatementNames::DEFAULT.prefix {
+        return StatementNames::DEFAULT.prefix;
+    }
+
+    let mut prefixes = PREFIXES.lock().unwrap_or_else(PoisonError::into_inner);
+
+    if let Some(interned) = prefixes.get(prefix) {
+        return interned;
+    }
+
+    let interned: &'static str = Box::leak(prefix.into());
+    prefixes.insert(interned);
+    interned
+}
+
 impl Display for DisplayId {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{}{}", self.prefix, self.id)
+        match self.tag {
+            Some(tag) => write!(f, "{}{tag:016x}_{}", self.prefix, self.id),
+            None => write!(f, "{}{}", self.prefix, self.id),
+        }
     }
 }
 
@@ -80,11 +188,11 @@ impl PortalId {
 
     /// Calculate the number of bytes that will be written by [`Self::put_name_with_nul()`].
     pub fn name_len(&self) -> Saturating<usize> {
-        self.0.name_len(Self::NAME_PREFIX)
+        self.0.name_len(Self::NAME_PREFIX, None)
     }
 
     pub fn put_name_with_nul(&self, buf: &mut Vec<u8>) {
-        self.0.put_name_with_nul(Self::NAME_PREFIX, buf)
+        self.0.put_name_with_nul(Self::NAME_PREFIX, None, buf)
     }
 }
 
@@ -105,16 +213,20 @@ impl IdInner {
     }
 
     #[inline(always)]
-    fn display(&self, prefix: &'static str) -> Option<DisplayId> {
-        self.0.map(|id| DisplayId { prefix, id })
+    fn display(&self, prefix: &'static str, tag: Option<u64>) -> Option<DisplayId> {
+        self.0.map(|id| DisplayId { prefix, tag, id })
     }
 
     #[inline(always)]
-    fn name_len(&self, name_prefix: &str) -> Saturating<usize> {
+    fn name_len(&self, name_prefix: &str, tag: Option<u64>) -> Saturating<usize> {
         let mut len = Saturating(0);
 
         if let Some(id) = self.0 {
             len += name_prefix.len();
+            // the tag in hexadecimal and an underscore
+            if tag.is_some() {
+                len += 17;
+            }
             // estimate the length of the ID in decimal
             // `.ilog10()` can't panic since the value is never zero
             len += id.get().ilog10() as usize;
@@ -129,9 +241,20 @@ impl IdInner {
     }
 
     #[inline(always)]
-    fn put_name_with_nul(&self, name_prefix: &str, buf: &mut Vec<u8>) {
+    fn put_name_with_nul(&self, name_prefix: &str, tag: Option<u64>, buf: &mut Vec<u8>) {
         if let Some(id) = self.0 {
             buf.extend_from_slice(name_prefix.as_bytes());
+
+            if let Some(tag) = tag {
+                const HEX: &[u8; 16] = b"0123456789abcdef";
+
+                for byte in tag.to_be_bytes() {
+                    buf.push(HEX[usize::from(byte >> 4)]);
+                    buf.push(HEX[usize::from(byte & 0xf)]);
+                }
+                buf.push(b'_');
+            }
+
             buf.extend_from_slice(itoa::Buffer::new().format(id.get()).as_bytes());
         }
 
@@ -154,3 +277,21 @@ fn statement_id_display_matches_encoding() {
 
     assert_eq!(str, EXPECTED_STR);
 }
+
+#[test]
+fn unique_statement_id_display_matches_encoding() {
+    let names = StatementNames::new("app_", false).unwrap();
+    let id = StatementId::named_start(StatementNames {
+        tag: Some(0x0123_4567_89ab_cdef),
+        ..names
+    });
+
+    let mut bytes = Vec::new();
+    id.put_name_with_nul(&mut bytes);
+
+    assert_eq!(bytes, b"app_0123456789abcdef_1\0");
+    assert_eq!(id.display().unwrap().to_string(), "app_0123456789abcdef_1");
+    assert_eq!(id.name_len().0, bytes.len());
+
+    assert!(StatementNames::new(&"x".repeat(StatementNames::MAX_PREFIX_LEN + 1), true).is_err());
+}
@@ -0,0 +1,400 @@
+use std::borrow::Cow;
+
+use sqlx_core::bytes::Buf;
+use sqlx_core::json_writer::{bytes_to_json, f32_to_json, float_to_json, DecodeJson};
+use sqlx_core::types::JsonValue;
+
+use crate::decode::Decode;
+use crate::error::BoxDynError;
+use crate::type_info::{PgType, PgTypeKind};
+use crate::types::numeric::{PgNumeric, PgNumericSign};
+use crate::types::Oid;
+#[cfg(any(feature = "chrono", feature = "time"))]
+use crate::types::PgInfinite;
+use crate::value::ValueRef;
+use crate::{PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+impl DecodeJson for Postgres {
+    /// Values in the text format, and `NUMERIC`, `UUID`, `DATE`, `TIME`, `TIMESTAMP` and
+    /// `TIMESTAMPTZ` in the binary format, are written as strings; the timestamps in ISO 8601,
+    /// with `TIMESTAMPTZ` in UTC, and years before 1 AD with a ` BC` suffix, like Postgres.
+    ///
+    /// `UUID` in the binary format requires the `uuid` feature, and the date and time types
+    /// the `chrono` or `time` feature.
+    fn decode_json(value: PgValueRef<'_>) -> Result<JsonValue, BoxDynError> {
+        let ty = value.type_info.clone();
+
+        Ok(match ty.0 {
+            PgType::Bool => <bool as Decode<Postgres>>::decode(value)?.into(),
+            PgType::Int2 => <i16 as Decode<Postgres>>::decode(value)?.into(),
+            PgType::Int4 => <i32 as Decode<Postgres>>::decode(value)?.into(),
+            PgType::Int8 => <i64 as Decode<Postgres>>::decode(value)?.into(),
+            PgType::Oid => <Oid as Decode<Postgres>>::decode(value)?.0.into(),
+            PgType::Float4 => f32_to_json(<f32 as Decode<Postgres>>::decode(value)?),
+            PgType::Float8 => float_to_json(<f64 as Decode<Postgres>>::decode(value)?),
+            PgType::Json | PgType::Jsonb => <JsonValue as Decode<Postgres>>::decode(value)?,
+
+            // the text format of everything else is as good as it gets
+            _ if value.format() == PgValueFormat::Text => value.as_str()?.into(),
+
+            PgType::Text | PgType::Varchar | PgType::Bpchar | PgType::Name | PgType::Unknown => {
+                value.as_str()?.into()
+            }
+            PgType::Bytea => bytes_to_json(value.as_bytes()?),
+            PgType::Numeric => format_numeric(PgNumeric::decode(value.as_bytes()?)?).into(),
+            #[cfg(feature = "uuid")]
+            PgType::Uuid => <uuid::Uuid as Decode<Postgres>>::decode(value)?
+                .to_string()
+                .into(),
+            #[cfg(any(feature = "chrono", feature = "time"))]
+            PgType::Date => temporal::date(value)?.into(),
+            #[cfg(any(feature = "chrono", feature = "time"))]
+            PgType::Time => temporal::time(value)?.into(),
+            #[cfg(any(feature = "chrono", feature = "time"))]
+            PgType::Timestamp => temporal::timestamp(value)?.into(),
+            #[cfg(any(feature = "chrono", feature = "time"))]
+            PgType::Timestamptz => temporal::timestamptz(value)?.into(),
+
+            _ => match ty.kind() {
+                PgTypeKind::Enum(_) => value.as_str()?.into(),
+                PgTypeKind::Domain(base) => decode_element(PgValueRef {
+                    type_info: base.clone(),
+                    ..value
+                })?,
+                PgTypeKind::Array(element) => decode_array(value.as_bytes()?, element)?,
+                // e.g. `citext`
+                PgTypeKind::Simple if ty.name() == "citext" => value.as_str()?.into(),
+                _ => {
+                    return Err(format!(
+                        "cannot convert a value of type {ty} to JSON; \
+                         cast it to text or json in the query"
+                    )
+                    .into())
+                }
+            },
+        })
+    }
+}
+
+fn decode_element(value: PgValueRef<'_>) -> Result<JsonValue, BoxDynError> {
+    if value.is_null() {
+        Ok(JsonValue::Null)
+    } else {
+        Postgres::decode_json(value)
+    }
+}
+
+/// Decode a one-dimensional array in the binary format, like `Vec<T>`.
+fn decode_array(mut buf: &[u8], element: &PgTypeInfo) -> Result<JsonValue, BoxDynError> {
+    if buf.len() < 12 {
+        return Err("array header is too short".into());
+    }
+
+    let ndim = buf.get_i32();
+
+    if ndim == 0 {
+        return Ok(JsonValue::Array(Vec::new()));
+    }
+
+    if ndim != 1 {
+        return Err(format!(
+            "encountered an array of {ndim} dimensions; only one-dimensional arrays are supported"
+        )
+        .into());
+    }
+
+    let _flags = buf.get_i32();
+
+    let element = PgTypeInfo::try_from_oid(Oid(buf.get_u32()))
+        .map(Cow::Owned)
+        .unwrap_or(Cow::Borrowed(element));
+
+    if buf.len() < 8 {
+        return Err("array header is too short".into());
+    }
+
+    let len = buf.get_i32();
+    let _lower = buf.get_i32();
+
+    let len = usize::try_from(len)
+        .map_err(|_| format!("overflow converting array len ({len}) to usize"))?;
+
+    let mut elements = Vec::with_capacity(len);
+
+    for _ in 0..len {
+        let value = PgValueRef::get(
+            &mut buf,
+            PgValueFormat::Binary,
+            element.clone().into_owned(),
+        )?;
+
+        elements.push(decode_element(value)?);
+    }
+
+    Ok(JsonValue::Array(elements))
+}
+
+/// Format a decoded `NUMERIC`, keeping all of its digits.
+fn format_numeric(numeric: PgNumeric) -> String {
+    let PgNumeric::Number {
+        sign,
+        digits,
+        weight,
+        scale,
+    } = numeric
+    else {
+        return "NaN".into();
+    };
+
+    let weight = i32::from(weight);
+
+    // base-10000 digit `i` is worth 10000 ^ (weight - i)
+    let digit = |i: i32| {
+        usize::try_from(i)
+            .ok()
+            .and_then(|i| digits.get(i))
+            .copied()
+            .unwrap_or(0)
+    };
+
+    let mut formatted = String::new();
+
+    if sign == PgNumericSign::Negative {
+        formatted.push('-');
+    }
+
+    if weight < 0 {
+        formatted.push('0');
+    } else {
+        formatted.push_str(&digit(0).to_string());
+
+        for i in 1..=weight {
+            formatted.push_str(&format!("{:04}", digit(i)));
+        }
+    }
+
+    if let Ok(scale @ 1..) = usize::try_from(scale) {
+        let mut fraction = String::with_capacity(scale + 4);
+        let mut i = weight + 1;
+
+        while fraction.len() < scale {
+            fraction.push_str(&format!("{:04}", digit(i)));
+            i += 1;
+        }
+
+        fraction.truncate(scale);
+
+        formatted.push('.');
+        formatted.push_str(&fraction);
+    }
+
+    formatted
+}
+
+/// Format a date, and optionally a time of day and UTC offset, as ISO 8601, with years before
+/// 1 AD numbered and suffixed with ` BC` as Postgres does.
+#[cfg_attr(not(any(feature = "chrono", feature = "time")), allow(dead_code))]
+fn format_datetime(year: i32, month: u32, day: u32, time: Option<&str>, offset: &str) -> String {
+    let (year, era) = if year > 0 {
+        (year, "")
+    } else {
+        (1 - year, " BC")
+    };
+
+    let mut formatted = format!("{year:04}-{month:02}-{day:02}");
+
+    if let Some(time) = time {
+        formatted.push('T');
+        formatted.push_str(time);
+        formatted.push_str(offset);
+    }
+
+    formatted.push_str(era);
+    formatted
+}
+
+/// Format a time of day as `HH:MM:SS[.ffffff]`.
+#[cfg_attr(not(any(feature = "chrono", feature = "time")), allow(dead_code))]
+fn format_time(hour: u32, minute: u32, second: u32, micros: u32) -> String {
+    let mut formatted = format!("{hour:02}:{minute:02}:{second:02}");
+
+    if micros != 0 {
+        formatted.push_str(&format!(".{micros:06}"));
+    }
+
+    formatted
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn format_infinite<T>(value: PgInfinite<T>, format: impl FnOnce(T) -> String) -> String {
+    match value {
+        PgInfinite::NegativeInfinity => "-infinity".into(),
+        PgInfinite::Finite(value) => format(value),
+        PgInfinite::Infinity => "infinity".into(),
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod temporal {
+    use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+
+    use super::{format_datetime, format_infinite, format_time, PgInfinite};
+    use crate::decode::Decode;
+    use crate::error::BoxDynError;
+    use crate::{PgValueRef, Postgres};
+
+    pub(super) fn date(value: PgValueRef<'_>) -> Result<String, BoxDynError> {
+        let date = <PgInfinite<NaiveDate> as Decode<Postgres>>::decode(value)?;
+
+        Ok(format_infinite(date, |date| {
+            format_datetime(date.year(), date.month(), date.day(), None, "")
+        }))
+    }
+
+    pub(super) fn time(value: PgValueRef<'_>) -> Result<String, BoxDynError> {
+        let time = <NaiveTime as Decode<Postgres>>::decode(value)?;
+        Ok(clock(&time))
+    }
+
+    pub(super) fn timestamp(value: PgValueRef<'_>) -> Result<String, BoxDynError> {
+        let timestamp = <PgInfinite<NaiveDateTime> as Decode<Postgres>>::decode(value)?;
+        Ok(format_infinite(timestamp, |ts| datetime(&ts, "")))
+    }
+
+    pub(super) fn timestamptz(value: PgValueRef<'_>) -> Result<String, BoxDynError> {
+        let timestamp = <PgInfinite<DateTime<Utc>> as Decode<Postgres>>::decode(value)?;
+        Ok(format_infinite(timestamp, |ts| {
+            datetime(&ts.naive_utc(), "+00:00")
+        }))
+    }
+
+    fn datetime(ts: &NaiveDateTime, offset: &str) -> String {
+        format_datetime(
+            ts.year(),
+            ts.month(),
+            ts.day(),
+            Some(&clock(&ts.time())),
+            offset,
+        )
+    }
+
+    fn clock(time: &NaiveTime) -> String {
+        format_time(
+            time.hour(),
+            time.minute(),
+            time.second(),
+            time.nanosecond() / 1000,
+        )
+    }
+}
+
+#[cfg(all(feature = "time", not(feature = "chrono")))]
+mod temporal {
+    use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+    use super::{format_datetime, format_infinite, format_time, PgInfinite};
+    use crate::decode::Decode;
+    use crate::error::BoxDynError;
+    use crate::{PgValueRef, Postgres};
+
+    pub(super) fn date(value: PgValueRef<'_>) -> Result<String, BoxDynError> {
+        let date = <PgInfinite<Date> as Decode<Postgres>>::decode(value)?;
+        Ok(format_infinite(date, |date| {
+            format_datetime(
+                date.year(),
+                u8::from(date.month()).into(),
+                date.day().into(),
+                None,
+                "",
+            )
+        }))
+    }
+
+    pub(super) fn time(value: PgValueRef<'_>) -> Result<String, BoxDynError> {
+        let time = <Time as Decode<Postgres>>::decode(value)?;
+        Ok(clock(time))
+    }
+
+    pub(super) fn timestamp(value: PgValueRef<'_>) -> Result<String, BoxDynError> {
+        let timestamp = <PgInfinite<PrimitiveDateTime> as Decode<Postgres>>::decode(value)?;
+        Ok(format_infinite(timestamp, |ts| datetime(ts, "")))
+    }
+
+    pub(super) fn timestamptz(value: PgValueRef<'_>) -> Result<String, BoxDynError> {
+        let timestamp = <PgInfinite<OffsetDateTime> as Decode<Postgres>>::decode(value)?;
+        Ok(format_infinite(timestamp, |ts| {
+            datetime(PrimitiveDateTime::new(ts.date(), ts.time()), "+00:00")
+        }))
+    }
+
+    fn datetime(ts: PrimitiveDateTime, offset: &str) -> String {
+        format_datetime(
+            ts.year(),
+            u8::from(ts.month()).into(),
+            ts.day().into(),
+            Some(&clock(ts.time())),
+            offset,
+        )
+    }
+
+    fn clock(time: Time) -> String {
+        format_time(
+            time.hour().into(),
+            time.minute().into(),
+            time.second().into(),
+            time.microsecond(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numeric(weight: i16, sign: u16, scale: i16, digits: &[i16]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&u16::try_from(digits.len()).unwrap().to_be_bytes());
+        buf.extend_from_slice(&weight.to_be_bytes());
+        buf.extend_from_slice(&sign.to_be_bytes());
+        buf.extend_from_slice(&scale.to_be_bytes());
+
+        for digit in digits {
+            buf.extend_from_slice(&digit.to_be_bytes());
+        }
+
+        buf
+    }
+
+    #[test]
+    fn test_format_numeric() {
+        let format = |weight, sign, scale, digits: &[i16]| {
+            format_numeric(PgNumeric::decode(&numeric(weight, sign, scale, digits)).unwrap())
+        };
+
+        assert_eq!(format(0, 0, 0, &[]), "0");
+        assert_eq!(format(1, 0, 0, &[12, 3456]), "123456");
+        assert_eq!(format(0, 0x4000, 2, &[1, 2300]), "-1.23");
+        assert_eq!(format(-2, 0, 8, &[12]), "0.00000012");
+        assert_eq!(format(2, 0, 0, &[1]), "100000000");
+        assert_eq!(format(0, 0xC000, 0, &[]), "NaN");
+    }
+
+    #[test]
+    fn test_format_dates_and_times() {
+        assert_eq!(format_datetime(2024, 10, 14, None, ""), "2024-10-14");
+        assert_eq!(format_datetime(0, 3, 15, None, ""), "0001-03-15 BC");
+        assert_eq!(format_datetime(-43, 3, 15, None, ""), "0044-03-15 BC");
+
+        assert_eq!(format_time(0, 0, 0, 0), "00:00:00");
+        assert_eq!(format_time(9, 5, 0, 123_456), "09:05:00.123456");
+
+        assert_eq!(
+            format_datetime(2024, 10, 14, Some("09:05:00"), "+00:00"),
+            "2024-10-14T09:05:00+00:00"
+        );
+        assert_eq!(
+            format_datetime(-43, 3, 15, Some("12:00:00"), "+00:00"),
+            "0044-03-15T12:00:00+00:00 BC"
+        );
+    }
+}
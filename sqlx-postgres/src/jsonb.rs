@@ -0,0 +1,234 @@
+use serde_json::{Map, Value};
+use sqlx_core::query_builder::QueryBuilder;
+
+use crate::types::Json;
+use crate::Postgres;
+
+/// A call to one of the [SQL/JSON path functions] on a `jsonb` value, with the path and its
+/// variables bound as query parameters.
+///
+/// The path is bound as text and cast to `jsonpath`, and the variables are collected into a
+/// single `jsonb` object, so neither has to be escaped or interpolated into the query by hand.
+/// Push the call into a [`QueryBuilder`] with [`push_to()`][Self::push_to].
+///
+/// The `target` is pushed into the query as-is; it is meant to be a column name or other SQL
+/// expression, and must never contain untrusted input.
+///
+/// Rows returned by `jsonb_path_query` and friends can be decoded into any
+/// `serde::Deserialize` type by wrapping it in [`Json`].
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::postgres::PgJsonbPath;
+/// use sqlx::types::Json;
+/// use sqlx::{Postgres, QueryBuilder};
+///
+/// #[derive(serde::Deserialize)]
+/// struct Item {
+///     name: String,
+///     price: i64,
+/// }
+///
+/// let mut query = QueryBuilder::<Postgres>::new("SELECT ");
+///
+/// PgJsonbPath::query("orders.data", "$.items[*] ? (@.price >= $min)")
+///     .var("min", 100)
+///     .push_to(&mut query);
+///
+/// query.push(" FROM orders WHERE id = ").push_bind(42_i64);
+///
+/// let items: Vec<Json<Item>> = query.build_query_scalar().fetch_all(conn).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [SQL/JSON path functions]: https://www.postgresql.org/docs/current/functions-json.html#FUNCTIONS-JSON-PROCESSING-TABLE
+#[derive(Debug, Clone)]
+pub struct PgJsonbPath {
+    function: &'static str,
+    target: String,
+    path: String,
+    vars: Map<String, Value>,
+    silent: bool,
+}
+
+impl PgJsonbPath {
+    fn new(function: &'static str, target: impl Into<String>, path: impl Into<String>) -> Self {
+        Self {
+            function,
+            target: target.into(),
+            path: path.into(),
+            vars: Map::new(),
+            silent: false,
+        }
+    }
+
+    /// `jsonb_path_query(target, path)`: every item matched by the path, one per row.
+    pub fn query(target: impl Into<String>, path: impl Into<String>) -> Self {
+        Self::new("jsonb_path_query", target, path)
+    }
+
+    /// `jsonb_path_query_first(target, path)`: the first item matched by the path, or `NULL`.
+    pub fn query_first(target: impl Into<String>, path: impl Into<String>) -> Self {
+        Self::new("jsonb_path_query_first", target, path)
+    }
+
+    /// `jsonb_path_query_array(target, path)`: every item matched by the path, in one array.
+    pub fn query_array(target: impl Into<String>, path: impl Into<String>) -> Self {
+        Self::new("jsonb_path_query_array", target, path)
+    }
+
+    /// `jsonb_path_exists(target, path)`: whether the path matches any item.
+    pub fn exists(target: impl Into<String>, path: impl Into<String>) -> Self {
+        Self::new("jsonb_path_exists", target, path)
+    }
+
+    /// `jsonb_path_match(target, path)`: the result of a path predicate check.
+    pub fn matches(target: impl Into<String>, path: impl Into<String>) -> Self {
+        Self::new("jsonb_path_match", target, path)
+    }
+
+    /// Set the value of the variable `$name` in the path.
+    ///
+    /// A value of any `serde::Serialize` type can be passed through
+    /// [`serde_json::to_value()`].
+    pub fn var(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.vars.insert(name.into(), value.into());
+        self
+    }
+
+    /// If `true`, suppress the errors for missing object fields, array indices out of bounds and
+    /// type mismatches that the path would raise in strict mode.
+    ///
+    /// Default: `false`.
+    pub fn silent(mut self, silent: bool) -> Self {
+        self.silent = silent;
+        self
+    }
+
+    /// Push this function call into `query`, binding the path and its variables.
+    pub fn push_to(&self, query: &mut QueryBuilder<'_, Postgres>) {
+        query
+            .push(self.function)
+            .push("(")
+            .push(&self.target)
+            .push(", ")
+            .push_bind(self.path.clone())
+            .push("::jsonpath, ")
+            .push_bind(Json(Value::Object(self.vars.clone())))
+            .push("::jsonb, ")
+            .push(self.silent)
+            .push(")");
+    }
+}
+
+/// A `jsonb` expression with one or more values replaced by [`jsonb_set`], with the paths and
+/// new values bound as query parameters.
+///
+/// Each call to [`set()`][Self::set] or [`replace()`][Self::replace] wraps the expression in
+/// another `jsonb_set` call, so the updates are applied in order. Push the expression into a
+/// [`QueryBuilder`] with [`push_to()`][Self::push_to].
+///
+/// The `target` is pushed into the query as-is; it is meant to be a column name or other SQL
+/// expression, and must never contain untrusted input.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use serde_json::json;
+/// use sqlx::postgres::PgJsonbSet;
+/// use sqlx::{Postgres, QueryBuilder};
+///
+/// let mut query = QueryBuilder::<Postgres>::new("UPDATE users SET settings = ");
+///
+/// PgJsonbSet::new("settings")
+///     .set(["theme"], "dark")
+///     .set(["notifications", "email"], json!({ "enabled": false }))
+///     .push_to(&mut query);
+///
+/// query.push(" WHERE id = ").push_bind(42_i64);
+///
+/// query.build().execute(conn).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`jsonb_set`]: https://www.postgresql.org/docs/current/functions-json.html#FUNCTIONS-JSON-PROCESSING-TABLE
+#[derive(Debug, Clone)]
+pub struct PgJsonbSet {
+    target: String,
+    updates: Vec<JsonbSetUpdate>,
+}
+
+#[derive(Debug, Clone)]
+struct JsonbSetUpdate {
+    path: Vec<String>,
+    value: Value,
+    create_missing: bool,
+}
+
+impl PgJsonbSet {
+    /// Start from the `jsonb` value of `target`.
+    pub fn new(target: impl Into<String>) -> Self {
+        Self {
+            target: target.into(),
+            updates: Vec::new(),
+        }
+    }
+
+    /// Set the item at `path` to `value`, adding it if it does not exist yet.
+    ///
+    /// The path is a list of object keys and array indices, as with `#>` in SQL. A value of any
+    /// `serde::Serialize` type can be passed through [`serde_json::to_value()`].
+    pub fn set<I>(self, path: I, value: impl Into<Value>) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.update(path, value.into(), true)
+    }
+
+    /// Replace the item at `path` with `value`, leaving the target unchanged if it does not
+    /// exist.
+    pub fn replace<I>(self, path: I, value: impl Into<Value>) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.update(path, value.into(), false)
+    }
+
+    fn update<I>(mut self, path: I, value: Value, create_missing: bool) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.updates.push(JsonbSetUpdate {
+            path: path.into_iter().map(Into::into).collect(),
+            value,
+            create_missing,
+        });
+        self
+    }
+
+    /// Push this expression into `query`, binding the paths and new values.
+    ///
+    /// With no updates, only the target is pushed.
+    pub fn push_to(&self, query: &mut QueryBuilder<'_, Postgres>) {
+        for _ in &self.updates {
+            query.push("jsonb_set(");
+        }
+
+        query.push(&self.target);
+
+        for update in &self.updates {
+            query
+                .push(", ")
+                .push_bind(update.path.clone())
+                .push("::text[], ")
+                .push_bind(Json(update.value.clone()))
+                .push("::jsonb, ")
+                .push(update.create_missing)
+                .push(")");
+        }
+    }
+}
@@ -10,14 +10,22 @@ mod arguments;
 mod column;
 mod connection;
 mod copy;
+mod copy_binary;
 mod database;
 mod error;
+mod estimate;
 mod io;
+mod json_writer;
+mod jsonb;
 mod listener;
 mod message;
 mod options;
+mod partition;
 mod query_result;
+mod replay;
+mod replication;
 mod row;
+mod settings;
 mod statement;
 mod transaction;
 mod type_checking;
@@ -37,9 +45,19 @@ pub mod any;
 #[doc(hidden)]
 pub use copy::PG_COPY_MAX_DATA_LEN;
 
+#[cfg(any(test, feature = "fuzz"))]
+#[doc(hidden)]
+pub mod fuzz;
+
 #[cfg(feature = "migrate")]
 mod migrate;
 
+#[cfg(feature = "monitoring")]
+pub mod monitoring;
+
+#[cfg(feature = "migrate")]
+mod schema_migrator;
+
 #[cfg(feature = "migrate")]
 mod testing;
 
@@ -50,13 +68,27 @@ pub use arguments::{PgArgumentBuffer, PgArguments};
 pub use column::PgColumn;
 pub use connection::PgConnection;
 pub use copy::{PgCopyIn, PgPoolCopyExt};
+pub use copy_binary::{PgCopyDecode, PgCopyEncode, PgCopyRow};
 pub use database::Postgres;
 pub use error::{PgDatabaseError, PgErrorPosition};
+pub use jsonb::{PgJsonbPath, PgJsonbSet};
 pub use listener::{PgListener, PgNotification};
 pub use message::PgSeverity;
-pub use options::{PgConnectOptions, PgSslMode};
+pub use options::{PgConnectOptions, PgSslMode, PgTimeZonePolicy};
+pub use partition::{PgPartition, PgPartitionInterval, PgPartitionManager, PgPartitionReport};
 pub use query_result::PgQueryResult;
+pub use replay::{
+    PgReplayEntry, PgReplayParam, PgReplayReader, PgReplayValue, PgStatementRecorder,
+};
+pub use replication::{
+    PgChange, PgDecodingPlugin, PgLsn, PgRelation, PgRelationColumn, PgReplicationAcknowledger,
+    PgReplicationConnection, PgReplicationEvent, PgReplicationOptions, PgReplicationSlot,
+    PgReplicationStream, PgReplicationSystem, PgTuple, PgTupleValue,
+};
 pub use row::PgRow;
+#[cfg(feature = "migrate")]
+pub use schema_migrator::PgSchemaMigrator;
+pub use settings::{PgSetting, PgTransactionExt};
 pub use statement::PgStatement;
 pub use transaction::PgTransactionManager;
 pub use type_info::{PgTypeInfo, PgTypeKind};
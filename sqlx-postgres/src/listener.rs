@@ -13,11 +13,11 @@ use tracing::Instrument;
 
 use crate::describe::Describe;
 use crate::error::Error;
-use crate::executor::{Execute, Executor};
+use crate::executor::{Execute, Executor, FetchOptions};
 use crate::message::{BackendMessageFormat, Notification};
 use crate::pool::PoolOptions;
 use crate::pool::{Pool, PoolConnection};
-use crate::{PgConnection, PgQueryResult, PgRow, PgStatement, PgTypeInfo, Postgres};
+use crate::{PgConnection, PgQueryResult, PgRow, PgStatement, PgTypeInfo, PgValue, Postgres};
 
 /// A stream of asynchronous notifications from Postgres.
 ///
@@ -407,6 +407,25 @@ impl<'c> Executor<'c> for &'c mut PgListener {
         .boxed()
     }
 
+    fn fetch_with<'e, 'q, E>(
+        self,
+        query: E,
+        options: FetchOptions,
+    ) -> BoxStream<'e, Result<PgRow, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        futures_util::stream::once(async move {
+            let res: Result<_, Error> = Ok(self.connection().await?.fetch_with(query, options));
+            res
+        })
+        .try_flatten()
+        .boxed()
+    }
+
     fn fetch_optional<'e, 'q, E>(self, query: E) -> BoxFuture<'e, Result<Option<PgRow>, Error>>
     where
         'c: 'e,
@@ -417,6 +436,25 @@ impl<'c> Executor<'c> for &'c mut PgListener {
         async move { self.connection().await?.fetch_optional(query).await }.boxed()
     }
 
+    #[doc(hidden)]
+    fn fetch_first_column<'e, 'q, E>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<PgRow, PgValue>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        futures_util::stream::once(async move {
+            let res: Result<_, Error> = Ok(self.connection().await?.fetch_first_column(query));
+            res
+        })
+        .try_flatten()
+        .boxed()
+    }
+
     fn prepare_with<'e, 'q: 'e>(
         self,
         query: &'q str,
@@ -64,14 +64,22 @@ impl BackendMessage for Authentication {
     const FORMAT: BackendMessageFormat = BackendMessageFormat::Authentication;
 
     fn decode_body(mut buf: Bytes) -> Result<Self, Error> {
+        if buf.len() < 4 {
+            return Err(err_protocol!(
+                "expected at least 4 bytes, got {}",
+                buf.len()
+            ));
+        }
+
         Ok(match buf.get_u32() {
             0 => Authentication::Ok,
 
             3 => Authentication::CleartextPassword,
 
             5 => {
-                let mut salt = [0; 4];
-                buf.copy_to_slice(&mut salt);
+                let salt = buf[..]
+                    .try_into()
+                    .map_err(|_| err_protocol!("expected a salt of 4 bytes, got {}", buf.len()))?;
 
                 Authentication::Md5Password(AuthenticationMd5Password { salt })
             }
@@ -123,6 +131,17 @@ impl<'a> Iterator for SaslMechanisms<'a> {
     }
 }
 
+/// Split an attribute of a SCRAM message, `<key>=<value>`.
+fn sasl_attribute(item: &[u8]) -> Result<(u8, &[u8]), Error> {
+    match item {
+        [key, b'=', value @ ..] => Ok((*key, value)),
+        _ => Err(err_protocol!(
+            "malformed SASL attribute: {:?}",
+            String::from_utf8_lossy(item)
+        )),
+    }
+}
+
 #[derive(Debug)]
 pub struct AuthenticationSaslContinue {
     pub salt: Vec<u8>,
@@ -141,8 +160,7 @@ impl ProtocolDecode<'_> for AuthenticationSaslContinue {
         // r=/z+giZiTxAH7r8sNAeHr7cvpqV3uo7G/bJBIJO3pjVM7t3ng,s=4UV68bIkC8f9/X8xH7aPhg==,i=4096
 
         for item in buf.split(|b| *b == b',') {
-            let key = item[0];
-            let value = &item[2..];
+            let (key, value) = sasl_attribute(item)?;
 
             match key {
                 b'r' => {
@@ -180,8 +198,7 @@ impl ProtocolDecode<'_> for AuthenticationSaslFinal {
         let mut verifier = Vec::new();
 
         for item in buf.split(|b| *b == b',') {
-            let key = item[0];
-            let value = &item[2..];
+            let (key, value) = sasl_attribute(item)?;
 
             if let b'v' = key {
                 verifier = BASE64_STANDARD.decode(value).map_err(Error::protocol)?;
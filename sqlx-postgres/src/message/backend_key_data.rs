@@ -19,6 +19,10 @@ impl BackendMessage for BackendKeyData {
     const FORMAT: BackendMessageFormat = BackendMessageFormat::BackendKeyData;
 
     fn decode_body(buf: Bytes) -> Result<Self, Error> {
+        if buf.len() < 8 {
+            return Err(err_protocol!("expected 8 bytes, got {}", buf.len()));
+        }
+
         let process_id = BigEndian::read_u32(&buf);
         let secret_key = BigEndian::read_u32(&buf[4..]);
 
@@ -30,6 +30,15 @@ impl CommandComplete {
             .and_then(|i| atoi(&self.tag[(i + 1)..]))
             .unwrap_or(0)
     }
+
+    /// Returns the command tag, without the NUL terminator.
+    pub fn into_tag(mut self) -> Bytes {
+        if self.tag.last() == Some(&0) {
+            self.tag.truncate(self.tag.len() - 1);
+        }
+
+        self.tag
+    }
 }
 
 #[test]
@@ -39,6 +48,7 @@ fn test_decode_command_complete_for_insert() {
     let cc = CommandComplete::decode_body(Bytes::from_static(DATA)).unwrap();
 
     assert_eq!(cc.rows_affected(), 1214);
+    assert_eq!(&*cc.into_tag(), b"INSERT 0 1214");
 }
 
 #[test]
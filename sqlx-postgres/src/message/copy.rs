@@ -8,7 +8,7 @@ use sqlx_core::Error;
 use std::num::Saturating;
 use std::ops::Deref;
 
-/// The same structure is sent for both `CopyInResponse` and `CopyOutResponse`
+/// The same structure is sent for `CopyInResponse`, `CopyOutResponse` and `CopyBothResponse`
 pub struct CopyResponseData {
     pub format: i8,
     pub num_columns: i16,
@@ -20,6 +20,10 @@ pub struct CopyInResponse(pub CopyResponseData);
 #[allow(dead_code)]
 pub struct CopyOutResponse(pub CopyResponseData);
 
+/// Sent in response to `START_REPLICATION` on a replication connection.
+#[allow(dead_code)]
+pub struct CopyBothResponse(pub CopyResponseData);
+
 pub struct CopyData<B>(pub B);
 
 pub struct CopyFail {
@@ -31,9 +35,23 @@ pub struct CopyDone;
 impl CopyResponseData {
     #[inline]
     fn decode(mut buf: Bytes) -> Result<Self> {
+        if buf.len() < 3 {
+            return Err(err_protocol!(
+                "expected at least 3 bytes, got {}",
+                buf.len()
+            ));
+        }
+
         let format = buf.get_i8();
         let num_columns = buf.get_i16();
 
+        if buf.len() < usize::try_from(num_columns).unwrap_or(0) * 2 {
+            return Err(err_protocol!(
+                "expected {num_columns} format codes of 2 bytes, got {} bytes",
+                buf.len()
+            ));
+        }
+
         let format_codes = (0..num_columns).map(|_| buf.get_i16()).collect();
 
         Ok(CopyResponseData {
@@ -62,6 +80,15 @@ impl BackendMessage for CopyOutResponse {
     }
 }
 
+impl BackendMessage for CopyBothResponse {
+    const FORMAT: BackendMessageFormat = BackendMessageFormat::CopyBothResponse;
+
+    #[inline(always)]
+    fn decode_body(buf: Bytes) -> std::result::Result<Self, Error> {
+        Ok(Self(CopyResponseData::decode(buf)?))
+    }
+}
+
 impl BackendMessage for CopyData<Bytes> {
     const FORMAT: BackendMessageFormat = BackendMessageFormat::CopyData;
 
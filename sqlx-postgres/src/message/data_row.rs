@@ -23,6 +23,48 @@ impl DataRow {
             .as_ref()
             .map(|col| &self.storage[(col.start as usize)..(col.end as usize)])
     }
+
+    /// Decode only the first value of a `DataRow` message body, without building the list of
+    /// ranges for the whole row.
+    ///
+    /// Returns `None` for a `NULL` value.
+    pub(crate) fn decode_first_value(buf: Bytes) -> Result<Option<Bytes>, Error> {
+        if buf.len() < 2 {
+            return Err(err_protocol!(
+                "expected at least 2 bytes, got {}",
+                buf.len()
+            ));
+        }
+
+        if BigEndian::read_u16(&buf) == 0 {
+            return Err(err_protocol!(
+                "expected at least 1 value in data row, got 0"
+            ));
+        }
+
+        if buf.len() < 6 {
+            return Err(err_protocol!(
+                "expected 4 bytes at offset 2, got {}",
+                buf.len() - 2
+            ));
+        }
+
+        let Ok(length) = usize::try_from(BigEndian::read_i32(&buf[2..])) else {
+            // Negative values signify NULL
+            return Ok(None);
+        };
+
+        let value_end = 6_usize.saturating_add(length);
+
+        if buf.len() < value_end {
+            return Err(err_protocol!(
+                "expected {length} bytes at offset 6, got {}",
+                buf.len() - 6
+            ));
+        }
+
+        Ok(Some(buf.slice(6..value_end)))
+    }
 }
 
 impl BackendMessage for DataRow {
@@ -67,6 +109,13 @@ impl BackendMessage for DataRow {
                     err_protocol!("value_start + length out of range ({offset} + {length})")
                 })?;
 
+                if (buf.len() as u64) < (value_end as u64) {
+                    return Err(err_protocol!(
+                        "expected {length} bytes at offset {value_start}, got {}",
+                        (buf.len() as u64) - (value_start as u64)
+                    ));
+                }
+
                 values.push(Some(value_start..value_end));
                 offset = value_end;
             } else {
@@ -115,6 +164,24 @@ fn test_decode_data_row() {
     assert_eq!(row.get(7).unwrap(), &[0_u8, 0, 0, 80][..]);
 }
 
+#[test]
+fn test_decode_first_value() {
+    const DATA: &[u8] = b"\
+        \x00\x02\
+        \x00\x00\x00\x04\
+        \x00\x00\x00\n\
+        \xff\xff\xff\xff";
+
+    let value = DataRow::decode_first_value(DATA.into()).unwrap();
+    assert_eq!(value.as_deref(), Some(&[0_u8, 0, 0, 10][..]));
+
+    let value = DataRow::decode_first_value((&b"\x00\x01\xff\xff\xff\xff"[..]).into()).unwrap();
+    assert!(value.is_none());
+
+    assert!(DataRow::decode_first_value((&b"\x00\x00"[..]).into()).is_err());
+    assert!(DataRow::decode_first_value((&b"\x00\x01\x00\x00\x00\x04\x00"[..]).into()).is_err());
+}
+
 #[cfg(all(test, not(debug_assertions)))]
 #[bench]
 fn bench_data_row_get(b: &mut test::Bencher) {
@@ -1,5 +1,6 @@
-use sqlx_core::bytes::Bytes;
+use sqlx_core::bytes::{Buf, Bytes, BytesMut};
 use std::num::Saturating;
+use std::ops::ControlFlow;
 
 use crate::error::Error;
 use crate::io::PgBufMutExt;
@@ -35,7 +36,10 @@ pub use backend_key_data::BackendKeyData;
 pub use bind::Bind;
 pub use close::Close;
 pub use command_complete::CommandComplete;
-pub use copy::{CopyData, CopyDone, CopyFail, CopyInResponse, CopyOutResponse, CopyResponseData};
+pub use copy::{
+    CopyBothResponse, CopyData, CopyDone, CopyFail, CopyInResponse, CopyOutResponse,
+    CopyResponseData,
+};
 pub use data_row::DataRow;
 pub use describe::Describe;
 pub use execute::Execute;
@@ -95,6 +99,7 @@ pub enum BackendMessageFormat {
     BindComplete,
     CloseComplete,
     CommandComplete,
+    CopyBothResponse,
     CopyData,
     CopyDone,
     CopyInResponse,
@@ -142,6 +147,51 @@ impl ReceivedMessage {
     }
 }
 
+impl ReceivedMessage {
+    /// Split the next message off the front of `buf`, or return the number of bytes `buf` must
+    /// hold to contain it, in which case `buf` is not modified.
+    pub fn split_from(buf: &mut BytesMut) -> Result<ControlFlow<Self, usize>, Error> {
+        // all packets in postgres start with a 5-byte header
+        // this header contains the message type and the total length of the message
+        let Some(mut header) = buf.get(..5) else {
+            return Ok(ControlFlow::Continue(5));
+        };
+
+        let format = BackendMessageFormat::try_from_u8(header.get_u8())?;
+
+        let message_len = header.get_u32() as usize;
+
+        // the length includes itself
+        if message_len < 4 {
+            return Err(err_protocol!(
+                "message length shorter than its own prefix: {message_len}"
+            ));
+        }
+
+        let expected_len = message_len
+            .checked_add(1)
+            // this shouldn't really happen but is mostly a sanity check
+            .ok_or_else(|| err_protocol!("message_len + 1 overflows usize: {message_len}"))?;
+
+        if buf.len() < expected_len {
+            return Ok(ControlFlow::Continue(expected_len));
+        }
+
+        // `buf` SHOULD NOT be modified ABOVE this line
+
+        // pop off the format code since it's not counted in `message_len`
+        buf.advance(1);
+
+        // consume the message, including the length prefix
+        let mut contents = buf.split_to(message_len).freeze();
+
+        // cut off the length prefix
+        contents.advance(4);
+
+        Ok(ControlFlow::Break(ReceivedMessage { format, contents }))
+    }
+}
+
 impl BackendMessageFormat {
     pub fn try_from_u8(v: u8) -> Result<Self, Error> {
         // https://www.postgresql.org/docs/current/protocol-message-formats.html
@@ -155,6 +205,7 @@ impl BackendMessageFormat {
             b'c' => BackendMessageFormat::CopyDone,
             b'G' => BackendMessageFormat::CopyInResponse,
             b'H' => BackendMessageFormat::CopyOutResponse,
+            b'W' => BackendMessageFormat::CopyBothResponse,
             b'D' => BackendMessageFormat::DataRow,
             b'E' => BackendMessageFormat::ErrorResponse,
             b'I' => BackendMessageFormat::EmptyQueryResponse,
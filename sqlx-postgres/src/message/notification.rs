@@ -15,6 +15,13 @@ impl BackendMessage for Notification {
     const FORMAT: BackendMessageFormat = BackendMessageFormat::NotificationResponse;
 
     fn decode_body(mut buf: Bytes) -> Result<Self, Error> {
+        if buf.len() < 4 {
+            return Err(err_protocol!(
+                "expected at least 4 bytes, got {}",
+                buf.len()
+            ));
+        }
+
         let process_id = buf.get_u32();
         let channel = buf.get_bytes_nul()?;
         let payload = buf.get_bytes_nul()?;
@@ -14,9 +14,23 @@ impl BackendMessage for ParameterDescription {
     const FORMAT: BackendMessageFormat = BackendMessageFormat::ParameterDescription;
 
     fn decode_body(mut buf: Bytes) -> Result<Self, Error> {
+        if buf.len() < 2 {
+            return Err(err_protocol!(
+                "expected at least 2 bytes, got {}",
+                buf.len()
+            ));
+        }
+
         // Note: this is correct, max parameters is 65535, not 32767
         // https://github.com/launchbadge/sqlx/issues/3464
         let cnt = buf.get_u16();
+
+        if buf.len() < usize::from(cnt) * 4 {
+            return Err(err_protocol!(
+                "expected {cnt} parameter types of 4 bytes, got {} bytes",
+                buf.len()
+            ));
+        }
         let mut types = SmallVec::with_capacity(cnt as usize);
 
         for _ in 0..cnt {
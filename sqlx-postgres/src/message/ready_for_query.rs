@@ -25,7 +25,11 @@ impl BackendMessage for ReadyForQuery {
     const FORMAT: BackendMessageFormat = BackendMessageFormat::ReadyForQuery;
 
     fn decode_body(buf: Bytes) -> Result<Self, Error> {
-        let status = match buf[0] {
+        let Some(&status) = buf.first() else {
+            return Err(err_protocol!("expected 1 byte, got 0"));
+        };
+
+        let status = match status {
             b'I' => TransactionStatus::Idle,
             b'T' => TransactionStatus::Transaction,
             b'E' => TransactionStatus::Error,
@@ -1,11 +1,11 @@
 use std::str::FromStr;
-use std::time::Duration;
 use std::time::Instant;
+use std::time::{Duration, UNIX_EPOCH};
 
 use futures_core::future::BoxFuture;
 
 pub(crate) use sqlx_core::migrate::MigrateError;
-pub(crate) use sqlx_core::migrate::{AppliedMigration, Migration};
+pub(crate) use sqlx_core::migrate::{AppliedMigration, Migration, MigrationMetadata};
 pub(crate) use sqlx_core::migrate::{Migrate, MigrateDatabase};
 
 use crate::connection::{ConnectOptions, Connection};
@@ -111,33 +111,93 @@ impl MigrateDatabase for Postgres {
 }
 
 impl Migrate for PgConnection {
-    fn ensure_migrations_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+    fn ensure_migrations_table<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            self.execute(&*create_migrations_table_sql(table_name))
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn migrations_table_version<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<u32>, MigrateError>> {
         Box::pin(async move {
+            // Each version is recognized by a column it added.
             // language=SQL
-            self.execute(
+            let version: Option<i32> = query_scalar(
                 r#"
-CREATE TABLE IF NOT EXISTS _sqlx_migrations (
-    version BIGINT PRIMARY KEY,
-    description TEXT NOT NULL,
-    installed_on TIMESTAMPTZ NOT NULL DEFAULT now(),
-    success BOOLEAN NOT NULL,
-    checksum BYTEA NOT NULL,
-    execution_time BIGINT NOT NULL
-);
+SELECT CASE
+    WHEN to_regclass($1) IS NULL THEN NULL
+    WHEN EXISTS (
+        SELECT 1 FROM pg_attribute
+        WHERE attrelid = to_regclass($1) AND attname = 'skipped' AND NOT attisdropped
+    ) THEN 3
+    WHEN EXISTS (
+        SELECT 1 FROM pg_attribute
+        WHERE attrelid = to_regclass($1) AND attname = 'app_version' AND NOT attisdropped
+    ) THEN 2
+    ELSE 1
+END
                 "#,
             )
+            .bind(table_name)
+            .fetch_one(&mut *self)
             .await?;
 
+            Ok(version.and_then(|version| u32::try_from(version).ok()))
+        })
+    }
+
+    fn upgrade_migrations_table<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        from: u32,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            let mut tx = self.begin().await?;
+
+            if from < 2 {
+                // language=SQL
+                tx.execute(&*format!(
+                    r#"
+ALTER TABLE {table_name}
+    ADD COLUMN applied_by TEXT,
+    ADD COLUMN hostname TEXT,
+    ADD COLUMN app_version TEXT
+                    "#
+                ))
+                .await?;
+            }
+
+            if from < 3 {
+                // language=SQL
+                tx.execute(&*format!(
+                    "ALTER TABLE {table_name} ADD COLUMN skipped BOOLEAN NOT NULL DEFAULT FALSE"
+                ))
+                .await?;
+            }
+
+            tx.commit().await?;
+
             Ok(())
         })
     }
 
-    fn dirty_version(&mut self) -> BoxFuture<'_, Result<Option<i64>, MigrateError>> {
+    fn dirty_version<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<i64>, MigrateError>> {
         Box::pin(async move {
             // language=SQL
-            let row: Option<(i64,)> = query_as(
-                "SELECT version FROM _sqlx_migrations WHERE success = false ORDER BY version LIMIT 1",
-            )
+            let row: Option<(i64,)> = query_as(&format!(
+                "SELECT version FROM {table_name} WHERE success = false ORDER BY version LIMIT 1"
+            ))
             .fetch_optional(self)
             .await?;
 
@@ -145,22 +205,50 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
         })
     }
 
-    fn list_applied_migrations(
-        &mut self,
-    ) -> BoxFuture<'_, Result<Vec<AppliedMigration>, MigrateError>> {
+    fn list_applied_migrations<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>> {
         Box::pin(async move {
             // language=SQL
-            let rows: Vec<(i64, Vec<u8>)> =
-                query_as("SELECT version, checksum FROM _sqlx_migrations ORDER BY version")
-                    .fetch_all(self)
-                    .await?;
+            let rows: Vec<AppliedMigrationRow> = query_as(&format!(
+                r#"
+SELECT version, checksum, execution_time, applied_by, hostname, app_version,
+    CAST(extract(epoch FROM installed_on) AS BIGINT), skipped
+FROM {table_name}
+ORDER BY version
+                "#
+            ))
+            .fetch_all(self)
+            .await?;
 
             let migrations = rows
                 .into_iter()
-                .map(|(version, checksum)| AppliedMigration {
-                    version,
-                    checksum: checksum.into(),
-                })
+                .map(
+                    |(
+                        version,
+                        checksum,
+                        execution_time,
+                        applied_by,
+                        hostname,
+                        app_version,
+                        installed_on,
+                        skipped,
+                    )| {
+                        let mut migration = AppliedMigration::new(version, checksum.into());
+                        // `-1` until the time of a completed migration has been recorded
+                        migration.execution_time =
+                            u64::try_from(execution_time).ok().map(Duration::from_nanos);
+                        migration.applied_by = applied_by;
+                        migration.hostname = hostname;
+                        migration.app_version = app_version;
+                        migration.installed_on = installed_on
+                            .and_then(|secs| u64::try_from(secs).ok())
+                            .map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+                        migration.skipped = skipped;
+                        migration
+                    },
+                )
                 .collect();
 
             Ok(migrations)
@@ -205,50 +293,85 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
 
     fn apply<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
         Box::pin(async move {
-            let start = Instant::now();
+            let metadata = MigrationMetadata::current();
+            apply_migration(self, table_name, migration, &metadata).await
+        })
+    }
 
-            // execute migration queries
-            if migration.no_tx {
-                execute_migration(self, migration).await?;
-            } else {
-                // Use a single transaction for the actual migration script and the essential bookeeping so we never
-                // execute migrations twice. See https://github.com/launchbadge/sqlx/issues/1966.
-                // The `execution_time` however can only be measured for the whole transaction. This value _only_ exists for
-                // data lineage and debugging reasons, so it is not super important if it is lost. So we initialize it to -1
-                // and update it once the actual transaction completed.
-                let mut tx = self.begin().await?;
-                execute_migration(&mut tx, migration).await?;
-                tx.commit().await?;
-            }
+    fn apply_with_metadata<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+        metadata: &'m MigrationMetadata,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        Box::pin(apply_migration(self, table_name, migration, metadata))
+    }
 
-            // Update `elapsed_time`.
-            // NOTE: The process may disconnect/die at this point, so the elapsed time value might be lost. We accept
-            //       this small risk since this value is not super important.
-            let elapsed = start.elapsed();
+    fn ensure_migrations_table_script(&mut self, table_name: &str) -> Result<String, MigrateError> {
+        Ok(create_migrations_table_sql(table_name))
+    }
 
+    fn apply_script(
+        &mut self,
+        table_name: &str,
+        migration: &Migration,
+        metadata: &MigrationMetadata,
+    ) -> Result<String, MigrateError> {
+        let mut script = String::new();
+
+        if !migration.no_tx {
+            script.push_str("BEGIN;\n\n");
+        }
+
+        script.push_str(migration.sql()?.trim());
+        if !script.ends_with(';') {
+            script.push(';');
+        }
+
+        if migration.migration_type.is_repeatable() {
             // language=SQL
-            #[allow(clippy::cast_possible_truncation)]
-            let _ = query(
-                r#"
-    UPDATE _sqlx_migrations
-    SET execution_time = $1
-    WHERE version = $2
-                "#,
-            )
-            .bind(elapsed.as_nanos() as i64)
-            .bind(migration.version)
-            .execute(self)
-            .await?;
-
-            Ok(elapsed)
-        })
+            script.push_str(&format!(
+                "\n\nDELETE FROM {table_name} WHERE version = {};",
+                migration.version
+            ));
+        }
+
+        // language=SQL
+        script.push_str(&format!(
+            r#"
+
+INSERT INTO {table_name} (
+    version, description, success, checksum, execution_time, applied_by, hostname, app_version
+)
+VALUES ( {}, {}, TRUE, '\x{}'::bytea, -1, current_user, {}, {} );
+"#,
+            migration.version,
+            quote_literal(&migration.description),
+            hex::encode(&migration.checksum),
+            metadata
+                .hostname
+                .as_deref()
+                .map_or("NULL".into(), quote_literal),
+            metadata
+                .app_version
+                .as_deref()
+                .map_or("NULL".into(), quote_literal),
+        ));
+
+        if !migration.no_tx {
+            script.push_str("\nCOMMIT;\n");
+        }
+
+        Ok(script)
     }
 
     fn revert<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
         Box::pin(async move {
@@ -256,12 +379,12 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
 
             // execute migration queries
             if migration.no_tx {
-                revert_migration(self, migration).await?;
+                revert_migration(self, table_name, migration).await?;
             } else {
                 // Use a single transaction for the actual migration script and the essential bookeeping so we never
                 // execute migrations twice. See https://github.com/launchbadge/sqlx/issues/1966.
                 let mut tx = self.begin().await?;
-                revert_migration(&mut tx, migration).await?;
+                revert_migration(&mut tx, table_name, migration).await?;
                 tx.commit().await?;
             }
 
@@ -270,27 +393,212 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
             Ok(elapsed)
         })
     }
+
+    fn mark_applied<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+        metadata: &'m MigrationMetadata,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(record_unrun_migration(
+            self, table_name, migration, metadata, false,
+        ))
+    }
+
+    fn mark_skipped<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+        metadata: &'m MigrationMetadata,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(record_unrun_migration(
+            self, table_name, migration, metadata, true,
+        ))
+    }
+
+    fn update_checksum<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        version: i64,
+        checksum: &'e [u8],
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=SQL
+            let _ = query(&format!(
+                r#"UPDATE {table_name} SET checksum = $1 WHERE version = $2"#
+            ))
+            .bind(checksum)
+            .bind(version)
+            .execute(self)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn remove_applied_migration<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        version: i64,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=SQL
+            let _ = query(&format!(r#"DELETE FROM {table_name} WHERE version = $1"#))
+                .bind(version)
+                .execute(self)
+                .await?;
+
+            Ok(())
+        })
+    }
+}
+
+fn create_migrations_table_sql(table_name: &str) -> String {
+    // language=SQL
+    format!(
+        r#"
+CREATE TABLE IF NOT EXISTS {table_name} (
+    version BIGINT PRIMARY KEY,
+    description TEXT NOT NULL,
+    installed_on TIMESTAMPTZ NOT NULL DEFAULT now(),
+    success BOOLEAN NOT NULL,
+    checksum BYTEA NOT NULL,
+    execution_time BIGINT NOT NULL,
+    applied_by TEXT,
+    hostname TEXT,
+    app_version TEXT,
+    skipped BOOLEAN NOT NULL DEFAULT FALSE
+);
+"#
+    )
+}
+
+// assumes `standard_conforming_strings`, which is the default since Postgres 9.1
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+type AppliedMigrationRow = (
+    i64,
+    Vec<u8>,
+    i64,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<i64>,
+    bool,
+);
+
+/// Record `migration` as applied without running it, either because it was applied by other
+/// means or because it is `skipped`.
+async fn record_unrun_migration(
+    conn: &mut PgConnection,
+    table_name: &str,
+    migration: &Migration,
+    metadata: &MigrationMetadata,
+    skipped: bool,
+) -> Result<(), MigrateError> {
+    // the migration was never run here, so its `execution_time` is left unknown
+    // language=SQL
+    let _ = query(&format!(
+        r#"
+    INSERT INTO {table_name} (
+        version, description, success, checksum, execution_time, applied_by, hostname,
+        app_version, skipped
+    )
+    VALUES ( $1, $2, TRUE, $3, -1, current_user, $4, $5, $6 )
+        "#
+    ))
+    .bind(migration.version)
+    .bind(&*migration.description)
+    .bind(&*migration.checksum)
+    .bind(&metadata.hostname)
+    .bind(&metadata.app_version)
+    .bind(skipped)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn apply_migration(
+    conn: &mut PgConnection,
+    table_name: &str,
+    migration: &Migration,
+    metadata: &MigrationMetadata,
+) -> Result<Duration, MigrateError> {
+    let start = Instant::now();
+
+    // execute migration queries
+    if migration.no_tx {
+        execute_migration(conn, table_name, migration, metadata).await?;
+    } else {
+        // Use a single transaction for the actual migration script and the essential bookeeping so we never
+        // execute migrations twice. See https://github.com/launchbadge/sqlx/issues/1966.
+        // The `execution_time` however can only be measured for the whole transaction. This value _only_ exists for
+        // data lineage and debugging reasons, so it is not super important if it is lost. So we initialize it to -1
+        // and update it once the actual transaction completed.
+        let mut tx = conn.begin().await?;
+        execute_migration(&mut tx, table_name, migration, metadata).await?;
+        tx.commit().await?;
+    }
+
+    // Update `elapsed_time`.
+    // NOTE: The process may disconnect/die at this point, so the elapsed time value might be lost. We accept
+    //       this small risk since this value is not super important.
+    let elapsed = start.elapsed();
+
+    // language=SQL
+    #[allow(clippy::cast_possible_truncation)]
+    let _ = query(&format!(
+        r#"
+    UPDATE {table_name}
+    SET execution_time = $1
+    WHERE version = $2
+        "#
+    ))
+    .bind(elapsed.as_nanos() as i64)
+    .bind(migration.version)
+    .execute(conn)
+    .await?;
+
+    Ok(elapsed)
 }
 
 async fn execute_migration(
     conn: &mut PgConnection,
+    table_name: &str,
     migration: &Migration,
+    metadata: &MigrationMetadata,
 ) -> Result<(), MigrateError> {
     let _ = conn
-        .execute(&*migration.sql)
+        .execute(&*migration.sql()?)
         .await
         .map_err(|e| MigrateError::ExecuteMigration(e, migration.version))?;
 
+    // a repeatable migration replaces the record of its previous run
+    if migration.migration_type.is_repeatable() {
+        // language=SQL
+        let _ = query(&format!(r#"DELETE FROM {table_name} WHERE version = $1"#))
+            .bind(migration.version)
+            .execute(&mut *conn)
+            .await?;
+    }
+
     // language=SQL
-    let _ = query(
+    let _ = query(&format!(
         r#"
-    INSERT INTO _sqlx_migrations ( version, description, success, checksum, execution_time )
-    VALUES ( $1, $2, TRUE, $3, -1 )
-                "#,
+    INSERT INTO {table_name} (
+        version, description, success, checksum, execution_time, applied_by, hostname, app_version
     )
+    VALUES ( $1, $2, TRUE, $3, -1, current_user, $4, $5 )
+                "#
+    ))
     .bind(migration.version)
     .bind(&*migration.description)
     .bind(&*migration.checksum)
+    .bind(&metadata.hostname)
+    .bind(&metadata.app_version)
     .execute(conn)
     .await?;
 
@@ -299,15 +607,16 @@ async fn execute_migration(
 
 async fn revert_migration(
     conn: &mut PgConnection,
+    table_name: &str,
     migration: &Migration,
 ) -> Result<(), MigrateError> {
     let _ = conn
-        .execute(&*migration.sql)
+        .execute(&*migration.sql()?)
         .await
         .map_err(|e| MigrateError::ExecuteMigration(e, migration.version))?;
 
     // language=SQL
-    let _ = query(r#"DELETE FROM _sqlx_migrations WHERE version = $1"#)
+    let _ = query(&format!(r#"DELETE FROM {table_name} WHERE version = $1"#))
         .bind(migration.version)
         .execute(conn)
         .await?;
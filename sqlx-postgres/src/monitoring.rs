@@ -0,0 +1,397 @@
+//! Typed access to the monitoring views [`pg_stat_activity`] and [`pg_stat_statements`].
+//!
+//! The columns of these views have been added, renamed and removed across Postgres versions,
+//! such as `total_time` becoming `total_exec_time` in `pg_stat_statements` 1.8 (Postgres 13).
+//! The functions here look up the columns of the view on the server they query, read each
+//! field from whichever name it has there, and leave fields the server doesn't have as `None`.
+//!
+//! ```rust,no_run
+//! # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+//! use std::time::Duration;
+//! use sqlx::postgres::monitoring::{self, PgBackendState};
+//!
+//! for backend in monitoring::activity(&pool).await? {
+//!     if backend.state == Some(PgBackendState::IdleInTransaction)
+//!         && backend.state_duration > Some(Duration::from_secs(60))
+//!     {
+//!         println!("{} has been idle in a transaction for {:?}", backend.pid, backend.state_duration);
+//!     }
+//! }
+//!
+//! let mut statements = monitoring::statements(&pool).await?;
+//! statements.truncate(10);
+//!
+//! for statement in statements {
+//!     println!("{:?} {:?}", statement.total_exec_time, statement.query);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Both views only show the queries of other roles to superusers and members of
+//! `pg_read_all_stats`.
+//!
+//! [`pg_stat_activity`]: https://www.postgresql.org/docs/current/monitoring-stats.html#MONITORING-PG-STAT-ACTIVITY-VIEW
+//! [`pg_stat_statements`]: https://www.postgresql.org/docs/current/pgstatstatements.html
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::acquire::Acquire;
+use crate::error::Error;
+use crate::query::query;
+use crate::query_scalar::query_scalar;
+use crate::row::Row;
+use crate::types::Oid;
+use crate::{PgConnection, PgRow, Postgres};
+
+/// A server process, as a row of `pg_stat_activity`.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct PgStatActivity {
+    /// `pid`: the process ID of the backend.
+    pub pid: i32,
+    /// `leader_pid`: the process ID of the leader of a parallel query this process is a worker
+    /// of (Postgres 13 and later).
+    pub leader_pid: Option<i32>,
+    /// `datname`: the database the backend is connected to.
+    pub datname: Option<String>,
+    /// `usename`: the role the backend is logged in as.
+    pub usename: Option<String>,
+    /// `application_name`: the name given by the client.
+    pub application_name: Option<String>,
+    /// `client_addr`: the IP address of the client, `None` for Unix sockets and processes
+    /// without a client.
+    pub client_addr: Option<String>,
+    /// `client_port`: the TCP port of the client, `-1` for Unix sockets.
+    pub client_port: Option<i32>,
+    /// `backend_type`: such as `client backend` or `autovacuum worker` (Postgres 10 and later).
+    pub backend_type: Option<String>,
+    /// `state`: what the backend is doing, `None` for processes without a client.
+    pub state: Option<PgBackendState>,
+    /// `wait_event_type`: the kind of event the backend is waiting for, if any.
+    pub wait_event_type: Option<String>,
+    /// `wait_event`: the event the backend is waiting for, if any.
+    pub wait_event: Option<String>,
+    /// `query`: the current query, or the last one if the backend is not
+    /// [active][PgBackendState::Active].
+    pub query: Option<String>,
+    /// `query_id`: the identifier of the query, as in [`PgStatStatement::query_id`]
+    /// (Postgres 14 and later, with `compute_query_id`).
+    pub query_id: Option<i64>,
+    /// The time since `backend_start`, when the process was started.
+    pub backend_duration: Option<Duration>,
+    /// The time since `xact_start`, when the current transaction was started.
+    pub transaction_duration: Option<Duration>,
+    /// The time since `query_start`, when the current or last query was started.
+    pub query_duration: Option<Duration>,
+    /// The time since `state_change`, when the [state][Self::state] last changed.
+    pub state_duration: Option<Duration>,
+}
+
+/// The `state` of a backend in `pg_stat_activity`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PgBackendState {
+    /// `active`: running a query.
+    Active,
+    /// `idle`: waiting for a command from the client.
+    Idle,
+    /// `idle in transaction`: in a transaction, but not running a query.
+    IdleInTransaction,
+    /// `idle in transaction (aborted)`: in a transaction in which a statement failed.
+    IdleInTransactionAborted,
+    /// `fastpath function call`: running a fast-path function.
+    FastpathFunctionCall,
+    /// `disabled`: `track_activities` is off for this backend.
+    Disabled,
+    /// A state added after this enum was written.
+    Other(String),
+}
+
+impl PgBackendState {
+    fn parse(state: &str) -> Self {
+        match state {
+            "active" => PgBackendState::Active,
+            "idle" => PgBackendState::Idle,
+            "idle in transaction" => PgBackendState::IdleInTransaction,
+            "idle in transaction (aborted)" => PgBackendState::IdleInTransactionAborted,
+            "fastpath function call" => PgBackendState::FastpathFunctionCall,
+            "disabled" => PgBackendState::Disabled,
+            other => PgBackendState::Other(other.to_owned()),
+        }
+    }
+}
+
+/// The statistics of a statement, as a row of `pg_stat_statements`.
+///
+/// Times which the server doesn't track, such as [`total_plan_time`][Self::total_plan_time]
+/// with `pg_stat_statements.track_planning` off, are zero.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct PgStatStatement {
+    /// `userid`: the OID of the role that ran the statement.
+    pub userid: Oid,
+    /// `dbid`: the OID of the database the statement ran in.
+    pub dbid: Oid,
+    /// `toplevel`: whether the statement was run by a client rather than a function
+    /// (Postgres 14 and later).
+    pub toplevel: Option<bool>,
+    /// `queryid`: the hash identifying the normalized statement.
+    pub query_id: Option<i64>,
+    /// `query`: the text of a representative statement, with constants replaced by
+    /// placeholders.
+    pub query: Option<String>,
+    /// `calls`: the number of times the statement was run.
+    pub calls: i64,
+    /// `rows`: the total number of rows returned or affected.
+    pub rows: i64,
+    /// `total_exec_time` (`total_time` before Postgres 13).
+    pub total_exec_time: Duration,
+    /// `min_exec_time` (`min_time` before Postgres 13).
+    pub min_exec_time: Duration,
+    /// `max_exec_time` (`max_time` before Postgres 13).
+    pub max_exec_time: Duration,
+    /// `mean_exec_time` (`mean_time` before Postgres 13).
+    pub mean_exec_time: Duration,
+    /// `total_plan_time`: the time spent planning the statement (Postgres 13 and later).
+    pub total_plan_time: Option<Duration>,
+    /// `shared_blks_hit`: the number of shared buffer hits.
+    pub shared_blks_hit: i64,
+    /// `shared_blks_read`: the number of shared blocks read from disk.
+    pub shared_blks_read: i64,
+    /// `shared_blks_dirtied`: the number of shared blocks dirtied.
+    pub shared_blks_dirtied: i64,
+    /// `shared_blks_written`: the number of shared blocks written.
+    pub shared_blks_written: i64,
+    /// `temp_blks_read`: the number of temporary blocks read.
+    pub temp_blks_read: i64,
+    /// `temp_blks_written`: the number of temporary blocks written.
+    pub temp_blks_written: i64,
+    /// `shared_blk_read_time` (`blk_read_time` before Postgres 17), with `track_io_timing`.
+    pub blk_read_time: Duration,
+    /// `shared_blk_write_time` (`blk_write_time` before Postgres 17), with `track_io_timing`.
+    pub blk_write_time: Duration,
+}
+
+/// Get every server process from `pg_stat_activity`, including background processes.
+pub async fn activity<'a, A>(conn: A) -> Result<Vec<PgStatActivity>, Error>
+where
+    A: Acquire<'a, Database = Postgres>,
+{
+    let mut conn = conn.acquire().await?;
+    let columns = view_columns(&mut conn, "pg_catalog.pg_stat_activity").await?;
+
+    let select = Select::new(&columns)
+        .column("pid", &["pid"])
+        .column("leader_pid", &["leader_pid"])
+        .expr("datname", &["datname"], |c| format!("{c}::text"))
+        .expr("usename", &["usename"], |c| format!("{c}::text"))
+        .column("application_name", &["application_name"])
+        .expr("client_addr", &["client_addr"], |c| format!("host({c})"))
+        .column("client_port", &["client_port"])
+        .column("backend_type", &["backend_type"])
+        .column("state", &["state"])
+        .column("wait_event_type", &["wait_event_type"])
+        .column("wait_event", &["wait_event"])
+        .column("query", &["query"])
+        .column("query_id", &["query_id"])
+        .expr("backend_duration", &["backend_start"], since)
+        .expr("transaction_duration", &["xact_start"], since)
+        .expr("query_duration", &["query_start"], since)
+        .expr("state_duration", &["state_change"], since)
+        .from("pg_catalog.pg_stat_activity ORDER BY pid");
+
+    let rows = query(&select).fetch_all(&mut *conn).await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(PgStatActivity {
+                pid: row.try_get("pid")?,
+                leader_pid: row.try_get("leader_pid")?,
+                datname: row.try_get("datname")?,
+                usename: row.try_get("usename")?,
+                application_name: row.try_get("application_name")?,
+                client_addr: row.try_get("client_addr")?,
+                client_port: row.try_get("client_port")?,
+                backend_type: row.try_get("backend_type")?,
+                state: row
+                    .try_get::<Option<&str>, _>("state")?
+                    .map(PgBackendState::parse),
+                wait_event_type: row.try_get("wait_event_type")?,
+                wait_event: row.try_get("wait_event")?,
+                query: row.try_get("query")?,
+                query_id: row.try_get("query_id")?,
+                backend_duration: seconds(row, "backend_duration")?,
+                transaction_duration: seconds(row, "transaction_duration")?,
+                query_duration: seconds(row, "query_duration")?,
+                state_duration: seconds(row, "state_duration")?,
+            })
+        })
+        .collect()
+}
+
+/// Get the statistics of every statement from `pg_stat_statements`, the statements with the
+/// highest total execution time first.
+///
+/// This needs the `pg_stat_statements` extension, which must be in
+/// `shared_preload_libraries` and created in the database with
+/// `CREATE EXTENSION pg_stat_statements`. The server returns an error otherwise.
+pub async fn statements<'a, A>(conn: A) -> Result<Vec<PgStatStatement>, Error>
+where
+    A: Acquire<'a, Database = Postgres>,
+{
+    let mut conn = conn.acquire().await?;
+    let columns = view_columns(&mut conn, "pg_stat_statements").await?;
+
+    let select = Select::new(&columns)
+        .column("userid", &["userid"])
+        .column("dbid", &["dbid"])
+        .column("toplevel", &["toplevel"])
+        .column("query_id", &["queryid"])
+        .column("query", &["query"])
+        .column("calls", &["calls"])
+        .column("rows", &["rows"])
+        .column("total_exec_time", &["total_exec_time", "total_time"])
+        .column("min_exec_time", &["min_exec_time", "min_time"])
+        .column("max_exec_time", &["max_exec_time", "max_time"])
+        .column("mean_exec_time", &["mean_exec_time", "mean_time"])
+        .column("total_plan_time", &["total_plan_time"])
+        .column("shared_blks_hit", &["shared_blks_hit"])
+        .column("shared_blks_read", &["shared_blks_read"])
+        .column("shared_blks_dirtied", &["shared_blks_dirtied"])
+        .column("shared_blks_written", &["shared_blks_written"])
+        .column("temp_blks_read", &["temp_blks_read"])
+        .column("temp_blks_written", &["temp_blks_written"])
+        .column("blk_read_time", &["shared_blk_read_time", "blk_read_time"])
+        .column(
+            "blk_write_time",
+            &["shared_blk_write_time", "blk_write_time"],
+        )
+        .from("pg_stat_statements ORDER BY total_exec_time DESC");
+
+    let rows = query(&select).fetch_all(&mut *conn).await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(PgStatStatement {
+                userid: row.try_get("userid")?,
+                dbid: row.try_get("dbid")?,
+                toplevel: row.try_get("toplevel")?,
+                query_id: row.try_get("query_id")?,
+                query: row.try_get("query")?,
+                calls: row.try_get("calls")?,
+                rows: row.try_get("rows")?,
+                total_exec_time: millis(row, "total_exec_time")?.unwrap_or_default(),
+                min_exec_time: millis(row, "min_exec_time")?.unwrap_or_default(),
+                max_exec_time: millis(row, "max_exec_time")?.unwrap_or_default(),
+                mean_exec_time: millis(row, "mean_exec_time")?.unwrap_or_default(),
+                total_plan_time: millis(row, "total_plan_time")?,
+                shared_blks_hit: row.try_get("shared_blks_hit")?,
+                shared_blks_read: row.try_get("shared_blks_read")?,
+                shared_blks_dirtied: row.try_get("shared_blks_dirtied")?,
+                shared_blks_written: row.try_get("shared_blks_written")?,
+                temp_blks_read: row.try_get("temp_blks_read")?,
+                temp_blks_written: row.try_get("temp_blks_written")?,
+                blk_read_time: millis(row, "blk_read_time")?.unwrap_or_default(),
+                blk_write_time: millis(row, "blk_write_time")?.unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+async fn view_columns(conn: &mut PgConnection, view: &str) -> Result<HashSet<String>, Error> {
+    // language=PostgreSQL
+    let columns: Vec<String> = query_scalar(
+        "SELECT attname::text FROM pg_catalog.pg_attribute \
+         WHERE attrelid = $1::regclass AND attnum > 0 AND NOT attisdropped",
+    )
+    .bind(view)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(columns.into_iter().collect())
+}
+
+/// Builds a `SELECT` from a view, reading each field from the first of its column names which
+/// exists in the view on this server, or `NULL`.
+struct Select<'c> {
+    columns: &'c HashSet<String>,
+    sql: String,
+}
+
+impl<'c> Select<'c> {
+    fn new(columns: &'c HashSet<String>) -> Self {
+        Self {
+            columns,
+            sql: String::from("SELECT "),
+        }
+    }
+
+    fn column(self, alias: &str, names: &[&str]) -> Self {
+        self.expr(alias, names, str::to_owned)
+    }
+
+    fn expr(mut self, alias: &str, names: &[&str], expr: impl FnOnce(&str) -> String) -> Self {
+        let expr = names
+            .iter()
+            .find(|name| self.columns.contains(**name))
+            .map_or_else(|| "NULL".to_owned(), |name| expr(name));
+
+        if !self.sql.ends_with(' ') {
+            self.sql.push_str(", ");
+        }
+
+        let _ = write!(self.sql, "{expr} AS {alias}");
+        self
+    }
+
+    fn from(mut self, from: &str) -> String {
+        let _ = write!(self.sql, " FROM {from}");
+        self.sql
+    }
+}
+
+fn since(column: &str) -> String {
+    format!("extract(epoch FROM clock_timestamp() - {column})::float8")
+}
+
+fn seconds(row: &PgRow, column: &str) -> Result<Option<Duration>, Error> {
+    let secs: Option<f64> = row.try_get(column)?;
+
+    // a process may have started after `clock_timestamp()` was taken for an earlier row
+    Ok(secs.map(|secs| Duration::try_from_secs_f64(secs.max(0.0)).unwrap_or_default()))
+}
+
+fn millis(row: &PgRow, column: &str) -> Result<Option<Duration>, Error> {
+    let millis: Option<f64> = row.try_get(column)?;
+
+    Ok(millis
+        .map(|millis| Duration::try_from_secs_f64(millis.max(0.0) / 1000.0).unwrap_or_default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_falls_back_to_null() {
+        let columns: HashSet<String> = ["pid", "total_time", "client_addr"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let sql = Select::new(&columns)
+            .column("pid", &["pid"])
+            .column("total_exec_time", &["total_exec_time", "total_time"])
+            .expr("client_addr", &["client_addr"], |c| format!("host({c})"))
+            .expr("query_duration", &["query_start"], since)
+            .from("v");
+
+        assert_eq!(
+            sql,
+            "SELECT pid AS pid, total_time AS total_exec_time, host(client_addr) AS client_addr, \
+             NULL AS query_duration FROM v"
+        );
+    }
+}
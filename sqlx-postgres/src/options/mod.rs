@@ -2,15 +2,21 @@ use std::borrow::Cow;
 use std::env::var;
 use std::fmt::{Display, Write};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 pub use ssl_mode::PgSslMode;
+pub use time_zone_policy::PgTimeZonePolicy;
 
-use crate::{connection::LogSettings, net::tls::CertificateInput};
+use crate::connection::{LogSettings, StaleStatements};
+use crate::types::Oid;
+use crate::{net::tls::CertificateInput, HashMap, PgStatementRecorder, PgValueFormat};
+use sqlx_core::database_url::Password;
 
 mod connect;
 mod parse;
 mod pgpass;
 mod ssl_mode;
+mod time_zone_policy;
 
 /// Options and flags which can be used to configure a PostgreSQL connection.
 ///
@@ -33,6 +39,8 @@ mod ssl_mode;
 /// | `sslmode` | `prefer` | Determines whether or with what priority a secure SSL TCP/IP connection will be negotiated. See [`PgSslMode`]. |
 /// | `sslrootcert` | `None` | Sets the name of a file containing a list of trusted SSL Certificate Authorities. |
 /// | `statement-cache-capacity` | `100` | The maximum number of prepared statements stored in the cache. Set to `0` to disable. |
+/// | `statement-name-prefix` | `sqlx_s_` | The prefix of the names of prepared statements. See [`PgConnectOptions::statement_name_prefix()`]. |
+/// | `unique-statement-names` | `false` | Whether to add a random tag of the connection to the names of prepared statements. See [`PgConnectOptions::unique_statement_names()`]. |
 /// | `host` | `None` | Path to the directory containing a PostgreSQL unix domain socket, which will be used instead of TCP if set. |
 /// | `hostaddr` | `None` | Same as `host`, but only accepts IP addresses. |
 /// | `application-name` | `None` | The name will be displayed in the pg_stat_activity view and included in CSV log entries. |
@@ -41,6 +49,7 @@ mod ssl_mode;
 /// | `port` | `5432` | Port number to connect to at the server host, or socket file name extension for Unix-domain connections. |
 /// | `dbname` | `None` | The database name. |
 /// | `options` | `None` | The runtime parameters to send to the server at connection start. |
+/// | `time-zone-policy` | `allow` | Whether to check for timestamps converted with the session time zone. See [`PgTimeZonePolicy`]. |
 ///
 /// The URL scheme designator can be either `postgresql://` or `postgres://`.
 /// Each of the URL parts is optional.
@@ -92,17 +101,28 @@ pub struct PgConnectOptions {
     pub(crate) port: u16,
     pub(crate) socket: Option<PathBuf>,
     pub(crate) username: String,
-    pub(crate) password: Option<String>,
+    pub(crate) password: Option<Password>,
     pub(crate) database: Option<String>,
     pub(crate) ssl_mode: PgSslMode,
     pub(crate) ssl_root_cert: Option<CertificateInput>,
     pub(crate) ssl_client_cert: Option<CertificateInput>,
     pub(crate) ssl_client_key: Option<CertificateInput>,
     pub(crate) statement_cache_capacity: usize,
+    pub(crate) statement_name_prefix: Option<String>,
+    pub(crate) unique_statement_names: bool,
     pub(crate) application_name: Option<String>,
     pub(crate) log_settings: LogSettings,
     pub(crate) extra_float_digits: Option<Cow<'static, str>>,
     pub(crate) options: Option<String>,
+    pub(crate) on_error_rollback: bool,
+    pub(crate) statement_timeout: Option<Duration>,
+    pub(crate) role: Option<String>,
+    /// Set by [`PgReplicationConnection`][crate::replication::PgReplicationConnection] only.
+    pub(crate) replication: bool,
+    pub(crate) statement_recorder: Option<PgStatementRecorder>,
+    pub(crate) time_zone_policy: PgTimeZonePolicy,
+    pub(crate) stale_statements: StaleStatements,
+    pub(crate) result_formats: HashMap<Oid, PgValueFormat>,
 }
 
 impl Default for PgConnectOptions {
@@ -155,7 +175,7 @@ impl PgConnectOptions {
             host,
             socket: None,
             username,
-            password: var("PGPASSWORD").ok(),
+            password: var("PGPASSWORD").ok().map(Password::from),
             database,
             ssl_root_cert: var("PGSSLROOTCERT").ok().map(CertificateInput::from),
             ssl_client_cert: var("PGSSLCERT").ok().map(CertificateInput::from),
@@ -165,10 +185,20 @@ impl PgConnectOptions {
                 .and_then(|v| v.parse().ok())
                 .unwrap_or_default(),
             statement_cache_capacity: 100,
+            statement_name_prefix: None,
+            unique_statement_names: false,
             application_name: var("PGAPPNAME").ok(),
             extra_float_digits: Some("2".into()),
             log_settings: Default::default(),
             options: var("PGOPTIONS").ok(),
+            on_error_rollback: false,
+            statement_timeout: None,
+            role: None,
+            replication: false,
+            statement_recorder: None,
+            time_zone_policy: PgTimeZonePolicy::Allow,
+            stale_statements: StaleStatements::default(),
+            result_formats: HashMap::new(),
         }
     }
 
@@ -179,7 +209,8 @@ impl PgConnectOptions {
                 self.port,
                 &self.username,
                 self.database.as_deref(),
-            );
+            )
+            .map(Password::from);
         }
 
         self
@@ -259,7 +290,7 @@ impl PgConnectOptions {
     ///     .password("safe-and-secure");
     /// ```
     pub fn password(mut self, password: &str) -> Self {
-        self.password = Some(password.to_owned());
+        self.password = Some(password.into());
         self
     }
 
@@ -421,11 +452,47 @@ impl PgConnectOptions {
     /// dropped.
     ///
     /// The default cache capacity is 100 statements.
+    ///
+    /// A cached statement which no longer matches the schema, e.g. because a column it returns
+    /// changed type, fails with `cached plan must not change result type`. It is then prepared
+    /// again and, outside of a transaction, executed again, and evicted from the caches of all
+    /// other connections made with these options, such as those of a pool, before they use it.
     pub fn statement_cache_capacity(mut self, capacity: usize) -> Self {
         self.statement_cache_capacity = capacity;
         self
     }
 
+    /// Sets the prefix of the names of the statements a connection prepares, which are
+    /// numbered after it. Defaults to `sqlx_s_`.
+    ///
+    /// Where several clients share a server session, as through some multiplexers, each needs
+    /// names of its own. Connecting fails if the prefix is longer than 36 bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .statement_name_prefix("billing_");
+    /// ```
+    pub fn statement_name_prefix(mut self, prefix: &str) -> Self {
+        self.statement_name_prefix = Some(prefix.to_owned());
+        self
+    }
+
+    /// If `true`, the names of the statements a connection prepares include a random tag of the
+    /// connection after the [prefix][Self::statement_name_prefix], as in
+    /// `sqlx_s_9f86d081884c7d65_1`, so that they don't collide with those of other clients
+    /// sharing its server session. Defaults to `false`.
+    ///
+    /// Regardless, if preparing a statement fails because its name is taken, the connection
+    /// switches to a new random tag and prepares it again, unless a transaction was aborted
+    /// by the failure.
+    pub fn unique_statement_names(mut self, unique: bool) -> Self {
+        self.unique_statement_names = unique;
+        self
+    }
+
     /// Sets the application name. Defaults to None
     ///
     /// # Example
@@ -520,6 +587,125 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets whether a failed statement inside a transaction is rolled back on its own, leaving
+    /// the transaction usable, like psql's `ON_ERROR_ROLLBACK`. Defaults to `false`.
+    ///
+    /// When enabled, each statement executed while a transaction is open is preceded by a
+    /// `SAVEPOINT`, which is rolled back to if the statement fails and released otherwise.
+    /// This costs an extra round-trip per statement. A statement whose stream or future is
+    /// dropped before it finishes is rolled back as well. Transaction control statements, like
+    /// those of nested transactions, are not wrapped.
+    ///
+    /// Without this, any error aborts the whole transaction and every following statement fails
+    /// until it is rolled back.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .on_error_rollback(true);
+    /// ```
+    pub fn on_error_rollback(mut self, on_error_rollback: bool) -> Self {
+        self.on_error_rollback = on_error_rollback;
+        self
+    }
+
+    /// Sets the default `statement_timeout` for the session.
+    ///
+    /// Any statement that takes longer than this is aborted by the server. The timeout is
+    /// sent with the startup message, in milliseconds; `Duration::ZERO` disables it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// # use std::time::Duration;
+    /// let options = PgConnectOptions::new()
+    ///     .statement_timeout(Duration::from_secs(30));
+    /// ```
+    pub fn statement_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.statement_timeout = timeout.into();
+        self
+    }
+
+    /// Sets a role to switch to with `SET ROLE` once connected.
+    ///
+    /// The user given in [`username`][Self::username] must be a member of this role.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .username("app_login")
+    ///     .role("app_readonly");
+    /// ```
+    pub fn role(mut self, role: &str) -> Self {
+        self.role = Some(role.to_owned());
+        self
+    }
+
+    /// Records every statement executed on the connection, and its parameters, with `recorder`.
+    ///
+    /// See [`PgStatementRecorder`] for an example.
+    pub fn record_statements(mut self, recorder: PgStatementRecorder) -> Self {
+        self.statement_recorder = Some(recorder);
+        self
+    }
+
+    /// Sets what to do when a `TIMESTAMP` is bound where the query expects a `TIMESTAMPTZ`, or
+    /// vice versa. Defaults to [`PgTimeZonePolicy::Allow`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::{PgConnectOptions, PgTimeZonePolicy};
+    /// let options = PgConnectOptions::new()
+    ///     .time_zone_policy(PgTimeZonePolicy::Deny);
+    /// ```
+    pub fn time_zone_policy(mut self, policy: PgTimeZonePolicy) -> Self {
+        self.time_zone_policy = policy;
+        self
+    }
+
+    /// Sets the format in which the server sends values of the type with `oid` in the results
+    /// of prepared statements.
+    ///
+    /// SQLx asks for all results in the binary format by default, which is faster to decode
+    /// than text, and which every type supported by SQLx is decoded from. The binary encoding
+    /// of some types defined by extensions is undocumented or has no decoder in Rust, or the
+    /// server can't produce one at all, failing the whole query with `no binary output
+    /// function available`; results of these types can be asked for in text instead, and
+    /// decoded from it with a [`Decode`][sqlx_core::decode::Decode] implementation reading
+    /// [`PgValueRef::as_str()`][crate::PgValueRef::as_str], or as a `String` with
+    /// [`Row::try_get_unchecked()`][sqlx_core::row::Row::try_get_unchecked]. The other columns
+    /// of the results are still binary.
+    ///
+    /// The OIDs of extension types differ between databases; they can be looked up with
+    /// `SELECT 'geometry'::regtype::oid`. Arrays of the type have an OID of their own, which
+    /// also needs to be set if they are selected. SQL executed as a plain `&str`, which isn't
+    /// prepared, always returns text.
+    ///
+    /// [`PgConnection::set_result_format()`][crate::PgConnection::set_result_format] changes
+    /// this for a connection.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::{PgConnectOptions, PgValueFormat};
+    /// # use sqlx_postgres::types::Oid;
+    /// let options = PgConnectOptions::new()
+    ///     .result_format(Oid(16394), PgValueFormat::Text);
+    /// ```
+    pub fn result_format(mut self, oid: Oid, format: PgValueFormat) -> Self {
+        match format {
+            PgValueFormat::Binary => self.result_formats.remove(&oid),
+            PgValueFormat::Text => self.result_formats.insert(oid, format),
+        };
+        self
+    }
+
     /// We try using a socket if hostname starts with `/` or if socket parameter
     /// is specified.
     pub(crate) fn fetch_socket(&self) -> Option<String> {
@@ -648,6 +834,35 @@ impl PgConnectOptions {
     pub fn get_options(&self) -> Option<&str> {
         self.options.as_deref()
     }
+
+    /// Get the default statement timeout.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// # use std::time::Duration;
+    /// let options = PgConnectOptions::new()
+    ///     .statement_timeout(Duration::from_secs(30));
+    /// assert_eq!(options.get_statement_timeout(), Some(Duration::from_secs(30)));
+    /// ```
+    pub fn get_statement_timeout(&self) -> Option<Duration> {
+        self.statement_timeout
+    }
+
+    /// Get the role to switch to once connected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .role("app_readonly");
+    /// assert_eq!(options.get_role(), Some("app_readonly"));
+    /// ```
+    pub fn get_role(&self) -> Option<&str> {
+        self.role.as_deref()
+    }
 }
 
 fn default_host(port: u16) -> String {
@@ -0,0 +1,49 @@
+use crate::error::Error;
+use std::str::FromStr;
+
+/// What to do when a `TIMESTAMP` is bound where the query expects a `TIMESTAMPTZ`, or vice versa.
+///
+/// Postgres converts between the two silently, using the `TimeZone` of the session, so binding
+/// a `NaiveDateTime` to a `TIMESTAMPTZ` column stores a different instant depending on the
+/// server's configuration. This is rarely intended, and easy to miss if the session is in UTC.
+///
+/// With any policy other than `Allow`, each statement binding a timestamp is checked against
+/// the parameter types the server infers, once when it is prepared. This costs an extra
+/// round-trip for those statements (three in a transaction). Bind timestamps as the type the
+/// query expects, e.g. with `PgUtc` for a naive date and time in UTC, or cast them to the type
+/// they are bound as in the query (e.g. `$1::timestamp` for a `NaiveDateTime`) to mark the
+/// conversion as intended.
+///
+/// It is used by the [`time_zone_policy`](super::PgConnectOptions::time_zone_policy) method.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PgTimeZonePolicy {
+    /// Let Postgres convert the timestamps.
+    ///
+    /// This is the default.
+    #[default]
+    Allow,
+
+    /// Log a warning, then let Postgres convert the timestamps.
+    Warn,
+
+    /// Fail to execute the statement with [`Error::Encode`].
+    Deny,
+}
+
+impl FromStr for PgTimeZonePolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match &*s.to_ascii_lowercase() {
+            "allow" => PgTimeZonePolicy::Allow,
+            "warn" => PgTimeZonePolicy::Warn,
+            "deny" => PgTimeZonePolicy::Deny,
+
+            _ => {
+                return Err(Error::Configuration(
+                    format!("unknown value {s:?} for `time_zone_policy`").into(),
+                ));
+            }
+        })
+    }
+}
@@ -0,0 +1,527 @@
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::query_as::query_as;
+use crate::query_scalar::query_scalar;
+use crate::types::Oid;
+use crate::PgConnection;
+
+/// The length of time covered by each partition of a [`PgPartitionManager`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PgPartitionInterval {
+    /// One partition per day, named like `events_p2024_10_14`.
+    Day,
+    /// One partition per ISO week starting on Monday, named like `events_p2024w42`.
+    Week,
+    /// One partition per month, named like `events_p2024_10`.
+    Month,
+    /// One partition per year, named like `events_p2024`.
+    Year,
+}
+
+impl PgPartitionInterval {
+    /// The field name for `date_trunc()` and `interval`.
+    fn unit(self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::Year => "year",
+        }
+    }
+
+    /// The `to_char()` pattern for the `{suffix}` of partition names.
+    fn suffix_format(self) -> &'static str {
+        match self {
+            Self::Day => "YYYY_MM_DD",
+            Self::Week => r#"IYYY"w"IW"#,
+            Self::Month => "YYYY_MM",
+            Self::Year => "YYYY",
+        }
+    }
+}
+
+/// A partition of the table managed by a [`PgPartitionManager`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PgPartition {
+    /// The name of the partition, without its schema.
+    pub name: String,
+    /// The partition bound, as in `FOR VALUES FROM ('2024-10-01') TO ('2024-11-01')` or `DEFAULT`.
+    pub bound: String,
+}
+
+/// What [`PgPartitionManager::maintain()`] and its parts did, by partition name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PgPartitionReport {
+    /// Partitions which were created.
+    pub created: Vec<String>,
+    /// Partitions which were detached by the retention sweep.
+    pub detached: Vec<String>,
+    /// Partitions which were dropped by the retention sweep, after being detached.
+    pub dropped: Vec<String>,
+}
+
+/// Creates, attaches and detaches time-range partitions of a table partitioned with
+/// `PARTITION BY RANGE` on a `date`, `timestamp` or `timestamptz` column.
+///
+/// [`maintain()`][Self::maintain] is meant to be run periodically, e.g. daily from a background
+/// task. It creates the partition of the current period and [`premake()`][Self::premake] future
+/// ones if they don't exist yet, then detaches the partitions which are entirely older than
+/// [`retain()`][Self::retain] periods before the current one, and drops them if
+/// [`drop_detached()`][Self::drop_detached] is set.
+///
+/// Periods are computed by Postgres from `localtimestamp`, so they follow the `TimeZone` of the
+/// session, as do the bounds of partitions keyed on `timestamptz`.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::postgres::{PgPartitionInterval, PgPartitionManager};
+///
+/// // CREATE TABLE events (id BIGINT, created_at TIMESTAMPTZ NOT NULL)
+/// //     PARTITION BY RANGE (created_at);
+/// let report = PgPartitionManager::new("events")
+///     .interval(PgPartitionInterval::Month)
+///     .premake(3)
+///     .ddl("CREATE INDEX ON {partition} (id)")
+///     .retain(12)
+///     .drop_detached(true)
+///     .maintain(conn)
+///     .await?;
+///
+/// println!("created {:?}, dropped {:?}", report.created, report.dropped);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Running it from several processes at once is safe, but wasteful; use a
+/// [`PgAdvisoryLock`][crate::PgAdvisoryLock] to run it from one at a time.
+#[derive(Debug, Clone)]
+pub struct PgPartitionManager {
+    parent: String,
+    interval: PgPartitionInterval,
+    premake: u32,
+    name_template: String,
+    ddl: Vec<String>,
+    retain: Option<u32>,
+    drop_detached: bool,
+    detach_concurrently: bool,
+}
+
+impl PgPartitionManager {
+    /// Manage the partitions of `parent`, a table name which may be schema-qualified and is
+    /// resolved like a `regclass`, so it follows `search_path` and quoting rules.
+    ///
+    /// Partitions are created in the schema of `parent`.
+    pub fn new(parent: impl Into<String>) -> Self {
+        Self {
+            parent: parent.into(),
+            interval: PgPartitionInterval::Month,
+            premake: 1,
+            name_template: "{table}_p{suffix}".into(),
+            ddl: Vec::new(),
+            retain: None,
+            drop_detached: false,
+            detach_concurrently: false,
+        }
+    }
+
+    /// Set the length of time covered by each partition. Defaults to a month.
+    pub fn interval(mut self, interval: PgPartitionInterval) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set the number of partitions to create ahead of the current period. Defaults to 1.
+    pub fn premake(mut self, premake: u32) -> Self {
+        self.premake = premake;
+        self
+    }
+
+    /// Set the template for the names of new partitions. Defaults to `{table}_p{suffix}`.
+    ///
+    /// `{table}` is replaced with the name of the parent table without its schema, and
+    /// `{suffix}` with the start of the period, formatted as shown on [`PgPartitionInterval`].
+    pub fn name_template(mut self, template: impl Into<String>) -> Self {
+        self.name_template = template.into();
+        self
+    }
+
+    /// Add a statement to execute after each partition is created, e.g. to create indexes
+    /// or grant privileges which aren't inherited from the parent.
+    ///
+    /// `{partition}` and `{parent}` are replaced with the quoted, schema-qualified names of the
+    /// new partition and of the parent table, and `{from}` and `{to}` with the quoted literals
+    /// of the partition bounds.
+    pub fn ddl(mut self, template: impl Into<String>) -> Self {
+        self.ddl.push(template.into());
+        self
+    }
+
+    /// Detach partitions which end more than `periods` whole periods before the start of the
+    /// current one. Defaults to keeping all partitions.
+    ///
+    /// `retain(0)` detaches every partition that ends before the current period.
+    pub fn retain(mut self, periods: u32) -> Self {
+        self.retain = Some(periods);
+        self
+    }
+
+    /// Drop partitions after the retention sweep detaches them. Defaults to `false`, which
+    /// keeps them as standalone tables, e.g. to be archived.
+    pub fn drop_detached(mut self, drop_detached: bool) -> Self {
+        self.drop_detached = drop_detached;
+        self
+    }
+
+    /// Detach partitions with `DETACH PARTITION ... CONCURRENTLY`, which doesn't block queries
+    /// on the parent but requires Postgres 14 and can't run inside a transaction.
+    /// Defaults to `false`.
+    pub fn detach_concurrently(mut self, concurrently: bool) -> Self {
+        self.detach_concurrently = concurrently;
+        self
+    }
+
+    /// Create the partitions which are missing, then run the retention sweep if enabled.
+    pub async fn maintain(&self, conn: &mut PgConnection) -> Result<PgPartitionReport, Error> {
+        let created = self.create_partitions(conn).await?;
+        let mut report = self.sweep(conn).await?;
+
+        report.created = created;
+
+        Ok(report)
+    }
+
+    /// Create the partition of the current period and the [`premake()`][Self::premake] ones
+    /// after it, if they don't exist yet. Returns the names of the partitions created.
+    ///
+    /// A period is skipped if a partition with its name already exists. Creating a partition
+    /// which overlaps an existing one with another name fails.
+    pub async fn create_partitions(&self, conn: &mut PgConnection) -> Result<Vec<String>, Error> {
+        let parent = Parent::resolve(conn, &self.parent).await?;
+
+        // language=PostgreSQL
+        let periods: Vec<(String, String, String)> = query_as(
+            r#"
+SELECT to_char(start, 'YYYY-MM-DD HH24:MI:SS'), to_char(start + step, 'YYYY-MM-DD HH24:MI:SS'),
+       to_char(start, $2)
+FROM (
+    SELECT date_trunc($1, localtimestamp) + n * ('1 ' || $1)::interval AS start,
+           ('1 ' || $1)::interval AS step
+    FROM generate_series(0, $3) n
+) periods
+ORDER BY start
+            "#,
+        )
+        .bind(self.interval.unit())
+        .bind(self.interval.suffix_format())
+        .bind(i64::from(self.premake))
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let existing: Vec<String> = parent
+            .partitions(conn)
+            .await?
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+
+        let mut created = Vec::new();
+
+        for (from, to, suffix) in periods {
+            let name = render(
+                &self.name_template,
+                &[("table", &parent.name), ("suffix", &suffix)],
+            );
+
+            if existing.contains(&name) {
+                continue;
+            }
+
+            let partition = format!("{}.{}", parent.quoted_schema, quote_ident(&name));
+            let (from, to) = (quote_literal(&from), quote_literal(&to));
+
+            let mut sql = format!(
+                "CREATE TABLE {partition} PARTITION OF {} FOR VALUES FROM ({from}) TO ({to});",
+                parent.quoted
+            );
+
+            for ddl in &self.ddl {
+                sql.push('\n');
+                sql.push_str(&render(
+                    ddl,
+                    &[
+                        ("partition", &partition),
+                        ("parent", &parent.quoted),
+                        ("from", &from),
+                        ("to", &to),
+                    ],
+                ));
+                sql.push(';');
+            }
+
+            // sent as one simple query, so the partition and its DDL are created together
+            // or not at all
+            conn.execute(&*sql).await?;
+
+            created.push(name);
+        }
+
+        Ok(created)
+    }
+
+    /// Detach the partitions which are past [`retain()`][Self::retain], and drop them if
+    /// [`drop_detached()`][Self::drop_detached] is set.
+    ///
+    /// Partitions whose upper bound is `MAXVALUE`, and the default partition, are never detached.
+    /// Does nothing if no retention is set.
+    pub async fn sweep(&self, conn: &mut PgConnection) -> Result<PgPartitionReport, Error> {
+        let mut report = PgPartitionReport::default();
+
+        let Some(retain) = self.retain else {
+            return Ok(report);
+        };
+
+        let parent = Parent::resolve(conn, &self.parent).await?;
+
+        // the upper bound of a range partition is rendered as a quoted literal, e.g.
+        // `FOR VALUES FROM ('2024-10-01 00:00:00+00') TO ('2024-11-01 00:00:00+00')`
+        // language=PostgreSQL
+        let expired: Vec<String> = query_scalar(
+            r#"
+SELECT c.relname::text
+FROM pg_inherits i
+JOIN pg_class c ON c.oid = i.inhrelid
+WHERE i.inhparent = $1
+  AND (regexp_match(pg_get_expr(c.relpartbound, c.oid), 'TO \(''([^'']*)''\)'))[1]::timestamp
+      <= date_trunc($2, localtimestamp) - $3 * ('1 ' || $2)::interval
+ORDER BY c.relname
+            "#,
+        )
+        .bind(parent.oid)
+        .bind(self.interval.unit())
+        .bind(i64::from(retain))
+        .fetch_all(&mut *conn)
+        .await?;
+
+        for name in expired {
+            self.detach(conn, &name).await?;
+
+            if self.drop_detached {
+                conn.execute(&*format!(
+                    "DROP TABLE {}.{}",
+                    parent.quoted_schema,
+                    quote_ident(&name)
+                ))
+                .await?;
+
+                report.dropped.push(name.clone());
+            }
+
+            report.detached.push(name);
+        }
+
+        Ok(report)
+    }
+
+    /// Attach an existing table as the partition for the range `[from, to)`.
+    ///
+    /// `table` is resolved like the parent, and `from` and `to` are literals of the type of the
+    /// partition key, such as `2024-10-01`.
+    pub async fn attach(
+        &self,
+        conn: &mut PgConnection,
+        table: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<(), Error> {
+        let parent = Parent::resolve(conn, &self.parent).await?;
+        let table = Parent::quote(conn, table).await?;
+
+        conn.execute(&*format!(
+            "ALTER TABLE {} ATTACH PARTITION {table} FOR VALUES FROM ({}) TO ({})",
+            parent.quoted,
+            quote_literal(from),
+            quote_literal(to)
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// Detach the partition named `partition`, in the schema of the parent table.
+    ///
+    /// Uses `CONCURRENTLY` if [`detach_concurrently()`][Self::detach_concurrently] is set.
+    pub async fn detach(&self, conn: &mut PgConnection, partition: &str) -> Result<(), Error> {
+        let parent = Parent::resolve(conn, &self.parent).await?;
+
+        conn.execute(&*format!(
+            "ALTER TABLE {} DETACH PARTITION {}.{}{}",
+            parent.quoted,
+            parent.quoted_schema,
+            quote_ident(partition),
+            if self.detach_concurrently {
+                " CONCURRENTLY"
+            } else {
+                ""
+            }
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    /// List the partitions of the parent table, ordered by name.
+    pub async fn partitions(&self, conn: &mut PgConnection) -> Result<Vec<PgPartition>, Error> {
+        Parent::resolve(conn, &self.parent)
+            .await?
+            .partitions(conn)
+            .await
+    }
+}
+
+/// The partitioned table, as found in the catalog.
+struct Parent {
+    oid: Oid,
+    name: String,
+    quoted: String,
+    quoted_schema: String,
+}
+
+impl Parent {
+    async fn resolve(conn: &mut PgConnection, parent: &str) -> Result<Self, Error> {
+        // language=PostgreSQL
+        let (oid, name, quoted, quoted_schema, partitioned): (Oid, String, String, String, bool) =
+            query_as(
+                r#"
+SELECT c.oid, c.relname::text, quote_ident(n.nspname) || '.' || quote_ident(c.relname),
+       quote_ident(n.nspname), c.relkind = 'p'
+FROM pg_class c
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE c.oid = $1::regclass
+                "#,
+            )
+            .bind(parent)
+            .fetch_one(&mut *conn)
+            .await?;
+
+        if !partitioned {
+            return Err(Error::InvalidArgument(format!(
+                "{quoted} is not a partitioned table"
+            )));
+        }
+
+        Ok(Self {
+            oid,
+            name,
+            quoted,
+            quoted_schema,
+        })
+    }
+
+    /// The quoted, schema-qualified name of `table`.
+    async fn quote(conn: &mut PgConnection, table: &str) -> Result<String, Error> {
+        // language=PostgreSQL
+        query_scalar(
+            r#"
+SELECT quote_ident(n.nspname) || '.' || quote_ident(c.relname)
+FROM pg_class c
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE c.oid = $1::regclass
+            "#,
+        )
+        .bind(table)
+        .fetch_one(&mut *conn)
+        .await
+    }
+
+    async fn partitions(&self, conn: &mut PgConnection) -> Result<Vec<PgPartition>, Error> {
+        // language=PostgreSQL
+        let partitions: Vec<(String, String)> = query_as(
+            r#"
+SELECT c.relname::text, pg_get_expr(c.relpartbound, c.oid)
+FROM pg_inherits i
+JOIN pg_class c ON c.oid = i.inhrelid
+WHERE i.inhparent = $1
+ORDER BY c.relname
+            "#,
+        )
+        .bind(self.oid)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(partitions
+            .into_iter()
+            .map(|(name, bound)| PgPartition { name, bound })
+            .collect())
+    }
+}
+
+/// Replace each `{key}` in `template` with its value, leaving unknown keys as they are.
+fn render(template: &str, values: &[(&str, &str)]) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let value = rest.find('}').and_then(|end| {
+            let (_, value) = values.iter().find(|(key, _)| *key == &rest[1..end])?;
+            Some((end, value))
+        });
+
+        match value {
+            Some((end, value)) => {
+                rendered.push_str(value);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                rendered.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    rendered.push_str(rest);
+    rendered
+}
+
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        assert_eq!(
+            render(
+                "{table}_p{suffix}",
+                &[("table", "events"), ("suffix", "2024_10")]
+            ),
+            "events_p2024_10"
+        );
+        // values are not rendered again
+        assert_eq!(render("{a}{b}", &[("a", "{b}"), ("b", "x")]), "{b}x");
+        assert_eq!(
+            render(
+                "CREATE INDEX ON {partition} (id) -- {unknown} {",
+                &[("partition", r#""public"."events_p2024""#)]
+            ),
+            r#"CREATE INDEX ON "public"."events_p2024" (id) -- {unknown} {"#
+        );
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote_ident(r#"a"b"#), r#""a""b""#);
+        assert_eq!(quote_literal("it's"), "'it''s'");
+    }
+}
@@ -1,20 +1,65 @@
 use std::iter::{Extend, IntoIterator};
 
+use sqlx_core::bytes::Bytes;
+
+use crate::types::Oid;
+
 #[derive(Debug, Default)]
 pub struct PgQueryResult {
     pub(super) rows_affected: u64,
+    pub(super) command_tag: Option<Bytes>,
 }
 
 impl PgQueryResult {
     pub fn rows_affected(&self) -> u64 {
         self.rows_affected
     }
+
+    /// The command tag sent by the server when the statement completed, e.g. `INSERT 0 5`,
+    /// `UPDATE 3` or `CREATE TABLE`.
+    ///
+    /// If this result combines several statements, this is the tag of the last one.
+    pub fn command_tag(&self) -> Option<&str> {
+        self.command_tag
+            .as_deref()
+            .and_then(|tag| std::str::from_utf8(tag).ok())
+    }
+
+    /// The command of [`command_tag()`][Self::command_tag] without its row count and OID,
+    /// e.g. `INSERT`, `MERGE` or `CREATE TABLE`.
+    ///
+    /// Note that an `INSERT ... ON CONFLICT DO UPDATE` is reported as `INSERT` whether rows
+    /// were inserted or updated.
+    pub fn command(&self) -> Option<&str> {
+        let tag = self.command_tag()?;
+
+        let end = tag
+            .match_indices(' ')
+            .find(|(i, _)| tag[i + 1..].starts_with(|c: char| c.is_ascii_digit()))
+            .map_or(tag.len(), |(i, _)| i);
+
+        Some(&tag[..end])
+    }
+
+    /// The OID of the inserted row, for an `INSERT` of a single row into a table with OIDs.
+    ///
+    /// Tables cannot have OIDs since Postgres 12, so this is always `None` for newer servers.
+    pub fn inserted_oid(&self) -> Option<Oid> {
+        let rest = self.command_tag()?.strip_prefix("INSERT ")?;
+        let (oid, _) = rest.split_once(' ')?;
+
+        oid.parse::<u32>().ok().filter(|&oid| oid != 0).map(Oid)
+    }
 }
 
 impl Extend<PgQueryResult> for PgQueryResult {
     fn extend<T: IntoIterator<Item = PgQueryResult>>(&mut self, iter: T) {
         for elem in iter {
             self.rows_affected += elem.rows_affected;
+
+            if elem.command_tag.is_some() {
+                self.command_tag = elem.command_tag;
+            }
         }
     }
 }
@@ -28,3 +73,24 @@ impl From<PgQueryResult> for sqlx_core::any::AnyQueryResult {
         }
     }
 }
+
+#[test]
+fn test_command_tag() {
+    fn result(tag: &'static str) -> PgQueryResult {
+        PgQueryResult {
+            rows_affected: 0,
+            command_tag: Some(Bytes::from_static(tag.as_bytes())),
+        }
+    }
+
+    let insert = result("INSERT 0 5");
+    assert_eq!(insert.command_tag(), Some("INSERT 0 5"));
+    assert_eq!(insert.command(), Some("INSERT"));
+    assert_eq!(insert.inserted_oid(), None);
+
+    assert_eq!(result("INSERT 16384 1").inserted_oid(), Some(Oid(16384)));
+    assert_eq!(result("MERGE 2").command(), Some("MERGE"));
+    assert_eq!(result("CREATE TABLE").command(), Some("CREATE TABLE"));
+    assert_eq!(result("UPDATE 3").inserted_oid(), None);
+    assert_eq!(PgQueryResult::default().command(), None);
+}
@@ -0,0 +1,514 @@
+//! Recording executed statements to a file, and replaying them against another database.
+//!
+//! # File Format
+//!
+//! All integers are big-endian. A file starts with the magic bytes `SQLXRPL` followed by a
+//! version byte, currently `1`. Each statement is then written as:
+//!
+//! * a kind byte: `Q` for a simple query, which has no parameters, or `P` for a prepared
+//!   statement;
+//! * the SQL, as a `u32` length followed by that many bytes of UTF-8;
+//! * the number of parameters as a `u16`, followed by each parameter:
+//!   * the OID of its type as a `u32`;
+//!   * the name of its type, as a `u16` length followed by that many bytes of UTF-8;
+//!   * its value as an `i32` length followed by that many bytes, in the binary format of its
+//!     type. A length of `-1` is `NULL` and `-2` is a value that was redacted.
+
+use std::fmt::{self, Debug, Formatter};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use futures_channel::oneshot;
+
+use crate::encode::{Encode, IsNull};
+use crate::error::{BoxDynError, Error};
+use crate::executor::Executor;
+use crate::ext::ustr::UStr;
+use crate::type_info::PgType;
+use crate::types::{Oid, Type};
+use crate::{PgArgumentBuffer, PgArguments, PgConnection, PgQueryResult, PgTypeInfo, Postgres};
+
+const MAGIC: &[u8; 7] = b"SQLXRPL";
+const VERSION: u8 = 1;
+
+const KIND_SIMPLE: u8 = b'Q';
+const KIND_PREPARED: u8 = b'P';
+
+const LEN_NULL: i32 = -1;
+const LEN_REDACTED: i32 = -2;
+
+// OIDs below this are assigned to built-in types, and are the same on every server.
+const FIRST_NORMAL_OID: u32 = 16384;
+
+type RedactRule = dyn Fn(&str, usize) -> bool + Send + Sync;
+
+/// Records every statement executed on a connection, with its parameters, to a file which can
+/// be replayed with [`PgReplayReader`] or `sqlx replay`.
+///
+/// Set on a connection with [`PgConnectOptions::record_statements`]. The recorder may be shared by
+/// any number of connections, such as all the connections of a pool; statements are written in the
+/// order they are sent to the server. This includes the queries the driver makes itself, such as
+/// looking up the OIDs of custom types.
+///
+/// Parameters are recorded as encoded, so anything sensitive should be excluded with
+/// [`redact`][Self::redact].
+///
+/// Statements are written to the file by a background thread, so recording never blocks the
+/// connection; use [`flush`][Self::flush] to wait for them to be written. A statement which can't
+/// be recorded is logged and skipped, and does not fail the query.
+///
+/// ```rust,no_run
+/// # async fn example() -> sqlx::Result<()> {
+/// use sqlx::postgres::{PgConnectOptions, PgStatementRecorder};
+/// use sqlx::ConnectOptions;
+///
+/// // Redact the first parameter of every statement touching the `users` table.
+/// let recorder = PgStatementRecorder::create("statements.sqlxrpl")?
+///     .redact(|sql, index| sql.contains("users") && index == 0);
+///
+/// let conn = PgConnectOptions::new()
+///     .record_statements(recorder)
+///     .connect()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`PgConnectOptions::record_statements`]: crate::PgConnectOptions::record_statements
+#[derive(Clone)]
+pub struct PgStatementRecorder {
+    writer: mpsc::Sender<WriterCommand>,
+    rules: Vec<Arc<RedactRule>>,
+}
+
+enum WriterCommand {
+    Write(Vec<u8>),
+    Flush(oneshot::Sender<io::Result<()>>),
+}
+
+impl PgStatementRecorder {
+    /// Create the file at `path`, truncating it if it exists, and write the header.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut file = BufWriter::new(File::create(path)?);
+
+        file.write_all(MAGIC)?;
+        file.write_all(&[VERSION])?;
+
+        let (writer, commands) = mpsc::channel();
+
+        thread::Builder::new()
+            .name("sqlx-postgres-recorder".into())
+            .spawn(move || write_entries(file, commands))?;
+
+        Ok(Self {
+            writer,
+            rules: Vec::new(),
+        })
+    }
+
+    /// Add a redaction rule, which is passed the SQL of a statement and the zero-based index of
+    /// a parameter, and returns `true` if the value of that parameter must not be recorded.
+    ///
+    /// A parameter is redacted if any rule matches it. Redacted values are bound as `NULL` when
+    /// replayed.
+    pub fn redact(mut self, rule: impl Fn(&str, usize) -> bool + Send + Sync + 'static) -> Self {
+        self.rules.push(Arc::new(rule));
+        self
+    }
+
+    /// Wait until every statement recorded so far has been written to the file.
+    ///
+    /// Returns the first error encountered writing the file since the last flush.
+    pub async fn flush(&self) -> io::Result<()> {
+        let (tx, rx) = oneshot::channel();
+
+        self.writer
+            .send(WriterCommand::Flush(tx))
+            .map_err(|_| recorder_stopped())?;
+
+        rx.await.map_err(|_| recorder_stopped())?
+    }
+
+    pub(crate) fn record_simple(&self, sql: &str) {
+        let entry = encode_simple(sql);
+        self.write(entry);
+    }
+
+    /// `arguments` must have had its patches applied, so the OIDs embedded in it are final.
+    pub(crate) fn record_prepared(
+        &self,
+        sql: &str,
+        parameters: &[PgTypeInfo],
+        arguments: &PgArgumentBuffer,
+    ) {
+        let entry = self.encode_prepared(sql, parameters, arguments);
+        self.write(entry);
+    }
+
+    fn write(&self, entry: Result<Vec<u8>, Error>) {
+        match entry {
+            // entries are sent whole, so they cannot be interleaved by other connections
+            Ok(entry) => {
+                if self.writer.send(WriterCommand::Write(entry)).is_err() {
+                    tracing::warn!("statement recorder has stopped; statement not recorded");
+                }
+            }
+            Err(error) => tracing::warn!(%error, "failed to record statement"),
+        }
+    }
+
+    fn encode_prepared(
+        &self,
+        sql: &str,
+        parameters: &[PgTypeInfo],
+        arguments: &PgArgumentBuffer,
+    ) -> Result<Vec<u8>, Error> {
+        let mut entry = Vec::with_capacity(sql.len() + arguments.len() + 7);
+
+        entry.push(KIND_PREPARED);
+        put_str_u32(&mut entry, sql)?;
+
+        let count = u16::try_from(parameters.len())
+            .map_err(|_| err_protocol!("too many parameters to record: {}", parameters.len()))?;
+        entry.extend_from_slice(&count.to_be_bytes());
+
+        let mut values = &arguments[..];
+
+        for (index, ty) in parameters.iter().enumerate() {
+            let value = take_value(&mut values)?;
+
+            entry.extend_from_slice(&ty.oid().map_or(0, |oid| oid.0).to_be_bytes());
+            put_str_u16(&mut entry, ty.0.name())?;
+
+            match value {
+                _ if self.rules.iter().any(|rule| rule(sql, index)) => {
+                    entry.extend_from_slice(&LEN_REDACTED.to_be_bytes());
+                }
+
+                None => entry.extend_from_slice(&LEN_NULL.to_be_bytes()),
+
+                Some(bytes) => {
+                    // the length was already checked when the value was encoded
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+                    entry.extend_from_slice(&(bytes.len() as i32).to_be_bytes());
+                    entry.extend_from_slice(bytes);
+                }
+            }
+        }
+
+        Ok(entry)
+    }
+}
+
+fn encode_simple(sql: &str) -> Result<Vec<u8>, Error> {
+    let mut entry = Vec::with_capacity(sql.len() + 7);
+
+    entry.push(KIND_SIMPLE);
+    put_str_u32(&mut entry, sql)?;
+    entry.extend_from_slice(&0_u16.to_be_bytes());
+
+    Ok(entry)
+}
+
+/// Runs on the recorder's thread until every [`PgStatementRecorder`] sharing it is dropped.
+fn write_entries(mut file: BufWriter<File>, commands: mpsc::Receiver<WriterCommand>) {
+    // the first error since the last flush, which is reported to the next caller of `flush()`
+    let mut error: Option<io::Error> = None;
+
+    while let Ok(command) = commands.recv() {
+        match command {
+            WriterCommand::Write(entry) => {
+                if let Err(e) = file.write_all(&entry) {
+                    tracing::warn!(error = %e, "failed to write recorded statement");
+                    error.get_or_insert(e);
+                }
+            }
+
+            WriterCommand::Flush(tx) => {
+                let result = match (error.take(), file.flush()) {
+                    (Some(e), _) | (None, Err(e)) => Err(e),
+                    (None, Ok(())) => Ok(()),
+                };
+
+                tx.send(result).ok();
+            }
+        }
+    }
+
+    if let Err(e) = file.flush() {
+        tracing::warn!(error = %e, "failed to write recorded statements");
+    }
+}
+
+fn recorder_stopped() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "statement recorder has stopped")
+}
+
+impl Debug for PgStatementRecorder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgStatementRecorder")
+            .field("rules", &self.rules.len())
+            .finish()
+    }
+}
+
+/// Reads the statements written by a [`PgStatementRecorder`], in the order they were executed.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::postgres::PgReplayReader;
+///
+/// for entry in PgReplayReader::open("statements.sqlxrpl")? {
+///     entry?.execute(&mut *conn).await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PgReplayReader {
+    file: BufReader<File>,
+}
+
+impl PgReplayReader {
+    /// Open the file at `path`, checking that it was written by a [`PgStatementRecorder`].
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let mut file = BufReader::new(File::open(path)?);
+
+        let mut header = [0_u8; 8];
+        file.read_exact(&mut header)
+            .map_err(|_| invalid_data("not a statement recording"))?;
+
+        if &header[..7] != MAGIC {
+            return Err(invalid_data("not a statement recording"));
+        }
+
+        if header[7] != VERSION {
+            return Err(invalid_data(format!(
+                "unsupported statement recording version {}",
+                header[7]
+            )));
+        }
+
+        Ok(Self { file })
+    }
+
+    fn read_entry(&mut self, kind: u8) -> Result<PgReplayEntry, Error> {
+        let prepared = match kind {
+            KIND_SIMPLE => false,
+            KIND_PREPARED => true,
+            kind => return Err(invalid_data(format!("unknown entry kind {kind:#04x}"))),
+        };
+
+        let sql_len = u32::from_be_bytes(self.read_array()?);
+        let sql = self.read_string(sql_len as usize)?;
+
+        let count = u16::from_be_bytes(self.read_array()?);
+        let mut params = Vec::with_capacity(count.into());
+
+        for _ in 0..count {
+            let oid = Oid(u32::from_be_bytes(self.read_array()?));
+
+            let name_len = u16::from_be_bytes(self.read_array()?);
+            let type_name = self.read_string(name_len.into())?;
+
+            let value = match i32::from_be_bytes(self.read_array()?) {
+                LEN_NULL => PgReplayValue::Null,
+                LEN_REDACTED => PgReplayValue::Redacted,
+                len => {
+                    let len = usize::try_from(len)
+                        .map_err(|_| invalid_data(format!("invalid value length {len}")))?;
+
+                    PgReplayValue::Bytes(self.read_bytes(len)?)
+                }
+            };
+
+            params.push(PgReplayParam {
+                oid,
+                type_name,
+                value,
+            });
+        }
+
+        Ok(PgReplayEntry {
+            sql,
+            params,
+            prepared,
+        })
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let mut buf = [0_u8; N];
+        self.file.read_exact(&mut buf).map_err(truncated)?;
+        Ok(buf)
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<Vec<u8>, Error> {
+        let mut buf = vec![0_u8; len];
+        self.file.read_exact(&mut buf).map_err(truncated)?;
+        Ok(buf)
+    }
+
+    fn read_string(&mut self, len: usize) -> Result<String, Error> {
+        String::from_utf8(self.read_bytes(len)?).map_err(|e| invalid_data(e.to_string()))
+    }
+}
+
+impl Iterator for PgReplayReader {
+    type Item = Result<PgReplayEntry, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut kind = [0_u8];
+
+        match self.file.read(&mut kind) {
+            Ok(0) => None,
+            Ok(_) => Some(self.read_entry(kind[0])),
+            Err(e) => Some(Err(e.into())),
+        }
+    }
+}
+
+/// A statement read from a recording.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgReplayEntry {
+    /// The SQL of the statement.
+    pub sql: String,
+    /// The parameters the statement was executed with.
+    pub params: Vec<PgReplayParam>,
+    /// `true` if the statement was executed as a prepared statement, which it is whenever it was
+    /// given arguments, and `false` if it was executed as a simple query.
+    pub prepared: bool,
+}
+
+impl PgReplayEntry {
+    /// Execute the statement, with the parameters it was recorded with, on `conn`.
+    ///
+    /// Parameters of built-in types are bound with the type they were recorded with, and
+    /// parameters of other types are looked up by name, as their OIDs usually differ between
+    /// databases. Arrays of non-built-in types embed the OID of their element type in their
+    /// value, so they can only be replayed against a database where that type has the same OID.
+    pub async fn execute(&self, conn: &mut PgConnection) -> Result<PgQueryResult, Error> {
+        if !self.prepared {
+            return conn.execute(&*self.sql).await;
+        }
+
+        let mut arguments = PgArguments::default();
+
+        for param in &self.params {
+            arguments.add(param).map_err(Error::Encode)?;
+        }
+
+        conn.execute(sqlx_core::query::query_with(&self.sql, arguments))
+            .await
+    }
+}
+
+/// A parameter of a [`PgReplayEntry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgReplayParam {
+    /// The OID of the type of the parameter, in the database it was recorded from.
+    pub oid: Oid,
+    /// The name of the type of the parameter.
+    pub type_name: String,
+    /// The value of the parameter.
+    pub value: PgReplayValue,
+}
+
+/// The value of a [`PgReplayParam`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PgReplayValue {
+    /// `NULL`.
+    Null,
+    /// The value was excluded by [`PgStatementRecorder::redact`].
+    Redacted,
+    /// The value, in the binary format of its type.
+    Bytes(Vec<u8>),
+}
+
+impl PgReplayParam {
+    fn type_info(&self) -> PgTypeInfo {
+        if self.oid.0 < FIRST_NORMAL_OID {
+            if let Some(ty) = PgTypeInfo::try_from_oid(self.oid) {
+                return ty;
+            }
+        }
+
+        PgTypeInfo(PgType::DeclareWithName(UStr::new(&self.type_name)))
+    }
+}
+
+impl Type<Postgres> for &'_ PgReplayParam {
+    fn type_info() -> PgTypeInfo {
+        // overridden by `produces()`
+        PgTypeInfo::UNKNOWN
+    }
+}
+
+impl Encode<'_, Postgres> for &'_ PgReplayParam {
+    fn produces(&self) -> Option<PgTypeInfo> {
+        Some(self.type_info())
+    }
+
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        match &self.value {
+            PgReplayValue::Bytes(bytes) => {
+                buf.extend_from_slice(bytes);
+                Ok(IsNull::No)
+            }
+            PgReplayValue::Null | PgReplayValue::Redacted => Ok(IsNull::Yes),
+        }
+    }
+}
+
+/// Take the next value from an encoded argument buffer, returning `None` if it is `NULL`.
+fn take_value<'a>(buf: &mut &'a [u8]) -> Result<Option<&'a [u8]>, Error> {
+    let (len, rest) = buf
+        .split_first_chunk::<4>()
+        .ok_or_else(|| err_protocol!("argument buffer ended unexpectedly"))?;
+
+    let Ok(len) = usize::try_from(i32::from_be_bytes(*len)) else {
+        *buf = rest;
+        return Ok(None);
+    };
+
+    if rest.len() < len {
+        return Err(err_protocol!("argument buffer ended unexpectedly"));
+    }
+
+    let (value, rest) = rest.split_at(len);
+    *buf = rest;
+
+    Ok(Some(value))
+}
+
+fn put_str_u32(buf: &mut Vec<u8>, s: &str) -> Result<(), Error> {
+    let len = u32::try_from(s.len()).map_err(|_| err_protocol!("SQL too long to record"))?;
+
+    buf.extend_from_slice(&len.to_be_bytes());
+    buf.extend_from_slice(s.as_bytes());
+
+    Ok(())
+}
+
+fn put_str_u16(buf: &mut Vec<u8>, s: &str) -> Result<(), Error> {
+    let len = u16::try_from(s.len()).map_err(|_| err_protocol!("type name too long to record"))?;
+
+    buf.extend_from_slice(&len.to_be_bytes());
+    buf.extend_from_slice(s.as_bytes());
+
+    Ok(())
+}
+
+fn invalid_data(message: impl Into<String>) -> Error {
+    Error::Io(io::Error::new(io::ErrorKind::InvalidData, message.into()))
+}
+
+fn truncated(e: io::Error) -> Error {
+    if e.kind() == io::ErrorKind::UnexpectedEof {
+        invalid_data("statement recording ended in the middle of an entry")
+    } else {
+        e.into()
+    }
+}
@@ -0,0 +1,214 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use sqlx_core::bytes::Bytes;
+
+use crate::replication::PgLsn;
+use crate::types::Oid;
+
+/// A change streamed from a replication slot, returned by
+/// [`PgReplicationStream::recv()`][super::PgReplicationStream::recv].
+#[derive(Debug, Clone)]
+pub struct PgReplicationEvent {
+    pub(super) lsn: PgLsn,
+    pub(super) change: PgChange,
+}
+
+impl PgReplicationEvent {
+    /// The position in the log at which the server sent the change.
+    pub fn lsn(&self) -> PgLsn {
+        self.lsn
+    }
+
+    /// The position to pass to [`acknowledge()`][super::PgReplicationStream::acknowledge] once
+    /// this change and the ones before it were processed: the end of the transaction for
+    /// [`PgChange::Commit`], and [`lsn()`][Self::lsn] otherwise.
+    pub fn ack_lsn(&self) -> PgLsn {
+        match self.change {
+            PgChange::Commit { end_lsn, .. } => end_lsn,
+            _ => self.lsn,
+        }
+    }
+
+    pub fn change(&self) -> &PgChange {
+        &self.change
+    }
+
+    pub fn into_change(self) -> PgChange {
+        self.change
+    }
+}
+
+/// A change decoded from the output of a logical decoding plugin.
+///
+/// Some fields are only known with some plugins, and are `None` with others.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum PgChange {
+    /// The start of a transaction.
+    Begin {
+        /// The ID of the transaction.
+        xid: Option<u32>,
+        /// The position of the commit of the transaction.
+        final_lsn: Option<PgLsn>,
+        commit_time: Option<SystemTime>,
+    },
+
+    /// The end of a transaction.
+    Commit {
+        /// The position of the commit.
+        lsn: Option<PgLsn>,
+        /// The position after the commit, to acknowledge once the transaction is processed.
+        end_lsn: PgLsn,
+        commit_time: Option<SystemTime>,
+    },
+
+    Insert {
+        relation: Arc<PgRelation>,
+        new: PgTuple,
+    },
+
+    Update {
+        relation: Arc<PgRelation>,
+        /// The replica identity of the row before the update: its key columns, or all of its
+        /// columns with `REPLICA IDENTITY FULL`. `None` if the key did not change and the
+        /// identity is not `FULL`.
+        old: Option<PgTuple>,
+        new: PgTuple,
+    },
+
+    Delete {
+        relation: Arc<PgRelation>,
+        /// The replica identity of the deleted row.
+        old: PgTuple,
+    },
+
+    Truncate {
+        relations: Vec<Arc<PgRelation>>,
+        cascade: bool,
+        restart_identity: bool,
+    },
+
+    /// A message written with `pg_logical_emit_message()`.
+    Message {
+        /// `false` if the message was written outside of any transaction, or regardless of
+        /// whether its transaction committed.
+        transactional: bool,
+        prefix: String,
+        content: Bytes,
+    },
+}
+
+/// A table, as described by the decoding plugin before the first change to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgRelation {
+    pub(super) oid: Option<Oid>,
+    pub(super) schema: String,
+    pub(super) name: String,
+    pub(super) columns: Vec<PgRelationColumn>,
+}
+
+impl PgRelation {
+    /// The OID of the table; not sent by `wal2json`.
+    pub fn oid(&self) -> Option<Oid> {
+        self.oid
+    }
+
+    pub fn schema(&self) -> &str {
+        &self.schema
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The columns of the table, in the order of the values of its [`PgTuple`]s.
+    pub fn columns(&self) -> &[PgRelationColumn] {
+        &self.columns
+    }
+
+    /// The index of the column named `name`, to look its value up in a [`PgTuple`].
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|column| column.name == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgRelationColumn {
+    pub(super) name: String,
+    pub(super) type_oid: Option<Oid>,
+    pub(super) is_key: bool,
+}
+
+impl PgRelationColumn {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn type_oid(&self) -> Option<Oid> {
+        self.type_oid
+    }
+
+    /// Whether the column is part of the replica identity of the table.
+    pub fn is_key(&self) -> bool {
+        self.is_key
+    }
+}
+
+/// The values of a row, in the order of the [columns of its relation][PgRelation::columns].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgTuple(pub(super) Vec<PgTupleValue>);
+
+impl PgTuple {
+    pub fn values(&self) -> &[PgTupleValue] {
+        &self.0
+    }
+
+    pub fn get(&self, index: usize) -> Option<&PgTupleValue> {
+        self.0.get(index)
+    }
+}
+
+/// A value of a [`PgTuple`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PgTupleValue {
+    /// `NULL`, or a column which is not part of an old tuple holding only the replica identity.
+    Null,
+    /// A TOASTed value which the change did not modify, and which is not sent again.
+    UnchangedToast,
+    /// The value in the text format of its type.
+    Text(String),
+}
+
+impl PgTupleValue {
+    /// The value in the text format, if it is not `NULL` or unchanged.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
+/// Convert microseconds since the Postgres epoch, as the replication protocol sends times.
+pub(super) fn pg_time(micros: i64) -> SystemTime {
+    // 2000-01-01T00:00:00Z
+    let epoch = SystemTime::UNIX_EPOCH + Duration::from_secs(946_684_800);
+    let offset = Duration::from_micros(micros.unsigned_abs());
+
+    if micros >= 0 {
+        epoch + offset
+    } else {
+        epoch - offset
+    }
+}
+
+/// The current time in microseconds since the Postgres epoch.
+pub(super) fn pg_now() -> i64 {
+    let since_epoch = SystemTime::now()
+        .duration_since(pg_time(0))
+        .unwrap_or_default();
+
+    i64::try_from(since_epoch.as_micros()).unwrap_or(i64::MAX)
+}
@@ -0,0 +1,87 @@
+use std::fmt::{self, Debug, Display, Formatter};
+use std::str::FromStr;
+
+use crate::error::Error;
+
+/// A position in the write-ahead log, a Log Sequence Number, as the `pg_lsn` type shows it:
+/// two hexadecimal numbers separated by a slash, such as `16/B374D848`.
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PgLsn(u64);
+
+impl PgLsn {
+    /// The start of the log, `0/0`.
+    ///
+    /// Starting replication at it starts from the position the slot has confirmed.
+    pub const ZERO: Self = Self(0);
+
+    pub const fn from_u64(lsn: u64) -> Self {
+        Self(lsn)
+    }
+
+    pub const fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for PgLsn {
+    fn from(lsn: u64) -> Self {
+        Self(lsn)
+    }
+}
+
+impl From<PgLsn> for u64 {
+    fn from(lsn: PgLsn) -> Self {
+        lsn.0
+    }
+}
+
+impl Display for PgLsn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:X}/{:X}", self.0 >> 32, self.0 & 0xFFFF_FFFF)
+    }
+}
+
+impl Debug for PgLsn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "PgLsn({self})")
+    }
+}
+
+impl FromStr for PgLsn {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let parse = |half: &str| {
+            let valid =
+                (1..=8).contains(&half.len()) && half.bytes().all(|b| b.is_ascii_hexdigit());
+            valid.then(|| u32::from_str_radix(half, 16).ok()).flatten()
+        };
+
+        s.split_once('/')
+            .and_then(|(high, low)| Some((u64::from(parse(high)?) << 32) | u64::from(parse(low)?)))
+            .map(Self)
+            .ok_or_else(|| Error::Decode(format!("invalid LSN: {s:?}").into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_and_displays_lsns() {
+        for s in ["0/0", "16/B374D848", "FFFFFFFF/FFFFFFFF"] {
+            assert_eq!(s.parse::<PgLsn>().unwrap().to_string(), s);
+        }
+
+        assert_eq!(
+            "16/b374d848".parse::<PgLsn>().unwrap(),
+            PgLsn::from(0x16_B374_D848)
+        );
+        assert!(PgLsn::from(0x1_0000_0000) > PgLsn::from(0xFFFF_FFFF));
+
+        for s in ["", "0", "/0", "0/", "1/2/3", "100000000/0", "0/+1", "G/0"] {
+            assert!(s.parse::<PgLsn>().is_err(), "{s}");
+        }
+    }
+}
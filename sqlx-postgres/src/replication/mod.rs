@@ -0,0 +1,622 @@
+//! Streaming the changes made to a database through [logical replication], for change data
+//! capture.
+//!
+//! A [`PgReplicationConnection`] creates and drops replication slots, and starts streaming the
+//! changes recorded by a slot, which a logical decoding plugin turns into messages. The output
+//! of `pgoutput`, the plugin built into Postgres, and of [`wal2json`] is decoded into
+//! [`PgChange`]s.
+//!
+//! A slot keeps the log from being removed until its changes are acknowledged, so a change is
+//! streamed again after a reconnection until the position after it is passed to
+//! [`PgReplicationStream::acknowledge()`].
+//!
+//! ```rust,no_run
+//! # async fn example() -> sqlx::Result<()> {
+//! use sqlx::postgres::{PgChange, PgDecodingPlugin, PgReplicationConnection, PgReplicationOptions};
+//!
+//! let mut conn = PgReplicationConnection::connect("postgres://replicator@localhost/app").await?;
+//!
+//! // `CREATE PUBLICATION app_changes FOR ALL TABLES` was run beforehand
+//! conn.create_slot("app_slot", PgDecodingPlugin::PgOutput, false).await?;
+//!
+//! let mut stream = conn
+//!     .start("app_slot", PgReplicationOptions::pgoutput().publication("app_changes"))
+//!     .await?;
+//!
+//! while let Some(event) = stream.recv().await? {
+//!     if let PgChange::Insert { relation, new } = event.change() {
+//!         println!("inserted into {}: {:?}", relation.name(), new.values());
+//!     }
+//!
+//!     stream.acknowledge(event.ack_lsn());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The server must run with `wal_level = logical`, and the role must have the `REPLICATION`
+//! attribute.
+//!
+//! [logical replication]: https://www.postgresql.org/docs/current/logicaldecoding.html
+//! [`wal2json`]: https://github.com/eulerto/wal2json
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use futures_core::stream::Stream;
+use sqlx_core::bytes::{Buf, BufMut, Bytes};
+
+use crate::connection::Connection;
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::message::{BackendMessageFormat, CopyBothResponse, CopyData, CopyDone};
+use crate::row::Row;
+use crate::{PgConnectOptions, PgConnection, PgRow};
+
+use self::change::pg_now;
+use self::pgoutput::PgOutputDecoder;
+
+mod change;
+mod lsn;
+mod pgoutput;
+mod wal2json;
+
+pub use change::{
+    PgChange, PgRelation, PgRelationColumn, PgReplicationEvent, PgTuple, PgTupleValue,
+};
+pub use lsn::PgLsn;
+
+/// A connection in the logical replication mode, which can manage replication slots and stream
+/// the changes they record.
+///
+/// It is opened with the `replication=database` startup parameter, so it only accepts the simple
+/// query protocol; it is not a [`PgConnection`] and cannot run prepared queries.
+#[derive(Debug)]
+pub struct PgReplicationConnection {
+    conn: PgConnection,
+}
+
+/// The identity of the server, as returned by
+/// [`PgReplicationConnection::identify_system()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PgReplicationSystem {
+    /// The identifier of the cluster, shared by its physical replicas.
+    pub system_id: String,
+    /// The current timeline.
+    pub timeline: i32,
+    /// The current end of the log.
+    pub xlog_pos: PgLsn,
+    /// The database connected to.
+    pub database: Option<String>,
+}
+
+/// A replication slot created by [`PgReplicationConnection::create_slot()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PgReplicationSlot {
+    pub name: String,
+    /// The position from which the slot records changes.
+    pub consistent_point: PgLsn,
+    /// The snapshot exported when the slot was created, to copy the data of the tables as of
+    /// [`consistent_point`][Self::consistent_point] with `SET TRANSACTION SNAPSHOT`, in
+    /// another connection, until this one runs another command.
+    pub snapshot_name: Option<String>,
+    pub plugin: Option<String>,
+}
+
+/// A logical decoding plugin whose output [`PgReplicationStream`] decodes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PgDecodingPlugin {
+    /// `pgoutput`, the plugin built into Postgres, which streams the changes to the tables of
+    /// one or more publications.
+    PgOutput,
+    /// [`wal2json`](https://github.com/eulerto/wal2json), which must be installed on the server.
+    Wal2Json,
+}
+
+impl PgDecodingPlugin {
+    /// The name of the plugin, as given to `CREATE_REPLICATION_SLOT`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::PgOutput => "pgoutput",
+            Self::Wal2Json => "wal2json",
+        }
+    }
+}
+
+/// Options for [`PgReplicationConnection::start()`].
+#[derive(Debug, Clone)]
+pub struct PgReplicationOptions {
+    plugin: PgDecodingPlugin,
+    start_lsn: PgLsn,
+    publications: Vec<String>,
+    messages: bool,
+}
+
+impl PgReplicationOptions {
+    /// Options for a slot created with `plugin`.
+    pub fn new(plugin: PgDecodingPlugin) -> Self {
+        Self {
+            plugin,
+            start_lsn: PgLsn::ZERO,
+            publications: Vec::new(),
+            messages: false,
+        }
+    }
+
+    /// Options for a slot created with [`PgDecodingPlugin::PgOutput`], which needs at least one
+    /// [`publication()`][Self::publication].
+    pub fn pgoutput() -> Self {
+        Self::new(PgDecodingPlugin::PgOutput)
+    }
+
+    /// Options for a slot created with [`PgDecodingPlugin::Wal2Json`].
+    pub fn wal2json() -> Self {
+        Self::new(PgDecodingPlugin::Wal2Json)
+    }
+
+    /// Add a publication whose tables to stream the changes of, for `pgoutput`.
+    pub fn publication(mut self, name: impl Into<String>) -> Self {
+        self.publications.push(name.into());
+        self
+    }
+
+    /// Stream the changes after `lsn`, rather than after the position the slot last
+    /// confirmed, which is used if `lsn` is earlier.
+    pub fn start_lsn(mut self, lsn: PgLsn) -> Self {
+        self.start_lsn = lsn;
+        self
+    }
+
+    /// Stream the messages written with `pg_logical_emit_message()` as [`PgChange::Message`],
+    /// with `pgoutput` on Postgres 14 and later. `wal2json` always streams them.
+    pub fn messages(mut self, messages: bool) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    fn command(&self, slot: &str) -> Result<String, Error> {
+        let mut command = format!("START_REPLICATION SLOT {slot} LOGICAL {}", self.start_lsn);
+
+        let options = match self.plugin {
+            PgDecodingPlugin::PgOutput => {
+                if self.publications.is_empty() {
+                    return Err(Error::Configuration(
+                        "pgoutput streams the changes of at least one publication".into(),
+                    ));
+                }
+
+                let publications = self
+                    .publications
+                    .iter()
+                    .map(|name| format!("\"{}\"", name.replace('"', "\"\"")))
+                    .collect::<Vec<_>>()
+                    .join(",");
+
+                let mut options = vec![
+                    ("proto_version", "1".to_owned()),
+                    ("publication_names", publications),
+                ];
+
+                if self.messages {
+                    options.push(("messages", "true".to_owned()));
+                }
+
+                options
+            }
+
+            PgDecodingPlugin::Wal2Json => wal2json::OPTIONS
+                .iter()
+                .map(|&(name, value)| (name, value.to_owned()))
+                .collect(),
+        };
+
+        for (i, (name, value)) in options.iter().enumerate() {
+            let separator = if i == 0 { " (" } else { ", " };
+            let _ = write!(
+                command,
+                "{separator}\"{name}\" '{}'",
+                value.replace('\'', "''")
+            );
+        }
+        command.push(')');
+
+        Ok(command)
+    }
+}
+
+impl PgReplicationConnection {
+    /// Open a replication connection to the database at `url`.
+    pub async fn connect(url: &str) -> Result<Self, Error> {
+        Self::connect_with(&url.parse()?).await
+    }
+
+    /// Open a replication connection with `options`.
+    pub async fn connect_with(options: &PgConnectOptions) -> Result<Self, Error> {
+        let mut options = options.clone();
+        options.replication = true;
+
+        Ok(Self {
+            conn: PgConnection::establish(&options).await?,
+        })
+    }
+
+    /// Run `IDENTIFY_SYSTEM`.
+    pub async fn identify_system(&mut self) -> Result<PgReplicationSystem, Error> {
+        let row = self.command("IDENTIFY_SYSTEM").await?;
+
+        Ok(PgReplicationSystem {
+            system_id: row.try_get("systemid")?,
+            timeline: row.try_get("timeline")?,
+            xlog_pos: row.try_get::<String, _>("xlogpos")?.parse()?,
+            database: row.try_get("dbname")?,
+        })
+    }
+
+    /// Create a logical replication slot named `name`, recording changes from now on for
+    /// `plugin` to decode.
+    ///
+    /// A temporary slot is dropped when the connection is closed.
+    pub async fn create_slot(
+        &mut self,
+        name: &str,
+        plugin: PgDecodingPlugin,
+        temporary: bool,
+    ) -> Result<PgReplicationSlot, Error> {
+        check_slot_name(name)?;
+
+        let temporary = if temporary { " TEMPORARY" } else { "" };
+        let row = self
+            .command(&format!(
+                "CREATE_REPLICATION_SLOT {name}{temporary} LOGICAL {}",
+                plugin.name()
+            ))
+            .await?;
+
+        Ok(PgReplicationSlot {
+            name: row.try_get("slot_name")?,
+            consistent_point: row.try_get::<String, _>("consistent_point")?.parse()?,
+            snapshot_name: row.try_get("snapshot_name")?,
+            plugin: row.try_get("output_plugin")?,
+        })
+    }
+
+    /// Drop the replication slot named `name`, which releases the log it kept.
+    ///
+    /// If the slot is streaming to another connection, waits for it to stop if `wait` is `true`,
+    /// and returns an error otherwise.
+    pub async fn drop_slot(&mut self, name: &str, wait: bool) -> Result<(), Error> {
+        check_slot_name(name)?;
+
+        let wait = if wait { " WAIT" } else { "" };
+        self.conn
+            .execute(&*format!("DROP_REPLICATION_SLOT {name}{wait}"))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Start streaming the changes recorded by the slot named `slot`.
+    ///
+    /// The connection is given back by [`PgReplicationStream::stop()`].
+    pub async fn start(
+        mut self,
+        slot: &str,
+        options: PgReplicationOptions,
+    ) -> Result<PgReplicationStream, Error> {
+        check_slot_name(slot)?;
+        let command = options.command(slot)?;
+
+        self.conn.wait_until_ready().await?;
+        self.conn.queue_simple_query(&command)?;
+        self.conn.inner.stream.flush().await?;
+
+        let _: CopyBothResponse = self.conn.inner.stream.recv_expect().await?;
+
+        Ok(PgReplicationStream {
+            conn: self.conn,
+            decoder: match options.plugin {
+                PgDecodingPlugin::PgOutput => Decoder::PgOutput(PgOutputDecoder::default()),
+                PgDecodingPlugin::Wal2Json => Decoder::Wal2Json,
+            },
+            acknowledged: Arc::new(AtomicU64::new(options.start_lsn.as_u64())),
+            reported: options.start_lsn.as_u64(),
+            received: options.start_lsn.as_u64(),
+            finished: false,
+        })
+    }
+
+    /// Close the connection; a temporary slot is dropped.
+    pub async fn close(self) -> Result<(), Error> {
+        self.conn.close().await
+    }
+
+    /// Run a replication command which returns one row.
+    async fn command(&mut self, command: &str) -> Result<PgRow, Error> {
+        self.conn
+            .fetch_optional(command)
+            .await?
+            .ok_or_else(|| err_protocol!("{command} returned no row"))
+    }
+}
+
+/// Check that `name` is a valid slot name, as it is interpolated into replication commands.
+fn check_slot_name(name: &str) -> Result<(), Error> {
+    let valid = !name.is_empty()
+        && name.len() <= 63
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::Configuration(
+            format!(
+                "invalid replication slot name {name:?}: expected up to 63 lowercase letters, \
+                 digits and underscores"
+            )
+            .into(),
+        ))
+    }
+}
+
+enum Decoder {
+    PgOutput(PgOutputDecoder),
+    Wal2Json,
+}
+
+/// The changes recorded by a replication slot, streamed by
+/// [`PgReplicationConnection::start()`].
+///
+/// The position acknowledged with [`acknowledge()`][Self::acknowledge] is reported to the server
+/// the next time [`recv()`][Self::recv] is called, or when the server asks for it, which it
+/// does every half of its `wal_sender_timeout`. The slot then releases the log up to it.
+pub struct PgReplicationStream {
+    conn: PgConnection,
+    decoder: Decoder,
+    acknowledged: Arc<AtomicU64>,
+    // the positions last reported to the server, and received from it
+    reported: u64,
+    received: u64,
+    finished: bool,
+}
+
+/// A handle to acknowledge the changes of a [`PgReplicationStream`] with, from any task,
+/// returned by [`PgReplicationStream::acknowledger()`].
+#[derive(Debug, Clone)]
+pub struct PgReplicationAcknowledger {
+    acknowledged: Arc<AtomicU64>,
+}
+
+impl PgReplicationAcknowledger {
+    /// See [`PgReplicationStream::acknowledge()`].
+    pub fn acknowledge(&self, lsn: PgLsn) {
+        self.acknowledged.fetch_max(lsn.as_u64(), Ordering::AcqRel);
+    }
+}
+
+impl PgReplicationStream {
+    /// Receive the next change, or `None` once the server ended the stream.
+    pub async fn recv(&mut self) -> Result<Option<PgReplicationEvent>, Error> {
+        while !self.finished {
+            if self.acknowledged.load(Ordering::Acquire) > self.reported {
+                self.send_status(false).await?;
+            }
+
+            let message = match self.conn.inner.stream.recv().await {
+                Ok(message) => message,
+                Err(error) => {
+                    // the server left the copy mode
+                    self.finished = true;
+                    return Err(error);
+                }
+            };
+
+            match message.format {
+                BackendMessageFormat::CopyData => {
+                    let CopyData(buf) = message.decode::<CopyData<Bytes>>()?;
+
+                    if let Some(event) = self.handle_copy_data(buf).await? {
+                        return Ok(Some(event));
+                    }
+                }
+
+                BackendMessageFormat::CopyDone => {
+                    self.conn.inner.stream.send(CopyDone).await?;
+                    self.conn.wait_until_ready().await?;
+                    self.finished = true;
+                }
+
+                format => {
+                    return Err(err_protocol!(
+                        "unexpected message format during replication: {format:?}"
+                    ))
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn handle_copy_data(
+        &mut self,
+        mut buf: Bytes,
+    ) -> Result<Option<PgReplicationEvent>, Error> {
+        match buf.first() {
+            // XLogData
+            Some(b'w') if buf.len() >= 25 => {
+                buf.advance(1);
+                let lsn = PgLsn::from(buf.get_u64());
+                let wal_end = buf.get_u64();
+                let _send_time = buf.get_i64();
+
+                self.received = std::cmp::max(self.received, wal_end);
+
+                let change = match &mut self.decoder {
+                    Decoder::PgOutput(decoder) => decoder.decode(buf)?,
+                    Decoder::Wal2Json => Some(wal2json::decode(&buf, lsn)?),
+                };
+
+                Ok(change.map(|change| PgReplicationEvent { lsn, change }))
+            }
+
+            // Primary keepalive message
+            Some(b'k') if buf.len() >= 18 => {
+                buf.advance(1);
+                let wal_end = buf.get_u64();
+                let _send_time = buf.get_i64();
+                let reply = buf.get_u8() == 1;
+
+                self.received = std::cmp::max(self.received, wal_end);
+
+                if reply {
+                    self.send_status(false).await?;
+                }
+
+                Ok(None)
+            }
+
+            _ => Err(err_protocol!(
+                "unexpected replication message of {} bytes: {:?}",
+                buf.len(),
+                buf.first().map(|&tag| tag as char)
+            )),
+        }
+    }
+
+    /// Acknowledge that the changes up to `lsn` were processed, as given by
+    /// [`PgReplicationEvent::ack_lsn()`], so the server can release the log before it.
+    ///
+    /// Acknowledging an earlier position than before has no effect.
+    pub fn acknowledge(&self, lsn: PgLsn) {
+        self.acknowledged.fetch_max(lsn.as_u64(), Ordering::AcqRel);
+    }
+
+    /// The latest position acknowledged.
+    pub fn acknowledged_lsn(&self) -> PgLsn {
+        PgLsn::from(self.acknowledged.load(Ordering::Acquire))
+    }
+
+    /// A handle to acknowledge changes from other tasks, such as after
+    /// [`into_stream()`][Self::into_stream].
+    pub fn acknowledger(&self) -> PgReplicationAcknowledger {
+        PgReplicationAcknowledger {
+            acknowledged: self.acknowledged.clone(),
+        }
+    }
+
+    /// Report the acknowledged position to the server now, and ask it to reply with a keepalive
+    /// if `reply_requested`.
+    pub async fn send_status(&mut self, reply_requested: bool) -> Result<(), Error> {
+        let acknowledged = self.acknowledged.load(Ordering::Acquire);
+        let write = std::cmp::max(self.received, acknowledged);
+
+        self.conn
+            .inner
+            .stream
+            .send(CopyData(status_update(
+                write,
+                acknowledged,
+                pg_now(),
+                reply_requested,
+            )))
+            .await?;
+
+        self.reported = acknowledged;
+        Ok(())
+    }
+
+    /// Stop streaming, reporting the acknowledged position, and return the connection.
+    pub async fn stop(mut self) -> Result<PgReplicationConnection, Error> {
+        if !self.finished {
+            self.send_status(false).await?;
+            self.conn.inner.stream.send(CopyDone).await?;
+        }
+
+        // discards the changes sent before the server received `CopyDone`
+        self.conn.wait_until_ready().await?;
+
+        Ok(PgReplicationConnection { conn: self.conn })
+    }
+
+    /// Consume this stream into a [`Stream`] of changes.
+    ///
+    /// Changes are acknowledged through an [`acknowledger()`][Self::acknowledger] taken before.
+    pub fn into_stream(mut self) -> impl Stream<Item = Result<PgReplicationEvent, Error>> + Unpin {
+        Box::pin(try_stream! {
+            while let Some(event) = self.recv().await? {
+                r#yield!(event);
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// Encode a standby status update, which reports the positions written, flushed and applied.
+fn status_update(write: u64, flush: u64, clock: i64, reply_requested: bool) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(34);
+    buf.put_u8(b'r');
+    buf.put_u64(write);
+    buf.put_u64(flush);
+    // applied
+    buf.put_u64(flush);
+    buf.put_i64(clock);
+    buf.put_u8(reply_requested.into());
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_builds_start_replication_commands() {
+        let options = PgReplicationOptions::pgoutput()
+            .publication("orders")
+            .publication("it's \"quoted\"")
+            .start_lsn(PgLsn::from(0x16_B374_D848))
+            .messages(true);
+
+        assert_eq!(
+            options.command("app_slot").unwrap(),
+            "START_REPLICATION SLOT app_slot LOGICAL 16/B374D848 (\"proto_version\" '1', \
+             \"publication_names\" '\"orders\",\"it''s \"\"quoted\"\"\"', \"messages\" 'true')"
+        );
+
+        assert!(matches!(
+            PgReplicationOptions::pgoutput().command("app_slot"),
+            Err(Error::Configuration(_))
+        ));
+
+        assert!(PgReplicationOptions::wal2json()
+            .command("app_slot")
+            .unwrap()
+            .starts_with("START_REPLICATION SLOT app_slot LOGICAL 0/0 (\"format-version\" '2'"));
+    }
+
+    #[test]
+    fn it_checks_slot_names() {
+        assert!(check_slot_name("app_slot_2").is_ok());
+
+        for name in ["", "App", "slot; DROP TABLE users", &"a".repeat(64)] {
+            assert!(check_slot_name(name).is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn it_encodes_status_updates() {
+        let mut buf = Bytes::from(status_update(0x30, 0x20, 7, true));
+
+        assert_eq!(buf.len(), 34);
+        assert_eq!(buf.get_u8(), b'r');
+        assert_eq!(buf.get_u64(), 0x30);
+        assert_eq!(buf.get_u64(), 0x20);
+        assert_eq!(buf.get_u64(), 0x20);
+        assert_eq!(buf.get_i64(), 7);
+        assert_eq!(buf.get_u8(), 1);
+    }
+}
@@ -0,0 +1,251 @@
+//! Decoding of the output of the [`wal2json`] plugin, in its `format-version` 2, which writes
+//! one JSON object per change.
+//!
+//! [`wal2json`]: https://github.com/eulerto/wal2json
+
+use std::sync::Arc;
+
+use serde::Deserialize;
+use serde_json::Value;
+use sqlx_core::bytes::Bytes;
+
+use crate::error::Error;
+use crate::replication::change::{PgChange, PgRelation, PgRelationColumn, PgTuple, PgTupleValue};
+use crate::replication::PgLsn;
+use crate::types::Oid;
+
+/// The options passed to `wal2json` for the output [`decode()`] expects.
+pub(super) const OPTIONS: &[(&str, &str)] = &[
+    ("format-version", "2"),
+    ("include-xids", "1"),
+    ("include-lsn", "1"),
+    ("include-pk", "1"),
+    ("include-type-oids", "1"),
+];
+
+#[derive(Deserialize)]
+struct Message {
+    action: String,
+    xid: Option<u32>,
+    lsn: Option<String>,
+    nextlsn: Option<String>,
+    schema: Option<String>,
+    table: Option<String>,
+    #[serde(default)]
+    columns: Vec<Column>,
+    #[serde(default)]
+    identity: Vec<Column>,
+    #[serde(default)]
+    pk: Vec<Key>,
+    #[serde(default)]
+    transactional: bool,
+    prefix: Option<String>,
+    content: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Column {
+    name: String,
+    typeoid: Option<u32>,
+    #[serde(default)]
+    value: Value,
+}
+
+#[derive(Deserialize)]
+struct Key {
+    name: String,
+}
+
+/// Decode a change written by `wal2json`, received at `lsn`.
+pub(super) fn decode(buf: &[u8], lsn: PgLsn) -> Result<PgChange, Error> {
+    let message: Message =
+        serde_json::from_slice(buf).map_err(|e| err_protocol!("invalid wal2json message: {e}"))?;
+
+    let message_lsn = message.lsn.as_deref().map(str::parse).transpose()?;
+
+    let change = match &*message.action {
+        "B" => PgChange::Begin {
+            xid: message.xid,
+            final_lsn: message.nextlsn.as_deref().map(str::parse).transpose()?,
+            commit_time: None,
+        },
+
+        "C" => PgChange::Commit {
+            lsn: message_lsn,
+            end_lsn: match message.nextlsn.as_deref() {
+                Some(nextlsn) => nextlsn.parse()?,
+                None => message_lsn.unwrap_or(lsn),
+            },
+            commit_time: None,
+        },
+
+        "I" => {
+            let relation = relation(&message, &message.columns)?;
+
+            PgChange::Insert {
+                new: tuple(&relation, &message.columns),
+                relation,
+            }
+        }
+
+        "U" => {
+            let relation = relation(&message, &message.columns)?;
+
+            PgChange::Update {
+                old: (!message.identity.is_empty()).then(|| tuple(&relation, &message.identity)),
+                new: tuple(&relation, &message.columns),
+                relation,
+            }
+        }
+
+        "D" => {
+            let relation = relation(&message, &message.identity)?;
+
+            PgChange::Delete {
+                old: tuple(&relation, &message.identity),
+                relation,
+            }
+        }
+
+        // `wal2json` writes one message per truncated table, without the options
+        "T" => PgChange::Truncate {
+            relations: vec![relation(&message, &[])?],
+            cascade: false,
+            restart_identity: false,
+        },
+
+        "M" => PgChange::Message {
+            transactional: message.transactional,
+            prefix: message.prefix.unwrap_or_default(),
+            content: message.content.map(Bytes::from).unwrap_or_default(),
+        },
+
+        action => return Err(err_protocol!("unexpected wal2json action: {action:?}")),
+    };
+
+    Ok(change)
+}
+
+fn relation(message: &Message, columns: &[Column]) -> Result<Arc<PgRelation>, Error> {
+    let (Some(schema), Some(name)) = (&message.schema, &message.table) else {
+        return Err(err_protocol!(
+            "wal2json message {:?} names no table",
+            message.action
+        ));
+    };
+
+    Ok(Arc::new(PgRelation {
+        oid: None,
+        schema: schema.clone(),
+        name: name.clone(),
+        columns: columns
+            .iter()
+            .map(|column| PgRelationColumn {
+                name: column.name.clone(),
+                type_oid: column.typeoid.map(Oid),
+                is_key: message.pk.iter().any(|key| key.name == column.name),
+            })
+            .collect(),
+    }))
+}
+
+/// The values of `columns` in the order of the columns of `relation`, with `NULL` for those
+/// missing, such as the columns outside of the replica identity in an old tuple.
+fn tuple(relation: &PgRelation, columns: &[Column]) -> PgTuple {
+    let values = relation
+        .columns
+        .iter()
+        .map(
+            |column| match columns.iter().find(|value| value.name == column.name) {
+                None
+                | Some(Column {
+                    value: Value::Null, ..
+                }) => PgTupleValue::Null,
+                Some(Column {
+                    value: Value::String(text),
+                    ..
+                }) => PgTupleValue::Text(text.clone()),
+                Some(Column { value, .. }) => PgTupleValue::Text(value.to_string()),
+            },
+        )
+        .collect();
+
+    PgTuple(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_decodes_changes() {
+        let change = decode(
+            br#"{"action":"U","xid":750,"lsn":"0/16D3A88","schema":"public","table":"users",
+                "columns":[{"name":"id","type":"integer","typeoid":23,"value":1},
+                           {"name":"name","type":"text","typeoid":25,"value":"bob"},
+                           {"name":"email","type":"text","typeoid":25,"value":null}],
+                "identity":[{"name":"id","type":"integer","typeoid":23,"value":1}],
+                "pk":[{"name":"id","type":"integer","typeoid":23}]}"#,
+            PgLsn::from(0x16D_3A88),
+        )
+        .unwrap();
+
+        let PgChange::Update { relation, old, new } = change else {
+            panic!("expected an update, got {change:?}");
+        };
+
+        assert_eq!(relation.oid(), None);
+        assert_eq!(relation.name(), "users");
+        assert!(relation.columns()[0].is_key());
+        assert!(!relation.columns()[1].is_key());
+        assert_eq!(relation.columns()[2].type_oid(), Some(Oid(25)));
+
+        assert_eq!(
+            new.values(),
+            [
+                PgTupleValue::Text("1".into()),
+                PgTupleValue::Text("bob".into()),
+                PgTupleValue::Null
+            ]
+        );
+        assert_eq!(
+            old.unwrap().values(),
+            [
+                PgTupleValue::Text("1".into()),
+                PgTupleValue::Null,
+                PgTupleValue::Null
+            ]
+        );
+    }
+
+    #[test]
+    fn it_decodes_transactions() {
+        let lsn = PgLsn::from(0x100);
+
+        let PgChange::Begin { xid, .. } =
+            decode(br#"{"action":"B","xid":750,"lsn":"0/100"}"#, lsn).unwrap()
+        else {
+            panic!("expected a begin");
+        };
+        assert_eq!(xid, Some(750));
+
+        let PgChange::Commit {
+            lsn: commit_lsn,
+            end_lsn,
+            ..
+        } = decode(
+            br#"{"action":"C","xid":750,"lsn":"0/120","nextlsn":"0/128"}"#,
+            lsn,
+        )
+        .unwrap()
+        else {
+            panic!("expected a commit");
+        };
+        assert_eq!(commit_lsn, Some(PgLsn::from(0x120)));
+        assert_eq!(end_lsn, PgLsn::from(0x128));
+
+        assert!(decode(br#"{"action":"X"}"#, lsn).is_err());
+        assert!(decode(br#"{"action":"I","columns":[]}"#, lsn).is_err());
+        assert!(decode(b"not json", lsn).is_err());
+    }
+}
@@ -2,8 +2,8 @@ use crate::column::ColumnIndex;
 use crate::error::Error;
 use crate::message::DataRow;
 use crate::statement::PgStatementMetadata;
-use crate::value::PgValueFormat;
-use crate::{PgColumn, PgValueRef, Postgres};
+use crate::value::PgResultFormats;
+use crate::{PgColumn, PgValueFormat, PgValueRef, Postgres};
 pub(crate) use sqlx_core::row::Row;
 use sqlx_core::type_checking::TypeChecking;
 use sqlx_core::value::ValueRef;
@@ -13,7 +13,7 @@ use std::sync::Arc;
 /// Implementation of [`Row`] for PostgreSQL.
 pub struct PgRow {
     pub(crate) data: DataRow,
-    pub(crate) format: PgValueFormat,
+    pub(crate) formats: PgResultFormats,
     pub(crate) metadata: Arc<PgStatementMetadata>,
 }
 
@@ -33,7 +33,7 @@ impl Row for PgRow {
         let value = self.data.get(index);
 
         Ok(PgValueRef {
-            format: self.format,
+            format: self.formats.get(index),
             row: Some(&self.data.storage),
             type_info: column.type_info.clone(),
             value,
@@ -41,6 +41,34 @@ impl Row for PgRow {
     }
 }
 
+impl PgRow {
+    /// The value of a column exactly as the server sent it, or `None` if it is `NULL`.
+    ///
+    /// The bytes are in the [format][Self::value_format] of the column: the text representation
+    /// of its type, or its binary one. This allows decoding types without a [`Decode`] impl
+    /// yourself, or passing values on without decoding them at all.
+    ///
+    /// [`Decode`]: sqlx_core::decode::Decode
+    pub fn raw_value<I>(&self, index: I) -> Result<Option<&[u8]>, Error>
+    where
+        I: ColumnIndex<Self>,
+    {
+        let index = index.index(self)?;
+
+        Ok(self.data.get(index))
+    }
+
+    /// The format of the value of a column, as returned by [`raw_value()`][Self::raw_value].
+    pub fn value_format<I>(&self, index: I) -> Result<PgValueFormat, Error>
+    where
+        I: ColumnIndex<Self>,
+    {
+        let index = index.index(self)?;
+
+        Ok(self.formats.get(index))
+    }
+}
+
 impl ColumnIndex<PgRow> for &'_ str {
     fn index(&self, row: &PgRow) -> Result<usize, Error> {
         row.metadata
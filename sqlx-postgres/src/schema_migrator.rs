@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use sqlx_core::migrate::{MigrateError, Migration, Migrator};
+
+use crate::connection::Connection;
+use crate::executor::Executor;
+use crate::query::query;
+use crate::query_as::query_as;
+use crate::query_scalar::query_scalar;
+use crate::PgConnection;
+
+/// Applies the migrations of a [`Migrator`] once per schema, for schema-per-tenant databases.
+///
+/// Each migration is executed with `search_path` set to the schema being migrated, so migration
+/// scripts should use unqualified names for the objects they create.
+///
+/// Applied migrations are tracked by `(schema, version)` in a table named after the
+/// [table name][Migrator::table_name] of the `Migrator` with a `_per_schema` suffix, so
+/// `_sqlx_migrations_per_schema` by default. Unless the name is qualified with a schema, the
+/// table is created in the schema that is current when [`run()`][Self::run] is called. It is
+/// separate from the table used by [`Migrator::run()`], so both can be used against the same
+/// database.
+///
+/// The `ignore_missing` and `locking` settings of the `Migrator` are respected.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> Result<(), sqlx::migrate::MigrateError> {
+/// use sqlx::postgres::PgSchemaMigrator;
+///
+/// let migrator = sqlx::migrate::Migrator::new(std::path::Path::new("./tenant_migrations")).await?;
+///
+/// PgSchemaMigrator::new(&migrator)
+///     .schemas_from_query("SELECT schema_name FROM tenants ORDER BY schema_name")
+///     .create_schemas(true)
+///     .run(conn)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PgSchemaMigrator<'m> {
+    migrator: &'m Migrator,
+    schemas: Vec<String>,
+    schemas_query: Option<String>,
+    create_schemas: bool,
+}
+
+impl<'m> PgSchemaMigrator<'m> {
+    /// Create a new instance which applies the migrations of `migrator`.
+    ///
+    /// No schemas are migrated until some are added with [`schemas()`][Self::schemas]
+    /// or [`schemas_from_query()`][Self::schemas_from_query].
+    pub fn new(migrator: &'m Migrator) -> Self {
+        Self {
+            migrator,
+            schemas: Vec::new(),
+            schemas_query: None,
+            create_schemas: false,
+        }
+    }
+
+    /// Add schemas to migrate.
+    pub fn schemas(mut self, schemas: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.schemas.extend(schemas.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set a query returning the names of the schemas to migrate in its first column.
+    ///
+    /// The query is executed at the start of [`run()`][Self::run] and the schemas it returns
+    /// are migrated after any given to [`schemas()`][Self::schemas].
+    pub fn schemas_from_query(mut self, sql: impl Into<String>) -> Self {
+        self.schemas_query = Some(sql.into());
+        self
+    }
+
+    /// Create each schema with `CREATE SCHEMA IF NOT EXISTS` before migrating it. Defaults to `false`.
+    pub fn create_schemas(mut self, create_schemas: bool) -> Self {
+        self.create_schemas = create_schemas;
+        self
+    }
+
+    /// Run any pending migrations against each schema, in order.
+    ///
+    /// Migration stops at the first schema which fails, leaving the remaining schemas untouched.
+    pub async fn run(&self, conn: &mut PgConnection) -> Result<(), MigrateError> {
+        if self.migrator.locking {
+            sqlx_core::migrate::Migrate::lock(conn).await?;
+        }
+
+        let res = self.run_inner(conn).await;
+
+        if self.migrator.locking {
+            sqlx_core::migrate::Migrate::unlock(conn).await?;
+        }
+
+        res
+    }
+
+    async fn run_inner(&self, conn: &mut PgConnection) -> Result<(), MigrateError> {
+        let table = format!("{}_per_schema", self.migrator.table_name());
+
+        // The table is schema-qualified as migrations run with a different `search_path`.
+        let table = if table.contains('.') {
+            table
+        } else {
+            let home: String = query_scalar("SELECT current_schema()")
+                .fetch_one(&mut *conn)
+                .await?;
+
+            format!(r#""{}".{table}"#, quote(&home))
+        };
+
+        // language=SQL
+        conn.execute(&*format!(
+            r#"
+CREATE TABLE IF NOT EXISTS {table} (
+    schema TEXT NOT NULL,
+    version BIGINT NOT NULL,
+    description TEXT NOT NULL,
+    installed_on TIMESTAMPTZ NOT NULL DEFAULT now(),
+    success BOOLEAN NOT NULL,
+    checksum BYTEA NOT NULL,
+    execution_time BIGINT NOT NULL,
+    PRIMARY KEY (schema, version)
+);
+            "#
+        ))
+        .await?;
+
+        let mut schemas = self.schemas.clone();
+
+        if let Some(sql) = &self.schemas_query {
+            let queried: Vec<String> = query_scalar(sql).fetch_all(&mut *conn).await?;
+            schemas.extend(queried);
+        }
+
+        for schema in &schemas {
+            self.migrate_schema(conn, &table, schema).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn migrate_schema(
+        &self,
+        conn: &mut PgConnection,
+        table: &str,
+        schema: &str,
+    ) -> Result<(), MigrateError> {
+        if self.create_schemas {
+            conn.execute(&*format!(
+                r#"CREATE SCHEMA IF NOT EXISTS "{}""#,
+                quote(schema)
+            ))
+            .await?;
+        }
+
+        // language=SQL
+        let applied: HashMap<i64, Vec<u8>> = query_as(&format!(
+            "SELECT version, checksum FROM {table} WHERE schema = $1 ORDER BY version"
+        ))
+        .bind(schema)
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .collect();
+
+        if !self.migrator.ignore_missing {
+            if let Some(version) = applied
+                .keys()
+                .find(|version| !self.migrator.version_exists(**version))
+            {
+                return Err(MigrateError::VersionMissing(*version));
+            }
+        }
+
+        for migration in self.migrator.iter() {
+            if migration.migration_type.is_down_migration() {
+                continue;
+            }
+
+            match applied.get(&migration.version) {
+                Some(checksum) => {
+                    if *migration.checksum != **checksum {
+                        return Err(MigrateError::VersionMismatch(migration.version));
+                    }
+                }
+                None => apply(conn, table, schema, migration).await?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+async fn apply(
+    conn: &mut PgConnection,
+    table: &str,
+    schema: &str,
+    migration: &Migration,
+) -> Result<(), MigrateError> {
+    let start = Instant::now();
+    let search_path = format!(r#""{}""#, quote(schema));
+
+    if migration.no_tx {
+        let previous: String = query_scalar("SELECT current_setting('search_path')")
+            .fetch_one(&mut *conn)
+            .await?;
+
+        // language=SQL
+        query("SELECT set_config('search_path', $1, false)")
+            .bind(&search_path)
+            .execute(&mut *conn)
+            .await?;
+
+        let res = execute_migration(conn, table, schema, migration).await;
+
+        query("SELECT set_config('search_path', $1, false)")
+            .bind(&previous)
+            .execute(&mut *conn)
+            .await?;
+
+        res?;
+    } else {
+        let mut tx = conn.begin().await?;
+
+        // language=SQL
+        query("SELECT set_config('search_path', $1, true)")
+            .bind(&search_path)
+            .execute(&mut *tx)
+            .await?;
+
+        execute_migration(&mut tx, table, schema, migration).await?;
+        tx.commit().await?;
+    }
+
+    let elapsed = start.elapsed();
+
+    // language=SQL
+    #[allow(clippy::cast_possible_truncation)]
+    let _ = query(&format!(
+        "UPDATE {table} SET execution_time = $1 WHERE schema = $2 AND version = $3"
+    ))
+    .bind(elapsed.as_nanos() as i64)
+    .bind(schema)
+    .bind(migration.version)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn execute_migration(
+    conn: &mut PgConnection,
+    table: &str,
+    schema: &str,
+    migration: &Migration,
+) -> Result<(), MigrateError> {
+    let _ = conn
+        .execute(&*migration.sql()?)
+        .await
+        .map_err(|e| MigrateError::ExecuteMigration(e, migration.version))?;
+
+    // language=SQL
+    let _ = query(&format!(
+        r#"
+    INSERT INTO {table} ( schema, version, description, success, checksum, execution_time )
+    VALUES ( $1, $2, $3, TRUE, $4, -1 )
+        "#
+    ))
+    .bind(schema)
+    .bind(migration.version)
+    .bind(&*migration.description)
+    .bind(&*migration.checksum)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+fn quote(ident: &str) -> String {
+    ident.replace('"', "\"\"")
+}
@@ -0,0 +1,260 @@
+use std::time::Duration;
+
+use futures_core::future::BoxFuture;
+
+use crate::error::Error;
+use crate::query::query;
+use crate::transaction::Transaction;
+use crate::Postgres;
+
+/// Implements methods for changing settings for the rest of a transaction on a
+/// [`Transaction`] with Postgres.
+///
+/// Settings are changed with `set_config(name, value, true)`, the function form of
+/// [`SET LOCAL`], so they are reset by the server when the transaction ends, whether it is
+/// committed, rolled back or dropped. A connection that returns to a pool never keeps them.
+///
+/// In a savepoint, the change is undone if the savepoint is rolled back, and lasts until the
+/// end of the enclosing transaction otherwise.
+///
+/// ```rust,no_run
+/// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+/// use std::time::Duration;
+/// use sqlx::postgres::{PgSetting, PgTransactionExt};
+///
+/// let mut tx = pool.begin().await?;
+///
+/// tx.set_local("work_mem", "256MB").await?;
+/// tx.set_local_setting(PgSetting::StatementTimeout(Duration::from_secs(30)))
+///     .await?;
+///
+/// sqlx::query("REFRESH MATERIALIZED VIEW daily_totals")
+///     .execute(&mut *tx)
+///     .await?;
+///
+/// tx.commit().await
+/// # }
+/// ```
+///
+/// [`SET LOCAL`]: https://www.postgresql.org/docs/current/sql-set.html
+pub trait PgTransactionExt {
+    /// Set the configuration parameter `name` to `value` until the end of the transaction.
+    ///
+    /// The value is bound as a query parameter and checked by the server. Returns
+    /// [`Error::InvalidArgument`] without running anything if `name` is not a valid parameter
+    /// name; custom parameters such as `app.tenant_id` are allowed.
+    fn set_local<'a>(
+        &'a mut self,
+        name: &'a str,
+        value: &'a str,
+    ) -> BoxFuture<'a, Result<(), Error>>;
+
+    /// Change one of the commonly used settings in [`PgSetting`] until the end of the
+    /// transaction.
+    ///
+    /// Returns [`Error::InvalidArgument`] without running anything if the value is out of
+    /// the range the server accepts.
+    fn set_local_setting(&mut self, setting: PgSetting) -> BoxFuture<'_, Result<(), Error>>;
+}
+
+impl PgTransactionExt for Transaction<'_, Postgres> {
+    fn set_local<'a>(
+        &'a mut self,
+        name: &'a str,
+        value: &'a str,
+    ) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            if !is_setting_name(name) {
+                return Err(Error::InvalidArgument(format!(
+                    "{name:?} is not a valid setting name"
+                )));
+            }
+
+            // language=SQL
+            query("SELECT set_config($1, $2, true)")
+                .bind(name)
+                .bind(value)
+                .execute(&mut **self)
+                .await?;
+
+            if name.eq_ignore_ascii_case("search_path") {
+                self.search_path_changed();
+            }
+
+            Ok(())
+        })
+    }
+
+    fn set_local_setting(&mut self, setting: PgSetting) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            let value = setting.value()?;
+            self.set_local(setting.name(), &value).await
+        })
+    }
+}
+
+/// Commonly used Postgres settings, with values checked before they are sent.
+///
+/// See [`PgTransactionExt::set_local_setting()`] and the [Postgres documentation] of each
+/// setting.
+///
+/// [Postgres documentation]: https://www.postgresql.org/docs/current/runtime-config-client.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PgSetting {
+    /// `statement_timeout`: abort statements that take longer than this.
+    ///
+    /// `Duration::ZERO` disables the timeout. Durations are rounded up to whole milliseconds.
+    StatementTimeout(Duration),
+    /// `lock_timeout`: abort statements that wait longer than this for a lock.
+    ///
+    /// `Duration::ZERO` disables the timeout. Durations are rounded up to whole milliseconds.
+    LockTimeout(Duration),
+    /// `idle_in_transaction_session_timeout`: end the session if the transaction is left idle
+    /// longer than this.
+    ///
+    /// `Duration::ZERO` disables the timeout. Durations are rounded up to whole milliseconds.
+    IdleInTransactionSessionTimeout(Duration),
+    /// `work_mem`: memory for each sort or hash operation, in kilobytes, at least 64.
+    WorkMem(u64),
+    /// `maintenance_work_mem`: memory for maintenance operations such as `CREATE INDEX`, in
+    /// kilobytes, at least 1024.
+    MaintenanceWorkMem(u64),
+    /// `search_path`: the schemas to look up unqualified names in, in order.
+    ///
+    /// Each schema name is quoted, so it is used exactly as given.
+    SearchPath(Vec<String>),
+    /// `role`: the role to run the rest of the transaction as, like `SET LOCAL ROLE`.
+    Role(String),
+    /// `TimeZone`: the time zone for displaying and interpreting timestamps.
+    TimeZone(String),
+    /// `synchronous_commit`: whether a commit waits for its WAL records to be flushed.
+    SynchronousCommit(bool),
+    /// `application_name`: the name reported in `pg_stat_activity` and server logs.
+    ApplicationName(String),
+}
+
+impl PgSetting {
+    /// The name of the setting.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PgSetting::StatementTimeout(_) => "statement_timeout",
+            PgSetting::LockTimeout(_) => "lock_timeout",
+            PgSetting::IdleInTransactionSessionTimeout(_) => "idle_in_transaction_session_timeout",
+            PgSetting::WorkMem(_) => "work_mem",
+            PgSetting::MaintenanceWorkMem(_) => "maintenance_work_mem",
+            PgSetting::SearchPath(_) => "search_path",
+            PgSetting::Role(_) => "role",
+            PgSetting::TimeZone(_) => "TimeZone",
+            PgSetting::SynchronousCommit(_) => "synchronous_commit",
+            PgSetting::ApplicationName(_) => "application_name",
+        }
+    }
+
+    /// The value of the setting, as sent to the server.
+    ///
+    /// Returns [`Error::InvalidArgument`] if the value is out of range.
+    pub fn value(&self) -> Result<String, Error> {
+        match self {
+            PgSetting::StatementTimeout(timeout)
+            | PgSetting::LockTimeout(timeout)
+            | PgSetting::IdleInTransactionSessionTimeout(timeout) => {
+                let millis =
+                    timeout.as_millis() + u128::from(timeout.subsec_nanos() % 1_000_000 != 0);
+
+                if millis > i32::MAX as u128 {
+                    return Err(self.out_of_range(format_args!("{timeout:?}")));
+                }
+
+                Ok(format!("{millis}ms"))
+            }
+
+            PgSetting::WorkMem(kilobytes) => self.kilobytes(*kilobytes, 64),
+            PgSetting::MaintenanceWorkMem(kilobytes) => self.kilobytes(*kilobytes, 1024),
+
+            PgSetting::SearchPath(schemas) => Ok(schemas
+                .iter()
+                .map(|schema| format!("\"{}\"", schema.replace('"', "\"\"")))
+                .collect::<Vec<_>>()
+                .join(", ")),
+
+            PgSetting::Role(value)
+            | PgSetting::TimeZone(value)
+            | PgSetting::ApplicationName(value) => Ok(value.clone()),
+
+            PgSetting::SynchronousCommit(on) => Ok(if *on { "on" } else { "off" }.to_owned()),
+        }
+    }
+
+    fn kilobytes(&self, kilobytes: u64, min: u64) -> Result<String, Error> {
+        if !(min..=i32::MAX as u64).contains(&kilobytes) {
+            return Err(self.out_of_range(format_args!("{kilobytes} kB")));
+        }
+
+        Ok(format!("{kilobytes}kB"))
+    }
+
+    fn out_of_range(&self, value: std::fmt::Arguments<'_>) -> Error {
+        Error::InvalidArgument(format!("{value} is out of range for {}", self.name()))
+    }
+}
+
+// Names of built-in settings are identifiers; custom ones are qualified, as in `app.tenant_id`.
+fn is_setting_name(name: &str) -> bool {
+    name.split('.').all(|part| {
+        part.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_setting_values() {
+        assert_eq!(
+            PgSetting::StatementTimeout(Duration::from_secs(30))
+                .value()
+                .unwrap(),
+            "30000ms"
+        );
+        assert_eq!(
+            PgSetting::LockTimeout(Duration::from_micros(1500))
+                .value()
+                .unwrap(),
+            "2ms"
+        );
+        assert_eq!(
+            PgSetting::LockTimeout(Duration::ZERO).value().unwrap(),
+            "0ms"
+        );
+        assert_eq!(PgSetting::WorkMem(65536).value().unwrap(), "65536kB");
+        assert_eq!(
+            PgSetting::SearchPath(vec!["tenant \"1\"".into(), "public".into()])
+                .value()
+                .unwrap(),
+            r#""tenant ""1""", "public""#
+        );
+
+        assert!(PgSetting::WorkMem(63).value().is_err());
+        assert!(PgSetting::MaintenanceWorkMem(512).value().is_err());
+        assert!(PgSetting::StatementTimeout(Duration::from_secs(u64::MAX))
+            .value()
+            .is_err());
+    }
+
+    #[test]
+    fn test_is_setting_name() {
+        assert!(is_setting_name("work_mem"));
+        assert!(is_setting_name("TimeZone"));
+        assert!(is_setting_name("app.tenant_id"));
+
+        assert!(!is_setting_name(""));
+        assert!(!is_setting_name("app."));
+        assert!(!is_setting_name("1abc"));
+        assert!(!is_setting_name("work_mem; DROP TABLE users"));
+    }
+}
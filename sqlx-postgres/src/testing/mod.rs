@@ -8,6 +8,7 @@ use futures_core::future::BoxFuture;
 use once_cell::sync::OnceCell;
 use sqlx_core::connection::Connection;
 use sqlx_core::query_scalar::query_scalar;
+use sqlx_core::row::Row;
 
 use crate::error::Error;
 use crate::executor::Executor;
@@ -17,6 +18,9 @@ use crate::{PgConnectOptions, PgConnection, Postgres};
 
 pub(crate) use sqlx_core::testing::*;
 
+#[cfg(feature = "proptest")]
+mod roundtrip;
+
 // Using a blocking `OnceCell` here because the critical sections are short.
 static MASTER_POOL: OnceCell<Pool<Postgres>> = OnceCell::new();
 // Automatically delete any databases created before the start of the test binary.
@@ -90,6 +94,72 @@ impl TestSupport for Postgres {
         // but I'm keeping the code around for now because I plan to come back to it.
         todo!()
     }
+
+    fn explain<'c>(
+        conn: &'c mut PgConnection,
+        query: &'c str,
+    ) -> BoxFuture<'c, Result<QueryPlan, Error>> {
+        Box::pin(async move {
+            let rows = conn.fetch_all(&*format!("EXPLAIN {query}")).await?;
+
+            let mut plan = QueryPlan::default();
+
+            for row in rows {
+                plan.lines.push(row.try_get(0)?);
+            }
+
+            // The text plan is for people; node names vary too much to parse it reliably,
+            // e.g. `Index Only Scan Backward using` or `Parallel Index Scan using`.
+            let json: String = conn
+                .fetch_one(&*format!("EXPLAIN (FORMAT JSON) {query}"))
+                .await?
+                .try_get_unchecked(0)?;
+
+            let json: serde_json::Value = serde_json::from_str(&json)
+                .map_err(|e| Error::Protocol(format!("invalid EXPLAIN output: {e}")))?;
+
+            collect_indexes(&json, &mut plan.indexes);
+
+            Ok(plan)
+        })
+    }
+
+    fn clean_tables<'c>(
+        conn: &'c mut PgConnection,
+        tables: &'c [&'c str],
+    ) -> BoxFuture<'c, Result<(), Error>> {
+        Box::pin(async move {
+            // Truncating the tables in one statement is allowed even if they reference each other.
+            conn.execute(&*format!(
+                "TRUNCATE TABLE {} RESTART IDENTITY",
+                tables.join(", ")
+            ))
+            .await?;
+
+            Ok(())
+        })
+    }
+}
+
+/// Collect the `"Index Name"` of every node of a plan from `EXPLAIN (FORMAT JSON)`.
+fn collect_indexes(value: &serde_json::Value, indexes: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(node) => {
+            if let Some(serde_json::Value::String(index)) = node.get("Index Name") {
+                indexes.push(index.clone());
+            }
+
+            for value in node.values() {
+                collect_indexes(value, indexes);
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for value in values {
+                collect_indexes(value, indexes);
+            }
+        }
+        _ => {}
+    }
 }
 
 async fn test_context(args: &TestArgs) -> Result<TestContext<Postgres>, Error> {
@@ -0,0 +1,144 @@
+use std::fmt::Debug;
+use std::ops::Bound;
+
+use sqlx_core::testing::proptest::prelude::*;
+use sqlx_core::testing::Roundtrip;
+use sqlx_core::types::JsonValue;
+
+use crate::types::{PgInterval, PgRange};
+use crate::Postgres;
+
+macro_rules! impl_roundtrip {
+    ($($ty:ty => $strategy:expr),* $(,)?) => {
+        $(
+            impl Roundtrip<Postgres> for $ty {
+                fn strategy() -> BoxedStrategy<Self> {
+                    $strategy.boxed()
+                }
+            }
+        )*
+    };
+}
+
+impl_roundtrip!(
+    bool => any::<bool>(),
+    i16 => any::<i16>(),
+    i32 => any::<i32>(),
+    i64 => any::<i64>(),
+    // `NaN` survives, but never compares equal to itself
+    f32 => any::<f32>().prop_filter("NaN", |v| !v.is_nan()),
+    f64 => any::<f64>().prop_filter("NaN", |v| !v.is_nan()),
+    // `TEXT` cannot contain NUL
+    String => any::<String>().prop_filter("NUL", |v| !v.contains('\0')),
+    Vec<u8> => any::<Vec<u8>>(),
+    // the extremes of every field are taken for infinity
+    PgInterval => (-i32::MAX..i32::MAX, -i32::MAX..i32::MAX, -i64::MAX..i64::MAX)
+        .prop_map(|(months, days, microseconds)| PgInterval { months, days, microseconds }),
+    JsonValue => json(),
+    Vec<i32> => any::<Vec<i32>>(),
+    Vec<Option<i64>> => any::<Vec<Option<i64>>>(),
+    Vec<String> => any::<Vec<String>>().prop_filter("NUL", |v| v.iter().all(|s| !s.contains('\0'))),
+    PgRange<i32> => range(any::<i32>()),
+    PgRange<i64> => range(any::<i64>()),
+);
+
+/// Values of `JSONB`, which keeps numbers as `NUMERIC` and cannot contain NUL.
+fn json() -> impl Strategy<Value = JsonValue> {
+    let string = any::<String>().prop_filter("NUL", |v| !v.contains('\0'));
+
+    let leaf = prop_oneof![
+        Just(JsonValue::Null),
+        any::<bool>().prop_map(JsonValue::from),
+        any::<i64>().prop_map(JsonValue::from),
+        string.clone().prop_map(JsonValue::from),
+    ];
+
+    leaf.prop_recursive(4, 32, 8, move |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..8).prop_map(JsonValue::from),
+            prop::collection::btree_map(string.clone(), inner, 0..8)
+                .prop_map(|map| JsonValue::Object(map.into_iter().collect())),
+        ]
+    })
+}
+
+/// Non-empty ranges in the canonical form of discrete range types, `[start,end)`, which is what
+/// Postgres turns any other one into.
+fn range<T>(bound: impl Strategy<Value = T> + Clone) -> impl Strategy<Value = PgRange<T>>
+where
+    T: PartialOrd + Debug + Clone,
+{
+    (bound.clone(), bound, any::<(bool, bool)>())
+        .prop_filter("empty", |(start, end, _)| start != end)
+        .prop_map(|(a, b, (unbounded_start, unbounded_end))| {
+            let (start, end) = if a < b { (a, b) } else { (b, a) };
+
+            PgRange {
+                start: match unbounded_start {
+                    true => Bound::Unbounded,
+                    false => Bound::Included(start),
+                },
+                end: match unbounded_end {
+                    true => Bound::Unbounded,
+                    false => Bound::Excluded(end),
+                },
+            }
+        })
+}
+
+#[cfg(feature = "chrono")]
+mod chrono {
+    use ::chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+    use sqlx_core::testing::strategy::chrono::{date, time_micros};
+
+    use super::*;
+
+    // Postgres goes beyond these years, but `chrono` does not go much further
+    impl_roundtrip!(
+        NaiveDate => date(1..=9999),
+        NaiveTime => time_micros(),
+        NaiveDateTime => (date(1..=9999), time_micros()).prop_map(|(d, t)| d.and_time(t)),
+        DateTime<Utc> =>
+            (date(1..=9999), time_micros()).prop_map(|(d, t)| d.and_time(t).and_utc()),
+    );
+}
+
+#[cfg(feature = "time")]
+mod time {
+    use ::time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+    use sqlx_core::testing::strategy::time::{date, time_micros};
+
+    use super::*;
+
+    impl_roundtrip!(
+        Date => date(1..=9999),
+        Time => time_micros(),
+        PrimitiveDateTime =>
+            (date(1..=9999), time_micros()).prop_map(|(d, t)| PrimitiveDateTime::new(d, t)),
+        OffsetDateTime => (date(1..=9999), time_micros())
+            .prop_map(|(d, t)| PrimitiveDateTime::new(d, t).assume_utc()),
+    );
+}
+
+#[cfg(feature = "uuid")]
+impl_roundtrip!(uuid::Uuid => any::<u128>().prop_map(uuid::Uuid::from_u128));
+
+#[cfg(feature = "bigdecimal")]
+impl_roundtrip!(
+    bigdecimal::BigDecimal => (any::<i64>(), 0..=20_i64)
+        .prop_map(|(num, scale)| bigdecimal::BigDecimal::new(num.into(), scale)),
+);
+
+#[cfg(feature = "ipnetwork")]
+impl_roundtrip!(
+    std::net::IpAddr => any::<std::net::IpAddr>(),
+    ipnetwork::IpNetwork => any::<std::net::IpAddr>()
+        .prop_flat_map(|ip| (Just(ip), 0..=if ip.is_ipv4() { 32_u8 } else { 128 }))
+        .prop_map(|(ip, prefix)| ipnetwork::IpNetwork::new(ip, prefix).expect("BUG: prefix out of range")),
+);
+
+#[cfg(feature = "rust_decimal")]
+impl_roundtrip!(
+    rust_decimal::Decimal => (any::<i64>(), 0..=10_u32)
+        .prop_map(|(num, scale)| rust_decimal::Decimal::new(num, scale)),
+);
@@ -46,6 +46,10 @@ impl_type_checking!(
 
         sqlx::postgres::types::PgCircle,
 
+        sqlx::postgres::types::PgTsVector,
+
+        sqlx::postgres::types::PgTsQuery,
+
         #[cfg(feature = "uuid")]
         sqlx::types::Uuid,
 
@@ -98,6 +102,8 @@ impl_type_checking!(
         sqlx::types::BitVec,
 
         sqlx::postgres::types::PgHstore,
+
+        sqlx::types::Vector,
         // Arrays
 
         Vec<bool> | &[bool],
@@ -112,6 +118,8 @@ impl_type_checking!(
         Vec<sqlx::postgres::types::Oid> | &[sqlx::postgres::types::Oid],
         Vec<sqlx::postgres::types::PgMoney> | &[sqlx::postgres::types::PgMoney],
         Vec<sqlx::postgres::types::PgInterval> | &[sqlx::postgres::types::PgInterval],
+        Vec<sqlx::postgres::types::PgTsVector> | &[sqlx::postgres::types::PgTsVector],
+        Vec<sqlx::postgres::types::PgTsQuery> | &[sqlx::postgres::types::PgTsQuery],
 
         #[cfg(feature = "uuid")]
         Vec<sqlx::types::Uuid> | &[sqlx::types::Uuid],
@@ -144,6 +144,10 @@ pub enum PgType {
     Int8RangeArray,
     Jsonpath,
     JsonpathArray,
+    TsVector,
+    TsVectorArray,
+    TsQuery,
+    TsQueryArray,
     Money,
     MoneyArray,
 
@@ -447,6 +451,10 @@ impl PgType {
             3927 => PgType::Int8RangeArray,
             4072 => PgType::Jsonpath,
             4073 => PgType::JsonpathArray,
+            3614 => PgType::TsVector,
+            3643 => PgType::TsVectorArray,
+            3615 => PgType::TsQuery,
+            3645 => PgType::TsQueryArray,
 
             _ => {
                 return None;
@@ -555,6 +563,10 @@ impl PgType {
             PgType::Int8RangeArray => Oid(3927),
             PgType::Jsonpath => Oid(4072),
             PgType::JsonpathArray => Oid(4073),
+            PgType::TsVector => Oid(3614),
+            PgType::TsVectorArray => Oid(3643),
+            PgType::TsQuery => Oid(3615),
+            PgType::TsQueryArray => Oid(3645),
 
             PgType::Custom(ty) => ty.oid,
 
@@ -659,6 +671,10 @@ impl PgType {
             PgType::Int8RangeArray => "INT8RANGE[]",
             PgType::Jsonpath => "JSONPATH",
             PgType::JsonpathArray => "JSONPATH[]",
+            PgType::TsVector => "TSVECTOR",
+            PgType::TsVectorArray => "TSVECTOR[]",
+            PgType::TsQuery => "TSQUERY",
+            PgType::TsQueryArray => "TSQUERY[]",
             PgType::Money => "MONEY",
             PgType::MoneyArray => "MONEY[]",
             PgType::Void => "VOID",
@@ -760,6 +776,10 @@ impl PgType {
             PgType::Int8RangeArray => "_int8range",
             PgType::Jsonpath => "jsonpath",
             PgType::JsonpathArray => "_jsonpath",
+            PgType::TsVector => "tsvector",
+            PgType::TsVectorArray => "_tsvector",
+            PgType::TsQuery => "tsquery",
+            PgType::TsQueryArray => "_tsquery",
             PgType::Money => "money",
             PgType::MoneyArray => "_money",
             PgType::Void => "void",
@@ -861,6 +881,10 @@ impl PgType {
             PgType::Int8RangeArray => &PgTypeKind::Array(PgTypeInfo(PgType::Int8Range)),
             PgType::Jsonpath => &PgTypeKind::Simple,
             PgType::JsonpathArray => &PgTypeKind::Array(PgTypeInfo(PgType::Jsonpath)),
+            PgType::TsVector => &PgTypeKind::Simple,
+            PgType::TsVectorArray => &PgTypeKind::Array(PgTypeInfo(PgType::TsVector)),
+            PgType::TsQuery => &PgTypeKind::Simple,
+            PgType::TsQueryArray => &PgTypeKind::Array(PgTypeInfo(PgType::TsQuery)),
             PgType::Money => &PgTypeKind::Simple,
             PgType::MoneyArray => &PgTypeKind::Array(PgTypeInfo(PgType::Money)),
 
@@ -977,6 +1001,10 @@ impl PgType {
             PgType::Int8RangeArray => Some(Cow::Owned(PgTypeInfo(PgType::Int8Range))),
             PgType::Jsonpath => None,
             PgType::JsonpathArray => Some(Cow::Owned(PgTypeInfo(PgType::Jsonpath))),
+            PgType::TsVector => None,
+            PgType::TsVectorArray => Some(Cow::Owned(PgTypeInfo(PgType::TsVector))),
+            PgType::TsQuery => None,
+            PgType::TsQueryArray => Some(Cow::Owned(PgTypeInfo(PgType::TsQuery))),
             // There is no `UnknownArray`
             PgType::Unknown => None,
             // There is no `VoidArray`
@@ -1095,6 +1123,16 @@ impl PgTypeInfo {
     pub(crate) const JSONPATH: Self = Self(PgType::Jsonpath);
     pub(crate) const JSONPATH_ARRAY: Self = Self(PgType::JsonpathArray);
 
+    //
+    // text search types
+    // https://www.postgresql.org/docs/current/datatype-textsearch.html
+    //
+
+    pub(crate) const TS_VECTOR: Self = Self(PgType::TsVector);
+    pub(crate) const TS_VECTOR_ARRAY: Self = Self(PgType::TsVectorArray);
+    pub(crate) const TS_QUERY: Self = Self(PgType::TsQuery);
+    pub(crate) const TS_QUERY_ARRAY: Self = Self(PgType::TsQueryArray);
+
     //
     // network address types
     // https://www.postgresql.org/docs/current/datatype-net-types.html
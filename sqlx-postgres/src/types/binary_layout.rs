@@ -0,0 +1,328 @@
+//! Round trips of values through the binary format, and the bytes of the fixed-size encodings.
+//!
+//! Postgres sends binary values in network byte order whatever the platform of the client or
+//! the server, so the expected bytes are spelled out instead of being computed with
+//! `to_be_bytes()`: an encoder or decoder using the byte order or the word size of the host
+//! fails these tests when they are run on a big-endian or 32-bit target, which CI does. A round
+//! trip alone would not catch an encoder and a decoder making the same mistake.
+//!
+//! The round trips are properties over the values generated by `Roundtrip::strategy()` for each
+//! type, so they only run with the `proptest` feature.
+
+use std::fmt::Debug;
+use std::ops::Bound;
+
+use sqlx_core::decode::Decode;
+use sqlx_core::encode::{Encode, IsNull};
+use sqlx_core::types::Type;
+
+use crate::types::{Oid, PgInterval, PgMoney, PgPoint, PgRange};
+use crate::{PgArgumentBuffer, PgValueFormat, PgValueRef, Postgres};
+
+fn encode<T>(value: &T) -> Vec<u8>
+where
+    T: for<'q> Encode<'q, Postgres>,
+{
+    let mut buf = PgArgumentBuffer::default();
+    assert!(matches!(value.encode_by_ref(&mut buf), Ok(IsNull::No)));
+    buf.to_vec()
+}
+
+fn decode<T>(bytes: &[u8]) -> T
+where
+    T: for<'r> Decode<'r, Postgres> + Type<Postgres>,
+{
+    T::decode(PgValueRef {
+        value: Some(bytes),
+        row: None,
+        type_info: T::type_info(),
+        format: PgValueFormat::Binary,
+    })
+    .unwrap()
+}
+
+#[track_caller]
+fn assert_round_trip<T>(value: T)
+where
+    T: for<'q> Encode<'q, Postgres> + for<'r> Decode<'r, Postgres> + Type<Postgres>,
+    T: PartialEq + Debug,
+{
+    let bytes = encode(&value);
+    assert_eq!(decode::<T>(&bytes), value, "decoded from {bytes:02x?}");
+}
+
+#[track_caller]
+fn assert_bytes<T>(value: T, expected: &[u8])
+where
+    T: for<'q> Encode<'q, Postgres> + for<'r> Decode<'r, Postgres> + Type<Postgres>,
+    T: PartialEq + Debug,
+{
+    assert_eq!(encode(&value), expected, "encoded from {value:?}");
+    assert_eq!(decode::<T>(expected), value);
+}
+
+#[test]
+fn it_encodes_integers_in_network_byte_order() {
+    assert_bytes(true, &[1]);
+    assert_bytes(0x12_i8, &[0x12]);
+    assert_bytes(0x0102_i16, &[0x01, 0x02]);
+    assert_bytes(-2_i16, &[0xff, 0xfe]);
+    assert_bytes(0x0102_0304_i32, &[0x01, 0x02, 0x03, 0x04]);
+    assert_bytes(i32::MIN, &[0x80, 0x00, 0x00, 0x00]);
+    assert_bytes(
+        0x0102_0304_0506_0708_i64,
+        &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08],
+    );
+    assert_bytes(-2_i64, &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe]);
+    assert_bytes(Oid(0x0102_0304), &[0x01, 0x02, 0x03, 0x04]);
+}
+
+#[test]
+fn it_encodes_floats_in_network_byte_order() {
+    assert_bytes(1.5_f32, &[0x3f, 0xc0, 0x00, 0x00]);
+    assert_bytes(-0.0_f32, &[0x80, 0x00, 0x00, 0x00]);
+    assert_bytes(1.5_f64, &[0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+}
+
+#[test]
+fn it_encodes_arrays_in_network_byte_order() {
+    assert_bytes(
+        vec![0x0102_0304_i32, -1],
+        &[
+            0, 0, 0, 1, // dimensions
+            0, 0, 0, 0, // flags
+            0, 0, 0, 23, // element type: `int4`
+            0, 0, 0, 2, // length
+            0, 0, 0, 1, // lower bound
+            0, 0, 0, 4, 0x01, 0x02, 0x03, 0x04, // first element
+            0, 0, 0, 4, 0xff, 0xff, 0xff, 0xff, // second element
+        ],
+    );
+}
+
+#[test]
+fn it_encodes_intervals_and_money_in_network_byte_order() {
+    assert_bytes(
+        PgInterval {
+            months: 0x0102_0304,
+            days: -2,
+            microseconds: 0x0102_0304_0506_0708,
+        },
+        &[
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, // microseconds
+            0xff, 0xff, 0xff, 0xfe, // days
+            0x01, 0x02, 0x03, 0x04, // months
+        ],
+    );
+    assert_bytes(
+        PgMoney(-2),
+        &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe],
+    );
+}
+
+#[test]
+fn it_encodes_points_in_network_byte_order() {
+    assert_bytes(
+        PgPoint { x: 1.5, y: -2.0 },
+        &[
+            0x3f, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // x
+            0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // y
+        ],
+    );
+}
+
+#[test]
+fn it_round_trips_unbounded_ranges() {
+    assert_round_trip(PgRange::from(..=-5_i64));
+    assert_round_trip(PgRange::<i32>::from((Bound::Unbounded, Bound::Unbounded)));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn it_encodes_chrono_values_in_network_byte_order() {
+    use chrono::{NaiveDate, NaiveTime};
+
+    let epoch = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+
+    // days and microseconds since 2000-01-01
+    assert_bytes(epoch.succ_opt().unwrap(), &[0, 0, 0, 1]);
+    assert_bytes(
+        NaiveDate::from_ymd_opt(1999, 12, 31).unwrap(),
+        &[0xff, 0xff, 0xff, 0xff],
+    );
+    assert_bytes(
+        epoch.and_hms_micro_opt(0, 0, 0, 258).unwrap(),
+        &[0, 0, 0, 0, 0, 0, 0x01, 0x02],
+    );
+    assert_bytes(
+        NaiveTime::from_hms_micro_opt(0, 0, 0, 258).unwrap(),
+        &[0, 0, 0, 0, 0, 0, 0x01, 0x02],
+    );
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn it_encodes_time_values_in_network_byte_order() {
+    use time::macros::{date, datetime, time};
+
+    assert_bytes(date!(2000 - 01 - 02), &[0, 0, 0, 1]);
+    assert_bytes(date!(1999 - 12 - 31), &[0xff, 0xff, 0xff, 0xff]);
+    assert_bytes(
+        datetime!(2000-01-01 0:00:00.000_258),
+        &[0, 0, 0, 0, 0, 0, 0x01, 0x02],
+    );
+    assert_bytes(time!(0:00:00.000_258), &[0, 0, 0, 0, 0, 0, 0x01, 0x02]);
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn it_encodes_uuids_in_network_byte_order() {
+    let uuid = uuid::Uuid::from_u128(0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10);
+
+    assert_bytes(
+        uuid,
+        &[
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+            0x0f, 0x10,
+        ],
+    );
+}
+
+#[cfg(feature = "proptest")]
+mod properties {
+    use sqlx_core::testing::proptest::prelude::*;
+    use sqlx_core::testing::proptest::test_runner::TestRunner;
+    use sqlx_core::testing::Roundtrip;
+    use sqlx_core::types::JsonValue;
+
+    use super::*;
+
+    /// Encode the values generated by `strategy` and check they decode to themselves; a value
+    /// that fails is shrunk before it is reported.
+    #[track_caller]
+    fn assert_round_trips_with<S>(strategy: S)
+    where
+        S: Strategy,
+        S::Value: for<'q> Encode<'q, Postgres> + for<'r> Decode<'r, Postgres> + Type<Postgres>,
+        S::Value: PartialEq + Debug,
+    {
+        TestRunner::default()
+            .run(&strategy, |value| {
+                let bytes = encode(&value);
+                prop_assert_eq!(
+                    decode::<S::Value>(&bytes),
+                    value,
+                    "decoded from {:02x?}",
+                    bytes
+                );
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    #[track_caller]
+    fn assert_round_trips<T: Roundtrip<Postgres>>() {
+        assert_round_trips_with(T::strategy());
+    }
+
+    #[test]
+    fn integers_round_trip() {
+        assert_round_trips::<bool>();
+        assert_round_trips_with(any::<i8>());
+        assert_round_trips::<i16>();
+        assert_round_trips::<i32>();
+        assert_round_trips::<i64>();
+        assert_round_trips_with(any::<u32>().prop_map(Oid));
+        assert_round_trips_with(any::<i64>().prop_map(PgMoney));
+    }
+
+    #[test]
+    fn floats_round_trip() {
+        assert_round_trips::<f32>();
+        assert_round_trips::<f64>();
+        assert_round_trips_with((any::<f64>(), any::<f64>()).prop_map(|(x, y)| PgPoint { x, y }));
+
+        // compared by bits, as NaN is not equal to itself
+        TestRunner::default()
+            .run(&(any::<u32>(), any::<u64>()), |(float, double)| {
+                prop_assert_eq!(
+                    decode::<f32>(&encode(&f32::from_bits(float))).to_bits(),
+                    float
+                );
+                prop_assert_eq!(
+                    decode::<f64>(&encode(&f64::from_bits(double))).to_bits(),
+                    double
+                );
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn strings_and_bytes_round_trip() {
+        assert_round_trips::<String>();
+        assert_round_trips::<Vec<u8>>();
+        assert_round_trips::<JsonValue>();
+    }
+
+    #[test]
+    fn arrays_and_ranges_round_trip() {
+        assert_round_trips::<Vec<i32>>();
+        assert_round_trips::<Vec<Option<i64>>>();
+        assert_round_trips::<Vec<String>>();
+        assert_round_trips::<PgRange<i32>>();
+        assert_round_trips::<PgRange<i64>>();
+    }
+
+    #[test]
+    fn intervals_round_trip() {
+        assert_round_trips::<PgInterval>();
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_values_round_trip() {
+        use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+        assert_round_trips::<NaiveDate>();
+        assert_round_trips::<NaiveTime>();
+        assert_round_trips::<NaiveDateTime>();
+        assert_round_trips::<DateTime<Utc>>();
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_values_round_trip() {
+        use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+        assert_round_trips::<Date>();
+        assert_round_trips::<Time>();
+        assert_round_trips::<PrimitiveDateTime>();
+        assert_round_trips::<OffsetDateTime>();
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn uuids_round_trip() {
+        assert_round_trips::<uuid::Uuid>();
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn big_decimals_round_trip() {
+        assert_round_trips::<bigdecimal::BigDecimal>();
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn decimals_round_trip() {
+        assert_round_trips::<rust_decimal::Decimal>();
+    }
+
+    #[cfg(feature = "ipnetwork")]
+    #[test]
+    fn ip_networks_round_trip() {
+        assert_round_trips::<std::net::IpAddr>();
+        assert_round_trips::<ipnetwork::IpNetwork>();
+    }
+}
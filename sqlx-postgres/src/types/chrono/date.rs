@@ -5,6 +5,7 @@ use chrono::{NaiveDate, TimeDelta};
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
+use crate::types::infinite::{date_infinity, out_of_range};
 use crate::types::Type;
 use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 
@@ -43,17 +44,22 @@ impl<'r> Decode<'r, Postgres> for NaiveDate {
         Ok(match value.format() {
             PgValueFormat::Binary => {
                 // DATE is encoded as the days since epoch
-                let days: i32 = Decode::<Postgres>::decode(value)?;
+                let days_i32: i32 = Decode::<Postgres>::decode(value)?;
 
-                let days = TimeDelta::try_days(days.into())
+                let days = TimeDelta::try_days(days_i32.into())
                     .unwrap_or_else(|| {
-                        unreachable!("BUG: days ({days}) as `i32` multiplied into seconds should not overflow `i64`")
+                        unreachable!("BUG: days ({days_i32}) as `i32` multiplied into seconds should not overflow `i64`")
                     });
 
-                postgres_epoch_date() + days
+                postgres_epoch_date()
+                    .checked_add_signed(days)
+                    .ok_or_else(|| out_of_range("NaiveDate", date_infinity(days_i32)))?
             }
 
-            PgValueFormat::Text => NaiveDate::parse_from_str(value.as_str()?, "%Y-%m-%d")?,
+            PgValueFormat::Text => match value.as_str()? {
+                s @ ("infinity" | "-infinity") => return Err(out_of_range("NaiveDate", Some(s))),
+                s => NaiveDate::parse_from_str(s, "%Y-%m-%d")?,
+            },
         })
     }
 }
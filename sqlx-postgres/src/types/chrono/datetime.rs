@@ -1,6 +1,7 @@
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
+use crate::types::infinite::{out_of_range, timestamp_infinity};
 use crate::types::Type;
 use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 use chrono::{
@@ -53,11 +54,12 @@ impl<'r> Decode<'r, Postgres> for NaiveDateTime {
             PgValueFormat::Binary => {
                 // TIMESTAMP is encoded as the microseconds since the epoch
                 let us = Decode::<Postgres>::decode(value)?;
-                postgres_epoch_datetime() + Duration::microseconds(us)
+                from_micros(us, "NaiveDateTime")?
             }
 
             PgValueFormat::Text => {
                 let s = value.as_str()?;
+                check_text_infinity(s, "NaiveDateTime")?;
                 NaiveDateTime::parse_from_str(
                     s,
                     if s.contains('+') {
@@ -102,12 +104,13 @@ impl<'r> Decode<'r, Postgres> for DateTime<FixedOffset> {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
         Ok(match value.format() {
             PgValueFormat::Binary => {
-                let naive = <NaiveDateTime as Decode<Postgres>>::decode(value)?;
-                Utc.fix().from_utc_datetime(&naive)
+                let us = Decode::<Postgres>::decode(value)?;
+                Utc.fix().from_utc_datetime(&from_micros(us, "DateTime")?)
             }
 
             PgValueFormat::Text => {
                 let s = value.as_str()?;
+                check_text_infinity(s, "DateTime")?;
                 DateTime::parse_from_str(
                     s,
                     if s.contains('+') || s.contains('-') {
@@ -131,3 +134,17 @@ fn postgres_epoch_datetime() -> NaiveDateTime {
         .and_hms_opt(0, 0, 0)
         .expect("expected 2000-01-01T00:00:00 to be a valid NaiveDateTime")
 }
+
+// TIMESTAMP is encoded as the microseconds since the epoch
+fn from_micros(us: i64, rust_type: &str) -> Result<NaiveDateTime, BoxDynError> {
+    postgres_epoch_datetime()
+        .checked_add_signed(Duration::microseconds(us))
+        .ok_or_else(|| out_of_range(rust_type, timestamp_infinity(us)))
+}
+
+fn check_text_infinity(s: &str, rust_type: &str) -> Result<(), BoxDynError> {
+    match s {
+        "infinity" | "-infinity" => Err(out_of_range(rust_type, Some(s))),
+        _ => Ok(()),
+    }
+}
@@ -3,7 +3,7 @@ use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
 use sqlx_core::decode::Decode;
 use sqlx_core::encode::{Encode, IsNull};
 use sqlx_core::error::BoxDynError;
-use sqlx_core::types::Type;
+use sqlx_core::types::{CiString, Type};
 use std::fmt;
 use std::fmt::{Debug, Display, Formatter};
 use std::ops::Deref;
@@ -31,6 +31,8 @@ use std::str::FromStr;
 /// ### Note: `PartialEq` is Case-Sensitive
 /// This type derives `PartialEq` which forwards to the implementation on `String`, which
 /// is case-sensitive. This impl exists mainly for testing.
+/// [`CiString`](sqlx_core::types::CiString) also maps to `citext`, and compares
+/// case-insensitively without regard to locale.
 ///
 /// To properly emulate the case-insensitivity of `citext` would require use of locale-aware
 /// functions in `libc`, and even then would require querying the locale of the database server
@@ -104,3 +106,35 @@ impl Decode<'_, Postgres> for PgCiText {
         Ok(PgCiText(value.as_str()?.to_owned()))
     }
 }
+
+impl Type<Postgres> for CiString {
+    fn type_info() -> PgTypeInfo {
+        <PgCiText as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <PgCiText as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl PgHasArrayType for CiString {
+    fn array_type_info() -> PgTypeInfo {
+        PgCiText::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        PgCiText::array_compatible(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for CiString {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <&str as Encode<Postgres>>::encode(&**self, buf)
+    }
+}
+
+impl Decode<'_, Postgres> for CiString {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(CiString(value.as_str()?.to_owned()))
+    }
+}
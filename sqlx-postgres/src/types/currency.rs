@@ -0,0 +1,436 @@
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+/// An amount of money in a known currency, mapped to the PostgreSQL [`MONEY`] type.
+///
+/// `MONEY` is sent over the wire as an integer number of the smallest units of the currency of
+/// the database's `lc_monetary` locale, e.g. cents for `en_US`. While [`PgMoney`] leaves it to
+/// the application to interpret that integer, `PgCurrency` takes the number of fractional
+/// digits from the currency `C` instead, so that the amount `A` can be any of:
+///
+/// * `i64`: the amount in minor units, e.g. cents, as sent over the wire.
+/// * [`rust_decimal::Decimal`], with the `rust_decimal` feature.
+/// * [`bigdecimal::BigDecimal`], with the `bigdecimal` feature.
+///
+/// ```rust
+/// # #[cfg(feature = "rust_decimal")]
+/// # {
+/// use rust_decimal::Decimal;
+/// use sqlx::postgres::types::{iso, PgCurrency};
+///
+/// let price = PgCurrency::<Decimal, iso::Usd>::new(Decimal::new(1999, 2));
+///
+/// assert_eq!(price.code(), "USD");
+/// assert_eq!(price.amount.to_string(), "19.99");
+/// # }
+/// ```
+///
+/// Encoding an amount with more fractional digits than the currency has is an error, instead of
+/// rounding it.
+///
+/// With the `rusty_money` feature, a `PgCurrency` can be converted to and from a
+/// `rusty_money::Money` with [`TryFrom`]; this fails if the currencies don't match, or under the
+/// same conditions as encoding.
+///
+/// Unlike [`PgMoney`], values can also be decoded from the text format, which Postgres formats
+/// according to `lc_monetary` (e.g. `-$1,234.56`): all but the digits and the sign are ignored.
+///
+/// ### Note: `lc_monetary`
+/// The same `MONEY` column holds amounts in whichever currency `lc_monetary` is set to, so `C`
+/// has to agree with it: a database with `lc_monetary = 'ja_JP'` stores whole yen, which would
+/// be decoded as hundredths of a dollar by a `PgCurrency<_, iso::Usd>`.
+///
+/// To store amounts in several currencies, use a `NUMERIC` column with a separate column for the
+/// currency code instead.
+///
+/// [`MONEY`]: https://www.postgresql.org/docs/current/datatype-money.html
+/// [`PgMoney`]: crate::types::PgMoney
+pub struct PgCurrency<A, C> {
+    /// The amount of money, in major units unless `A` is `i64`.
+    pub amount: A,
+    currency: PhantomData<fn() -> C>,
+}
+
+/// A currency, identified by its [ISO 4217] code.
+///
+/// Common currencies are defined in [`iso`]; others can be added by implementing this on a
+/// marker type.
+///
+/// [ISO 4217]: https://www.iso.org/iso-4217-currency-codes.html
+pub trait PgCurrencyCode {
+    /// The three-letter code of the currency, e.g. `USD`.
+    const CODE: &'static str;
+
+    /// The number of digits after the decimal separator, e.g. 2 for cents.
+    const MINOR_UNITS: u32;
+}
+
+/// An amount which can be converted to and from an integer number of minor units, for use
+/// with [`PgCurrency`].
+pub trait PgMoneyAmount: Sized {
+    /// Convert the amount to minor units, failing if it has more than `minor_units` fractional
+    /// digits or doesn't fit in an `i64`.
+    fn to_minor_units(&self, minor_units: u32) -> Result<i64, BoxDynError>;
+
+    /// Convert an amount in minor units.
+    fn from_minor_units(value: i64, minor_units: u32) -> Self;
+}
+
+impl<A, C: PgCurrencyCode> PgCurrency<A, C> {
+    /// Wrap an amount in the currency `C`.
+    pub fn new(amount: A) -> Self {
+        Self {
+            amount,
+            currency: PhantomData,
+        }
+    }
+
+    /// The ISO 4217 code of the currency.
+    pub fn code(&self) -> &'static str {
+        C::CODE
+    }
+
+    /// Extract the amount.
+    pub fn into_inner(self) -> A {
+        self.amount
+    }
+}
+
+impl<A: Clone, C> Clone for PgCurrency<A, C> {
+    fn clone(&self) -> Self {
+        Self {
+            amount: self.amount.clone(),
+            currency: PhantomData,
+        }
+    }
+}
+
+impl<A: Copy, C> Copy for PgCurrency<A, C> {}
+
+impl<A: Default, C> Default for PgCurrency<A, C> {
+    fn default() -> Self {
+        Self {
+            amount: A::default(),
+            currency: PhantomData,
+        }
+    }
+}
+
+impl<A: PartialEq, C> PartialEq for PgCurrency<A, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.amount == other.amount
+    }
+}
+
+impl<A: Eq, C> Eq for PgCurrency<A, C> {}
+
+impl<A: Debug, C: PgCurrencyCode> Debug for PgCurrency<A, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PgCurrency")
+            .field(&self.amount)
+            .field(&C::CODE)
+            .finish()
+    }
+}
+
+impl<A, C> Type<Postgres> for PgCurrency<A, C> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::MONEY
+    }
+}
+
+impl<A, C> PgHasArrayType for PgCurrency<A, C> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::MONEY_ARRAY
+    }
+}
+
+impl<A: PgMoneyAmount, C: PgCurrencyCode> Encode<'_, Postgres> for PgCurrency<A, C> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let value = self.amount.to_minor_units(C::MINOR_UNITS)?;
+
+        buf.extend(&value.to_be_bytes());
+
+        Ok(IsNull::No)
+    }
+}
+
+impl<A: PgMoneyAmount, C: PgCurrencyCode> Decode<'_, Postgres> for PgCurrency<A, C> {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        let minor_units = match value.format() {
+            PgValueFormat::Binary => BigEndian::read_i64(value.as_bytes()?),
+            PgValueFormat::Text => parse_minor_units(value.as_str()?)?,
+        };
+
+        Ok(Self::new(A::from_minor_units(minor_units, C::MINOR_UNITS)))
+    }
+}
+
+// `cash_out()` always prints every fractional digit of the locale, so the digits alone are the
+// value in minor units; negative values are prefixed with a sign or wrapped in parentheses.
+fn parse_minor_units(s: &str) -> Result<i64, BoxDynError> {
+    let negative = s.contains('-') || s.starts_with('(');
+
+    let mut value: i64 = 0;
+    let mut has_digits = false;
+
+    for digit in s.chars().filter_map(|c| c.to_digit(10)) {
+        value = value
+            .checked_mul(10)
+            .and_then(|value| value.checked_sub(i64::from(digit)))
+            .ok_or_else(|| format!("MONEY value out of range: {s:?}"))?;
+        has_digits = true;
+    }
+
+    if !has_digits {
+        return Err(format!("invalid MONEY value: {s:?}").into());
+    }
+
+    // accumulated as a negative number so that `i64::MIN` doesn't overflow
+    if negative {
+        Ok(value)
+    } else {
+        value
+            .checked_neg()
+            .ok_or_else(|| format!("MONEY value out of range: {s:?}").into())
+    }
+}
+
+impl PgMoneyAmount for i64 {
+    fn to_minor_units(&self, _minor_units: u32) -> Result<i64, BoxDynError> {
+        Ok(*self)
+    }
+
+    fn from_minor_units(value: i64, _minor_units: u32) -> Self {
+        value
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl PgMoneyAmount for rust_decimal::Decimal {
+    fn to_minor_units(&self, minor_units: u32) -> Result<i64, BoxDynError> {
+        if self.normalize().scale() > minor_units {
+            return Err(format!("{self} has more than {minor_units} fractional digits").into());
+        }
+
+        let mut scaled = *self;
+        scaled.rescale(minor_units);
+
+        i64::try_from(scaled.mantissa())
+            .map_err(|_| format!("{self} is out of range for MONEY").into())
+    }
+
+    fn from_minor_units(value: i64, minor_units: u32) -> Self {
+        rust_decimal::Decimal::new(value, minor_units)
+    }
+}
+
+#[cfg(feature = "bigdecimal")]
+impl PgMoneyAmount for bigdecimal::BigDecimal {
+    fn to_minor_units(&self, minor_units: u32) -> Result<i64, BoxDynError> {
+        let (_, scale) = self.normalized().as_bigint_and_exponent();
+
+        if scale > i64::from(minor_units) {
+            return Err(format!("{self} has more than {minor_units} fractional digits").into());
+        }
+
+        let (digits, _) = self
+            .with_scale(i64::from(minor_units))
+            .into_bigint_and_exponent();
+
+        i64::try_from(digits).map_err(|_| format!("{self} is out of range for MONEY").into())
+    }
+
+    fn from_minor_units(value: i64, minor_units: u32) -> Self {
+        bigdecimal::BigDecimal::new(num_bigint::BigInt::from(value), i64::from(minor_units))
+    }
+}
+
+#[cfg(feature = "rusty_money")]
+impl<A, C> TryFrom<PgCurrency<A, C>> for rusty_money::Money<'static, rusty_money::iso::Currency>
+where
+    A: PgMoneyAmount,
+    C: PgCurrencyCode,
+{
+    type Error = BoxDynError;
+
+    fn try_from(value: PgCurrency<A, C>) -> Result<Self, BoxDynError> {
+        let currency = rusty_money::iso::find(C::CODE)
+            .ok_or_else(|| format!("unknown ISO 4217 currency: {}", C::CODE))?;
+
+        let minor_units = value.amount.to_minor_units(C::MINOR_UNITS)?;
+
+        Ok(Self::from_decimal(
+            rust_decimal::Decimal::new(minor_units, C::MINOR_UNITS),
+            currency,
+        ))
+    }
+}
+
+#[cfg(feature = "rusty_money")]
+impl<A, C, T> TryFrom<rusty_money::Money<'_, T>> for PgCurrency<A, C>
+where
+    A: PgMoneyAmount,
+    C: PgCurrencyCode,
+    T: rusty_money::FormattableCurrency,
+{
+    type Error = BoxDynError;
+
+    fn try_from(value: rusty_money::Money<'_, T>) -> Result<Self, BoxDynError> {
+        let code = value.currency().code();
+
+        if code != C::CODE {
+            return Err(format!("expected an amount in {}, got {code}", C::CODE).into());
+        }
+
+        let minor_units = value.amount().to_minor_units(C::MINOR_UNITS)?;
+
+        Ok(Self::new(A::from_minor_units(minor_units, C::MINOR_UNITS)))
+    }
+}
+
+/// Common currencies, for use with [`PgCurrency`].
+pub mod iso {
+    use super::PgCurrencyCode;
+
+    macro_rules! currencies {
+        ($($(#[$meta:meta])* $name:ident = $code:literal, $minor_units:literal;)*) => {
+            $(
+                $(#[$meta])*
+                #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+                pub struct $name;
+
+                impl PgCurrencyCode for $name {
+                    const CODE: &'static str = $code;
+                    const MINOR_UNITS: u32 = $minor_units;
+                }
+            )*
+        };
+    }
+
+    currencies! {
+        /// Australian dollar.
+        Aud = "AUD", 2;
+        /// Brazilian real.
+        Brl = "BRL", 2;
+        /// Canadian dollar.
+        Cad = "CAD", 2;
+        /// Swiss franc.
+        Chf = "CHF", 2;
+        /// Chinese yuan.
+        Cny = "CNY", 2;
+        /// Danish krone.
+        Dkk = "DKK", 2;
+        /// Euro.
+        Eur = "EUR", 2;
+        /// Pound sterling.
+        Gbp = "GBP", 2;
+        /// Hong Kong dollar.
+        Hkd = "HKD", 2;
+        /// Indian rupee.
+        Inr = "INR", 2;
+        /// Japanese yen.
+        Jpy = "JPY", 0;
+        /// South Korean won.
+        Krw = "KRW", 0;
+        /// Kuwaiti dinar.
+        Kwd = "KWD", 3;
+        /// Mexican peso.
+        Mxn = "MXN", 2;
+        /// Norwegian krone.
+        Nok = "NOK", 2;
+        /// New Zealand dollar.
+        Nzd = "NZD", 2;
+        /// Polish złoty.
+        Pln = "PLN", 2;
+        /// Swedish krona.
+        Sek = "SEK", 2;
+        /// Singapore dollar.
+        Sgd = "SGD", 2;
+        /// United States dollar.
+        Usd = "USD", 2;
+        /// South African rand.
+        Zar = "ZAR", 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_formatted_money() {
+        assert_eq!(parse_minor_units("$1,234.56").unwrap(), 123456);
+        assert_eq!(parse_minor_units("-$1,234.56").unwrap(), -123456);
+        assert_eq!(parse_minor_units("($0.05)").unwrap(), -5);
+        assert_eq!(parse_minor_units("1.234,56 €").unwrap(), 123456);
+        assert_eq!(parse_minor_units("￥1,000").unwrap(), 1000);
+        assert_eq!(
+            parse_minor_units("-$92,233,720,368,547,758.08").unwrap(),
+            i64::MIN
+        );
+        assert!(parse_minor_units("$92,233,720,368,547,758.08").is_err());
+        assert!(parse_minor_units("$").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "rust_decimal")]
+    fn converts_decimal() {
+        use rust_decimal::Decimal;
+
+        assert_eq!(Decimal::new(1999, 2).to_minor_units(2).unwrap(), 1999);
+        assert_eq!(Decimal::new(19, 0).to_minor_units(2).unwrap(), 1900);
+        assert_eq!(Decimal::new(19990, 3).to_minor_units(2).unwrap(), 1999);
+        assert!(Decimal::new(19999, 3).to_minor_units(2).is_err());
+        assert_eq!(Decimal::from_minor_units(-1999, 2), Decimal::new(-1999, 2));
+    }
+
+    #[test]
+    #[cfg(feature = "bigdecimal")]
+    fn converts_bigdecimal() {
+        use bigdecimal::BigDecimal;
+        use std::str::FromStr;
+
+        let amount = BigDecimal::from_str("19.990").unwrap();
+        assert_eq!(amount.to_minor_units(2).unwrap(), 1999);
+        assert_eq!(amount.to_minor_units(0).ok(), None);
+        assert_eq!(
+            BigDecimal::from_minor_units(1999, 3),
+            BigDecimal::from_str("1.999").unwrap()
+        );
+        assert!(BigDecimal::from_str("1e30")
+            .unwrap()
+            .to_minor_units(2)
+            .is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "rusty_money")]
+    fn converts_rusty_money() {
+        use rust_decimal::Decimal;
+        use rusty_money::{iso, Money};
+
+        let price = PgCurrency::<i64, super::iso::Usd>::new(1999);
+        let money = Money::try_from(price).unwrap();
+        assert_eq!(money, Money::from_minor(1999, iso::USD));
+
+        let yen =
+            PgCurrency::<Decimal, super::iso::Jpy>::try_from(Money::from_major(500, iso::JPY))
+                .unwrap();
+        assert_eq!(yen.amount, Decimal::new(500, 0));
+
+        assert!(PgCurrency::<i64, super::iso::Eur>::try_from(money).is_err());
+        assert!(PgCurrency::<i64, super::iso::Usd>::try_from(
+            Money::from_str("0.001", iso::USD).unwrap()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,249 @@
+use crate::error::BoxDynError;
+
+/// A `TIMESTAMP`, `TIMESTAMPTZ` or `DATE` which may also be one of the special values
+/// `infinity` or `-infinity`.
+///
+/// Postgres orders `infinity` after and `-infinity` before every other value, which makes them
+/// convenient for open-ended validity periods, e.g. `valid_until = 'infinity'`. The date and time
+/// types of `chrono` and `time` cannot represent them, so decoding them as such is an error;
+/// decode them as `PgInfinite<T>` instead, and choose how to handle them:
+///
+/// * Match on the variants, to treat the infinities as typed sentinels.
+/// * [`finite()`][Self::finite], to map them to `None`.
+/// * [`saturating()`][Self::saturating], to map them to the smallest or largest value of `T`.
+///
+/// ```rust,ignore
+/// let valid_until: PgInfinite<DateTime<Utc>> =
+///     sqlx::query_scalar("SELECT valid_until FROM subscriptions WHERE id = $1")
+///         .bind(id)
+///         .fetch_one(&mut conn)
+///         .await?;
+///
+/// let expires_at: Option<DateTime<Utc>> = valid_until.finite();
+/// ```
+///
+/// `PgInfinite<T>` is implemented for `TIMESTAMP`, `TIMESTAMPTZ` and `DATE` types of `chrono`
+/// and `time`, and encodes the infinities as well.
+///
+/// Infinite `NUMERIC` values (Postgres 14 and later) cannot be represented by `BigDecimal` or
+/// `Decimal`, so decoding them is an error, like `NaN`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PgInfinite<T> {
+    /// `-infinity`, before every other value.
+    NegativeInfinity,
+    /// Any other value.
+    Finite(T),
+    /// `infinity`, after every other value.
+    Infinity,
+}
+
+/// The smallest and largest values of a type, which [`PgInfinite::saturating()`] maps
+/// `-infinity` and `infinity` to.
+pub trait PgInfiniteBounds: Sized {
+    /// The value `-infinity` saturates to.
+    const MIN: Self;
+    /// The value `infinity` saturates to.
+    const MAX: Self;
+}
+
+impl<T> PgInfinite<T> {
+    /// Returns the finite value, or `None` for `infinity` and `-infinity`.
+    pub fn finite(self) -> Option<T> {
+        match self {
+            Self::Finite(value) => Some(value),
+            Self::NegativeInfinity | Self::Infinity => None,
+        }
+    }
+
+    /// Returns `true` unless the value is `infinity` or `-infinity`.
+    pub fn is_finite(&self) -> bool {
+        matches!(self, Self::Finite(_))
+    }
+
+    /// Returns the finite value, or [`T::MIN`] for `-infinity` and [`T::MAX`] for `infinity`.
+    ///
+    /// [`T::MIN`]: PgInfiniteBounds::MIN
+    /// [`T::MAX`]: PgInfiniteBounds::MAX
+    pub fn saturating(self) -> T
+    where
+        T: PgInfiniteBounds,
+    {
+        match self {
+            Self::NegativeInfinity => T::MIN,
+            Self::Finite(value) => value,
+            Self::Infinity => T::MAX,
+        }
+    }
+
+    /// Maps the finite value with `f`, keeping the infinities.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> PgInfinite<U> {
+        match self {
+            Self::NegativeInfinity => PgInfinite::NegativeInfinity,
+            Self::Finite(value) => PgInfinite::Finite(f(value)),
+            Self::Infinity => PgInfinite::Infinity,
+        }
+    }
+}
+
+impl<T> From<T> for PgInfinite<T> {
+    fn from(value: T) -> Self {
+        Self::Finite(value)
+    }
+}
+
+// `infinity` and `-infinity` are sent as the largest and smallest value of the integer a
+// `TIMESTAMP` (microseconds) or `DATE` (days) is sent as.
+
+pub(crate) fn timestamp_infinity(micros: i64) -> Option<&'static str> {
+    match micros {
+        i64::MAX => Some("infinity"),
+        i64::MIN => Some("-infinity"),
+        _ => None,
+    }
+}
+
+pub(crate) fn date_infinity(days: i32) -> Option<&'static str> {
+    match days {
+        i32::MAX => Some("infinity"),
+        i32::MIN => Some("-infinity"),
+        _ => None,
+    }
+}
+
+/// The error for a `TIMESTAMP` or `DATE` which can't be represented by `rust_type`.
+pub(crate) fn out_of_range(rust_type: &str, infinity: Option<&str>) -> BoxDynError {
+    match infinity {
+        Some(infinity) => format!(
+            "`{infinity}` cannot be decoded as `{rust_type}`; \
+             decode it as `PgInfinite<{rust_type}>` instead"
+        )
+        .into(),
+        None => format!("value out of range for `{rust_type}`").into(),
+    }
+}
+
+macro_rules! impl_infinite {
+    ($ty:ty, $raw:ty) => {
+        impl crate::types::Type<crate::Postgres> for crate::types::PgInfinite<$ty> {
+            fn type_info() -> crate::PgTypeInfo {
+                <$ty as crate::types::Type<crate::Postgres>>::type_info()
+            }
+
+            fn compatible(ty: &crate::PgTypeInfo) -> bool {
+                <$ty as crate::types::Type<crate::Postgres>>::compatible(ty)
+            }
+        }
+
+        impl crate::types::PgHasArrayType for crate::types::PgInfinite<$ty> {
+            fn array_type_info() -> crate::PgTypeInfo {
+                <$ty as crate::types::PgHasArrayType>::array_type_info()
+            }
+        }
+
+        impl crate::encode::Encode<'_, crate::Postgres> for crate::types::PgInfinite<$ty> {
+            fn encode_by_ref(
+                &self,
+                buf: &mut crate::PgArgumentBuffer,
+            ) -> Result<crate::encode::IsNull, crate::error::BoxDynError> {
+                match self {
+                    Self::NegativeInfinity => {
+                        crate::encode::Encode::<crate::Postgres>::encode(<$raw>::MIN, buf)
+                    }
+                    Self::Finite(value) => {
+                        crate::encode::Encode::<crate::Postgres>::encode_by_ref(value, buf)
+                    }
+                    Self::Infinity => {
+                        crate::encode::Encode::<crate::Postgres>::encode(<$raw>::MAX, buf)
+                    }
+                }
+            }
+
+            fn size_hint(&self) -> usize {
+                std::mem::size_of::<$raw>()
+            }
+        }
+
+        impl<'r> crate::decode::Decode<'r, crate::Postgres> for crate::types::PgInfinite<$ty> {
+            fn decode(value: crate::PgValueRef<'r>) -> Result<Self, crate::error::BoxDynError> {
+                let raw = match value.format() {
+                    crate::PgValueFormat::Binary => {
+                        let bytes = value.as_bytes()?;
+                        let bytes = bytes.try_into().map_err(|_| {
+                            format!(
+                                "expected {} bytes, got {}",
+                                std::mem::size_of::<$raw>(),
+                                bytes.len()
+                            )
+                        })?;
+                        <$raw>::from_be_bytes(bytes)
+                    }
+                    crate::PgValueFormat::Text => match value.as_str()? {
+                        "infinity" => <$raw>::MAX,
+                        "-infinity" => <$raw>::MIN,
+                        _ => 0,
+                    },
+                };
+
+                Ok(match raw {
+                    <$raw>::MAX => Self::Infinity,
+                    <$raw>::MIN => Self::NegativeInfinity,
+                    _ => Self::Finite(crate::decode::Decode::<crate::Postgres>::decode(value)?),
+                })
+            }
+        }
+    };
+}
+
+#[cfg(feature = "chrono")]
+mod chrono {
+    use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, Utc};
+
+    use super::PgInfiniteBounds;
+
+    impl_infinite!(NaiveDateTime, i64);
+    impl_infinite!(DateTime<Utc>, i64);
+    impl_infinite!(DateTime<FixedOffset>, i64);
+    impl_infinite!(DateTime<Local>, i64);
+    impl_infinite!(NaiveDate, i32);
+
+    impl PgInfiniteBounds for NaiveDateTime {
+        const MIN: Self = NaiveDateTime::MIN;
+        const MAX: Self = NaiveDateTime::MAX;
+    }
+
+    impl PgInfiniteBounds for DateTime<Utc> {
+        const MIN: Self = DateTime::<Utc>::MIN_UTC;
+        const MAX: Self = DateTime::<Utc>::MAX_UTC;
+    }
+
+    impl PgInfiniteBounds for NaiveDate {
+        const MIN: Self = NaiveDate::MIN;
+        const MAX: Self = NaiveDate::MAX;
+    }
+}
+
+#[cfg(feature = "time")]
+mod time {
+    use time::{Date, OffsetDateTime, PrimitiveDateTime};
+
+    use super::PgInfiniteBounds;
+
+    impl_infinite!(PrimitiveDateTime, i64);
+    impl_infinite!(OffsetDateTime, i64);
+    impl_infinite!(Date, i32);
+
+    impl PgInfiniteBounds for PrimitiveDateTime {
+        const MIN: Self = PrimitiveDateTime::MIN;
+        const MAX: Self = PrimitiveDateTime::MAX;
+    }
+
+    impl PgInfiniteBounds for OffsetDateTime {
+        const MIN: Self = PrimitiveDateTime::MIN.assume_utc();
+        const MAX: Self = PrimitiveDateTime::MAX.assume_utc();
+    }
+
+    impl PgInfiniteBounds for Date {
+        const MIN: Self = Date::MIN;
+        const MAX: Self = Date::MAX;
+    }
+}
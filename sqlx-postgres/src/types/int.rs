@@ -3,7 +3,7 @@ use std::num::{NonZeroI16, NonZeroI32, NonZeroI64};
 
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
-use crate::error::BoxDynError;
+use crate::error::{BoxDynError, IntegerOutOfRangeError};
 use crate::types::Type;
 use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 
@@ -57,7 +57,7 @@ impl Decode<'_, Postgres> for i8 {
         // note: decoding here is for the `"char"` type as Postgres does not have a native 1-byte integer type.
         // https://github.com/postgres/postgres/blob/master/src/backend/utils/adt/char.c#L58-L60
         match value.format() {
-            PgValueFormat::Binary => int_decode(value)?.try_into().map_err(Into::into),
+            PgValueFormat::Binary => Ok(IntegerOutOfRangeError::convert(int_decode(value)?)?),
             PgValueFormat::Text => {
                 let text = value.as_str()?;
 
@@ -101,7 +101,7 @@ impl Encode<'_, Postgres> for i16 {
 
 impl Decode<'_, Postgres> for i16 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)?.try_into().map_err(Into::into)
+        Ok(IntegerOutOfRangeError::convert(int_decode(value)?)?)
     }
 }
 
@@ -127,7 +127,7 @@ impl Encode<'_, Postgres> for i32 {
 
 impl Decode<'_, Postgres> for i32 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)?.try_into().map_err(Into::into)
+        Ok(IntegerOutOfRangeError::convert(int_decode(value)?)?)
     }
 }
 
@@ -1,4 +1,5 @@
 use std::mem;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
 
 use byteorder::{NetworkEndian, ReadBytesExt};
 
@@ -67,6 +68,90 @@ impl Encode<'_, Postgres> for PgInterval {
     }
 }
 
+impl PgInterval {
+    /// Add two intervals field by field, like `+` on `INTERVAL` in Postgres.
+    ///
+    /// Returns `None` if any field overflows.
+    pub fn checked_add(self, rhs: PgInterval) -> Option<PgInterval> {
+        Some(PgInterval {
+            months: self.months.checked_add(rhs.months)?,
+            days: self.days.checked_add(rhs.days)?,
+            microseconds: self.microseconds.checked_add(rhs.microseconds)?,
+        })
+    }
+
+    /// Subtract two intervals field by field, like `-` on `INTERVAL` in Postgres.
+    ///
+    /// Returns `None` if any field overflows.
+    pub fn checked_sub(self, rhs: PgInterval) -> Option<PgInterval> {
+        Some(PgInterval {
+            months: self.months.checked_sub(rhs.months)?,
+            days: self.days.checked_sub(rhs.days)?,
+            microseconds: self.microseconds.checked_sub(rhs.microseconds)?,
+        })
+    }
+
+    /// Negate each field of the interval.
+    ///
+    /// Returns `None` if any field overflows.
+    pub fn checked_neg(self) -> Option<PgInterval> {
+        Some(PgInterval {
+            months: self.months.checked_neg()?,
+            days: self.days.checked_neg()?,
+            microseconds: self.microseconds.checked_neg()?,
+        })
+    }
+}
+
+impl Add for PgInterval {
+    type Output = PgInterval;
+
+    /// Adds two intervals field by field.
+    ///
+    /// # Panics
+    /// Panics if any field overflows.
+    fn add(self, rhs: PgInterval) -> Self::Output {
+        self.checked_add(rhs).expect("overflow adding intervals")
+    }
+}
+
+impl AddAssign for PgInterval {
+    fn add_assign(&mut self, rhs: PgInterval) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for PgInterval {
+    type Output = PgInterval;
+
+    /// Subtracts two intervals field by field.
+    ///
+    /// # Panics
+    /// Panics if any field overflows.
+    fn sub(self, rhs: PgInterval) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("overflow subtracting intervals")
+    }
+}
+
+impl SubAssign for PgInterval {
+    fn sub_assign(&mut self, rhs: PgInterval) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for PgInterval {
+    type Output = PgInterval;
+
+    /// Negates each field of the interval.
+    ///
+    /// # Panics
+    /// Panics if any field is at its minimum value.
+    fn neg(self) -> Self::Output {
+        self.checked_neg().expect("overflow negating interval")
+    }
+}
+
 // We then implement Encode + Type for std Duration, chrono Duration, and time Duration
 // This is to enable ease-of-use for encoding when its simple
 
@@ -92,6 +177,38 @@ impl Encode<'_, Postgres> for std::time::Duration {
     }
 }
 
+impl Decode<'_, Postgres> for std::time::Duration {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        PgInterval::decode(value)?.try_into()
+    }
+}
+
+impl TryFrom<PgInterval> for std::time::Duration {
+    type Error = BoxDynError;
+
+    /// Convert a `PgInterval` to a `std::time::Duration`, taking a day to be 24 hours.
+    ///
+    /// This returns an error if the interval has a number of months, whose length varies,
+    /// or if it is negative.
+    fn try_from(value: PgInterval) -> Result<Self, BoxDynError> {
+        if value.months != 0 {
+            return Err(format!(
+                "`INTERVAL` with {} month(s) cannot be converted to a `std::time::Duration`",
+                value.months
+            )
+            .into());
+        }
+
+        let microseconds = i128::from(value.days) * 86_400_000_000 + i128::from(value.microseconds);
+
+        let microseconds = u64::try_from(microseconds).map_err(|_| {
+            format!("negative `INTERVAL` cannot be converted to a `std::time::Duration`: {value:?}")
+        })?;
+
+        Ok(std::time::Duration::from_micros(microseconds))
+    }
+}
+
 impl TryFrom<std::time::Duration> for PgInterval {
     type Error = BoxDynError;
 
@@ -330,6 +447,66 @@ fn test_pginterval_std() {
     assert!(PgInterval::try_from(std::time::Duration::from_secs(20_000_000_000_000)).is_err());
 }
 
+#[test]
+fn test_pginterval_to_std() {
+    let interval = PgInterval {
+        months: 0,
+        days: 1,
+        microseconds: -3_600_000_000,
+    };
+    assert_eq!(
+        std::time::Duration::try_from(interval).unwrap(),
+        std::time::Duration::from_secs(23 * 3600)
+    );
+
+    // Months don't have a fixed length
+    assert!(std::time::Duration::try_from(PgInterval {
+        months: 1,
+        days: 0,
+        microseconds: 0,
+    })
+    .is_err());
+
+    assert!(std::time::Duration::try_from(PgInterval {
+        months: 0,
+        days: 0,
+        microseconds: -1,
+    })
+    .is_err());
+}
+
+#[test]
+fn test_pginterval_arithmetic() {
+    let a = PgInterval {
+        months: 1,
+        days: 2,
+        microseconds: 3,
+    };
+    let b = PgInterval {
+        months: 4,
+        days: -5,
+        microseconds: 6,
+    };
+
+    assert_eq!(
+        a + b,
+        PgInterval {
+            months: 5,
+            days: -3,
+            microseconds: 9,
+        }
+    );
+    assert_eq!(a + b - b, a);
+    assert_eq!(-a + a, PgInterval::default());
+
+    let max = PgInterval {
+        months: i32::MAX,
+        days: 0,
+        microseconds: 0,
+    };
+    assert_eq!(max.checked_add(a), None);
+}
+
 #[test]
 #[cfg(feature = "chrono")]
 fn test_pginterval_chrono() {
@@ -15,11 +15,14 @@
 //! | `&[u8]`, `Vec<u8>`                    | BYTEA                                                |
 //! | `()`                                  | VOID                                                 |
 //! | [`PgInterval`]                        | INTERVAL                                             |
+//! | `std::time::Duration`                 | INTERVAL<sup>3</sup>                                 |
 //! | [`PgRange<T>`](PgRange)               | INT8RANGE, INT4RANGE, TSRANGE, TSTZRANGE, DATERANGE, NUMRANGE |
 //! | [`PgMoney`]                           | MONEY                                                |
+//! | [`PgCurrency<A, C>`](PgCurrency)      | MONEY                                                |
 //! | [`PgLTree`]                           | LTREE                                                |
 //! | [`PgLQuery`]                          | LQUERY                                               |
 //! | [`PgCiText`]                          | CITEXT<sup>1</sup>                                   |
+//! | `sqlx::types::CiString`               | CITEXT<sup>1</sup>                                   |
 //! | [`PgCube`]                            | CUBE                                                 |
 //! | [`PgPoint`]                           | POINT                                                |
 //! | [`PgLine`]                            | LINE                                                 |
@@ -29,10 +32,20 @@
 //! | [`PgPolygon`]                         | POLYGON                                              |
 //! | [`PgCircle`]                          | CIRCLE                                               |
 //! | [`PgHstore`]                          | HSTORE                                               |
+//! | [`PgTsVector`]                        | TSVECTOR                                             |
+//! | [`PgTsQuery`]                         | TSQUERY                                              |
+//! | `sqlx::types::Vector`                 | VECTOR<sup>2</sup>                                   |
 //!
 //! <sup>1</sup> SQLx generally considers `CITEXT` to be compatible with `String`, `&str`, etc.,
 //! but this wrapper type is available for edge cases, such as `CITEXT[]` which Postgres
-//! does not consider to be compatible with `TEXT[]`.
+//! does not consider to be compatible with `TEXT[]`. Unlike `PgCiText`, `CiString` also
+//! compares case-insensitively in Rust.
+//!
+//! <sup>2</sup> Requires the [`pgvector`](https://github.com/pgvector/pgvector) extension.
+//!
+//! <sup>3</sup> `INTERVAL` has microsecond precision, so encoding a `Duration` with nanoseconds
+//! is an error rather than rounding it. Decoding takes a day to be 24 hours, and returns an error
+//! for intervals which are negative or have a number of months, as their length varies.
 //!
 //! ### [`bigdecimal`](https://crates.io/crates/bigdecimal)
 //! Requires the `bigdecimal` Cargo feature flag.
@@ -52,6 +65,11 @@
 //!
 #![doc=include_str!("rust_decimal-range.md")]
 //!
+//! ### [`rusty_money`](https://crates.io/crates/rusty-money)
+//! Requires the `rusty_money` Cargo feature flag.
+//!
+//! [`PgCurrency`] can be converted to and from `rusty_money::Money` with `TryFrom`.
+//!
 //! ### [`chrono`](https://crates.io/crates/chrono)
 //!
 //! Requires the `chrono` Cargo feature flag.
@@ -64,6 +82,8 @@
 //! | `chrono::NaiveDate`                   | DATE                                                 |
 //! | `chrono::NaiveTime`                   | TIME                                                 |
 //! | [`PgTimeTz`]                          | TIMETZ                                               |
+//! | [`PgInfinite<T>`](PgInfinite)         | TIMESTAMPTZ, TIMESTAMP, DATE                         |
+//! | [`PgUtc<NaiveDateTime>`](PgUtc)       | TIMESTAMPTZ                                          |
 //!
 //! ### [`time`](https://crates.io/crates/time)
 //!
@@ -76,6 +96,13 @@
 //! | `time::Date`                          | DATE                                                 |
 //! | `time::Time`                          | TIME                                                 |
 //! | [`PgTimeTz`]                          | TIMETZ                                               |
+//! | [`PgInfinite<T>`](PgInfinite)         | TIMESTAMP, TIMESTAMPTZ, DATE                         |
+//! | [`PgUtc<PrimitiveDateTime>`](PgUtc)   | TIMESTAMPTZ                                          |
+//!
+//! The special values `infinity` and `-infinity` of `TIMESTAMP`, `TIMESTAMPTZ` and `DATE`
+//! cannot be represented by the `chrono` or `time` types above, so decoding them returns an
+//! error; decode them as [`PgInfinite<T>`](PgInfinite) to map them to `None`, to the bounds of
+//! `T`, or to sentinels.
 //!
 //! ### [`uuid`](https://crates.io/crates/uuid)
 //!
@@ -206,6 +233,7 @@ mod array;
 mod bool;
 mod bytes;
 mod citext;
+mod currency;
 mod float;
 mod hstore;
 mod int;
@@ -220,11 +248,18 @@ mod range;
 mod record;
 mod str;
 mod text;
+mod tsquery;
+mod tsvector;
 mod tuple;
+mod vector;
 mod void;
 
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod infinite;
 #[cfg(any(feature = "chrono", feature = "time"))]
 mod time_tz;
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod utc;
 
 #[cfg(feature = "bigdecimal")]
 mod bigdecimal;
@@ -233,8 +268,12 @@ mod cube;
 
 mod geometry;
 
-#[cfg(any(feature = "bigdecimal", feature = "rust_decimal"))]
-mod numeric;
+// also used to write `NUMERIC` values as JSON
+#[cfg_attr(
+    not(any(feature = "bigdecimal", feature = "rust_decimal")),
+    allow(dead_code)
+)]
+pub(crate) mod numeric;
 
 #[cfg(feature = "rust_decimal")]
 mod rust_decimal;
@@ -260,9 +299,13 @@ mod mac_address;
 #[cfg(feature = "bit-vec")]
 mod bit_vec;
 
+#[cfg(test)]
+mod binary_layout;
+
 pub use array::PgHasArrayType;
 pub use citext::PgCiText;
 pub use cube::PgCube;
+pub use currency::{iso, PgCurrency, PgCurrencyCode, PgMoneyAmount};
 pub use geometry::circle::PgCircle;
 pub use geometry::line::PgLine;
 pub use geometry::line_segment::PgLSeg;
@@ -282,9 +325,15 @@ pub use ltree::PgLTreeParseError;
 pub use money::PgMoney;
 pub use oid::Oid;
 pub use range::PgRange;
+pub use tsquery::{PgTsQuery, PgTsQueryLexeme, PgTsQueryNode, PgWebSearch};
+pub use tsvector::{PgTsLexeme, PgTsParseError, PgTsPosition, PgTsVector, PgTsWeight};
 
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub use infinite::{PgInfinite, PgInfiniteBounds};
 #[cfg(any(feature = "chrono", feature = "time"))]
 pub use time_tz::PgTimeTz;
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub use utc::PgUtc;
 
 // used in derive(Type) for `struct`
 // but the interface is not considered part of the public API
@@ -53,6 +53,9 @@ pub(crate) enum PgNumeric {
 const SIGN_POS: u16 = 0x0000;
 const SIGN_NEG: u16 = 0x4000;
 const SIGN_NAN: u16 = 0xC000; // overflows i16 (C equivalent truncates from integer literal)
+                              // Postgres 14 and later
+const SIGN_PINF: u16 = 0xD000;
+const SIGN_NINF: u16 = 0xF000;
 
 /// Possible sign values for [PgNumeric].
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -114,6 +117,13 @@ impl PgNumeric {
 
         if sign == SIGN_NAN {
             Ok(PgNumeric::NotANumber)
+        } else if sign == SIGN_PINF || sign == SIGN_NINF {
+            let infinity = if sign == SIGN_PINF { "" } else { "-" };
+
+            Err(format!(
+                "`{infinity}Infinity` NUMERIC values are not supported by BigDecimal or Decimal"
+            )
+            .into())
         } else {
             let digits: Vec<_> = (0..num_digits).map(|_| buf.get_i16()).collect::<_>();
 
@@ -1,6 +1,7 @@
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
+use crate::types::infinite::{date_infinity, out_of_range};
 use crate::types::time::PG_EPOCH;
 use crate::types::Type;
 use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
@@ -40,13 +41,15 @@ impl<'r> Decode<'r, Postgres> for Date {
             PgValueFormat::Binary => {
                 // DATE is encoded as the days since epoch
                 let days: i32 = Decode::<Postgres>::decode(value)?;
-                PG_EPOCH + Duration::days(days.into())
+                PG_EPOCH
+                    .checked_add(Duration::days(days.into()))
+                    .ok_or_else(|| out_of_range("Date", date_infinity(days)))?
             }
 
-            PgValueFormat::Text => Date::parse(
-                value.as_str()?,
-                &format_description!("[year]-[month]-[day]"),
-            )?,
+            PgValueFormat::Text => match value.as_str()? {
+                s @ ("infinity" | "-infinity") => return Err(out_of_range("Date", Some(s))),
+                s => Date::parse(s, &format_description!("[year]-[month]-[day]"))?,
+            },
         })
     }
 }
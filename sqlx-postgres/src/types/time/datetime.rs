@@ -1,6 +1,7 @@
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
+use crate::types::infinite::{out_of_range, timestamp_infinity};
 use crate::types::time::PG_EPOCH;
 use crate::types::Type;
 use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
@@ -53,39 +54,58 @@ impl Encode<'_, Postgres> for PrimitiveDateTime {
 
 impl<'r> Decode<'r, Postgres> for PrimitiveDateTime {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(match value.format() {
-            PgValueFormat::Binary => {
-                // TIMESTAMP is encoded as the microseconds since the epoch
-                let us = Decode::<Postgres>::decode(value)?;
-                PG_EPOCH.midnight() + Duration::microseconds(us)
+        decode_primitive(value, "PrimitiveDateTime")
+    }
+}
+
+fn decode_primitive(
+    value: PgValueRef<'_>,
+    rust_type: &str,
+) -> Result<PrimitiveDateTime, BoxDynError> {
+    Ok(match value.format() {
+        PgValueFormat::Binary => {
+            // TIMESTAMP is encoded as the microseconds since the epoch
+            let us = Decode::<Postgres>::decode(value)?;
+            PG_EPOCH
+                .midnight()
+                .checked_add(Duration::microseconds(us))
+                .ok_or_else(|| out_of_range(rust_type, timestamp_infinity(us)))?
+        }
+
+        PgValueFormat::Text => {
+            let s = value.as_str()?;
+
+            if let "infinity" | "-infinity" = s {
+                return Err(out_of_range(rust_type, Some(s)));
             }
 
-            PgValueFormat::Text => {
-                let s = value.as_str()?;
-
-                // If there is no decimal point we need to add one.
-                let s = if s.contains('.') {
-                    Cow::Borrowed(s)
-                } else {
-                    Cow::Owned(format!("{s}.0"))
-                };
-
-                // Contains a time-zone specifier
-                // This is given for timestamptz for some reason
-                // Postgres already guarantees this to always be UTC
-                if s.contains('+') {
-                    PrimitiveDateTime::parse(&s, &format_description!("[year]-[month]-[day] [hour]:[minute]:[second].[subsecond][offset_hour]"))?
-                } else {
-                    PrimitiveDateTime::parse(
-                        &s,
-                        &format_description!(
-                            "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond]"
-                        ),
-                    )?
-                }
+            // If there is no decimal point we need to add one.
+            let s = if s.contains('.') {
+                Cow::Borrowed(s)
+            } else {
+                Cow::Owned(format!("{s}.0"))
+            };
+
+            // Contains a time-zone specifier
+            // This is given for timestamptz for some reason
+            // Postgres already guarantees this to always be UTC
+            if s.contains('+') {
+                PrimitiveDateTime::parse(
+                    &s,
+                    &format_description!(
+                        "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond][offset_hour]"
+                    ),
+                )?
+            } else {
+                PrimitiveDateTime::parse(
+                    &s,
+                    &format_description!(
+                        "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond]"
+                    ),
+                )?
             }
-        })
-    }
+        }
+    })
 }
 
 impl Encode<'_, Postgres> for OffsetDateTime {
@@ -103,6 +123,6 @@ impl Encode<'_, Postgres> for OffsetDateTime {
 
 impl<'r> Decode<'r, Postgres> for OffsetDateTime {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(<PrimitiveDateTime as Decode<Postgres>>::decode(value)?.assume_utc())
+        Ok(decode_primitive(value, "OffsetDateTime")?.assume_utc())
     }
 }
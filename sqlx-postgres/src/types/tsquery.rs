@@ -0,0 +1,689 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::{BitAnd, BitOr, Not};
+use std::str::FromStr;
+
+use sqlx_core::bytes::Buf;
+use sqlx_core::query_builder::QueryBuilder;
+use sqlx_core::query_scalar::query_scalar;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::{BoxDynError, Error};
+use crate::types::tsvector::{parse_lexeme_until, write_lexeme, PgTsParseError, PgTsWeight};
+use crate::types::Type;
+use crate::{
+    PgArgumentBuffer, PgExecutor, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
+
+const QI_VAL: u8 = 1;
+const QI_OPR: u8 = 2;
+
+const OP_NOT: u8 = 1;
+const OP_AND: u8 = 2;
+const OP_OR: u8 = 3;
+const OP_PHRASE: u8 = 4;
+
+/// A lexeme to match in a [`PgTsQuery`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PgTsQueryLexeme {
+    pub word: String,
+    /// Only match the lexeme with one of these weights; any weight if empty.
+    pub weights: Vec<PgTsWeight>,
+    /// Match any lexeme starting with `word`, written as `'word':*`.
+    pub prefix: bool,
+}
+
+/// A node of a [`PgTsQuery`].
+///
+/// Nodes can be combined with the `&`, `|` and `!` operators, like in the text format:
+///
+/// ```rust
+/// # use sqlx_postgres::types::{PgTsQuery, PgTsQueryNode};
+/// let query = PgTsQuery::from(
+///     PgTsQueryNode::lexeme("fat") & (PgTsQueryNode::lexeme("rat") | !PgTsQueryNode::prefix("cat")),
+/// );
+///
+/// assert_eq!(query.to_string(), "'fat' & ( 'rat' | !'cat':* )");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PgTsQueryNode {
+    Lexeme(PgTsQueryLexeme),
+    Not(Box<PgTsQueryNode>),
+    And(Box<PgTsQueryNode>, Box<PgTsQueryNode>),
+    Or(Box<PgTsQueryNode>, Box<PgTsQueryNode>),
+    /// `left <distance> right`: `right` follows `left` at exactly `distance` positions.
+    Phrase {
+        left: Box<PgTsQueryNode>,
+        right: Box<PgTsQueryNode>,
+        distance: u16,
+    },
+}
+
+impl PgTsQueryNode {
+    /// Match a lexeme.
+    pub fn lexeme(word: impl Into<String>) -> Self {
+        PgTsQueryNode::Lexeme(PgTsQueryLexeme {
+            word: word.into(),
+            ..PgTsQueryLexeme::default()
+        })
+    }
+
+    /// Match lexemes starting with `word`.
+    pub fn prefix(word: impl Into<String>) -> Self {
+        PgTsQueryNode::Lexeme(PgTsQueryLexeme {
+            word: word.into(),
+            prefix: true,
+            ..PgTsQueryLexeme::default()
+        })
+    }
+
+    /// Match `self` immediately followed by `next`, i.e. `self <-> next`.
+    pub fn followed_by(self, next: PgTsQueryNode) -> Self {
+        self.followed_at(next, 1)
+    }
+
+    /// Match `self` followed by `next` at exactly `distance` positions, i.e. `self <N> next`.
+    pub fn followed_at(self, next: PgTsQueryNode, distance: u16) -> Self {
+        PgTsQueryNode::Phrase {
+            left: Box::new(self),
+            right: Box::new(next),
+            distance,
+        }
+    }
+
+    fn priority(&self) -> u8 {
+        match self {
+            PgTsQueryNode::Lexeme(_) => 5,
+            PgTsQueryNode::Not(_) => 4,
+            PgTsQueryNode::Phrase { .. } => 3,
+            PgTsQueryNode::And(..) => 2,
+            PgTsQueryNode::Or(..) => 1,
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            PgTsQueryNode::Lexeme(_) => 1,
+            PgTsQueryNode::Not(node) => 1 + node.len(),
+            PgTsQueryNode::And(left, right)
+            | PgTsQueryNode::Or(left, right)
+            | PgTsQueryNode::Phrase { left, right, .. } => 1 + left.len() + right.len(),
+        }
+    }
+
+    /// Write the node as Postgres does, with as few parentheses as possible.
+    fn write(&self, f: &mut Formatter<'_>, parent: u8, right_of_phrase: bool) -> fmt::Result {
+        let (left, right, op) = match self {
+            PgTsQueryNode::Lexeme(lexeme) => {
+                write_lexeme(f, &lexeme.word)?;
+
+                if lexeme.prefix || !lexeme.weights.is_empty() {
+                    f.write_str(":")?;
+                }
+
+                if lexeme.prefix {
+                    f.write_str("*")?;
+                }
+
+                for weight in &lexeme.weights {
+                    write!(f, "{}", weight.as_char())?;
+                }
+
+                return Ok(());
+            }
+            PgTsQueryNode::Not(node) => {
+                f.write_str("!")?;
+                return node.write(f, self.priority(), false);
+            }
+            PgTsQueryNode::And(left, right) => (left, right, "&".to_owned()),
+            PgTsQueryNode::Or(left, right) => (left, right, "|".to_owned()),
+            PgTsQueryNode::Phrase {
+                left,
+                right,
+                distance: 1,
+            } => (left, right, "<->".to_owned()),
+            PgTsQueryNode::Phrase {
+                left,
+                right,
+                distance,
+            } => (left, right, format!("<{distance}>")),
+        };
+
+        let is_phrase = matches!(self, PgTsQueryNode::Phrase { .. });
+        let parenthesize = self.priority() < parent || (is_phrase && right_of_phrase);
+
+        if parenthesize {
+            f.write_str("( ")?;
+        }
+
+        left.write(f, self.priority(), false)?;
+        write!(f, " {op} ")?;
+        right.write(f, self.priority(), is_phrase)?;
+
+        if parenthesize {
+            f.write_str(" )")?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the node in the binary format: each operator, then its right operand, then its left.
+    fn encode(&self, buf: &mut PgArgumentBuffer) -> Result<(), BoxDynError> {
+        let (left, right, op) = match self {
+            PgTsQueryNode::Lexeme(lexeme) => {
+                if lexeme.word.is_empty() || lexeme.word.contains('\0') {
+                    return Err(format!("invalid lexeme in tsquery: {:?}", lexeme.word).into());
+                }
+
+                let weights = lexeme
+                    .weights
+                    .iter()
+                    .fold(0u8, |mask, weight| mask | weight_mask(*weight));
+
+                buf.push(QI_VAL);
+                buf.push(weights);
+                buf.push(u8::from(lexeme.prefix));
+                buf.extend(lexeme.word.as_bytes());
+                buf.push(0);
+
+                return Ok(());
+            }
+            PgTsQueryNode::Not(node) => {
+                buf.extend(&[QI_OPR, OP_NOT]);
+                return node.encode(buf);
+            }
+            PgTsQueryNode::And(left, right) => (left, right, OP_AND),
+            PgTsQueryNode::Or(left, right) => (left, right, OP_OR),
+            PgTsQueryNode::Phrase { left, right, .. } => (left, right, OP_PHRASE),
+        };
+
+        buf.extend(&[QI_OPR, op]);
+
+        if let PgTsQueryNode::Phrase { distance, .. } = self {
+            buf.extend(&i16::try_from(*distance)?.to_be_bytes());
+        }
+
+        right.encode(buf)?;
+        left.encode(buf)
+    }
+
+    fn decode(buf: &mut &[u8], remaining: &mut usize) -> Result<Self, BoxDynError> {
+        if *remaining == 0 {
+            return Err("tsquery operator is missing an operand".into());
+        }
+
+        *remaining -= 1;
+
+        if buf.len() < 2 {
+            return Err("tsquery is too short".into());
+        }
+
+        match buf.get_u8() {
+            QI_VAL => {
+                let weights = buf.get_u8();
+
+                if buf.is_empty() {
+                    return Err("tsquery is too short".into());
+                }
+
+                let prefix = buf.get_u8() != 0;
+
+                let end = buf
+                    .iter()
+                    .position(|&b| b == 0)
+                    .ok_or("lexeme in tsquery is not terminated")?;
+
+                let word = std::str::from_utf8(&buf[..end])?.to_owned();
+                buf.advance(end + 1);
+
+                let weights = [PgTsWeight::A, PgTsWeight::B, PgTsWeight::C, PgTsWeight::D]
+                    .into_iter()
+                    .filter(|weight| weights & weight_mask(*weight) != 0)
+                    .collect();
+
+                Ok(PgTsQueryNode::Lexeme(PgTsQueryLexeme {
+                    word,
+                    weights,
+                    prefix,
+                }))
+            }
+            QI_OPR => {
+                let op = buf.get_u8();
+
+                let distance = if op == OP_PHRASE {
+                    if buf.len() < 2 {
+                        return Err("tsquery is too short".into());
+                    }
+
+                    u16::try_from(buf.get_i16())?
+                } else {
+                    0
+                };
+
+                if op == OP_NOT {
+                    return Ok(!Self::decode(buf, remaining)?);
+                }
+
+                let right = Box::new(Self::decode(buf, remaining)?);
+                let left = Box::new(Self::decode(buf, remaining)?);
+
+                Ok(match op {
+                    OP_AND => PgTsQueryNode::And(left, right),
+                    OP_OR => PgTsQueryNode::Or(left, right),
+                    OP_PHRASE => PgTsQueryNode::Phrase {
+                        left,
+                        right,
+                        distance,
+                    },
+                    op => return Err(format!("unknown tsquery operator: {op}").into()),
+                })
+            }
+            ty => Err(format!("unknown tsquery item type: {ty}").into()),
+        }
+    }
+}
+
+fn weight_mask(weight: PgTsWeight) -> u8 {
+    match weight {
+        PgTsWeight::A => 1 << 3,
+        PgTsWeight::B => 1 << 2,
+        PgTsWeight::C => 1 << 1,
+        PgTsWeight::D => 1,
+    }
+}
+
+impl BitAnd for PgTsQueryNode {
+    type Output = PgTsQueryNode;
+
+    fn bitand(self, rhs: PgTsQueryNode) -> Self::Output {
+        PgTsQueryNode::And(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl BitOr for PgTsQueryNode {
+    type Output = PgTsQueryNode;
+
+    fn bitor(self, rhs: PgTsQueryNode) -> Self::Output {
+        PgTsQueryNode::Or(Box::new(self), Box::new(rhs))
+    }
+}
+
+impl Not for PgTsQueryNode {
+    type Output = PgTsQueryNode;
+
+    fn not(self) -> Self::Output {
+        PgTsQueryNode::Not(Box::new(self))
+    }
+}
+
+/// Container for a text search query (`tsquery`) in Postgres.
+///
+/// See <https://www.postgresql.org/docs/current/datatype-textsearch.html#DATATYPE-TSQUERY>
+///
+/// A query built from its nodes is sent to Postgres as-is, so its lexemes must already be
+/// normalized, e.g. by stemming. To search for text entered by a user, see [`PgWebSearch`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PgTsQuery {
+    root: Option<PgTsQueryNode>,
+}
+
+impl PgTsQuery {
+    /// An empty query, which matches nothing.
+    ///
+    /// Postgres returns one when a search contains only stop words, but does not accept one
+    /// as a bind parameter; encoding an empty query is an error.
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if the query has no lexemes.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// The root node of the query, unless it is empty.
+    pub fn root(&self) -> Option<&PgTsQueryNode> {
+        self.root.as_ref()
+    }
+
+    /// Take the root node of the query, unless it is empty.
+    pub fn into_root(self) -> Option<PgTsQueryNode> {
+        self.root
+    }
+}
+
+impl From<PgTsQueryNode> for PgTsQuery {
+    fn from(root: PgTsQueryNode) -> Self {
+        Self { root: Some(root) }
+    }
+}
+
+impl Display for PgTsQuery {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.root {
+            Some(root) => root.write(f, 0, false),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for PgTsQuery {
+    type Err = PgTsParseError;
+
+    /// Parse the text format, where `|` binds loosest, then `&`, then `<->` and `!`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { rest: s };
+
+        if parser.peek().is_none() {
+            return Ok(Self::empty());
+        }
+
+        let root = parser.or()?;
+
+        match parser.peek() {
+            None => Ok(root.into()),
+            Some(_) => Err(parser.error()),
+        }
+    }
+}
+
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl Parser<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.rest = self.rest.trim_start();
+        self.rest.chars().next()
+    }
+
+    fn error(&self) -> PgTsParseError {
+        PgTsParseError::InvalidQuery(self.rest.to_owned())
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.rest = &self.rest[c.len_utf8()..];
+            true
+        } else {
+            false
+        }
+    }
+
+    fn or(&mut self) -> Result<PgTsQueryNode, PgTsParseError> {
+        let mut node = self.and()?;
+
+        while self.eat('|') {
+            node = node | self.and()?;
+        }
+
+        Ok(node)
+    }
+
+    fn and(&mut self) -> Result<PgTsQueryNode, PgTsParseError> {
+        let mut node = self.phrase()?;
+
+        while self.eat('&') {
+            node = node & self.phrase()?;
+        }
+
+        Ok(node)
+    }
+
+    fn phrase(&mut self) -> Result<PgTsQueryNode, PgTsParseError> {
+        let mut node = self.unary()?;
+
+        while self.peek() == Some('<') {
+            let end = self.rest.find('>').ok_or_else(|| self.error())?;
+
+            let distance = match &self.rest[1..end] {
+                "-" => 1,
+                distance => distance.trim().parse().map_err(|_| self.error())?,
+            };
+
+            self.rest = &self.rest[end + 1..];
+            node = node.followed_at(self.unary()?, distance);
+        }
+
+        Ok(node)
+    }
+
+    fn unary(&mut self) -> Result<PgTsQueryNode, PgTsParseError> {
+        if self.eat('!') {
+            return Ok(!self.unary()?);
+        }
+
+        if self.eat('(') {
+            let node = self.or()?;
+
+            return if self.eat(')') {
+                Ok(node)
+            } else {
+                Err(self.error())
+            };
+        }
+
+        if self.peek().is_none() {
+            return Err(self.error());
+        }
+
+        let (word, rest) = parse_lexeme_until(self.rest, &['&', '|', '!', '(', ')', '<', ':'])?;
+        let mut lexeme = PgTsQueryLexeme {
+            word,
+            ..PgTsQueryLexeme::default()
+        };
+
+        self.rest = rest;
+
+        if let Some(rest) = self.rest.strip_prefix(':') {
+            let end = rest
+                .find(|c: char| c != '*' && PgTsWeight::from_char(c).is_none())
+                .unwrap_or(rest.len());
+
+            for c in rest[..end].chars() {
+                match PgTsWeight::from_char(c) {
+                    Some(weight) if !lexeme.weights.contains(&weight) => {
+                        lexeme.weights.push(weight)
+                    }
+                    Some(_) => {}
+                    None => lexeme.prefix = true,
+                }
+            }
+
+            lexeme.weights.sort();
+            self.rest = &rest[end..];
+        }
+
+        Ok(PgTsQueryNode::Lexeme(lexeme))
+    }
+}
+
+impl Type<Postgres> for PgTsQuery {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TS_QUERY
+    }
+}
+
+impl PgHasArrayType for PgTsQuery {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TS_QUERY_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgTsQuery {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        // Postgres sends empty queries in the binary format, but refuses to receive them.
+        let root = self
+            .root
+            .as_ref()
+            .ok_or("an empty tsquery cannot be sent to Postgres")?;
+
+        buf.extend(&i32::try_from(root.len())?.to_be_bytes());
+        root.encode(buf)?;
+
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for PgTsQuery {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => {
+                let mut buf = value.as_bytes()?;
+
+                if buf.len() < 4 {
+                    return Err("tsquery is too short".into());
+                }
+
+                let mut remaining = usize::try_from(buf.get_i32())?;
+
+                if remaining == 0 {
+                    return Ok(Self::empty());
+                }
+
+                let root = PgTsQueryNode::decode(&mut buf, &mut remaining)?;
+
+                if remaining != 0 {
+                    return Err("tsquery has operands without an operator".into());
+                }
+
+                Ok(root.into())
+            }
+            PgValueFormat::Text => Ok(Self::from_str(value.as_str()?)?),
+        }
+    }
+}
+
+/// Text entered by a user, to be converted to a `tsquery` by `websearch_to_tsquery()`.
+///
+/// The text is always bound as a parameter, and `websearch_to_tsquery()` accepts any input
+/// without raising syntax errors: unquoted words must all match, `"quoted text"` matches
+/// a phrase, `or` matches either side and `-` excludes a word.
+///
+/// Requires Postgres 11 or newer.
+///
+/// ```rust,no_run
+/// # async fn example(pool: &sqlx::PgPool) -> sqlx::Result<()> {
+/// use sqlx::postgres::types::PgWebSearch;
+/// use sqlx::QueryBuilder;
+///
+/// let search = PgWebSearch::new(r#""fat rat" or cat -dog"#).config("english");
+///
+/// let mut query = QueryBuilder::new("SELECT title FROM post WHERE tsv @@ ");
+/// search.push_to(&mut query);
+///
+/// let titles: Vec<(String,)> = query.build_query_as().fetch_all(pool).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgWebSearch {
+    text: String,
+    config: Option<String>,
+}
+
+impl PgWebSearch {
+    /// Search for `text`, with the server's `default_text_search_config`.
+    ///
+    /// NUL characters, which Postgres does not allow in text, are removed.
+    pub fn new(text: impl Into<String>) -> Self {
+        let mut text = text.into();
+        text.retain(|c| c != '\0');
+
+        Self { text, config: None }
+    }
+
+    /// Parse and normalize the text with this text search configuration, e.g. `english`.
+    pub fn config(mut self, config: impl Into<String>) -> Self {
+        self.config = Some(config.into());
+        self
+    }
+
+    /// Push `websearch_to_tsquery(..)` to `query`, binding the configuration and the text.
+    pub fn push_to(&self, query: &mut QueryBuilder<'_, Postgres>) {
+        query.push("websearch_to_tsquery(");
+
+        if let Some(config) = &self.config {
+            query.push_bind(config.clone()).push("::regconfig, ");
+        }
+
+        query.push_bind(self.text.clone()).push(")");
+    }
+
+    /// Convert the text to a [`PgTsQuery`] on the server.
+    ///
+    /// The query can then be bound like any other value, or inspected, e.g. to tell
+    /// the user that their search only contained stop words.
+    pub async fn to_tsquery<'e, E>(&self, executor: E) -> Result<PgTsQuery, Error>
+    where
+        E: PgExecutor<'e>,
+    {
+        match &self.config {
+            Some(config) => {
+                query_scalar("SELECT websearch_to_tsquery($1::regconfig, $2)")
+                    .bind(config)
+                    .bind(&self.text)
+                    .fetch_one(executor)
+                    .await
+            }
+            None => {
+                query_scalar("SELECT websearch_to_tsquery($1)")
+                    .bind(&self.text)
+                    .fetch_one(executor)
+                    .await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lexeme(word: &str) -> PgTsQueryNode {
+        PgTsQueryNode::lexeme(word)
+    }
+
+    #[test]
+    fn test_tsquery_display() {
+        let query = PgTsQuery::from((lexeme("a") | lexeme("b")) & !(lexeme("c") & lexeme("d")));
+        assert_eq!(query.to_string(), "( 'a' | 'b' ) & !( 'c' & 'd' )");
+
+        let query = PgTsQuery::from(
+            lexeme("a").followed_by(lexeme("b").followed_at(lexeme("c"), 3)) | lexeme("it's"),
+        );
+        assert_eq!(query.to_string(), "'a' <-> ( 'b' <3> 'c' ) | 'it''s'");
+
+        let query = PgTsQuery::from(PgTsQueryNode::Lexeme(PgTsQueryLexeme {
+            word: "sup".into(),
+            weights: vec![PgTsWeight::A, PgTsWeight::B],
+            prefix: true,
+        }));
+        assert_eq!(query.to_string(), "'sup':*AB");
+
+        assert_eq!(PgTsQuery::empty().to_string(), "");
+    }
+
+    #[test]
+    fn test_tsquery_from_str() {
+        for text in [
+            "( 'a' | 'b' ) & !( 'c' & 'd' )",
+            "'a' <-> ( 'b' <3> 'c' ) | 'it''s'",
+            "'sup':*AB & 'x':C",
+            "!!'a'",
+            "'a' & 'b' & 'c'",
+        ] {
+            assert_eq!(text.parse::<PgTsQuery>().unwrap().to_string(), text);
+        }
+
+        assert_eq!(
+            "fat & (rat|cat:*)".parse::<PgTsQuery>().unwrap(),
+            PgTsQuery::from(lexeme("fat") & (lexeme("rat") | PgTsQueryNode::prefix("cat")))
+        );
+
+        assert!("".parse::<PgTsQuery>().unwrap().is_empty());
+        assert!("'a' &".parse::<PgTsQuery>().is_err());
+        assert!("( 'a'".parse::<PgTsQuery>().is_err());
+        assert!("'a' 'b'".parse::<PgTsQuery>().is_err());
+    }
+}
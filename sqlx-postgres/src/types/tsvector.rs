@@ -0,0 +1,498 @@
+use std::cmp;
+use std::fmt::{self, Display, Formatter};
+use std::ops::Deref;
+use std::str::FromStr;
+
+use sqlx_core::bytes::Buf;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+/// The largest position Postgres stores in a `tsvector`; larger positions are clamped to it.
+const MAX_POSITION: u16 = (1 << 14) - 1;
+
+/// Represents errors parsing the text format of a `tsvector` or `tsquery`.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum PgTsParseError {
+    /// A quoted lexeme was not closed.
+    #[error("unterminated quoted lexeme")]
+    UnterminatedLexeme,
+
+    /// A lexeme was empty.
+    #[error("empty lexeme")]
+    EmptyLexeme,
+
+    /// A position or weight did not follow the syntax `<position>[A-D]`.
+    #[error("invalid position or weight: {0:?}")]
+    InvalidPosition(String),
+
+    /// A `tsquery` was not well-formed.
+    #[error("syntax error in tsquery at {0:?}")]
+    InvalidQuery(String),
+}
+
+/// The weight of a lexeme in a `tsvector`, `A` being the highest and `D` the default.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum PgTsWeight {
+    A,
+    B,
+    C,
+    #[default]
+    D,
+}
+
+impl PgTsWeight {
+    /// The weight in the two high bits of a position.
+    fn bits(self) -> u16 {
+        match self {
+            PgTsWeight::A => 3,
+            PgTsWeight::B => 2,
+            PgTsWeight::C => 1,
+            PgTsWeight::D => 0,
+        }
+    }
+
+    fn from_bits(bits: u16) -> Self {
+        match bits & 3 {
+            3 => PgTsWeight::A,
+            2 => PgTsWeight::B,
+            1 => PgTsWeight::C,
+            _ => PgTsWeight::D,
+        }
+    }
+
+    pub(crate) fn from_char(c: char) -> Option<Self> {
+        match c.to_ascii_uppercase() {
+            'A' => Some(PgTsWeight::A),
+            'B' => Some(PgTsWeight::B),
+            'C' => Some(PgTsWeight::C),
+            'D' => Some(PgTsWeight::D),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_char(self) -> char {
+        match self {
+            PgTsWeight::A => 'A',
+            PgTsWeight::B => 'B',
+            PgTsWeight::C => 'C',
+            PgTsWeight::D => 'D',
+        }
+    }
+}
+
+/// The position of a lexeme in a document, starting at 1.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PgTsPosition {
+    pub position: u16,
+    pub weight: PgTsWeight,
+}
+
+impl PgTsPosition {
+    pub fn new(position: u16, weight: PgTsWeight) -> Self {
+        Self { position, weight }
+    }
+}
+
+/// A normalized word in a `tsvector` and the positions it appears at, if they were recorded.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PgTsLexeme {
+    pub word: String,
+    pub positions: Vec<PgTsPosition>,
+}
+
+impl PgTsLexeme {
+    /// A lexeme without positions.
+    pub fn new(word: impl Into<String>) -> Self {
+        Self {
+            word: word.into(),
+            positions: Vec::new(),
+        }
+    }
+
+    /// Add a position the lexeme appears at.
+    pub fn position(mut self, position: u16, weight: PgTsWeight) -> Self {
+        self.positions.push(PgTsPosition::new(position, weight));
+        self
+    }
+}
+
+/// Container for a text search document (`tsvector`) in Postgres: a sorted list of distinct
+/// lexemes.
+///
+/// See <https://www.postgresql.org/docs/current/datatype-textsearch.html#DATATYPE-TSVECTOR>
+///
+/// Lexemes are kept in the order they were added and normalized when the value is sent to
+/// Postgres, which sorts them and merges duplicates. Decoded values are always normalized.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PgTsVector {
+    lexemes: Vec<PgTsLexeme>,
+}
+
+impl PgTsVector {
+    /// creates an empty tsvector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// push a lexeme to the tsvector
+    pub fn push(&mut self, lexeme: PgTsLexeme) {
+        self.lexemes.push(lexeme);
+    }
+
+    /// The lexemes sorted as Postgres sorts them, duplicates merged and positions sorted.
+    fn normalized(&self) -> Vec<PgTsLexeme> {
+        let mut lexemes = self.lexemes.clone();
+        lexemes.sort_by(|a, b| a.word.cmp(&b.word));
+        lexemes.dedup_by(|next, prev| {
+            let duplicate = next.word == prev.word;
+
+            if duplicate {
+                prev.positions.append(&mut next.positions);
+            }
+
+            duplicate
+        });
+
+        for lexeme in &mut lexemes {
+            for position in &mut lexeme.positions {
+                position.position = cmp::min(position.position, MAX_POSITION);
+            }
+
+            lexeme.positions.sort_by_key(|position| position.position);
+            lexeme.positions.dedup_by_key(|position| position.position);
+        }
+
+        lexemes
+    }
+}
+
+impl FromIterator<PgTsLexeme> for PgTsVector {
+    fn from_iter<T: IntoIterator<Item = PgTsLexeme>>(iter: T) -> Self {
+        Self {
+            lexemes: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoIterator for PgTsVector {
+    type Item = PgTsLexeme;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.lexemes.into_iter()
+    }
+}
+
+impl Deref for PgTsVector {
+    type Target = [PgTsLexeme];
+
+    fn deref(&self) -> &Self::Target {
+        &self.lexemes
+    }
+}
+
+impl FromStr for PgTsVector {
+    type Err = PgTsParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lexemes = Vec::new();
+        let mut rest = s.trim_start();
+
+        while !rest.is_empty() {
+            let (word, after) = parse_lexeme(rest)?;
+            let mut lexeme = PgTsLexeme::new(word);
+            rest = after;
+
+            if let Some(after) = rest.strip_prefix(':') {
+                let end = after.find(char::is_whitespace).unwrap_or(after.len());
+
+                for position in after[..end].split(',') {
+                    lexeme.positions.push(parse_position(position)?);
+                }
+
+                rest = &after[end..];
+            }
+
+            lexemes.push(lexeme);
+            rest = rest.trim_start();
+        }
+
+        Ok(Self { lexemes })
+    }
+}
+
+/// Parse a lexeme, either quoted as `'it''s'` or bare, returning it and the remaining input.
+///
+/// Bare lexemes end at whitespace or at any of `stop`.
+pub(crate) fn parse_lexeme_until<'a>(
+    s: &'a str,
+    stop: &[char],
+) -> Result<(String, &'a str), PgTsParseError> {
+    let mut word = String::new();
+    let mut chars = s.char_indices().peekable();
+
+    if s.starts_with('\'') {
+        chars.next();
+
+        loop {
+            match chars.next() {
+                Some((_, '\\')) => match chars.next() {
+                    Some((_, c)) => word.push(c),
+                    None => return Err(PgTsParseError::UnterminatedLexeme),
+                },
+                Some((_, '\'')) if matches!(chars.peek(), Some((_, '\''))) => {
+                    chars.next();
+                    word.push('\'');
+                }
+                Some((i, '\'')) => {
+                    return if word.is_empty() {
+                        Err(PgTsParseError::EmptyLexeme)
+                    } else {
+                        Ok((word, &s[i + 1..]))
+                    };
+                }
+                Some((_, c)) => word.push(c),
+                None => return Err(PgTsParseError::UnterminatedLexeme),
+            }
+        }
+    }
+
+    while let Some(&(i, c)) = chars.peek() {
+        if c.is_whitespace() || stop.contains(&c) {
+            return if word.is_empty() {
+                Err(PgTsParseError::EmptyLexeme)
+            } else {
+                Ok((word, &s[i..]))
+            };
+        }
+
+        chars.next();
+
+        if c == '\\' {
+            match chars.next() {
+                Some((_, c)) => word.push(c),
+                None => return Err(PgTsParseError::UnterminatedLexeme),
+            }
+        } else {
+            word.push(c);
+        }
+    }
+
+    if word.is_empty() {
+        Err(PgTsParseError::EmptyLexeme)
+    } else {
+        Ok((word, ""))
+    }
+}
+
+fn parse_lexeme(s: &str) -> Result<(String, &str), PgTsParseError> {
+    parse_lexeme_until(s, &[':'])
+}
+
+fn parse_position(s: &str) -> Result<PgTsPosition, PgTsParseError> {
+    let invalid = || PgTsParseError::InvalidPosition(s.to_owned());
+
+    let digits = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+
+    let position = s[..digits].parse::<u32>().map_err(|_| invalid())?;
+
+    let weight = match &s[digits..] {
+        "" => PgTsWeight::D,
+        weight => {
+            let mut chars = weight.chars();
+            let weight = chars.next().and_then(PgTsWeight::from_char);
+
+            match (weight, chars.next()) {
+                (Some(weight), None) => weight,
+                _ => return Err(invalid()),
+            }
+        }
+    };
+
+    Ok(PgTsPosition {
+        position: u16::try_from(cmp::min(position, u32::from(MAX_POSITION)))
+            .unwrap_or(MAX_POSITION),
+        weight,
+    })
+}
+
+/// Write a lexeme quoted as Postgres writes it.
+pub(crate) fn write_lexeme(f: &mut Formatter<'_>, word: &str) -> fmt::Result {
+    f.write_str("'")?;
+
+    for c in word.chars() {
+        match c {
+            '\'' => f.write_str("''")?,
+            '\\' => f.write_str("\\\\")?,
+            c => write!(f, "{c}")?,
+        }
+    }
+
+    f.write_str("'")
+}
+
+impl Display for PgTsVector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, lexeme) in self.lexemes.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+
+            write_lexeme(f, &lexeme.word)?;
+
+            for (i, position) in lexeme.positions.iter().enumerate() {
+                f.write_str(if i == 0 { ":" } else { "," })?;
+                write!(f, "{}", position.position)?;
+
+                if position.weight != PgTsWeight::D {
+                    write!(f, "{}", position.weight.as_char())?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Type<Postgres> for PgTsVector {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TS_VECTOR
+    }
+}
+
+impl PgHasArrayType for PgTsVector {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TS_VECTOR_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgTsVector {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let lexemes = self.normalized();
+
+        buf.extend(&i32::try_from(lexemes.len())?.to_be_bytes());
+
+        for lexeme in &lexemes {
+            if lexeme.word.is_empty() || lexeme.word.contains('\0') {
+                return Err(format!("invalid lexeme in tsvector: {:?}", lexeme.word).into());
+            }
+
+            buf.extend(lexeme.word.as_bytes());
+            buf.push(0);
+
+            buf.extend(&u16::try_from(lexeme.positions.len())?.to_be_bytes());
+
+            for position in &lexeme.positions {
+                let entry = (position.weight.bits() << 14) | position.position;
+                buf.extend(&entry.to_be_bytes());
+            }
+        }
+
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for PgTsVector {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => {
+                let mut buf = value.as_bytes()?;
+
+                if buf.len() < 4 {
+                    return Err("tsvector is too short".into());
+                }
+
+                let len = usize::try_from(buf.get_i32())?;
+                let mut lexemes = Vec::with_capacity(len);
+
+                for _ in 0..len {
+                    let end = buf
+                        .iter()
+                        .position(|&b| b == 0)
+                        .ok_or("lexeme in tsvector is not terminated")?;
+
+                    let word = std::str::from_utf8(&buf[..end])?.to_owned();
+                    buf.advance(end + 1);
+
+                    if buf.len() < 2 {
+                        return Err("tsvector is too short".into());
+                    }
+
+                    let count = usize::from(buf.get_u16());
+
+                    if buf.len() < count * 2 {
+                        return Err("tsvector is too short".into());
+                    }
+
+                    let positions = (0..count)
+                        .map(|_| {
+                            let entry = buf.get_u16();
+
+                            PgTsPosition {
+                                position: entry & MAX_POSITION,
+                                weight: PgTsWeight::from_bits(entry >> 14),
+                            }
+                        })
+                        .collect();
+
+                    lexemes.push(PgTsLexeme { word, positions });
+                }
+
+                Ok(Self { lexemes })
+            }
+            PgValueFormat::Text => Ok(Self::from_str(value.as_str()?)?),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tsvector_from_str() {
+        let vector: PgTsVector = "'a':1A,2 'fat' 'it''s':3C".parse().unwrap();
+
+        assert_eq!(
+            vector,
+            PgTsVector::from_iter([
+                PgTsLexeme::new("a")
+                    .position(1, PgTsWeight::A)
+                    .position(2, PgTsWeight::D),
+                PgTsLexeme::new("fat"),
+                PgTsLexeme::new("it's").position(3, PgTsWeight::C),
+            ])
+        );
+
+        assert_eq!(vector.to_string(), "'a':1A,2 'fat' 'it''s':3C");
+
+        let bare: PgTsVector = "cat:3 rat:99999".parse().unwrap();
+        assert_eq!(bare.to_string(), "'cat':3 'rat':16383");
+
+        assert!("'open".parse::<PgTsVector>().is_err());
+        assert!("a:x".parse::<PgTsVector>().is_err());
+        assert_eq!("".parse::<PgTsVector>().unwrap(), PgTsVector::new());
+    }
+
+    #[test]
+    fn test_tsvector_normalized() {
+        let vector = PgTsVector::from_iter([
+            PgTsLexeme::new("rat").position(5, PgTsWeight::D),
+            PgTsLexeme::new("cat").position(2, PgTsWeight::B),
+            PgTsLexeme::new("rat").position(1, PgTsWeight::A),
+        ]);
+
+        assert_eq!(
+            PgTsVector {
+                lexemes: vector.normalized()
+            }
+            .to_string(),
+            "'cat':2B 'rat':1A,5"
+        );
+    }
+}
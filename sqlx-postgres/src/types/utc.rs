@@ -0,0 +1,101 @@
+/// A naive date and time which is in UTC, bound and decoded as a `TIMESTAMPTZ`.
+///
+/// Binding a `NaiveDateTime` or `PrimitiveDateTime` where Postgres expects a `TIMESTAMPTZ` makes
+/// the server interpret it in the `TimeZone` of the session, and decoding a `TIMESTAMPTZ` as one
+/// is an error. Wrap the value in `PgUtc` to convert it explicitly instead, whatever the session
+/// time zone is:
+///
+/// ```rust,ignore
+/// sqlx::query("INSERT INTO events (happened_at) VALUES ($1)")
+///     .bind(PgUtc(naive_utc))
+///     .execute(&mut conn)
+///     .await?;
+///
+/// let PgUtc(happened_at): PgUtc<NaiveDateTime> =
+///     sqlx::query_scalar("SELECT happened_at FROM events")
+///         .fetch_one(&mut conn)
+///         .await?;
+/// ```
+///
+/// Implemented for `chrono::NaiveDateTime` and `time::PrimitiveDateTime`. See
+/// [`PgTimeZonePolicy`][crate::PgTimeZonePolicy] to find the statements which need it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PgUtc<T>(pub T);
+
+impl<T> PgUtc<T> {
+    /// Returns the wrapped date and time, which is in UTC.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for PgUtc<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+macro_rules! impl_utc {
+    ($naive:ty, $aware:ty, $to_aware:expr, $to_naive:expr) => {
+        impl crate::types::Type<crate::Postgres> for crate::types::PgUtc<$naive> {
+            fn type_info() -> crate::PgTypeInfo {
+                crate::PgTypeInfo::TIMESTAMPTZ
+            }
+        }
+
+        impl crate::types::PgHasArrayType for crate::types::PgUtc<$naive> {
+            fn array_type_info() -> crate::PgTypeInfo {
+                crate::PgTypeInfo::TIMESTAMPTZ_ARRAY
+            }
+        }
+
+        impl crate::encode::Encode<'_, crate::Postgres> for crate::types::PgUtc<$naive> {
+            fn encode_by_ref(
+                &self,
+                buf: &mut crate::PgArgumentBuffer,
+            ) -> Result<crate::encode::IsNull, crate::error::BoxDynError> {
+                let to_aware: fn($naive) -> $aware = $to_aware;
+                crate::encode::Encode::<crate::Postgres>::encode(to_aware(self.0), buf)
+            }
+
+            fn size_hint(&self) -> usize {
+                std::mem::size_of::<i64>()
+            }
+        }
+
+        impl<'r> crate::decode::Decode<'r, crate::Postgres> for crate::types::PgUtc<$naive> {
+            fn decode(value: crate::PgValueRef<'r>) -> Result<Self, crate::error::BoxDynError> {
+                let to_naive: fn($aware) -> $naive = $to_naive;
+                let aware: $aware = crate::decode::Decode::<crate::Postgres>::decode(value)?;
+                Ok(Self(to_naive(aware)))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "chrono")]
+mod chrono {
+    use chrono::{DateTime, NaiveDateTime, Utc};
+
+    impl_utc!(
+        NaiveDateTime,
+        DateTime<Utc>,
+        |naive| naive.and_utc(),
+        |aware| aware.naive_utc()
+    );
+}
+
+#[cfg(feature = "time")]
+mod time {
+    use time::{OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+    impl_utc!(
+        PrimitiveDateTime,
+        OffsetDateTime,
+        |naive| naive.assume_utc(),
+        |aware| {
+            let aware = aware.to_offset(UtcOffset::UTC);
+            PrimitiveDateTime::new(aware.date(), aware.time())
+        }
+    );
+}
@@ -0,0 +1,115 @@
+use std::str::FromStr;
+
+use sqlx_core::bytes::Buf;
+use sqlx_core::types::{HasVectorDistance, Vector, VectorDistance};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+impl Type<Postgres> for Vector {
+    fn type_info() -> PgTypeInfo {
+        // Since `vector` is enabled by an extension, it does not have a stable OID.
+        PgTypeInfo::with_name("vector")
+    }
+}
+
+impl PgHasArrayType for Vector {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_vector")
+    }
+}
+
+impl Encode<'_, Postgres> for Vector {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        encode_vector(self, buf)?;
+
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        4 + self.len() * 4
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for Vector {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => decode_vector(value.as_bytes()?),
+            PgValueFormat::Text => Vector::from_str(value.as_str()?),
+        }
+    }
+}
+
+impl HasVectorDistance for Postgres {
+    fn vector_distance_sql(
+        distance: VectorDistance,
+    ) -> Option<(&'static str, &'static str, &'static str)> {
+        // https://github.com/pgvector/pgvector#querying
+        let operator = match distance {
+            VectorDistance::L2 => " <-> ",
+            VectorDistance::Cosine => " <=> ",
+            VectorDistance::InnerProduct => " <#> ",
+            // pgvector 0.7.0+
+            VectorDistance::L1 => " <+> ",
+            _ => return None,
+        };
+
+        Some(("(", operator, ")"))
+    }
+}
+
+/// The binary format of `vector`: the number of dimensions as an `int2`, an unused `int2`,
+/// then each element as a `float4`.
+fn encode_vector(vector: &[f32], buf: &mut Vec<u8>) -> Result<(), BoxDynError> {
+    let dims = i16::try_from(vector.len())
+        .map_err(|_| format!("a vector can't have more than {} dimensions", i16::MAX))?;
+
+    buf.extend(&dims.to_be_bytes());
+    buf.extend(&0i16.to_be_bytes());
+
+    for value in vector {
+        buf.extend(&value.to_be_bytes());
+    }
+
+    Ok(())
+}
+
+fn decode_vector(mut buf: &[u8]) -> Result<Vector, BoxDynError> {
+    if buf.len() < 4 {
+        return Err("vector is too short".into());
+    }
+
+    let dims = usize::try_from(buf.get_i16())?;
+    let _unused = buf.get_i16();
+
+    if buf.len() != dims * 4 {
+        return Err(format!(
+            "expected {} bytes for a vector of {dims} dimensions, got {}",
+            dims * 4,
+            buf.len()
+        )
+        .into());
+    }
+
+    Ok(Vector((0..dims).map(|_| buf.get_f32()).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_binary() {
+        let mut buf = Vec::new();
+        encode_vector(&[1.0, -2.5], &mut buf).unwrap();
+
+        assert_eq!(buf, [0, 2, 0, 0, 0x3f, 0x80, 0, 0, 0xc0, 0x20, 0, 0]);
+        assert_eq!(decode_vector(&buf).unwrap(), Vector(vec![1.0, -2.5]));
+
+        assert!(decode_vector(&buf[..10]).is_err());
+        assert!(encode_vector(&vec![0.0; 40_000], &mut Vec::new()).is_err());
+    }
+}
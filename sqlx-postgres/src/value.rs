@@ -4,6 +4,7 @@ use sqlx_core::bytes::{Buf, Bytes};
 pub(crate) use sqlx_core::value::{Value, ValueRef};
 use std::borrow::Cow;
 use std::str::from_utf8;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 #[repr(u8)]
@@ -12,6 +13,31 @@ pub enum PgValueFormat {
     Binary = 1,
 }
 
+/// The formats of the columns of a result, as requested in `Bind`.
+#[derive(Debug, Clone)]
+pub(crate) enum PgResultFormats {
+    /// One format for all of the columns.
+    All(PgValueFormat),
+    /// The format of each column.
+    PerColumn(Arc<[PgValueFormat]>),
+}
+
+impl PgResultFormats {
+    pub(crate) fn get(&self, index: usize) -> PgValueFormat {
+        match self {
+            Self::All(format) => *format,
+            Self::PerColumn(formats) => formats[index],
+        }
+    }
+
+    pub(crate) fn as_slice(&self) -> &[PgValueFormat] {
+        match self {
+            Self::All(format) => std::slice::from_ref(format),
+            Self::PerColumn(formats) => formats,
+        }
+    }
+}
+
 /// Implementation of [`ValueRef`] for PostgreSQL.
 #[derive(Clone)]
 pub struct PgValueRef<'r> {
@@ -9,16 +9,17 @@ use futures_core::stream::BoxStream;
 use futures_util::{StreamExt, TryFutureExt, TryStreamExt};
 
 use sqlx_core::any::{
-    Any, AnyArguments, AnyColumn, AnyConnectOptions, AnyConnectionBackend, AnyQueryResult, AnyRow,
-    AnyStatement, AnyTypeInfo, AnyTypeInfoKind, AnyValueKind,
+    Any, AnyArguments, AnyCapabilities, AnyColumn, AnyConnectOptions, AnyConnectionBackend,
+    AnyPlaceholderStyle, AnyQueryResult, AnyRow, AnyStatement, AnyTypeInfo, AnyTypeInfoKind,
+    AnyValueKind,
 };
 
 use crate::type_info::DataType;
-use sqlx_core::connection::{ConnectOptions, Connection};
+use sqlx_core::connection::{CachedStatements, ConnectOptions, Connection};
 use sqlx_core::database::Database;
 use sqlx_core::describe::Describe;
 use sqlx_core::executor::Executor;
-use sqlx_core::transaction::TransactionManager;
+use sqlx_core::transaction::{StatementJournal, TransactionManager};
 use std::pin::pin;
 
 sqlx_core::declare_driver_with_optional_migrate!(DRIVER = Sqlite);
@@ -28,6 +29,20 @@ impl AnyConnectionBackend for SqliteConnection {
         <Sqlite as Database>::NAME
     }
 
+    fn capabilities(&self) -> AnyCapabilities {
+        // SAFETY: reads a constant compiled into the library
+        let version = unsafe { libsqlite3_sys::sqlite3_libversion_number() };
+
+        AnyCapabilities {
+            // added in 3.35.0
+            supports_returning: version >= 3_035_000,
+            supports_savepoints: true,
+            placeholder_style: AnyPlaceholderStyle::QuestionMark,
+            // the default `SQLITE_MAX_VARIABLE_NUMBER` was raised from 999 in 3.32.0
+            max_bind_params: if version >= 3_032_000 { 32766 } else { 999 },
+        }
+    }
+
     fn close(self: Box<Self>) -> BoxFuture<'static, sqlx_core::Result<()>> {
         Connection::close(*self)
     }
@@ -63,6 +78,25 @@ impl AnyConnectionBackend for SqliteConnection {
         SqliteTransactionManager::get_transaction_depth(self)
     }
 
+    fn cached_statements_size(&self) -> usize {
+        Connection::cached_statements_size(self)
+    }
+
+    fn clear_cached_statements(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::clear_cached_statements(self)
+    }
+
+    fn cached_statements(&self) -> CachedStatements {
+        Connection::cached_statements(self)
+    }
+
+    fn set_statement_cache_capacity(
+        &mut self,
+        capacity: usize,
+    ) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::set_statement_cache_capacity(self, capacity)
+    }
+
     fn shrink_buffers(&mut self) {
         // NO-OP.
     }
@@ -75,6 +109,13 @@ impl AnyConnectionBackend for SqliteConnection {
         Connection::should_flush(self)
     }
 
+    fn set_statement_journal(
+        &mut self,
+        journal: Option<StatementJournal>,
+    ) -> Option<StatementJournal> {
+        Connection::set_statement_journal(self, journal)
+    }
+
     #[cfg(feature = "migrate")]
     fn as_migrate(
         &mut self,
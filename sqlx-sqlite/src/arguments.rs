@@ -75,6 +75,12 @@ impl<'q> Arguments<'q> for SqliteArguments<'q> {
 }
 
 impl SqliteArguments<'_> {
+    pub(crate) fn has_nan(&self) -> bool {
+        self.values
+            .iter()
+            .any(|value| matches!(value, SqliteArgumentValue::Double(v) if v.is_nan()))
+    }
+
     pub(super) fn bind(&self, handle: &mut StatementHandle, offset: usize) -> Result<usize, Error> {
         let mut arg_i = offset;
         // for handle in &statement.handles {
@@ -47,6 +47,7 @@ pub struct EstablishParams {
     busy_timeout: Duration,
     statement_cache_capacity: usize,
     log_settings: LogSettings,
+    reject_nan: bool,
     extensions: IndexMap<CString, Option<CString>>,
     pub(crate) thread_name: String,
     pub(crate) command_channel_size: usize,
@@ -156,6 +157,7 @@ impl EstablishParams {
             busy_timeout: options.busy_timeout,
             statement_cache_capacity: options.statement_cache_capacity,
             log_settings: options.log_settings.clone(),
+            reject_nan: options.reject_nan,
             extensions,
             thread_name: (options.thread_name)(thread_id as u64),
             command_channel_size: options.command_channel_size,
@@ -289,6 +291,7 @@ impl EstablishParams {
             handle,
             statements: Statements::new(self.statement_cache_capacity),
             log_settings: self.log_settings.clone(),
+            reject_nan: self.reject_nan,
             progress_handler_callback: None,
             update_hook_callback: None,
             #[cfg(feature = "preupdate-hook")]
@@ -24,6 +24,12 @@ pub(crate) fn iter<'a>(
     args: Option<SqliteArguments<'a>>,
     persistent: bool,
 ) -> Result<ExecuteIter<'a>, Error> {
+    if conn.reject_nan && args.as_ref().is_some_and(SqliteArguments::has_nan) {
+        return Err(Error::Encode(
+            "SQLite does not support NaN values; bind `None` instead".into(),
+        ));
+    }
+
     // fetch the cached statement or allocate a new one
     let statement = conn.statements.get(query, persistent)?;
 
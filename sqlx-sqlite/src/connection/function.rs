@@ -0,0 +1,427 @@
+use std::ffi::CString;
+use std::fmt::{self, Debug, Formatter};
+use std::mem::size_of;
+use std::os::raw::{c_int, c_void};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::slice;
+use std::sync::Arc;
+
+use libsqlite3_sys::{
+    sqlite3_aggregate_context, sqlite3_context, sqlite3_create_function_v2, sqlite3_result_blob64,
+    sqlite3_result_double, sqlite3_result_error, sqlite3_result_error_nomem, sqlite3_result_int,
+    sqlite3_result_int64, sqlite3_result_null, sqlite3_result_text64, sqlite3_user_data,
+    sqlite3_value, SQLITE_DETERMINISTIC, SQLITE_OK, SQLITE_TRANSIENT, SQLITE_UTF8,
+};
+use sqlx_core::decode::Decode;
+use sqlx_core::encode::{Encode, IsNull};
+use sqlx_core::error::{mismatched_types, BoxDynError, Error};
+use sqlx_core::type_info::TypeInfo;
+use sqlx_core::types::Type;
+use sqlx_core::value::ValueRef;
+
+use crate::connection::handle::ConnectionHandle;
+use crate::type_info::DataType;
+use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+
+/// A function implemented in Rust which can be called from SQL, created with
+/// [`scalar()`][Self::scalar] or [`aggregate()`][Self::aggregate].
+///
+/// Register it on every connection with [`SqliteConnectOptions::function()`], or on a single
+/// one with [`SqliteConnection::create_function()`]. The function runs on the worker thread of
+/// the connection, while the statement calling it is executed.
+///
+/// See [`sqlite3_create_function()`](https://www.sqlite.org/c3ref/create_function.html) for
+/// details.
+///
+/// [`SqliteConnectOptions::function()`]: crate::SqliteConnectOptions::function
+/// [`SqliteConnection::create_function()`]: crate::SqliteConnection::create_function
+#[derive(Clone)]
+pub struct SqliteFunction {
+    name: Arc<str>,
+    num_args: i32,
+    deterministic: bool,
+    #[allow(clippy::type_complexity)]
+    register: Arc<dyn Fn(&mut ConnectionHandle, &CString, c_int, c_int) -> c_int + Send + Sync>,
+}
+
+impl SqliteFunction {
+    /// A scalar function, which returns a value for each call.
+    ///
+    /// `num_args` is the number of arguments the function takes, or `-1` for any number of
+    /// arguments. SQLite picks the function registered for the number of arguments of a call,
+    /// so functions of the same name can be registered for different numbers of arguments.
+    ///
+    /// If `function` returns an error, the statement fails with its message.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_sqlite::{SqliteConnectOptions, SqliteFunction};
+    /// // `SELECT normalize(name) FROM users`
+    /// let normalize = SqliteFunction::scalar("normalize", 1, |args| {
+    ///     let name: Option<String> = args.try_get(0)?;
+    ///
+    ///     Ok(name.map(|name| name.trim().to_lowercase()))
+    /// })
+    /// .deterministic(true);
+    ///
+    /// let options = SqliteConnectOptions::new().function(normalize);
+    /// ```
+    pub fn scalar<N, F, R>(name: N, num_args: i32, function: F) -> Self
+    where
+        N: Into<Arc<str>>,
+        F: Fn(&SqliteFunctionArgs<'_>) -> Result<R, BoxDynError> + Send + Sync + 'static,
+        R: Encode<'static, Sqlite>,
+    {
+        let function = Arc::new(function);
+
+        SqliteFunction::new(
+            name,
+            num_args,
+            move |handle, name, num_args, flags| unsafe {
+                sqlite3_create_function_v2(
+                    handle.as_ptr(),
+                    name.as_ptr(),
+                    num_args,
+                    flags,
+                    Arc::into_raw(Arc::clone(&function)) as *mut c_void,
+                    Some(call_scalar::<F, R>),
+                    None,
+                    None,
+                    Some(drop_arc::<F>),
+                )
+            },
+        )
+    }
+
+    /// An aggregate function, which returns a value for each group of rows, like `sum()` or
+    /// `group_concat()`.
+    ///
+    /// For each group, `init` creates the state of the aggregation, `step` updates it with the
+    /// arguments of each row, and `finalize` returns the value of the group from it. A group
+    /// without rows, such as with `SELECT my_sum(x) FROM t` on an empty table, is finalized
+    /// without any `step`.
+    ///
+    /// If `step` or `finalize` return an error, the statement fails with its message.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_sqlite::SqliteFunction;
+    /// // `SELECT longest(name) FROM users GROUP BY team`
+    /// let longest = SqliteFunction::aggregate(
+    ///     "longest",
+    ///     1,
+    ///     String::new,
+    ///     |longest, args| {
+    ///         let name: String = args.try_get(0)?;
+    ///
+    ///         if name.chars().count() > longest.chars().count() {
+    ///             *longest = name;
+    ///         }
+    ///
+    ///         Ok(())
+    ///     },
+    ///     Ok,
+    /// );
+    /// ```
+    pub fn aggregate<N, T, I, S, F, R>(
+        name: N,
+        num_args: i32,
+        init: I,
+        step: S,
+        finalize: F,
+    ) -> Self
+    where
+        N: Into<Arc<str>>,
+        T: 'static,
+        I: Fn() -> T + Send + Sync + 'static,
+        S: Fn(&mut T, &SqliteFunctionArgs<'_>) -> Result<(), BoxDynError> + Send + Sync + 'static,
+        F: Fn(T) -> Result<R, BoxDynError> + Send + Sync + 'static,
+        R: Encode<'static, Sqlite>,
+    {
+        let aggregate = Arc::new(Aggregate {
+            init,
+            step,
+            finalize,
+        });
+
+        SqliteFunction::new(
+            name,
+            num_args,
+            move |handle, name, num_args, flags| unsafe {
+                sqlite3_create_function_v2(
+                    handle.as_ptr(),
+                    name.as_ptr(),
+                    num_args,
+                    flags,
+                    Arc::into_raw(Arc::clone(&aggregate)) as *mut c_void,
+                    None,
+                    Some(call_step::<T, I, S, F>),
+                    Some(call_final::<T, I, S, F, R>),
+                    Some(drop_arc::<Aggregate<I, S, F>>),
+                )
+            },
+        )
+    }
+
+    fn new<N, G>(name: N, num_args: i32, register: G) -> Self
+    where
+        N: Into<Arc<str>>,
+        G: Fn(&mut ConnectionHandle, &CString, c_int, c_int) -> c_int + Send + Sync + 'static,
+    {
+        SqliteFunction {
+            name: name.into(),
+            num_args,
+            deterministic: false,
+            register: Arc::new(register),
+        }
+    }
+
+    /// Declare that the function always returns the same value for the same arguments, which
+    /// lets SQLite evaluate it fewer times, and use it in indexes, `CHECK` constraints and
+    /// generated columns.
+    ///
+    /// Defaults to `false`. A function which is not in fact deterministic may corrupt the
+    /// indexes using it.
+    pub fn deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    pub(crate) fn create(&self, handle: &mut ConnectionHandle) -> Result<(), Error> {
+        let name = CString::new(&*self.name)
+            .map_err(|_| err_protocol!("invalid function name: {:?}", self.name))?;
+
+        let mut flags = SQLITE_UTF8;
+
+        if self.deterministic {
+            flags |= SQLITE_DETERMINISTIC;
+        }
+
+        // SQLite calls the destructor of the function data if this fails
+        if (self.register)(handle, &name, self.num_args, flags) == SQLITE_OK {
+            Ok(())
+        } else {
+            Err(handle.expect_error().into())
+        }
+    }
+}
+
+impl Debug for SqliteFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SqliteFunction")
+            .field("name", &self.name)
+            .field("num_args", &self.num_args)
+            .field("deterministic", &self.deterministic)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The arguments of a call to a [`SqliteFunction`].
+pub struct SqliteFunctionArgs<'a> {
+    values: &'a [*mut sqlite3_value],
+}
+
+impl<'a> SqliteFunctionArgs<'a> {
+    /// The number of arguments of the call.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Decode the argument at `index`, like [`Row::try_get()`][sqlx_core::row::Row::try_get].
+    pub fn try_get<T>(&self, index: usize) -> Result<T, Error>
+    where
+        T: Decode<'a, Sqlite> + Type<Sqlite>,
+    {
+        let value = self.try_get_raw(index)?;
+
+        if !value.is_null() {
+            let ty = value.type_info();
+
+            if !ty.is_null() && !T::compatible(&ty) {
+                return Err(Error::ColumnDecode {
+                    index: format!("{index:?}"),
+                    source: mismatched_types::<Sqlite, T>(&ty),
+                });
+            }
+        }
+
+        T::decode(value).map_err(|source| Error::ColumnDecode {
+            index: format!("{index:?}"),
+            source,
+        })
+    }
+
+    /// The argument at `index`, without decoding it.
+    pub fn try_get_raw(&self, index: usize) -> Result<SqliteValueRef<'a>, Error> {
+        let value = *self
+            .values
+            .get(index)
+            .ok_or(Error::ColumnIndexOutOfBounds {
+                index,
+                len: self.values.len(),
+            })?;
+
+        // SAFETY: SQLite passes valid values, which it frees after the call
+        Ok(unsafe { SqliteValueRef::borrowed(value, SqliteTypeInfo(DataType::Null)) })
+    }
+}
+
+struct Aggregate<I, S, F> {
+    init: I,
+    step: S,
+    finalize: F,
+}
+
+unsafe extern "C" fn drop_arc<T>(p: *mut c_void) {
+    drop(Arc::from_raw(p as *const T));
+}
+
+unsafe fn function_args<'a>(argc: c_int, argv: *mut *mut sqlite3_value) -> SqliteFunctionArgs<'a> {
+    let values = match usize::try_from(argc) {
+        Ok(len) if len > 0 && !argv.is_null() => slice::from_raw_parts(argv, len),
+        _ => &[],
+    };
+
+    SqliteFunctionArgs { values }
+}
+
+unsafe extern "C" fn call_scalar<F, R>(
+    ctx: *mut sqlite3_context,
+    argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) where
+    F: Fn(&SqliteFunctionArgs<'_>) -> Result<R, BoxDynError>,
+    R: Encode<'static, Sqlite>,
+{
+    let function = &*(sqlite3_user_data(ctx) as *const F);
+    let args = function_args(argc, argv);
+
+    set_result(ctx, catch_unwind(AssertUnwindSafe(|| function(&args))));
+}
+
+/// The state of an aggregation, allocated by SQLite for each group. SQLite zeroes it, so the
+/// state is created on the first row.
+type AggregateState<T> = Option<Box<T>>;
+
+unsafe fn aggregate_state<T>(ctx: *mut sqlite3_context, alloc: bool) -> *mut AggregateState<T> {
+    // a zero size only returns the state if it was allocated
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)] // the size of a pointer
+    let size = if alloc {
+        size_of::<AggregateState<T>>() as c_int
+    } else {
+        0
+    };
+
+    sqlite3_aggregate_context(ctx, size).cast()
+}
+
+unsafe extern "C" fn call_step<T, I, S, F>(
+    ctx: *mut sqlite3_context,
+    argc: c_int,
+    argv: *mut *mut sqlite3_value,
+) where
+    I: Fn() -> T,
+    S: Fn(&mut T, &SqliteFunctionArgs<'_>) -> Result<(), BoxDynError>,
+{
+    let aggregate = &*(sqlite3_user_data(ctx) as *const Aggregate<I, S, F>);
+    let args = function_args(argc, argv);
+
+    let state = aggregate_state::<T>(ctx, true);
+    if state.is_null() {
+        sqlite3_result_error_nomem(ctx);
+        return;
+    }
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let state = (*state).get_or_insert_with(|| Box::new((aggregate.init)()));
+        (aggregate.step)(state, &args)
+    }));
+
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(error)) => set_error(ctx, &error.to_string()),
+        Err(_) => set_error(ctx, "function panicked"),
+    }
+}
+
+unsafe extern "C" fn call_final<T, I, S, F, R>(ctx: *mut sqlite3_context)
+where
+    I: Fn() -> T,
+    F: Fn(T) -> Result<R, BoxDynError>,
+    R: Encode<'static, Sqlite>,
+{
+    let aggregate = &*(sqlite3_user_data(ctx) as *const Aggregate<I, S, F>);
+
+    // take the state so that it is dropped even if SQLite finalizes after an error
+    let state = aggregate_state::<T>(ctx, false);
+    let state = if state.is_null() {
+        None
+    } else {
+        (*state).take()
+    };
+
+    set_result(
+        ctx,
+        catch_unwind(AssertUnwindSafe(|| {
+            let state = state.map_or_else(|| (aggregate.init)(), |state| *state);
+            (aggregate.finalize)(state)
+        })),
+    );
+}
+
+unsafe fn set_result<R>(
+    ctx: *mut sqlite3_context,
+    result: std::thread::Result<Result<R, BoxDynError>>,
+) where
+    R: Encode<'static, Sqlite>,
+{
+    let value = match result {
+        Ok(Ok(value)) => value,
+        Ok(Err(error)) => return set_error(ctx, &error.to_string()),
+        Err(_) => return set_error(ctx, "function panicked"),
+    };
+
+    let mut buf = Vec::with_capacity(1);
+    let value = match value.encode(&mut buf) {
+        Ok(IsNull::No) => buf.pop().unwrap_or(SqliteArgumentValue::Null),
+        Ok(IsNull::Yes) => SqliteArgumentValue::Null,
+        Err(error) => return set_error(ctx, &error.to_string()),
+    };
+
+    match value {
+        SqliteArgumentValue::Null => sqlite3_result_null(ctx),
+        SqliteArgumentValue::Text(text) => sqlite3_result_text64(
+            ctx,
+            text.as_ptr().cast(),
+            text.len() as u64,
+            SQLITE_TRANSIENT(),
+            ENCODING_UTF8,
+        ),
+        SqliteArgumentValue::Blob(blob) => sqlite3_result_blob64(
+            ctx,
+            blob.as_ptr().cast(),
+            blob.len() as u64,
+            SQLITE_TRANSIENT(),
+        ),
+        SqliteArgumentValue::Double(value) => sqlite3_result_double(ctx, value),
+        SqliteArgumentValue::Int(value) => sqlite3_result_int(ctx, value),
+        SqliteArgumentValue::Int64(value) => sqlite3_result_int64(ctx, value),
+    }
+}
+
+// the encoding argument of `sqlite3_result_text64()` is an `unsigned char`
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+const ENCODING_UTF8: u8 = SQLITE_UTF8 as u8;
+
+unsafe fn set_error(ctx: *mut sqlite3_context, message: &str) {
+    // a negative length reads up to the first nul byte, so pass the length
+    let len = c_int::try_from(message.len()).unwrap_or(c_int::MAX);
+
+    sqlite3_result_error(ctx, message.as_ptr().cast(), len);
+}
@@ -8,6 +8,7 @@ use std::panic::catch_unwind;
 use std::ptr;
 use std::ptr::NonNull;
 
+pub use function::{SqliteFunction, SqliteFunctionArgs};
 use futures_core::future::BoxFuture;
 use futures_intrusive::sync::MutexGuard;
 use futures_util::future;
@@ -23,7 +24,7 @@ use sqlx_core::common::StatementCache;
 pub(crate) use sqlx_core::connection::*;
 use sqlx_core::error::Error;
 use sqlx_core::executor::Executor;
-use sqlx_core::transaction::Transaction;
+use sqlx_core::transaction::{StatementJournal, Transaction};
 
 use crate::connection::establish::EstablishParams;
 use crate::connection::worker::ConnectionWorker;
@@ -37,6 +38,7 @@ pub(crate) mod establish;
 pub(crate) mod execute;
 mod executor;
 mod explain;
+mod function;
 mod handle;
 pub(crate) mod intmap;
 #[cfg(feature = "preupdate-hook")]
@@ -111,6 +113,9 @@ pub(crate) struct ConnectionState {
 
     log_settings: LogSettings,
 
+    /// Fail queries binding `NaN`, which SQLite would bind as `NULL`.
+    reject_nan: bool,
+
     /// Stores the progress handler set on the current connection. If the handler returns `false`,
     /// the query is interrupted.
     progress_handler_callback: Option<Handler>,
@@ -200,6 +205,25 @@ impl SqliteConnection {
 
         Ok(LockedSqliteHandle { guard })
     }
+
+    /// Register a function implemented in Rust on this connection.
+    ///
+    /// See [`SqliteConnectOptions::function()`] to register it on every connection instead, and
+    /// [`SqliteFunction`] for details.
+    pub async fn create_function(&mut self, function: SqliteFunction) -> Result<(), Error> {
+        self.lock_handle().await?.create_function(&function)
+    }
+
+    /// Apply a collation to this connection.
+    ///
+    /// See [`SqliteConnectOptions::collation()`] for details.
+    pub async fn create_collation(
+        &mut self,
+        name: &str,
+        compare: impl Fn(&str, &str) -> Ordering + Send + Sync + 'static,
+    ) -> Result<(), Error> {
+        self.lock_handle().await?.create_collation(name, compare)
+    }
 }
 
 impl Debug for SqliteConnection {
@@ -275,6 +299,20 @@ impl Connection for SqliteConnection {
         })
     }
 
+    fn cached_statements(&self) -> CachedStatements {
+        CachedStatements {
+            size: self.worker.shared.get_cached_statements_size(),
+            capacity: self.worker.shared.get_cached_statements_capacity(),
+        }
+    }
+
+    fn set_statement_cache_capacity(
+        &mut self,
+        capacity: usize,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(self.worker.set_cache_capacity(capacity))
+    }
+
     #[inline]
     fn shrink_buffers(&mut self) {
         // No-op.
@@ -293,6 +331,14 @@ impl Connection for SqliteConnection {
     fn should_flush(&self) -> bool {
         false
     }
+
+    #[doc(hidden)]
+    fn set_statement_journal(
+        &mut self,
+        journal: Option<StatementJournal>,
+    ) -> Option<StatementJournal> {
+        self.worker.shared.set_statement_journal(journal)
+    }
 }
 
 /// Implements a C binding to a progress callback. The function returns `0` if the
@@ -390,6 +436,13 @@ impl LockedSqliteHandle<'_> {
         collation::create_collation(&mut self.guard.handle, name, compare)
     }
 
+    /// Register a function implemented in Rust on the open database.
+    ///
+    /// See [`SqliteFunction`] for details.
+    pub fn create_function(&mut self, function: &SqliteFunction) -> Result<(), Error> {
+        function.create(&mut self.guard.handle)
+    }
+
     /// Sets a progress handler that is invoked periodically during long running calls. If the progress callback
     /// returns `false`, then the operation is interrupted.
     ///
@@ -604,6 +657,14 @@ impl Statements {
         self.cached.len()
     }
 
+    fn capacity(&self) -> usize {
+        self.cached.capacity()
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.cached.set_capacity(capacity);
+    }
+
     fn clear(&mut self) {
         self.cached.clear();
         self.temp = None;
@@ -12,6 +12,7 @@ use sqlx_core::describe::Describe;
 use sqlx_core::error::Error;
 use sqlx_core::transaction::{
     begin_ansi_transaction_sql, commit_ansi_transaction_sql, rollback_ansi_transaction_sql,
+    StatementJournal,
 };
 use sqlx_core::Either;
 
@@ -38,6 +39,9 @@ pub(crate) struct ConnectionWorker {
 pub(crate) struct WorkerSharedState {
     transaction_depth: AtomicUsize,
     cached_statements_size: AtomicUsize,
+    cached_statements_capacity: AtomicUsize,
+    // set from the connection and picked up by the worker before each statement
+    statement_journal: std::sync::Mutex<Option<StatementJournal>>,
     pub(crate) conn: Mutex<ConnectionState>,
 }
 
@@ -49,6 +53,29 @@ impl WorkerSharedState {
     pub(crate) fn get_cached_statements_size(&self) -> usize {
         self.cached_statements_size.load(Ordering::Acquire)
     }
+
+    pub(crate) fn get_cached_statements_capacity(&self) -> usize {
+        self.cached_statements_capacity.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn set_statement_journal(
+        &self,
+        journal: Option<StatementJournal>,
+    ) -> Option<StatementJournal> {
+        let mut slot = self
+            .statement_journal
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+
+        std::mem::replace(&mut slot, journal)
+    }
+
+    fn statement_journal(&self) -> Option<StatementJournal> {
+        self.statement_journal
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
 }
 
 enum Command {
@@ -91,6 +118,10 @@ enum Command {
     ClearCache {
         tx: oneshot::Sender<()>,
     },
+    SetCacheCapacity {
+        capacity: usize,
+        tx: oneshot::Sender<()>,
+    },
     Ping {
         tx: oneshot::Sender<()>,
     },
@@ -119,6 +150,8 @@ impl ConnectionWorker {
                 let shared = Arc::new(WorkerSharedState {
                     transaction_depth: AtomicUsize::new(0),
                     cached_statements_size: AtomicUsize::new(0),
+                    cached_statements_capacity: AtomicUsize::new(conn.statements.capacity()),
+                    statement_journal: std::sync::Mutex::new(None),
                     // note: must be fair because in `Command::UnlockDb` we unlock the mutex
                     // and then immediately try to relock it; an unfair mutex would immediately
                     // grant us the lock even if another task is waiting.
@@ -164,6 +197,8 @@ impl ConnectionWorker {
                             tx,
                             limit
                         } => {
+                            conn.log_settings.journal = shared.statement_journal();
+
                             let iter = match execute::iter(&mut conn, &query, arguments, persistent)
                             {
                                 Ok(iter) => iter,
@@ -313,6 +348,13 @@ impl ConnectionWorker {
                             update_cached_statements_size(&conn, &shared.cached_statements_size);
                             tx.send(()).ok();
                         }
+                        Command::SetCacheCapacity { capacity, tx } => {
+                            // evicted statements are finalized when dropped
+                            conn.statements.set_capacity(capacity);
+                            shared.cached_statements_capacity.store(capacity, Ordering::Release);
+                            update_cached_statements_size(&conn, &shared.cached_statements_size);
+                            tx.send(()).ok();
+                        }
                         Command::UnlockDb => {
                             drop(conn);
                             conn = futures_executor::block_on(shared.conn.lock());
@@ -461,6 +503,11 @@ impl ConnectionWorker {
         self.oneshot_cmd(|tx| Command::ClearCache { tx }).await
     }
 
+    pub(crate) async fn set_cache_capacity(&mut self, capacity: usize) -> Result<(), Error> {
+        self.oneshot_cmd(|tx| Command::SetCacheCapacity { capacity, tx })
+            .await
+    }
+
     pub(crate) async fn unlock_db(&mut self) -> Result<MutexGuard<'_, ConnectionState>, Error> {
         let (guard, res) = futures_util::future::join(
             // we need to join the wait queue for the lock before we send the message
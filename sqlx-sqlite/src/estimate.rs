@@ -0,0 +1,71 @@
+use futures_core::future::BoxFuture;
+use sqlx_core::estimate::{CountTarget, EstimateCount};
+use sqlx_core::query_scalar::query_scalar;
+
+use crate::error::Error;
+use crate::{Sqlite, SqliteConnection};
+
+impl EstimateCount for Sqlite {
+    fn estimate_count<'c>(
+        conn: &'c mut SqliteConnection,
+        target: CountTarget<'c>,
+    ) -> BoxFuture<'c, Result<u64, Error>> {
+        Box::pin(async move {
+            let from = match target {
+                CountTarget::Table(table) => {
+                    let (schema, name) = match table.split_once('.') {
+                        Some((schema, name)) => (quote(schema), unquote(name)),
+                        None => ("main".to_owned(), unquote(table)),
+                    };
+
+                    // `sqlite_stat1` is created by the first `ANALYZE`
+                    let analyzed: bool = query_scalar(&format!(
+                        "SELECT EXISTS(SELECT 1 FROM {schema}.sqlite_master \
+                         WHERE type = 'table' AND name = 'sqlite_stat1')"
+                    ))
+                    .fetch_one(&mut *conn)
+                    .await?;
+
+                    if analyzed {
+                        // the first number of each row for a table is the number of rows in it
+                        let stat: Option<String> = query_scalar(&format!(
+                            "SELECT stat FROM {schema}.sqlite_stat1 WHERE tbl = ?1 LIMIT 1"
+                        ))
+                        .bind(name)
+                        .fetch_optional(&mut *conn)
+                        .await?;
+
+                        let rows = stat
+                            .as_deref()
+                            .and_then(|stat| stat.split_whitespace().next()?.parse::<u64>().ok());
+
+                        if let Some(rows) = rows {
+                            return Ok(rows);
+                        }
+                    }
+
+                    format!("{schema}.{}", quote(name))
+                }
+                CountTarget::Query(query) => format!("({query})"),
+            };
+
+            let count: i64 = query_scalar(&format!("SELECT COUNT(*) FROM {from}"))
+                .persistent(false)
+                .fetch_one(&mut *conn)
+                .await?;
+
+            #[allow(clippy::cast_sign_loss)]
+            Ok(count as u64)
+        })
+    }
+}
+
+fn unquote(name: &str) -> &str {
+    name.strip_prefix('"')
+        .and_then(|name| name.strip_suffix('"'))
+        .unwrap_or(name)
+}
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", unquote(name).replace('"', "\"\""))
+}
@@ -0,0 +1,429 @@
+//! Helpers for [FTS5](https://www.sqlite.org/fts5.html) full-text search indexes.
+
+use std::fmt::Write;
+
+use sqlx_core::query_as::query_as;
+
+use crate::decode::Decode;
+use crate::error::{BoxDynError, Error};
+use crate::executor::Executor;
+use crate::type_info::DataType;
+use crate::types::Type;
+use crate::{Sqlite, SqliteConnection, SqliteTypeInfo, SqliteValueRef};
+
+/// An FTS5 virtual table indexing columns of an existing table.
+///
+/// The index is an [external content table][external]: the text is only stored in the content
+/// table, and triggers on it keep the index up to date.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::SqliteConnection) -> sqlx::Result<()> {
+/// use sqlx::sqlite::{Fts5Table, MatchQuery};
+///
+/// let fts = Fts5Table::new("post_fts", "post")
+///     .columns(["title", "content"])
+///     .content_rowid("post_id")
+///     .tokenize("porter unicode61");
+///
+/// fts.create(&mut *conn).await?;
+///
+/// let matches = MatchQuery::terms("post_fts", ["sqlite", "search"])
+///     .limit(10)
+///     .fetch_all(&mut *conn)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [external]: https://www.sqlite.org/fts5.html#external_content_tables
+#[derive(Debug, Clone)]
+pub struct Fts5Table {
+    name: String,
+    content: String,
+    content_rowid: String,
+    columns: Vec<String>,
+    tokenize: Option<String>,
+    prefix: Vec<u32>,
+}
+
+impl Fts5Table {
+    /// Index the table named `content` in a new virtual table named `name`.
+    pub fn new(name: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            content: content.into(),
+            content_rowid: "rowid".into(),
+            columns: Vec::new(),
+            tokenize: None,
+            prefix: Vec::new(),
+        }
+    }
+
+    /// Index a column of the content table.
+    pub fn column(mut self, column: impl Into<String>) -> Self {
+        self.columns.push(column.into());
+        self
+    }
+
+    /// Index several columns of the content table.
+    pub fn columns<I>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.columns.extend(columns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set the `INTEGER PRIMARY KEY` column of the content table, if it is not `rowid`.
+    ///
+    /// This is the `rowid` of each row in the index.
+    pub fn content_rowid(mut self, column: impl Into<String>) -> Self {
+        self.content_rowid = column.into();
+        self
+    }
+
+    /// Set the [tokenizer](https://www.sqlite.org/fts5.html#tokenizers), e.g.
+    /// `porter unicode61` or `trigram`.
+    pub fn tokenize(mut self, tokenize: impl Into<String>) -> Self {
+        self.tokenize = Some(tokenize.into());
+        self
+    }
+
+    /// Build [prefix indexes](https://www.sqlite.org/fts5.html#prefix_indexes) for prefix
+    /// queries of these lengths, e.g. `sql*`.
+    pub fn prefix(mut self, lengths: &[u32]) -> Self {
+        self.prefix = lengths.to_vec();
+        self
+    }
+
+    /// The name of the virtual table.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The `CREATE VIRTUAL TABLE` statement for the index.
+    pub fn create_table_sql(&self) -> String {
+        let mut sql = format!("CREATE VIRTUAL TABLE {} USING fts5(", quote(&self.name));
+
+        for column in &self.columns {
+            write!(sql, "{}, ", quote(column)).expect("error writing to String");
+        }
+
+        write!(
+            sql,
+            "content={}, content_rowid={}",
+            quote_literal(&self.content),
+            quote_literal(&self.content_rowid)
+        )
+        .expect("error writing to String");
+
+        if let Some(tokenize) = &self.tokenize {
+            write!(sql, ", tokenize={}", quote_literal(tokenize)).expect("error writing to String");
+        }
+
+        if !self.prefix.is_empty() {
+            let prefix: Vec<String> = self.prefix.iter().map(u32::to_string).collect();
+
+            write!(sql, ", prefix={}", quote_literal(&prefix.join(" ")))
+                .expect("error writing to String");
+        }
+
+        sql.push_str(");\n");
+        sql
+    }
+
+    /// The triggers which keep the index in sync with the content table.
+    ///
+    /// An external content index can't tell which text is being removed, so deletes
+    /// and updates write the old values back with the special `'delete'` command.
+    pub fn triggers_sql(&self) -> String {
+        let table = quote(&self.name);
+        let content = quote(&self.content);
+
+        let columns: Vec<String> = self.columns.iter().map(|column| quote(column)).collect();
+        let columns = columns.join(", ");
+
+        let values = |row: &str| {
+            let values: Vec<String> = std::iter::once(&self.content_rowid)
+                .chain(&self.columns)
+                .map(|column| format!("{row}.{}", quote(column)))
+                .collect();
+
+            values.join(", ")
+        };
+
+        let new = format!(
+            "INSERT INTO {table}(rowid, {columns}) VALUES ({});",
+            values("new")
+        );
+        let old = format!(
+            "INSERT INTO {table}({table}, rowid, {columns}) VALUES ('delete', {});",
+            values("old")
+        );
+
+        let trigger = |event: &str| quote(&format!("{}_{event}", self.name));
+
+        format!(
+            "CREATE TRIGGER {ai} AFTER INSERT ON {content} BEGIN\n    {new}\nEND;\n\
+             CREATE TRIGGER {ad} AFTER DELETE ON {content} BEGIN\n    {old}\nEND;\n\
+             CREATE TRIGGER {au} AFTER UPDATE ON {content} BEGIN\n    {old}\n    {new}\nEND;\n",
+            ai = trigger("ai"),
+            ad = trigger("ad"),
+            au = trigger("au"),
+        )
+    }
+
+    /// The statement indexing the rows already in the content table.
+    pub fn rebuild_sql(&self) -> String {
+        format!(
+            "INSERT INTO {0}({0}) VALUES ('rebuild');\n",
+            quote(&self.name)
+        )
+    }
+
+    /// Create the index and its triggers, then index the existing rows.
+    pub fn up_sql(&self) -> String {
+        let mut sql = self.create_table_sql();
+        sql.push_str(&self.triggers_sql());
+        sql.push_str(&self.rebuild_sql());
+        sql
+    }
+
+    /// Drop the triggers and the index.
+    pub fn down_sql(&self) -> String {
+        let mut sql = String::new();
+
+        for event in ["ai", "ad", "au"] {
+            writeln!(
+                sql,
+                "DROP TRIGGER IF EXISTS {};",
+                quote(&format!("{}_{event}", self.name))
+            )
+            .expect("error writing to String");
+        }
+
+        writeln!(sql, "DROP TABLE IF EXISTS {};", quote(&self.name))
+            .expect("error writing to String");
+
+        sql
+    }
+
+    /// A migration running [`up_sql()`][Self::up_sql], to be added to a
+    /// [`Migrator`][sqlx_core::migrate::Migrator].
+    #[cfg(feature = "migrate")]
+    pub fn migration(&self, version: i64) -> sqlx_core::migrate::Migration {
+        sqlx_core::migrate::Migration::new(
+            version,
+            format!("create fts5 index {}", self.name).into(),
+            sqlx_core::migrate::MigrationType::Simple,
+            self.up_sql().into(),
+            false,
+        )
+    }
+
+    /// Run [`up_sql()`][Self::up_sql] in a transaction.
+    pub async fn create(&self, conn: &mut SqliteConnection) -> Result<(), Error> {
+        use sqlx_core::connection::Connection;
+
+        let mut tx = conn.begin().await?;
+        tx.execute(&*self.up_sql()).await?;
+        tx.commit().await
+    }
+}
+
+/// The relevance of a full-text match, as the `rank` of an FTS5 table or the value of `bm25()`.
+///
+/// Better matches have a lower, i.e. more negative, rank; they sort first in ascending order.
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Default)]
+pub struct FtsRank(pub f64);
+
+impl FtsRank {
+    /// The rank as a score, where better matches have a higher, positive score.
+    pub fn score(self) -> f64 {
+        -self.0
+    }
+}
+
+impl Type<Sqlite> for FtsRank {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo(DataType::Float)
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for FtsRank {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        <f64 as Decode<Sqlite>>::decode(value).map(FtsRank)
+    }
+}
+
+/// A row matched by a [`MatchQuery`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct FtsMatch {
+    /// The `rowid` of the matching row, i.e. its `content_rowid` in the content table.
+    pub rowid: i64,
+    /// The relevance of the match.
+    pub rank: FtsRank,
+    /// The snippet of the matching text, if one was requested.
+    pub snippet: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Snippet {
+    column: i64,
+    open: String,
+    close: String,
+    ellipsis: String,
+    tokens: u8,
+}
+
+/// A full-text query against an FTS5 table, returning the matching rows best first.
+#[derive(Debug, Clone)]
+pub struct MatchQuery {
+    table: String,
+    query: String,
+    weights: Vec<f64>,
+    snippet: Option<Snippet>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+impl MatchQuery {
+    /// Match `query` in the [FTS5 query syntax](https://www.sqlite.org/fts5.html#full_text_query_syntax).
+    pub fn new(table: impl Into<String>, query: impl Into<String>) -> Self {
+        Self {
+            table: table.into(),
+            query: query.into(),
+            weights: Vec::new(),
+            snippet: None,
+            limit: None,
+            offset: None,
+        }
+    }
+
+    /// Match rows containing all of `terms`.
+    ///
+    /// Each term is quoted as a string, so it can't contain query syntax such as `OR` or `*`.
+    pub fn terms<I>(table: impl Into<String>, terms: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let query = terms
+            .into_iter()
+            .map(|term| format!("\"{}\"", term.as_ref().replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" AND ");
+
+        Self::new(table, query)
+    }
+
+    /// Rank with `bm25()`, weighting matches in each column of the index by these weights,
+    /// in the order of the columns.
+    pub fn weights(mut self, weights: &[f64]) -> Self {
+        self.weights = weights.to_vec();
+        self
+    }
+
+    /// Return a snippet of the text in the column at `column` around the match, with the
+    /// matching terms between `open` and `close`.
+    pub fn snippet(
+        mut self,
+        column: i64,
+        open: impl Into<String>,
+        close: impl Into<String>,
+        ellipsis: impl Into<String>,
+        tokens: u8,
+    ) -> Self {
+        self.snippet = Some(Snippet {
+            column,
+            open: open.into(),
+            close: close.into(),
+            ellipsis: ellipsis.into(),
+            tokens,
+        });
+        self
+    }
+
+    /// Return at most `limit` rows.
+    pub fn limit(mut self, limit: i64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Skip the first `offset` rows.
+    pub fn offset(mut self, offset: i64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// The `SELECT` statement for this query.
+    ///
+    /// The query string is bound to `?1` and snippet markers to `?2` through `?4`.
+    pub fn sql(&self) -> String {
+        let table = quote(&self.table);
+
+        let rank = if self.weights.is_empty() {
+            "rank".to_owned()
+        } else {
+            let weights: Vec<String> = self.weights.iter().map(f64::to_string).collect();
+            format!("bm25({table}, {})", weights.join(", "))
+        };
+
+        let snippet = match &self.snippet {
+            Some(snippet) => format!(
+                "snippet({table}, {}, ?2, ?3, ?4, {})",
+                snippet.column, snippet.tokens
+            ),
+            None => "NULL".to_owned(),
+        };
+
+        let mut sql = format!(
+            "SELECT rowid, {rank}, {snippet} FROM {table} WHERE {table} MATCH ?1 ORDER BY {rank}"
+        );
+
+        // `OFFSET` requires a `LIMIT`, where `-1` is no limit
+        if self.limit.is_some() || self.offset.is_some() {
+            write!(sql, " LIMIT {}", self.limit.unwrap_or(-1)).expect("error writing to String");
+        }
+
+        if let Some(offset) = self.offset {
+            write!(sql, " OFFSET {offset}").expect("error writing to String");
+        }
+
+        sql
+    }
+
+    /// Run the query, returning the matching rows best first.
+    pub async fn fetch_all(&self, conn: &mut SqliteConnection) -> Result<Vec<FtsMatch>, Error> {
+        let sql = self.sql();
+        let mut query = query_as::<_, (i64, FtsRank, Option<String>)>(&sql).bind(&self.query);
+
+        if let Some(snippet) = &self.snippet {
+            query = query
+                .bind(&snippet.open)
+                .bind(&snippet.close)
+                .bind(&snippet.ellipsis);
+        }
+
+        let rows = query.fetch_all(conn).await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(rowid, rank, snippet)| FtsMatch {
+                rowid,
+                rank,
+                snippet,
+            })
+            .collect())
+    }
+}
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
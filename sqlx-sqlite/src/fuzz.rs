@@ -0,0 +1,60 @@
+//! Entry points for the fuzz targets in `fuzz/`; not part of the public API.
+
+use crate::connection::establish::EstablishParams;
+use crate::connection::{describe, execute};
+use crate::error::Error;
+use crate::SqliteConnectOptions;
+
+/// Tables, an index and a view for the queries to describe to refer to.
+const SCHEMA: &str = "
+    CREATE TABLE accounts (id INTEGER PRIMARY KEY, name TEXT NOT NULL, email TEXT UNIQUE);
+    CREATE TABLE posts (
+        id INTEGER PRIMARY KEY,
+        account_id INTEGER NOT NULL REFERENCES accounts (id),
+        title TEXT,
+        body BLOB,
+        score REAL DEFAULT 0
+    );
+    CREATE INDEX posts_by_account ON posts (account_id, score);
+    CREATE VIEW scores AS SELECT account_id, SUM(score) AS total FROM posts GROUP BY account_id;
+";
+
+/// Describe `query` on an in-memory database with a fixed schema, as the query macros do,
+/// which interprets the program SQLite compiles the query to when its column types are not
+/// declared.
+pub fn describe(query: &str) -> Result<(), Error> {
+    let options: SqliteConnectOptions = "sqlite::memory:".parse()?;
+    let mut conn = EstablishParams::from_options(&options)?.establish()?;
+
+    execute::iter(&mut conn, SCHEMA, None, false)?.finish()?;
+
+    describe::describe(&mut conn, query)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Queries like the seeds of the fuzz target.
+    const QUERIES: &[&str] = &[
+        "SELECT coalesce(title, 'untitled'), length(body), max(score), typeof(id) \
+         FROM posts GROUP BY account_id HAVING count(*) > 1",
+        "INSERT INTO posts (account_id, title, body) VALUES (?, ?, ?) RETURNING id, score",
+        "SELECT a.id, a.name, p.title FROM accounts a LEFT JOIN posts p ON p.account_id = a.id \
+         WHERE a.email = ?1",
+        "WITH RECURSIVE n(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n WHERE x < 10) \
+         SELECT x, (SELECT COUNT(*) FROM posts WHERE score > x) FROM n",
+        "UPDATE posts SET score = score + 1 \
+         WHERE id IN (SELECT id FROM posts WHERE account_id = ?) RETURNING *",
+        "SELECT account_id, total, total > 10 AS popular FROM scores ORDER BY total DESC LIMIT 5",
+    ];
+
+    #[test]
+    fn it_describes_the_seeds() {
+        for query in QUERIES {
+            describe(query).unwrap();
+        }
+    }
+}
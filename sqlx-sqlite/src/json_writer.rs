@@ -0,0 +1,27 @@
+use sqlx_core::json_writer::{bytes_to_json, float_to_json, DecodeJson};
+use sqlx_core::types::JsonValue;
+
+use crate::error::BoxDynError;
+use crate::type_info::DataType;
+use crate::value::ValueRef;
+use crate::{Sqlite, SqliteValueRef};
+
+impl DecodeJson for Sqlite {
+    fn decode_json(value: SqliteValueRef<'_>) -> Result<JsonValue, BoxDynError> {
+        // the storage class of the value itself, as SQLite doesn't enforce declared types;
+        // booleans are stored as integers and are written as such
+        Ok(match value.type_info().0 {
+            DataType::Null => JsonValue::Null,
+            DataType::Float => float_to_json(value.double()),
+            DataType::Blob => bytes_to_json(value.blob()),
+            DataType::Text
+            | DataType::Date
+            | DataType::Time
+            | DataType::Datetime
+            | DataType::Uuid => value.text()?.into(),
+            DataType::Integer | DataType::Int4 | DataType::Bool | DataType::Numeric => {
+                value.int64().into()
+            }
+        })
+    }
+}
@@ -49,9 +49,13 @@ pub use column::SqliteColumn;
 pub use connection::serialize::SqliteOwnedBuf;
 #[cfg(feature = "preupdate-hook")]
 pub use connection::PreupdateHookResult;
-pub use connection::{LockedSqliteHandle, SqliteConnection, SqliteOperation, UpdateHookResult};
+pub use connection::{
+    LockedSqliteHandle, SqliteConnection, SqliteFunction, SqliteFunctionArgs, SqliteOperation,
+    UpdateHookResult,
+};
 pub use database::Sqlite;
 pub use error::SqliteError;
+pub use fts::{Fts5Table, FtsMatch, FtsRank, MatchQuery};
 pub use options::{
     SqliteAutoVacuum, SqliteConnectOptions, SqliteJournalMode, SqliteLockingMode, SqliteSynchronous,
 };
@@ -75,6 +79,13 @@ mod column;
 mod connection;
 mod database;
 mod error;
+mod estimate;
+mod fts;
+#[cfg(any(test, feature = "fuzz"))]
+#[doc(hidden)]
+pub mod fuzz;
+#[cfg(feature = "json")]
+mod json_writer;
 mod logger;
 mod options;
 mod query_result;
@@ -3,16 +3,18 @@ use crate::error::Error;
 use crate::executor::Executor;
 use crate::fs;
 use crate::migrate::MigrateError;
-use crate::migrate::{AppliedMigration, Migration};
+use crate::migrate::{AppliedMigration, Migration, MigrationMetadata};
 use crate::migrate::{Migrate, MigrateDatabase};
 use crate::query::query;
 use crate::query_as::query_as;
+use crate::query_scalar::query_scalar;
 use crate::{Sqlite, SqliteConnectOptions, SqliteConnection, SqliteJournalMode};
 use futures_core::future::BoxFuture;
+use std::fmt::Write;
 use std::str::FromStr;
 use std::sync::atomic::Ordering;
-use std::time::Duration;
 use std::time::Instant;
+use std::time::{Duration, UNIX_EPOCH};
 
 pub(crate) use sqlx_core::migrate::*;
 
@@ -64,33 +66,86 @@ impl MigrateDatabase for Sqlite {
 }
 
 impl Migrate for SqliteConnection {
-    fn ensure_migrations_table(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+    fn ensure_migrations_table<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            self.execute(&*create_migrations_table_sql(table_name))
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn migrations_table_version<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<u32>, MigrateError>> {
         Box::pin(async move {
+            let (schema, table) = split_table_name(table_name);
+
+            // Each version is recognized by a column it added.
             // language=SQLite
-            self.execute(
-                r#"
-CREATE TABLE IF NOT EXISTS _sqlx_migrations (
-    version BIGINT PRIMARY KEY,
-    description TEXT NOT NULL,
-    installed_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
-    success BOOLEAN NOT NULL,
-    checksum BLOB NOT NULL,
-    execution_time BIGINT NOT NULL
-);
-                "#,
-            )
-            .await?;
+            let columns: Vec<String> = query_scalar("SELECT name FROM pragma_table_info(?1, ?2)")
+                .bind(table)
+                .bind(schema)
+                .fetch_all(&mut *self)
+                .await?;
+
+            Ok(match () {
+                _ if columns.is_empty() => None,
+                _ if columns.iter().any(|c| c == "skipped") => Some(3),
+                _ if columns.iter().any(|c| c == "app_version") => Some(2),
+                _ => Some(1),
+            })
+        })
+    }
+
+    fn upgrade_migrations_table<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        from: u32,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            let mut tx = self.begin().await?;
+
+            if from < 2 {
+                // SQLite can only add a single column per statement.
+                // language=SQLite
+                tx.execute(&*format!(
+                    r#"
+ALTER TABLE {table_name} ADD COLUMN applied_by TEXT;
+ALTER TABLE {table_name} ADD COLUMN hostname TEXT;
+ALTER TABLE {table_name} ADD COLUMN app_version TEXT;
+                    "#
+                ))
+                .await?;
+            }
+
+            if from < 3 {
+                // language=SQLite
+                tx.execute(&*format!(
+                    "ALTER TABLE {table_name} ADD COLUMN skipped BOOLEAN NOT NULL DEFAULT FALSE"
+                ))
+                .await?;
+            }
+
+            tx.commit().await?;
 
             Ok(())
         })
     }
 
-    fn dirty_version(&mut self) -> BoxFuture<'_, Result<Option<i64>, MigrateError>> {
+    fn dirty_version<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Option<i64>, MigrateError>> {
         Box::pin(async move {
             // language=SQLite
-            let row: Option<(i64,)> = query_as(
-                "SELECT version FROM _sqlx_migrations WHERE success = false ORDER BY version LIMIT 1",
-            )
+            let row: Option<(i64,)> = query_as(&format!(
+                "SELECT version FROM {table_name} WHERE success = false ORDER BY version LIMIT 1"
+            ))
             .fetch_optional(self)
             .await?;
 
@@ -98,22 +153,50 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
         })
     }
 
-    fn list_applied_migrations(
-        &mut self,
-    ) -> BoxFuture<'_, Result<Vec<AppliedMigration>, MigrateError>> {
+    fn list_applied_migrations<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>> {
         Box::pin(async move {
             // language=SQLite
-            let rows: Vec<(i64, Vec<u8>)> =
-                query_as("SELECT version, checksum FROM _sqlx_migrations ORDER BY version")
-                    .fetch_all(self)
-                    .await?;
+            let rows: Vec<AppliedMigrationRow> = query_as(&format!(
+                r#"
+SELECT version, checksum, execution_time, applied_by, hostname, app_version,
+    CAST(strftime('%s', installed_on) AS INTEGER), skipped
+FROM {table_name}
+ORDER BY version
+                "#
+            ))
+            .fetch_all(self)
+            .await?;
 
             let migrations = rows
                 .into_iter()
-                .map(|(version, checksum)| AppliedMigration {
-                    version,
-                    checksum: checksum.into(),
-                })
+                .map(
+                    |(
+                        version,
+                        checksum,
+                        execution_time,
+                        applied_by,
+                        hostname,
+                        app_version,
+                        installed_on,
+                        skipped,
+                    )| {
+                        let mut migration = AppliedMigration::new(version, checksum.into());
+                        // `-1` until the time of a completed migration has been recorded
+                        migration.execution_time =
+                            u64::try_from(execution_time).ok().map(Duration::from_nanos);
+                        migration.applied_by = applied_by;
+                        migration.hostname = hostname;
+                        migration.app_version = app_version;
+                        migration.installed_on = installed_on
+                            .and_then(|secs| u64::try_from(secs).ok())
+                            .map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+                        migration.skipped = skipped;
+                        migration
+                    },
+                )
                 .collect();
 
             Ok(migrations)
@@ -130,63 +213,84 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
 
     fn apply<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
         Box::pin(async move {
-            let mut tx = self.begin().await?;
-            let start = Instant::now();
-
-            // Use a single transaction for the actual migration script and the essential bookeeping so we never
-            // execute migrations twice. See https://github.com/launchbadge/sqlx/issues/1966.
-            // The `execution_time` however can only be measured for the whole transaction. This value _only_ exists for
-            // data lineage and debugging reasons, so it is not super important if it is lost. So we initialize it to -1
-            // and update it once the actual transaction completed.
-            let _ = tx
-                .execute(&*migration.sql)
-                .await
-                .map_err(|e| MigrateError::ExecuteMigration(e, migration.version))?;
-
-            // language=SQL
-            let _ = query(
-                r#"
-    INSERT INTO _sqlx_migrations ( version, description, success, checksum, execution_time )
-    VALUES ( ?1, ?2, TRUE, ?3, -1 )
-                "#,
-            )
-            .bind(migration.version)
-            .bind(&*migration.description)
-            .bind(&*migration.checksum)
-            .execute(&mut *tx)
-            .await?;
-
-            tx.commit().await?;
-
-            // Update `elapsed_time`.
-            // NOTE: The process may disconnect/die at this point, so the elapsed time value might be lost. We accept
-            //       this small risk since this value is not super important.
+            let metadata = MigrationMetadata::current();
+            apply_migration(self, table_name, migration, &metadata).await
+        })
+    }
 
-            let elapsed = start.elapsed();
+    fn apply_with_metadata<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+        metadata: &'m MigrationMetadata,
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        Box::pin(apply_migration(self, table_name, migration, metadata))
+    }
 
-            // language=SQL
-            #[allow(clippy::cast_possible_truncation)]
-            let _ = query(
-                r#"
-    UPDATE _sqlx_migrations
-    SET execution_time = ?1
-    WHERE version = ?2
-                "#,
-            )
-            .bind(elapsed.as_nanos() as i64)
-            .bind(migration.version)
-            .execute(self)
-            .await?;
+    fn ensure_migrations_table_script(&mut self, table_name: &str) -> Result<String, MigrateError> {
+        Ok(create_migrations_table_sql(table_name))
+    }
 
-            Ok(elapsed)
-        })
+    fn apply_script(
+        &mut self,
+        table_name: &str,
+        migration: &Migration,
+        metadata: &MigrationMetadata,
+    ) -> Result<String, MigrateError> {
+        let mut script = String::from("BEGIN;\n\n");
+
+        script.push_str(migration.sql()?.trim());
+        if !script.ends_with(';') {
+            script.push(';');
+        }
+
+        if migration.migration_type.is_repeatable() {
+            // language=SQLite
+            let _ = write!(
+                script,
+                "\n\nDELETE FROM {table_name} WHERE version = {};",
+                migration.version
+            );
+        }
+
+        let mut checksum = String::new();
+        for byte in &*migration.checksum {
+            let _ = write!(checksum, "{byte:02x}");
+        }
+
+        // language=SQLite
+        script.push_str(&format!(
+            r#"
+
+INSERT INTO {table_name} (
+    version, description, success, checksum, execution_time, hostname, app_version
+)
+VALUES ( {}, {}, TRUE, X'{checksum}', -1, {}, {} );
+
+COMMIT;
+"#,
+            migration.version,
+            quote_literal(&migration.description),
+            metadata
+                .hostname
+                .as_deref()
+                .map_or("NULL".into(), quote_literal),
+            metadata
+                .app_version
+                .as_deref()
+                .map_or("NULL".into(), quote_literal),
+        ));
+
+        Ok(script)
     }
 
     fn revert<'e: 'm, 'm>(
         &'e mut self,
+        table_name: &'m str,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
         Box::pin(async move {
@@ -195,10 +299,10 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
             let mut tx = self.begin().await?;
             let start = Instant::now();
 
-            let _ = tx.execute(&*migration.sql).await?;
+            let _ = tx.execute(&*migration.sql()?).await?;
 
             // language=SQL
-            let _ = query(r#"DELETE FROM _sqlx_migrations WHERE version = ?1"#)
+            let _ = query(&format!(r#"DELETE FROM {table_name} WHERE version = ?1"#))
                 .bind(migration.version)
                 .execute(&mut *tx)
                 .await?;
@@ -210,4 +314,205 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
             Ok(elapsed)
         })
     }
+
+    fn mark_applied<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+        metadata: &'m MigrationMetadata,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(record_unrun_migration(
+            self, table_name, migration, metadata, false,
+        ))
+    }
+
+    fn mark_skipped<'e: 'm, 'm>(
+        &'e mut self,
+        table_name: &'m str,
+        migration: &'m Migration,
+        metadata: &'m MigrationMetadata,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(record_unrun_migration(
+            self, table_name, migration, metadata, true,
+        ))
+    }
+
+    fn update_checksum<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        version: i64,
+        checksum: &'e [u8],
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=SQL
+            let _ = query(&format!(
+                r#"UPDATE {table_name} SET checksum = ?1 WHERE version = ?2"#
+            ))
+            .bind(checksum)
+            .bind(version)
+            .execute(self)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn remove_applied_migration<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        version: i64,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=SQL
+            let _ = query(&format!(r#"DELETE FROM {table_name} WHERE version = ?1"#))
+                .bind(version)
+                .execute(self)
+                .await?;
+
+            Ok(())
+        })
+    }
+}
+
+fn create_migrations_table_sql(table_name: &str) -> String {
+    // language=SQLite
+    format!(
+        r#"
+CREATE TABLE IF NOT EXISTS {table_name} (
+    version BIGINT PRIMARY KEY,
+    description TEXT NOT NULL,
+    installed_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    success BOOLEAN NOT NULL,
+    checksum BLOB NOT NULL,
+    execution_time BIGINT NOT NULL,
+    applied_by TEXT,
+    hostname TEXT,
+    app_version TEXT,
+    skipped BOOLEAN NOT NULL DEFAULT FALSE
+);
+"#
+    )
+}
+
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+type AppliedMigrationRow = (
+    i64,
+    Vec<u8>,
+    i64,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<i64>,
+    bool,
+);
+
+/// Record `migration` as applied without running it, either because it was applied by other
+/// means or because it is `skipped`.
+async fn record_unrun_migration(
+    conn: &mut SqliteConnection,
+    table_name: &str,
+    migration: &Migration,
+    metadata: &MigrationMetadata,
+    skipped: bool,
+) -> Result<(), MigrateError> {
+    // language=SQL
+    let _ = query(&format!(
+        r#"
+    INSERT INTO {table_name} (
+        version, description, success, checksum, execution_time, hostname, app_version, skipped
+    )
+    VALUES ( ?1, ?2, TRUE, ?3, -1, ?4, ?5, ?6 )
+        "#
+    ))
+    .bind(migration.version)
+    .bind(&*migration.description)
+    .bind(&*migration.checksum)
+    .bind(&metadata.hostname)
+    .bind(&metadata.app_version)
+    .bind(skipped)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn apply_migration(
+    conn: &mut SqliteConnection,
+    table_name: &str,
+    migration: &Migration,
+    metadata: &MigrationMetadata,
+) -> Result<Duration, MigrateError> {
+    let mut tx = conn.begin().await?;
+    let start = Instant::now();
+
+    // Use a single transaction for the actual migration script and the essential bookeeping so we never
+    // execute migrations twice. See https://github.com/launchbadge/sqlx/issues/1966.
+    // The `execution_time` however can only be measured for the whole transaction. This value _only_ exists for
+    // data lineage and debugging reasons, so it is not super important if it is lost. So we initialize it to -1
+    // and update it once the actual transaction completed.
+    let _ = tx
+        .execute(&*migration.sql()?)
+        .await
+        .map_err(|e| MigrateError::ExecuteMigration(e, migration.version))?;
+
+    // a repeatable migration replaces the record of its previous run
+    if migration.migration_type.is_repeatable() {
+        // language=SQL
+        let _ = query(&format!(r#"DELETE FROM {table_name} WHERE version = ?1"#))
+            .bind(migration.version)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    // language=SQL
+    let _ = query(&format!(
+        r#"
+    INSERT INTO {table_name} (
+        version, description, success, checksum, execution_time, hostname, app_version
+    )
+    VALUES ( ?1, ?2, TRUE, ?3, -1, ?4, ?5 )
+        "#
+    ))
+    .bind(migration.version)
+    .bind(&*migration.description)
+    .bind(&*migration.checksum)
+    .bind(&metadata.hostname)
+    .bind(&metadata.app_version)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    // Update `elapsed_time`.
+    // NOTE: The process may disconnect/die at this point, so the elapsed time value might be lost. We accept
+    //       this small risk since this value is not super important.
+
+    let elapsed = start.elapsed();
+
+    // language=SQL
+    #[allow(clippy::cast_possible_truncation)]
+    let _ = query(&format!(
+        r#"
+    UPDATE {table_name}
+    SET execution_time = ?1
+    WHERE version = ?2
+        "#
+    ))
+    .bind(elapsed.as_nanos() as i64)
+    .bind(migration.version)
+    .execute(conn)
+    .await?;
+
+    Ok(elapsed)
+}
+
+// splits a possibly schema-qualified table name into its schema and table
+fn split_table_name(table_name: &str) -> (Option<&str>, &str) {
+    match table_name.rsplit_once('.') {
+        Some((schema, table)) => (Some(schema), table),
+        None => (None, table_name),
+    }
 }
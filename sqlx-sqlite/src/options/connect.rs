@@ -46,6 +46,14 @@ impl ConnectOptions for SqliteConnectOptions {
                 }
             }
 
+            if !self.functions.is_empty() {
+                let mut locked = conn.lock_handle().await?;
+
+                for function in &self.functions {
+                    locked.create_function(function)?;
+                }
+            }
+
             Ok(conn)
         })
     }
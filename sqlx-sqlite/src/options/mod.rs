@@ -18,6 +18,7 @@ pub use synchronous::SqliteSynchronous;
 
 use crate::common::DebugFn;
 use crate::connection::collation::Collation;
+use crate::connection::SqliteFunction;
 use sqlx_core::IndexMap;
 
 /// Options and flags which can be used to configure a SQLite connection.
@@ -82,12 +83,15 @@ pub struct SqliteConnectOptions {
     pub(crate) row_channel_size: usize,
 
     pub(crate) collations: Vec<Collation>,
+    pub(crate) functions: Vec<SqliteFunction>,
 
     pub(crate) serialized: bool,
     pub(crate) thread_name: Arc<DebugFn<dyn Fn(u64) -> String + Send + Sync + 'static>>,
 
     pub(crate) optimize_on_close: OptimizeOnClose,
 
+    pub(crate) reject_nan: bool,
+
     #[cfg(feature = "regexp")]
     pub(crate) register_regexp_function: bool,
 }
@@ -205,11 +209,13 @@ impl SqliteConnectOptions {
             pragmas,
             extensions: Default::default(),
             collations: Default::default(),
+            functions: Default::default(),
             serialized: false,
             thread_name: Arc::new(DebugFn(|id| format!("sqlx-sqlite-worker-{id}"))),
             command_channel_size: 50,
             row_channel_size: 50,
             optimize_on_close: OptimizeOnClose::Disabled,
+            reject_nan: false,
             #[cfg(feature = "regexp")]
             register_regexp_function: false,
         }
@@ -363,6 +369,11 @@ impl SqliteConnectOptions {
 
     /// Add a custom collation for comparing strings in SQL.
     ///
+    /// The collation is registered on every connection opened with these options, so every
+    /// connection of a pool created with them can use it, e.g. in `ORDER BY name COLLATE <name>`
+    /// or in the definition of a column. Use [`LockedSqliteHandle::create_collation()`] to add
+    /// one to a single connection instead.
+    ///
     /// If a collation with the same name already exists, it will be replaced.
     ///
     /// See [`sqlite3_create_collation()`](https://www.sqlite.org/c3ref/create_collation.html) for details.
@@ -377,6 +388,20 @@ impl SqliteConnectOptions {
     /// >
     /// > If a collating function fails any of the above constraints and that collating function is
     /// > registered and used, then the behavior of SQLite is undefined.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_sqlite::SqliteConnectOptions;
+    /// // sort words by their lowercase form, then by the original to stay consistent with `=`
+    /// let options = SqliteConnectOptions::new().collation("nocase_unicode", |a, b| {
+    ///     a.to_lowercase()
+    ///         .cmp(&b.to_lowercase())
+    ///         .then_with(|| a.cmp(b))
+    /// });
+    /// ```
+    ///
+    /// [`LockedSqliteHandle::create_collation()`]: crate::LockedSqliteHandle::create_collation
     pub fn collation<N, F>(mut self, name: N, collate: F) -> Self
     where
         N: Into<Arc<str>>,
@@ -386,6 +411,23 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Add a function implemented in Rust, which can be called from SQL.
+    ///
+    /// The function is registered on every connection opened with these options, so every
+    /// connection of a pool created with them can use it. Use
+    /// [`SqliteConnection::create_function()`] to add one to a single connection instead.
+    ///
+    /// If a function with the same name and number of arguments already exists, it will be
+    /// replaced.
+    ///
+    /// See [`SqliteFunction`] for details and examples.
+    ///
+    /// [`SqliteConnection::create_function()`]: crate::SqliteConnection::create_function
+    pub fn function(mut self, function: SqliteFunction) -> Self {
+        self.functions.push(function);
+        self
+    }
+
     /// Set to `true` to signal to SQLite that the database file is on read-only media.
     ///
     /// If enabled, SQLite assumes the database file _cannot_ be modified, even by higher
@@ -425,6 +467,17 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Sets whether queries binding a `NaN` `f32` or `f64` fail with [`Error::Encode`].
+    ///
+    /// SQLite has no `NaN` value and binds one as `NULL`, which otherwise only comes to light when
+    /// it is read back. The default is `false`, binding `NaN` as SQLite does.
+    ///
+    /// [`Error::Encode`]: crate::Error::Encode
+    pub fn reject_nan(mut self, reject: bool) -> Self {
+        self.reject_nan = reject;
+        self
+    }
+
     /// Provide a callback to generate the name of the background worker thread.
     ///
     /// The value passed to the callback is an auto-incremented integer for use as the thread ID.
@@ -6,6 +6,7 @@ use sqlx_core::column::ColumnIndex;
 use sqlx_core::error::Error;
 use sqlx_core::ext::ustr::UStr;
 use sqlx_core::row::Row;
+use sqlx_core::value::ValueRef;
 use sqlx_core::HashMap;
 
 use crate::statement::StatementHandle;
@@ -52,6 +53,23 @@ impl SqliteRow {
     }
 }
 
+impl SqliteRow {
+    /// The value of a column exactly as SQLite stored it, or `None` if it is `NULL`.
+    ///
+    /// These are the bytes of a `TEXT` or `BLOB` value; `INTEGER` and `REAL` values are converted
+    /// to their text by SQLite. The storage class of the value is the
+    /// [type info][sqlx_core::value::ValueRef::type_info] of [`try_get_raw()`][Row::try_get_raw].
+    pub fn raw_value<I>(&self, index: I) -> Result<Option<&[u8]>, Error>
+    where
+        I: ColumnIndex<Self>,
+    {
+        let index = index.index(self)?;
+        let value = SqliteValueRef::value(&self.values[index]);
+
+        Ok((!value.is_null()).then(|| value.blob()))
+    }
+}
+
 impl Row for SqliteRow {
     type Database = Sqlite;
 
@@ -1,12 +1,21 @@
 use crate::error::Error;
+use crate::executor::Executor;
 use crate::pool::PoolOptions;
-use crate::testing::{FixtureSnapshot, TestArgs, TestContext, TestSupport};
+use crate::testing::{FixtureSnapshot, QueryPlan, TestArgs, TestContext, TestSupport};
+use crate::SqliteConnection;
 use crate::{Sqlite, SqliteConnectOptions};
 use futures_core::future::BoxFuture;
+use sqlx_core::connection::Connection;
+use sqlx_core::query::query;
+use sqlx_core::query_scalar::query_scalar;
+use sqlx_core::row::Row;
 use std::path::{Path, PathBuf};
 
 pub(crate) use sqlx_core::testing::*;
 
+#[cfg(feature = "proptest")]
+mod roundtrip;
+
 const BASE_PATH: &str = "target/sqlx/test-dbs";
 
 impl TestSupport for Sqlite {
@@ -34,6 +43,77 @@ impl TestSupport for Sqlite {
     fn db_name(args: &TestArgs) -> String {
         convert_path(args.test_path)
     }
+
+    fn explain<'c>(
+        conn: &'c mut SqliteConnection,
+        query: &'c str,
+    ) -> BoxFuture<'c, Result<QueryPlan, Error>> {
+        Box::pin(async move {
+            let rows = conn
+                .fetch_all(&*format!("EXPLAIN QUERY PLAN {query}"))
+                .await?;
+
+            let mut plan = QueryPlan::default();
+
+            for row in rows {
+                let detail: String = row.try_get("detail")?;
+
+                // e.g. `SEARCH users USING INDEX users_email_idx (email=?)`
+                if let Some((_, index)) = detail
+                    .split_once("USING INDEX ")
+                    .or_else(|| detail.split_once("USING COVERING INDEX "))
+                {
+                    if let Some(index) = index.split_whitespace().next() {
+                        plan.indexes.push(index.to_owned());
+                    }
+                }
+
+                plan.lines.push(detail);
+            }
+
+            Ok(plan)
+        })
+    }
+
+    fn clean_tables<'c>(
+        conn: &'c mut SqliteConnection,
+        tables: &'c [&'c str],
+    ) -> BoxFuture<'c, Result<(), Error>> {
+        Box::pin(async move {
+            let mut tx = conn.begin().await?;
+
+            // `PRAGMA foreign_keys` can't be changed inside a transaction, but the checks can
+            // be deferred to the commit, when all of the tables are empty.
+            tx.execute("PRAGMA defer_foreign_keys = ON").await?;
+
+            for table in tables {
+                tx.execute(&*format!("DELETE FROM {table}")).await?;
+            }
+
+            // Only exists once a table with `AUTOINCREMENT` has been created.
+            let has_sequences: bool = query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE name = 'sqlite_sequence')",
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+
+            if has_sequences {
+                for table in tables {
+                    // `sqlite_sequence` holds unqualified names
+                    let name = table.rsplit('.').next().unwrap_or(table);
+
+                    query("DELETE FROM sqlite_sequence WHERE name = ?1")
+                        .bind(name.trim_matches('"'))
+                        .execute(&mut *tx)
+                        .await?;
+                }
+            }
+
+            tx.commit().await?;
+
+            Ok(())
+        })
+    }
 }
 
 async fn test_context(args: &TestArgs) -> Result<TestContext<Sqlite>, Error> {
@@ -0,0 +1,66 @@
+use sqlx_core::testing::proptest::prelude::*;
+use sqlx_core::testing::Roundtrip;
+
+use crate::Sqlite;
+
+macro_rules! impl_roundtrip {
+    ($($ty:ty => $strategy:expr),* $(,)?) => {
+        $(
+            impl Roundtrip<Sqlite> for $ty {
+                fn strategy() -> BoxedStrategy<Self> {
+                    $strategy.boxed()
+                }
+            }
+        )*
+    };
+}
+
+impl_roundtrip!(
+    bool => any::<bool>(),
+    i8 => any::<i8>(),
+    i16 => any::<i16>(),
+    i32 => any::<i32>(),
+    i64 => any::<i64>(),
+    u8 => any::<u8>(),
+    u16 => any::<u16>(),
+    u32 => any::<u32>(),
+    // `NaN` is refused by the encoder
+    f32 => any::<f32>().prop_filter("NaN", |v| !v.is_nan()),
+    f64 => any::<f64>().prop_filter("NaN", |v| !v.is_nan()),
+    String => any::<String>(),
+    Vec<u8> => any::<Vec<u8>>(),
+);
+
+#[cfg(feature = "chrono")]
+mod chrono {
+    use ::chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+    use sqlx_core::testing::strategy::chrono::{date, time_micros};
+
+    use super::*;
+
+    // stored as text, with the four-digit years of ISO 8601
+    impl_roundtrip!(
+        NaiveDate => date(1..=9999),
+        NaiveTime => time_micros(),
+        NaiveDateTime => (date(1..=9999), time_micros()).prop_map(|(d, t)| d.and_time(t)),
+    );
+}
+
+#[cfg(feature = "time")]
+mod time {
+    use ::time::{Date, PrimitiveDateTime, Time};
+    use sqlx_core::testing::strategy::time::{date, time_micros};
+
+    use super::*;
+
+    // stored as text, with the four-digit years of ISO 8601
+    impl_roundtrip!(
+        Date => date(1..=9999),
+        Time => time_micros(),
+        PrimitiveDateTime =>
+            (date(1..=9999), time_micros()).prop_map(|(d, t)| PrimitiveDateTime::new(d, t)),
+    );
+}
+
+#[cfg(feature = "uuid")]
+impl_roundtrip!(uuid::Uuid => any::<u128>().prop_map(uuid::Uuid::from_u128));
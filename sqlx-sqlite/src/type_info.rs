@@ -35,6 +35,9 @@ pub(crate) enum DataType {
     Date,
     Time,
     Datetime,
+    /// Chosen if the column's declared type is `UUID`;
+    /// the values may be stored as a 16-byte `BLOB` or as `TEXT`.
+    Uuid,
 }
 
 /// Type information for a SQLite type.
@@ -67,6 +70,7 @@ impl TypeInfo for SqliteTypeInfo {
             DataType::Date => "DATE",
             DataType::Time => "TIME",
             DataType::Datetime => "DATETIME",
+            DataType::Uuid => "UUID",
         }
     }
 }
@@ -103,6 +107,8 @@ impl FromStr for DataType {
             "time" => DataType::Time,
             "datetime" | "timestamp" => DataType::Datetime,
 
+            "uuid" => DataType::Uuid,
+
             _ if s.contains("int") => DataType::Integer,
 
             _ if s.contains("char") || s.contains("clob") || s.contains("text") => DataType::Text,
@@ -157,5 +163,7 @@ fn test_data_type_from_str() -> Result<(), BoxDynError> {
     assert_eq!(DataType::Time, "TIME".parse()?);
     assert_eq!(DataType::Date, "DATE".parse()?);
 
+    assert_eq!(DataType::Uuid, "UUID".parse()?);
+
     Ok(())
 }
@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::type_info::DataType;
+use crate::types::Type;
+use crate::value::ValueRef;
+use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+
+impl Type<Sqlite> for Duration {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo(DataType::Float)
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        matches!(ty.0, DataType::Float | DataType::Integer | DataType::Int4)
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for Duration {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<SqliteArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        args.push(SqliteArgumentValue::Double(self.as_secs_f64()));
+
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for Duration {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Duration, BoxDynError> {
+        if value.type_info().0 == DataType::Float {
+            let seconds = value.double();
+
+            return Duration::try_from_secs_f64(seconds).map_err(|_| {
+                format!("`std::time::Duration` cannot be decoded from {seconds} seconds").into()
+            });
+        }
+
+        let seconds = value.int64();
+
+        u64::try_from(seconds)
+            .map(Duration::from_secs)
+            .map_err(|_| {
+                format!("`std::time::Duration` cannot be decoded from {seconds} seconds").into()
+            })
+    }
+}
@@ -1,6 +1,6 @@
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
-use crate::error::BoxDynError;
+use crate::error::{BoxDynError, IntegerOutOfRangeError};
 use crate::type_info::DataType;
 use crate::types::Type;
 use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
@@ -32,7 +32,7 @@ impl<'r> Decode<'r, Sqlite> for i8 {
         // which leads to bugs, e.g.:
         // https://github.com/launchbadge/sqlx/issues/3179
         // Similar bug in Postgres: https://github.com/launchbadge/sqlx/issues/3161
-        Ok(value.int64().try_into()?)
+        Ok(IntegerOutOfRangeError::convert(value.int64())?)
     }
 }
 
@@ -59,7 +59,7 @@ impl<'q> Encode<'q, Sqlite> for i16 {
 
 impl<'r> Decode<'r, Sqlite> for i16 {
     fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(value.int64().try_into()?)
+        Ok(IntegerOutOfRangeError::convert(value.int64())?)
     }
 }
 
@@ -86,7 +86,7 @@ impl<'q> Encode<'q, Sqlite> for i32 {
 
 impl<'r> Decode<'r, Sqlite> for i32 {
     fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(value.int64().try_into()?)
+        Ok(IntegerOutOfRangeError::convert(value.int64())?)
     }
 }
 
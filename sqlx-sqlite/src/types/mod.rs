@@ -13,10 +13,33 @@
 //! | `u16`                                 | INTEGER                                              |
 //! | `u32`                                 | INTEGER                                              |
 //! | `u64`                                 | INTEGER (Decode only; see note)                      |
-//! | `f32`                                 | REAL                                                 |
-//! | `f64`                                 | REAL                                                 |
+//! | `f32`                                 | REAL (see note)                                      |
+//! | `f64`                                 | REAL (see note)                                      |
 //! | `&str`, [`String`]                    | TEXT                                                 |
 //! | `&[u8]`, `Vec<u8>`                    | BLOB                                                 |
+//! | `sqlx::types::CiString`               | TEXT COLLATE NOCASE                                  |
+//! | `sqlx::types::Vector`                 | BLOB, TEXT (see note)                                |
+//! | `std::time::Duration`                 | REAL, INTEGER (see note)                             |
+//!
+//! #### Note: `NaN`
+//! SQLite has no `NaN` value and binds one as `NULL`. To catch this, set
+//! [`SqliteConnectOptions::reject_nan()`][crate::SqliteConnectOptions::reject_nan], and queries
+//! binding a `NaN` `f32` or `f64` fail instead; bind an `Option` to store `NULL` deliberately.
+//! Infinities are stored and decoded as such.
+//!
+//! #### Note: Durations
+//! SQLite has no interval type, so a `Duration` is encoded as a `REAL` number of seconds, which
+//! can be used in date arithmetic such as `unixepoch() + ?` or
+//! `datetime('now', '+' || ? || ' seconds')`. It decodes from a non-negative number of seconds,
+//! either `REAL` or `INTEGER`.
+//!
+//! #### Note: Vectors
+//! `Vector` encodes to the `BLOB` format of [sqlite-vec], an array of little-endian `f32`, and
+//! also decodes from its JSON text format (e.g. `'[1,2.5]'`). The sqlite-vec extension must be
+//! loaded to use the distance functions in [`QueryBuilder::push_vector_distance()`].
+//!
+//! [sqlite-vec]: https://github.com/asg017/sqlite-vec
+//! [`QueryBuilder::push_vector_distance()`]: sqlx_core::query_builder::QueryBuilder::push_vector_distance
 //!
 //! #### Note: Unsigned Integers
 //! Decoding of unsigned integer types simply performs a checked conversion
@@ -148,10 +171,13 @@
 //!
 //! | Rust type                             | Sqlite type(s)                                       |
 //! |---------------------------------------|------------------------------------------------------|
-//! | `uuid::Uuid`                          | BLOB, TEXT                                           |
+//! | `uuid::Uuid`                          | UUID, BLOB, TEXT                                     |
 //! | `uuid::fmt::Hyphenated`               | TEXT                                                 |
 //! | `uuid::fmt::Simple`                   | TEXT                                                 |
 //!
+//! `Uuid` encodes to a 16-byte `BLOB`, and decodes from either that or the text format.
+//! The macros use `Uuid` for columns declared as `UUID`.
+//!
 //! ### [`json`](https://crates.io/crates/serde_json)
 //!
 //! Requires the `json` Cargo feature flag.
@@ -227,6 +253,7 @@ mod bool;
 mod bytes;
 #[cfg(feature = "chrono")]
 mod chrono;
+mod duration;
 mod float;
 mod int;
 #[cfg(feature = "json")]
@@ -238,3 +265,4 @@ mod time;
 mod uint;
 #[cfg(feature = "uuid")]
 mod uuid;
+mod vector;
@@ -3,6 +3,7 @@ use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::type_info::DataType;
 use crate::types::Type;
+use crate::value::ValueRef;
 use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
 use std::borrow::Cow;
 use uuid::{
@@ -16,7 +17,7 @@ impl Type<Sqlite> for Uuid {
     }
 
     fn compatible(ty: &SqliteTypeInfo) -> bool {
-        matches!(ty.0, DataType::Blob | DataType::Text)
+        matches!(ty.0, DataType::Blob | DataType::Text | DataType::Uuid)
     }
 }
 
@@ -35,6 +36,11 @@ impl<'q> Encode<'q, Sqlite> for Uuid {
 
 impl Decode<'_, Sqlite> for Uuid {
     fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+        // a `UUID` column may hold either format, depending on what inserted the value
+        if value.type_info().0 == DataType::Text {
+            return Uuid::try_parse(value.text()?).map_err(Into::into);
+        }
+
         // construct a Uuid from the returned bytes
         Uuid::from_slice(value.blob()).map_err(Into::into)
     }
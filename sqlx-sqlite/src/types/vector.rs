@@ -0,0 +1,84 @@
+use std::borrow::Cow;
+use std::str::FromStr;
+
+use sqlx_core::types::{HasVectorDistance, Vector, VectorDistance};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::type_info::DataType;
+use crate::types::Type;
+use crate::value::ValueRef;
+use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+
+impl Type<Sqlite> for Vector {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo(DataType::Blob)
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        matches!(ty.0, DataType::Blob | DataType::Text)
+    }
+}
+
+impl<'q> Encode<'q, Sqlite> for Vector {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<SqliteArgumentValue<'q>>,
+    ) -> Result<IsNull, BoxDynError> {
+        args.push(SqliteArgumentValue::Blob(Cow::Owned(encode_vector(self))));
+
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Sqlite> for Vector {
+    fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+        // sqlite-vec functions return the blob format, but vectors are often inserted as JSON
+        if value.type_info().0 == DataType::Text {
+            return Vector::from_str(value.text()?);
+        }
+
+        decode_vector(value.blob())
+    }
+}
+
+impl HasVectorDistance for Sqlite {
+    fn vector_distance_sql(
+        distance: VectorDistance,
+    ) -> Option<(&'static str, &'static str, &'static str)> {
+        // https://alexgarcia.xyz/sqlite-vec/api-reference.html#distance
+        let function = match distance {
+            VectorDistance::L2 => "vec_distance_l2(",
+            VectorDistance::Cosine => "vec_distance_cosine(",
+            VectorDistance::L1 => "vec_distance_l1(",
+            _ => return None,
+        };
+
+        Some((function, ", ", ")"))
+    }
+}
+
+/// The `float32` blob format of sqlite-vec: each element as a little-endian `f32`.
+fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+fn decode_vector(blob: &[u8]) -> Result<Vector, BoxDynError> {
+    if blob.len() % 4 != 0 {
+        return Err(format!(
+            "expected a multiple of 4 bytes for a vector of f32, got {}",
+            blob.len()
+        )
+        .into());
+    }
+
+    Ok(Vector(
+        blob.chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect(),
+    ))
+}
@@ -6,8 +6,9 @@ use std::sync::Once;
 pub use sqlx_core::any::driver::install_drivers;
 
 pub use sqlx_core::any::{
-    Any, AnyArguments, AnyConnectOptions, AnyExecutor, AnyPoolOptions, AnyQueryResult, AnyRow,
-    AnyStatement, AnyTransactionManager, AnyTypeInfo, AnyTypeInfoKind, AnyValue, AnyValueRef,
+    Any, AnyArguments, AnyCapabilities, AnyConnectOptions, AnyExecutor, AnyPlaceholderStyle,
+    AnyPoolOptions, AnyQueryResult, AnyRow, AnySqlTranslator, AnyStatement, AnyTransactionManager,
+    AnyTranslatedSql, AnyTypeInfo, AnyTypeInfoKind, AnyValue, AnyValueRef,
 };
 
 #[allow(deprecated)]
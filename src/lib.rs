@@ -11,13 +11,23 @@ compile_error!(
 
 pub use sqlx_core::acquire::Acquire;
 pub use sqlx_core::arguments::{Arguments, IntoArguments};
+pub use sqlx_core::augment::{self, QueryAugmenter};
 pub use sqlx_core::column::Column;
 pub use sqlx_core::column::ColumnIndex;
-pub use sqlx_core::connection::{ConnectOptions, Connection};
+pub use sqlx_core::connection::{CachedStatements, ConnectOptions, Connection};
 pub use sqlx_core::database::{self, Database};
+pub use sqlx_core::database_url::{DatabaseKind, DatabaseUrl};
 pub use sqlx_core::describe::Describe;
-pub use sqlx_core::executor::{Execute, Executor};
+pub use sqlx_core::estimate::{self, estimate_count};
+pub use sqlx_core::executor::{Execute, Executor, FetchOptions};
+pub use sqlx_core::fetch_guard::{self, FetchAllGuard};
+pub use sqlx_core::fixtures;
+pub use sqlx_core::from_columns::FromColumns;
+#[doc(hidden)]
+pub use sqlx_core::from_row::resolve_column_name as __resolve_column_name;
 pub use sqlx_core::from_row::FromRow;
+#[cfg(feature = "json")]
+pub use sqlx_core::json_writer::DecodeJson;
 pub use sqlx_core::pool::{self, Pool};
 #[doc(hidden)]
 pub use sqlx_core::query::query_with_result as __query_with_result;
@@ -29,8 +39,9 @@ pub use sqlx_core::query_scalar::query_scalar_with_result as __query_scalar_with
 pub use sqlx_core::query_scalar::{query_scalar, query_scalar_with};
 pub use sqlx_core::raw_sql::{raw_sql, RawSql};
 pub use sqlx_core::row::Row;
+pub use sqlx_core::script::ScriptBind;
 pub use sqlx_core::statement::Statement;
-pub use sqlx_core::transaction::{Transaction, TransactionManager};
+pub use sqlx_core::transaction::{JournalEntry, StatementJournal, Transaction, TransactionManager};
 pub use sqlx_core::type_info::TypeInfo;
 pub use sqlx_core::types::Type;
 pub use sqlx_core::value::{Value, ValueRef};
@@ -42,6 +53,21 @@ pub use sqlx_core::error::{self, Error, Result};
 #[cfg(feature = "migrate")]
 pub use sqlx_core::migrate;
 
+#[cfg(feature = "sqlx-toml")]
+pub use sqlx_core::config;
+
+#[cfg(feature = "sqlx-toml")]
+pub use sqlx_core::type_registry;
+
+pub use sqlx_core::deadline;
+
+#[cfg(all(
+    feature = "tower",
+    any(feature = "_rt-async-std", feature = "_rt-tokio")
+))]
+#[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+pub use sqlx_core::tower;
+
 #[cfg(feature = "mysql")]
 #[cfg_attr(docsrs, doc(cfg(feature = "mysql")))]
 #[doc(inline)]
@@ -86,6 +112,9 @@ pub use sqlx_macros::test;
 #[cfg(feature = "migrate")]
 pub use sqlx_core::testing;
 
+#[cfg(feature = "migrate")]
+pub use sqlx_core::assert_index_used;
+
 #[doc(hidden)]
 pub use sqlx_core::rt::test_block_on;
 
@@ -104,6 +133,10 @@ pub mod ty_match;
 #[doc(hidden)]
 pub mod spec_error;
 
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub mod list_params;
+
 #[doc(hidden)]
 pub use sqlx_core::rt as __rt;
 
@@ -155,7 +188,7 @@ pub use self::decode::Decode;
 pub mod query {
     pub use sqlx_core::query::{Map, Query};
     pub use sqlx_core::query_as::QueryAs;
-    pub use sqlx_core::query_scalar::QueryScalar;
+    pub use sqlx_core::query_scalar::{QueryScalar, QueryScalarStream};
 }
 
 /// Convenience re-export of common traits.
@@ -0,0 +1,124 @@
+//! Support for the `IN (?...)` list parameters of the query macros.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+use sqlx_core::arguments::Arguments;
+use sqlx_core::database::Database;
+use sqlx_core::encode::Encode;
+use sqlx_core::error::BoxDynError;
+use sqlx_core::types::Type;
+
+// replaces the list in `IN (...)` if there are no values, since `IN ()` is not valid SQL
+const EMPTY_LIST: &str = "SELECT NULL FROM (SELECT 1) AS no_values WHERE 1 = 0";
+
+/// View a list argument as a slice; lists are given as slices, `Vec`s or arrays.
+pub fn as_slice<T, L>(list: &L) -> &[T]
+where
+    L: AsRef<[T]> + ?Sized,
+{
+    list.as_ref()
+}
+
+/// The number of values bound for `list`: its length rounded up to a power of two, so that a
+/// query is only ever expanded (and prepared) for a few distinct lengths.
+pub fn bound_len<T>(list: &[T]) -> usize {
+    match list.len() {
+        0 => 0,
+        len => len.next_power_of_two(),
+    }
+}
+
+/// Bind the values of `list`, repeating the last one up to [`bound_len()`] values, which does not
+/// change the result of `IN (...)`.
+pub fn add_list<'q, DB, T>(args: &mut DB::Arguments<'q>, list: &'q [T]) -> Result<(), BoxDynError>
+where
+    DB: Database,
+    &'q T: Encode<'q, DB> + Type<DB>,
+{
+    for value in list {
+        args.add(value)?;
+    }
+
+    if let Some(last) = list.last() {
+        for _ in list.len()..bound_len(list) {
+            args.add(last)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// `sql` with the `?` at each offset, in ascending order, repeated for the bound length of its
+/// list.
+///
+/// Each distinct expansion is built once and kept for the rest of the process, as it is likely
+/// in the statement cache of some connection anyway; rounding the lengths bounds their number.
+pub fn expand_sql(sql: &'static str, lists: &[(usize, usize)]) -> &'static str {
+    type Expansions = HashMap<(&'static str, Vec<usize>), &'static str>;
+
+    static EXPANSIONS: OnceLock<Mutex<Expansions>> = OnceLock::new();
+
+    let lens = lists.iter().map(|&(_, len)| len).collect();
+
+    let mut expansions = EXPANSIONS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    expansions
+        .entry((sql, lens))
+        .or_insert_with(|| Box::leak(expand(sql, lists).into_boxed_str()))
+}
+
+fn expand(sql: &str, lists: &[(usize, usize)]) -> String {
+    let mut expanded = String::with_capacity(sql.len() + lists.len() * 16);
+    let mut copied = 0;
+
+    for &(offset, len) in lists {
+        expanded.push_str(&sql[copied..offset]);
+
+        if len == 0 {
+            expanded.push_str(EMPTY_LIST);
+        } else {
+            for i in 0..len {
+                expanded.push_str(if i == 0 { "?" } else { ", ?" });
+            }
+        }
+
+        // skip the `?`
+        copied = offset + 1;
+    }
+
+    expanded.push_str(&sql[copied..]);
+    expanded
+}
+
+/// Get a value of the type of the items of `list`, for type checking.
+pub fn dupe_item<T>(_list: &[T]) -> &T {
+    panic!()
+}
+
+#[test]
+fn test_expand_sql() {
+    let sql = "SELECT * FROM users WHERE id IN (?) AND name = ? AND team IN (?)";
+
+    assert_eq!(
+        expand_sql(sql, &[(33, 4), (62, 1)]),
+        "SELECT * FROM users WHERE id IN (?, ?, ?, ?) AND name = ? AND team IN (?)"
+    );
+    assert_eq!(
+        expand_sql(sql, &[(33, 0), (62, 2)]),
+        "SELECT * FROM users WHERE id IN (SELECT NULL FROM (SELECT 1) AS no_values WHERE 1 = 0) \
+         AND name = ? AND team IN (?, ?)"
+    );
+
+    // built once
+    assert!(std::ptr::eq(
+        expand_sql(sql, &[(33, 4), (62, 1)]),
+        expand_sql(sql, &[(33, 4), (62, 1)])
+    ));
+
+    assert_eq!(bound_len(&[1, 2, 3]), 4);
+    assert_eq!(bound_len::<i32>(&[]), 0);
+}
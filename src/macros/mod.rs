@@ -110,11 +110,48 @@
 /// * Postgres: `$N` where `N` is the 1-based positional argument index
 /// * MySQL/SQLite: `?` which matches arguments in order that it appears in the query
 ///
+/// ## List Parameters
+/// A slice, `Vec` or array can be bound to a list of values with `IN ($N...)` in Postgres and
+/// `IN (?...)` in MySQL and SQLite; the parameter must be the only item in the parentheses.
+/// `NOT IN` works the same way:
+///
+/// ```rust,ignore
+/// let ids: Vec<i64> = vec![1, 2, 3];
+///
+/// let accounts = sqlx::query!("SELECT id, name FROM accounts WHERE id IN (?...)", ids)
+///     .fetch_all(&mut conn)
+///     .await?;
+/// ```
+///
+/// In Postgres the list is rewritten to `= ANY($N)` (or `<> ALL($N)`) and bound as an array, so
+/// the argument is checked like any other array argument.
+///
+/// In MySQL and SQLite the query is checked with a single `?` in place of the list, so each value
+/// is checked against the type of that parameter. When the query runs, the list is expanded to one
+/// `?` per value, with the length rounded up to a power of two (repeating the last value) so that
+/// only a few variants of the query are prepared. An empty list matches no rows.
+/// `?...` cannot be combined with numbered `?NNN` parameters.
+///
 /// ## Nullability: Bind Parameters
 /// For a given expected type `T`, both `T` and `Option<T>` are allowed (as well as either
 /// behind references). `Option::None` will be bound as `NULL`, so if binding a type behind `Option`
 /// be sure your query can support it.
 ///
+/// Arrays in Postgres may also have `NULL` elements, so for an array parameter expecting `&[T]`
+/// a slice, `Vec` or array of `Option<T>` is allowed too. Together with `UNNEST()` this keeps
+/// batch inserts and updates type-checked:
+///
+/// ```rust,ignore
+/// let ids: Vec<i64> = vec![1, 2];
+/// let names: Vec<Option<String>> = vec![Some("Alice".into()), None];
+///
+/// sqlx::query!(
+///     "INSERT INTO users (id, name) SELECT * FROM UNNEST($1::int8[], $2::text[])",
+///     &ids,
+///     &names,
+/// )
+/// ```
+///
 /// Note, however, if binding in a `where` clause, that equality comparisons with `NULL` may not
 /// work as expected; instead you must use `IS NOT NULL` or `IS NULL` to check if a column is not
 /// null or is null, respectively.
@@ -132,6 +169,11 @@
 /// has a `NOT NULL` constraint. Columns that do not have a `NOT NULL` constraint or are the result
 /// of an expression are assumed to be nullable and so `Option<T>` is used instead of `T`.
 ///
+/// The exception is a few expressions which can never be `NULL`: literals (other than `NULL`),
+/// `COUNT(..)` and `COALESCE(..)` with such a fallback, e.g. `COALESCE(SUM(amount), 0)`.
+/// These are recognized in the query plan, so they are only inferred for queries without
+/// bind parameters.
+///
 /// For MySQL, the implementation looks at [the `NOT_NULL` flag](https://dev.mysql.com/doc/dev/mysql-server/8.0.12/group__group__cs__column__definition__flags.html#ga50377f5ca5b3e92f3931a81fe7b44043)
 /// of [the `ColumnDefinition` structure in `COM_QUERY_OK`](https://dev.mysql.com/doc/internals/en/com-query-response.html#column-definition):
 /// if it is set, `T` is used; if it is not set, `Option<T>` is used.
@@ -164,6 +206,18 @@
 /// Using `expr as _` simply signals to the macro to not type-check that bind expression,
 /// and then that syntax is stripped from the expression so as to not trigger type errors.
 ///
+/// If the override names a type from the [type-checking tables](crate::types) (or `Option` of
+/// one), the macro also declares that type for the parameter when preparing the query. This lets
+/// Postgres accept queries in which it cannot infer the type of a parameter by itself:
+///
+/// ```rust,ignore
+/// // error: could not determine data type of parameter $1
+/// sqlx::query!("select * from users where $1 is null or id = $1", id)
+///
+/// // OK: `$1` is declared as `INT8`
+/// sqlx::query!("select * from users where $1 is null or id = $1", id as Option<i64>)
+/// ```
+///
 /// ## Type Overrides: Output Columns
 /// Type overrides are also available for output columns, utilizing the SQL standard's support
 /// for arbitrary text in column names:
@@ -285,6 +339,27 @@
 /// | `foo!: T` | Forced not-null | Overridden |
 /// | `foo?: T` | Forced nullable | Overridden |
 ///
+/// ##### Registering Types
+/// To use a custom type for every column (and bind parameter) of some SQL type, such as an enum,
+/// a domain or the type of an extension, register it from the build script of your crate with
+/// [`TypeRegistry`](crate::type_registry::TypeRegistry) (requires the `sqlx-toml` feature in
+/// `[build-dependencies]`):
+///
+/// ```rust,ignore
+/// // build.rs
+/// use sqlx::type_registry::TypeRegistry;
+/// use sqlx::DatabaseKind;
+///
+/// fn main() -> std::io::Result<()> {
+///     TypeRegistry::new()
+///         .add(DatabaseKind::Postgres, "status", "crate::models::Status")
+///         .write()
+/// }
+/// ```
+///
+/// `query!()` and `query_as!()` then use `crate::models::Status` for `status` columns
+/// without a `foo: T` override.
+///
 /// ## Offline Mode
 /// The macros can be configured to not require a live database connection for compilation,
 /// but it requires a couple extra steps:
@@ -484,7 +559,25 @@ macro_rules! query_file_unchecked (
 /// \* All methods accept one of `&mut {connection type}`, `&mut Transaction` or `&Pool`.
 /// (`.execute()` is omitted as this macro requires at least one column to be returned.)
 ///
-/// ### Column Type Override: Infer from Struct Field
+/// ### Tuples
+/// Instead of a struct, the output type may be a tuple with one element for each column,
+/// which are matched by position rather than by name, so the names don't have to be valid Rust
+/// identifiers. Each element is checked against the inferred type of its column as fields are,
+/// and the number of elements must match the number of columns:
+///
+/// ```rust,ignore
+/// let (id, name, verified) = sqlx::query_as!(
+///     (i64, String, Option<bool>),
+///     "SELECT id, name, verified FROM accounts WHERE id = $1",
+///     1i64
+/// )
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+///
+/// Column type overrides still apply to columns with valid names.
+///
+/// /// ### Column Type Override: Infer from Struct Field
 /// In addition to the column type overrides supported by [`query!`][`crate::query!`],
 /// [`query_as!()`][`crate::query_as!`] supports an
 /// additional override option:
@@ -569,6 +662,12 @@ macro_rules! query_file_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_as (
+    (($($out_ty:ty),+ $(,)?), $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(record = ($($out_ty,)+), source = $query)
+    });
+    (($($out_ty:ty),+ $(,)?), $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(record = ($($out_ty,)+), source = $query, args = [$($args)*])
+    });
     ($out_struct:path, $query:expr) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query)
     });
@@ -613,6 +712,12 @@ macro_rules! query_as (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_as (
+    (($($out_ty:ty),+ $(,)?), $path:literal) => ( {
+        $crate::sqlx_macros::expand_query!(record = ($($out_ty,)+), source_file = $path)
+    });
+    (($($out_ty:ty),+ $(,)?), $path:literal, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(record = ($($out_ty,)+), source_file = $path, args = [$($args)*])
+    });
     ($out_struct:path, $path:literal) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source_file = $path)
     });
@@ -626,6 +731,12 @@ macro_rules! query_file_as (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_as_unchecked (
+    (($($out_ty:ty),+ $(,)?), $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(record = ($($out_ty,)+), source = $query, checked = false)
+    });
+    (($($out_ty:ty),+ $(,)?), $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(record = ($($out_ty,)+), source = $query, args = [$($args)*], checked = false)
+    });
     ($out_struct:path, $query:expr) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, checked = false)
     });
@@ -641,6 +752,12 @@ macro_rules! query_as_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_as_unchecked (
+    (($($out_ty:ty),+ $(,)?), $path:literal) => ( {
+        $crate::sqlx_macros::expand_query!(record = ($($out_ty,)+), source_file = $path, checked = false)
+    });
+    (($($out_ty:ty),+ $(,)?), $path:literal, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(record = ($($out_ty,)+), source_file = $path, args = [$($args)*], checked = false)
+    });
     ($out_struct:path, $path:literal) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source_file = $path, checked = false)
     });
@@ -675,6 +792,29 @@ macro_rules! query_scalar (
     )
 );
 
+/// A variant of [`query_scalar!`][`crate::query_scalar!`] whose column is always decoded as
+/// `Option<T>`, whether or not it is inferred to be nullable.
+///
+/// Use it with [`QueryScalar::fetch_optional_scalar()`][crate::query::QueryScalar::fetch_optional_scalar]
+/// for lookups where a missing row and a `NULL` value both mean there is no value:
+///
+/// ```rust,ignore
+/// let nickname: Option<String> =
+///     sqlx::query_scalar_optional!("SELECT nickname FROM accounts WHERE id = $1", 1i64)
+///         .fetch_optional_scalar(&mut conn)
+///         .await?;
+/// ```
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+macro_rules! query_scalar_optional (
+    ($query:expr) => (
+        $crate::sqlx_macros::expand_query!(optional_scalar = _, source = $query)
+    );
+    ($query:expr, $($args:tt)*) => (
+        $crate::sqlx_macros::expand_query!(optional_scalar = _, source = $query, args = [$($args)*])
+    )
+);
+
 /// A variant of [`query_scalar!`][`crate::query_scalar!`] which takes a file path like
 /// [`query_file!`][`crate::query_file!`].
 #[macro_export]
@@ -739,12 +879,39 @@ macro_rules! query_file_scalar_unchecked (
 /// static MIGRATOR: Migrator = sqlx::migrate!(); // defaults to "./migrations"
 /// ```
 ///
+/// Without a directory, the `source` set in the `[migrate]` table of a `sqlx.toml` file is used if
+/// there is one; its `table` is used unless `table_name` is given
+/// (see [the `config` module][crate::config] for details). The migrations it skips, with `skip`
+/// and the `skip-migrations` of the profile selected by `SQLX_PROFILE` at compile time, are
+/// skipped by the migrator; see [`Migrator::set_skip()`][crate::migrate::Migrator::set_skip()].
+///
 /// The directory must be relative to the project root (the directory containing `Cargo.toml`),
 /// unlike `include_str!()` which uses compiler internals to get the path of the file where it
 /// was invoked.
 ///
 /// See [MigrationSource][crate::migrate::MigrationSource] for details on structure of the ./migrations directory.
 ///
+/// ## Options
+/// The directory may be followed by options controlling how migrations are embedded:
+///
+/// ```rust,ignore
+/// static MIGRATOR: Migrator = sqlx::migrate!(
+///     "./migrations",
+///     compress = true,
+///     exclude = ["*_dev_seed.sql"],
+/// );
+/// ```
+///
+/// * `compress = true` embeds each migration deflate-compressed, which helps when there are many
+///   large migrations such as seed data. A compressed migration's [`sql`][crate::migrate::Migration::sql]
+///   field is empty; it is decompressed when applied, or on demand with
+///   [`Migration::sql()`][crate::migrate::Migration::sql()].
+/// * `exclude = ["<glob>", ...]` leaves out migrations whose path, relative to the migrations
+///   directory, matches any of the given glob patterns.
+/// * `table_name = "<table>"` tracks applied migrations in the given table, which may be
+///   qualified with a schema, instead of `_sqlx_migrations`;
+///   see [`Migrator::set_table_name()`][crate::migrate::Migrator::set_table_name()].
+///
 /// ## Triggering Recompilation on Migration Changes
 /// In some cases when making changes to embedded migrations, such as adding a new migration without
 /// changing any Rust source files, you might find that `cargo build` doesn't actually do anything,
@@ -809,11 +976,11 @@ macro_rules! query_file_scalar_unchecked (
 #[cfg(feature = "migrate")]
 #[macro_export]
 macro_rules! migrate {
-    ($dir:literal) => {{
-        $crate::sqlx_macros::migrate!($dir)
+    ($dir:literal $($options:tt)*) => {{
+        $crate::sqlx_macros::migrate!($dir $($options)*)
     }};
 
     () => {{
-        $crate::sqlx_macros::migrate!("./migrations")
+        $crate::sqlx_macros::migrate!()
     }};
 }
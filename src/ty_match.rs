@@ -42,6 +42,29 @@ impl<T, U> WrapSameExt for &'_ WrapSame<T, U> {
     type Wrapped = T;
 }
 
+// Array parameters, e.g. those passed to `UNNEST()` in Postgres, may have `NULL` elements:
+// for an expected `&[T]`, accept a slice, `Vec` or array of `Option<U>` as `&[Option<T>]`.
+
+impl<'a, T, U> WrapSameExt for WrapSame<&'a [T], Vec<Option<U>>> {
+    type Wrapped = &'a [Option<T>];
+}
+
+impl<'a, T, U> WrapSameExt for WrapSame<&'a [T], &'_ Vec<Option<U>>> {
+    type Wrapped = &'a [Option<T>];
+}
+
+impl<'a, T, U> WrapSameExt for WrapSame<&'a [T], &'_ [Option<U>]> {
+    type Wrapped = &'a [Option<T>];
+}
+
+impl<'a, T, U, const N: usize> WrapSameExt for WrapSame<&'a [T], [Option<U>; N]> {
+    type Wrapped = &'a [Option<T>];
+}
+
+impl<'a, T, U, const N: usize> WrapSameExt for WrapSame<&'a [T], &'_ [Option<U>; N]> {
+    type Wrapped = &'a [Option<T>];
+}
+
 pub struct MatchBorrow<T, U>(PhantomData<T>, PhantomData<U>);
 
 impl<T, U> MatchBorrow<T, U> {
@@ -62,24 +85,28 @@ impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a str>, Option<String>> {
     type Matched = Option<&'a str>;
 }
 
-impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a [u8]>, Option<Vec<u8>>> {
-    type Matched = Option<&'a [u8]>;
+impl<'a, T> MatchBorrowExt for MatchBorrow<Option<&'a [T]>, Option<Vec<T>>> {
+    type Matched = Option<&'a [T]>;
 }
 
 impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a str>, Option<&'a String>> {
     type Matched = Option<&'a str>;
 }
 
-impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a [u8]>, Option<&'a Vec<u8>>> {
-    type Matched = Option<&'a [u8]>;
+impl<'a, T> MatchBorrowExt for MatchBorrow<Option<&'a [T]>, Option<&'a Vec<T>>> {
+    type Matched = Option<&'a [T]>;
 }
 
 impl<'a> MatchBorrowExt for MatchBorrow<&'a str, String> {
     type Matched = &'a str;
 }
 
-impl<'a> MatchBorrowExt for MatchBorrow<&'a [u8], Vec<u8>> {
-    type Matched = &'a [u8];
+impl<'a, T> MatchBorrowExt for MatchBorrow<&'a [T], Vec<T>> {
+    type Matched = &'a [T];
+}
+
+impl<'a, T, const N: usize> MatchBorrowExt for MatchBorrow<&'a [T], [T; N]> {
+    type Matched = &'a [T];
 }
 
 impl<T> MatchBorrowExt for MatchBorrow<&'_ T, T> {
@@ -143,6 +170,11 @@ fn test_wrap_same() {
         let _: i32 = WrapSame::<i32, _>::new(&0i32).wrap_same();
         let _: i32 = WrapSame::<i32, _>::new(&"hello, world!").wrap_same();
         let _: Option<i32> = WrapSame::<i32, _>::new(&Some(String::new())).wrap_same();
+
+        let _: &[i64] = WrapSame::<&[i64], _>::new(&vec![0i64]).wrap_same();
+        let _: &[Option<i64>] = WrapSame::<&[i64], _>::new(&vec![Some(0i64)]).wrap_same();
+        let _: &[Option<i64>] = WrapSame::<&[i64], _>::new(&&[None::<i64>][..]).wrap_same();
+        let _: &[Option<i64>] = WrapSame::<&[i64], _>::new(&[Some(0i64)]).wrap_same();
     }
 }
 
@@ -160,5 +192,11 @@ fn test_match_borrow() {
 
         let (_, match_borrow) = MatchBorrow::new(0i64, &0i64);
         let _: i64 = match_borrow.match_borrow();
+
+        let (_, match_borrow) = MatchBorrow::new(&[0i64][..], &vec![0i64]);
+        let _: &[i64] = match_borrow.match_borrow();
+
+        let (_, match_borrow) = MatchBorrow::new(&[Some(0i64)][..], &[Some(0i64)]);
+        let _: &[Option<i64>] = match_borrow.match_borrow();
     }
 }
@@ -1,5 +1,5 @@
-use sqlx::any::AnyRow;
-use sqlx::{Any, Connection, Executor, Row};
+use sqlx::any::{AnyPlaceholderStyle, AnyRow};
+use sqlx::{Any, Connection, Executor, Row, Statement};
 use sqlx_test::new;
 
 #[sqlx_macros::test]
@@ -141,3 +141,100 @@ async fn it_can_fail_and_recover_with_pool() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_reports_capabilities() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let mut conn = new::<Any>().await?;
+    let capabilities = conn.capabilities();
+
+    assert!(capabilities.supports_savepoints());
+    assert!(capabilities.max_bind_params() > 0);
+
+    // an insert that works on whichever database is in use
+    let placeholder = capabilities.placeholder_style().placeholder(1);
+    conn.execute("CREATE TEMPORARY TABLE caps (id INT)").await?;
+
+    let id: i32 = if capabilities.supports_returning() {
+        sqlx::query_scalar(&format!(
+            "INSERT INTO caps VALUES ({placeholder}) RETURNING id"
+        ))
+        .bind(7_i32)
+        .fetch_one(&mut conn)
+        .await?
+    } else {
+        sqlx::query(&format!("INSERT INTO caps VALUES ({placeholder})"))
+            .bind(7_i32)
+            .execute(&mut conn)
+            .await?;
+
+        sqlx::query_scalar("SELECT id FROM caps")
+            .fetch_one(&mut conn)
+            .await?
+    };
+    assert_eq!(id, 7);
+
+    match conn.backend_name() {
+        "PostgreSQL" => {
+            assert_eq!(
+                capabilities.placeholder_style(),
+                AnyPlaceholderStyle::Numbered
+            );
+            assert!(capabilities.supports_returning());
+        }
+        "SQLite" => {
+            assert_eq!(
+                capabilities.placeholder_style(),
+                AnyPlaceholderStyle::QuestionMark
+            );
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_translates_placeholders() -> anyhow::Result<()> {
+    use sqlx::any::{AnyConnectOptions, AnySqlTranslator};
+    use sqlx::AnyConnection;
+
+    sqlx::any::install_default_drivers();
+
+    let options: AnyConnectOptions = dotenvy::var("DATABASE_URL")?.parse()?;
+    let mut conn =
+        AnyConnection::connect_with(&options.translate_sql(AnySqlTranslator::new())).await?;
+
+    let (a, b): (i64, i64) = sqlx::query_as("SELECT ? /* nested /* comment */ */, ?")
+        .bind(1_i64)
+        .bind(2_i64)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!((a, b), (1, 2));
+
+    // reordered and repeated for databases with `?` placeholders
+    let (a, b, c): (i64, i64, i64) = sqlx::query_as("SELECT $2, $1, $2 -- comment")
+        .bind(1_i64)
+        .bind(2_i64)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!((a, b, c), (2, 1, 2));
+
+    let statement = conn.prepare("SELECT $2, $1, '$1'").await?;
+    let row: (String, String, String) = statement
+        .query_as()
+        .bind("a")
+        .bind("b")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(row, ("b".into(), "a".into(), "$1".into()));
+
+    assert!(sqlx::query("SELECT ?, $1")
+        .bind(1_i64)
+        .execute(&mut conn)
+        .await
+        .is_err());
+
+    Ok(())
+}
@@ -1,10 +1,12 @@
+use futures::StreamExt;
 use sqlx::any::{AnyConnectOptions, AnyPoolOptions};
+use sqlx::pool::{Backoff, CloseReason, PoolEvent, PoolMetric, RetryPolicy};
 use sqlx::Executor;
 use std::sync::{
     atomic::{AtomicI32, AtomicUsize, Ordering},
     Arc, Mutex,
 };
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[sqlx_macros::test]
 async fn pool_should_invoke_after_connect() -> anyhow::Result<()> {
@@ -204,6 +206,207 @@ async fn test_pool_callbacks() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_pool_events() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_millis(100))
+        .connect_lazy(&dotenvy::var("DATABASE_URL")?)?;
+
+    let events = pool.events();
+
+    let conn = pool.acquire().await?;
+    assert!(matches!(
+        pool.acquire().await,
+        Err(sqlx::Error::PoolTimedOut)
+    ));
+    conn.close().await?;
+
+    pool.close().await;
+    drop(pool);
+
+    let events: Vec<PoolEvent> = events.collect().await;
+
+    assert_eq!(events.len(), 3, "{events:?}");
+    assert_eq!(events[0], PoolEvent::ConnectionCreated);
+    assert!(
+        matches!(events[1], PoolEvent::AcquireTimeout { wait } if wait >= Duration::from_millis(100)),
+        "{events:?}"
+    );
+    assert_eq!(
+        events[2],
+        PoolEvent::ConnectionClosed {
+            reason: CloseReason::Requested
+        }
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_pool_metrics() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let measured = Arc::new(Mutex::new(Vec::new()));
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_millis(100))
+        .on_metric({
+            let measured = measured.clone();
+            move |metric| measured.lock().unwrap().push(metric)
+        })
+        .connect_lazy(&dotenvy::var("DATABASE_URL")?)?;
+
+    let conn = pool.acquire().await?;
+    assert!(matches!(
+        pool.acquire().await,
+        Err(sqlx::Error::PoolTimedOut)
+    ));
+
+    let metrics = pool.metrics();
+    assert_eq!((metrics.size, metrics.idle, metrics.active), (1, 0, 1));
+    assert_eq!(metrics.acquires, 1);
+    assert_eq!(metrics.acquire_timeouts, 1);
+    assert_eq!(metrics.connection_errors, 0);
+    assert_eq!(metrics.acquire_wait.count(), 1);
+
+    drop(conn);
+    pool.acquire().await?;
+
+    let metrics = pool.metrics();
+    assert_eq!(metrics.acquires, 2);
+    assert_eq!(metrics.acquire_wait.count(), 2);
+
+    let measured = measured.lock().unwrap();
+    assert_eq!(measured.len(), 3, "{measured:?}");
+    assert!(
+        matches!(measured[0], PoolMetric::Acquired { .. }),
+        "{measured:?}"
+    );
+    assert!(
+        matches!(measured[1], PoolMetric::AcquireTimeout { wait } if wait >= Duration::from_millis(100)),
+        "{measured:?}"
+    );
+    assert!(
+        matches!(measured[2], PoolMetric::Acquired { .. }),
+        "{measured:?}"
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_pool_retry_policy() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let attempts = Arc::new(AtomicUsize::new(0));
+
+    let pool_options = AnyPoolOptions::new().after_connect({
+        let attempts = attempts.clone();
+        move |_conn, _meta| {
+            let attempts = attempts.clone();
+            Box::pin(async move {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(sqlx::Error::Protocol("after_connect failed".into()))
+            })
+        }
+    });
+
+    let policy = RetryPolicy::new()
+        .max_attempts(3)
+        .backoff(Backoff::Constant(Duration::from_millis(1)));
+
+    let pool = pool_options
+        .clone()
+        .retry_policy(policy.clone())
+        .connect_lazy(&dotenvy::var("DATABASE_URL")?)?;
+
+    assert!(matches!(
+        pool.acquire().await,
+        Err(sqlx::Error::Protocol(_))
+    ));
+    assert_eq!(attempts.swap(0, Ordering::SeqCst), 3);
+    assert_eq!(pool.metrics().connection_errors, 3);
+
+    let pool = pool_options
+        .retry_policy(policy.retry_on_acquire(false))
+        .connect_lazy(&dotenvy::var("DATABASE_URL")?)?;
+
+    assert!(matches!(
+        pool.acquire().await,
+        Err(sqlx::Error::Protocol(_))
+    ));
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_pool_close_with_timeout() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let closed = Arc::new(AtomicUsize::new(0));
+
+    let pool_options = AnyPoolOptions::new().before_close({
+        let closed = closed.clone();
+        move |_conn, _meta| {
+            let closed = closed.clone();
+            Box::pin(async move {
+                closed.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+        }
+    });
+
+    let pool = pool_options
+        .clone()
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    // one idle connection, and one returned while the pool is closing
+    let conn = pool.acquire().await?;
+    pool.acquire().await?;
+
+    let returned = sqlx_core::rt::spawn(async move {
+        sqlx_core::rt::sleep(Duration::from_millis(100)).await;
+        drop(conn);
+    });
+
+    assert_eq!(pool.close_with_timeout(Duration::from_secs(5)).await, 0);
+    returned.await;
+    assert_eq!(closed.swap(0, Ordering::SeqCst), 2);
+    assert!(matches!(pool.acquire().await, Err(sqlx::Error::PoolClosed)));
+
+    // a connection held past the timeout is closed hard, without `before_close`, once dropped
+    let pool = pool_options.connect(&dotenvy::var("DATABASE_URL")?).await?;
+    let conn = pool.acquire().await?;
+    pool.acquire().await?;
+
+    let started = Instant::now();
+    assert_eq!(pool.close_with_timeout(Duration::from_millis(100)).await, 1);
+    assert!(started.elapsed() < Duration::from_secs(1));
+
+    assert_eq!(pool.size(), 1);
+    drop(conn);
+
+    // the connection is closed in a task spawned on drop
+    for _ in 0..100 {
+        if pool.size() == 0 {
+            break;
+        }
+        sqlx_core::rt::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(pool.size(), 0);
+
+    // only the idle connection was closed before the timeout
+    assert_eq!(closed.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
 #[ignore]
 #[sqlx_macros::test]
 async fn test_connection_maintenance() -> anyhow::Result<()> {
@@ -5,6 +5,16 @@ use std::path::Path;
 static EMBEDDED_SIMPLE: Migrator = sqlx::migrate!("tests/migrate/migrations_simple");
 static EMBEDDED_REVERSIBLE: Migrator = sqlx::migrate!("tests/migrate/migrations_reversible");
 static EMBEDDED_SYMLINK: Migrator = sqlx::migrate!("tests/migrate/migrations_symlink");
+static EMBEDDED_COMPRESSED: Migrator =
+    sqlx::migrate!("tests/migrate/migrations_reversible", compress = true);
+static EMBEDDED_EXCLUDED: Migrator = sqlx::migrate!(
+    "tests/migrate/migrations_simple",
+    exclude = ["*_convert_type.sql"],
+);
+static EMBEDDED_CUSTOM_TABLE: Migrator = sqlx::migrate!(
+    "tests/migrate/migrations_simple",
+    table_name = "myschema.schema_migrations",
+);
 
 #[sqlx_macros::test]
 async fn same_output() -> anyhow::Result<()> {
@@ -16,6 +26,41 @@ async fn same_output() -> anyhow::Result<()> {
     assert_same(&EMBEDDED_SIMPLE, &runtime_simple);
     assert_same(&EMBEDDED_REVERSIBLE, &runtime_reversible);
     assert_same(&EMBEDDED_SYMLINK, &runtime_symlink);
+    assert_same(&EMBEDDED_COMPRESSED, &runtime_reversible);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn compressed_sql_is_not_embedded() -> anyhow::Result<()> {
+    for migration in EMBEDDED_COMPRESSED.iter() {
+        assert!(migration.is_compressed());
+        assert!(!migration.sql()?.is_empty());
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn excluded_migrations_are_skipped() -> anyhow::Result<()> {
+    let runtime_simple = Migrator::new(Path::new("tests/migrate/migrations_simple")).await?;
+
+    assert_eq!(EMBEDDED_EXCLUDED.migrations.len(), 1);
+    assert_eq!(EMBEDDED_EXCLUDED.migrations[0].version, 20220721115250);
+    assert_eq!(runtime_simple.migrations.len(), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn custom_table_name_is_embedded() -> anyhow::Result<()> {
+    let runtime_simple = Migrator::new(Path::new("tests/migrate/migrations_simple")).await?;
+
+    assert_eq!(
+        EMBEDDED_CUSTOM_TABLE.table_name(),
+        "myschema.schema_migrations"
+    );
+    assert_eq!(runtime_simple.table_name(), "_sqlx_migrations");
 
     Ok(())
 }
@@ -27,7 +72,7 @@ fn assert_same(embedded: &Migrator, runtime: &Migrator) {
         assert_eq!(e.version, r.version);
         assert_eq!(e.description, r.description);
         assert_eq!(e.migration_type, r.migration_type);
-        assert_eq!(e.sql, r.sql);
+        assert_eq!(e.sql().unwrap(), r.sql().unwrap());
         assert_eq!(e.checksum, r.checksum);
     }
 }
@@ -69,3 +69,31 @@ async fn uses_alias_name() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_describes_column_origins() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let d = conn
+        .describe(
+            "SELECT t.text AS tweet_text, r.text, r.id + 1 AS next_id \
+             FROM tweet t JOIN tweet_reply r ON r.tweet_id = t.id",
+        )
+        .await?;
+
+    let tweet_text = &d.columns()[0];
+    assert_eq!(tweet_text.table(), Some("tweet"));
+    assert_eq!(tweet_text.table_alias(), Some("t"));
+    assert_eq!(tweet_text.original_name(), Some("text"));
+    assert!(tweet_text.schema().is_some());
+
+    let reply_text = &d.columns()[1];
+    assert_eq!(reply_text.table(), Some("tweet_reply"));
+    assert_eq!(reply_text.original_name(), Some("text"));
+
+    let next_id = &d.columns()[2];
+    assert_eq!(next_id.table(), None);
+    assert_eq!(next_id.original_name(), None);
+
+    Ok(())
+}
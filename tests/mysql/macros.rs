@@ -33,6 +33,34 @@ async fn macro_select_from_cte_bind() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn macro_select_bind_list() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let ids: Vec<i64> = vec![3, 1];
+
+    let found = sqlx::query_scalar!(
+        "select id from (select 1 as id union all select 2 union all select 3) ids \
+         where id in (?...) order by id",
+        ids
+    )
+    .fetch_all(&mut conn)
+    .await?;
+    assert_eq!(found, [1, 3]);
+
+    // an empty list matches nothing
+    let found = sqlx::query_scalar!(
+        "select id from (select 1 as id union all select 2 union all select 3) ids \
+         where id not in (?...) order by id",
+        &ids[..0]
+    )
+    .fetch_all(&mut conn)
+    .await?;
+    assert_eq!(found, [1, 2, 3]);
+
+    Ok(())
+}
+
 #[derive(Debug)]
 struct RawAccount {
     r#type: i32,
@@ -1,4 +1,4 @@
-use sqlx::migrate::Migrator;
+use sqlx::migrate::{Migrate, Migrator};
 use sqlx::mysql::{MySql, MySqlConnection};
 use sqlx::pool::PoolConnection;
 use sqlx::Executor;
@@ -66,6 +66,71 @@ async fn reversible(mut conn: PoolConnection<MySql>) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx::test(migrations = false)]
+async fn records_metadata(mut conn: PoolConnection<MySql>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    // a table as created by earlier versions, which must be upgraded
+    conn.execute(
+        r#"
+CREATE TABLE _sqlx_migrations (
+    version BIGINT PRIMARY KEY,
+    description TEXT NOT NULL,
+    installed_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    success BOOLEAN NOT NULL,
+    checksum BLOB NOT NULL,
+    execution_time BIGINT NOT NULL
+);
+        "#,
+    )
+    .await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/mysql/migrations_simple")).await?;
+    migrator.set_app_version("1.2.3");
+    migrator.run(&mut conn).await?;
+
+    let applied = conn.list_applied_migrations("_sqlx_migrations").await?;
+    assert_eq!(applied.len(), 2);
+
+    let user: String = conn.fetch_one("SELECT CURRENT_USER()").await?.get(0);
+
+    for migration in applied {
+        assert_eq!(migration.applied_by.as_deref(), Some(&*user));
+        assert!(migration.hostname.is_some());
+        assert_eq!(migration.app_version.as_deref(), Some("1.2.3"));
+        assert!(migration.execution_time.is_some());
+    }
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn custom_table_name(mut conn: PoolConnection<MySql>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+    conn.execute("DROP TABLE IF EXISTS schema_migrations")
+        .await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/mysql/migrations_simple")).await?;
+    migrator.set_table_name("schema_migrations");
+    migrator.run(&mut conn).await?;
+
+    // running it a 2nd time should still work
+    migrator.run(&mut conn).await?;
+
+    let applied = conn.list_applied_migrations("schema_migrations").await?;
+    assert_eq!(applied.len(), 2);
+
+    let default_exists = conn
+        .list_applied_migrations("_sqlx_migrations")
+        .await
+        .is_ok();
+    assert!(!default_exists);
+
+    conn.execute("DROP TABLE schema_migrations").await?;
+
+    Ok(())
+}
+
 /// Ensure that we have a clean initial state.
 async fn clean_up(conn: &mut MySqlConnection) -> anyhow::Result<()> {
     conn.execute("DROP TABLE migrations_simple_test").await.ok();
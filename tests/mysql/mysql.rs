@@ -1,6 +1,8 @@
 use anyhow::Context;
 use futures::TryStreamExt;
-use sqlx::mysql::{MySql, MySqlConnection, MySqlPool, MySqlPoolOptions, MySqlRow};
+use sqlx::mysql::{
+    MySql, MySqlConnection, MySqlPool, MySqlPoolOptions, MySqlRow, MySqlValueFormat,
+};
 use sqlx::{Column, Connection, Executor, Row, Statement, TypeInfo};
 use sqlx_core::connection::ConnectOptions;
 use sqlx_mysql::MySqlConnectOptions;
@@ -158,6 +160,43 @@ async fn it_drops_results_in_affected_rows() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_reports_rows_matched_and_changed() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options: MySqlConnectOptions = env::var("DATABASE_URL")?.parse()?;
+
+    for found_rows in [true, false] {
+        let mut conn = options.clone().found_rows(found_rows).connect().await?;
+
+        conn.execute(
+            r#"
+CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+INSERT INTO users (id, name) VALUES (1, 'a'), (2, 'a'), (3, 'b');
+            "#,
+        )
+        .await?;
+
+        let done = sqlx::query("UPDATE users SET name = ? WHERE id <= ?")
+            .bind("b")
+            .bind(3_i32)
+            .execute(&mut conn)
+            .await?;
+
+        assert_eq!(done.rows_matched(), 3);
+        assert_eq!(done.rows_changed(), 2);
+        assert_eq!(done.rows_affected(), if found_rows { 3 } else { 2 });
+
+        // statements other than `UPDATE` report the rows affected for both
+        let done = conn.execute("DELETE FROM users WHERE id = 1").await?;
+
+        assert_eq!(done.rows_matched(), 1);
+        assert_eq!(done.rows_changed(), 1);
+    }
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_selects_null() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
@@ -589,6 +628,76 @@ async fn test_shrink_buffers() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "mysql-monitoring")]
+#[sqlx_macros::test]
+async fn it_reads_monitoring_tables() -> anyhow::Result<()> {
+    use sqlx::mysql::monitoring;
+
+    let mut conn = new::<MySql>().await?;
+
+    sqlx::query("SELECT 1 + 1").execute(&mut conn).await?;
+
+    let digests = monitoring::statement_digests(&mut conn).await?;
+
+    assert!(digests
+        .windows(2)
+        .all(|pair| pair[0].sum_timer_wait >= pair[1].sum_timer_wait));
+
+    // the test server is not a replica
+    assert!(monitoring::replica_status(&mut conn).await?.is_empty());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_loads_data_from_local_infile() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options: MySqlConnectOptions = env::var("DATABASE_URL")?.parse()?;
+
+    let mut conn = options
+        .local_infile_handler(|file_name| {
+            let chunks: Vec<Result<sqlx_core::bytes::Bytes, sqlx_core::error::BoxDynError>> =
+                match file_name {
+                    "users.csv" => vec![Ok("1,alice\n2,".into()), Ok("bob\n".into())],
+                    _ => vec![Ok("3,carol\n".into()), Err("file not found".into())],
+                };
+
+            futures::stream::iter(chunks)
+        })
+        .connect()
+        .await?;
+
+    // disabled by default as of MySQL 8.0, and a global setting the test should not change
+    let local_infile: i64 = sqlx::query_scalar("SELECT @@GLOBAL.local_infile")
+        .fetch_one(&mut conn)
+        .await?;
+    if local_infile == 0 {
+        return Ok(());
+    }
+
+    conn.execute("CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+        .await?;
+
+    let done = conn
+        .execute("LOAD DATA LOCAL INFILE 'users.csv' INTO TABLE users FIELDS TERMINATED BY ','")
+        .await?;
+    assert_eq!(done.rows_affected(), 2);
+
+    let res = conn
+        .execute("LOAD DATA LOCAL INFILE 'other.csv' INTO TABLE users FIELDS TERMINATED BY ','")
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::Io(_))), "{res:?}");
+
+    // the connection is still usable after the handler failed
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM users ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(names, ["alice", "bob", "carol"]);
+
+    Ok(())
+}
+
 async fn select_statement_count(conn: &mut MySqlConnection) -> Result<i64, sqlx::Error> {
     // Fails if performance schema does not exist
     sqlx::query_scalar(
@@ -603,3 +712,34 @@ async fn select_statement_count(conn: &mut MySqlConnection) -> Result<i64, sqlx:
     .fetch_one(conn)
     .await
 }
+
+#[sqlx_macros::test]
+async fn it_gets_raw_values() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE raw_values (id INTEGER PRIMARY KEY, name TEXT, note TEXT)",
+    )
+    .await?;
+    conn.execute("INSERT INTO raw_values (id, name) VALUES (1, 'abc')")
+        .await?;
+
+    // prepared statements return the binary format
+    let row = sqlx::query("SELECT id, name, note FROM raw_values")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.value_format(), MySqlValueFormat::Binary);
+    assert_eq!(row.raw_value(0)?, Some(&1_i32.to_le_bytes()[..]));
+    assert_eq!(row.raw_value("name")?, Some(&b"abc"[..]));
+    assert_eq!(row.raw_value(2)?, None);
+    assert!(row.raw_value(3).is_err());
+
+    // plain queries return the text format
+    let row = conn.fetch_one("SELECT id FROM raw_values").await?;
+
+    assert_eq!(row.value_format(), MySqlValueFormat::Text);
+    assert_eq!(row.raw_value(0)?, Some(&b"1"[..]));
+
+    Ok(())
+}
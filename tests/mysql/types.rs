@@ -84,6 +84,11 @@ test_type!(mysql_time<MySqlTime>(MySql,
     "TIME '-123:45:56.890011'" == MySqlTime::new(MySqlTimeSign::Negative, 123, 45, 56, 890_011).unwrap(),
 ));
 
+test_type!(std_duration<std::time::Duration>(MySql,
+    "TIME '00:00:00.000000'" == std::time::Duration::ZERO,
+    "TIME '123:45:56.890011'" == std::time::Duration::new(123 * 3600 + 45 * 60 + 56, 890_011_000),
+));
+
 #[cfg(feature = "chrono")]
 mod chrono {
     use sqlx::types::chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
@@ -384,3 +389,51 @@ CREATE TEMPORARY TABLE user_login (
 
     Ok(())
 }
+
+#[cfg(feature = "proptest")]
+#[sqlx_macros::test]
+async fn it_roundtrips_generated_values() -> anyhow::Result<()> {
+    use sqlx::testing::roundtrip;
+
+    let mut conn = new::<MySql>().await?;
+
+    roundtrip::<bool, _>(&mut conn).await?;
+    roundtrip::<i8, _>(&mut conn).await?;
+    roundtrip::<i16, _>(&mut conn).await?;
+    roundtrip::<i32, _>(&mut conn).await?;
+    roundtrip::<i64, _>(&mut conn).await?;
+    roundtrip::<u8, _>(&mut conn).await?;
+    roundtrip::<u16, _>(&mut conn).await?;
+    roundtrip::<u32, _>(&mut conn).await?;
+    roundtrip::<u64, _>(&mut conn).await?;
+    roundtrip::<f32, _>(&mut conn).await?;
+    roundtrip::<f64, _>(&mut conn).await?;
+    roundtrip::<String, _>(&mut conn).await?;
+    roundtrip::<Vec<u8>, _>(&mut conn).await?;
+
+    #[cfg(feature = "chrono")]
+    {
+        use sqlx::types::chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+        roundtrip::<NaiveDate, _>(&mut conn).await?;
+        roundtrip::<NaiveTime, _>(&mut conn).await?;
+        roundtrip::<NaiveDateTime, _>(&mut conn).await?;
+    }
+
+    #[cfg(feature = "time")]
+    {
+        use sqlx::types::time::{Date, PrimitiveDateTime, Time};
+
+        roundtrip::<Date, _>(&mut conn).await?;
+        roundtrip::<Time, _>(&mut conn).await?;
+        roundtrip::<PrimitiveDateTime, _>(&mut conn).await?;
+    }
+
+    #[cfg(feature = "uuid")]
+    roundtrip::<sqlx::types::Uuid, _>(&mut conn).await?;
+
+    #[cfg(feature = "rust_decimal")]
+    roundtrip::<sqlx::types::Decimal, _>(&mut conn).await?;
+
+    Ok(())
+}
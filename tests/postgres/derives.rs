@@ -665,6 +665,96 @@ async fn test_struct_default() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "macros")]
+#[sqlx_macros::test]
+async fn test_default_on_null() -> anyhow::Result<()> {
+    #[derive(Debug, sqlx::FromRow)]
+    struct HasDefaultOnNull {
+        not_default: i32,
+        #[sqlx(default_on_null)]
+        null: i32,
+        #[sqlx(default_on_null)]
+        missing: String,
+        #[sqlx(default_on_null, try_from = "i32")]
+        converted: i64,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let has_default: HasDefaultOnNull =
+        sqlx::query_as(r#"SELECT 1 AS not_default, NULL::int4 AS "null", NULL::int4 AS converted"#)
+            .fetch_one(&mut conn)
+            .await?;
+    println!("{has_default:?}");
+
+    assert_eq!(has_default.not_default, 1);
+    assert_eq!(has_default.null, 0);
+    assert_eq!(has_default.missing, "");
+    assert_eq!(has_default.converted, 0);
+
+    Ok(())
+}
+
+#[cfg(feature = "macros")]
+#[sqlx_macros::test]
+async fn test_alias() -> anyhow::Result<()> {
+    #[derive(Debug, sqlx::FromRow)]
+    #[sqlx(rename_all = "camelCase")]
+    struct User {
+        user_id: i32,
+        #[sqlx(alias = "login", alias = "name")]
+        user_name: String,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let user: User = sqlx::query_as(r#"SELECT 1 AS "userId", 'alice' AS "userName""#)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(user.user_id, 1);
+    assert_eq!(user.user_name, "alice");
+
+    let user: User = sqlx::query_as(r#"SELECT 2 AS "userId", 'bob' AS "name""#)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(user.user_id, 2);
+    assert_eq!(user.user_name, "bob");
+
+    let res = sqlx::query_as::<_, User>(r#"SELECT 3 AS "userId""#)
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::ColumnNotFound(name)) if name == "userName"));
+
+    Ok(())
+}
+
+#[cfg(feature = "macros")]
+#[sqlx_macros::test]
+async fn test_case_insensitive() -> anyhow::Result<()> {
+    #[derive(Debug, sqlx::FromRow)]
+    #[sqlx(case_insensitive)]
+    struct Account {
+        id: i32,
+        #[sqlx(rename = "Name")]
+        name: String,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let account: Account = sqlx::query_as(r#"SELECT 1 AS "ID", 'foo' AS "NAME", 'bar' AS "Name""#)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(account.id, 1);
+    // an exact match is preferred
+    assert_eq!(account.name, "bar");
+
+    Ok(())
+}
+
 #[cfg(feature = "macros")]
 #[sqlx_macros::test]
 async fn test_flatten() -> anyhow::Result<()> {
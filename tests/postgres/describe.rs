@@ -1,4 +1,5 @@
-use sqlx::{postgres::Postgres, Column, Executor, TypeInfo};
+use sqlx::postgres::{types::Oid, Postgres};
+use sqlx::{Column, Executor, TypeInfo};
 use sqlx_test::new;
 
 #[sqlx_macros::test]
@@ -29,7 +30,7 @@ async fn it_describes_simple() -> anyhow::Result<()> {
 async fn it_describes_expression() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
 
-    let d = conn.describe("SELECT 1::int8 + 10").await?;
+    let d = conn.describe("SELECT id + 10 FROM tweet").await?;
 
     // ?column? will cause the macro to emit an error ad ask the user to explicitly name the type
     assert_eq!(d.columns()[0].name(), "?column?");
@@ -42,6 +43,37 @@ async fn it_describes_expression() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_describes_non_null_expressions() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let d = conn
+        .describe("SELECT count(*), 1::int8 + 10, 'a'::text, COALESCE(max(owner_id), 0), max(id) FROM tweet")
+        .await?;
+
+    assert_eq!(d.nullable(0), Some(false));
+    assert_eq!(d.nullable(1), Some(false));
+    assert_eq!(d.nullable(2), Some(false));
+    assert_eq!(d.nullable(3), Some(false));
+    assert_eq!(d.nullable(4), None);
+
+    // literals pulled up from the inner side of an outer join may still be `NULL`
+    let d = conn
+        .describe("SELECT t.one FROM tweet LEFT JOIN (SELECT 1 AS one) t ON false")
+        .await?;
+
+    assert_eq!(d.nullable(0), None);
+
+    // parameters are bound as `NULL` for the inference, which may fold expressions away
+    let d = conn
+        .describe("SELECT CASE WHEN $1::int4 IS NULL THEN 1 END")
+        .await?;
+
+    assert_eq!(d.nullable(0), None);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_describes_enum() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -93,3 +125,29 @@ async fn it_describes_composite() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_describes_column_origins() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let d = conn
+        .describe(
+            "SELECT t.text AS tweet_text, r.text, r.id + 1 AS next_id \
+             FROM tweet t JOIN tweet_reply r ON r.tweet_id = t.id",
+        )
+        .await?;
+
+    let (tweet, reply): (Oid, Oid) =
+        sqlx::query_as("SELECT 'tweet'::regclass::oid, 'tweet_reply'::regclass::oid")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(d.columns()[0].relation_id(), Some(tweet));
+    assert_eq!(d.columns()[0].relation_attribute_no(), Some(3));
+    assert_eq!(d.columns()[1].relation_id(), Some(reply));
+    assert_eq!(d.columns()[1].relation_attribute_no(), Some(4));
+    assert_eq!(d.columns()[2].relation_id(), None);
+    assert_eq!(d.columns()[2].relation_attribute_no(), None);
+
+    Ok(())
+}
@@ -60,12 +60,12 @@ async fn test_text_var_char_char_n() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
 
     // TEXT
-    // we cannot infer nullability from an expression
+    // literals are inferred to be non-null
     let rec = sqlx::query!("SELECT 'Hello'::text as greeting")
         .fetch_one(&mut conn)
         .await?;
 
-    assert_eq!(rec.greeting.as_deref(), Some("Hello"));
+    assert_eq!(rec.greeting, "Hello");
 
     // VARCHAR(N)
 
@@ -73,7 +73,7 @@ async fn test_text_var_char_char_n() -> anyhow::Result<()> {
         .fetch_one(&mut conn)
         .await?;
 
-    assert_eq!(rec.greeting.as_deref(), Some("Hello"));
+    assert_eq!(rec.greeting, "Hello");
 
     // CHAR(N)
 
@@ -81,7 +81,7 @@ async fn test_text_var_char_char_n() -> anyhow::Result<()> {
         .fetch_one(&mut conn)
         .await?;
 
-    assert_eq!(rec.greeting.as_deref(), Some("Hello"));
+    assert_eq!(rec.greeting, "Hello");
 
     Ok(())
 }
@@ -195,14 +195,20 @@ async fn test_query_scalar() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
 
     let id = sqlx::query_scalar!("select 1").fetch_one(&mut conn).await?;
-    // nullability inference can't handle expressions
-    assert_eq!(id, Some(1i32));
+    // literals are inferred to be non-null
+    assert_eq!(id, 1i32);
+
+    // nullability inference can't handle most other expressions
+    let id = sqlx::query_scalar!("select 1 + $1::int4", 1i32)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(id, Some(2i32));
 
     // invalid column names are ignored
     let id = sqlx::query_scalar!(r#"select 1 as "&foo""#)
         .fetch_one(&mut conn)
         .await?;
-    assert_eq!(id, Some(1i32));
+    assert_eq!(id, 1i32);
 
     let id = sqlx::query_scalar!(r#"select 1 as "foo!""#)
         .fetch_one(&mut conn)
@@ -219,7 +225,7 @@ async fn test_query_scalar() -> anyhow::Result<()> {
         .fetch_one(&mut conn)
         .await?;
 
-    assert_eq!(id, Some(MyInt4(1i32)));
+    assert_eq!(id, MyInt4(1i32));
 
     let id = sqlx::query_scalar!(r#"select 1 as "foo?: MyInt4""#)
         .fetch_one(&mut conn)
@@ -235,9 +241,7 @@ async fn test_query_scalar() -> anyhow::Result<()> {
 
     let id: MyInt4 = sqlx::query_scalar!(r#"select 1 as "foo: _""#)
         .fetch_one(&mut conn)
-        .await?
-        // don't hint that it should be `Option<MyInt4>`
-        .unwrap();
+        .await?;
 
     assert_eq!(id, MyInt4(1i32));
 
@@ -258,6 +262,76 @@ async fn test_query_scalar() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_text_search_types() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let row = sqlx::query!(
+        r#"SELECT to_tsvector('english', $1) "document!", plainto_tsquery('english', $1) "query!""#,
+        "fat rats"
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    let document: sqlx::postgres::types::PgTsVector = row.document;
+    let query: sqlx::postgres::types::PgTsQuery = row.query;
+
+    assert_eq!(document.to_string(), "'fat':1 'rat':2");
+    assert_eq!(query.to_string(), "'fat' & 'rat'");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_as_tuple() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let (id, name, count) = sqlx::query_as!(
+        (i32, Option<String>, Option<i64>),
+        r#"SELECT id "id!", name, count(*) OVER () FROM (VALUES (1, $1)) accounts(id, name)"#,
+        "Herp Derpinson"
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(id, 1);
+    assert_eq!(name.as_deref(), Some("Herp Derpinson"));
+    assert_eq!(count, Some(1));
+
+    // overrides apply by position
+    let rows: Vec<(MyInt4, String)> =
+        sqlx::query_as!((MyInt4, String), r#"SELECT 1 "id: MyInt4", 'a' "name!""#)
+            .fetch_all(&mut conn)
+            .await?;
+
+    assert_eq!(rows, vec![(MyInt4(1), "a".to_owned())]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_scalar_optional() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // literals are inferred to be non-null, but the column is still optional
+    let id = sqlx::query_scalar_optional!("select 1")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(id, Some(1i32));
+
+    let id = sqlx::query_scalar_optional!("select 1 where $1", false)
+        .fetch_optional_scalar(&mut conn)
+        .await?;
+    assert_eq!(id, None);
+
+    let id = sqlx::query_scalar_optional!(r#"select 1 as "foo!: MyInt4""#)
+        .fetch_optional_scalar(&mut conn)
+        .await?;
+    assert_eq!(id, Some(MyInt4(1)));
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn query_by_string() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -380,13 +454,76 @@ async fn test_array_from_slice() -> anyhow::Result<()> {
         .fetch_one(&mut conn)
         .await?;
 
-    assert_eq!(account.my_array, Some(vec![4, 3, 2, 1]));
+    assert_eq!(account.my_array, vec![4, 3, 2, 1]);
 
     println!("account ID: {:?}", account.my_array);
 
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_unnest_arrays_with_null_elements() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let ids: Vec<i64> = vec![1, 2, 3];
+    let names: Vec<Option<String>> = vec![Some("a".into()), None, Some("c".into())];
+    let scores: &[Option<f64>] = &[None, Some(0.5), Some(1.0)];
+
+    // no type overrides needed: the elements of each array are checked, `NULL` included
+    let rows = sqlx::query!(
+        "SELECT * FROM UNNEST($1::int8[], $2::text[], $3::float8[], $4::bool[]) \
+         AS batch(id, name, score, flag)",
+        &ids,
+        names,
+        scores,
+        [Some(true), None, Some(false)],
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    let rows: Vec<_> = rows
+        .into_iter()
+        .map(|row| (row.id, row.name, row.score, row.flag))
+        .collect();
+
+    assert_eq!(
+        rows,
+        [
+            (Some(1), Some("a".into()), None, Some(true)),
+            (Some(2), None, Some(0.5), None),
+            (Some(3), Some("c".into()), Some(1.0), Some(false)),
+        ]
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_list_params() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let ids: Vec<i32> = vec![4, 1, 3];
+
+    // checked and bound as an `int4[]`
+    let found = sqlx::query_scalar!(
+        r#"SELECT x AS "x!" FROM generate_series(1, 5) AS x WHERE x IN ($1...) ORDER BY x"#,
+        &ids
+    )
+    .fetch_all(&mut conn)
+    .await?;
+    assert_eq!(found, [1, 3, 4]);
+
+    let others = sqlx::query_scalar!(
+        r#"SELECT x AS "x!" FROM generate_series(1, 5) AS x WHERE x NOT IN ( $1... ) ORDER BY x"#,
+        &ids[..0]
+    )
+    .fetch_all(&mut conn)
+    .await?;
+    assert_eq!(others, [1, 2, 3, 4, 5]);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn fetch_is_usable_issue_224() -> anyhow::Result<()> {
     // ensures that the stream returned by `query::Map::fetch()` is usable with `TryStreamExt`
@@ -592,6 +729,18 @@ async fn test_bind_arg_override_exact() -> anyhow::Result<()> {
 
     assert_eq!(record.id, Some(1i32));
 
+    // overrides are declared to Postgres when parameter types can't be inferred
+    let record = sqlx::query!(
+        r#"select $1::int4 is null as "is_null!", $2 is null as "also_null!""#,
+        Some(MyInt4(1)) as Option<MyInt4>,
+        None::<String> as Option<String>
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert!(!record.is_null);
+    assert!(record.also_null);
+
     Ok(())
 }
 
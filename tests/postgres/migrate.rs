@@ -1,6 +1,6 @@
-use sqlx::migrate::Migrator;
+use sqlx::migrate::{Migrate, MigrateError, Migrator, MIGRATIONS_TABLE_VERSION};
 use sqlx::pool::PoolConnection;
-use sqlx::postgres::{PgConnection, Postgres};
+use sqlx::postgres::{PgConnection, PgSchemaMigrator, Postgres};
 use sqlx::Executor;
 use sqlx::Row;
 use std::path::Path;
@@ -85,6 +85,226 @@ async fn no_tx(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx::test(migrations = false)]
+async fn per_schema(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+    conn.execute("DROP SCHEMA IF EXISTS tenant_a, tenant_b CASCADE")
+        .await?;
+    conn.execute("DROP TABLE IF EXISTS _sqlx_migrations_per_schema")
+        .await?;
+
+    let migrator = Migrator::new(Path::new("tests/postgres/migrations_simple")).await?;
+
+    PgSchemaMigrator::new(&migrator)
+        .schemas(["tenant_a"])
+        .schemas_from_query("SELECT 'tenant_b'")
+        .create_schemas(true)
+        .run(&mut conn)
+        .await?;
+
+    for schema in ["tenant_a", "tenant_b"] {
+        let res: String = conn
+            .fetch_one(&*format!(
+                "SELECT some_payload FROM {schema}.migrations_simple_test"
+            ))
+            .await?
+            .get(0);
+        assert_eq!(res, "110_suffix");
+    }
+
+    // nothing should have been created outside of the tenant schemas
+    let exists: bool = conn
+        .fetch_one("SELECT to_regclass('public.migrations_simple_test') IS NOT NULL")
+        .await?
+        .get(0);
+    assert!(!exists);
+
+    let applied: Vec<(String, i64)> = sqlx::query_as(
+        "SELECT schema, version FROM _sqlx_migrations_per_schema ORDER BY schema, version",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    assert_eq!(
+        applied,
+        [
+            ("tenant_a".to_string(), 20220721115250),
+            ("tenant_a".to_string(), 20220721115524),
+            ("tenant_b".to_string(), 20220721115250),
+            ("tenant_b".to_string(), 20220721115524),
+        ]
+    );
+
+    // running it a 2nd time should still work
+    PgSchemaMigrator::new(&migrator)
+        .schemas(["tenant_a", "tenant_b"])
+        .run(&mut conn)
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn records_metadata(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    // a table as created by earlier versions, which must be upgraded
+    conn.execute(
+        r#"
+CREATE TABLE _sqlx_migrations (
+    version BIGINT PRIMARY KEY,
+    description TEXT NOT NULL,
+    installed_on TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    success BOOLEAN NOT NULL,
+    checksum BYTEA NOT NULL,
+    execution_time BIGINT NOT NULL
+);
+        "#,
+    )
+    .await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/postgres/migrations_simple")).await?;
+    migrator.set_app_version("1.2.3");
+
+    assert_eq!(
+        conn.migrations_table_version("_sqlx_migrations").await?,
+        Some(1)
+    );
+
+    migrator.set_schema_upgrade(false);
+    let res = migrator.run(&mut conn).await;
+    assert!(matches!(
+        res,
+        Err(MigrateError::SchemaUpgradeRequired(
+            _,
+            1,
+            MIGRATIONS_TABLE_VERSION
+        ))
+    ));
+
+    migrator.set_schema_upgrade(true);
+    migrator.run(&mut conn).await?;
+
+    assert_eq!(
+        conn.migrations_table_version("_sqlx_migrations").await?,
+        Some(MIGRATIONS_TABLE_VERSION)
+    );
+
+    let applied = conn.list_applied_migrations("_sqlx_migrations").await?;
+    assert_eq!(applied.len(), 2);
+
+    let user: String = conn.fetch_one("SELECT current_user").await?.get(0);
+
+    for migration in applied {
+        assert_eq!(migration.applied_by.as_deref(), Some(&*user));
+        assert!(migration.hostname.is_some());
+        assert_eq!(migration.app_version.as_deref(), Some("1.2.3"));
+        assert!(migration.execution_time.is_some());
+    }
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn skip(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/postgres/migrations_simple")).await?;
+
+    migrator.set_skip([1]);
+    let err = migrator.run(&mut conn).await.unwrap_err();
+    assert!(matches!(err, MigrateError::VersionNotPresent(1)), "{err}");
+
+    // the second migration is only recorded as skipped
+    migrator.set_skip([20220721115524]);
+    migrator.run(&mut conn).await?;
+    migrator.run(&mut conn).await?;
+
+    let applied = conn.list_applied_migrations("_sqlx_migrations").await?;
+    assert_eq!(applied.len(), 2);
+    assert!(!applied[0].skipped);
+    assert!(applied[1].skipped);
+    assert_eq!(applied[1].execution_time, None);
+
+    let res: i64 = conn
+        .fetch_one("SELECT some_payload FROM migrations_simple_test")
+        .await?
+        .get(0);
+    assert_eq!(res, 100);
+
+    // and applied once it is no longer skipped
+    migrator.set_skip([]);
+    migrator.run(&mut conn).await?;
+
+    let applied = conn.list_applied_migrations("_sqlx_migrations").await?;
+    assert_eq!(applied.len(), 2);
+    assert!(!applied[1].skipped);
+
+    let res: String = conn
+        .fetch_one("SELECT some_payload FROM migrations_simple_test")
+        .await?
+        .get(0);
+    assert_eq!(res, "110_suffix");
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn custom_table_name(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+    conn.execute("DROP SCHEMA IF EXISTS custom_migrations CASCADE")
+        .await?;
+    conn.execute("CREATE SCHEMA custom_migrations").await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/postgres/migrations_reversible")).await?;
+    migrator.set_table_name("custom_migrations.schema_migrations");
+    migrator.run(&mut conn).await?;
+
+    let versions: Vec<i64> = sqlx::query_scalar(
+        "SELECT version FROM custom_migrations.schema_migrations ORDER BY version",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    assert_eq!(versions, [20220721124650, 20220721125033]);
+
+    let default_exists: bool =
+        sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+            .fetch_one(&mut *conn)
+            .await?;
+    assert!(!default_exists);
+
+    // running it a 2nd time should still work
+    migrator.run(&mut conn).await?;
+
+    migrator.undo(&mut conn, 20220721124650).await?;
+
+    let applied = conn
+        .list_applied_migrations("custom_migrations.schema_migrations")
+        .await?;
+    assert_eq!(applied.len(), 1);
+
+    // the per-schema table is named after the migrator's table
+    conn.execute("DROP SCHEMA IF EXISTS tenant_c CASCADE")
+        .await?;
+
+    PgSchemaMigrator::new(&migrator)
+        .schemas(["tenant_c"])
+        .create_schemas(true)
+        .run(&mut conn)
+        .await?;
+
+    let applied: Vec<i64> = sqlx::query_scalar(
+        "SELECT version FROM custom_migrations.schema_migrations_per_schema ORDER BY version",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+    assert_eq!(applied, [20220721124650, 20220721125033]);
+
+    conn.execute("DROP SCHEMA custom_migrations, tenant_c CASCADE")
+        .await?;
+
+    Ok(())
+}
+
 /// Ensure that we have a clean initial state.
 async fn clean_up(conn: &mut PgConnection) -> anyhow::Result<()> {
     conn.execute("DROP DATABASE IF EXISTS test_db").await.ok();
@@ -2,10 +2,16 @@ use futures::{Stream, StreamExt, TryStreamExt};
 
 use sqlx::postgres::types::Oid;
 use sqlx::postgres::{
-    PgAdvisoryLock, PgConnectOptions, PgConnection, PgDatabaseError, PgErrorPosition, PgListener,
-    PgPoolOptions, PgRow, PgSeverity, Postgres, PG_COPY_MAX_DATA_LEN,
+    PgAdvisoryLock, PgConnectOptions, PgConnection, PgDatabaseError, PgErrorPosition, PgJsonbPath,
+    PgJsonbSet, PgListener, PgPoolOptions, PgReplayReader, PgReplayValue, PgRow, PgSetting,
+    PgSeverity, PgStatementRecorder, PgTransactionExt, PgValueFormat, Postgres,
+    PG_COPY_MAX_DATA_LEN,
+};
+use sqlx::types::{Checked, Json, Saturating};
+use sqlx::{
+    CachedStatements, Column, Connection, Executor, FetchOptions, QueryBuilder, Row, Statement,
+    TypeInfo,
 };
-use sqlx::{Column, Connection, Executor, Row, Statement, TypeInfo};
 use sqlx_core::{bytes::Bytes, error::BoxDynError};
 use sqlx_test::{new, pool, setup_if_needed};
 use std::env;
@@ -190,6 +196,43 @@ CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY);
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_reports_command_tags() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let done = conn
+        .execute("CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY, name TEXT)")
+        .await?;
+
+    assert_eq!(done.command(), Some("CREATE TABLE"));
+
+    let done = conn
+        .execute("INSERT INTO users (id) VALUES (1), (2), (3)")
+        .await?;
+
+    assert_eq!(done.command_tag(), Some("INSERT 0 3"));
+    assert_eq!(done.command(), Some("INSERT"));
+    assert_eq!(done.inserted_oid(), None);
+
+    let done = sqlx::query("UPDATE users SET name = $1 WHERE id > 1")
+        .bind("a")
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(done.command_tag(), Some("UPDATE 2"));
+    assert_eq!(done.command(), Some("UPDATE"));
+
+    // the tag of the last statement is kept
+    let done = conn
+        .execute("DELETE FROM users WHERE id = 1; SELECT * FROM users")
+        .await?;
+
+    assert_eq!(done.rows_affected(), 3);
+    assert_eq!(done.command_tag(), Some("SELECT 2"));
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_nest_map() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -385,6 +428,114 @@ async fn it_can_query_all_scalar() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_fetches_rows_in_batches() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let options = FetchOptions { fetch_size: 1000 };
+
+    let mut ids = sqlx::query_scalar::<_, i64>("SELECT i FROM generate_series(1, $1) i")
+        .bind(100_000_i64)
+        .fetch_with(&mut conn, options);
+
+    let mut expected = 0;
+    while let Some(id) = ids.try_next().await? {
+        expected += 1;
+        assert_eq!(id, expected);
+    }
+    assert_eq!(expected, 100_000);
+    drop(ids);
+
+    // a fetch abandoned in the middle of a batch is ended before the next query
+    let mut rows =
+        sqlx::query("SELECT i FROM generate_series(1, 10000) i").fetch_with(&mut conn, options);
+    let _ = rows.try_next().await?;
+    drop(rows);
+
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    // errors end the fetch as well
+    let res = sqlx::query("SELECT 1 / (i - 1500) FROM generate_series(1, 2000) i")
+        .fetch_with(&mut conn, options)
+        .try_collect::<Vec<_>>()
+        .await;
+    assert!(res.is_err());
+
+    let mut tx = conn.begin().await?;
+
+    let count = sqlx::query_as::<_, (i32,)>("SELECT i FROM generate_series(1, 2500) i")
+        .fetch_with(&mut *tx, options)
+        .try_fold(0, |count, _| async move { Ok(count + 1) })
+        .await?;
+    assert_eq!(count, 2500);
+
+    let value: i32 = sqlx::query_scalar("SELECT 2").fetch_one(&mut *tx).await?;
+    assert_eq!(value, 2);
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_streams_scalars() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut ids = sqlx::query_scalar::<_, i64>("SELECT i, 'ignored' FROM generate_series(1, $1) i")
+        .bind(100_000_i64)
+        .fetch_first_column(&mut conn);
+
+    let mut expected = 0;
+    while let Some(id) = ids.try_next().await? {
+        expected += 1;
+        assert_eq!(id, expected);
+    }
+    assert_eq!(expected, 100_000);
+    drop(ids);
+
+    let scalar: Vec<Option<String>> = sqlx::query_scalar("SELECT 'a' UNION ALL SELECT NULL")
+        .fetch_first_column(&mut conn)
+        .try_collect()
+        .await?;
+    assert_eq!(scalar, vec![Some("a".to_owned()), None]);
+
+    let pool = pool::<Postgres>().await?;
+    let sum: i32 = sqlx::query_scalar("SELECT i FROM generate_series(1, 10) i")
+        .fetch_first_column(&pool)
+        .try_fold(0, |sum, i: i32| async move { Ok(sum + i) })
+        .await?;
+    assert_eq!(sum, 55);
+
+    let err = sqlx::query_scalar::<_, i32>("SELECT 'text'::text")
+        .fetch_first_column(&mut conn)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, sqlx::Error::ColumnDecode { index, .. } if index == "0"),
+        "{err:?}"
+    );
+
+    let err = sqlx::query_scalar::<_, i32>("SELECT")
+        .fetch_first_column(&mut conn)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            sqlx::Error::ColumnIndexOutOfBounds { index: 0, len: 0 }
+        ),
+        "{err:?}"
+    );
+
+    // the connection is still usable after a decode error
+    let scalar: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(scalar, 1);
+
+    Ok(())
+}
+
 #[ignore]
 #[sqlx_macros::test]
 async fn copy_can_work_with_failed_transactions() -> anyhow::Result<()> {
@@ -568,278 +719,1510 @@ async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
 }
 
 #[sqlx_macros::test]
-async fn it_can_drop_multiple_transactions() -> anyhow::Result<()> {
+async fn it_clears_cached_statements_on_set_search_path() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
 
-    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_users_3952 (id INTEGER PRIMARY KEY)")
+    conn.execute(
+        r#"
+DROP SCHEMA IF EXISTS "search_path_A" CASCADE;
+DROP SCHEMA IF EXISTS search_path_b CASCADE;
+CREATE SCHEMA "search_path_A";
+CREATE SCHEMA search_path_b;
+CREATE TABLE "search_path_A".tenant_data (value TEXT NOT NULL);
+CREATE TABLE search_path_b.tenant_data (value INTEGER NOT NULL);
+INSERT INTO "search_path_A".tenant_data VALUES ('a');
+INSERT INTO search_path_b.tenant_data VALUES (2);
+        "#,
+    )
+    .await?;
+
+    // the same statement, run against tables with a different type of column
+    let sql = "SELECT value FROM tenant_data WHERE $1";
+
+    conn.set_search_path(&["search_path_A"]).await?;
+    let row = sqlx::query(sql).bind(true).fetch_one(&mut conn).await?;
+    assert_eq!(row.try_get::<String, _>(0)?, "a");
+
+    conn.set_search_path(&["search_path_b", "public"]).await?;
+    let row = sqlx::query(sql).bind(true).fetch_one(&mut conn).await?;
+    assert_eq!(row.try_get::<i32, _>(0)?, 2);
+
+    let search_path: String = sqlx::query_scalar("SELECT current_setting('search_path')")
+        .fetch_one(&mut conn)
         .await?;
+    assert_eq!(search_path, "search_path_b, public");
 
-    conn.execute("TRUNCATE _sqlx_users_3952").await?;
+    conn.execute("DROP SCHEMA \"search_path_A\", search_path_b CASCADE")
+        .await?;
 
-    // begin .. (drop)
+    Ok(())
+}
 
-    // run 2 times to see what happens if we drop transactions repeatedly
-    for _ in 0..2 {
-        {
-            let mut tx = conn.begin().await?;
+#[sqlx_macros::test]
+async fn it_clears_cached_statements_on_raw_search_path_changes() -> anyhow::Result<()> {
+    use sqlx::postgres::PgTransactionExt;
 
-            // do actually something before dropping
-            let _user = sqlx::query("INSERT INTO _sqlx_users_3952 (id) VALUES ($1) RETURNING id")
-                .bind(20_i32)
-                .fetch_one(&mut *tx)
-                .await?;
-        }
+    let mut conn = new::<Postgres>().await?;
 
-        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_3952")
-            .fetch_one(&mut conn)
-            .await?;
+    conn.execute(
+        r#"
+DROP SCHEMA IF EXISTS raw_search_path_a CASCADE;
+DROP SCHEMA IF EXISTS raw_search_path_b CASCADE;
+CREATE SCHEMA raw_search_path_a;
+CREATE SCHEMA raw_search_path_b;
+CREATE TABLE raw_search_path_a.tenant_data (value TEXT NOT NULL);
+CREATE TABLE raw_search_path_b.tenant_data (id INTEGER NOT NULL, value TEXT NOT NULL);
+INSERT INTO raw_search_path_a.tenant_data VALUES ('a');
+INSERT INTO raw_search_path_b.tenant_data VALUES (1, 'b');
+        "#,
+    )
+    .await?;
 
-        assert_eq!(count, 0);
+    // the same statement, returning different columns for each tenant
+    let sql = "SELECT * FROM tenant_data WHERE $1";
+
+    async fn tenant(conn: &mut PgConnection, sql: &str) -> anyhow::Result<String> {
+        let row = sqlx::query(sql).bind(true).fetch_one(conn).await?;
+        Ok(row.try_get("value")?)
     }
 
+    conn.execute("SET search_path TO raw_search_path_a").await?;
+    assert_eq!(tenant(&mut conn, sql).await?, "a");
+
+    conn.execute("SET search_path TO raw_search_path_b").await?;
+    assert_eq!(tenant(&mut conn, sql).await?, "b");
+
+    sqlx::query("SELECT set_config('search_path', $1, false)")
+        .bind("raw_search_path_a")
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(tenant(&mut conn, sql).await?, "a");
+
+    // reverted at the end of the transaction
+    let mut tx = conn.begin().await?;
+    tx.execute("SET LOCAL search_path TO raw_search_path_b")
+        .await?;
+    assert_eq!(tenant(&mut tx, sql).await?, "b");
+    tx.commit().await?;
+
+    assert_eq!(tenant(&mut conn, sql).await?, "a");
+
+    let mut tx = conn.begin().await?;
+    tx.set_local("search_path", "raw_search_path_b").await?;
+    assert_eq!(tenant(&mut tx, sql).await?, "b");
+
+    // reverted by rolling back to a savepoint
+    let mut savepoint = tx.begin().await?;
+    savepoint
+        .execute("SET LOCAL search_path TO raw_search_path_a")
+        .await?;
+    assert_eq!(tenant(&mut savepoint, sql).await?, "a");
+    savepoint.rollback().await?;
+
+    assert_eq!(tenant(&mut tx, sql).await?, "b");
+    tx.rollback().await?;
+
+    assert_eq!(tenant(&mut conn, sql).await?, "a");
+
+    conn.execute("DROP SCHEMA raw_search_path_a, raw_search_path_b CASCADE")
+        .await?;
+
     Ok(())
 }
 
-// run with `cargo test --features postgres -- --ignored --nocapture pool_smoke_test`
-#[ignore]
 #[sqlx_macros::test]
-async fn pool_smoke_test() -> anyhow::Result<()> {
-    use futures::{future, task::Poll, Future};
+async fn it_reprepares_stale_statements() -> anyhow::Result<()> {
+    setup_if_needed();
 
-    eprintln!("starting pool");
+    // connections made with the same options share the statements found to be stale, like
+    // those of a pool
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = PgConnection::connect_with(&options).await?;
+    let mut other = PgConnection::connect_with(&options).await?;
 
-    let pool = PgPoolOptions::new()
-        .acquire_timeout(Duration::from_secs(5))
-        .min_connections(1)
-        .max_connections(1)
-        .connect(&dotenvy::var("DATABASE_URL")?)
-        .await?;
+    conn.execute(
+        r#"
+DROP TABLE IF EXISTS stale_statements_test;
+CREATE TABLE stale_statements_test (value INTEGER NOT NULL);
+INSERT INTO stale_statements_test VALUES (1);
+        "#,
+    )
+    .await?;
 
-    // spin up more tasks than connections available, and ensure we don't deadlock
-    for i in 0..200 {
-        let pool = pool.clone();
-        sqlx_core::rt::spawn(async move {
-            for j in 0.. {
-                if let Err(e) = sqlx::query("select 1 + 1").execute(&pool).await {
-                    // normal error at termination of the test
-                    if matches!(e, sqlx::Error::PoolClosed) {
-                        eprintln!("pool task {i} exiting normally after {j} iterations");
-                    } else {
-                        eprintln!("pool task {i} dying due to {e} after {j} iterations");
-                    }
-                    break;
-                }
+    let sql = "SELECT value FROM stale_statements_test WHERE $1";
 
-                // shouldn't be necessary if the pool is fair
-                // sqlx_core::rt::yield_now().await;
-            }
-        });
+    for conn in [&mut conn, &mut other] {
+        let row = sqlx::query(sql).bind(true).fetch_one(&mut *conn).await?;
+        assert_eq!(row.try_get::<i32, _>(0)?, 1);
     }
 
-    // spawn a bunch of tasks that attempt to acquire but give up to ensure correct handling
-    // of cancellations
-    for _ in 0..50 {
-        let pool = pool.clone();
-        sqlx_core::rt::spawn(async move {
-            while !pool.is_closed() {
-                let mut acquire = pin!(pool.acquire());
+    conn.execute("ALTER TABLE stale_statements_test ALTER COLUMN value TYPE TEXT")
+        .await?;
 
-                // poll the acquire future once to put the waiter in the queue
-                future::poll_fn(move |cx| {
-                    let _ = acquire.as_mut().poll(cx);
-                    Poll::Ready(())
-                })
-                .await;
+    // the cached plan changed its result type, so the statement is prepared again
+    let row = sqlx::query(sql).bind(true).fetch_one(&mut conn).await?;
+    assert_eq!(row.try_get::<String, _>(0)?, "1");
 
-                // this one is necessary since this is a hot loop,
-                // otherwise this task will never be descheduled
-                sqlx_core::rt::yield_now().await;
-            }
-        });
-    }
+    // the other connection prepares it again before using it, so it works in a transaction
+    // too, which the failed statement would abort
+    let mut tx = other.begin().await?;
+    let row = sqlx::query(sql).bind(true).fetch_one(&mut *tx).await?;
+    assert_eq!(row.try_get::<String, _>(0)?, "1");
+    tx.rollback().await?;
 
-    eprintln!("sleeping for 30 seconds");
+    // the statement no longer exists on the server
+    conn.execute("DEALLOCATE ALL").await?;
+    let row = sqlx::query(sql).bind(true).fetch_one(&mut conn).await?;
+    assert_eq!(row.try_get::<String, _>(0)?, "1");
 
-    sqlx_core::rt::sleep(Duration::from_secs(30)).await;
+    conn.execute("DROP TABLE stale_statements_test").await?;
 
-    // assert_eq!(pool.size(), 10);
+    Ok(())
+}
 
-    eprintln!("closing pool");
+#[sqlx_macros::test]
+async fn it_requests_text_results_for_configured_types() -> anyhow::Result<()> {
+    use sqlx::postgres::PgValueFormat;
+    use sqlx::ValueRef;
 
-    sqlx_core::rt::timeout(Duration::from_secs(30), pool.close()).await?;
+    let mut conn = new::<Postgres>().await?;
 
-    eprintln!("pool closed successfully");
+    // `point`
+    conn.set_result_format(Oid(600), PgValueFormat::Text);
+
+    let row = sqlx::query("SELECT '(1,2)'::point, '\\x0102'::bytea, $1::int4")
+        .bind(3_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.try_get_raw(0)?.format(), PgValueFormat::Text);
+    assert_eq!(row.try_get_unchecked::<String, _>(0)?, "(1,2)");
+    // the other columns are still binary
+    assert_eq!(row.try_get_raw(1)?.format(), PgValueFormat::Binary);
+    assert_eq!(row.try_get::<Vec<u8>, _>(1)?, [1, 2]);
+    assert_eq!(row.try_get::<i32, _>(2)?, 3);
+
+    conn.set_result_format(Oid(600), PgValueFormat::Binary);
+
+    let row = sqlx::query("SELECT '(1,2)'::point")
+        .fetch_one(&mut conn)
+        .await?;
+    let value = row.try_get_raw(0)?;
+    assert_eq!(value.format(), PgValueFormat::Binary);
+    assert!(!value.is_null());
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn test_invalid_query() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+async fn it_applies_deadlines_to_queries() -> anyhow::Result<()> {
+    use sqlx::deadline::Deadline;
 
-    conn.execute("definitely not a correct query")
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let timeout = "SELECT current_setting('statement_timeout')";
+
+    let (during, err) = Deadline::after(Duration::from_millis(500))
+        .scope(async {
+            let during: String = sqlx::query_scalar(timeout).fetch_one(&pool).await?;
+            let err = sqlx::query("SELECT pg_sleep(5)")
+                .execute(&pool)
+                .await
+                .unwrap_err();
+
+            anyhow::Ok((during, err))
+        })
+        .await?;
+
+    assert!(during.ends_with("ms") && during != "0", "{during}");
+    // query_canceled
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("57014"),
+        "{err}"
+    );
+
+    // the session default is back once there is no deadline
+    let after: String = sqlx::query_scalar(timeout).fetch_one(&pool).await?;
+    assert_eq!(after, "0");
+
+    let err = Deadline::after(Duration::ZERO)
+        .scope(sqlx::query("SELECT 1").execute(&pool))
         .await
         .unwrap_err();
+    assert!(matches!(err, sqlx::Error::DeadlineExceeded), "{err}");
 
-    let mut s = conn.fetch("select 1");
-    let row = s.try_next().await?.unwrap();
+    // acquiring a connection gives up at the deadline too
+    let _conn = pool.acquire().await?;
+    let err = Deadline::after(Duration::from_millis(100))
+        .scope(pool.acquire())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::PoolTimedOut), "{err}");
 
-    assert_eq!(row.get::<i32, _>(0), 1i32);
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_keeps_configured_statement_timeouts_under_deadlines() -> anyhow::Result<()> {
+    use sqlx::deadline::Deadline;
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn =
+        PgConnection::connect_with(&options.options([("statement_timeout", "300")])).await?;
+
+    let timeout = "SELECT current_setting('statement_timeout')";
+
+    // a deadline further away than the configured timeout does not raise it
+    let during: String = Deadline::after(Duration::from_secs(60))
+        .scope(sqlx::query_scalar(timeout).fetch_one(&mut conn))
+        .await?;
+    assert_eq!(during, "300ms");
+
+    let during: String = Deadline::after(Duration::from_millis(200))
+        .scope(sqlx::query_scalar(timeout).fetch_one(&mut conn))
+        .await?;
+    let millis: u64 = during.trim_end_matches("ms").parse()?;
+    assert!(millis > 0 && millis <= 200, "{during}");
+
+    let after: String = sqlx::query_scalar(timeout).fetch_one(&mut conn).await?;
+    assert_eq!(after, "300ms");
 
     Ok(())
 }
 
-/// Tests the edge case of executing a completely empty query string.
-///
-/// This gets flagged as an `EmptyQueryResponse` in Postgres. We
-/// catch this and just return no rows.
-#[sqlx_macros::test]
-async fn test_empty_query() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
-    let done = conn.execute("").await?;
+#[cfg(feature = "chrono")]
+#[sqlx_macros::test]
+async fn it_enforces_time_zone_policy() -> anyhow::Result<()> {
+    use sqlx::postgres::types::PgUtc;
+    use sqlx::postgres::PgTimeZonePolicy;
+    use sqlx::types::chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+    setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn =
+        PgConnection::connect_with(&options.time_zone_policy(PgTimeZonePolicy::Deny)).await?;
+
+    conn.execute(
+        "SET TIME ZONE 'America/New_York'; \
+         CREATE TEMPORARY TABLE events (id INTEGER PRIMARY KEY, happened_at TIMESTAMPTZ NOT NULL)",
+    )
+    .await?;
+
+    let naive = NaiveDate::from_ymd_opt(2024, 3, 15)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap();
+
+    let res = sqlx::query("INSERT INTO events (id, happened_at) VALUES ($1, $2)")
+        .bind(1_i32)
+        .bind(naive)
+        .execute(&mut conn)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::Encode(_))), "{res:?}");
+
+    let res = sqlx::query_scalar::<_, NaiveDateTime>("SELECT $1::timestamptz::timestamp")
+        .bind(Utc::now())
+        .fetch_one(&mut conn)
+        .await;
+    assert!(res.is_ok(), "{res:?}");
+
+    let res = sqlx::query_scalar::<_, NaiveDateTime>("SELECT $1::timestamp")
+        .bind(Utc::now())
+        .fetch_one(&mut conn)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::Encode(_))), "{res:?}");
+
+    // checking a statement doesn't abort the transaction, even if the server can't infer its
+    // parameters without their types
+    let mut tx = conn.begin().await?;
+
+    let is_null: bool = sqlx::query_scalar("SELECT $1 IS NULL")
+        .bind(naive)
+        .fetch_one(&mut *tx)
+        .await?;
+    assert!(!is_null);
+
+    sqlx::query("INSERT INTO events (id, happened_at) VALUES ($1, $2)")
+        .bind(1_i32)
+        .bind(PgUtc(naive))
+        .execute(&mut *tx)
+        .await?;
+
+    // casting the parameter to its own type marks the conversion as intended
+    sqlx::query("INSERT INTO events (id, happened_at) VALUES ($1, $2::timestamp)")
+        .bind(2_i32)
+        .bind(naive)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    let happened_at: Vec<DateTime<Utc>> =
+        sqlx::query_scalar("SELECT happened_at FROM events ORDER BY id")
+            .fetch_all(&mut conn)
+            .await?;
+    assert_eq!(happened_at[0], naive.and_utc());
+    assert_eq!(
+        happened_at[1],
+        NaiveDate::from_ymd_opt(2024, 3, 15)
+            .unwrap()
+            .and_hms_opt(16, 0, 0)
+            .unwrap()
+            .and_utc()
+    );
+
+    let PgUtc(decoded): PgUtc<NaiveDateTime> =
+        sqlx::query_scalar("SELECT happened_at FROM events WHERE id = 1")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(decoded, naive);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rolls_back_failed_statements_with_on_error_rollback() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = PgConnection::connect_with(&options.on_error_rollback(true)).await?;
+
+    conn.execute("CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("INSERT INTO users (id) VALUES ($1)")
+        .bind(1_i32)
+        .execute(&mut *tx)
+        .await?;
+
+    // a duplicate key fails the statement, but not the transaction
+    let res = sqlx::query("INSERT INTO users (id) VALUES ($1)")
+        .bind(1_i32)
+        .execute(&mut *tx)
+        .await;
+
+    assert!(res.is_err());
+    assert!(tx.fetch_optional("SELECT 1 / 0").await.is_err());
+
+    sqlx::query("INSERT INTO users (id) VALUES ($1)")
+        .bind(2_i32)
+        .execute(&mut *tx)
+        .await?;
+
+    // nested transactions use savepoints of their own
+    let mut tx2 = tx.begin().await?;
+
+    sqlx::query("INSERT INTO users (id) VALUES ($1)")
+        .bind(3_i32)
+        .execute(&mut *tx2)
+        .await?;
+
+    tx2.rollback().await?;
+    tx.commit().await?;
+
+    let ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM users ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(ids, [1, 2]);
+
+    // outside of a transaction, nothing changes
+    assert!(conn.execute("SELECT 1 / 0").await.is_err());
+    assert!(!conn.is_in_transaction());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_finishes_statement_savepoints_of_dropped_streams() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = PgConnection::connect_with(&options.on_error_rollback(true)).await?;
+
+    conn.execute("CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut tx = conn.begin().await?;
+
+    // the statement fails after the stream is dropped
+    {
+        let mut rows =
+            sqlx::query_scalar::<_, i32>("SELECT 1 / (3 - i) FROM generate_series(1, 5) i")
+                .fetch(&mut *tx);
+        assert_eq!(rows.try_next().await?, Some(0));
+    }
+
+    sqlx::query("INSERT INTO users (id) VALUES ($1)")
+        .bind(1_i32)
+        .execute(&mut *tx)
+        .await?;
+
+    // the statement succeeds after the stream is dropped
+    {
+        let mut rows = sqlx::query_scalar::<_, i32>("SELECT generate_series(1, 3)").fetch(&mut *tx);
+        assert_eq!(rows.try_next().await?, Some(1));
+    }
+
+    // nested transactions don't leave statement savepoints behind either
+    for id in [2, 3] {
+        let mut tx2 = tx.begin().await?;
+
+        sqlx::query("INSERT INTO users (id) VALUES ($1)")
+            .bind(id)
+            .execute(&mut *tx2)
+            .await?;
+
+        tx2.commit().await?;
+    }
+
+    let ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM users ORDER BY id")
+        .fetch_all(&mut *tx)
+        .await?;
+    assert_eq!(ids, [1, 2, 3]);
+
+    // every statement savepoint was released
+    assert!(tx
+        .execute("RELEASE SAVEPOINT _sqlx_statement")
+        .await
+        .is_err());
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_renames_statements_whose_name_is_taken() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = PgConnection::connect_with(&options.statement_name_prefix("taken_")).await?;
+
+    // as another client sharing the session would
+    conn.execute("PREPARE taken_1 AS SELECT 1").await?;
+
+    let value: i32 = sqlx::query_scalar("SELECT $1::int4")
+        .bind(2_i32)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 2);
+
+    let names: Vec<String> =
+        sqlx::query_scalar("SELECT name FROM pg_prepared_statements ORDER BY prepare_time")
+            .fetch_all(&mut conn)
+            .await?;
+    assert_eq!(names[0], "taken_1");
+    assert!(
+        names[1..]
+            .iter()
+            .all(|name| name.starts_with("taken_") && name.len() > 20),
+        "{names:?}"
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_drop_multiple_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_users_3952 (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    conn.execute("TRUNCATE _sqlx_users_3952").await?;
+
+    // begin .. (drop)
+
+    // run 2 times to see what happens if we drop transactions repeatedly
+    for _ in 0..2 {
+        {
+            let mut tx = conn.begin().await?;
+
+            // do actually something before dropping
+            let _user = sqlx::query("INSERT INTO _sqlx_users_3952 (id) VALUES ($1) RETURNING id")
+                .bind(20_i32)
+                .fetch_one(&mut *tx)
+                .await?;
+        }
+
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_3952")
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(count, 0);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_journals_statements_in_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE journaled (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let journal = {
+        let mut tx = conn.begin().await?;
+        let journal = tx.journal();
+
+        sqlx::query("INSERT INTO journaled (id) VALUES ($1), ($2)")
+            .bind(1_i32)
+            .bind(2_i32)
+            .execute(&mut *tx)
+            .await?;
+
+        // a savepoint without a journal of its own is recorded in the outer one
+        let mut tx2 = tx.begin().await?;
+        tx2.execute("DELETE FROM journaled WHERE id = 2").await?;
+        tx2.commit().await?;
+
+        assert!(tx.execute("SELECT 1 / 0").await.is_err());
+        assert!(!journal.is_rolled_back());
+
+        journal
+    };
+
+    // statements after the transaction is gone are not recorded
+    conn.execute("SELECT 1").await?;
+
+    assert!(journal.is_rolled_back());
+
+    let entries = journal.entries();
+    let sql: Vec<&str> = entries.iter().map(|entry| entry.sql()).collect();
+
+    assert_eq!(
+        sql,
+        [
+            "INSERT INTO journaled (id) VALUES ($1), ($2)",
+            "DELETE FROM journaled WHERE id = 2",
+            "SELECT 1 / 0",
+        ]
+    );
+    assert_eq!(entries[0].rows_affected(), 2);
+    assert_eq!(entries[1].rows_affected(), 1);
+
+    // a committed transaction keeps its journal too
+    let mut tx = conn.begin().await?;
+    let journal = tx.journal();
+    tx.execute("INSERT INTO journaled (id) VALUES (3)").await?;
+    tx.commit().await?;
+
+    assert!(!journal.is_rolled_back());
+    assert_eq!(journal.entries().len(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_sets_settings_for_the_transaction() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let default_work_mem: String = sqlx::query_scalar("SHOW work_mem")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let mut tx = conn.begin().await?;
+
+    tx.set_local("work_mem", "256MB").await?;
+    tx.set_local("sqlx.tenant_id", "42").await?;
+    tx.set_local_setting(PgSetting::StatementTimeout(Duration::from_millis(1500)))
+        .await?;
+
+    let settings: (String, String, String) = sqlx::query_as(
+        "SELECT current_setting('work_mem'), current_setting('sqlx.tenant_id'), \
+         current_setting('statement_timeout')",
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    assert_eq!(settings, ("256MB".into(), "42".into(), "1500ms".into()));
+
+    // invalid names and values are rejected before anything is sent
+    assert!(matches!(
+        tx.set_local("work_mem = 1; RESET ALL", "64kB").await,
+        Err(sqlx::Error::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        tx.set_local_setting(PgSetting::WorkMem(1)).await,
+        Err(sqlx::Error::InvalidArgument(_))
+    ));
+
+    tx.commit().await?;
+
+    let work_mem: String = sqlx::query_scalar("SHOW work_mem")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(work_mem, default_work_mem);
+
+    // dropping the transaction resets them too
+    let mut tx = conn.begin().await?;
+    tx.set_local_setting(PgSetting::WorkMem(128 * 1024)).await?;
+    drop(tx);
+
+    let work_mem: String = sqlx::query_scalar("SHOW work_mem")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(work_mem, default_work_mem);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_estimates_row_counts() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE estimated AS SELECT * FROM generate_series(1, 1000) AS id",
+    )
+    .await?;
+
+    // without statistics, the estimate comes from the planner's guess for the table size
+    assert!(sqlx::estimate_count(&mut conn, "estimated").await? > 0);
+
+    conn.execute("ANALYZE estimated").await?;
+
+    assert_eq!(sqlx::estimate_count(&mut conn, "estimated").await?, 1000);
+    assert_eq!(
+        sqlx::estimate_count(&mut conn, "pg_temp.estimated").await?,
+        1000
+    );
+
+    let filtered =
+        sqlx::estimate_count(&mut conn, "SELECT * FROM estimated WHERE id <= 100").await?;
+    assert!((50..=200).contains(&filtered), "{filtered}");
+
+    assert!(sqlx::estimate_count(&mut conn, "no_such_table")
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[cfg(feature = "pg-monitoring")]
+#[sqlx_macros::test]
+async fn it_reads_monitoring_views() -> anyhow::Result<()> {
+    use sqlx::postgres::monitoring::{self, PgBackendState};
+
+    let mut conn = new::<Postgres>().await?;
+
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let activity = monitoring::activity(&mut conn).await?;
+    let this = activity
+        .iter()
+        .find(|backend| backend.pid == pid)
+        .expect("the current backend is in pg_stat_activity");
+
+    assert_eq!(this.state, Some(PgBackendState::Active));
+    assert_eq!(this.datname.as_deref(), Some("sqlx"));
+    assert!(this
+        .query
+        .as_deref()
+        .unwrap_or_default()
+        .contains("pg_stat_activity"));
+    assert!(this.backend_duration.is_some());
+
+    // `pg_stat_statements` must be preloaded by the server, which the test databases may not be
+    let available: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT FROM pg_extension WHERE extname = 'pg_stat_statements') \
+         AND current_setting('shared_preload_libraries') LIKE '%pg_stat_statements%'",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    if available {
+        let statements = monitoring::statements(&mut conn).await?;
+
+        assert!(statements
+            .windows(2)
+            .all(|pair| pair[0].total_exec_time >= pair[1].total_exec_time));
+    } else {
+        assert!(monitoring::statements(&mut conn).await.is_err());
+    }
+
+    Ok(())
+}
+
+#[cfg(all(any(feature = "chrono", feature = "time"), feature = "uuid"))]
+#[sqlx_macros::test]
+async fn it_fetches_json_arrays() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut out = Vec::new();
+
+    let count = sqlx::query(
+        r#"
+SELECT id, name, score, price, tags, doc, created_at, day, blob, guid, active
+FROM (VALUES
+    (1, 'a', 0.5::float4, 12.340::numeric(10, 3), ARRAY['x', NULL], '{"k": [1]}'::jsonb,
+     '2024-10-14 09:05:00.123456+00'::timestamptz, '2024-10-14'::date, '\x0102'::bytea,
+     '00010203-0405-0607-0809-0a0b0c0d0e0f'::uuid, true),
+    ($1, NULL, NULL, NULL, '{}', NULL, NULL, NULL, NULL, NULL, NULL)
+) t (id, name, score, price, tags, doc, created_at, day, blob, guid, active)
+"#,
+    )
+    .bind(2_i32)
+    .fetch_json_array(&mut conn, &mut out)
+    .await?;
+
+    assert_eq!(count, 2);
+
+    let json: serde_json::Value = serde_json::from_slice(&out)?;
+    assert_eq!(
+        json,
+        serde_json::json!([
+            {
+                "id": 1, "name": "a", "score": 0.5, "price": "12.340", "tags": ["x", null],
+                "doc": {"k": [1]}, "created_at": "2024-10-14T09:05:00.123456+00:00",
+                "day": "2024-10-14", "blob": "AQI=", "guid": "00010203-0405-0607-0809-0a0b0c0d0e0f",
+                "active": true
+            },
+            {
+                "id": 2, "name": null, "score": null, "price": null, "tags": [], "doc": null,
+                "created_at": null, "day": null, "blob": null, "guid": null, "active": null
+            }
+        ])
+    );
+
+    // columns are written in order, even when they share a name
+    out.clear();
+
+    let count = sqlx::query("SELECT 1 AS b, 2 AS a, 3 AS b FROM generate_series(1, 2)")
+        .fetch_json_lines(&mut conn, &mut out)
+        .await?;
+
+    assert_eq!(count, 2);
+    assert_eq!(
+        String::from_utf8(out.clone())?,
+        "{\"b\":1,\"a\":2,\"b\":3}\n{\"b\":1,\"a\":2,\"b\":3}\n"
+    );
+
+    out.clear();
+
+    let count = sqlx::query("SELECT 1 WHERE false")
+        .fetch_json_array(&mut conn, &mut out)
+        .await?;
+
+    assert_eq!((count, &out[..]), (0, &b"[]"[..]));
+
+    out.clear();
+
+    sqlx::query(
+        "SELECT '0044-03-15 BC'::date AS day, '0044-03-15 12:00:00 BC'::timestamp AS at, \
+         'infinity'::timestamptz AS until",
+    )
+    .fetch_json_lines(&mut conn, &mut out)
+    .await?;
+
+    assert_eq!(
+        String::from_utf8(out.clone())?,
+        "{\"day\":\"0044-03-15 BC\",\"at\":\"0044-03-15T12:00:00 BC\",\"until\":\"infinity\"}\n"
+    );
+
+    let err = sqlx::query("SELECT '1 day'::interval AS span")
+        .fetch_json_array(&mut conn, &mut Vec::new())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, sqlx::Error::ColumnDecode { .. }), "{err:?}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_manages_partitions() -> anyhow::Result<()> {
+    use sqlx::postgres::{PgPartitionInterval, PgPartitionManager};
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"
+DROP TABLE IF EXISTS partitioned_events, partitioned_events_archive;
+CREATE TABLE partitioned_events (id BIGINT, created_at TIMESTAMPTZ NOT NULL)
+    PARTITION BY RANGE (created_at);
+CREATE TABLE partitioned_events_archive (id BIGINT, created_at TIMESTAMPTZ NOT NULL);
+"#,
+    )
+    .await?;
+
+    let manager = PgPartitionManager::new("partitioned_events")
+        .interval(PgPartitionInterval::Month)
+        .premake(2)
+        .ddl("CREATE INDEX ON {partition} (id)")
+        .ddl("COMMENT ON TABLE {partition} IS {from}")
+        .retain(1)
+        .drop_detached(true);
+
+    let current: String = sqlx::query_scalar("SELECT to_char(localtimestamp, 'YYYY_MM')")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let report = manager.maintain(&mut conn).await?;
+
+    assert_eq!(report.created.len(), 3);
+    assert_eq!(report.created[0], format!("partitioned_events_p{current}"));
+    assert!(report.dropped.is_empty());
+
+    // idempotent
+    assert!(manager.create_partitions(&mut conn).await?.is_empty());
+
+    let comment: String = sqlx::query_scalar("SELECT obj_description($1::regclass, 'pg_class')")
+        .bind(&report.created[0])
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(comment.ends_with("-01 00:00:00"), "{comment}");
+
+    let indexes: i64 =
+        sqlx::query_scalar("SELECT count(*) FROM pg_indexes WHERE tablename = ANY($1)")
+            .bind(&report.created)
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(indexes, 3);
+
+    // an old partition is swept, while one from the previous month is retained
+    manager
+        .attach(
+            &mut conn,
+            "partitioned_events_archive",
+            "2000-01-01",
+            "2000-02-01",
+        )
+        .await?;
+
+    conn.execute(
+        "CREATE TABLE partitioned_events_previous PARTITION OF partitioned_events FOR VALUES \
+         FROM (date_trunc('month', localtimestamp) - interval '1 month') \
+         TO (date_trunc('month', localtimestamp))",
+    )
+    .await?;
+
+    assert_eq!(manager.partitions(&mut conn).await?.len(), 5);
+
+    let report = manager.maintain(&mut conn).await?;
+
+    assert!(report.created.is_empty());
+    assert_eq!(report.detached, ["partitioned_events_archive"]);
+    assert_eq!(report.dropped, ["partitioned_events_archive"]);
+
+    let partitions = manager.partitions(&mut conn).await?;
+    assert_eq!(partitions.len(), 4);
+    assert!(partitions
+        .iter()
+        .any(|p| p.name == "partitioned_events_previous"));
+    assert!(partitions[0].bound.starts_with("FOR VALUES FROM ("));
+
+    manager
+        .detach(&mut conn, "partitioned_events_previous")
+        .await?;
+    assert_eq!(manager.partitions(&mut conn).await?.len(), 3);
+
+    // only partitioned tables can be managed
+    let err = PgPartitionManager::new("partitioned_events_previous")
+        .partitions(&mut conn)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::InvalidArgument(_)), "{err:?}");
+
+    conn.execute("DROP TABLE partitioned_events, partitioned_events_previous")
+        .await?;
+
+    Ok(())
+}
+
+// run with `cargo test --features postgres -- --ignored --nocapture pool_smoke_test`
+#[ignore]
+#[sqlx_macros::test]
+async fn pool_smoke_test() -> anyhow::Result<()> {
+    use futures::{future, task::Poll, Future};
+
+    eprintln!("starting pool");
+
+    let pool = PgPoolOptions::new()
+        .acquire_timeout(Duration::from_secs(5))
+        .min_connections(1)
+        .max_connections(1)
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    // spin up more tasks than connections available, and ensure we don't deadlock
+    for i in 0..200 {
+        let pool = pool.clone();
+        sqlx_core::rt::spawn(async move {
+            for j in 0.. {
+                if let Err(e) = sqlx::query("select 1 + 1").execute(&pool).await {
+                    // normal error at termination of the test
+                    if matches!(e, sqlx::Error::PoolClosed) {
+                        eprintln!("pool task {i} exiting normally after {j} iterations");
+                    } else {
+                        eprintln!("pool task {i} dying due to {e} after {j} iterations");
+                    }
+                    break;
+                }
+
+                // shouldn't be necessary if the pool is fair
+                // sqlx_core::rt::yield_now().await;
+            }
+        });
+    }
+
+    // spawn a bunch of tasks that attempt to acquire but give up to ensure correct handling
+    // of cancellations
+    for _ in 0..50 {
+        let pool = pool.clone();
+        sqlx_core::rt::spawn(async move {
+            while !pool.is_closed() {
+                let mut acquire = pin!(pool.acquire());
+
+                // poll the acquire future once to put the waiter in the queue
+                future::poll_fn(move |cx| {
+                    let _ = acquire.as_mut().poll(cx);
+                    Poll::Ready(())
+                })
+                .await;
+
+                // this one is necessary since this is a hot loop,
+                // otherwise this task will never be descheduled
+                sqlx_core::rt::yield_now().await;
+            }
+        });
+    }
+
+    eprintln!("sleeping for 30 seconds");
+
+    sqlx_core::rt::sleep(Duration::from_secs(30)).await;
+
+    // assert_eq!(pool.size(), 10);
+
+    eprintln!("closing pool");
+
+    sqlx_core::rt::timeout(Duration::from_secs(30), pool.close()).await?;
+
+    eprintln!("pool closed successfully");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_invalid_query() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("definitely not a correct query")
+        .await
+        .unwrap_err();
+
+    let mut s = conn.fetch("select 1");
+    let row = s.try_next().await?.unwrap();
+
+    assert_eq!(row.get::<i32, _>(0), 1i32);
+
+    Ok(())
+}
+
+/// Tests the edge case of executing a completely empty query string.
+///
+/// This gets flagged as an `EmptyQueryResponse` in Postgres. We
+/// catch this and just return no rows.
+#[sqlx_macros::test]
+async fn test_empty_query() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let done = conn.execute("").await?;
+
+    assert_eq!(done.rows_affected(), 0);
+
+    Ok(())
+}
+
+/// Test a simple select expression. This should return the row.
+#[sqlx_macros::test]
+async fn test_select_expression() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut s = conn.fetch("SELECT 5");
+    let row = s.try_next().await?.unwrap();
+
+    assert!(5i32 == row.try_get::<i32, _>(0)?);
+
+    Ok(())
+}
+
+/// Test that we can interleave reads and writes to the database
+/// in one simple query. Using the `Cursor` API we should be
+/// able to fetch from both queries in sequence.
+#[sqlx_macros::test]
+async fn test_multi_read_write() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut s = conn.fetch(
+        "
+CREATE TABLE IF NOT EXISTS _sqlx_test_postgres_5112 (
+    id BIGSERIAL PRIMARY KEY,
+    text TEXT NOT NULL
+);
+
+SELECT 'Hello World' as _1;
+
+INSERT INTO _sqlx_test_postgres_5112 (text) VALUES ('this is a test');
+
+SELECT id, text FROM _sqlx_test_postgres_5112;
+    ",
+    );
+
+    let row = s.try_next().await?.unwrap();
+
+    assert!("Hello World" == row.try_get::<&str, _>("_1")?);
+
+    let row = s.try_next().await?.unwrap();
+
+    let id: i64 = row.try_get("id")?;
+    let text: &str = row.try_get("text")?;
+
+    assert_eq!(1_i64, id);
+    assert_eq!("this is a test", text);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_caches_statements() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    for i in 0..2 {
+        let row = sqlx::query("SELECT $1 AS val")
+            .bind(Oid(i))
+            .persistent(true)
+            .fetch_one(&mut conn)
+            .await?;
+
+        let val: Oid = row.get("val");
+
+        assert_eq!(Oid(i), val);
+    }
+
+    assert_eq!(1, conn.cached_statements_size());
+    conn.clear_cached_statements().await?;
+    assert_eq!(0, conn.cached_statements_size());
+
+    for i in 0..2 {
+        let row = sqlx::query("SELECT $1 AS val")
+            .bind(Oid(i))
+            .persistent(false)
+            .fetch_one(&mut conn)
+            .await?;
+
+        let val: Oid = row.get("val");
+
+        assert_eq!(Oid(i), val);
+    }
+
+    assert_eq!(0, conn.cached_statements_size());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_closes_statement_from_cache_issue_470() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+
+    // a capacity of 1 means that before each statement (after the first)
+    // we will close the previous statement
+    options = options.statement_cache_capacity(1);
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    for i in 0..5 {
+        let row = sqlx::query(&*format!("SELECT {i}::int4 AS val"))
+            .fetch_one(&mut conn)
+            .await?;
+
+        let val: i32 = row.get("val");
+
+        assert_eq!(i, val);
+    }
+
+    assert_eq!(1, conn.cached_statements_size());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_resizes_statement_cache() -> anyhow::Result<()> {
+    async fn prepared_on_server(conn: &mut PgConnection) -> anyhow::Result<i64> {
+        // a simple query, which is not prepared itself
+        let row = conn
+            .fetch_one("SELECT count(*) FROM pg_prepared_statements")
+            .await?;
+
+        Ok(row.try_get(0)?)
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    for i in 0..3 {
+        sqlx::query(&format!("SELECT {i}::int4"))
+            .fetch_one(&mut conn)
+            .await?;
+    }
+
+    assert_eq!(
+        conn.cached_statements(),
+        CachedStatements {
+            size: 3,
+            capacity: 100
+        }
+    );
+    assert_eq!(prepared_on_server(&mut conn).await?, 3);
+
+    // the least recently used statements are closed on the server
+    conn.set_statement_cache_capacity(1).await?;
+
+    assert_eq!(
+        conn.cached_statements(),
+        CachedStatements {
+            size: 1,
+            capacity: 1
+        }
+    );
+    assert_eq!(prepared_on_server(&mut conn).await?, 1);
+
+    let val: i32 = sqlx::query_scalar("SELECT 2::int4")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(val, 2);
+    assert_eq!(conn.cached_statements().size, 1);
+
+    conn.clear_cached_statements().await?;
+    assert_eq!(prepared_on_server(&mut conn).await?, 0);
+
+    // pools override the capacity of their connections
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .statement_cache_capacity(0)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    sqlx::query("SELECT 1::int4").fetch_one(&mut *conn).await?;
+
+    assert_eq!(
+        conn.cached_statements(),
+        CachedStatements {
+            size: 0,
+            capacity: 0
+        }
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_prepares_statements_on_connect() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .after_connect(|conn, _meta| {
+            Box::pin(async move {
+                conn.execute("CREATE TEMPORARY TABLE warm (id INTEGER)")
+                    .await?;
+                Ok(())
+            })
+        })
+        // `warm` only exists once `after_connect` has run
+        .prepare_on_connect(["SELECT id FROM warm", "SELECT $1::int4 + 1"])
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    assert_eq!(2, conn.cached_statements_size());
+
+    let val: i32 = sqlx::query_scalar("SELECT $1::int4 + 1")
+        .bind(1_i32)
+        .fetch_one(&mut *conn)
+        .await?;
 
-    assert_eq!(done.rows_affected(), 0);
+    assert_eq!(val, 2);
+    assert_eq!(2, conn.cached_statements_size());
 
     Ok(())
 }
 
-/// Test a simple select expression. This should return the row.
 #[sqlx_macros::test]
-async fn test_select_expression() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+async fn it_recycles_connections_with_connection_manager() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
 
-    let mut s = conn.fetch("SELECT 5");
-    let row = s.try_next().await?.unwrap();
+    let manager = sqlx::pool::ConnectionManager::<Postgres>::from_url(&env::var("DATABASE_URL")?)?;
+    let mut conn = manager.connect().await?;
 
-    assert!(5i32 == row.try_get::<i32, _>(0)?);
+    manager.recycle(&mut conn).await?;
+
+    let val: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(val, 1);
+
+    conn.close().await?;
 
     Ok(())
 }
 
-/// Test that we can interleave reads and writes to the database
-/// in one simple query. Using the `Cursor` API we should be
-/// able to fetch from both queries in sequence.
 #[sqlx_macros::test]
-async fn test_multi_read_write() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+async fn it_sets_application_name() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
 
-    let mut s = conn.fetch(
-        "
-CREATE TABLE IF NOT EXISTS _sqlx_test_postgres_5112 (
-    id BIGSERIAL PRIMARY KEY,
-    text TEXT NOT NULL
-);
+    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    options = options.application_name("some-name");
 
-SELECT 'Hello World' as _1;
+    let mut conn = PgConnection::connect_with(&options).await?;
 
-INSERT INTO _sqlx_test_postgres_5112 (text) VALUES ('this is a test');
+    let row = sqlx::query("select current_setting('application_name') as app_name")
+        .fetch_one(&mut conn)
+        .await?;
 
-SELECT id, text FROM _sqlx_test_postgres_5112;
-    ",
-    );
+    let val: String = row.get("app_name");
 
-    let row = s.try_next().await?.unwrap();
+    assert_eq!("some-name", &val);
 
-    assert!("Hello World" == row.try_get::<&str, _>("_1")?);
+    Ok(())
+}
 
-    let row = s.try_next().await?.unwrap();
+#[sqlx_macros::test]
+async fn it_decodes_out_of_range_integers_with_adapters() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-    let id: i64 = row.try_get("id")?;
-    let text: &str = row.try_get("text")?;
+    let (Saturating(high), Saturating(low)): (Saturating<i16>, Saturating<i16>) =
+        sqlx::query_as("SELECT 100000::BIGINT, -100000::INT")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!((high, low), (i16::MAX, i16::MIN));
 
-    assert_eq!(1_i64, id);
-    assert_eq!("this is a test", text);
+    let (Checked(high), Checked(small)): (Checked<i16>, Checked<i16>) =
+        sqlx::query_as("SELECT 100000::BIGINT, 42::SMALLINT")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!((high, small), (None, Some(42)));
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_caches_statements() -> anyhow::Result<()> {
+async fn it_sets_statement_timeout_and_role() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
     let mut conn = new::<Postgres>().await?;
+    conn.execute(
+        r#"
+DO $$
+BEGIN
+    CREATE ROLE sqlx_options_role;
+EXCEPTION WHEN duplicate_object THEN NULL;
+END
+$$;
+        "#,
+    )
+    .await?;
 
-    for i in 0..2 {
-        let row = sqlx::query("SELECT $1 AS val")
-            .bind(Oid(i))
-            .persistent(true)
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    let options = options
+        .statement_timeout(Duration::from_millis(1500))
+        .role("sqlx_options_role");
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let (timeout, role): (String, String) =
+        sqlx::query_as("select current_setting('statement_timeout'), current_user::text")
             .fetch_one(&mut conn)
             .await?;
 
-        let val: Oid = row.get("val");
+    assert_eq!(timeout, "1500ms");
+    assert_eq!(role, "sqlx_options_role");
 
-        assert_eq!(Oid(i), val);
+    let err = conn.execute("select pg_sleep(3)").await.unwrap_err();
+    assert_eq!(
+        err.into_database_error().unwrap().code().as_deref(),
+        Some("57014")
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_records_and_replays_statements() -> anyhow::Result<()> {
+    #[derive(sqlx::Type)]
+    #[sqlx(type_name = "status", rename_all = "lowercase")]
+    enum Status {
+        Open,
     }
 
-    assert_eq!(1, conn.cached_statements_size());
-    conn.clear_cached_statements().await?;
-    assert_eq!(0, conn.cached_statements_size());
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("statements.sqlxrpl");
 
-    for i in 0..2 {
-        let row = sqlx::query("SELECT $1 AS val")
-            .bind(Oid(i))
-            .persistent(false)
-            .fetch_one(&mut conn)
-            .await?;
+    let recorder = PgStatementRecorder::create(&path)?
+        .redact(|sql, index| sql.contains("replay_test") && index == 3);
 
-        let val: Oid = row.get("val");
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    let mut conn = PgConnection::connect_with(&options.record_statements(recorder.clone())).await?;
 
-        assert_eq!(Oid(i), val);
-    }
+    conn.execute(
+        "CREATE TEMPORARY TABLE replay_test (id INT4, name TEXT, status status, secret TEXT)",
+    )
+    .await?;
 
-    assert_eq!(0, conn.cached_statements_size());
+    sqlx::query("INSERT INTO replay_test (id, name, status, secret) VALUES ($1, $2, $3, $4)")
+        .bind(1_i32)
+        .bind("alice")
+        .bind(Status::Open)
+        .bind("hunter2")
+        .execute(&mut conn)
+        .await?;
 
-    Ok(())
-}
+    conn.close().await?;
+    recorder.flush().await?;
 
-#[sqlx_macros::test]
-async fn it_closes_statement_from_cache_issue_470() -> anyhow::Result<()> {
-    sqlx_test::setup_if_needed();
+    let entries = PgReplayReader::open(&path)?.collect::<Result<Vec<_>, _>>()?;
+    let entries: Vec<_> = entries
+        .into_iter()
+        .filter(|entry| entry.sql.contains("replay_test"))
+        .collect();
 
-    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert!(!entries[0].prepared);
+    assert!(entries[1].prepared);
 
-    // a capacity of 1 means that before each statement (after the first)
-    // we will close the previous statement
-    options = options.statement_cache_capacity(1);
+    let params = &entries[1].params;
+    assert_eq!(params.len(), 4);
+    assert_eq!(
+        params[0].value,
+        PgReplayValue::Bytes(1_i32.to_be_bytes().to_vec())
+    );
+    assert_eq!(params[1].value, PgReplayValue::Bytes(b"alice".to_vec()));
+    assert_eq!(params[2].type_name, "status");
+    assert_eq!(params[3].value, PgReplayValue::Redacted);
 
-    let mut conn = PgConnection::connect_with(&options).await?;
+    let mut conn = new::<Postgres>().await?;
 
-    for i in 0..5 {
-        let row = sqlx::query(&*format!("SELECT {i}::int4 AS val"))
+    for entry in &entries {
+        entry.execute(&mut conn).await?;
+    }
+
+    let row: (i32, String, String, Option<String>) =
+        sqlx::query_as("SELECT id, name, status::text, secret FROM replay_test")
             .fetch_one(&mut conn)
             .await?;
 
-        let val: i32 = row.get("val");
-
-        assert_eq!(i, val);
-    }
+    assert_eq!(row, (1, "alice".to_owned(), "open".to_owned(), None));
 
-    assert_eq!(1, conn.cached_statements_size());
+    Ok(())
+}
 
+#[sqlx_macros::test]
+async fn it_can_handle_parameter_status_message_issue_484() -> anyhow::Result<()> {
+    new::<Postgres>().await?.execute("SET NAMES 'UTF8'").await?;
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_sets_application_name() -> anyhow::Result<()> {
-    sqlx_test::setup_if_needed();
+async fn it_executes_scripts_with_binds() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut tx = conn.begin().await?;
 
-    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
-    options = options.application_name("some-name");
+    let result = tx
+        .execute_script(
+            "CREATE TEMPORARY TABLE script_test (id INT4, name TEXT);
+             INSERT INTO script_test VALUES ($1, $2), ($1 + 1, $tag$ $2; $tag$);
+             UPDATE script_test SET name = name || $3 WHERE id = $1;",
+            &[&10_i32, &"ten", &"!"],
+        )
+        .await?;
 
-    let mut conn = PgConnection::connect_with(&options).await?;
+    assert_eq!(result.rows_affected(), 3);
 
-    let row = sqlx::query("select current_setting('application_name') as app_name")
-        .fetch_one(&mut conn)
+    let rows: Vec<(i32, String)> = sqlx::query_as("SELECT id, name FROM script_test ORDER BY id")
+        .fetch_all(&mut *tx)
         .await?;
 
-    let val: String = row.get("app_name");
+    assert_eq!(rows, [(10, "ten!".to_owned()), (11, " $2; ".to_owned())]);
 
-    assert_eq!("some-name", &val);
+    tx.rollback().await?;
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_handle_parameter_status_message_issue_484() -> anyhow::Result<()> {
-    new::<Postgres>().await?.execute("SET NAMES 'UTF8'").await?;
+async fn it_builds_jsonb_path_and_set_expressions() -> anyhow::Result<()> {
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Item {
+        name: String,
+        price: i64,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"CREATE TEMPORARY TABLE jsonb_docs (id INT4, doc JSONB);
+           INSERT INTO jsonb_docs VALUES (1, '{"items": [{"name": "a", "price": 5}, {"name": "b", "price": 50}]}')"#,
+    )
+    .await?;
+
+    let mut query = QueryBuilder::<Postgres>::new("SELECT ");
+    PgJsonbPath::query("doc", "$.items[*] ? (@.price >= $min && @.name != $skip)")
+        .var("min", 10)
+        .var("skip", "it's")
+        .push_to(&mut query);
+    query.push(" FROM jsonb_docs");
+
+    let items: Vec<Json<Item>> = query.build_query_scalar().fetch_all(&mut conn).await?;
+    assert_eq!(
+        items,
+        [Json(Item {
+            name: "b".to_owned(),
+            price: 50
+        })]
+    );
+
+    let mut query = QueryBuilder::<Postgres>::new("SELECT ");
+    PgJsonbPath::exists("doc", "$.missing.field")
+        .silent(true)
+        .push_to(&mut query);
+    query.push(" FROM jsonb_docs");
+
+    let exists: bool = query.build_query_scalar().fetch_one(&mut conn).await?;
+    assert!(!exists);
+
+    let mut query = QueryBuilder::<Postgres>::new("UPDATE jsonb_docs SET doc = ");
+    PgJsonbSet::new("doc")
+        .set(["owner"], serde_json::json!({ "name": "o'brien" }))
+        .set(["items", "0", "price"], 7)
+        .replace(["absent"], true)
+        .push_to(&mut query);
+    query.push(" WHERE id = ").push_bind(1_i32);
+    query.build().execute(&mut conn).await?;
+
+    let doc: serde_json::Value = sqlx::query_scalar("SELECT doc FROM jsonb_docs")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(
+        doc,
+        serde_json::json!({
+            "items": [{ "name": "a", "price": 7 }, { "name": "b", "price": 50 }],
+            "owner": { "name": "o'brien" },
+        })
+    );
+
     Ok(())
 }
 
@@ -997,6 +2380,34 @@ from (values (null)) vals(val)
     assert_eq!(describe.nullable(0), Some(true));
     assert_eq!(describe.nullable(1), Some(true));
 
+    // the planner executes this as a right join with the CTE as the outer half
+    // language=PostgreSQL
+    let describe = conn
+        .describe(
+            "with updated as (update tweet set text = text returning id, owner_id)
+    select tweet.id, updated.id, updated.owner_id
+    from tweet
+    left join updated using (id)",
+        )
+        .await?;
+
+    assert_eq!(describe.nullable(0), Some(false));
+    assert_eq!(describe.nullable(1), Some(true));
+    assert_eq!(describe.nullable(2), Some(true));
+
+    // columns returned through a data-modifying CTE keep the nullability of the table
+    // language=PostgreSQL
+    let describe = conn
+        .describe(
+            "with inserted as (insert into tweet (text) values ('x') returning id, text, owner_id)
+    select id, text, owner_id from inserted",
+        )
+        .await?;
+
+    assert_eq!(describe.nullable(0), Some(false));
+    assert_eq!(describe.nullable(1), Some(false));
+    assert_eq!(describe.nullable(2), Some(true));
+
     Ok(())
 }
 
@@ -1724,6 +3135,56 @@ async fn it_can_copy_out() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_copy_binary_rows_in_and_out() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    conn.execute(
+        r#"
+        CREATE TEMPORARY TABLE users (id INTEGER NOT NULL, name TEXT, tags TEXT[] NOT NULL);
+    "#,
+    )
+    .await?;
+
+    let users = (1..=1000).map(|id: i32| {
+        let name = (id % 2 == 0).then(|| format!("user {id}"));
+        (id, name, vec![id.to_string()])
+    });
+
+    let rows = conn
+        .copy_in_binary(
+            "COPY users (id, name, tags) FROM STDIN (FORMAT BINARY)",
+            futures::stream::iter(users.clone()),
+        )
+        .await?;
+    assert_eq!(rows, 1000);
+
+    let copied: Vec<(i32, Option<String>, Vec<String>)> = conn
+        .copy_out_binary(
+            "COPY (SELECT id, name, tags FROM users ORDER BY id) TO STDOUT (FORMAT BINARY)",
+        )
+        .await?
+        .try_collect()
+        .await?;
+    assert_eq!(copied, users.collect::<Vec<_>>());
+
+    // a row which does not match the columns aborts the `COPY`
+    let res = conn
+        .copy_in_binary(
+            "COPY users (id, name, tags) FROM STDIN (FORMAT BINARY)",
+            futures::stream::iter([(1_i32,)]),
+        )
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::InvalidArgument(_))));
+
+    // conn is safe for reuse
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 1000);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_encodes_custom_array_issue_1504() -> anyhow::Result<()> {
     use sqlx::encode::IsNull;
@@ -2121,3 +3582,100 @@ async fn it_can_recover_from_copy_in_invalid_params() -> anyhow::Result<()> {
     )
     .await
 }
+
+#[sqlx_macros::test]
+async fn it_streams_logical_replication_changes() -> anyhow::Result<()> {
+    use sqlx::postgres::{
+        PgChange, PgDecodingPlugin, PgReplicationConnection, PgReplicationOptions,
+    };
+
+    let mut conn = new::<Postgres>().await?;
+
+    // Only possible with `wal_level = logical`, as a role with the `REPLICATION` attribute
+    let wal_level: String = sqlx::query_scalar("SHOW wal_level")
+        .fetch_one(&mut conn)
+        .await?;
+    if wal_level != "logical" {
+        return Ok(());
+    }
+
+    conn.execute(
+        r#"
+DROP PUBLICATION IF EXISTS test_replication_pub;
+DROP TABLE IF EXISTS test_replication;
+CREATE TABLE test_replication (id int4 PRIMARY KEY, name text);
+CREATE PUBLICATION test_replication_pub FOR TABLE test_replication;
+"#,
+    )
+    .await?;
+
+    let mut replication = PgReplicationConnection::connect(&env::var("DATABASE_URL")?).await?;
+    let system = replication.identify_system().await?;
+    assert!(system.timeline > 0);
+
+    let slot = replication
+        .create_slot("test_replication_slot", PgDecodingPlugin::PgOutput, true)
+        .await?;
+
+    conn.execute("INSERT INTO test_replication (id, name) VALUES (1, 'alice')")
+        .await?;
+
+    let mut stream = replication
+        .start(
+            &slot.name,
+            PgReplicationOptions::pgoutput().publication("test_replication_pub"),
+        )
+        .await?;
+
+    let mut inserted = None;
+    while let Some(event) = stream.recv().await? {
+        assert!(event.lsn() >= slot.consistent_point);
+
+        match event.change() {
+            PgChange::Insert { relation, new } => {
+                assert_eq!(relation.name(), "test_replication");
+                inserted = new
+                    .get(1)
+                    .and_then(|value| value.as_str())
+                    .map(str::to_owned);
+            }
+            PgChange::Commit { .. } => {
+                stream.acknowledge(event.ack_lsn());
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    assert_eq!(inserted.as_deref(), Some("alice"));
+
+    stream.stop().await?.close().await?;
+    conn.execute("DROP PUBLICATION test_replication_pub; DROP TABLE test_replication")
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_gets_raw_values() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // prepared statements return the binary format
+    let row = sqlx::query("SELECT 1::int4, 'abc'::text, NULL::int4")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.value_format(0)?, PgValueFormat::Binary);
+    assert_eq!(row.raw_value(0)?, Some(&1_i32.to_be_bytes()[..]));
+    assert_eq!(row.raw_value(1)?, Some(&b"abc"[..]));
+    assert_eq!(row.raw_value(2)?, None);
+    assert!(row.raw_value(3).is_err());
+
+    // simple queries return the text format
+    let row = conn.fetch_one("SELECT 1::int4").await?;
+
+    assert_eq!(row.value_format(0)?, PgValueFormat::Text);
+    assert_eq!(row.raw_value(0)?, Some(&b"1"[..]));
+
+    Ok(())
+}
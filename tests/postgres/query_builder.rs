@@ -157,3 +157,34 @@ async fn test_max_number_of_binds() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "uuid-v7")]
+#[sqlx_macros::test]
+async fn test_push_values_or_new_uuid_v7() -> anyhow::Result<()> {
+    use sqlx::types::{uuid_v7, Uuid};
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE orders (id UUID PRIMARY KEY, customer TEXT NOT NULL)")
+        .await?;
+
+    let orders = [(Some(Uuid::nil()), "alice"), (None, "bob"), (None, "carol")];
+
+    let mut qb = QueryBuilder::<Postgres>::new("INSERT INTO orders (id, customer) ");
+
+    qb.push_values(orders, |mut b, (id, customer)| {
+        b.push_bind(uuid_v7::OrNew(id)).push_bind(customer);
+    });
+
+    qb.build().execute(&mut conn).await?;
+
+    let ids: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM orders ORDER BY customer")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(ids[0], Uuid::nil());
+    assert!(uuid_v7::timestamp(&ids[1]).is_some());
+    assert!(ids[1] < ids[2]);
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 // The no-arg variant is covered by other tests already.
 
-use sqlx::PgPool;
+use sqlx::{Executor, PgPool};
 
 const MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("tests/postgres/migrations");
 
@@ -42,6 +42,35 @@ async fn it_gets_users(pool: PgPool) -> sqlx::Result<()> {
     Ok(())
 }
 
+// This should apply the migrations from both directories, ordered by version
+#[sqlx::test(
+    migrations = ["tests/postgres/migrations_simple", "tests/postgres/migrations"],
+    fixtures("users")
+)]
+async fn it_merges_migration_sources(pool: PgPool) -> sqlx::Result<()> {
+    let usernames: Vec<String> =
+        sqlx::query_scalar(r#"SELECT username FROM "user" ORDER BY username"#)
+            .fetch_all(&pool)
+            .await?;
+
+    assert_eq!(usernames, ["alice", "bob"]);
+
+    let payload: String = sqlx::query_scalar("SELECT some_payload FROM migrations_simple_test")
+        .fetch_one(&pool)
+        .await?;
+
+    assert_eq!(payload, "110_suffix");
+
+    let versions: Vec<i64> =
+        sqlx::query_scalar("SELECT version FROM _sqlx_migrations ORDER BY installed_on, version")
+            .fetch_all(&pool)
+            .await?;
+
+    assert_eq!(versions, [0, 1, 2, 3, 20220721115250, 20220721115524]);
+
+    Ok(())
+}
+
 // This should apply migrations and then fixtures `fixtures/users.sql` and `fixtures/posts.sql`
 #[sqlx::test(migrations = "tests/postgres/migrations", fixtures("users", "posts"))]
 async fn it_gets_posts(pool: PgPool) -> sqlx::Result<()> {
@@ -199,3 +228,60 @@ macro_rules! macro_using_test {
     };
 }
 macro_using_test!("tests/postgres/migrations");
+
+#[sqlx::test(migrations = "tests/postgres/migrations")]
+async fn it_asserts_index_usage(pool: PgPool) -> sqlx::Result<()> {
+    let mut conn = pool.acquire().await?;
+    // the planner would rather scan an empty table than use the index
+    conn.execute("SET enable_seqscan = off").await?;
+
+    sqlx::assert_index_used!(
+        &mut *conn,
+        "SELECT post_id FROM post ORDER BY created_at DESC LIMIT 10",
+        "post_created_at_idx"
+    );
+
+    // an `Index Only Scan Backward`
+    sqlx::assert_index_used!(
+        &mut *conn,
+        "SELECT created_at FROM post ORDER BY created_at LIMIT 10",
+        "post_created_at_idx"
+    );
+
+    let plan = sqlx::testing::explain(&mut *conn, "SELECT content FROM post").await?;
+    assert!(!plan.uses_index("post_created_at_idx"), "{plan}");
+
+    Ok(())
+}
+
+#[sqlx::test(
+    migrations = "tests/postgres/migrations",
+    fixtures("users", "posts", "comments")
+)]
+async fn it_cleans_tables(pool: PgPool) -> sqlx::Result<()> {
+    // a referencing table which is not cleaned
+    let res = sqlx::testing::clean_tables(&pool, &[r#""user""#, "post"]).await;
+    assert!(res.is_err());
+
+    pool.execute("CREATE TABLE tag (tag_id SERIAL PRIMARY KEY, name TEXT NOT NULL)")
+        .await?;
+    pool.execute("INSERT INTO tag (name) VALUES ('a'), ('b')")
+        .await?;
+
+    // listed in the wrong order for `DELETE`
+    sqlx::testing::clean_tables(&pool, &[r#""user""#, "post", "comment", "tag"]).await?;
+
+    let count: i64 = sqlx::query_scalar(
+        r#"SELECT (SELECT count(*) FROM "user") + (SELECT count(*) FROM post) + (SELECT count(*) FROM comment) + (SELECT count(*) FROM tag)"#,
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(count, 0);
+
+    let tag_id: i32 = sqlx::query_scalar("INSERT INTO tag (name) VALUES ('c') RETURNING tag_id")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(tag_id, 1);
+
+    Ok(())
+}
@@ -3,7 +3,10 @@ extern crate time_ as time;
 use std::net::SocketAddr;
 use std::ops::Bound;
 
-use sqlx::postgres::types::{Oid, PgCiText, PgInterval, PgMoney, PgRange};
+use sqlx::postgres::types::{
+    iso, Oid, PgCiText, PgCurrency, PgInterval, PgMoney, PgRange, PgTsLexeme, PgTsQuery,
+    PgTsQueryLexeme, PgTsQueryNode, PgTsVector, PgTsWeight, PgWebSearch,
+};
 use sqlx::postgres::Postgres;
 use sqlx_test::{new, test_decode_type, test_prepared_type, test_type};
 
@@ -257,6 +260,9 @@ mod chrono {
         DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
     };
 
+    use sqlx::postgres::types::PgInfinite;
+    use sqlx::Row;
+
     type PgTimeTz = sqlx::postgres::types::PgTimeTz<NaiveTime, FixedOffset>;
 
     test_type!(chrono_date<NaiveDate>(Postgres,
@@ -304,6 +310,52 @@ mod chrono {
         "TIMETZ '05:10:20.115100-05'" == PgTimeTz { time: NaiveTime::from_hms_micro_opt(5, 10, 20, 115100).unwrap(), offset: FixedOffset::west_opt(60 * 60 * 5).unwrap() },
         "TIMETZ '05:10:20+02'" == PgTimeTz { time: NaiveTime::from_hms_opt(5, 10, 20).unwrap(), offset: FixedOffset::east_opt(60 * 60 * 2 ).unwrap() }
     ));
+
+    test_type!(chrono_date_infinite<PgInfinite<NaiveDate>>(Postgres,
+        "DATE 'infinity'" == PgInfinite::<NaiveDate>::Infinity,
+        "DATE '-infinity'" == PgInfinite::<NaiveDate>::NegativeInfinity,
+        "DATE '2001-01-05'" == PgInfinite::Finite(NaiveDate::from_ymd_opt(2001, 1, 5).unwrap())
+    ));
+
+    test_type!(chrono_date_time_infinite<PgInfinite<NaiveDateTime>>(Postgres,
+        "'infinity'::timestamp" == PgInfinite::<NaiveDateTime>::Infinity,
+        "'-infinity'::timestamp" == PgInfinite::<NaiveDateTime>::NegativeInfinity,
+        "'2019-01-02 05:10:20'::timestamp"
+            == PgInfinite::Finite(NaiveDate::from_ymd_opt(2019, 1, 2).unwrap().and_hms_opt(5, 10, 20).unwrap())
+    ));
+
+    test_type!(chrono_date_time_tz_infinite_vec<Vec<PgInfinite<DateTime::<Utc>>>>(Postgres,
+        "array['-infinity', '2019-01-02 05:10:20+00', 'infinity']::timestamptz[]"
+            == vec![
+                PgInfinite::<DateTime<Utc>>::NegativeInfinity,
+                PgInfinite::Finite(Utc.with_ymd_and_hms(2019, 1, 2, 5, 10, 20).unwrap()),
+                PgInfinite::<DateTime<Utc>>::Infinity,
+            ]
+    ));
+
+    #[sqlx_macros::test]
+    async fn test_chrono_infinity() -> anyhow::Result<()> {
+        let mut conn = new::<Postgres>().await?;
+
+        // the bare types cannot represent it, in either format
+        let err = sqlx::query_scalar::<_, DateTime<Utc>>("SELECT 'infinity'::timestamptz")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("PgInfinite<DateTime>"), "{err}");
+
+        let row = conn.fetch_one("SELECT '-infinity'::date").await?;
+        assert!(row.try_get::<NaiveDate, _>(0).is_err());
+
+        let valid_until: PgInfinite<DateTime<Utc>> =
+            sqlx::query_scalar("SELECT 'infinity'::timestamptz")
+                .fetch_one(&mut conn)
+                .await?;
+        assert_eq!(valid_until.finite(), None);
+        assert_eq!(valid_until.saturating(), DateTime::<Utc>::MAX_UTC);
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "time")]
@@ -312,6 +364,8 @@ mod time_tests {
     use sqlx::types::time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
     use time::macros::{date, time};
 
+    use sqlx::postgres::types::PgInfinite;
+
     type PgTimeTz = sqlx::postgres::types::PgTimeTz<Time, UtcOffset>;
 
     test_type!(time_date<Date>(
@@ -347,6 +401,43 @@ mod time_tests {
         "TIMETZ '05:10:20.115100-05'" == PgTimeTz { time: time!(5:10:20.115100), offset: UtcOffset::from_whole_seconds(-(60 * 60 * 5)).unwrap() },
         "TIMETZ '05:10:20+02'" == PgTimeTz { time: time!(5:10:20), offset: UtcOffset::from_whole_seconds(60 * 60 * 2 ).unwrap() }
     ));
+
+    test_type!(time_date_infinite<PgInfinite<Date>>(
+        Postgres,
+        "DATE 'infinity'" == PgInfinite::<Date>::Infinity,
+        "DATE '2001-01-05'" == PgInfinite::Finite(date!(2001 - 1 - 5))
+    ));
+
+    test_type!(time_timestamp_infinite<PgInfinite<OffsetDateTime>>(
+        Postgres,
+        "TIMESTAMPTZ '-infinity'" == PgInfinite::<OffsetDateTime>::NegativeInfinity,
+        "TIMESTAMPTZ '2019-01-02 05:10:20.115100'"
+            == PgInfinite::Finite(date!(2019 - 1 - 2).with_time(time!(5:10:20.115100)).assume_utc())
+    ));
+
+    #[sqlx_macros::test]
+    async fn test_time_infinity() -> anyhow::Result<()> {
+        let mut conn = new::<Postgres>().await?;
+
+        let err = sqlx::query_scalar::<_, PrimitiveDateTime>("SELECT '-infinity'::timestamp")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("PgInfinite<PrimitiveDateTime>"),
+            "{err}"
+        );
+
+        // `-infinity` is before every other date
+        let before: bool = sqlx::query_scalar("SELECT $1 < DATE '1970-01-01'")
+            .bind(PgInfinite::<Date>::NegativeInfinity)
+            .fetch_one(&mut conn)
+            .await?;
+        assert!(before);
+        assert_eq!(PgInfinite::<Date>::NegativeInfinity.saturating(), Date::MIN);
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "json")]
@@ -620,12 +711,103 @@ test_prepared_type!(interval<PgInterval>(
         },
 ));
 
+test_prepared_type!(std_duration<std::time::Duration>(Postgres,
+    "INTERVAL '1 day 02:03:04.5'" == std::time::Duration::from_millis(((24 + 2) * 3600 + 3 * 60 + 4) * 1000 + 500),
+    "INTERVAL '0'" == std::time::Duration::ZERO,
+));
+
+#[sqlx_macros::test]
+async fn test_std_duration_interval_arithmetic() -> anyhow::Result<()> {
+    use std::time::Duration;
+
+    let mut conn = new::<Postgres>().await?;
+
+    let expired: bool = sqlx::query_scalar("SELECT now() - $1 > now() - INTERVAL '1 hour'")
+        .bind(Duration::from_secs(60))
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(expired);
+
+    let interval: PgInterval = sqlx::query_scalar("SELECT INTERVAL '1 month' + $1")
+        .bind(Duration::from_secs(90))
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(
+        interval - PgInterval::try_from(Duration::from_secs(90)).unwrap(),
+        PgInterval {
+            months: 1,
+            days: 0,
+            microseconds: 0,
+        }
+    );
+
+    // decoding an interval with months is an error, as they vary in length
+    let res = sqlx::query_scalar::<_, Duration>("SELECT INTERVAL '1 month'")
+        .fetch_one(&mut conn)
+        .await;
+    assert!(res.is_err());
+
+    Ok(())
+}
+
 test_prepared_type!(money<PgMoney>(Postgres, "123.45::money" == PgMoney(12345)));
 
 test_prepared_type!(money_vec<Vec<PgMoney>>(Postgres,
     "array[123.45,420.00,666.66]::money[]" == vec![PgMoney(12345), PgMoney(42000), PgMoney(66666)],
 ));
 
+type Usd<A> = PgCurrency<A, iso::Usd>;
+
+test_prepared_type!(currency<Usd<i64>>(Postgres,
+    "123.45::money" == Usd::new(12345),
+    "(-0.05)::money" == Usd::new(-5),
+));
+
+#[cfg(feature = "rust_decimal")]
+test_prepared_type!(currency_decimal<Usd<sqlx::types::Decimal>>(Postgres,
+    "123.45::money" == Usd::new(sqlx::types::Decimal::new(12345, 2)),
+));
+
+#[cfg(feature = "bigdecimal")]
+test_prepared_type!(currency_bigdecimal_vec<Vec<Usd<sqlx::types::BigDecimal>>>(Postgres,
+    "array[1.5,-2.25]::money[]" == vec![
+        Usd::new(sqlx::types::BigDecimal::from_str("1.50").unwrap()),
+        Usd::new(sqlx::types::BigDecimal::from_str("-2.25").unwrap()),
+    ],
+));
+
+#[sqlx_macros::test]
+async fn test_currency_text_format() -> anyhow::Result<()> {
+    use sqlx::Row;
+
+    let mut conn = new::<Postgres>().await?;
+
+    // the simple query protocol returns the value formatted for `lc_monetary`
+    let row = conn
+        .fetch_one("SELECT '-1234.56'::money, '1000'::money")
+        .await?;
+
+    let negative: Usd<i64> = row.try_get(0)?;
+    assert_eq!(negative.amount, -123456);
+
+    let whole: Usd<i64> = row.try_get(1)?;
+    assert_eq!(whole.amount, 100000);
+
+    // amounts which can't be represented are rejected rather than rounded
+    #[cfg(feature = "rust_decimal")]
+    {
+        let res = sqlx::query("SELECT $1")
+            .bind(PgCurrency::<_, iso::Usd>::new(sqlx::types::Decimal::new(
+                1999, 3,
+            )))
+            .execute(&mut conn)
+            .await;
+        assert!(res.is_err());
+    }
+
+    Ok(())
+}
+
 test_prepared_type!(citext_array<Vec<PgCiText>>(Postgres,
     "array['one','two','three']::citext[]" == vec![
         PgCiText("one".to_string()),
@@ -634,6 +816,17 @@ test_prepared_type!(citext_array<Vec<PgCiText>>(Postgres,
     ],
 ));
 
+test_type!(ci_string<sqlx::types::CiString>(Postgres,
+    "'Alice@Example.com'::citext" == sqlx::types::CiString::from("alice@example.com"),
+));
+
+test_prepared_type!(ci_string_array<Vec<sqlx::types::CiString>>(Postgres,
+    "array['One','TWO']::citext[]" == vec![
+        sqlx::types::CiString::from("one"),
+        sqlx::types::CiString::from("two"),
+    ],
+));
+
 // FIXME: needed to disable `ltree` tests in version that don't have a binary format for it
 // but `PgLTree` should just fall back to text format
 #[cfg(any(postgres_14, postgres_15))]
@@ -653,6 +846,69 @@ test_type!(ltree_vec<Vec<sqlx::postgres::types::PgLTree>>(Postgres,
         ]
 ));
 
+test_type!(tsvector<PgTsVector>(Postgres,
+    "$$'a':1A 'fat':2,4 'it''s':3C$$::tsvector" == PgTsVector::from_iter([
+        PgTsLexeme::new("a").position(1, PgTsWeight::A),
+        PgTsLexeme::new("fat").position(2, PgTsWeight::D).position(4, PgTsWeight::D),
+        PgTsLexeme::new("it's").position(3, PgTsWeight::C),
+    ]),
+    "'cat rat'::tsvector" == PgTsVector::from_iter([PgTsLexeme::new("cat"), PgTsLexeme::new("rat")]),
+    "''::tsvector" == PgTsVector::new(),
+));
+
+test_type!(tsquery<PgTsQuery>(Postgres,
+    "'fat & (rat | !cat:*)'::tsquery" == PgTsQuery::from(
+        PgTsQueryNode::lexeme("fat") & (PgTsQueryNode::lexeme("rat") | !PgTsQueryNode::prefix("cat"))
+    ),
+    "'a <-> b <2> c'::tsquery" == PgTsQuery::from(
+        PgTsQueryNode::lexeme("a")
+            .followed_by(PgTsQueryNode::lexeme("b"))
+            .followed_at(PgTsQueryNode::lexeme("c"), 2)
+    ),
+    "'sup:AB'::tsquery" == PgTsQuery::from(PgTsQueryNode::Lexeme(PgTsQueryLexeme {
+        word: "sup".into(),
+        weights: vec![PgTsWeight::A, PgTsWeight::B],
+        prefix: false,
+    })),
+));
+
+#[sqlx_macros::test]
+async fn test_websearch() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let search = PgWebSearch::new("\"fat rats\" or cats -dog\0").config("english");
+
+    assert_eq!(
+        search.to_tsquery(&mut conn).await?.to_string(),
+        "'fat' <-> 'rat' | 'cat' & !'dog'"
+    );
+
+    let mut query =
+        sqlx::QueryBuilder::new("SELECT to_tsvector('english', 'Some cats chase rats') @@ ");
+    search.push_to(&mut query);
+
+    let matched: bool = query.build_query_scalar().fetch_one(&mut conn).await?;
+    assert!(matched);
+
+    // the converted query can be bound
+    let query: PgTsQuery = search.to_tsquery(&mut conn).await?;
+
+    let matched: bool = sqlx::query_scalar("SELECT to_tsvector('english', 'a fat rat') @@ $1")
+        .bind(&query)
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(matched);
+
+    // stop words only
+    let query = PgWebSearch::new("the")
+        .config("english")
+        .to_tsquery(&mut conn)
+        .await?;
+    assert!(query.is_empty());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_text_adapter() -> anyhow::Result<()> {
     #[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
@@ -695,3 +951,64 @@ CREATE TEMPORARY TABLE user_login (
 
     Ok(())
 }
+
+#[cfg(feature = "proptest")]
+#[sqlx_macros::test]
+async fn it_roundtrips_generated_values() -> anyhow::Result<()> {
+    use sqlx::testing::roundtrip;
+
+    let mut conn = new::<Postgres>().await?;
+
+    roundtrip::<bool, _>(&mut conn).await?;
+    roundtrip::<i16, _>(&mut conn).await?;
+    roundtrip::<i32, _>(&mut conn).await?;
+    roundtrip::<i64, _>(&mut conn).await?;
+    roundtrip::<f32, _>(&mut conn).await?;
+    roundtrip::<f64, _>(&mut conn).await?;
+    roundtrip::<String, _>(&mut conn).await?;
+    roundtrip::<Vec<u8>, _>(&mut conn).await?;
+    roundtrip::<PgInterval, _>(&mut conn).await?;
+    roundtrip::<sqlx::types::JsonValue, _>(&mut conn).await?;
+    roundtrip::<Vec<i32>, _>(&mut conn).await?;
+    roundtrip::<Vec<Option<i64>>, _>(&mut conn).await?;
+    roundtrip::<Vec<String>, _>(&mut conn).await?;
+    roundtrip::<PgRange<i32>, _>(&mut conn).await?;
+    roundtrip::<PgRange<i64>, _>(&mut conn).await?;
+
+    #[cfg(feature = "chrono")]
+    {
+        use sqlx::types::chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+        roundtrip::<NaiveDate, _>(&mut conn).await?;
+        roundtrip::<NaiveTime, _>(&mut conn).await?;
+        roundtrip::<NaiveDateTime, _>(&mut conn).await?;
+        roundtrip::<DateTime<Utc>, _>(&mut conn).await?;
+    }
+
+    #[cfg(feature = "time")]
+    {
+        use sqlx::types::time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+        roundtrip::<Date, _>(&mut conn).await?;
+        roundtrip::<Time, _>(&mut conn).await?;
+        roundtrip::<PrimitiveDateTime, _>(&mut conn).await?;
+        roundtrip::<OffsetDateTime, _>(&mut conn).await?;
+    }
+
+    #[cfg(feature = "uuid")]
+    roundtrip::<sqlx::types::Uuid, _>(&mut conn).await?;
+
+    #[cfg(feature = "bigdecimal")]
+    roundtrip::<sqlx::types::BigDecimal, _>(&mut conn).await?;
+
+    #[cfg(feature = "ipnetwork")]
+    {
+        roundtrip::<std::net::IpAddr, _>(&mut conn).await?;
+        roundtrip::<sqlx::types::ipnetwork::IpNetwork, _>(&mut conn).await?;
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    roundtrip::<sqlx::types::Decimal, _>(&mut conn).await?;
+
+    Ok(())
+}
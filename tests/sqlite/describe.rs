@@ -1024,3 +1024,28 @@ async fn it_describes_analytical_function() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "uuid")]
+#[sqlx_macros::test]
+async fn it_describes_uuid_columns() -> anyhow::Result<()> {
+    use sqlx_core::type_checking::TypeChecking;
+
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE uuid_keys (id UUID PRIMARY KEY, data BLOB)")
+        .await?;
+
+    let d = conn.describe("SELECT id, data FROM uuid_keys").await?;
+
+    assert_eq!(d.column(0).type_info().name(), "UUID");
+    assert_eq!(
+        Sqlite::return_type_for_id(d.column(0).type_info()),
+        Some("sqlx::types::Uuid")
+    );
+    assert_eq!(
+        Sqlite::return_type_for_id(d.column(1).type_info()),
+        Some("Vec<u8>")
+    );
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+-- depends: users, posts
 insert into comment(comment_id, post_id, user_id, content, created_at)
 values (1,
         1,
@@ -1,3 +1,4 @@
+-- depends: users
 insert into post(post_id, user_id, content, created_at)
 values (1,
         1,
@@ -0,0 +1,10 @@
+-- depends: users
+insert into post(post_id, user_id, content, created_at)
+values (3,
+        1,
+        'release {{ uuid release }} is out',
+        '{{ now - 2 days }}'),
+       (4,
+        2,
+        'release {{ uuid release }} broke everything',
+        '{{ now }}');
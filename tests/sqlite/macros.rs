@@ -88,6 +88,45 @@ async fn macro_select_bind() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn macro_select_bind_list() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let ids = vec![3i64, 1, 2];
+
+    let accounts = sqlx::query!(
+        "select id, name from accounts where id in (?...) and name = ? order by id",
+        ids,
+        "Herp Derpinson",
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].id, 1);
+
+    // an empty list matches nothing
+    let none: &[i64] = &[];
+
+    let count = sqlx::query_scalar!(
+        r#"select count(*) as "count!: i64" from accounts where id in (?...)"#,
+        none
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    assert_eq!(count, 0);
+
+    let count = sqlx::query_scalar!(
+        r#"select count(*) as "count!: i64" from accounts where id not in ( ?... )"#,
+        none
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    assert!(count > 0);
+
+    Ok(())
+}
+
 #[derive(Debug)]
 struct RawAccount {
     id: i64,
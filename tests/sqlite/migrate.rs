@@ -1,4 +1,7 @@
-use sqlx::migrate::Migrator;
+use sqlx::migrate::{
+    Migrate, MigrateError, Migration, MigrationHooks, MigrationType, Migrator,
+    MIGRATIONS_TABLE_VERSION,
+};
 use sqlx::pool::PoolConnection;
 use sqlx::sqlite::{Sqlite, SqliteConnection};
 use sqlx::Executor;
@@ -66,12 +69,376 @@ async fn reversible(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx::test(migrations = false)]
+async fn records_metadata(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    // a table as created by earlier versions, which must be upgraded
+    conn.execute(
+        r#"
+CREATE TABLE _sqlx_migrations (
+    version BIGINT PRIMARY KEY,
+    description TEXT NOT NULL,
+    installed_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    success BOOLEAN NOT NULL,
+    checksum BLOB NOT NULL,
+    execution_time BIGINT NOT NULL
+);
+        "#,
+    )
+    .await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/sqlite/migrations_simple")).await?;
+    migrator.set_app_version("1.2.3");
+
+    assert_eq!(
+        conn.migrations_table_version("_sqlx_migrations").await?,
+        Some(1)
+    );
+
+    migrator.set_schema_upgrade(false);
+    let res = migrator.run(&mut conn).await;
+    assert!(matches!(
+        res,
+        Err(MigrateError::SchemaUpgradeRequired(
+            _,
+            1,
+            MIGRATIONS_TABLE_VERSION
+        ))
+    ));
+
+    migrator.set_schema_upgrade(true);
+    migrator.run(&mut conn).await?;
+
+    assert_eq!(
+        conn.migrations_table_version("_sqlx_migrations").await?,
+        Some(MIGRATIONS_TABLE_VERSION)
+    );
+
+    let applied = conn.list_applied_migrations("_sqlx_migrations").await?;
+    assert_eq!(applied.len(), 2);
+
+    for migration in applied {
+        assert_eq!(migration.applied_by, None);
+        assert!(migration.hostname.is_some());
+        assert_eq!(migration.app_version.as_deref(), Some("1.2.3"));
+        assert!(migration.execution_time.is_some());
+    }
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn custom_table_name(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+    conn.execute("DROP TABLE IF EXISTS schema_migrations")
+        .await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/sqlite/migrations_simple")).await?;
+    migrator.set_table_name("main.schema_migrations");
+    migrator.run(&mut conn).await?;
+
+    // running it a 2nd time should still work
+    migrator.run(&mut conn).await?;
+
+    let applied = conn
+        .list_applied_migrations("main.schema_migrations")
+        .await?;
+    assert_eq!(applied.len(), 2);
+
+    let default_exists = conn
+        .list_applied_migrations("_sqlx_migrations")
+        .await
+        .is_ok();
+    assert!(!default_exists);
+
+    conn.execute("DROP TABLE schema_migrations").await?;
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn script(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    let migrator = Migrator::new(Path::new("tests/sqlite/migrations_simple")).await?;
+
+    let script = migrator.script(&mut conn).await?;
+    assert!(script.starts_with("CREATE TABLE IF NOT EXISTS _sqlx_migrations"));
+    assert_eq!(script.matches("INSERT INTO _sqlx_migrations").count(), 2);
+
+    // rendering the script doesn't touch the database
+    assert_eq!(
+        conn.migrations_table_version("_sqlx_migrations").await?,
+        None
+    );
+
+    // running the script by hand is equivalent to running the migrations
+    conn.execute(&*script).await?;
+
+    let res: String = conn
+        .fetch_one("SELECT some_payload FROM migrations_simple_test")
+        .await?
+        .get(0);
+    assert_eq!(res, "110_suffix");
+
+    migrator.run(&mut conn).await?;
+    assert_eq!(migrator.script(&mut conn).await?, "");
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn repeatable(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/sqlite/migrations_repeatable")).await?;
+
+    // repeatable migrations are applied after the versioned migrations
+    let types: Vec<_> = migrator.iter().map(|m| m.migration_type).collect();
+    assert_eq!(types, [MigrationType::Simple, MigrationType::Repeatable]);
+
+    migrator.run(&mut conn).await?;
+
+    let total: i64 = conn
+        .fetch_one("SELECT total FROM migrations_repeatable_totals")
+        .await?
+        .get(0);
+    assert_eq!(total, 300);
+
+    // unchanged, so it isn't applied again
+    migrator.run(&mut conn).await?;
+    assert_eq!(migrator.script(&mut conn).await?, "");
+
+    // changing a repeatable migration applies it again, replacing its record
+    let version = Migration::repeatable_version("payload totals");
+    let changed = Migration::new(
+        version,
+        "payload totals".into(),
+        MigrationType::Repeatable,
+        r#"
+DROP VIEW IF EXISTS migrations_repeatable_totals;
+
+CREATE VIEW migrations_repeatable_totals AS
+SELECT SUM(some_payload) AS total, COUNT(*) AS count
+FROM migrations_repeatable_test;
+        "#
+        .into(),
+        false,
+    );
+    migrator.migrations.to_mut()[1] = changed.clone();
+
+    let script = migrator.script(&mut conn).await?;
+    assert!(script.contains(&format!(
+        "DELETE FROM _sqlx_migrations WHERE version = {version}"
+    )));
+
+    migrator.run(&mut conn).await?;
+
+    let count: i64 = conn
+        .fetch_one("SELECT count FROM migrations_repeatable_totals")
+        .await?
+        .get(0);
+    assert_eq!(count, 2);
+
+    let applied = conn.list_applied_migrations("_sqlx_migrations").await?;
+    assert_eq!(applied.len(), 2);
+    let applied = applied.iter().find(|m| m.version == version).unwrap();
+    assert_eq!(applied.checksum, changed.checksum);
+
+    // renaming a repeatable migration applies it under its new name, and the record left behind
+    // under the old name is not a missing migration
+    let renamed_version = Migration::repeatable_version("payload summary");
+    migrator.migrations.to_mut()[1] = Migration::new(
+        renamed_version,
+        "payload summary".into(),
+        MigrationType::Repeatable,
+        changed.sql()?.into_owned().into(),
+        false,
+    );
+
+    migrator.run(&mut conn).await?;
+
+    let applied = conn.list_applied_migrations("_sqlx_migrations").await?;
+    assert_eq!(applied.len(), 3);
+    assert!(applied.iter().any(|m| m.version == renamed_version));
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn baseline(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    let migrator = Migrator::new(Path::new("tests/sqlite/migrations_simple")).await?;
+
+    // an existing database, with the schema of the first migration
+    conn.execute(
+        r#"
+CREATE TABLE migrations_simple_test (
+    some_id BIGINT NOT NULL PRIMARY KEY,
+    some_payload BIGINT NOT NULL
+);
+
+INSERT INTO migrations_simple_test (some_id, some_payload)
+VALUES (1, 100);
+        "#,
+    )
+    .await?;
+
+    let err = migrator.baseline(&mut conn, 1).await.unwrap_err();
+    assert!(matches!(err, MigrateError::VersionNotPresent(1)), "{err}");
+
+    migrator.baseline(&mut conn, 20220721115250).await?;
+
+    let applied = conn.list_applied_migrations("_sqlx_migrations").await?;
+    assert_eq!(applied.len(), 1);
+    assert_eq!(applied[0].version, 20220721115250);
+    assert_eq!(applied[0].execution_time, None);
+
+    // baselining again changes nothing, and only the newer migration is run
+    migrator.baseline(&mut conn, 20220721115250).await?;
+    migrator.run(&mut conn).await?;
+
+    let res: String = conn
+        .fetch_one("SELECT some_payload FROM migrations_simple_test")
+        .await?
+        .get(0);
+    assert_eq!(res, "110_suffix");
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn skip(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/sqlite/migrations_simple")).await?;
+
+    migrator.set_skip([1]);
+    let err = migrator.run(&mut conn).await.unwrap_err();
+    assert!(matches!(err, MigrateError::VersionNotPresent(1)), "{err}");
+
+    // the second migration is only recorded as skipped
+    migrator.set_skip([20220721115524]);
+    migrator.run(&mut conn).await?;
+    migrator.run(&mut conn).await?;
+
+    let applied = conn.list_applied_migrations("_sqlx_migrations").await?;
+    assert_eq!(applied.len(), 2);
+    assert!(!applied[0].skipped);
+    assert!(applied[1].skipped);
+    assert_eq!(applied[1].execution_time, None);
+
+    let res: i64 = conn
+        .fetch_one("SELECT some_payload FROM migrations_simple_test")
+        .await?
+        .get(0);
+    assert_eq!(res, 100);
+
+    // and applied once it is no longer skipped
+    migrator.set_skip([]);
+    migrator.run(&mut conn).await?;
+
+    let applied = conn.list_applied_migrations("_sqlx_migrations").await?;
+    assert_eq!(applied.len(), 2);
+    assert!(!applied[1].skipped);
+
+    let res: String = conn
+        .fetch_one("SELECT some_payload FROM migrations_simple_test")
+        .await?
+        .get(0);
+    assert_eq!(res, "110_suffix");
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn hooks(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+    conn.execute(
+        "DROP TABLE IF EXISTS migration_audit; \
+         CREATE TABLE migration_audit (event TEXT NOT NULL, version BIGINT NOT NULL);",
+    )
+    .await?;
+
+    let migrator = Migrator::new(Path::new("tests/sqlite/migrations_simple")).await?;
+
+    // the hook after the second migration fails, rolling back everything it did
+    let hooks = MigrationHooks::<Sqlite>::new()
+        .before_migration(|conn, migration| {
+            Box::pin(async move {
+                sqlx::query("INSERT INTO migration_audit VALUES ('before', ?)")
+                    .bind(migration.version)
+                    .execute(&mut *conn)
+                    .await?;
+                Ok(())
+            })
+        })
+        .after_migration(|conn, migration| {
+            Box::pin(async move {
+                sqlx::query("INSERT INTO migration_audit VALUES ('after', ?)")
+                    .bind(migration.version)
+                    .execute(&mut *conn)
+                    .await?;
+
+                if migration.version == 20220721115524 {
+                    return Err(sqlx::Error::Protocol("cache unavailable".into()));
+                }
+                Ok(())
+            })
+        })
+        .on_failure(|conn, migration, _error| {
+            Box::pin(async move {
+                sqlx::query("INSERT INTO migration_audit VALUES ('failed', ?)")
+                    .bind(migration.version)
+                    .execute(&mut *conn)
+                    .await
+                    .unwrap();
+            })
+        });
+
+    let err = migrator.with_hooks(hooks).run(&mut conn).await.unwrap_err();
+    assert!(
+        matches!(err, MigrateError::Hook(_, 20220721115524)),
+        "{err}"
+    );
+
+    let audit: Vec<(String, i64)> = sqlx::query_as("SELECT event, version FROM migration_audit")
+        .fetch_all(&mut *conn)
+        .await?;
+    assert_eq!(
+        audit,
+        [
+            ("before".to_owned(), 20220721115250),
+            ("after".to_owned(), 20220721115250),
+            ("failed".to_owned(), 20220721115524),
+        ]
+    );
+
+    let applied = conn.list_applied_migrations("_sqlx_migrations").await?;
+    assert_eq!(applied.len(), 1);
+    let res: i64 = conn
+        .fetch_one("SELECT some_payload FROM migrations_simple_test")
+        .await?
+        .get(0);
+    assert_eq!(res, 100);
+
+    Ok(())
+}
+
 /// Ensure that we have a clean initial state.
 async fn clean_up(conn: &mut SqliteConnection) -> anyhow::Result<()> {
     conn.execute("DROP TABLE migrations_simple_test").await.ok();
     conn.execute("DROP TABLE migrations_reversible_test")
         .await
         .ok();
+    conn.execute("DROP VIEW migrations_repeatable_totals")
+        .await
+        .ok();
+    conn.execute("DROP TABLE migrations_repeatable_test")
+        .await
+        .ok();
     conn.execute("DROP TABLE _sqlx_migrations").await.ok();
 
     Ok(())
@@ -0,0 +1,7 @@
+CREATE TABLE migrations_repeatable_test (
+    some_id BIGINT NOT NULL PRIMARY KEY,
+    some_payload BIGINT NOT NULL
+);
+
+INSERT INTO migrations_repeatable_test (some_id, some_payload)
+VALUES (1, 100), (2, 200);
@@ -0,0 +1,6 @@
+-- Re-applied whenever this file changes, after the versioned migrations.
+DROP VIEW IF EXISTS migrations_repeatable_totals;
+
+CREATE VIEW migrations_repeatable_totals AS
+SELECT SUM(some_payload) AS total
+FROM migrations_repeatable_test;
@@ -0,0 +1,66 @@
+use sqlx::pool::ConnectionManager;
+use sqlx::sqlite::Sqlite;
+use sqlx::Connection;
+
+fn manager() -> anyhow::Result<ConnectionManager<Sqlite>> {
+    Ok(ConnectionManager::from_url("sqlite::memory:")?)
+}
+
+#[sqlx_macros::test]
+async fn it_manages_connections_for_deadpool() -> anyhow::Result<()> {
+    let pool = deadpool::managed::Pool::<ConnectionManager<Sqlite>>::builder(manager()?)
+        .max_size(1)
+        .build()?;
+
+    {
+        let mut conn = pool.get().await?;
+
+        sqlx::query("CREATE TABLE pets (name TEXT NOT NULL)")
+            .execute(&mut *conn)
+            .await?;
+
+        // Dropped without committing while the connection goes back to the pool.
+        let mut tx = conn.begin().await?;
+
+        sqlx::query("INSERT INTO pets (name) VALUES ('tux')")
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    // The in-memory database only exists on the connection that was recycled.
+    let mut conn = pool.get().await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pets")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(count, 0);
+
+    assert_eq!(pool.status().size, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_manages_connections_for_bb8() -> anyhow::Result<()> {
+    let pool = bb8::Pool::builder()
+        .max_size(1)
+        .test_on_check_out(true)
+        .build(manager()?)
+        .await?;
+
+    let mut conn = pool.get().await?;
+
+    let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut *conn).await?;
+    assert_eq!(one, 1);
+
+    drop(conn);
+
+    let mut conn = pool.get().await?;
+
+    let two: i32 = sqlx::query_scalar("SELECT 2").fetch_one(&mut *conn).await?;
+    assert_eq!(two, 2);
+
+    assert_eq!(pool.state().connections, 1);
+
+    Ok(())
+}
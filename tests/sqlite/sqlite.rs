@@ -1,10 +1,13 @@
 use futures::TryStreamExt;
 use rand::{Rng, SeedableRng};
 use rand_xoshiro::Xoshiro256PlusPlus;
-use sqlx::sqlite::{SqliteConnectOptions, SqliteOperation, SqlitePoolOptions};
+use sqlx::sqlite::{
+    Fts5Table, MatchQuery, SqliteConnectOptions, SqliteFunction, SqliteOperation, SqlitePoolOptions,
+};
+use sqlx::types::{Checked, Saturating};
 use sqlx::{
-    query, sqlite::Sqlite, sqlite::SqliteRow, Column, ConnectOptions, Connection, Executor, Row,
-    SqliteConnection, SqlitePool, Statement, TypeInfo,
+    query, sqlite::Sqlite, sqlite::SqliteRow, CachedStatements, Column, ConnectOptions, Connection,
+    Executor, QueryAugmenter, Row, SqliteConnection, SqlitePool, Statement, TypeInfo,
 };
 use sqlx_sqlite::LockedSqliteHandle;
 use sqlx_test::new;
@@ -16,6 +19,43 @@ async fn it_connects() -> anyhow::Result<()> {
     Ok(new::<Sqlite>().await?.ping().await?)
 }
 
+#[sqlx_macros::test]
+async fn it_describes_out_of_range_integers() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let row = conn
+        .fetch_one("SELECT 3000000000 AS big, -1 AS negative")
+        .await?;
+
+    let err = row.try_get::<i32, _>(0).unwrap_err().to_string();
+    assert_eq!(
+        err,
+        "error occurred while decoding column 0 (\"big\"): \
+         value 3000000000 is out of range for `i32`; try decoding as `i64`"
+    );
+
+    let err = row.try_get::<u16, _>("negative").unwrap_err().to_string();
+    assert_eq!(
+        err,
+        "error occurred while decoding column \"negative\": \
+         value -1 is out of range for `u16`; try decoding as `i8`"
+    );
+
+    let Saturating(big): Saturating<i32> = row.try_get("big")?;
+    assert_eq!(big, i32::MAX);
+
+    let Saturating(negative): Saturating<u16> = row.try_get("negative")?;
+    assert_eq!(negative, 0);
+
+    let Checked(big): Checked<i32> = row.try_get("big")?;
+    assert_eq!(big, None);
+
+    let Checked(negative): Checked<i8> = row.try_get("negative")?;
+    assert_eq!(negative, Some(-1));
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_fetches_and_inflates_row() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -97,6 +137,29 @@ async fn it_maths() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_gets_defaults_for_null_values() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let row = conn.fetch_one("SELECT NULL AS a, 5 AS b").await?;
+
+    assert_eq!(row.get_or::<i32, _>("a", 10), 10);
+    assert_eq!(row.get_or::<i32, _>("b", 10), 5);
+    assert_eq!(row.try_get_or_default::<i32, _>("a")?, 0);
+    assert_eq!(row.try_get_or_default::<String, _>(0)?, "");
+    assert_eq!(row.try_get_optional::<i32, _>("a")?, None);
+    assert_eq!(row.try_get_optional::<i32, _>("b")?, Some(5));
+    assert_eq!(row.try_get_optional::<i32, _>("c")?, None);
+    assert_eq!(row.try_get_optional::<i32, _>(2)?, None);
+
+    assert!(matches!(
+        row.try_get_or_default::<i32, _>("c"),
+        Err(sqlx::Error::ColumnNotFound(_))
+    ));
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_bind_multiple_statements_multiple_values() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -191,6 +254,82 @@ async fn it_fetches_in_loop() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_fetches_columns() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let (ids, texts, owners): (Vec<i64>, Vec<String>, Vec<Option<i64>>) =
+        sqlx::query("SELECT id, text, owner_id FROM tweet ORDER BY id")
+            .fetch_columns(&mut conn)
+            .await?;
+
+    let rows: Vec<(i64, String, Option<i64>)> =
+        sqlx::query_as("SELECT id, text, owner_id FROM tweet ORDER BY id")
+            .fetch_all(&mut conn)
+            .await?;
+
+    assert!(!rows.is_empty());
+    assert_eq!(ids, rows.iter().map(|row| row.0).collect::<Vec<_>>());
+    assert_eq!(
+        texts,
+        rows.iter().map(|row| row.1.clone()).collect::<Vec<_>>()
+    );
+    assert_eq!(owners, rows.iter().map(|row| row.2).collect::<Vec<_>>());
+
+    // a value that fails to decode errors out the whole fetch
+    let res = sqlx::query("SELECT 1, 'not a number'")
+        .fetch_columns::<_, (Vec<i64>, Vec<i64>)>(&mut conn)
+        .await;
+
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[sqlx_macros::test]
+async fn it_fetches_json_arrays() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let mut out = Vec::new();
+
+    let count =
+        sqlx::query("SELECT 1 AS id, 'a' AS name, 0.5 AS score, x'0102' AS data, NULL AS n")
+            .fetch_json_array(&mut conn, &mut out)
+            .await?;
+
+    assert_eq!(count, 1);
+    assert_eq!(
+        String::from_utf8(out)?,
+        r#"[{"id":1,"name":"a","score":0.5,"data":"AQI=","n":null}]"#
+    );
+
+    let mut out = Vec::new();
+
+    let count = sqlx::query("SELECT id FROM tweet ORDER BY id")
+        .fetch_json_lines(&mut conn, &mut out)
+        .await?;
+
+    let ids: Vec<i64> = sqlx::query_scalar("SELECT id FROM tweet ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+
+    let lines = String::from_utf8(out)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<Vec<serde_json::Value>, _>>()?;
+
+    assert_eq!(count, u64::try_from(ids.len())?);
+    assert_eq!(
+        lines,
+        ids.iter()
+            .map(|id| serde_json::json!({ "id": id }))
+            .collect::<Vec<_>>()
+    );
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_executes_with_pool() -> anyhow::Result<()> {
     let pool: SqlitePool = SqlitePoolOptions::new()
@@ -441,6 +580,132 @@ CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL COLLATE
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_registers_collations_on_pooled_connections() -> anyhow::Result<()> {
+    let options = SqliteConnectOptions::new()
+        .in_memory(true)
+        .collation("test_collation", |l, r| l.cmp(r).reverse());
+
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(2)
+        .connect_with(options)
+        .await?;
+
+    // every connection of the pool has its own in-memory database and the collation
+    let mut conns = vec![pool.acquire().await?, pool.acquire().await?];
+
+    for conn in &mut conns {
+        let names: Vec<String> = sqlx::query_scalar(
+            "SELECT column1 FROM (VALUES ('a'), ('c'), ('b')) ORDER BY column1 COLLATE test_collation",
+        )
+        .fetch_all(&mut **conn)
+        .await?;
+
+        assert_eq!(names, ["c", "b", "a"]);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_supports_functions() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.create_function(
+        SqliteFunction::scalar("repeat_text", 2, |args| {
+            let text: String = args.try_get(0)?;
+            let times: i64 = args.try_get(1)?;
+
+            if times < 0 {
+                return Err("negative count".into());
+            }
+
+            Ok(text.repeat(times as usize))
+        })
+        .deterministic(true),
+    )
+    .await?;
+
+    conn.create_function(SqliteFunction::aggregate(
+        "product",
+        1,
+        || 1i64,
+        |product, args| {
+            *product *= args.try_get::<i64>(0)?;
+            Ok(())
+        },
+        Ok,
+    ))
+    .await?;
+
+    let text: String = sqlx::query_scalar("SELECT repeat_text(?, 3)")
+        .bind("ab")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(text, "ababab");
+
+    let err = sqlx::query_scalar::<_, String>("SELECT repeat_text('ab', -1)")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("negative count"), "{err}");
+
+    let product: i64 = sqlx::query_scalar("SELECT product(column1) FROM (VALUES (2), (3), (7))")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(product, 42);
+
+    // a group without rows is finalized with the initial state
+    let product: i64 =
+        sqlx::query_scalar("SELECT product(column1) FROM (VALUES (2)) WHERE column1 > 2")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(product, 1);
+
+    // only deterministic functions can be used in indexes
+    conn.execute(
+        "CREATE TEMPORARY TABLE words (word TEXT NOT NULL); \
+         CREATE INDEX words_doubled ON words (repeat_text(word, 2))",
+    )
+    .await?;
+
+    conn.create_function(SqliteFunction::scalar("random_text", 0, |_| Ok("a")))
+        .await?;
+
+    assert!(conn
+        .execute("CREATE INDEX words_random ON words (random_text())")
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_registers_functions_on_pooled_connections() -> anyhow::Result<()> {
+    let options = SqliteConnectOptions::new()
+        .in_memory(true)
+        .function(SqliteFunction::scalar("double", 1, |args| {
+            Ok(args.try_get::<i64>(0)? * 2)
+        }));
+
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(2)
+        .connect_with(options)
+        .await?;
+
+    let mut conns = vec![pool.acquire().await?, pool.acquire().await?];
+
+    for conn in &mut conns {
+        let value: i64 = sqlx::query_scalar("SELECT double(21)")
+            .fetch_one(&mut **conn)
+            .await?;
+
+        assert_eq!(value, 42);
+    }
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_caches_statements() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -492,6 +757,59 @@ async fn it_caches_statements() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_resizes_statement_cache() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    for i in 0..3 {
+        sqlx::query(&format!("SELECT {i}"))
+            .fetch_one(&mut conn)
+            .await?;
+    }
+
+    // the size is updated once the worker finishes the last statement
+    conn.ping().await?;
+
+    assert_eq!(
+        conn.cached_statements(),
+        CachedStatements {
+            size: 3,
+            capacity: 100
+        }
+    );
+
+    conn.set_statement_cache_capacity(1).await?;
+
+    assert_eq!(
+        conn.cached_statements(),
+        CachedStatements {
+            size: 1,
+            capacity: 1
+        }
+    );
+
+    // pools override the capacity of their connections
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .statement_cache_capacity(0)
+        .connect("sqlite::memory:")
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    sqlx::query("SELECT 1").fetch_one(&mut *conn).await?;
+
+    assert_eq!(
+        conn.cached_statements(),
+        CachedStatements {
+            size: 0,
+            capacity: 0
+        }
+    );
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -1348,6 +1666,216 @@ async fn it_can_use_transaction_options() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_journals_statements_in_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+    let mut tx = conn.begin().await?;
+    let journal = tx.journal();
+
+    tx.execute("CREATE TEMPORARY TABLE journaled (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    sqlx::query("INSERT INTO journaled (id) VALUES (?)")
+        .bind(1_i32)
+        .execute(&mut *tx)
+        .await?;
+
+    drop(tx);
+    conn.execute("SELECT 1").await?;
+
+    assert!(journal.is_rolled_back());
+
+    let entries = journal.entries();
+    let sql: Vec<&str> = entries.iter().map(|entry| entry.sql()).collect();
+
+    assert_eq!(
+        sql,
+        [
+            "CREATE TEMPORARY TABLE journaled (id INTEGER PRIMARY KEY)",
+            "INSERT INTO journaled (id) VALUES (?)",
+        ]
+    );
+    assert_eq!(entries[1].rows_affected(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_estimates_row_counts() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE estimated (id INTEGER PRIMARY KEY, even BOOLEAN);
+         CREATE INDEX temp.estimated_even ON estimated (even);
+         WITH RECURSIVE ids(id) AS (SELECT 1 UNION ALL SELECT id + 1 FROM ids WHERE id < 100)
+         INSERT INTO estimated SELECT id, id % 2 = 0 FROM ids;",
+    )
+    .await?;
+
+    // without statistics, tables are counted
+    assert_eq!(
+        sqlx::estimate_count(&mut conn, "temp.estimated").await?,
+        100
+    );
+
+    conn.execute("ANALYZE temp").await?;
+    conn.execute("DELETE FROM estimated WHERE id > 90").await?;
+
+    // the statistics are not updated until the next `ANALYZE`
+    assert_eq!(
+        sqlx::estimate_count(&mut conn, "temp.estimated").await?,
+        100
+    );
+
+    assert_eq!(
+        sqlx::estimate_count(&mut conn, "SELECT * FROM estimated WHERE even").await?,
+        45
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_searches_with_fts5() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+    let mut tx = conn.begin().await?;
+
+    tx.execute(
+        "CREATE TABLE article (article_id INTEGER PRIMARY KEY, title TEXT, body TEXT);
+         INSERT INTO article (title, body) VALUES
+             ('Searching', 'full-text search in sqlite'),
+             ('Indexing', 'an index makes lookups fast');",
+    )
+    .await?;
+
+    let fts = Fts5Table::new("article_fts", "article")
+        .columns(["title", "body"])
+        .content_rowid("article_id")
+        .tokenize("porter");
+
+    // rows inserted before the index is created are indexed too
+    fts.create(&mut tx).await?;
+
+    tx.execute(
+        "INSERT INTO article (title, body) VALUES ('Searches', 'searching and more searching');
+         UPDATE article SET body = 'nothing to see' WHERE article_id = 2;",
+    )
+    .await?;
+
+    let matches = MatchQuery::terms("article_fts", ["search"])
+        .fetch_all(&mut tx)
+        .await?;
+    let mut rowids: Vec<i64> = matches.iter().map(|m| m.rowid).collect();
+    rowids.sort();
+    assert_eq!(rowids, [1, 3]);
+    assert!(matches[0].rank <= matches[1].rank);
+    assert!(matches[0].rank.score() > 0.0);
+
+    // the updated text is no longer indexed
+    let matches = MatchQuery::new("article_fts", "lookups")
+        .fetch_all(&mut tx)
+        .await?;
+    assert!(matches.is_empty());
+
+    tx.execute("DELETE FROM article WHERE article_id = 1")
+        .await?;
+
+    let matches = MatchQuery::terms("article_fts", ["sqlite"])
+        .weights(&[10.0, 1.0])
+        .snippet(1, "[", "]", "...", 4)
+        .limit(5)
+        .fetch_all(&mut tx)
+        .await?;
+    assert!(matches.is_empty());
+
+    let matches = MatchQuery::new("article_fts", "body:searching")
+        .snippet(1, "[", "]", "...", 4)
+        .fetch_all(&mut tx)
+        .await?;
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].rowid, 3);
+    assert_eq!(
+        matches[0].snippet.as_deref(),
+        Some("[searching] and more [searching]")
+    );
+
+    tx.execute(&*fts.down_sql()).await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_scripts_with_binds() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+    let mut tx = conn.begin().await?;
+
+    let result = tx
+        .execute_script(
+            "CREATE TEMPORARY TABLE script_test (id INTEGER, name TEXT);
+             INSERT INTO script_test VALUES ($1, $2), ($1 + 1, 'it''s; $2');
+             UPDATE script_test SET name = name || $3 WHERE id = $1;",
+            &[&10_i32, &"ten".to_owned(), &"!".to_owned()],
+        )
+        .await?;
+
+    assert_eq!(result.rows_affected(), 3);
+
+    let rows: Vec<(i32, String)> = sqlx::query_as("SELECT id, name FROM script_test ORDER BY id")
+        .fetch_all(&mut *tx)
+        .await?;
+
+    assert_eq!(rows, [(10, "ten!".to_owned()), (11, "it's; $2".to_owned())]);
+
+    let err = tx
+        .execute_script("SELECT 1; SELECT $2", &[&1_i32])
+        .await
+        .unwrap_err();
+
+    assert!(
+        err.to_string()
+            .contains("statement 2 of the script refers to $2, but 1 binds were given"),
+        "{err}"
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_augments_queries_on_soft_deleted_tables() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE augment_users (id INTEGER, deleted_at TEXT);
+         CREATE TEMPORARY TABLE augment_posts (user_id INTEGER, title TEXT);
+         INSERT INTO augment_users VALUES (1, NULL), (2, '2024-01-01');
+         INSERT INTO augment_posts VALUES (1, 'kept'), (2, 'hidden');",
+    )
+    .await?;
+
+    let augmenter = QueryAugmenter::new().soft_delete("augment_users", "deleted_at");
+
+    let sql = augmenter.augment(
+        "SELECT augment_posts.title FROM augment_posts \
+         JOIN augment_users ON augment_users.id = augment_posts.user_id \
+         WHERE augment_users.id > ?",
+    );
+    let titles: Vec<String> = sqlx::query_scalar(&sql)
+        .bind(0_i32)
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(titles, ["kept"]);
+
+    let sql = augmenter.augment("SELECT count(*) FROM augment_users /* sqlx:unaugmented */");
+    let count: i32 = sqlx::query_scalar(&sql).fetch_one(&mut conn).await?;
+    assert_eq!(count, 2);
+
+    // soft-deleted rows can still be modified
+    let sql = augmenter.augment("DELETE FROM augment_users WHERE deleted_at IS NOT NULL");
+    assert_eq!(conn.execute(&*sql).await?.rows_affected(), 1);
+
+    Ok(())
+}
+
 fn transaction_state(handle: &mut LockedSqliteHandle) -> SqliteTransactionState {
     use libsqlite3_sys::{sqlite3_txn_state, SQLITE_TXN_NONE, SQLITE_TXN_READ, SQLITE_TXN_WRITE};
 
@@ -1367,3 +1895,23 @@ enum SqliteTransactionState {
     Read,
     Write,
 }
+
+#[sqlx_macros::test]
+async fn it_gets_raw_values() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let row = sqlx::query("SELECT 'abc', x'0102', 42, NULL")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.raw_value(0)?, Some(&b"abc"[..]));
+    assert_eq!(row.raw_value(1)?, Some(&[1_u8, 2][..]));
+    assert_eq!(row.raw_value(2)?, Some(&b"42"[..]));
+    assert_eq!(row.raw_value(3)?, None);
+    assert!(row.raw_value(4).is_err());
+
+    // the value is still decoded as it was stored
+    assert_eq!(row.try_get::<i64, _>(2)?, 42);
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 // The no-arg variant is covered by other tests already.
 
+use sqlx::fixtures::Fixtures;
 use sqlx::{Row, SqlitePool};
 
 const MIGRATOR: sqlx::migrate::Migrator = sqlx::migrate!("tests/sqlite/migrations");
@@ -98,6 +99,108 @@ async fn it_gets_comments(pool: SqlitePool) -> sqlx::Result<()> {
     Ok(())
 }
 
+#[sqlx::test(
+    migrations = "tests/sqlite/migrations",
+    fixtures("users", "templated_posts")
+)]
+async fn it_renders_fixture_templates(pool: SqlitePool) -> sqlx::Result<()> {
+    let posts: Vec<(String, bool)> = sqlx::query_as(
+        "SELECT content, created_at < datetime('now', '-1 day') FROM post ORDER BY post_id",
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    let release = posts[0].0.split(' ').nth(1).unwrap();
+
+    assert_eq!(release.len(), 36);
+    assert_eq!(posts[1].0, format!("release {release} broke everything"));
+    assert!(posts[0].1);
+    assert!(!posts[1].1);
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = "tests/sqlite/migrations")]
+async fn it_applies_fixtures_at_runtime(pool: SqlitePool) -> sqlx::Result<()> {
+    let fixtures = Fixtures::new("tests/sqlite/fixtures");
+
+    // nothing is applied if any fixture is missing
+    assert!(fixtures
+        .apply(&pool, &["users", "no_such_fixture"])
+        .await
+        .is_err());
+
+    fixtures
+        .apply(&pool, &["users", "posts", "templated_posts.sql"])
+        .await?;
+
+    let post_ids: Vec<i64> = sqlx::query_scalar("SELECT post_id FROM post ORDER BY post_id")
+        .fetch_all(&pool)
+        .await?;
+
+    assert_eq!(post_ids, [1, 2, 3, 4]);
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = "tests/sqlite/migrations")]
+async fn it_applies_fixture_dependencies_at_runtime(pool: SqlitePool) -> sqlx::Result<()> {
+    // `comments` depends on `users` and `posts`
+    Fixtures::new("tests/sqlite/fixtures")
+        .apply(&pool, &["comments", "users"])
+        .await?;
+
+    let counts: (i64, i64, i64) = sqlx::query_as(
+        "SELECT (SELECT count(*) FROM user), (SELECT count(*) FROM post), (SELECT count(*) FROM comment)",
+    )
+    .fetch_one(&pool)
+    .await?;
+
+    assert_eq!(counts, (2, 2, 3));
+
+    Ok(())
+}
+
+// Generated data, which a SQL fixture can't express.
+async fn numbered_posts(pool: SqlitePool) -> sqlx::Result<()> {
+    for n in 1..=3 {
+        sqlx::query("INSERT INTO post (user_id, content) VALUES (1, ?)")
+            .bind(format!("post #{n} of {}", "0".repeat(n)))
+            .execute(&pool)
+            .await?;
+    }
+
+    Ok(())
+}
+
+// Applied as `users`, `posts`, `comments` and then `numbered_posts`.
+#[sqlx::test(
+    migrations = "tests/sqlite/migrations",
+    fixtures("comments", "posts", "users", numbered_posts(depends("users")))
+)]
+async fn it_orders_fixtures_by_dependencies(pool: SqlitePool) -> sqlx::Result<()> {
+    let posts: Vec<(i64, String)> =
+        sqlx::query_as("SELECT post_id, content FROM post ORDER BY post_id")
+            .fetch_all(&pool)
+            .await?;
+
+    assert_eq!(posts.len(), 5);
+    assert_eq!(posts[0].1, "This new computer is lightning-fast!");
+    assert_eq!(posts[4], (5, "post #3 of 000".to_owned()));
+
+    let comments: i64 = sqlx::query_scalar("SELECT count(*) FROM comment")
+        .fetch_one(&pool)
+        .await?;
+
+    assert_eq!(comments, 3);
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = "tests/sqlite/migrations", fixtures("comments"))]
+#[should_panic(expected = "depends on \"users\", which is not applied")]
+async fn it_requires_fixture_dependencies(_pool: SqlitePool) {}
+
 #[sqlx::test(
     migrations = "tests/sqlite/migrations",
     fixtures(path = "./fixtures", scripts("users", "posts"))
@@ -105,3 +208,51 @@ async fn it_gets_comments(pool: SqlitePool) -> sqlx::Result<()> {
 async fn this_should_compile(_pool: SqlitePool) -> sqlx::Result<()> {
     Ok(())
 }
+
+#[sqlx::test(migrations = "tests/sqlite/migrations")]
+async fn it_asserts_index_usage(pool: SqlitePool) -> sqlx::Result<()> {
+    sqlx::assert_index_used!(
+        &pool,
+        "SELECT post_id FROM post ORDER BY created_at DESC LIMIT 10",
+        "post_created_at"
+    );
+
+    let plan = sqlx::testing::explain(&pool, "SELECT content FROM post").await?;
+    assert!(!plan.uses_index("post_created_at"), "{plan}");
+
+    Ok(())
+}
+
+#[sqlx::test(
+    migrations = "tests/sqlite/migrations",
+    fixtures("users", "posts", "comments")
+)]
+async fn it_cleans_tables(pool: SqlitePool) -> sqlx::Result<()> {
+    // a referencing table which is not cleaned
+    let res = sqlx::testing::clean_tables(&pool, &["user", "post"]).await;
+    assert!(res.is_err());
+
+    sqlx::query("CREATE TABLE tag (tag_id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL)")
+        .execute(&pool)
+        .await?;
+    sqlx::query("INSERT INTO tag (name) VALUES ('a'), ('b')")
+        .execute(&pool)
+        .await?;
+
+    // listed in the wrong order for `DELETE`
+    sqlx::testing::clean_tables(&pool, &["user", "post", "comment", "tag"]).await?;
+
+    let count: i64 = sqlx::query_scalar(
+        "SELECT (SELECT count(*) FROM user) + (SELECT count(*) FROM post) + (SELECT count(*) FROM comment) + (SELECT count(*) FROM tag)",
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(count, 0);
+
+    let tag_id: i64 = sqlx::query_scalar("INSERT INTO tag (name) VALUES ('c') RETURNING tag_id")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(tag_id, 1);
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use http::{Request, Response, StatusCode};
+use sqlx::sqlite::{Sqlite, SqlitePool, SqlitePoolOptions};
+use sqlx::tower::{RequestTransaction, TransactionLayer};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Records the request path in `paths` and responds with the status code given as the path.
+#[derive(Clone)]
+struct Handler;
+
+impl Service<Request<()>> for Handler {
+    type Response = Response<()>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Response<()>, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<()>) -> Self::Future {
+        Box::pin(async move {
+            let tx = req
+                .extensions()
+                .get::<RequestTransaction<Sqlite>>()
+                .cloned()
+                .expect("transaction in extensions");
+
+            let path = req.uri().path().trim_start_matches('/').to_owned();
+
+            let mut tx = tx.lock().await.expect("transaction still open");
+
+            sqlx::query("INSERT INTO paths (path) VALUES (?)")
+                .bind(&path)
+                .execute(&mut **tx)
+                .await
+                .unwrap();
+
+            let mut response = Response::new(());
+            *response.status_mut() = path
+                .parse::<u16>()
+                .map_or(StatusCode::OK, |code| StatusCode::from_u16(code).unwrap());
+
+            Ok(response)
+        })
+    }
+}
+
+async fn call<S>(service: &mut S, path: &str) -> StatusCode
+where
+    S: Service<Request<()>, Response = Response<()>, Error = Infallible>,
+{
+    std::future::poll_fn(|cx| service.poll_ready(cx))
+        .await
+        .unwrap();
+
+    let req = Request::builder().uri(path).body(()).unwrap();
+
+    service.call(req).await.unwrap().status()
+}
+
+async fn paths(pool: &SqlitePool) -> anyhow::Result<Vec<String>> {
+    Ok(sqlx::query_scalar("SELECT path FROM paths ORDER BY path")
+        .fetch_all(pool)
+        .await?)
+}
+
+#[sqlx_macros::test]
+async fn it_commits_or_rolls_back_per_response_status() -> anyhow::Result<()> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect("sqlite::memory:")
+        .await?;
+
+    sqlx::query("CREATE TABLE paths (path TEXT NOT NULL)")
+        .execute(&pool)
+        .await?;
+
+    let mut service = TransactionLayer::new(pool.clone()).layer(Handler);
+
+    assert_eq!(call(&mut service, "/200").await, StatusCode::OK);
+    assert_eq!(call(&mut service, "/303").await, StatusCode::SEE_OTHER);
+    assert_eq!(call(&mut service, "/404").await, StatusCode::NOT_FOUND);
+    assert_eq!(
+        call(&mut service, "/500").await,
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+
+    assert_eq!(paths(&pool).await?, ["200", "303"]);
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 extern crate time_ as time;
 
-use sqlx::sqlite::{Sqlite, SqliteRow};
+use sqlx::sqlite::{Sqlite, SqliteConnectOptions, SqliteConnection, SqliteRow};
+use sqlx::Connection;
 use sqlx_core::executor::Executor;
 use sqlx_core::row::Row;
 use sqlx_core::types::Text;
@@ -208,6 +209,160 @@ test_type!(uuid_simple<sqlx::types::uuid::fmt::Simple>(Sqlite,
         == sqlx::types::Uuid::parse_str("00000000000000000000000000000000").unwrap().simple()
 ));
 
+test_type!(vector<sqlx::types::Vector>(Sqlite,
+    "X'0000803F000020C0'" == sqlx::types::Vector(vec![1.0, -2.5]),
+    "X''" == sqlx::types::Vector(vec![])
+));
+
+test_type!(std_duration<std::time::Duration>(Sqlite,
+    "1.5" == std::time::Duration::from_millis(1500),
+    "0.0" == std::time::Duration::ZERO,
+));
+
+#[sqlx_macros::test]
+async fn test_duration_seconds() -> anyhow::Result<()> {
+    use std::time::Duration;
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let ttl: Duration = sqlx::query_scalar("SELECT 3600")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(ttl, Duration::from_secs(3600));
+
+    let expires_in: f64 = sqlx::query_scalar("SELECT (unixepoch() + ?) - unixepoch()")
+        .bind(Duration::from_millis(90_500))
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(expires_in, 90.5);
+
+    let res = sqlx::query_scalar::<_, Duration>("SELECT -1")
+        .fetch_one(&mut conn)
+        .await;
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_float_nan() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // SQLite binds `NaN` as `NULL`
+    let value: Option<f64> = sqlx::query_scalar("SELECT ?")
+        .bind(f64::NAN)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, None);
+
+    let value: f64 = sqlx::query_scalar("SELECT ?")
+        .bind(f64::NEG_INFINITY)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, f64::NEG_INFINITY);
+
+    let options: SqliteConnectOptions = std::env::var("DATABASE_URL")?.parse()?;
+    let mut conn = SqliteConnection::connect_with(&options.reject_nan(true)).await?;
+
+    let err = sqlx::query_scalar::<_, Option<f64>>("SELECT ?")
+        .bind(f64::NAN)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::Encode(_)), "{err:?}");
+
+    let value: f64 = sqlx::query_scalar("SELECT ?")
+        .bind(f64::INFINITY)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, f64::INFINITY);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_vector_from_text() -> anyhow::Result<()> {
+    use sqlx::types::{Vector, VectorDistance};
+    use sqlx::QueryBuilder;
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let vector: Vector = sqlx::query_scalar("SELECT '[1, -2.5]'")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(vector, Vector(vec![1.0, -2.5]));
+
+    let mut query = QueryBuilder::<Sqlite>::new("SELECT id FROM items ORDER BY ");
+    query.push_vector_distance("embedding", VectorDistance::Cosine, vec![1.0, 0.0])?;
+
+    assert_eq!(
+        query.sql(),
+        "SELECT id FROM items ORDER BY vec_distance_cosine(embedding, ?)"
+    );
+
+    let result = query.push_vector_distance("embedding", VectorDistance::InnerProduct, vec![1.0]);
+    assert!(matches!(result, Err(sqlx::Error::InvalidArgument(_))));
+    assert_eq!(
+        query.sql(),
+        "SELECT id FROM items ORDER BY vec_distance_cosine(embedding, ?)"
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "uuid")]
+#[sqlx_macros::test]
+async fn test_uuid_from_text() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let uuid: sqlx::types::Uuid =
+        sqlx::query_scalar("SELECT 'b731678f-636f-4135-bc6f-19440c13bd19'")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(
+        uuid,
+        sqlx::types::Uuid::parse_str("b731678f-636f-4135-bc6f-19440c13bd19")?
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_ci_string_nocase() -> anyhow::Result<()> {
+    use sqlx::types::CiString;
+
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE ci_users (email TEXT COLLATE NOCASE UNIQUE)")
+        .await?;
+
+    sqlx::query("INSERT INTO ci_users (email) VALUES (?)")
+        .bind(CiString::from("Alice@Example.com"))
+        .execute(&mut conn)
+        .await?;
+
+    let email = CiString::from("alice@EXAMPLE.com");
+
+    let found: CiString = sqlx::query_scalar("SELECT email FROM ci_users WHERE email = ?")
+        .bind(&email)
+        .fetch_one(&mut conn)
+        .await?;
+
+    // the stored value keeps its case, but the database and Rust agree it's the same email
+    assert_eq!(&*found, "Alice@Example.com");
+    assert_eq!(found, email);
+
+    assert!(sqlx::query("INSERT INTO ci_users (email) VALUES (?)")
+        .bind(email)
+        .execute(&mut conn)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_text_adapter() -> anyhow::Result<()> {
     #[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
@@ -250,3 +405,47 @@ CREATE TEMPORARY TABLE user_login (
 
     Ok(())
 }
+
+#[cfg(feature = "proptest")]
+#[sqlx_macros::test]
+async fn it_roundtrips_generated_values() -> anyhow::Result<()> {
+    use sqlx::testing::roundtrip;
+
+    let mut conn = new::<Sqlite>().await?;
+
+    roundtrip::<bool, _>(&mut conn).await?;
+    roundtrip::<i8, _>(&mut conn).await?;
+    roundtrip::<i16, _>(&mut conn).await?;
+    roundtrip::<i32, _>(&mut conn).await?;
+    roundtrip::<i64, _>(&mut conn).await?;
+    roundtrip::<u8, _>(&mut conn).await?;
+    roundtrip::<u16, _>(&mut conn).await?;
+    roundtrip::<u32, _>(&mut conn).await?;
+    roundtrip::<f32, _>(&mut conn).await?;
+    roundtrip::<f64, _>(&mut conn).await?;
+    roundtrip::<String, _>(&mut conn).await?;
+    roundtrip::<Vec<u8>, _>(&mut conn).await?;
+
+    #[cfg(feature = "chrono")]
+    {
+        use sqlx::types::chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+        roundtrip::<NaiveDate, _>(&mut conn).await?;
+        roundtrip::<NaiveTime, _>(&mut conn).await?;
+        roundtrip::<NaiveDateTime, _>(&mut conn).await?;
+    }
+
+    #[cfg(feature = "time")]
+    {
+        use sqlx::types::time::{Date, PrimitiveDateTime, Time};
+
+        roundtrip::<Date, _>(&mut conn).await?;
+        roundtrip::<Time, _>(&mut conn).await?;
+        roundtrip::<PrimitiveDateTime, _>(&mut conn).await?;
+    }
+
+    #[cfg(feature = "uuid")]
+    roundtrip::<sqlx::types::Uuid, _>(&mut conn).await?;
+
+    Ok(())
+}